@@ -0,0 +1,25 @@
+-- Payees as first-class entities
+
+CREATE TABLE IF NOT EXISTS payees (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL COLLATE NOCASE UNIQUE,
+    default_category_id TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE TABLE IF NOT EXISTS payee_aliases (
+    id TEXT PRIMARY KEY,
+    payee_id TEXT NOT NULL,
+    alias TEXT NOT NULL COLLATE NOCASE UNIQUE,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_payee_aliases_payee ON payee_aliases(payee_id);
+
+-- Seed payees from the names already used on transactions
+INSERT OR IGNORE INTO payees (id, name)
+SELECT lower(hex(randomblob(16))), payee
+FROM transactions
+WHERE payee IS NOT NULL AND payee != '' AND deleted_at IS NULL
+GROUP BY payee COLLATE NOCASE;
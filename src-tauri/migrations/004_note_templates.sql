@@ -0,0 +1,9 @@
+-- Reusable note templates with {variable} placeholders
+
+CREATE TABLE IF NOT EXISTS note_templates (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    template TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
@@ -0,0 +1,4 @@
+-- Foreign-currency transactions keep the amount charged in the original currency
+
+ALTER TABLE transactions ADD COLUMN original_amount INTEGER;
+ALTER TABLE transactions ADD COLUMN original_currency TEXT;
@@ -0,0 +1,11 @@
+-- Derive available balances for existing accounts: cleared funds for deposit accounts,
+-- remaining credit for cards with a limit
+
+UPDATE accounts SET available_balance = CASE
+    WHEN account_type = 'credit_card' AND credit_limit IS NOT NULL
+        THEN credit_limit + current_balance
+    ELSE current_balance - (
+        SELECT COALESCE(SUM(t.amount), 0) FROM transactions t
+        WHERE t.account_id = accounts.id AND t.status = 'pending' AND t.deleted_at IS NULL
+    )
+END;
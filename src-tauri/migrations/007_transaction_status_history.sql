@@ -0,0 +1,12 @@
+-- Timestamped record of every transaction status transition
+
+CREATE TABLE IF NOT EXISTS transaction_status_history (
+    id TEXT PRIMARY KEY,
+    transaction_id TEXT NOT NULL,
+    from_status TEXT NOT NULL,
+    to_status TEXT NOT NULL,
+    changed_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_transaction_status_history_transaction
+    ON transaction_status_history(transaction_id);
@@ -0,0 +1,24 @@
+-- Saved transactions for frequently typed manual entries (rent, cash withdrawals, ...)
+
+CREATE TABLE IF NOT EXISTS transaction_templates (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    account_id TEXT,
+    payee TEXT,
+    amount INTEGER,
+    category_id TEXT,
+    notes TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE TABLE IF NOT EXISTS transaction_template_splits (
+    id TEXT PRIMARY KEY,
+    template_id TEXT NOT NULL,
+    category_id TEXT,
+    amount INTEGER NOT NULL,
+    memo TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_transaction_template_splits_template
+    ON transaction_template_splits(template_id);
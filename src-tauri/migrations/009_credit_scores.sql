@@ -0,0 +1,11 @@
+-- Manually entered credit-score history
+
+CREATE TABLE IF NOT EXISTS credit_scores (
+    id TEXT PRIMARY KEY,
+    date TEXT NOT NULL,
+    bureau TEXT NOT NULL,
+    score INTEGER NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_credit_scores_date ON credit_scores(date);
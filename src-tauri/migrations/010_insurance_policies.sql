@@ -0,0 +1,18 @@
+-- Insurance policies with premiums and renewal dates
+
+CREATE TABLE IF NOT EXISTS insurance_policies (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    policy_type TEXT NOT NULL DEFAULT 'other',
+    provider TEXT,
+    policy_number TEXT,
+    premium INTEGER NOT NULL DEFAULT 0,
+    premium_frequency TEXT NOT NULL DEFAULT 'yearly',
+    term_months INTEGER NOT NULL DEFAULT 12,
+    renewal_date TEXT,
+    recurring_transaction_id TEXT,
+    notes TEXT,
+    is_active INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
@@ -0,0 +1,25 @@
+-- Gift cards and store credit, tracked outside of bank accounts
+
+CREATE TABLE IF NOT EXISTS gift_cards (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    merchant TEXT,
+    initial_balance INTEGER NOT NULL DEFAULT 0,
+    balance INTEGER NOT NULL DEFAULT 0,
+    expiry_date TEXT,
+    notes TEXT,
+    is_active INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+-- Purchases paid (in whole or part) from a gift card; one card per transaction
+CREATE TABLE IF NOT EXISTS gift_card_usages (
+    id TEXT PRIMARY KEY,
+    gift_card_id TEXT NOT NULL,
+    transaction_id TEXT NOT NULL UNIQUE,
+    amount INTEGER NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_gift_card_usages_card ON gift_card_usages(gift_card_id);
@@ -0,0 +1,13 @@
+-- Cashback / rewards earn rates per card account. A rule without a category is the
+-- card's base rate for everything not covered by a category rule.
+
+CREATE TABLE IF NOT EXISTS reward_rules (
+    id TEXT PRIMARY KEY,
+    account_id TEXT NOT NULL,
+    category_id TEXT,
+    rate REAL NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_reward_rules_account ON reward_rules(account_id);
@@ -0,0 +1,7 @@
+-- Pre-authorization holds (hotel, gas, rental deposits) are tracked separately from
+-- real spending until the settled charge posts or the hold expires
+
+ALTER TABLE transactions ADD COLUMN is_hold INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE transactions ADD COLUMN hold_expires_at TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_transactions_holds ON transactions(is_hold) WHERE is_hold = 1;
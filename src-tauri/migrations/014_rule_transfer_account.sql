@@ -0,0 +1,4 @@
+-- Rules can turn a matching withdrawal into a transfer to another account,
+-- e.g. ATM withdrawals into the cash wallet
+
+ALTER TABLE category_rules ADD COLUMN transfer_account_id TEXT;
@@ -0,0 +1,5 @@
+-- Day of the month a bill is expected, for the bills calendar. Categories carry it for
+-- bills tracked by budget rather than by a recurring transaction (e.g. utilities).
+
+ALTER TABLE categories ADD COLUMN due_day INTEGER;
+ALTER TABLE recurring_transactions ADD COLUMN due_day INTEGER;
@@ -0,0 +1,13 @@
+-- Large purchases being saved up for, feeding the cash-flow forecast and safe-to-spend
+
+CREATE TABLE IF NOT EXISTS planned_purchases (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    target_amount INTEGER NOT NULL,
+    desired_date TEXT NOT NULL,
+    category_id TEXT,
+    notes TEXT,
+    is_purchased INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
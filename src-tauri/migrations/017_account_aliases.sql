@@ -0,0 +1,10 @@
+-- Nicknames an account goes by, e.g. the name a bank prints on statements
+
+CREATE TABLE IF NOT EXISTS account_aliases (
+    id TEXT PRIMARY KEY,
+    account_id TEXT NOT NULL,
+    alias TEXT NOT NULL COLLATE NOCASE UNIQUE,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_account_aliases_account ON account_aliases(account_id);
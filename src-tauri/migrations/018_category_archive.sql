@@ -0,0 +1,3 @@
+-- Archived categories are hidden from pickers and rule targets but keep their history
+
+ALTER TABLE categories ADD COLUMN is_archived INTEGER NOT NULL DEFAULT 0;
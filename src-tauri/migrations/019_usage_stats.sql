@@ -0,0 +1,12 @@
+-- Local-only usage counters: how often features are used and how often each statement
+-- parser succeeds. Nothing here is ever sent anywhere.
+
+CREATE TABLE IF NOT EXISTS usage_stats (
+    kind TEXT NOT NULL, -- 'feature' or 'parser'
+    name TEXT NOT NULL,
+    uses INTEGER NOT NULL DEFAULT 0,
+    failures INTEGER NOT NULL DEFAULT 0,
+    first_used_at TEXT NOT NULL,
+    last_used_at TEXT NOT NULL,
+    PRIMARY KEY (kind, name)
+);
@@ -0,0 +1,4 @@
+-- Compound rule conditions: a JSON array of conditions that must all match. Rules without
+-- one keep matching on their single rule type and pattern.
+
+ALTER TABLE category_rules ADD COLUMN conditions TEXT;
@@ -0,0 +1,6 @@
+-- Fingerprint of the statement file behind each import batch, so selecting a file that
+-- was already imported can be flagged before it's parsed.
+
+ALTER TABLE import_batches ADD COLUMN file_hash TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_import_batches_file_hash ON import_batches(file_hash);
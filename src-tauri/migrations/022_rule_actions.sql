@@ -0,0 +1,7 @@
+-- Rule actions: a JSON array of edits a rule makes to each matching transaction besides
+-- setting its category (rename payee, append a note, add a tag, mark as transfer, set
+-- status). Transactions gain tags, stored as a JSON array of strings, for the tag action.
+
+ALTER TABLE category_rules ADD COLUMN actions TEXT;
+
+ALTER TABLE transactions ADD COLUMN tags TEXT;
@@ -0,0 +1,4 @@
+-- How often a savings account credits interest, for projecting interest growth on goals
+-- linked to it. interest_rate is the account's APY; this only decides when it lands.
+
+ALTER TABLE accounts ADD COLUMN interest_compounding TEXT NOT NULL DEFAULT 'monthly';
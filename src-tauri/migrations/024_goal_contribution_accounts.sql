@@ -0,0 +1,9 @@
+-- Transfers into a goal's contribution account (e.g. the "House Fund" savings account)
+-- are recorded as contributions to the goal when they're imported, so its progress
+-- follows real money movement. Contributions remember their transaction so each
+-- transfer only counts once.
+
+ALTER TABLE goals ADD COLUMN contribution_account_id TEXT REFERENCES accounts(id);
+
+CREATE INDEX IF NOT EXISTS idx_goal_contributions_transaction
+    ON goal_contributions(transaction_id);
@@ -0,0 +1,4 @@
+-- Watch-only budgets track a category's spending and trend without a limit, so there's
+-- nothing remaining or overspent. They're left out of allocations and forecasts.
+
+ALTER TABLE budgets ADD COLUMN is_watch_only INTEGER NOT NULL DEFAULT 0;
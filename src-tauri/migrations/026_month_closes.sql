@@ -0,0 +1,16 @@
+-- Closing a month records its net worth snapshot and report, carries unspent rollover
+-- budgets into the next month's budget period, and locks its reconciled transactions.
+-- Reopening a month deletes its row, which unlocks them again.
+
+CREATE TABLE IF NOT EXISTS month_closes (
+    month TEXT PRIMARY KEY,
+    net_worth_snapshot_id TEXT NOT NULL REFERENCES net_worth_snapshots(id),
+    rolled_over_budgets INTEGER NOT NULL DEFAULT 0,
+    report TEXT NOT NULL,
+    archived_review_count INTEGER NOT NULL DEFAULT 0,
+    locked_count INTEGER NOT NULL DEFAULT 0,
+    closed_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_budget_periods_budget_start
+    ON budget_periods(budget_id, period_start);
@@ -0,0 +1,5 @@
+-- Custom budget periods repeat every period_days days counting from period_anchor.
+-- Bi-weekly budgets also count from the anchor, or from the day they were created.
+
+ALTER TABLE budgets ADD COLUMN period_days INTEGER;
+ALTER TABLE budgets ADD COLUMN period_anchor TEXT;
@@ -0,0 +1,4 @@
+-- A budget's amount can be set for a single month through its budget period. Months
+-- without one use the budget's own amount, prorated to a month for non-monthly budgets.
+
+ALTER TABLE budget_periods ADD COLUMN amount_override INTEGER;
@@ -0,0 +1,16 @@
+-- Budgets can set alert thresholds as percentages of their monthly amount. Crossing one
+-- records an alert, once per budget, month and threshold, and notifies the frontend.
+
+ALTER TABLE budgets ADD COLUMN alert_thresholds TEXT;
+
+CREATE TABLE IF NOT EXISTS budget_alerts (
+    id TEXT PRIMARY KEY,
+    budget_id TEXT NOT NULL,
+    month TEXT NOT NULL,
+    threshold INTEGER NOT NULL,
+    spent INTEGER NOT NULL,
+    budgeted INTEGER NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    dismissed_at TEXT,
+    UNIQUE (budget_id, month, threshold)
+);
@@ -0,0 +1,4 @@
+-- A budget can count spending in its category's subcategories as well as the category
+-- itself, so a "Food" budget covers "Groceries" and "Dining".
+
+ALTER TABLE budgets ADD COLUMN include_children INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,5 @@
+-- Accounts and single transactions can be kept out of budgets and spending reports, for
+-- business-card spending or expenses that get reimbursed.
+
+ALTER TABLE accounts ADD COLUMN exclude_from_budget INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE transactions ADD COLUMN exclude_from_budget INTEGER NOT NULL DEFAULT 0;
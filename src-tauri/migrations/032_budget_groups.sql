@@ -0,0 +1,17 @@
+-- Budget groups share one monthly pool across several categories, e.g. "Fun money" for
+-- dining, entertainment and hobbies. A category belongs to at most one group.
+
+CREATE TABLE IF NOT EXISTS budget_groups (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE TABLE IF NOT EXISTS budget_group_categories (
+    category_id TEXT PRIMARY KEY,
+    group_id TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_budget_group_categories_group ON budget_group_categories(group_id);
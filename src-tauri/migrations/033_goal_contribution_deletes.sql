@@ -0,0 +1,4 @@
+-- Goal contributions can be deleted to correct mistakes. They're kept with a deletion
+-- time so a transfer recorded as a contribution isn't picked up again on the next import.
+
+ALTER TABLE goal_contributions ADD COLUMN deleted_at TEXT;
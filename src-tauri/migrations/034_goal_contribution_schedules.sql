@@ -0,0 +1,15 @@
+-- Goals can contribute to themselves on a schedule. Due contributions are made when the
+-- database is unlocked; with a source account, each one also moves the money there by
+-- transfer into the goal's contribution (or linked) account.
+
+CREATE TABLE IF NOT EXISTS goal_contribution_schedules (
+    goal_id TEXT PRIMARY KEY,
+    amount INTEGER NOT NULL,
+    frequency TEXT NOT NULL,
+    start_date TEXT NOT NULL,
+    next_date TEXT NOT NULL,
+    from_account_id TEXT REFERENCES accounts(id),
+    is_active INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
@@ -0,0 +1,14 @@
+-- Debt payoff goals cover one or more credit card and loan accounts. A goal's monthly
+-- payment is split across them by its strategy: snowball pays the smallest balance first,
+-- avalanche the highest interest rate.
+
+ALTER TABLE goals ADD COLUMN payoff_strategy TEXT;
+ALTER TABLE goals ADD COLUMN monthly_payment INTEGER;
+
+CREATE TABLE IF NOT EXISTS goal_debts (
+    goal_id TEXT NOT NULL,
+    account_id TEXT NOT NULL REFERENCES accounts(id),
+    -- The least that has to be paid on the debt each month
+    minimum_payment INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (goal_id, account_id)
+);
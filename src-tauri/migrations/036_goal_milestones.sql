@@ -0,0 +1,15 @@
+-- Goal milestones are percentages of a goal's target worth celebrating on the way there.
+-- A goal without its own list uses 25/50/75/100.
+
+ALTER TABLE goals ADD COLUMN milestones TEXT;
+
+-- When each milestone was first reached. A milestone the goal falls back below is cleared
+-- so reaching it again is celebrated again.
+CREATE TABLE IF NOT EXISTS goal_milestones (
+    goal_id TEXT NOT NULL REFERENCES goals(id),
+    percent INTEGER NOT NULL,
+    -- The goal's saved amount when the milestone was reached
+    amount INTEGER NOT NULL,
+    reached_at TEXT NOT NULL,
+    PRIMARY KEY (goal_id, percent)
+);
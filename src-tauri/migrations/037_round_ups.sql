@@ -0,0 +1,28 @@
+-- Round-up savings: spending on the selected accounts is rounded up to the next dollar and
+-- the difference set aside, then contributed to the chosen goal on a schedule. There is
+-- one round-up setup, so the settings table holds a single row.
+
+CREATE TABLE IF NOT EXISTS round_up_settings (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    goal_id TEXT REFERENCES goals(id),
+    frequency TEXT NOT NULL DEFAULT 'weekly',
+    -- Spending from this date on is rounded up; contributions fall due from it too
+    start_date TEXT NOT NULL,
+    next_date TEXT NOT NULL,
+    is_enabled INTEGER NOT NULL DEFAULT 0,
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE TABLE IF NOT EXISTS round_up_accounts (
+    account_id TEXT PRIMARY KEY REFERENCES accounts(id)
+);
+
+-- The round-up taken from each transaction. Pending until contributed.
+CREATE TABLE IF NOT EXISTS round_ups (
+    transaction_id TEXT PRIMARY KEY REFERENCES transactions(id),
+    amount INTEGER NOT NULL,
+    contribution_id TEXT REFERENCES goal_contributions(id),
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_round_ups_pending ON round_ups(contribution_id);
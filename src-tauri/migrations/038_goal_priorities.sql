@@ -0,0 +1,5 @@
+-- Goals can be put in priority order, highest first, and weighted, for splitting a lump
+-- sum across them.
+
+ALTER TABLE goals ADD COLUMN priority INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE goals ADD COLUMN allocation_weight INTEGER NOT NULL DEFAULT 1;
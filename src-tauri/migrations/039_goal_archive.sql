@@ -0,0 +1,5 @@
+-- Archived goals are put away without deleting them: they leave the goal list and stop
+-- receiving contributions, but keep their contribution history and can be restored.
+
+ALTER TABLE goals ADD COLUMN is_archived INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE goals ADD COLUMN archived_at TEXT;
@@ -0,0 +1,16 @@
+-- A closing statement records an account's balance on the statement date and locks its
+-- transactions dated on or before it. Unlocking keeps the statement but lifts the lock
+-- so mistakes can be corrected.
+
+CREATE TABLE IF NOT EXISTS account_statements (
+    id TEXT PRIMARY KEY,
+    account_id TEXT NOT NULL REFERENCES accounts(id),
+    statement_date TEXT NOT NULL,
+    balance INTEGER NOT NULL,
+    is_locked INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    unlocked_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_account_statements_account
+    ON account_statements(account_id, statement_date);
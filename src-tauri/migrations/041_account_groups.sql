@@ -0,0 +1,12 @@
+-- User-defined account groups, e.g. "Liquid", "Retirement" or "Property", that net worth
+-- and the dashboard break their totals down by. An account belongs to at most one group.
+
+CREATE TABLE IF NOT EXISTS account_groups (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    display_order INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+ALTER TABLE accounts ADD COLUMN account_group_id TEXT;
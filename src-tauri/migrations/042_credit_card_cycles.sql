@@ -0,0 +1,6 @@
+-- Statement cycle of a credit card: the day of the month its statement closes, the day
+-- payment is due and the minimum payment, for utilization and due date warnings.
+
+ALTER TABLE accounts ADD COLUMN statement_closing_day INTEGER;
+ALTER TABLE accounts ADD COLUMN payment_due_day INTEGER;
+ALTER TABLE accounts ADD COLUMN minimum_payment INTEGER;
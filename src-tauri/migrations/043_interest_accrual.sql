@@ -0,0 +1,6 @@
+-- Estimated interest accrual for savings and loan accounts. Accounts opt in, and each
+-- month's interest is posted once the month is over; interest_accrued_through is the last
+-- month posted, as YYYY-MM.
+
+ALTER TABLE accounts ADD COLUMN accrue_interest INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE accounts ADD COLUMN interest_accrued_through TEXT;
@@ -0,0 +1,23 @@
+-- Exchange rates for aggregating accounts held in different currencies. Totals are
+-- reported in the base currency (the base_currency setting, USD when unset), converting
+-- each amount at the rate nearest its date.
+
+CREATE TABLE IF NOT EXISTS fx_rates (
+    id TEXT PRIMARY KEY,
+    from_currency TEXT NOT NULL,
+    to_currency TEXT NOT NULL,
+    rate_date TEXT NOT NULL,
+    -- Units of to_currency that one unit of from_currency buys
+    rate REAL NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    UNIQUE (from_currency, to_currency, rate_date)
+);
+
+CREATE INDEX IF NOT EXISTS idx_fx_rates_inverse ON fx_rates(to_currency, from_currency, rate_date);
+
+-- Rates in both directions: a rate entered one way converts back at its inverse
+CREATE VIEW IF NOT EXISTS fx_rate_pairs AS
+    SELECT from_currency, to_currency, rate_date, rate FROM fx_rates
+    UNION ALL
+    SELECT to_currency, from_currency, rate_date, 1.0 / rate FROM fx_rates;
@@ -0,0 +1,13 @@
+-- Where each exchange rate came from: 'manual' for rates entered by hand, otherwise the
+-- provider it was fetched from. A manual rate wins over a fetched one on the same date.
+
+ALTER TABLE fx_rates ADD COLUMN source TEXT NOT NULL DEFAULT 'manual';
+
+DROP VIEW IF EXISTS fx_rate_pairs;
+
+CREATE VIEW fx_rate_pairs AS
+    SELECT from_currency, to_currency, rate_date, rate, source = 'manual' AS is_manual
+    FROM fx_rates
+    UNION ALL
+    SELECT to_currency, from_currency, rate_date, 1.0 / rate, source = 'manual'
+    FROM fx_rates;
@@ -0,0 +1,19 @@
+-- Closing an account. A closed account is inactive and has a closing date; its
+-- transactions stay in place for reports. The closure records where the account stood
+-- when it was closed, and where any remaining balance was transferred.
+
+ALTER TABLE accounts ADD COLUMN closed_on TEXT;
+
+CREATE TABLE IF NOT EXISTS account_closures (
+    account_id TEXT PRIMARY KEY,
+    closed_on TEXT NOT NULL,
+    -- Balance before any transfer out
+    closing_balance INTEGER NOT NULL,
+    transfer_account_id TEXT,
+    transfer_id TEXT,
+    transaction_count INTEGER NOT NULL,
+    first_transaction_date TEXT,
+    last_transaction_date TEXT,
+    notes TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
@@ -0,0 +1,10 @@
+-- Account types are now validated. Map a bare "credit" to credit cards, and anything else
+-- unrecognised to "other".
+
+UPDATE accounts SET account_type = 'credit_card' WHERE account_type = 'credit';
+
+UPDATE accounts SET account_type = 'other'
+WHERE account_type NOT IN (
+    'checking', 'savings', 'credit_card', 'loan', 'investment', 'retirement', 'property',
+    'cash', 'other'
+);
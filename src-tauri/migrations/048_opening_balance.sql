@@ -0,0 +1,17 @@
+-- An account's opening balance is kept apart from its transactions, so its balance can be
+-- recomputed as the opening balance plus everything posted since. Existing accounts open
+-- with whatever part of their balance their transactions don't account for, on the date
+-- of their first transaction.
+
+ALTER TABLE accounts ADD COLUMN opening_balance INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE accounts ADD COLUMN opening_date TEXT;
+
+UPDATE accounts SET
+    opening_balance = current_balance - COALESCE((
+        SELECT SUM(amount) FROM transactions
+        WHERE account_id = accounts.id AND deleted_at IS NULL
+    ), 0),
+    opening_date = COALESCE((
+        SELECT MIN(date) FROM transactions
+        WHERE account_id = accounts.id AND deleted_at IS NULL
+    ), date(created_at));
@@ -0,0 +1,11 @@
+-- Investment transactions record buys, sells, dividends, splits and fees in an investment
+-- account. Each keeps the change it made to its holding, so deleting it can undo exactly
+-- that, and links the cash transaction it posted to the account.
+
+ALTER TABLE investment_transactions ADD COLUMN quantity_change REAL NOT NULL DEFAULT 0;
+ALTER TABLE investment_transactions ADD COLUMN cost_basis_change INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE investment_transactions ADD COLUMN transaction_id TEXT;
+ALTER TABLE investment_transactions ADD COLUMN notes TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_investment_transactions_account
+    ON investment_transactions(account_id, date);
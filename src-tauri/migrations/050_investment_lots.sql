@@ -0,0 +1,31 @@
+-- Cost basis lots. Each buy opens a lot; a sale takes units out of lots first in, first
+-- out, or evenly across them at the average cost, depending on the cost basis method
+-- setting. What a sale took from each lot is kept so deleting the sale can put it back,
+-- and the sale's realized gain is kept on the investment transaction.
+
+CREATE TABLE IF NOT EXISTS investment_lots (
+    id TEXT PRIMARY KEY,
+    account_id TEXT NOT NULL,
+    security_id TEXT NOT NULL,
+    -- The buy that opened the lot, or NULL for units held before lots were tracked
+    investment_transaction_id TEXT,
+    acquired_on TEXT NOT NULL,
+    quantity REAL NOT NULL,
+    cost INTEGER NOT NULL,
+    remaining_quantity REAL NOT NULL,
+    remaining_cost INTEGER NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_investment_lots_holding
+    ON investment_lots(account_id, security_id, acquired_on);
+
+CREATE TABLE IF NOT EXISTS investment_lot_sales (
+    investment_transaction_id TEXT NOT NULL,
+    lot_id TEXT NOT NULL,
+    quantity REAL NOT NULL,
+    cost INTEGER NOT NULL,
+    PRIMARY KEY (investment_transaction_id, lot_id)
+);
+
+ALTER TABLE investment_transactions ADD COLUMN realized_gain INTEGER;
@@ -0,0 +1,3 @@
+-- Whether a compound rule needs all of its conditions to match or any one of them
+
+ALTER TABLE category_rules ADD COLUMN match_mode TEXT NOT NULL DEFAULT 'all';
@@ -0,0 +1,9 @@
+-- Record the milestones goals had already passed before milestones were tracked, so the
+-- first check after upgrading doesn't celebrate them. When they were reached isn't known;
+-- the goal's last update stands in.
+
+INSERT OR IGNORE INTO goal_milestones (goal_id, percent, amount, reached_at)
+SELECT g.id, m.value, g.current_amount, g.updated_at
+FROM goals g, json_each(COALESCE(g.milestones, '[25, 50, 75, 100]')) m
+WHERE g.target_amount > 0
+  AND g.current_amount * 100 >= g.target_amount * m.value;
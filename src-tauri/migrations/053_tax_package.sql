@@ -0,0 +1,34 @@
+-- Categories whose transactions are tax deductible, chosen by the user. Subcategories of
+-- a deductible category count as deductible too.
+ALTER TABLE categories ADD COLUMN is_tax_deductible INTEGER NOT NULL DEFAULT 0;
+
+-- Start with the usual deductible expenses marked
+UPDATE categories SET is_tax_deductible = 1
+WHERE id IN (
+    'cat_housing_rent',
+    'cat_housing_property_tax',
+    'cat_health_insurance',
+    'cat_health_doctor',
+    'cat_health_pharmacy',
+    'cat_health_dental',
+    'cat_health_vision',
+    'cat_personal_education',
+    'cat_personal_childcare',
+    'cat_personal_charity',
+    'cat_financial_interest',
+    'cat_financial_taxes'
+);
+
+-- Files kept with a transaction, such as receipts. The contents are stored in the
+-- database so they're encrypted along with everything else.
+CREATE TABLE IF NOT EXISTS transaction_attachments (
+    id TEXT PRIMARY KEY,
+    transaction_id TEXT NOT NULL REFERENCES transactions(id),
+    file_name TEXT NOT NULL,
+    size INTEGER NOT NULL,
+    contents BLOB NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_transaction_attachments_transaction
+    ON transaction_attachments(transaction_id);
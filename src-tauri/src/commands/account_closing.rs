@@ -0,0 +1,233 @@
+use crate::commands::accounts::fetch_account;
+use crate::commands::goals::deactivate_account_contribution_schedules;
+use crate::commands::transactions::{insert_transaction, link_transaction_pair};
+use crate::db::Database;
+use crate::error::{AppError, Result};
+use crate::events::{emit_changed, ACCOUNTS_CHANGED, GOALS_CHANGED, TRANSACTIONS_CHANGED};
+use crate::models::{Account, AccountClosure};
+use chrono::NaiveDate;
+use rusqlite::{Connection, OptionalExtension};
+use std::sync::Mutex;
+use tauri::{AppHandle, State};
+
+const ACCOUNT_CLOSURE_COLUMNS: &str = "account_id, closed_on, closing_balance,
+     transfer_account_id, transfer_id, transaction_count, first_transaction_date,
+     last_transaction_date, notes, created_at";
+
+fn account_closure_from_row(row: &rusqlite::Row) -> rusqlite::Result<AccountClosure> {
+    Ok(AccountClosure {
+        account_id: row.get(0)?,
+        closed_on: row.get(1)?,
+        closing_balance: row.get(2)?,
+        transfer_account_id: row.get(3)?,
+        transfer_id: row.get(4)?,
+        transaction_count: row.get(5)?,
+        first_transaction_date: row.get(6)?,
+        last_transaction_date: row.get(7)?,
+        notes: row.get(8)?,
+        created_at: row.get(9)?,
+    })
+}
+
+fn fetch_account_closure(conn: &Connection, account_id: &str) -> Result<Option<AccountClosure>> {
+    Ok(conn
+        .query_row(
+            &format!(
+                "SELECT {} FROM account_closures WHERE account_id = ?1",
+                ACCOUNT_CLOSURE_COLUMNS
+            ),
+            [account_id],
+            account_closure_from_row,
+        )
+        .optional()?)
+}
+
+/// Move the whole balance of `account` into `target` as a linked transfer dated `date`,
+/// returning the transfer ID. The transfer is kept out of budgets.
+fn transfer_closing_balance(
+    conn: &Connection,
+    account: &Account,
+    target: &Account,
+    date: &str,
+) -> Result<String> {
+    if target.id == account.id {
+        return Err(AppError::Validation(
+            "Transfer the closing balance to a different account".to_string(),
+        ));
+    }
+    if target.closed_on.is_some() {
+        return Err(AppError::Validation(format!("{} is closed", target.name)));
+    }
+    if target.currency != account.currency {
+        return Err(AppError::Validation(format!(
+            "{} is in {}, not {}. Transfer the balance by hand, then close the account",
+            target.name, target.currency, account.currency
+        )));
+    }
+
+    let notes = format!("Closing balance of {}", account.name);
+    let out_id = insert_transaction(
+        conn,
+        &serde_json::json!({
+            "accountId": account.id,
+            "date": date,
+            "amount": -account.current_balance,
+            "payee": format!("Transfer to {}", target.name),
+            "notes": notes,
+            "excludeFromBudget": true,
+        }),
+    )?;
+    let in_id = insert_transaction(
+        conn,
+        &serde_json::json!({
+            "accountId": target.id,
+            "date": date,
+            "amount": account.current_balance,
+            "payee": format!("Transfer from {}", account.name),
+            "notes": notes,
+            "excludeFromBudget": true,
+        }),
+    )?;
+    link_transaction_pair(conn, &out_id, &in_id)
+}
+
+/// Close an account. Its balance must be zero, or be transferred to `transferAccountId`
+/// in the same currency as part of closing. The account is marked inactive with its
+/// closing date, so it drops out of account pickers and totals, and a closure snapshot
+/// records its final balance and activity. Its transactions stay in reports.
+#[tauri::command]
+pub fn close_account(
+    account_id: String,
+    data: serde_json::Value,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<AccountClosure> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let account = fetch_account(conn, &account_id)?;
+    if account.closed_on.is_some() {
+        return Err(AppError::Validation(format!(
+            "{} is already closed",
+            account.name
+        )));
+    }
+    let closed_on = data["date"]
+        .as_str()
+        .map(|d| d.to_string())
+        .unwrap_or_else(|| chrono::Local::now().format("%Y-%m-%d").to_string());
+    if NaiveDate::parse_from_str(&closed_on, "%Y-%m-%d").is_err() {
+        return Err(AppError::Validation(format!(
+            "Invalid closing date: {}",
+            closed_on
+        )));
+    }
+    let transfer_account_id = data["transferAccountId"].as_str();
+    if account.current_balance != 0 && transfer_account_id.is_none() {
+        return Err(AppError::Validation(format!(
+            "{} still has a balance of {:.2}. Transfer it to another account or adjust it to zero before closing",
+            account.name,
+            account.current_balance as f64 / 100.0
+        )));
+    }
+
+    let tx = conn.unchecked_transaction()?;
+    let transfer = match transfer_account_id {
+        Some(target_id) if account.current_balance != 0 => {
+            let target = fetch_account(&tx, target_id)?;
+            let transfer_id = transfer_closing_balance(&tx, &account, &target, &closed_on)?;
+            Some((target.id, transfer_id))
+        }
+        _ => None,
+    };
+
+    let (transaction_count, first_date, last_date): (i64, Option<String>, Option<String>) = tx
+        .query_row(
+            "SELECT COUNT(*), MIN(date), MAX(date) FROM transactions
+             WHERE account_id = ?1 AND deleted_at IS NULL",
+            [&account.id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )?;
+    let now = chrono::Utc::now().to_rfc3339();
+    tx.execute(
+        "INSERT INTO account_closures (account_id, closed_on, closing_balance,
+                transfer_account_id, transfer_id, transaction_count, first_transaction_date,
+                last_transaction_date, notes, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+        rusqlite::params![
+            account.id,
+            closed_on,
+            account.current_balance,
+            transfer.as_ref().map(|(target_id, _)| target_id),
+            transfer.as_ref().map(|(_, transfer_id)| transfer_id),
+            transaction_count,
+            first_date,
+            last_date,
+            data["notes"].as_str(),
+            now,
+        ],
+    )?;
+    // Nothing is posted to a closed account, so interest accrual stops too
+    tx.execute(
+        "UPDATE accounts SET closed_on = ?1, is_active = 0, accrue_interest = 0, updated_at = ?2
+         WHERE id = ?3",
+        rusqlite::params![closed_on, now, account.id],
+    )?;
+    deactivate_account_contribution_schedules(&tx, &account.id, &now)?;
+    tx.commit()?;
+
+    emit_changed(
+        &app,
+        &[ACCOUNTS_CHANGED, TRANSACTIONS_CHANGED, GOALS_CHANGED],
+    );
+
+    fetch_account_closure(conn, &account.id)?
+        .ok_or_else(|| AppError::NotFound("Account closure not found".to_string()))
+}
+
+/// Reopen a closed account, discarding its closure snapshot. A closing transfer stays in
+/// both accounts' history.
+#[tauri::command]
+pub fn reopen_account(
+    account_id: String,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Account> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let account = fetch_account(conn, &account_id)?;
+    if account.closed_on.is_none() {
+        return Err(AppError::Validation(format!(
+            "{} isn't closed",
+            account.name
+        )));
+    }
+
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(
+        "DELETE FROM account_closures WHERE account_id = ?1",
+        [&account_id],
+    )?;
+    tx.execute(
+        "UPDATE accounts SET closed_on = NULL, is_active = 1, updated_at = ?1 WHERE id = ?2",
+        [&chrono::Utc::now().to_rfc3339(), &account_id],
+    )?;
+    tx.commit()?;
+
+    emit_changed(&app, &[ACCOUNTS_CHANGED]);
+
+    fetch_account(conn, &account_id)
+}
+
+/// The closure snapshot of a closed account, or None while it's open
+#[tauri::command]
+pub fn get_account_closure(
+    account_id: String,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Option<AccountClosure>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    fetch_account_closure(conn, &account_id)
+}
@@ -0,0 +1,220 @@
+use crate::commands::accounts::{account_columns, account_from_row, asset_liability_totals};
+use crate::db::Database;
+use crate::error::{AppError, Result};
+use crate::events::{emit_changed, ACCOUNTS_CHANGED};
+use crate::models::{Account, AccountGroup};
+use rusqlite::Connection;
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::{AppHandle, State};
+use uuid::Uuid;
+
+/// Net worth of the active accounts in one account group
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountGroupNetWorth {
+    /// `None` for the accounts that aren't in a group
+    pub group_id: Option<String>,
+    pub name: String,
+    pub total_assets: i64,
+    pub total_liabilities: i64,
+    pub net_worth: i64,
+}
+
+const ACCOUNT_GROUP_COLUMNS: &str = "id, name, display_order, created_at, updated_at";
+
+fn account_group_from_row(row: &rusqlite::Row) -> rusqlite::Result<AccountGroup> {
+    Ok(AccountGroup {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        display_order: row.get(2)?,
+        created_at: row.get(3)?,
+        updated_at: row.get(4)?,
+    })
+}
+
+fn load_account_groups(conn: &Connection) -> Result<Vec<AccountGroup>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM account_groups ORDER BY display_order, name",
+        ACCOUNT_GROUP_COLUMNS
+    ))?;
+    let groups = stmt
+        .query_map([], account_group_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(groups)
+}
+
+fn fetch_account_group(conn: &Connection, id: &str) -> Result<AccountGroup> {
+    conn.query_row(
+        &format!(
+            "SELECT {} FROM account_groups WHERE id = ?1",
+            ACCOUNT_GROUP_COLUMNS
+        ),
+        [id],
+        account_group_from_row,
+    )
+    .map_err(|_| AppError::NotFound("Account group not found".to_string()))
+}
+
+fn input_group_name(data: &serde_json::Value) -> Result<&str> {
+    let name = data["name"].as_str().unwrap_or("").trim();
+    if name.is_empty() {
+        return Err(AppError::Validation(
+            "Account group name is required".to_string(),
+        ));
+    }
+    Ok(name)
+}
+
+/// Net worth split by account group, in group order, with accounts outside any group
+/// last as "Ungrouped" when there are any
+pub(crate) fn net_worth_by_group(
+    conn: &Connection,
+    accounts: &[Account],
+) -> Result<Vec<AccountGroupNetWorth>> {
+    let groups = load_account_groups(conn)?;
+    let active: Vec<&Account> = accounts.iter().filter(|a| a.is_active).collect();
+    let grouped = |account: &Account| {
+        groups
+            .iter()
+            .any(|g| account.account_group_id.as_deref() == Some(g.id.as_str()))
+    };
+
+    let mut sections: Vec<(Option<String>, String)> = groups
+        .iter()
+        .map(|g| (Some(g.id.clone()), g.name.clone()))
+        .collect();
+    if active.iter().any(|a| !grouped(a)) {
+        sections.push((None, "Ungrouped".to_string()));
+    }
+
+    Ok(sections
+        .iter()
+        .map(|(group_id, name)| {
+            let members = active.iter().filter(|a| match group_id {
+                Some(_) => a.account_group_id == *group_id,
+                None => !grouped(a),
+            });
+            let (total_assets, total_liabilities) =
+                asset_liability_totals(members.map(|a| (a.account_type.as_str(), a.base_balance)));
+            AccountGroupNetWorth {
+                group_id: group_id.clone(),
+                name: name.clone(),
+                total_assets,
+                total_liabilities,
+                net_worth: total_assets - total_liabilities,
+            }
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub fn list_account_groups(db: State<'_, Mutex<Database>>) -> Result<Vec<AccountGroup>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    load_account_groups(conn)
+}
+
+#[tauri::command]
+pub fn create_account_group(
+    data: serde_json::Value,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<AccountGroup> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let name = input_group_name(&data)?;
+    let id = Uuid::new_v4().to_string();
+    let now = chrono::Utc::now().to_rfc3339();
+
+    conn.execute(
+        "INSERT INTO account_groups (id, name, display_order, created_at, updated_at)
+         VALUES (?1, ?2, COALESCE(?3, (SELECT COUNT(*) FROM account_groups)), ?4, ?5)",
+        rusqlite::params![id, name, data["displayOrder"].as_i64(), now, now],
+    )?;
+
+    emit_changed(&app, &[ACCOUNTS_CHANGED]);
+
+    fetch_account_group(conn, &id)
+}
+
+#[tauri::command]
+pub fn update_account_group(
+    id: String,
+    data: serde_json::Value,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<AccountGroup> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    fetch_account_group(conn, &id)?;
+    let name = match data.get("name") {
+        Some(_) => Some(input_group_name(&data)?),
+        None => None,
+    };
+    let now = chrono::Utc::now().to_rfc3339();
+
+    conn.execute(
+        "UPDATE account_groups SET
+            name = COALESCE(?1, name),
+            display_order = COALESCE(?2, display_order),
+            updated_at = ?3
+         WHERE id = ?4",
+        rusqlite::params![name, data["displayOrder"].as_i64(), now, id],
+    )?;
+
+    emit_changed(&app, &[ACCOUNTS_CHANGED]);
+
+    fetch_account_group(conn, &id)
+}
+
+/// Delete an account group. Its accounts are kept and become ungrouped.
+#[tauri::command]
+pub fn delete_account_group(
+    id: String,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<()> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let now = chrono::Utc::now().to_rfc3339();
+
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(
+        "UPDATE accounts SET account_group_id = NULL, updated_at = ?1 WHERE account_group_id = ?2",
+        [&now, &id],
+    )?;
+    let deleted = tx.execute("DELETE FROM account_groups WHERE id = ?1", [&id])?;
+    if deleted == 0 {
+        return Err(AppError::NotFound("Account group not found".to_string()));
+    }
+    tx.commit()?;
+
+    emit_changed(&app, &[ACCOUNTS_CHANGED]);
+
+    Ok(())
+}
+
+/// Current net worth broken down by account group
+#[tauri::command]
+pub fn get_net_worth_by_group(db: State<'_, Mutex<Database>>) -> Result<Vec<AccountGroupNetWorth>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM accounts WHERE deleted_at IS NULL",
+        account_columns()
+    ))?;
+    let accounts: Vec<Account> = stmt
+        .query_map([], account_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    net_worth_by_group(conn, &accounts)
+}
@@ -0,0 +1,179 @@
+use crate::db::Database;
+use crate::error::{AppError, Result};
+use crate::events::{emit_changed, ACCOUNTS_CHANGED, TRANSACTIONS_CHANGED};
+use chrono::NaiveDate;
+use rusqlite::Connection;
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::{AppHandle, State};
+use uuid::Uuid;
+
+/// An account's closing statement. While locked, transactions on the account dated on or
+/// before the statement date can't be edited or deleted.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountStatement {
+    pub id: String,
+    pub account_id: String,
+    pub statement_date: String,
+    /// The closing balance printed on the statement
+    pub balance: i64,
+    /// What the account's transactions add up to as of the statement date
+    pub ledger_balance: i64,
+    pub is_locked: bool,
+    pub created_at: String,
+    pub unlocked_at: Option<String>,
+}
+
+const ACCOUNT_STATEMENT_COLUMNS: &str = "s.id, s.account_id, s.statement_date, s.balance,
+    a.current_balance - COALESCE((
+        SELECT SUM(t.amount) FROM transactions t
+        WHERE t.account_id = s.account_id AND t.deleted_at IS NULL AND t.date > s.statement_date
+    ), 0),
+    s.is_locked, s.created_at, s.unlocked_at";
+
+fn account_statement_from_row(row: &rusqlite::Row) -> rusqlite::Result<AccountStatement> {
+    Ok(AccountStatement {
+        id: row.get(0)?,
+        account_id: row.get(1)?,
+        statement_date: row.get(2)?,
+        balance: row.get(3)?,
+        ledger_balance: row.get(4)?,
+        is_locked: row.get(5)?,
+        created_at: row.get(6)?,
+        unlocked_at: row.get(7)?,
+    })
+}
+
+fn fetch_account_statement(conn: &Connection, id: &str) -> Result<AccountStatement> {
+    conn.query_row(
+        &format!(
+            "SELECT {} FROM account_statements s
+             JOIN accounts a ON a.id = s.account_id
+             WHERE s.id = ?1",
+            ACCOUNT_STATEMENT_COLUMNS
+        ),
+        [id],
+        account_statement_from_row,
+    )
+    .map_err(|_| AppError::NotFound("Statement not found".to_string()))
+}
+
+/// Transactions on an account dated on or before its latest locked statement can't change;
+/// unlock the statement to correct them
+pub(crate) fn ensure_statement_unlocked(
+    conn: &Connection,
+    account_id: &str,
+    date: &str,
+) -> Result<()> {
+    let statement_date: Option<String> = conn.query_row(
+        "SELECT MAX(statement_date) FROM account_statements
+         WHERE account_id = ?1 AND is_locked = 1 AND statement_date >= ?2",
+        [account_id, date],
+        |row| row.get(0),
+    )?;
+
+    match statement_date {
+        Some(statement_date) => Err(AppError::Validation(format!(
+            "Transactions on or before {} are locked by a closing statement. Unlock the statement to change them",
+            statement_date
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Record a closing statement for an account and lock its transactions on or before the
+/// statement date
+#[tauri::command]
+pub fn record_account_statement(
+    account_id: String,
+    statement_date: String,
+    balance: i64,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<AccountStatement> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    if NaiveDate::parse_from_str(&statement_date, "%Y-%m-%d").is_err() {
+        return Err(AppError::Validation(format!(
+            "Invalid statement date: {}",
+            statement_date
+        )));
+    }
+    let exists: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM accounts WHERE id = ?1 AND deleted_at IS NULL)",
+        [&account_id],
+        |row| row.get(0),
+    )?;
+    if !exists {
+        return Err(AppError::NotFound("Account not found".to_string()));
+    }
+
+    let id = Uuid::new_v4().to_string();
+    let now = chrono::Utc::now().to_rfc3339();
+
+    conn.execute(
+        "INSERT INTO account_statements (id, account_id, statement_date, balance, is_locked,
+                created_at)
+         VALUES (?1, ?2, ?3, ?4, 1, ?5)",
+        rusqlite::params![id, account_id, statement_date, balance, now],
+    )?;
+
+    emit_changed(&app, &[ACCOUNTS_CHANGED, TRANSACTIONS_CHANGED]);
+
+    fetch_account_statement(conn, &id)
+}
+
+/// Closing statements for one account or all of them, latest statement date first
+#[tauri::command]
+pub fn list_account_statements(
+    account_id: Option<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<AccountStatement>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM account_statements s
+         JOIN accounts a ON a.id = s.account_id
+         WHERE a.deleted_at IS NULL AND (?1 IS NULL OR s.account_id = ?1)
+         ORDER BY s.statement_date DESC, s.created_at DESC",
+        ACCOUNT_STATEMENT_COLUMNS
+    ))?;
+    let statements = stmt
+        .query_map([account_id], account_statement_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(statements)
+}
+
+/// Lift a statement's lock so the transactions it covers can be corrected. The statement
+/// itself is kept.
+#[tauri::command]
+pub fn unlock_account_statement(
+    id: String,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<AccountStatement> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let updated = conn.execute(
+        "UPDATE account_statements SET is_locked = 0, unlocked_at = ?1
+         WHERE id = ?2 AND is_locked = 1",
+        [&now, &id],
+    )?;
+    if updated == 0 {
+        fetch_account_statement(conn, &id)?;
+        return Err(AppError::Validation(
+            "Statement is already unlocked".to_string(),
+        ));
+    }
+
+    emit_changed(&app, &[ACCOUNTS_CHANGED, TRANSACTIONS_CHANGED]);
+
+    fetch_account_statement(conn, &id)
+}
@@ -1,88 +1,371 @@
+use crate::commands::account_statements::ensure_statement_unlocked;
+use crate::commands::fx::{base_amount_sql, base_currency, validate_currency};
+use crate::commands::goals::deactivate_account_contribution_schedules;
+use crate::commands::interest::last_month_accrued;
+use crate::commands::month_close::ensure_transaction_unlocked;
+use crate::commands::transactions::{
+    insert_transaction, restore_transaction, soft_delete_transaction, transaction_from_row,
+    BALANCE_ADJUSTMENT_TYPE, TRANSACTION_COLUMNS,
+};
 use crate::db::Database;
 use crate::error::{AppError, Result};
-use crate::models::Account;
-use rusqlite::Connection;
+use crate::events::{
+    emit_changed, ACCOUNTS_CHANGED, GOALS_CHANGED, INVESTMENTS_CHANGED, TRANSACTIONS_CHANGED,
+};
+use crate::models::{Account, Transaction};
+use chrono::NaiveDate;
+use rusqlite::{Connection, OptionalExtension};
+use serde::Serialize;
 use std::sync::Mutex;
-use tauri::State;
+use tauri::{AppHandle, State};
 use uuid::Uuid;
 
-fn fetch_account(conn: &Connection, id: &str) -> Result<Account> {
+/// What merging one account into another moved
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountMergeResult {
+    pub account: Account,
+    pub transactions_moved: i64,
+    pub recurring_moved: i64,
+    pub holdings_moved: i64,
+}
+
+/// What deleting an account does, or would do, with its transactions
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountDeleteResult {
+    pub dry_run: bool,
+    /// "keep", "delete" or "reassign"
+    pub transactions: String,
+    pub transaction_count: i64,
+    /// Sum of the transactions' amounts
+    pub transaction_total: i64,
+    pub reassign_to_account_name: Option<String>,
+}
+
+/// What deleting an account can do with its transactions: keep them attached to it in the
+/// trash, move them to the trash too, or move them to another account
+pub(crate) const ACCOUNT_DELETE_TRANSACTIONS: &[&str] = &["keep", "delete", "reassign"];
+
+/// Columns pointing at an account that a merge moves from the source to the target. The
+/// source's balance snapshots and closure stay behind as history.
+const MERGED_ACCOUNT_REFERENCES: &[(&str, &str)] = &[
+    ("transactions", "transfer_account_id"),
+    ("investment_transactions", "account_id"),
+    ("investment_lots", "account_id"),
+    ("import_batches", "account_id"),
+    ("account_aliases", "account_id"),
+    ("account_statements", "account_id"),
+    ("category_rules", "account_id"),
+    ("category_rules", "transfer_account_id"),
+    ("transaction_templates", "account_id"),
+    ("reward_rules", "account_id"),
+    ("goals", "linked_account_id"),
+    ("goals", "contribution_account_id"),
+    ("goal_contribution_schedules", "from_account_id"),
+    ("goal_debts", "account_id"),
+    ("round_up_accounts", "account_id"),
+];
+
+/// Account types. Net worth, reports, interest and reconciliation treat each differently,
+/// so an account must have one of these.
+pub(crate) const ACCOUNT_TYPES: &[&str] = &[
+    "checking",
+    "savings",
+    "credit_card",
+    "loan",
+    "investment",
+    "retirement",
+    "property",
+    "cash",
+    "other",
+];
+/// Account types whose balances count towards assets in net worth
+pub(crate) const ASSET_ACCOUNT_TYPES: &[&str] = &[
+    "checking",
+    "savings",
+    "investment",
+    "retirement",
+    "property",
+    "cash",
+];
+/// Account types whose balances count towards liabilities in net worth
+pub(crate) const LIABILITY_ACCOUNT_TYPES: &[&str] = &["credit_card", "loan"];
+
+/// Total assets and liabilities, both positive, of `(account_type, balance)` pairs.
+/// Account types that are neither, like "other", count towards neither.
+pub(crate) fn asset_liability_totals<'a>(
+    balances: impl IntoIterator<Item = (&'a str, i64)>,
+) -> (i64, i64) {
+    let mut total_assets = 0;
+    let mut total_liabilities = 0;
+    for (account_type, balance) in balances {
+        if ASSET_ACCOUNT_TYPES.contains(&account_type) {
+            total_assets += balance;
+        } else if LIABILITY_ACCOUNT_TYPES.contains(&account_type) {
+            total_liabilities += balance.abs();
+        }
+    }
+    (total_assets, total_liabilities)
+}
+
+fn validate_account_type(data: &serde_json::Value) -> Result<()> {
+    match data["accountType"].as_str() {
+        Some(value) if !ACCOUNT_TYPES.contains(&value) => Err(AppError::Validation(format!(
+            "Unknown account type: {}",
+            value
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// How often a savings account can credit interest
+pub(crate) const INTEREST_COMPOUNDING: &[&str] = &["daily", "monthly", "quarterly", "annually"];
+
+fn validate_interest_compounding(data: &serde_json::Value) -> Result<()> {
+    match data["interestCompounding"].as_str() {
+        Some(value) if !INTEREST_COMPOUNDING.contains(&value) => Err(AppError::Validation(
+            format!("Unknown interest compounding: {}", value),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Statement closing and payment due days are days of the month, and a minimum payment
+/// can't be negative
+fn validate_statement_cycle(data: &serde_json::Value) -> Result<()> {
+    for (key, label) in [
+        ("statementClosingDay", "Statement closing day"),
+        ("paymentDueDay", "Payment due day"),
+    ] {
+        if let Some(day) = data[key].as_i64() {
+            if !(1..=31).contains(&day) {
+                return Err(AppError::Validation(format!(
+                    "{} must be between 1 and 31",
+                    label
+                )));
+            }
+        }
+    }
+    match data["minimumPayment"].as_i64() {
+        Some(amount) if amount < 0 => Err(AppError::Validation(
+            "Minimum payment can't be negative".to_string(),
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn validate_opening_date(data: &serde_json::Value) -> Result<()> {
+    match data["openingDate"].as_str() {
+        Some(date) if NaiveDate::parse_from_str(date, "%Y-%m-%d").is_err() => Err(
+            AppError::Validation(format!("Invalid opening date: {}", date)),
+        ),
+        _ => Ok(()),
+    }
+}
+
+/// The institution and account group given, if any, must exist
+fn validate_account_references(conn: &Connection, data: &serde_json::Value) -> Result<()> {
+    if let Some(institution_id) = data["institutionId"].as_str() {
+        let exists: bool = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM institutions WHERE id = ?1)",
+            [institution_id],
+            |row| row.get(0),
+        )?;
+        if !exists {
+            return Err(AppError::NotFound("Institution not found".to_string()));
+        }
+    }
+    if let Some(group_id) = data["accountGroupId"].as_str() {
+        let exists: bool = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM account_groups WHERE id = ?1)",
+            [group_id],
+            |row| row.get(0),
+        )?;
+        if !exists {
+            return Err(AppError::NotFound("Account group not found".to_string()));
+        }
+    }
+    Ok(())
+}
+
+/// Recompute an account's `available_balance` from its `current_balance`, which always
+/// includes pending transactions. Deposit accounts exclude pending items so only cleared
+/// funds show as available; credit cards with a limit report the remaining credit, where
+/// pending charges already count against the line.
+pub(crate) fn refresh_available_balance(conn: &Connection, account_id: &str) -> Result<()> {
+    conn.execute(
+        "UPDATE accounts SET available_balance = CASE
+            WHEN account_type = 'credit_card' AND credit_limit IS NOT NULL
+                THEN credit_limit + current_balance
+            ELSE current_balance - (
+                SELECT COALESCE(SUM(t.amount), 0) FROM transactions t
+                WHERE t.account_id = accounts.id AND t.status = 'pending' AND t.deleted_at IS NULL
+            )
+         END
+         WHERE id = ?1",
+        [account_id],
+    )?;
+
+    Ok(())
+}
+
+/// Live transactions on an account, oldest first
+fn live_account_transactions(conn: &Connection, account_id: &str) -> Result<Vec<(String, String)>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, date FROM transactions
+         WHERE account_id = ?1 AND deleted_at IS NULL
+         ORDER BY date, created_at",
+    )?;
+    let ids = stmt
+        .query_map([account_id], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(ids)
+}
+
+/// Refuse to move an account's transactions to `target_id` while any of them is locked by
+/// a closed month or a closing statement, on either account
+fn ensure_account_transactions_movable(
+    conn: &Connection,
+    account_id: &str,
+    target_id: &str,
+) -> Result<()> {
+    for (id, date) in live_account_transactions(conn, account_id)? {
+        ensure_transaction_unlocked(conn, &id)?;
+        ensure_statement_unlocked(conn, target_id, &date)?;
+    }
+    Ok(())
+}
+
+/// Columns selected for a full `Account`, in the order `account_from_row` expects.
+/// Aliases come back as a JSON array, the institution name is looked up and the balance is
+/// converted to the base currency at today's rate, so the select must be `FROM accounts`
+/// unaliased.
+pub(crate) fn account_columns() -> String {
+    format!(
+        "id, name, account_type, institution_id, account_number_masked, currency,
+         current_balance, available_balance, credit_limit, interest_rate,
+         is_active, is_hidden, display_order, ofx_account_id, last_sync_at,
+         notes, created_at, updated_at, interest_compounding, exclude_from_budget,
+         (SELECT json_group_array(alias) FROM (
+             SELECT alias FROM account_aliases WHERE account_id = accounts.id ORDER BY alias
+         )),
+         (SELECT name FROM institutions WHERE id = accounts.institution_id),
+         account_group_id, statement_closing_day, payment_due_day, minimum_payment,
+         accrue_interest, interest_accrued_through,
+         {},
+         closed_on, opening_balance, opening_date",
+        base_amount_sql("current_balance", "accounts.currency", "date('now')")
+    )
+}
+
+pub(crate) fn account_from_row(row: &rusqlite::Row) -> rusqlite::Result<Account> {
+    Ok(Account {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        account_type: row.get(2)?,
+        institution_id: row.get(3)?,
+        account_number_masked: row.get(4)?,
+        currency: row.get(5)?,
+        current_balance: row.get(6)?,
+        available_balance: row.get(7)?,
+        credit_limit: row.get(8)?,
+        interest_rate: row.get(9)?,
+        interest_compounding: row.get(18)?,
+        is_active: row.get(10)?,
+        is_hidden: row.get(11)?,
+        exclude_from_budget: row.get(19)?,
+        display_order: row.get(12)?,
+        ofx_account_id: row.get(13)?,
+        last_sync_at: row.get(14)?,
+        notes: row.get(15)?,
+        aliases: serde_json::from_str(&row.get::<_, String>(20)?).unwrap_or_default(),
+        institution_name: row.get(21)?,
+        account_group_id: row.get(22)?,
+        statement_closing_day: row.get(23)?,
+        payment_due_day: row.get(24)?,
+        minimum_payment: row.get(25)?,
+        accrue_interest: row.get(26)?,
+        interest_accrued_through: row.get(27)?,
+        base_balance: row.get(28)?,
+        closed_on: row.get(29)?,
+        opening_balance: row.get(30)?,
+        opening_date: row.get(31)?,
+        created_at: row.get(16)?,
+        updated_at: row.get(17)?,
+    })
+}
+
+fn digits(text: &str) -> String {
+    text.chars().filter(|c| c.is_ascii_digit()).collect()
+}
+
+/// Whether an account number a statement refers to (`reference`) is the stored one.
+/// Statements print anything from the full number to the last few digits, and stored
+/// numbers are usually masked, so the digits match when either one ends with the other.
+fn account_number_matches(stored: &str, reference: &str) -> bool {
+    let (stored, reference) = (digits(stored), digits(reference));
+    stored.len() >= 4
+        && reference.len() >= 4
+        && (stored.ends_with(&reference) || reference.ends_with(&stored))
+}
+
+/// Whether an account answers to a search: its name, an alias or its institution's name
+/// contains the query, or the query is its account number
+fn account_matches(account: &Account, query: &str) -> bool {
+    let query_lower = query.to_lowercase();
+    let contains = |text: &str| text.to_lowercase().contains(&query_lower);
+
+    contains(&account.name)
+        || account.aliases.iter().any(|a| contains(a))
+        || account.institution_name.as_deref().is_some_and(contains)
+        || [&account.account_number_masked, &account.ofx_account_id]
+            .into_iter()
+            .flatten()
+            .any(|number| account_number_matches(number, query))
+}
+
+pub(crate) fn fetch_account(conn: &Connection, id: &str) -> Result<Account> {
     conn.query_row(
-        "SELECT id, name, account_type, institution_id, account_number_masked, currency,
-                current_balance, available_balance, credit_limit, interest_rate,
-                is_active, is_hidden, display_order, ofx_account_id, last_sync_at,
-                notes, created_at, updated_at
-         FROM accounts
-         WHERE id = ?1 AND deleted_at IS NULL",
+        &format!(
+            "SELECT {} FROM accounts WHERE id = ?1 AND deleted_at IS NULL",
+            account_columns()
+        ),
         [id],
-        |row| {
-            Ok(Account {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                account_type: row.get(2)?,
-                institution_id: row.get(3)?,
-                account_number_masked: row.get(4)?,
-                currency: row.get(5)?,
-                current_balance: row.get(6)?,
-                available_balance: row.get(7)?,
-                credit_limit: row.get(8)?,
-                interest_rate: row.get(9)?,
-                is_active: row.get(10)?,
-                is_hidden: row.get(11)?,
-                display_order: row.get(12)?,
-                ofx_account_id: row.get(13)?,
-                last_sync_at: row.get(14)?,
-                notes: row.get(15)?,
-                created_at: row.get(16)?,
-                updated_at: row.get(17)?,
-            })
-        },
+        account_from_row,
     )
     .map_err(|_| AppError::NotFound("Account not found".to_string()))
 }
 
+/// List accounts, optionally only those matching `query` by name, alias, institution
+/// name or account number
 #[tauri::command]
-pub fn list_accounts(db: State<'_, Mutex<Database>>) -> Result<Vec<Account>> {
+pub fn list_accounts(
+    query: Option<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<Account>> {
     let database = db.lock().unwrap();
     let conn = database.get_connection()?;
 
-    let mut stmt = conn.prepare(
-        "SELECT id, name, account_type, institution_id, account_number_masked, currency,
-                current_balance, available_balance, credit_limit, interest_rate,
-                is_active, is_hidden, display_order, ofx_account_id, last_sync_at,
-                notes, created_at, updated_at
-         FROM accounts
-         WHERE deleted_at IS NULL
-         ORDER BY display_order, name"
-    )?;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM accounts WHERE deleted_at IS NULL ORDER BY display_order, name",
+        account_columns()
+    ))?;
 
-    let accounts = stmt
-        .query_map([], |row| {
-            Ok(Account {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                account_type: row.get(2)?,
-                institution_id: row.get(3)?,
-                account_number_masked: row.get(4)?,
-                currency: row.get(5)?,
-                current_balance: row.get(6)?,
-                available_balance: row.get(7)?,
-                credit_limit: row.get(8)?,
-                interest_rate: row.get(9)?,
-                is_active: row.get(10)?,
-                is_hidden: row.get(11)?,
-                display_order: row.get(12)?,
-                ofx_account_id: row.get(13)?,
-                last_sync_at: row.get(14)?,
-                notes: row.get(15)?,
-                created_at: row.get(16)?,
-                updated_at: row.get(17)?,
-            })
-        })?
+    let accounts: Vec<Account> = stmt
+        .query_map([], account_from_row)?
         .filter_map(|r| r.ok())
         .collect();
 
-    Ok(accounts)
+    let query = query.as_deref().map(str::trim).unwrap_or("");
+    if query.is_empty() {
+        return Ok(accounts);
+    }
+
+    Ok(accounts
+        .into_iter()
+        .filter(|account| account_matches(account, query))
+        .collect())
 }
 
 #[tauri::command]
@@ -95,29 +378,57 @@ pub fn get_account(id: String, db: State<'_, Mutex<Database>>) -> Result<Account
 #[tauri::command]
 pub fn create_account(
     data: serde_json::Value,
+    app: AppHandle,
     db: State<'_, Mutex<Database>>,
 ) -> Result<Account> {
     let database = db.lock().unwrap();
     let conn = database.get_connection()?;
 
+    validate_account_type(&data)?;
+    validate_interest_compounding(&data)?;
+    validate_statement_cycle(&data)?;
+    validate_opening_date(&data)?;
+    validate_account_references(conn, &data)?;
+
     let id = Uuid::new_v4().to_string();
     let now = chrono::Utc::now().to_rfc3339();
+    // New accounts are held in the base currency unless told otherwise
+    let currency = match data["currency"].as_str() {
+        Some(currency) => validate_currency(currency)?,
+        None => base_currency(conn)?,
+    };
+    // Interest accrues from the current month on
+    let accrue_interest = data["accrueInterest"].as_bool().unwrap_or(false);
+    let today = chrono::Local::now().date_naive();
+    // A new account has no transactions, so it's at its opening balance. A starting
+    // current balance is taken as the opening balance.
+    let opening_balance = data["openingBalance"]
+        .as_i64()
+        .or_else(|| data["currentBalance"].as_i64())
+        .unwrap_or(0);
+    let opening_date = data["openingDate"]
+        .as_str()
+        .map(|d| d.to_string())
+        .unwrap_or_else(|| today.format("%Y-%m-%d").to_string());
 
     conn.execute(
         "INSERT INTO accounts (
             id, name, account_type, institution_id, account_number_masked, currency,
             current_balance, available_balance, credit_limit, interest_rate,
             is_active, is_hidden, display_order, ofx_account_id, last_sync_at,
-            notes, created_at, updated_at
-        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18)",
+            notes, created_at, updated_at, interest_compounding, exclude_from_budget,
+            account_group_id, statement_closing_day, payment_due_day, minimum_payment,
+            accrue_interest, interest_accrued_through, opening_balance, opening_date
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18,
+                  ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?7, ?27)",
         rusqlite::params![
             id,
             data["name"].as_str().unwrap_or(""),
             data["accountType"].as_str().unwrap_or("checking"),
             data["institutionId"].as_str(),
             data["accountNumberMasked"].as_str(),
-            data["currency"].as_str().unwrap_or("USD"),
-            data["currentBalance"].as_i64().unwrap_or(0),
+            currency,
+            opening_balance,
             data["availableBalance"].as_i64(),
             data["creditLimit"].as_i64(),
             data["interestRate"].as_f64(),
@@ -129,9 +440,22 @@ pub fn create_account(
             data["notes"].as_str(),
             now,
             now,
+            data["interestCompounding"].as_str().unwrap_or("monthly"),
+            data["excludeFromBudget"].as_bool().unwrap_or(false),
+            data["accountGroupId"].as_str(),
+            data["statementClosingDay"].as_i64(),
+            data["paymentDueDay"].as_i64(),
+            data["minimumPayment"].as_i64(),
+            accrue_interest,
+            accrue_interest.then(|| last_month_accrued(today)),
+            opening_date,
         ],
     )?;
 
+    refresh_available_balance(conn, &id)?;
+
+    emit_changed(&app, &[ACCOUNTS_CHANGED]);
+
     fetch_account(conn, &id)
 }
 
@@ -139,23 +463,57 @@ pub fn create_account(
 pub fn update_account(
     id: String,
     data: serde_json::Value,
+    app: AppHandle,
     db: State<'_, Mutex<Database>>,
 ) -> Result<Account> {
     let database = db.lock().unwrap();
     let conn = database.get_connection()?;
 
+    validate_account_type(&data)?;
+    validate_interest_compounding(&data)?;
+    validate_statement_cycle(&data)?;
+    validate_opening_date(&data)?;
+    validate_account_references(conn, &data)?;
+
+    let currency = data["currency"]
+        .as_str()
+        .map(validate_currency)
+        .transpose()?;
     let now = chrono::Utc::now().to_rfc3339();
 
+    // Turning interest accrual on starts it from the current month, and a closed account
+    // stays inactive until it's reopened. Changing the opening balance moves the current
+    // balance with it, and setting the current balance directly moves the opening balance,
+    // so the two stay apart by exactly the account's transactions.
     conn.execute(
         "UPDATE accounts SET
             name = COALESCE(?1, name),
             account_type = COALESCE(?2, account_type),
-            current_balance = COALESCE(?3, current_balance),
-            is_active = COALESCE(?4, is_active),
+            current_balance = CASE WHEN ?28 IS NOT NULL
+                THEN current_balance + ?28 - opening_balance
+                ELSE COALESCE(?3, current_balance) END,
+            opening_balance = CASE WHEN ?28 IS NOT NULL
+                THEN ?28
+                ELSE opening_balance + COALESCE(?3, current_balance) - current_balance END,
+            opening_date = COALESCE(?29, opening_date),
+            is_active = CASE WHEN closed_on IS NULL THEN COALESCE(?4, is_active) ELSE 0 END,
             is_hidden = COALESCE(?5, is_hidden),
             notes = COALESCE(?6, notes),
-            updated_at = ?7
-         WHERE id = ?8",
+            interest_rate = CASE WHEN ?7 THEN ?8 ELSE interest_rate END,
+            interest_compounding = COALESCE(?9, interest_compounding),
+            updated_at = ?10,
+            exclude_from_budget = COALESCE(?12, exclude_from_budget),
+            institution_id = CASE WHEN ?13 THEN ?14 ELSE institution_id END,
+            account_group_id = CASE WHEN ?15 THEN ?16 ELSE account_group_id END,
+            statement_closing_day = CASE WHEN ?17 THEN ?18 ELSE statement_closing_day END,
+            payment_due_day = CASE WHEN ?19 THEN ?20 ELSE payment_due_day END,
+            minimum_payment = CASE WHEN ?21 THEN ?22 ELSE minimum_payment END,
+            credit_limit = CASE WHEN ?23 THEN ?24 ELSE credit_limit END,
+            interest_accrued_through = CASE WHEN ?25 AND NOT accrue_interest THEN ?26
+                ELSE interest_accrued_through END,
+            accrue_interest = COALESCE(?25, accrue_interest),
+            currency = COALESCE(?27, currency)
+         WHERE id = ?11",
         rusqlite::params![
             data["name"].as_str(),
             data["accountType"].as_str(),
@@ -163,25 +521,703 @@ pub fn update_account(
             data["isActive"].as_bool(),
             data["isHidden"].as_bool(),
             data["notes"].as_str(),
+            data.get("interestRate").is_some(),
+            data["interestRate"].as_f64(),
+            data["interestCompounding"].as_str(),
             now,
             id,
+            data["excludeFromBudget"].as_bool(),
+            data.get("institutionId").is_some(),
+            data["institutionId"].as_str(),
+            data.get("accountGroupId").is_some(),
+            data["accountGroupId"].as_str(),
+            data.get("statementClosingDay").is_some(),
+            data["statementClosingDay"].as_i64(),
+            data.get("paymentDueDay").is_some(),
+            data["paymentDueDay"].as_i64(),
+            data.get("minimumPayment").is_some(),
+            data["minimumPayment"].as_i64(),
+            data.get("creditLimit").is_some(),
+            data["creditLimit"].as_i64(),
+            data["accrueInterest"].as_bool(),
+            last_month_accrued(chrono::Local::now().date_naive()),
+            currency,
+            data["openingBalance"].as_i64(),
+            data["openingDate"].as_str(),
         ],
     )?;
 
+    refresh_available_balance(conn, &id)?;
+
+    emit_changed(&app, &[ACCOUNTS_CHANGED]);
+
     fetch_account(conn, &id)
 }
 
+/// Set `display_order` to each account's position in `ordered_ids`, all in one transaction
+/// so a drag-sorted list is saved with a single call
 #[tauri::command]
-pub fn delete_account(id: String, db: State<'_, Mutex<Database>>) -> Result<()> {
+pub fn reorder_accounts(
+    ordered_ids: Vec<String>,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<()> {
     let database = db.lock().unwrap();
     let conn = database.get_connection()?;
 
+    let unique: std::collections::HashSet<&String> = ordered_ids.iter().collect();
+    if unique.len() != ordered_ids.len() {
+        return Err(AppError::Validation(
+            "Account order lists an account more than once".to_string(),
+        ));
+    }
+
     let now = chrono::Utc::now().to_rfc3339();
 
-    conn.execute(
+    let tx = conn.unchecked_transaction()?;
+    for (position, id) in ordered_ids.iter().enumerate() {
+        let updated = tx.execute(
+            "UPDATE accounts SET display_order = ?1, updated_at = ?2
+             WHERE id = ?3 AND deleted_at IS NULL",
+            rusqlite::params![position as i32, now, id],
+        )?;
+        if updated == 0 {
+            return Err(AppError::NotFound(format!("Account not found: {}", id)));
+        }
+    }
+    tx.commit()?;
+
+    emit_changed(&app, &[ACCOUNTS_CHANGED]);
+
+    Ok(())
+}
+
+/// Bring an account to `new_balance` by recording the difference as a labeled adjustment
+/// transaction, rather than overwriting the balance, so the change stays in the history.
+/// The adjustment is kept out of budgets and reports.
+#[tauri::command]
+pub fn adjust_account_balance(
+    account_id: String,
+    new_balance: i64,
+    date: Option<String>,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Transaction> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let account = fetch_account(conn, &account_id)?;
+    let difference = new_balance - account.current_balance;
+    if difference == 0 {
+        return Err(AppError::Validation(
+            "The account is already at that balance".to_string(),
+        ));
+    }
+    let date = date.unwrap_or_else(|| chrono::Local::now().format("%Y-%m-%d").to_string());
+    let cents = |amount: i64| format!("{:.2}", amount as f64 / 100.0);
+
+    let transaction_id = insert_transaction(
+        conn,
+        &serde_json::json!({
+            "accountId": account_id,
+            "date": date,
+            "amount": difference,
+            "payee": "Balance Adjustment",
+            "notes": format!(
+                "Adjusted balance from {} to {}",
+                cents(account.current_balance),
+                cents(new_balance)
+            ),
+            "transactionType": BALANCE_ADJUSTMENT_TYPE,
+            "excludeFromBudget": true,
+        }),
+    )?;
+
+    emit_changed(&app, &[TRANSACTIONS_CHANGED, ACCOUNTS_CHANGED]);
+
+    Ok(conn.query_row(
+        &format!(
+            "SELECT {} FROM transactions WHERE id = ?1",
+            TRANSACTION_COLUMNS
+        ),
+        [&transaction_id],
+        transaction_from_row,
+    )?)
+}
+
+/// Move an account and, as `result.transactions` says, its transactions to the trash in one
+/// transaction
+fn trash_account(
+    conn: &Connection,
+    id: &str,
+    result: &AccountDeleteResult,
+    reassign_to: Option<&Account>,
+    now: &str,
+) -> Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    match result.transactions.as_str() {
+        // Deleted along with the account, so restoring its transactions from the trash
+        // puts their amounts back on its balance as usual
+        "delete" => {
+            for (transaction_id, _) in live_account_transactions(&tx, id)? {
+                soft_delete_transaction(&tx, &transaction_id, now)?;
+            }
+        }
+        "reassign" => {
+            if let Some(target) = reassign_to {
+                ensure_account_transactions_movable(&tx, id, &target.id)?;
+                tx.execute(
+                    "UPDATE transactions SET account_id = ?1, updated_at = ?2
+                     WHERE account_id = ?3 AND deleted_at IS NULL",
+                    [target.id.as_str(), now, id],
+                )?;
+                tx.execute(
+                    "UPDATE accounts SET current_balance = current_balance + ?1, updated_at = ?2
+                     WHERE id = ?3",
+                    rusqlite::params![result.transaction_total, now, target.id],
+                )?;
+                tx.execute(
+                    "UPDATE accounts SET current_balance = current_balance - ?1 WHERE id = ?2",
+                    rusqlite::params![result.transaction_total, id],
+                )?;
+                refresh_available_balance(&tx, &target.id)?;
+            }
+        }
+        _ => {}
+    }
+    tx.execute(
         "UPDATE accounts SET deleted_at = ?1 WHERE id = ?2",
-        [&now, &id],
+        [now, id],
     )?;
+    deactivate_account_contribution_schedules(&tx, id, now)?;
+    tx.commit()?;
 
     Ok(())
 }
+
+/// Move an account to the trash. Its transactions stay attached to it by default
+/// (`transactions` "keep"); "delete" moves them to the trash as well, and "reassign" moves
+/// them to `reassign_to_account_id`, carrying their amounts over to its balance. A dry run
+/// reports how many transactions would be affected without changing anything. Transactions
+/// locked by a closed month or a closing statement can't be deleted or moved, so the account
+/// is then left as it is.
+#[tauri::command]
+pub fn delete_account(
+    id: String,
+    transactions: Option<String>,
+    reassign_to_account_id: Option<String>,
+    dry_run: Option<bool>,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<AccountDeleteResult> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let account = fetch_account(conn, &id)?;
+    let mode = transactions.unwrap_or_else(|| "keep".to_string());
+    if !ACCOUNT_DELETE_TRANSACTIONS.contains(&mode.as_str()) {
+        return Err(AppError::Validation(format!(
+            "Unknown option for the account's transactions: {}",
+            mode
+        )));
+    }
+    let reassign_to = match (mode.as_str(), reassign_to_account_id) {
+        ("reassign", Some(target_id)) => {
+            let target = fetch_account(conn, &target_id)?;
+            if target.id == account.id {
+                return Err(AppError::Validation(
+                    "Reassign the transactions to a different account".to_string(),
+                ));
+            }
+            if target.currency != account.currency {
+                return Err(AppError::Validation(format!(
+                    "{} is in {}, not {}",
+                    target.name, target.currency, account.currency
+                )));
+            }
+            Some(target)
+        }
+        ("reassign", None) => {
+            return Err(AppError::Validation(
+                "Choose the account to reassign the transactions to".to_string(),
+            ))
+        }
+        _ => None,
+    };
+
+    let (transaction_count, transaction_total): (i64, i64) = conn.query_row(
+        "SELECT COUNT(*), COALESCE(SUM(amount), 0) FROM transactions
+         WHERE account_id = ?1 AND deleted_at IS NULL",
+        [&id],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+    let result = AccountDeleteResult {
+        dry_run: dry_run.unwrap_or(false),
+        transactions: mode,
+        transaction_count,
+        transaction_total,
+        reassign_to_account_name: reassign_to.as_ref().map(|t| t.name.clone()),
+    };
+    if result.dry_run {
+        return Ok(result);
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    trash_account(conn, &id, &result, reassign_to.as_ref(), &now)?;
+
+    emit_changed(
+        &app,
+        &[TRANSACTIONS_CHANGED, ACCOUNTS_CHANGED, GOALS_CHANGED],
+    );
+
+    Ok(result)
+}
+
+#[tauri::command]
+pub fn add_account_alias(
+    account_id: String,
+    alias: String,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Account> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    fetch_account(conn, &account_id)?;
+    let alias = alias.trim();
+    if alias.is_empty() {
+        return Err(AppError::Validation("Alias is required".to_string()));
+    }
+
+    let other_account: Option<String> = conn
+        .query_row(
+            "SELECT name FROM accounts
+             WHERE id != ?2 AND deleted_at IS NULL
+               AND (name = ?1 COLLATE NOCASE
+                    OR id IN (SELECT account_id FROM account_aliases WHERE alias = ?1))",
+            [alias, &account_id],
+            |row| row.get(0),
+        )
+        .optional()?;
+
+    if let Some(name) = other_account {
+        return Err(AppError::Validation(format!(
+            "'{}' already refers to the account '{}'",
+            alias, name
+        )));
+    }
+
+    conn.execute(
+        "INSERT OR REPLACE INTO account_aliases (id, account_id, alias, created_at)
+         VALUES (?1, ?2, ?3, ?4)",
+        rusqlite::params![
+            Uuid::new_v4().to_string(),
+            account_id,
+            alias,
+            chrono::Utc::now().to_rfc3339(),
+        ],
+    )?;
+
+    emit_changed(&app, &[ACCOUNTS_CHANGED]);
+
+    fetch_account(conn, &account_id)
+}
+
+#[tauri::command]
+pub fn remove_account_alias(
+    account_id: String,
+    alias: String,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Account> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    conn.execute(
+        "DELETE FROM account_aliases WHERE account_id = ?1 AND alias = ?2",
+        [&account_id, &alias],
+    )?;
+
+    emit_changed(&app, &[ACCOUNTS_CHANGED]);
+
+    fetch_account(conn, &account_id)
+}
+
+#[tauri::command]
+pub fn list_deleted_accounts(db: State<'_, Mutex<Database>>) -> Result<Vec<Account>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM accounts WHERE deleted_at IS NOT NULL ORDER BY deleted_at DESC",
+        account_columns()
+    ))?;
+
+    let accounts = stmt
+        .query_map([], account_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(accounts)
+}
+
+/// Bring accounts back from the trash in one transaction. Transactions kept with an account
+/// come back with it and its stored balance is still right for them; transactions moved to
+/// the trash along with it (deleted at the same moment) are restored as well, their amounts
+/// going back on its balance.
+fn restore_account_rows(conn: &Connection, ids: &[String], now: &str) -> Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    for id in ids {
+        let deleted_at: Option<String> = tx
+            .query_row(
+                "SELECT deleted_at FROM accounts WHERE id = ?1 AND deleted_at IS NOT NULL",
+                [id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        let Some(deleted_at) = deleted_at else {
+            continue;
+        };
+        tx.execute(
+            "UPDATE accounts SET deleted_at = NULL, updated_at = ?1 WHERE id = ?2",
+            [now, id],
+        )?;
+
+        let trashed_with_account: Vec<String> = tx
+            .prepare("SELECT id FROM transactions WHERE account_id = ?1 AND deleted_at = ?2")?
+            .query_map([id, &deleted_at], |row| row.get(0))?
+            .collect::<rusqlite::Result<_>>()?;
+        for transaction_id in &trashed_with_account {
+            restore_transaction(&tx, transaction_id, now)?;
+        }
+    }
+    tx.commit()?;
+
+    Ok(())
+}
+
+/// Bring accounts back from the trash, as `restore_account_rows` describes
+#[tauri::command]
+pub fn restore_accounts(
+    ids: Vec<String>,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<()> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let now = chrono::Utc::now().to_rfc3339();
+    restore_account_rows(conn, &ids, &now)?;
+
+    emit_changed(&app, &[TRANSACTIONS_CHANGED, ACCOUNTS_CHANGED]);
+
+    Ok(())
+}
+
+/// Move `source_id`'s holdings to `target_id`. A security the target already holds is folded
+/// into its holding, adding up the quantity and cost basis, rather than held twice.
+/// Returns how many of the source's holdings were moved or folded.
+fn merge_holdings(conn: &Connection, source_id: &str, target_id: &str, now: &str) -> Result<i64> {
+    let mut stmt = conn.prepare(
+        "SELECT id, security_id, quantity, cost_basis, acquisition_date FROM holdings
+         WHERE account_id = ?1",
+    )?;
+    let holdings = stmt
+        .query_map([source_id], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, f64>(2)?,
+                row.get::<_, Option<i64>>(3)?,
+                row.get::<_, Option<String>>(4)?,
+            ))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    for (id, security_id, quantity, cost_basis, acquisition_date) in &holdings {
+        let existing: Option<String> = conn
+            .query_row(
+                "SELECT id FROM holdings WHERE account_id = ?1 AND security_id = ?2
+                 ORDER BY created_at LIMIT 1",
+                [target_id, security_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        match existing {
+            Some(existing_id) => {
+                conn.execute(
+                    "UPDATE holdings SET
+                        quantity = quantity + ?1,
+                        cost_basis = CASE
+                            WHEN cost_basis IS NULL THEN ?2
+                            WHEN ?2 IS NULL THEN cost_basis
+                            ELSE cost_basis + ?2
+                        END,
+                        acquisition_date = CASE
+                            WHEN acquisition_date IS NULL OR ?3 < acquisition_date THEN ?3
+                            ELSE acquisition_date
+                        END,
+                        updated_at = ?4
+                     WHERE id = ?5",
+                    rusqlite::params![quantity, cost_basis, acquisition_date, now, existing_id],
+                )?;
+                conn.execute("DELETE FROM holdings WHERE id = ?1", [id])?;
+            }
+            None => {
+                conn.execute(
+                    "UPDATE holdings SET account_id = ?1, updated_at = ?2 WHERE id = ?3",
+                    [target_id, now, id],
+                )?;
+            }
+        }
+    }
+
+    Ok(holdings.len() as i64)
+}
+
+/// Merge `source_id` into `target_id`, for when the same real account was created twice,
+/// e.g. by imports that didn't recognise it. The source's transactions, recurring items,
+/// holdings and everything else pointing at it move to the target, the source's name and
+/// aliases become the target's aliases so later imports find it, and the source is moved
+/// to the trash. The moved transactions are added to the target's balance; the source's
+/// opening balance isn't, as the target has its own. A security both accounts hold ends up
+/// as one holding. Duplicates left by overlapping imports can be found and merged
+/// afterwards. Nothing is merged while any of the source's transactions is locked.
+#[tauri::command]
+pub fn merge_accounts(
+    source_id: String,
+    target_id: String,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<AccountMergeResult> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    if source_id == target_id {
+        return Err(AppError::Validation(
+            "Can't merge an account into itself".to_string(),
+        ));
+    }
+    let source = fetch_account(conn, &source_id)?;
+    let target = fetch_account(conn, &target_id)?;
+    if source.currency != target.currency {
+        return Err(AppError::Validation(format!(
+            "{} is in {} and {} in {}. Only accounts in the same currency can be merged",
+            source.name, source.currency, target.name, target.currency
+        )));
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let tx = conn.unchecked_transaction()?;
+    ensure_account_transactions_movable(&tx, &source_id, &target_id)?;
+
+    let moved_amount: i64 = tx.query_row(
+        "SELECT COALESCE(SUM(amount), 0) FROM transactions
+         WHERE account_id = ?1 AND deleted_at IS NULL",
+        [&source_id],
+        |row| row.get(0),
+    )?;
+    let transactions_moved = tx.execute(
+        "UPDATE transactions SET account_id = ?1, updated_at = ?2 WHERE account_id = ?3",
+        [&target_id, &now, &source_id],
+    )? as i64;
+    let recurring_moved = tx.execute(
+        "UPDATE recurring_transactions SET account_id = ?1, updated_at = ?2 WHERE account_id = ?3",
+        [&target_id, &now, &source_id],
+    )? as i64;
+    let holdings_moved = merge_holdings(&tx, &source_id, &target_id, &now)?;
+    // Rows the target already has (a goal covering both debts, both accounts rounding up)
+    // are kept once
+    for (table, column) in MERGED_ACCOUNT_REFERENCES {
+        tx.execute(
+            &format!("UPDATE OR IGNORE {table} SET {column} = ?1 WHERE {column} = ?2"),
+            [&target_id, &source_id],
+        )?;
+        tx.execute(
+            &format!("DELETE FROM {table} WHERE {column} = ?1"),
+            [&source_id],
+        )?;
+    }
+    if !source.name.eq_ignore_ascii_case(&target.name) {
+        tx.execute(
+            "INSERT OR IGNORE INTO account_aliases (id, account_id, alias) VALUES (?1, ?2, ?3)",
+            rusqlite::params![Uuid::new_v4().to_string(), target_id, source.name],
+        )?;
+    }
+
+    tx.execute(
+        "UPDATE accounts SET
+            current_balance = current_balance + ?1,
+            ofx_account_id = COALESCE(ofx_account_id, ?2),
+            account_number_masked = COALESCE(account_number_masked, ?3),
+            updated_at = ?4
+         WHERE id = ?5",
+        rusqlite::params![
+            moved_amount,
+            source.ofx_account_id,
+            source.account_number_masked,
+            now,
+            target_id,
+        ],
+    )?;
+    tx.execute(
+        "UPDATE accounts SET current_balance = current_balance - ?1, ofx_account_id = NULL,
+            deleted_at = ?2, updated_at = ?2
+         WHERE id = ?3",
+        rusqlite::params![moved_amount, now, source_id],
+    )?;
+    refresh_available_balance(&tx, &target_id)?;
+    tx.commit()?;
+
+    emit_changed(
+        &app,
+        &[TRANSACTIONS_CHANGED, ACCOUNTS_CHANGED, INVESTMENTS_CHANGED],
+    );
+
+    Ok(AccountMergeResult {
+        account: fetch_account(conn, &target_id)?,
+        transactions_moved,
+        recurring_moved,
+        holdings_moved,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_account_number_matches() {
+        assert!(account_number_matches("****1234", "1234"));
+        assert!(account_number_matches("****1234", "Account 000981234"));
+        assert!(account_number_matches("000981234", "x1234"));
+        assert!(!account_number_matches("****1234", "5678"));
+        assert!(!account_number_matches("****1234", "234"));
+        assert!(!account_number_matches("", "1234"));
+    }
+
+    #[test]
+    fn test_asset_liability_totals() {
+        let totals = asset_liability_totals([
+            ("checking", 250000),
+            ("savings", -1000),
+            ("credit_card", -40000),
+            ("loan", -900000),
+            ("other", 5000),
+        ]);
+        assert_eq!(totals, (249000, 940000));
+    }
+
+    #[test]
+    fn test_delete_account_keeps_locked_transactions() {
+        let conn = Database::in_memory();
+        for (id, name) in [("old", "Old checking"), ("new", "New checking")] {
+            conn.execute(
+                "INSERT INTO accounts (id, name, account_type, current_balance, opening_balance)
+                 VALUES (?1, ?2, 'checking', 0, 0)",
+                [id, name],
+            )
+            .unwrap();
+        }
+        insert_transaction(
+            &conn,
+            &serde_json::json!({
+                "accountId": "old",
+                "date": "2024-03-01",
+                "amount": -2500,
+                "payee": "Grocer",
+            }),
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO account_statements (id, account_id, statement_date, balance)
+             VALUES ('stmt', 'old', '2024-03-31', -2500)",
+            [],
+        )
+        .unwrap();
+        let account = fetch_account(&conn, "old").unwrap();
+        let target = fetch_account(&conn, "new").unwrap();
+        let result = |mode: &str| AccountDeleteResult {
+            dry_run: false,
+            transactions: mode.to_string(),
+            transaction_count: 1,
+            transaction_total: -2500,
+            reassign_to_account_name: Some(target.name.clone()),
+        };
+        let now = "2024-04-01T00:00:00+00:00";
+
+        for (mode, reassign_to) in [("delete", None), ("reassign", Some(&target))] {
+            let err = trash_account(&conn, "old", &result(mode), reassign_to, now).unwrap_err();
+            assert!(matches!(err, AppError::Validation(_)));
+        }
+        let account_after = fetch_account(&conn, "old").unwrap();
+        assert_eq!(account_after.current_balance, account.current_balance);
+        assert_eq!(fetch_account(&conn, "new").unwrap().current_balance, 0);
+        assert_eq!(live_account_transactions(&conn, "old").unwrap().len(), 1);
+
+        // Once the statement is unlocked the transactions go to the trash with the account
+        conn.execute("UPDATE account_statements SET is_locked = 0", [])
+            .unwrap();
+        trash_account(&conn, "old", &result("delete"), None, now).unwrap();
+        assert!(live_account_transactions(&conn, "old").unwrap().is_empty());
+        assert!(fetch_account(&conn, "old").is_err());
+
+        // Restoring the account brings back the transactions trashed with it
+        restore_account_rows(&conn, &["old".to_string()], "2024-04-02T00:00:00+00:00").unwrap();
+        assert_eq!(live_account_transactions(&conn, "old").unwrap().len(), 1);
+        assert_eq!(
+            fetch_account(&conn, "old").unwrap().current_balance,
+            account.current_balance
+        );
+    }
+
+    #[test]
+    fn test_merge_holdings_folds_shared_securities() {
+        let conn = Database::in_memory();
+        conn.execute_batch(
+            "INSERT INTO securities (id, symbol) VALUES ('vti', 'VTI'), ('bnd', 'BND');
+             INSERT INTO holdings (id, account_id, security_id, quantity, cost_basis, acquisition_date)
+             VALUES ('src_vti', 'old', 'vti', 2.5, 50000, '2020-01-15'),
+                    ('src_bnd', 'old', 'bnd', 10.0, NULL, NULL),
+                    ('dst_vti', 'new', 'vti', 4.0, 90000, '2021-06-01');",
+        )
+        .unwrap();
+
+        let moved = merge_holdings(&conn, "old", "new", "2024-04-01T00:00:00+00:00").unwrap();
+        assert_eq!(moved, 2);
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT security_id, quantity, cost_basis, acquisition_date FROM holdings
+                 WHERE account_id = 'new' ORDER BY security_id",
+            )
+            .unwrap();
+        let holdings: Vec<(String, f64, Option<i64>, Option<String>)> = stmt
+            .query_map([], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+            })
+            .unwrap()
+            .map(|r| r.unwrap())
+            .collect();
+        assert_eq!(
+            holdings,
+            vec![
+                ("bnd".to_string(), 10.0, None, None),
+                (
+                    "vti".to_string(),
+                    6.5,
+                    Some(140000),
+                    Some("2020-01-15".to_string())
+                ),
+            ]
+        );
+        let left: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM holdings WHERE account_id = 'old'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(left, 0);
+    }
+}
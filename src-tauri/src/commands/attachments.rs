@@ -0,0 +1,142 @@
+use crate::db::Database;
+use crate::error::{AppError, Result};
+use crate::events::{emit_changed, TRANSACTIONS_CHANGED};
+use crate::models::TransactionAttachment;
+use rusqlite::Connection;
+use std::sync::Mutex;
+use tauri::{AppHandle, State};
+use uuid::Uuid;
+
+/// Largest file that can be attached to a transaction
+const MAX_ATTACHMENT_SIZE: u64 = 25 * 1024 * 1024;
+
+const ATTACHMENT_COLUMNS: &str = "id, transaction_id, file_name, size, created_at";
+
+fn attachment_from_row(row: &rusqlite::Row) -> rusqlite::Result<TransactionAttachment> {
+    Ok(TransactionAttachment {
+        id: row.get(0)?,
+        transaction_id: row.get(1)?,
+        file_name: row.get(2)?,
+        size: row.get(3)?,
+        created_at: row.get(4)?,
+    })
+}
+
+/// A transaction's attachments with their contents, oldest first
+pub(crate) fn attachment_files(
+    conn: &Connection,
+    transaction_id: &str,
+) -> Result<Vec<(String, Vec<u8>)>> {
+    let mut stmt = conn.prepare(
+        "SELECT file_name, contents FROM transaction_attachments
+         WHERE transaction_id = ?1
+         ORDER BY created_at, id",
+    )?;
+    let files = stmt
+        .query_map([transaction_id], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(files)
+}
+
+/// Attach the file at `path` (a receipt, say) to a transaction. The file is copied into
+/// the database, so it can be moved or deleted afterwards.
+#[tauri::command]
+pub fn add_transaction_attachment(
+    transaction_id: String,
+    path: String,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<TransactionAttachment> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let exists: bool = conn.query_row(
+        "SELECT COUNT(*) > 0 FROM transactions WHERE id = ?1 AND deleted_at IS NULL",
+        [&transaction_id],
+        |row| row.get(0),
+    )?;
+    if !exists {
+        return Err(AppError::NotFound("Transaction not found".to_string()));
+    }
+
+    let file = std::path::Path::new(&path);
+    let file_name = file
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .ok_or_else(|| AppError::Validation("Attachment path has no file name".to_string()))?;
+    if std::fs::metadata(file)?.len() > MAX_ATTACHMENT_SIZE {
+        return Err(AppError::Validation(format!(
+            "Attachments can be at most {} MB",
+            MAX_ATTACHMENT_SIZE / (1024 * 1024)
+        )));
+    }
+    let contents = std::fs::read(file)?;
+
+    let id = Uuid::new_v4().to_string();
+    conn.execute(
+        "INSERT INTO transaction_attachments (id, transaction_id, file_name, size, contents)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        rusqlite::params![
+            id,
+            transaction_id,
+            file_name,
+            contents.len() as i64,
+            contents
+        ],
+    )?;
+
+    emit_changed(&app, &[TRANSACTIONS_CHANGED]);
+
+    conn.query_row(
+        &format!(
+            "SELECT {} FROM transaction_attachments WHERE id = ?1",
+            ATTACHMENT_COLUMNS
+        ),
+        [&id],
+        attachment_from_row,
+    )
+    .map_err(|e| e.into())
+}
+
+/// A transaction's attachments, without their contents
+#[tauri::command]
+pub fn list_transaction_attachments(
+    transaction_id: String,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<TransactionAttachment>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM transaction_attachments
+         WHERE transaction_id = ?1
+         ORDER BY created_at, id",
+        ATTACHMENT_COLUMNS
+    ))?;
+    let attachments = stmt
+        .query_map([&transaction_id], attachment_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(attachments)
+}
+
+#[tauri::command]
+pub fn delete_transaction_attachment(
+    id: String,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<()> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let deleted = conn.execute("DELETE FROM transaction_attachments WHERE id = ?1", [&id])?;
+    if deleted == 0 {
+        return Err(AppError::NotFound("Attachment not found".to_string()));
+    }
+
+    emit_changed(&app, &[TRANSACTIONS_CHANGED]);
+
+    Ok(())
+}
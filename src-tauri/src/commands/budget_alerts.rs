@@ -0,0 +1,176 @@
+use crate::commands::budgets::{
+    budget_from_row, budget_month_amount, budget_rollover, budget_spending, BUDGET_COLUMNS,
+};
+use crate::commands::forecast::{month_bounds, month_index};
+use crate::db::Database;
+use crate::error::Result;
+use crate::events::{emit_changed, BUDGETS_CHANGED, BUDGET_ALERT};
+use crate::models::Budget;
+use chrono::NaiveDate;
+use rusqlite::Connection;
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, State};
+use uuid::Uuid;
+
+/// A budget's spending reaching one of its alert thresholds in a month
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetAlert {
+    pub id: String,
+    pub budget_id: String,
+    pub category_id: String,
+    pub category_name: String,
+    pub month: String,
+    /// Percentage of the budget that was reached
+    pub threshold: u32,
+    /// Spending and the month's budget (including anything rolled over) when it was raised
+    pub spent: i64,
+    pub budgeted: i64,
+    pub created_at: String,
+    pub dismissed_at: Option<String>,
+}
+
+const BUDGET_ALERT_COLUMNS: &str = "a.id, a.budget_id, b.category_id, c.name, a.month,
+    a.threshold, a.spent, a.budgeted, a.created_at, a.dismissed_at";
+
+fn budget_alert_from_row(row: &rusqlite::Row) -> rusqlite::Result<BudgetAlert> {
+    Ok(BudgetAlert {
+        id: row.get(0)?,
+        budget_id: row.get(1)?,
+        category_id: row.get(2)?,
+        category_name: row.get(3)?,
+        month: row.get(4)?,
+        threshold: row.get(5)?,
+        spent: row.get(6)?,
+        budgeted: row.get(7)?,
+        created_at: row.get(8)?,
+        dismissed_at: row.get(9)?,
+    })
+}
+
+fn fetch_budget_alerts(
+    conn: &Connection,
+    filter: &str,
+    ids: &[String],
+) -> Result<Vec<BudgetAlert>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM budget_alerts a
+         JOIN budgets b ON b.id = a.budget_id
+         JOIN categories c ON c.id = b.category_id
+         WHERE {}
+         ORDER BY a.created_at DESC, a.threshold DESC",
+        BUDGET_ALERT_COLUMNS, filter
+    ))?;
+    let alerts = stmt
+        .query_map(rusqlite::params_from_iter(ids), budget_alert_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(alerts)
+}
+
+/// Record an alert for every threshold a spending budget has reached in the month of
+/// `today` that hasn't been alerted on yet. Returns the new alerts.
+pub(crate) fn check_budget_alerts(conn: &Connection, today: NaiveDate) -> Result<Vec<BudgetAlert>> {
+    let index = month_index(today);
+    let month_start = month_bounds(index).0.format("%Y-%m-%d").to_string();
+    let after_month = month_bounds(index + 1).0.format("%Y-%m-%d").to_string();
+    let month = today.format("%Y-%m").to_string();
+    let now = chrono::Utc::now().to_rfc3339();
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM budgets
+         WHERE alert_thresholds IS NOT NULL
+           AND is_watch_only = 0
+           AND category_id IN (
+               SELECT id FROM categories WHERE deleted_at IS NULL AND category_type != 'income'
+           )",
+        BUDGET_COLUMNS
+    ))?;
+    let budgets: Vec<Budget> = stmt
+        .query_map([], budget_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let mut raised = Vec::new();
+    for budget in &budgets {
+        let budgeted = budget_month_amount(conn, budget, &month_start)?
+            + budget_rollover(conn, &budget.id, &month_start)?;
+        if budgeted <= 0 {
+            continue;
+        }
+        let spent = budget_spending(conn, budget, &month_start, &after_month);
+
+        for threshold in &budget.alert_thresholds {
+            if spent * 100 < budgeted * *threshold as i64 {
+                continue;
+            }
+            let id = Uuid::new_v4().to_string();
+            let inserted = conn.execute(
+                "INSERT OR IGNORE INTO budget_alerts (id, budget_id, month, threshold, spent,
+                        budgeted, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                rusqlite::params![id, budget.id, month, threshold, spent, budgeted, now],
+            )?;
+            if inserted > 0 {
+                raised.push(id);
+            }
+        }
+    }
+
+    if raised.is_empty() {
+        return Ok(Vec::new());
+    }
+    let placeholders = vec!["?"; raised.len()].join(", ");
+    fetch_budget_alerts(conn, &format!("a.id IN ({})", placeholders), &raised)
+}
+
+/// Check budget alerts after a change to transactions and notify the frontend of any new
+/// ones. Like change events, a failed check never fails the command that made the change.
+pub(crate) fn raise_budget_alerts(app: &AppHandle, conn: &Connection) {
+    let today = chrono::Local::now().date_naive();
+    if let Ok(alerts) = check_budget_alerts(conn, today) {
+        if !alerts.is_empty() {
+            let _ = app.emit(BUDGET_ALERT, alerts);
+        }
+    }
+}
+
+/// Raised budget alerts, newest first. Dismissed ones are left out unless asked for.
+#[tauri::command]
+pub fn list_budget_alerts(
+    include_dismissed: Option<bool>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<BudgetAlert>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let filter = if include_dismissed.unwrap_or(false) {
+        "1 = 1"
+    } else {
+        "a.dismissed_at IS NULL"
+    };
+    fetch_budget_alerts(conn, filter, &[])
+}
+
+#[tauri::command]
+pub fn dismiss_budget_alerts(
+    ids: Vec<String>,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<()> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let now = chrono::Utc::now().to_rfc3339();
+    for id in &ids {
+        conn.execute(
+            "UPDATE budget_alerts SET dismissed_at = ?1 WHERE id = ?2 AND dismissed_at IS NULL",
+            [&now, id],
+        )?;
+    }
+
+    emit_changed(&app, &[BUDGETS_CHANGED]);
+
+    Ok(())
+}
@@ -0,0 +1,240 @@
+use crate::db::Database;
+use crate::error::{AppError, Result};
+use crate::events::{emit_changed, BUDGETS_CHANGED};
+use crate::models::BudgetGroup;
+use rusqlite::{Connection, OptionalExtension};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, State};
+use uuid::Uuid;
+
+/// Every budget group with its member categories, by name. Deleted categories are left out.
+pub(crate) fn load_budget_groups(conn: &Connection) -> Result<Vec<BudgetGroup>> {
+    let mut stmt = conn.prepare(
+        "SELECT g.group_id, g.category_id FROM budget_group_categories g
+         JOIN categories c ON c.id = g.category_id
+         WHERE c.deleted_at IS NULL
+         ORDER BY c.display_order, c.name",
+    )?;
+    let mut members: HashMap<String, Vec<String>> = HashMap::new();
+    for (group_id, category_id) in stmt
+        .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get(1)?)))?
+        .filter_map(|r| r.ok())
+    {
+        members.entry(group_id).or_default().push(category_id);
+    }
+
+    let mut stmt = conn.prepare(
+        "SELECT id, name, amount, created_at, updated_at FROM budget_groups ORDER BY name",
+    )?;
+    let groups = stmt
+        .query_map([], |row| {
+            let id: String = row.get(0)?;
+            Ok(BudgetGroup {
+                category_ids: members.remove(&id).unwrap_or_default(),
+                id,
+                name: row.get(1)?,
+                amount: row.get(2)?,
+                created_at: row.get(3)?,
+                updated_at: row.get(4)?,
+            })
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(groups)
+}
+
+fn fetch_budget_group(conn: &Connection, id: &str) -> Result<BudgetGroup> {
+    load_budget_groups(conn)?
+        .into_iter()
+        .find(|g| g.id == id)
+        .ok_or_else(|| AppError::NotFound("Budget group not found".to_string()))
+}
+
+/// Category ids from command input. Each must be an expense category that isn't already
+/// in a different group.
+fn input_group_categories(
+    conn: &Connection,
+    data: &serde_json::Value,
+    group_id: Option<&str>,
+) -> Result<Vec<String>> {
+    let ids: Vec<String> = data["categoryIds"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|v| v.as_str().map(String::from))
+        .collect();
+    if ids.is_empty() {
+        return Err(AppError::Validation(
+            "A budget group needs at least one category".to_string(),
+        ));
+    }
+
+    for id in &ids {
+        let category: Option<(String, String)> = conn
+            .query_row(
+                "SELECT name, category_type FROM categories WHERE id = ?1 AND deleted_at IS NULL",
+                [id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+        let Some((name, category_type)) = category else {
+            return Err(AppError::NotFound(format!("Category {} not found", id)));
+        };
+        if category_type == "income" {
+            return Err(AppError::Validation(format!(
+                "{} is an income category and can't be in a budget group",
+                name
+            )));
+        }
+
+        let other_group: Option<String> = conn
+            .query_row(
+                "SELECT bg.name FROM budget_group_categories g
+                 JOIN budget_groups bg ON bg.id = g.group_id
+                 WHERE g.category_id = ?1 AND g.group_id != COALESCE(?2, '')",
+                rusqlite::params![id, group_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if let Some(other) = other_group {
+            return Err(AppError::Validation(format!(
+                "{} is already in the budget group {}",
+                name, other
+            )));
+        }
+    }
+
+    Ok(ids)
+}
+
+fn set_group_categories(conn: &Connection, group_id: &str, category_ids: &[String]) -> Result<()> {
+    conn.execute(
+        "DELETE FROM budget_group_categories WHERE group_id = ?1",
+        [group_id],
+    )?;
+    for category_id in category_ids {
+        conn.execute(
+            "INSERT OR REPLACE INTO budget_group_categories (category_id, group_id)
+             VALUES (?1, ?2)",
+            [category_id, group_id],
+        )?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn list_budget_groups(db: State<'_, Mutex<Database>>) -> Result<Vec<BudgetGroup>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    load_budget_groups(conn)
+}
+
+#[tauri::command]
+pub fn create_budget_group(
+    data: serde_json::Value,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<BudgetGroup> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let name = data["name"].as_str().unwrap_or("").trim();
+    if name.is_empty() {
+        return Err(AppError::Validation(
+            "Budget group name is required".to_string(),
+        ));
+    }
+    let amount = data["amount"].as_i64().unwrap_or(0);
+    if amount < 0 {
+        return Err(AppError::Validation(
+            "Budget amounts can't be negative".to_string(),
+        ));
+    }
+    let category_ids = input_group_categories(conn, &data, None)?;
+
+    let id = Uuid::new_v4().to_string();
+    let now = chrono::Utc::now().to_rfc3339();
+
+    conn.execute(
+        "INSERT INTO budget_groups (id, name, amount, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?4)",
+        rusqlite::params![id, name, amount, now],
+    )?;
+    set_group_categories(conn, &id, &category_ids)?;
+
+    emit_changed(&app, &[BUDGETS_CHANGED]);
+
+    fetch_budget_group(conn, &id)
+}
+
+/// Update a group's name or amount; `categoryIds`, when given, replaces its categories
+#[tauri::command]
+pub fn update_budget_group(
+    id: String,
+    data: serde_json::Value,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<BudgetGroup> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    fetch_budget_group(conn, &id)?;
+
+    if data["name"].as_str().is_some_and(|n| n.trim().is_empty()) {
+        return Err(AppError::Validation(
+            "Budget group name is required".to_string(),
+        ));
+    }
+    if data["amount"].as_i64().is_some_and(|a| a < 0) {
+        return Err(AppError::Validation(
+            "Budget amounts can't be negative".to_string(),
+        ));
+    }
+    if !data["categoryIds"].is_null() {
+        let category_ids = input_group_categories(conn, &data, Some(&id))?;
+        set_group_categories(conn, &id, &category_ids)?;
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+
+    conn.execute(
+        "UPDATE budget_groups SET
+            name = COALESCE(?1, name),
+            amount = COALESCE(?2, amount),
+            updated_at = ?3
+         WHERE id = ?4",
+        rusqlite::params![
+            data["name"].as_str().map(str::trim),
+            data["amount"].as_i64(),
+            now,
+            id,
+        ],
+    )?;
+
+    emit_changed(&app, &[BUDGETS_CHANGED]);
+
+    fetch_budget_group(conn, &id)
+}
+
+#[tauri::command]
+pub fn delete_budget_group(
+    id: String,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<()> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    conn.execute(
+        "DELETE FROM budget_group_categories WHERE group_id = ?1",
+        [&id],
+    )?;
+    conn.execute("DELETE FROM budget_groups WHERE id = ?1", [&id])?;
+
+    emit_changed(&app, &[BUDGETS_CHANGED]);
+
+    Ok(())
+}
@@ -1,8 +1,23 @@
+use crate::commands::budget_groups::load_budget_groups;
+use crate::commands::forecast::{month_bounds, month_index, monthly_budget_amount};
+use crate::commands::fx::transaction_base_amount;
+use crate::commands::month_close::parse_month;
+use crate::commands::recurring::{
+    is_recurring_bill_paid, occurrences_between, recurring_from_row, RECURRING_COLUMNS,
+};
+use crate::commands::reports::{
+    reportable_transaction_filter, WeekSettings, BUDGET_EXCLUSION_FILTER,
+};
 use crate::db::Database;
 use crate::error::{AppError, Result};
-use crate::models::{Budget, Category};
+use crate::events::{emit_changed, BUDGETS_CHANGED};
+use crate::export::format_amount;
+use crate::models::{Budget, BudgetGroup, Category, RecurringTransaction};
+use chrono::{Datelike, Duration, NaiveDate};
+use rusqlite::{Connection, OptionalExtension};
+use std::collections::{HashMap, HashSet};
 use std::sync::Mutex;
-use tauri::State;
+use tauri::{AppHandle, State};
 use uuid::Uuid;
 use serde::Serialize;
 
@@ -11,8 +26,625 @@ use serde::Serialize;
 pub struct BudgetSummary {
     pub budget: Budget,
     pub category: Category,
+    /// The budget prorated to one month, what `spent` and `remaining` are measured against
+    pub monthly_amount: i64,
     pub spent: i64,
+    /// Unspent budget carried in when the previous month was closed
+    pub rolled_over: i64,
     pub remaining: i64,
+    /// Whether `monthly_amount` was set for this month rather than taken from the budget
+    pub is_month_amount: bool,
+    /// The budget's own period around the month (today's, for the current month), with
+    /// spending over the whole period; the same as the month for monthly budgets
+    pub period_start: String,
+    pub period_end: String,
+    pub period_spent: i64,
+    pub period_remaining: i64,
+    /// Spending in each subcategory, when the budget includes them; the rest of `spent`
+    /// is in the category itself
+    pub children: Vec<BudgetChildSpending>,
+    /// Where spending is headed by the end of the month; only for the current month
+    pub forecast: Option<BudgetForecast>,
+}
+
+/// End-of-month spending projected from the daily pace so far plus recurring charges
+/// still due this month
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetForecast {
+    pub projected_spent: i64,
+    /// Average daily spending so far, not counting recurring charges
+    pub daily_average: i64,
+    /// Recurring charges expected between tomorrow and the end of the month
+    pub upcoming_recurring: i64,
+    /// Projected spending minus the budget (including anything rolled over); positive
+    /// means on pace to overspend
+    pub projected_over: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetChildSpending {
+    pub category_id: String,
+    pub category_name: String,
+    /// Spending in the subcategory and any of its own subcategories
+    pub spent: i64,
+}
+
+/// Income budgets track money expected to arrive rather than a spending limit
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IncomeBudgetSummary {
+    pub budget: Budget,
+    pub category: Category,
+    pub expected: i64,
+    pub received: i64,
+    pub outstanding: i64,
+}
+
+/// Watch-only budgets report spending and how it compares with recent months, with no
+/// limit to measure against
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchedBudgetSummary {
+    pub budget: Budget,
+    pub category: Category,
+    pub spent: i64,
+    /// Average monthly spending over the previous three months
+    pub average: i64,
+    /// Spending this month minus the average; positive means spending more than usual
+    pub trend: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonthBudgetSummary {
+    pub expenses: Vec<BudgetSummary>,
+    pub income: Vec<IncomeBudgetSummary>,
+    pub watched: Vec<WatchedBudgetSummary>,
+    pub groups: Vec<BudgetGroupSummary>,
+}
+
+/// A budget group's spending across all of its categories
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetGroupSummary {
+    pub group: BudgetGroup,
+    pub spent: i64,
+    pub remaining: i64,
+    /// Spending in each of the group's categories
+    pub categories: Vec<BudgetChildSpending>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetCopyResult {
+    pub copied: i32,
+    /// Budgets that already had an amount set for the target month
+    pub skipped: i32,
+}
+
+/// Budgets created by `generate_budgets`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeneratedBudgets {
+    pub budgets: Vec<Budget>,
+    /// Average monthly income over the months looked at
+    pub monthly_income: i64,
+    /// Income left unbudgeted for savings by a 50/30/20 split; zero otherwise
+    pub savings: i64,
+}
+
+/// An amount set for one month of a budget in place of its usual amount
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetMonthAmount {
+    pub budget_id: String,
+    pub month: String,
+    pub amount: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RollingAverage {
+    pub window_months: u32,
+    pub average: i64,
+    pub difference: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryRollingAverage {
+    pub category_id: String,
+    pub current_month: String,
+    pub current_month_amount: i64,
+    pub averages: Vec<RollingAverage>,
+}
+
+/// How a parent category's monthly budget is split among its subcategories' budgets
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetAllocation {
+    pub category_id: String,
+    pub category_name: String,
+    /// Monthly budget, if the category has one
+    pub budget: Option<i64>,
+    /// Sum of the direct subcategories' monthly budgets
+    pub allocated: i64,
+    /// Budget left for spending outside the budgeted subcategories; `None` without a budget
+    pub unallocated: Option<i64>,
+    pub is_over_allocated: bool,
+    pub children: Vec<BudgetAllocation>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryHistoryMonth {
+    pub month: String,
+    pub amount: i64,
+    pub transaction_count: i64,
+    /// Monthly budget, if the category has one
+    pub budget: Option<i64>,
+    /// Amount over (positive) or under (negative) the budget
+    pub vs_budget: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryHistory {
+    pub category_id: String,
+    pub months: Vec<CategoryHistoryMonth>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetPerformanceMonth {
+    pub month: String,
+    /// What the budget allowed that month, not counting anything rolled over
+    pub budgeted: i64,
+    pub rolled_over: i64,
+    /// Spending, or money received for an income budget
+    pub actual: i64,
+    /// Actual minus budgeted; positive means over budget (or more income than expected)
+    pub difference: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetPerformance {
+    pub budget_id: String,
+    pub category_id: String,
+    pub months: Vec<BudgetPerformanceMonth>,
+    pub average_budgeted: i64,
+    pub average_actual: i64,
+    /// Months where actual exceeded what was budgeted
+    pub months_over: i32,
+}
+
+/// A category's totals for the month being summarized
+#[derive(Clone, Default)]
+struct MonthTotals {
+    spent: i64,
+    /// Spending over the watch-only trend window before the month
+    trend_spent: i64,
+    /// Income minus spending, what an income budget counts as received
+    net: i64,
+}
+
+/// Totals for a category and all of its subcategories
+fn subtree_totals(
+    category_id: &str,
+    totals: &HashMap<String, MonthTotals>,
+    subcategories: &HashMap<String, Vec<(String, String)>>,
+) -> MonthTotals {
+    let mut sum = totals.get(category_id).cloned().unwrap_or_default();
+    for (child_id, _) in subcategories.get(category_id).into_iter().flatten() {
+        let child = subtree_totals(child_id, totals, subcategories);
+        sum.spent += child.spent;
+        sum.trend_spent += child.trend_spent;
+        sum.net += child.net;
+    }
+    sum
+}
+
+/// Every category under `category_id`, at any depth, along with it
+fn subtree_ids<'a>(
+    category_id: &'a str,
+    subcategories: &'a HashMap<String, Vec<(String, String)>>,
+) -> HashSet<&'a str> {
+    let mut ids = HashSet::from([category_id]);
+    for (child_id, _) in subcategories.get(category_id).into_iter().flatten() {
+        ids.extend(subtree_ids(child_id, subcategories));
+    }
+    ids
+}
+
+/// Project a budget's spending to the end of the month containing `today`. Recurring
+/// charges in the budget's categories are counted on their due dates rather than spread
+/// through the daily average, so a bill paid early in the month doesn't inflate the pace.
+fn forecast_budget_month(
+    conn: &Connection,
+    recurring: &[RecurringTransaction],
+    categories: &HashSet<&str>,
+    spent: i64,
+    budgeted: i64,
+    today: NaiveDate,
+    (month_first, month_last): (NaiveDate, NaiveDate),
+) -> Result<BudgetForecast> {
+    let mut recurring_to_date = 0;
+    let mut upcoming_recurring = 0;
+    for bill in recurring {
+        if !bill
+            .category_id
+            .as_deref()
+            .is_some_and(|c| categories.contains(c))
+        {
+            continue;
+        }
+        for due in occurrences_between(bill, month_first, month_last) {
+            if due > today && !is_recurring_bill_paid(conn, bill, due)? {
+                upcoming_recurring += -bill.amount;
+            } else if due <= today {
+                recurring_to_date += -bill.amount;
+            }
+        }
+    }
+
+    let days_elapsed = (today - month_first).num_days() + 1;
+    let days_left = (month_last - today).num_days();
+    let daily_average = (spent - recurring_to_date).max(0) / days_elapsed;
+    let projected_spent = spent + daily_average * days_left + upcoming_recurring;
+
+    Ok(BudgetForecast {
+        projected_spent,
+        daily_average,
+        upcoming_recurring,
+        projected_over: projected_spent - budgeted,
+    })
+}
+
+/// Columns selected for a full `Budget`, in the order `budget_from_row` expects
+pub(crate) const BUDGET_COLUMNS: &str = "id, category_id, period_type, amount, rollover,
+    is_watch_only, created_at, updated_at, period_days, period_anchor, alert_thresholds,
+    include_children";
+
+/// Highest alert threshold, as a percentage of the budget
+const MAX_ALERT_THRESHOLD: u32 = 1000;
+
+const PERIOD_TYPES: &[&str] = &[
+    "weekly",
+    "biweekly",
+    "monthly",
+    "quarterly",
+    "yearly",
+    "custom",
+];
+
+/// Earlier full months a watch-only budget's spending is compared against
+const WATCH_TREND_MONTHS: i32 = 3;
+
+const DEFAULT_GENERATED_MONTHS: u32 = 3;
+const DEFAULT_GENERATED_BUDGETS: usize = 10;
+
+/// Top-level categories counted as needs by a 50/30/20 split, along with groceries and
+/// childcare under categories that are otherwise wants
+const NEEDS_CATEGORIES: &[&str] = &[
+    "cat_housing",
+    "cat_transport",
+    "cat_health",
+    "cat_bills",
+    "cat_food_groceries",
+    "cat_personal_childcare",
+];
+
+const DEFAULT_ROLLING_WINDOWS: [u32; 3] = [3, 6, 12];
+const DEFAULT_HISTORY_MONTHS: u32 = 12;
+
+pub(crate) fn budget_from_row(row: &rusqlite::Row) -> rusqlite::Result<Budget> {
+    Ok(Budget {
+        id: row.get(0)?,
+        category_id: row.get(1)?,
+        period_type: row.get(2)?,
+        amount: row.get(3)?,
+        rollover: row.get(4)?,
+        is_watch_only: row.get(5)?,
+        created_at: row.get(6)?,
+        updated_at: row.get(7)?,
+        period_days: row.get(8)?,
+        period_anchor: row.get(9)?,
+        alert_thresholds: row
+            .get::<_, Option<String>>(10)?
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default(),
+        include_children: row.get(11)?,
+    })
+}
+
+/// Spending in a category between two dates (end exclusive), in the base currency
+pub(crate) fn category_spending(conn: &Connection, category_id: &str, start: &str, end: &str) -> i64 {
+    conn.query_row(
+        &format!(
+            "SELECT COALESCE(SUM(ABS({})), 0)
+             FROM transactions t
+             WHERE category_id = ?1
+               AND date >= ?2
+               AND date < ?3
+               AND amount < 0
+               AND deleted_at IS NULL
+               AND transfer_id IS NULL
+               AND {}",
+            transaction_base_amount(),
+            BUDGET_EXCLUSION_FILTER
+        ),
+        rusqlite::params![category_id, start, end],
+        |row| row.get(0),
+    )
+    .unwrap_or(0)
+}
+
+/// Categories a budget counts, as a CTE named `budget_categories`: the category bound to
+/// `?1`, and its subcategories at any depth when `?2` is true
+const BUDGET_CATEGORIES_CTE: &str = "WITH RECURSIVE budget_categories(id) AS (
+         SELECT ?1
+         UNION
+         SELECT c.id FROM categories c
+         JOIN budget_categories bc ON c.parent_id = bc.id
+         WHERE ?2 AND c.deleted_at IS NULL
+     )";
+
+/// Spending a budget counts between two dates (end exclusive), including subcategory
+/// spending when the budget includes its children
+pub(crate) fn budget_spending(conn: &Connection, budget: &Budget, start: &str, end: &str) -> i64 {
+    if !budget.include_children {
+        return category_spending(conn, &budget.category_id, start, end);
+    }
+    conn.query_row(
+        &format!(
+            "{}
+             SELECT COALESCE(SUM(ABS({})), 0)
+             FROM transactions t
+             WHERE category_id IN (SELECT id FROM budget_categories)
+               AND date >= ?3
+               AND date < ?4
+               AND amount < 0
+               AND deleted_at IS NULL
+               AND transfer_id IS NULL
+               AND {}",
+            BUDGET_CATEGORIES_CTE,
+            transaction_base_amount(),
+            BUDGET_EXCLUSION_FILTER
+        ),
+        rusqlite::params![budget.category_id, budget.include_children, start, end],
+        |row| row.get(0),
+    )
+    .unwrap_or(0)
+}
+
+/// Amount carried into a budget's period starting on `period_start`, if one was recorded
+pub(crate) fn budget_rollover(conn: &Connection, budget_id: &str, period_start: &str) -> Result<i64> {
+    let rollover = conn
+        .query_row(
+            "SELECT rollover_amount FROM budget_periods WHERE budget_id = ?1 AND period_start = ?2",
+            [budget_id, period_start],
+            |row| row.get(0),
+        )
+        .optional()?;
+    Ok(rollover.unwrap_or(0))
+}
+
+/// Amount set for the month starting on `month_start`, if the budget has one
+fn budget_month_override(
+    conn: &Connection,
+    budget_id: &str,
+    month_start: &str,
+) -> Result<Option<i64>> {
+    let amount = conn
+        .query_row(
+            "SELECT amount_override FROM budget_periods WHERE budget_id = ?1 AND period_start = ?2",
+            [budget_id, month_start],
+            |row| row.get(0),
+        )
+        .optional()?;
+    Ok(amount.flatten())
+}
+
+/// What a budget allows in the month starting on `month_start`: the amount set for that
+/// month, or else the budget's amount prorated to a month
+pub(crate) fn budget_month_amount(
+    conn: &Connection,
+    budget: &Budget,
+    month_start: &str,
+) -> Result<i64> {
+    let month_override = budget_month_override(conn, &budget.id, month_start)?;
+    Ok(month_override.unwrap_or_else(|| {
+        monthly_budget_amount(&budget.period_type, budget.period_days, budget.amount)
+    }))
+}
+
+/// Format a month counted from year 0 (year * 12 + zero-based month) as YYYY-MM
+fn month_string(index: i32) -> String {
+    format!("{:04}-{:02}", index.div_euclid(12), index.rem_euclid(12) + 1)
+}
+
+/// First and last day of the budget period containing `date`. Weekly periods follow the
+/// week start setting; quarters and years are calendar ones.
+pub(crate) fn budget_period_bounds(
+    budget: &Budget,
+    date: NaiveDate,
+    weeks: &WeekSettings,
+) -> (NaiveDate, NaiveDate) {
+    match budget.period_type.as_str() {
+        "weekly" => {
+            let start = weeks.week_start(date);
+            (start, start + Duration::days(6))
+        }
+        "biweekly" | "custom" => {
+            let days = match budget.period_type.as_str() {
+                "biweekly" => 14,
+                _ => budget.period_days.unwrap_or(1).max(1),
+            };
+            let anchor = budget
+                .period_anchor
+                .as_deref()
+                .or(budget.created_at.get(..10))
+                .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+                .unwrap_or(date);
+            let start = anchor + Duration::days((date - anchor).num_days().div_euclid(days) * days);
+            (start, start + Duration::days(days - 1))
+        }
+        "quarterly" => {
+            let first = month_index(date) - date.month0() as i32 % 3;
+            (month_bounds(first).0, month_bounds(first + 2).1)
+        }
+        "yearly" => {
+            let first = date.year() * 12;
+            (month_bounds(first).0, month_bounds(first + 11).1)
+        }
+        _ => month_bounds(month_index(date)),
+    }
+}
+
+/// Check a budget's period type, and for custom periods their length and anchor date
+fn validate_budget_period(
+    period_type: &str,
+    period_days: Option<i64>,
+    period_anchor: Option<&str>,
+) -> Result<()> {
+    if !PERIOD_TYPES.contains(&period_type) {
+        return Err(AppError::Validation(format!(
+            "Invalid budget period: {}",
+            period_type
+        )));
+    }
+    if period_type == "custom" && !period_days.is_some_and(|d| (1..=366).contains(&d)) {
+        return Err(AppError::Validation(
+            "Custom budget periods must be between 1 and 366 days".to_string(),
+        ));
+    }
+    if let Some(anchor) = period_anchor {
+        NaiveDate::parse_from_str(anchor, "%Y-%m-%d").map_err(|_| {
+            AppError::Validation("Invalid period start date. Use YYYY-MM-DD".to_string())
+        })?;
+    }
+    Ok(())
+}
+
+/// Read a budget's `alertThresholds` input as stored JSON, sorted without repeats. `None`
+/// when the input doesn't mention thresholds; an empty list clears them.
+fn input_alert_thresholds(data: &serde_json::Value) -> Result<Option<Option<String>>> {
+    if data.get("alertThresholds").is_none() {
+        return Ok(None);
+    }
+    let mut thresholds: Vec<u32> = if data["alertThresholds"].is_null() {
+        Vec::new()
+    } else {
+        serde_json::from_value(data["alertThresholds"].clone())
+            .map_err(|e| AppError::Validation(format!("Invalid alert thresholds: {}", e)))?
+    };
+    if thresholds
+        .iter()
+        .any(|t| *t == 0 || *t > MAX_ALERT_THRESHOLD)
+    {
+        return Err(AppError::Validation(format!(
+            "Alert thresholds must be between 1% and {}%",
+            MAX_ALERT_THRESHOLD
+        )));
+    }
+    thresholds.sort_unstable();
+    thresholds.dedup();
+    Ok(Some(if thresholds.is_empty() {
+        None
+    } else {
+        Some(serde_json::to_string(&thresholds)?)
+    }))
+}
+
+/// Monthly budget amounts by category, optionally leaving one budget out. Watch-only
+/// budgets have no amount, so they aren't included.
+fn monthly_budgets(
+    conn: &Connection,
+    excluded_budget: Option<&str>,
+) -> Result<HashMap<String, i64>> {
+    let mut stmt = conn.prepare(
+        "SELECT category_id, period_type, period_days, amount FROM budgets
+         WHERE is_watch_only = 0 AND (?1 IS NULL OR id != ?1)",
+    )?;
+    let mut budgets: HashMap<String, i64> = HashMap::new();
+    for (category_id, period_type, period_days, amount) in stmt
+        .query_map([excluded_budget], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, Option<i64>>(2)?,
+                row.get::<_, i64>(3)?,
+            ))
+        })?
+        .filter_map(|r| r.ok())
+    {
+        *budgets.entry(category_id).or_default() +=
+            monthly_budget_amount(&period_type, period_days, amount);
+    }
+    Ok(budgets)
+}
+
+/// Parent ids of every live category
+fn category_parents(conn: &Connection) -> Result<HashMap<String, Option<String>>> {
+    let mut stmt = conn.prepare("SELECT id, parent_id FROM categories WHERE deleted_at IS NULL")?;
+    let parents = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(parents)
+}
+
+/// Subcategory budgets are carved out of the parent's: when a parent and its children both
+/// have budgets, the children's monthly amounts together can't exceed the parent's.
+/// Checks the saved budget both as a child (against its parent) and as a parent.
+fn validate_budget_allocation(
+    conn: &Connection,
+    budget_id: Option<&str>,
+    category_id: &str,
+    period_type: &str,
+    period_days: Option<i64>,
+    amount: i64,
+) -> Result<()> {
+    let mut budgets = monthly_budgets(conn, budget_id)?;
+    *budgets.entry(category_id.to_string()).or_default() +=
+        monthly_budget_amount(period_type, period_days, amount);
+    let parents = category_parents(conn)?;
+
+    let allocated_to = |parent_id: &str| -> i64 {
+        parents
+            .iter()
+            .filter(|(_, parent)| parent.as_deref() == Some(parent_id))
+            .filter_map(|(id, _)| budgets.get(id))
+            .sum()
+    };
+
+    if let Some(parent_id) = parents.get(category_id).cloned().flatten() {
+        if let Some(parent_budget) = budgets.get(&parent_id) {
+            let allocated = allocated_to(&parent_id);
+            if allocated > *parent_budget {
+                return Err(AppError::Validation(format!(
+                    "Subcategory budgets would total {} a month, more than the parent budget of {}",
+                    format_amount(allocated),
+                    format_amount(*parent_budget)
+                )));
+            }
+        }
+    }
+
+    let allocated = allocated_to(category_id);
+    let budget = budgets.get(category_id).copied().unwrap_or(0);
+    if allocated > budget {
+        return Err(AppError::Validation(format!(
+            "Budget of {} a month doesn't cover its subcategory budgets of {}",
+            format_amount(budget),
+            format_amount(allocated)
+        )));
+    }
+
+    Ok(())
 }
 
 #[tauri::command]
@@ -20,24 +652,13 @@ pub fn list_budgets(db: State<'_, Mutex<Database>>) -> Result<Vec<Budget>> {
     let database = db.lock().unwrap();
     let conn = database.get_connection()?;
 
-    let mut stmt = conn.prepare(
-        "SELECT id, category_id, period_type, amount, rollover, created_at, updated_at
-         FROM budgets
-         ORDER BY created_at DESC"
-    )?;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM budgets ORDER BY created_at DESC",
+        BUDGET_COLUMNS
+    ))?;
 
     let budgets = stmt
-        .query_map([], |row| {
-            Ok(Budget {
-                id: row.get(0)?,
-                category_id: row.get(1)?,
-                period_type: row.get(2)?,
-                amount: row.get(3)?,
-                rollover: row.get(4)?,
-                created_at: row.get(5)?,
-                updated_at: row.get(6)?,
-            })
-        })?
+        .query_map([], budget_from_row)?
         .filter_map(|r| r.ok())
         .collect();
 
@@ -45,7 +666,7 @@ pub fn list_budgets(db: State<'_, Mutex<Database>>) -> Result<Vec<Budget>> {
 }
 
 #[tauri::command]
-pub fn get_budget_summary(month: String, db: State<'_, Mutex<Database>>) -> Result<Vec<BudgetSummary>> {
+pub fn get_budget_summary(month: String, db: State<'_, Mutex<Database>>) -> Result<MonthBudgetSummary> {
     let database = db.lock().unwrap();
     let conn = database.get_connection()?;
 
@@ -65,172 +686,1156 @@ pub fn get_budget_summary(month: String, db: State<'_, Mutex<Database>>) -> Resu
         format!("{:04}-{:02}-01", year, month_num + 1)
     };
 
-    // Get all budgets with their categories
+    let trend_start = format!(
+        "{}-01",
+        month_string(year * 12 + month_num as i32 - 1 - WATCH_TREND_MONTHS)
+    );
+
+    // The month's totals for every category in one pass over the transactions. The trend
+    // window for watch-only budgets is the months just before this one, so it's read
+    // alongside.
+    let mut stmt = conn.prepare(&format!(
+        "SELECT category_id,
+                SUM(CASE WHEN date >= ?1 AND amount < 0 THEN -{amount} ELSE 0 END),
+                SUM(CASE WHEN date < ?1 AND amount < 0 THEN -{amount} ELSE 0 END),
+                SUM(CASE WHEN date >= ?1 THEN {amount} ELSE 0 END)
+         FROM transactions t
+         WHERE category_id IS NOT NULL
+           AND date >= ?3
+           AND date < ?2
+           AND deleted_at IS NULL
+           AND transfer_id IS NULL
+           AND {}
+         GROUP BY category_id",
+        BUDGET_EXCLUSION_FILTER,
+        amount = transaction_base_amount()
+    ))?;
+    let totals: HashMap<String, MonthTotals> = stmt
+        .query_map(
+            rusqlite::params![start_date, end_date, trend_start],
+            |row| {
+                Ok((
+                    row.get(0)?,
+                    MonthTotals {
+                        spent: row.get(1)?,
+                        trend_spent: row.get(2)?,
+                        net: row.get(3)?,
+                    },
+                ))
+            },
+        )?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    // Subcategories by parent, for budgets that include their children
     let mut stmt = conn.prepare(
-        "SELECT b.id, b.category_id, b.period_type, b.amount, b.rollover, b.created_at, b.updated_at,
-                c.id, c.name, c.parent_id, c.category_type, c.icon, c.color, c.is_system, c.display_order, c.created_at, c.updated_at
+        "SELECT id, parent_id, name FROM categories
+         WHERE parent_id IS NOT NULL AND deleted_at IS NULL
+         ORDER BY display_order, name",
+    )?;
+    let mut subcategories: HashMap<String, Vec<(String, String)>> = HashMap::new();
+    for (id, parent_id, name) in stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })?
+        .filter_map(|r| r.ok())
+    {
+        subcategories.entry(parent_id).or_default().push((id, name));
+    }
+
+    // Every budget with its category and anything recorded for the budget this month
+    let mut stmt = conn.prepare(
+        "SELECT b.id, b.category_id, b.period_type, b.amount, b.rollover, b.is_watch_only, b.created_at, b.updated_at,
+                b.period_days, b.period_anchor, b.alert_thresholds, b.include_children,
+                c.id, c.name, c.parent_id, c.category_type, c.icon, c.color, c.is_system, c.display_order, c.created_at, c.updated_at, c.due_day, c.is_archived, c.is_tax_deductible,
+                COALESCE(bp.rollover_amount, 0), bp.amount_override
          FROM budgets b
          JOIN categories c ON b.category_id = c.id
+         LEFT JOIN budget_periods bp ON bp.budget_id = b.id AND bp.period_start = ?1
          WHERE c.deleted_at IS NULL"
     )?;
 
-    let budget_categories: Vec<(Budget, Category)> = stmt
-        .query_map([], |row| {
+    let rows: Vec<(Budget, Category, i64, Option<i64>)> = stmt
+        .query_map([&start_date], |row| {
             Ok((
-                Budget {
-                    id: row.get(0)?,
-                    category_id: row.get(1)?,
-                    period_type: row.get(2)?,
-                    amount: row.get(3)?,
-                    rollover: row.get(4)?,
-                    created_at: row.get(5)?,
-                    updated_at: row.get(6)?,
-                },
+                budget_from_row(row)?,
                 Category {
-                    id: row.get(7)?,
-                    name: row.get(8)?,
-                    parent_id: row.get(9)?,
-                    category_type: row.get(10)?,
-                    icon: row.get(11)?,
-                    color: row.get(12)?,
-                    is_system: row.get(13)?,
-                    display_order: row.get(14)?,
-                    created_at: row.get(15)?,
-                    updated_at: row.get(16)?,
+                    id: row.get(12)?,
+                    name: row.get(13)?,
+                    parent_id: row.get(14)?,
+                    category_type: row.get(15)?,
+                    icon: row.get(16)?,
+                    color: row.get(17)?,
+                    is_system: row.get(18)?,
+                    display_order: row.get(19)?,
+                    created_at: row.get(20)?,
+                    updated_at: row.get(21)?,
+                    due_day: row.get(22)?,
+                    is_archived: row.get(23)?,
+                    is_tax_deductible: row.get(24)?,
                 },
+                row.get(25)?,
+                row.get(26)?,
             ))
         })?
         .filter_map(|r| r.ok())
         .collect();
 
-    let mut summaries = Vec::new();
+    // Non-monthly periods are shown around today in the current month, otherwise around
+    // the month's last day
+    let (month_first, month_last) = month_bounds(year * 12 + month_num as i32 - 1);
+    let today = chrono::Local::now().date_naive();
+    let reference_date = today.clamp(month_first, month_last);
+    let weeks = WeekSettings::read(conn)?;
 
-    for (budget, category) in budget_categories {
-        // Calculate spending for this category in the given month
-        let spent: i64 = conn.query_row(
-            "SELECT COALESCE(SUM(ABS(amount)), 0)
-             FROM transactions
-             WHERE category_id = ?1
-               AND date >= ?2
-               AND date < ?3
-               AND amount < 0
-               AND deleted_at IS NULL
-               AND transfer_id IS NULL",
-            rusqlite::params![budget.category_id, start_date, end_date],
-            |row| row.get(0),
-        ).unwrap_or(0);
+    // Spending is only forecast for the month in progress, from its recurring outflows
+    let is_current_month = (month_first..=month_last).contains(&today);
+    let recurring: Vec<RecurringTransaction> = if is_current_month {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM recurring_transactions WHERE is_active = 1 AND amount < 0",
+            RECURRING_COLUMNS
+        ))?;
+        let recurring = stmt
+            .query_map([], recurring_from_row)?
+            .filter_map(|r| r.ok())
+            .collect();
+        recurring
+    } else {
+        Vec::new()
+    };
+
+    let mut expenses = Vec::new();
+    let mut income = Vec::new();
+    let mut watched = Vec::new();
 
-        let remaining = budget.amount - spent;
+    for (budget, category, rolled_over, amount_override) in rows {
+        // Each subcategory the budget covers, with its own subcategories folded in
+        let children: Vec<BudgetChildSpending> = if budget.include_children {
+            subcategories
+                .get(&budget.category_id)
+                .into_iter()
+                .flatten()
+                .map(|(id, name)| BudgetChildSpending {
+                    category_id: id.clone(),
+                    category_name: name.clone(),
+                    spent: subtree_totals(id, &totals, &subcategories).spent,
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let month = if budget.include_children {
+            subtree_totals(&budget.category_id, &totals, &subcategories)
+        } else {
+            totals.get(&budget.category_id).cloned().unwrap_or_default()
+        };
 
-        summaries.push(BudgetSummary {
+        if budget.is_watch_only {
+            let average = month.trend_spent / WATCH_TREND_MONTHS as i64;
+
+            watched.push(WatchedBudgetSummary {
+                budget,
+                category,
+                spent: month.spent,
+                average,
+                trend: month.spent - average,
+            });
+            continue;
+        }
+
+        let monthly_amount = amount_override.unwrap_or_else(|| {
+            monthly_budget_amount(&budget.period_type, budget.period_days, budget.amount)
+        });
+
+        if category.category_type == "income" {
+            // Income categories compare what has landed against what was expected
+            let received = month.net;
+            let outstanding = (monthly_amount - received).max(0);
+
+            income.push(IncomeBudgetSummary {
+                budget,
+                category,
+                expected: monthly_amount,
+                received,
+                outstanding,
+            });
+            continue;
+        }
+
+        let spent = month.spent;
+        let remaining = monthly_amount + rolled_over - spent;
+
+        // Only periods that differ from the month need their own spending total
+        let (period_start, period_end) = budget_period_bounds(&budget, reference_date, &weeks);
+        let period_spent = if (period_start, period_end) == (month_first, month_last) {
+            spent
+        } else {
+            let after_period = (period_end + Duration::days(1)).format("%Y-%m-%d");
+            budget_spending(
+                conn,
+                &budget,
+                &period_start.format("%Y-%m-%d").to_string(),
+                &after_period.to_string(),
+            )
+        };
+        let period_remaining = budget.amount - period_spent;
+
+        let forecast = if is_current_month {
+            let categories = if budget.include_children {
+                subtree_ids(&budget.category_id, &subcategories)
+            } else {
+                HashSet::from([budget.category_id.as_str()])
+            };
+            Some(forecast_budget_month(
+                conn,
+                &recurring,
+                &categories,
+                spent,
+                monthly_amount + rolled_over,
+                today,
+                (month_first, month_last),
+            )?)
+        } else {
+            None
+        };
+
+        expenses.push(BudgetSummary {
             budget,
             category,
+            monthly_amount,
             spent,
+            rolled_over,
             remaining,
+            is_month_amount: amount_override.is_some(),
+            period_start: period_start.format("%Y-%m-%d").to_string(),
+            period_end: period_end.format("%Y-%m-%d").to_string(),
+            period_spent,
+            period_remaining,
+            children,
+            forecast,
         });
     }
 
-    Ok(summaries)
+    // Groups share one pool, so their categories' spending is added up against it
+    let mut groups = Vec::new();
+    let group_list = load_budget_groups(conn)?;
+    if !group_list.is_empty() {
+        let mut stmt = conn.prepare("SELECT id, name FROM categories WHERE deleted_at IS NULL")?;
+        let names: HashMap<String, String> = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        for group in group_list {
+            let categories: Vec<BudgetChildSpending> = group
+                .category_ids
+                .iter()
+                .map(|id| BudgetChildSpending {
+                    category_id: id.clone(),
+                    category_name: names.get(id).cloned().unwrap_or_default(),
+                    spent: totals.get(id).map_or(0, |t| t.spent),
+                })
+                .collect();
+            let spent = categories.iter().map(|c| c.spent).sum();
+
+            groups.push(BudgetGroupSummary {
+                remaining: group.amount - spent,
+                group,
+                spent,
+                categories,
+            });
+        }
+    }
+
+    Ok(MonthBudgetSummary {
+        expenses,
+        income,
+        watched,
+        groups,
+    })
 }
 
-#[tauri::command]
-pub fn create_budget(
-    data: serde_json::Value,
-    db: State<'_, Mutex<Database>>,
-) -> Result<Budget> {
-    let database = db.lock().unwrap();
-    let conn = database.get_connection()?;
+/// Insert a budget from command input, after the same checks `create_budget` makes
+fn insert_budget(conn: &Connection, data: &serde_json::Value) -> Result<Budget> {
+    let category_id = data["categoryId"].as_str().unwrap_or("");
+    let period_type = data["periodType"].as_str().unwrap_or("monthly");
+    let period_days = data["periodDays"].as_i64();
+    let period_anchor = data["periodAnchor"].as_str();
+    validate_budget_period(period_type, period_days, period_anchor)?;
+    let alert_thresholds = input_alert_thresholds(data)?.flatten();
+    let include_children = data["includeChildren"].as_bool().unwrap_or(false);
+    // Watch-only budgets have no limit
+    let is_watch_only = data["isWatchOnly"].as_bool().unwrap_or(false);
+    let amount = if is_watch_only {
+        0
+    } else {
+        data["amount"].as_i64().unwrap_or(0)
+    };
+    validate_budget_allocation(conn, None, category_id, period_type, period_days, amount)?;
 
     let id = Uuid::new_v4().to_string();
     let now = chrono::Utc::now().to_rfc3339();
 
     conn.execute(
-        "INSERT INTO budgets (id, category_id, period_type, amount, rollover, created_at, updated_at)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        "INSERT INTO budgets (id, category_id, period_type, amount, rollover, is_watch_only,
+                              created_at, updated_at, period_days, period_anchor,
+                              alert_thresholds, include_children)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
         rusqlite::params![
             id,
-            data["categoryId"].as_str().unwrap_or(""),
-            data["periodType"].as_str().unwrap_or("monthly"),
-            data["amount"].as_i64().unwrap_or(0),
-            data["rollover"].as_bool().unwrap_or(false),
+            category_id,
+            period_type,
+            amount,
+            !is_watch_only && data["rollover"].as_bool().unwrap_or(false),
+            is_watch_only,
             now,
             now,
+            (period_type == "custom").then_some(period_days).flatten(),
+            period_anchor,
+            alert_thresholds,
+            include_children,
         ],
     )?;
 
     conn.query_row(
-        "SELECT id, category_id, period_type, amount, rollover, created_at, updated_at
-         FROM budgets WHERE id = ?1",
+        &format!("SELECT {} FROM budgets WHERE id = ?1", BUDGET_COLUMNS),
         [&id],
-        |row| {
-            Ok(Budget {
-                id: row.get(0)?,
-                category_id: row.get(1)?,
-                period_type: row.get(2)?,
-                amount: row.get(3)?,
-                rollover: row.get(4)?,
-                created_at: row.get(5)?,
-                updated_at: row.get(6)?,
-            })
-        },
+        budget_from_row,
     )
     .map_err(|e| e.into())
 }
 
+#[tauri::command]
+pub fn create_budget(
+    data: serde_json::Value,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Budget> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let budget = insert_budget(conn, &data)?;
+
+    emit_changed(&app, &[BUDGETS_CHANGED]);
+
+    Ok(budget)
+}
+
 #[tauri::command]
 pub fn update_budget(
     id: String,
     data: serde_json::Value,
+    app: AppHandle,
     db: State<'_, Mutex<Database>>,
 ) -> Result<Budget> {
     let database = db.lock().unwrap();
     let conn = database.get_connection()?;
 
+    let existing = conn
+        .query_row(
+            &format!("SELECT {} FROM budgets WHERE id = ?1", BUDGET_COLUMNS),
+            [&id],
+            budget_from_row,
+        )
+        .map_err(|_| AppError::NotFound("Budget not found".to_string()))?;
+    let period_type = data["periodType"].as_str().unwrap_or(&existing.period_type);
+    let period_days = if data.get("periodDays").is_some() {
+        data["periodDays"].as_i64()
+    } else {
+        existing.period_days
+    };
+    let period_days = (period_type == "custom").then_some(period_days).flatten();
+    let period_anchor = if data.get("periodAnchor").is_some() {
+        data["periodAnchor"].as_str()
+    } else {
+        existing.period_anchor.as_deref()
+    };
+    validate_budget_period(period_type, period_days, period_anchor)?;
+    let alert_thresholds = input_alert_thresholds(&data)?;
+    let is_watch_only = data["isWatchOnly"]
+        .as_bool()
+        .unwrap_or(existing.is_watch_only);
+    let amount = if is_watch_only {
+        0
+    } else {
+        data["amount"].as_i64().unwrap_or(existing.amount)
+    };
+    validate_budget_allocation(
+        conn,
+        Some(&id),
+        data["categoryId"].as_str().unwrap_or(&existing.category_id),
+        period_type,
+        period_days,
+        amount,
+    )?;
+
     let now = chrono::Utc::now().to_rfc3339();
 
     conn.execute(
         "UPDATE budgets SET
             category_id = COALESCE(?1, category_id),
-            period_type = COALESCE(?2, period_type),
-            amount = COALESCE(?3, amount),
-            rollover = COALESCE(?4, rollover),
-            updated_at = ?5
-         WHERE id = ?6",
+            period_type = ?2,
+            amount = ?3,
+            rollover = CASE WHEN ?5 THEN 0 ELSE COALESCE(?4, rollover) END,
+            is_watch_only = ?5,
+            updated_at = ?6,
+            period_days = ?8,
+            period_anchor = ?9,
+            alert_thresholds = CASE WHEN ?10 THEN ?11 ELSE alert_thresholds END,
+            include_children = COALESCE(?12, include_children)
+         WHERE id = ?7",
         rusqlite::params![
             data["categoryId"].as_str(),
-            data["periodType"].as_str(),
-            data["amount"].as_i64(),
+            period_type,
+            amount,
             data["rollover"].as_bool(),
+            is_watch_only,
             now,
             id,
+            period_days,
+            period_anchor,
+            alert_thresholds.is_some(),
+            alert_thresholds.flatten(),
+            data["includeChildren"].as_bool(),
         ],
     )?;
 
+    emit_changed(&app, &[BUDGETS_CHANGED]);
+
     conn.query_row(
-        "SELECT id, category_id, period_type, amount, rollover, created_at, updated_at
-         FROM budgets WHERE id = ?1",
+        &format!("SELECT {} FROM budgets WHERE id = ?1", BUDGET_COLUMNS),
         [&id],
-        |row| {
-            Ok(Budget {
-                id: row.get(0)?,
-                category_id: row.get(1)?,
-                period_type: row.get(2)?,
-                amount: row.get(3)?,
-                rollover: row.get(4)?,
-                created_at: row.get(5)?,
-                updated_at: row.get(6)?,
-            })
-        },
+        budget_from_row,
     )
     .map_err(|e| e.into())
 }
 
 #[tauri::command]
-pub fn delete_budget(id: String, db: State<'_, Mutex<Database>>) -> Result<()> {
+pub fn delete_budget(
+    id: String,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<()> {
     let database = db.lock().unwrap();
     let conn = database.get_connection()?;
 
+    conn.execute("DELETE FROM budget_periods WHERE budget_id = ?1", [&id])?;
+    conn.execute("DELETE FROM budget_alerts WHERE budget_id = ?1", [&id])?;
     conn.execute("DELETE FROM budgets WHERE id = ?1", [&id])?;
 
+    emit_changed(&app, &[BUDGETS_CHANGED]);
+
+    Ok(())
+}
+
+/// Budgets of closed months keep the amounts they were closed with
+fn ensure_month_open(conn: &Connection, month: &str) -> Result<()> {
+    let is_closed: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM month_closes WHERE month = ?1)",
+        [month],
+        |row| row.get(0),
+    )?;
+    if is_closed {
+        return Err(AppError::Validation(format!(
+            "{} is closed. Reopen the month to change its budgets",
+            month
+        )));
+    }
+    Ok(())
+}
+
+/// Record (or with `None` clear) the amount set for one month of a budget
+fn set_month_override(
+    conn: &Connection,
+    budget_id: &str,
+    month_start: NaiveDate,
+    amount: Option<i64>,
+) -> Result<()> {
+    let (start, end) = month_bounds(month_index(month_start));
+    let start = start.format("%Y-%m-%d").to_string();
+
+    match amount {
+        Some(amount) => {
+            conn.execute(
+                "INSERT INTO budget_periods (id, budget_id, period_start, period_end,
+                        budgeted_amount, amount_override, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?5, ?6)
+                 ON CONFLICT(budget_id, period_start) DO UPDATE SET
+                    budgeted_amount = excluded.budgeted_amount,
+                    amount_override = excluded.amount_override",
+                rusqlite::params![
+                    Uuid::new_v4().to_string(),
+                    budget_id,
+                    start,
+                    end.format("%Y-%m-%d").to_string(),
+                    amount,
+                    chrono::Utc::now().to_rfc3339(),
+                ],
+            )?;
+        }
+        None => {
+            conn.execute(
+                "UPDATE budget_periods SET amount_override = NULL
+                 WHERE budget_id = ?1 AND period_start = ?2",
+                [budget_id, &start],
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Set a budget's amount for one month, or with no amount go back to the budget's usual
+/// amount
+#[tauri::command]
+pub fn set_budget_month_amount(
+    budget_id: String,
+    month: String,
+    amount: Option<i64>,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<()> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let month_start = parse_month(&month)?;
+    let is_watch_only: bool = conn
+        .query_row(
+            "SELECT is_watch_only FROM budgets WHERE id = ?1",
+            [&budget_id],
+            |row| row.get(0),
+        )
+        .map_err(|_| AppError::NotFound("Budget not found".to_string()))?;
+    if is_watch_only {
+        return Err(AppError::Validation(
+            "Watch-only budgets have no amount to set".to_string(),
+        ));
+    }
+    if amount.is_some_and(|a| a < 0) {
+        return Err(AppError::Validation(
+            "Budget amounts can't be negative".to_string(),
+        ));
+    }
+    ensure_month_open(conn, &month)?;
+
+    set_month_override(conn, &budget_id, month_start, amount)?;
+
+    emit_changed(&app, &[BUDGETS_CHANGED]);
+
     Ok(())
 }
+
+/// Amounts set for individual months, for one budget or all of them, by month
+#[tauri::command]
+pub fn list_budget_month_amounts(
+    budget_id: Option<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<BudgetMonthAmount>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let mut stmt = conn.prepare(
+        "SELECT budget_id, substr(period_start, 1, 7), amount_override FROM budget_periods
+         WHERE amount_override IS NOT NULL AND (?1 IS NULL OR budget_id = ?1)
+         ORDER BY period_start, budget_id",
+    )?;
+    let amounts = stmt
+        .query_map([budget_id], |row| {
+            Ok(BudgetMonthAmount {
+                budget_id: row.get(0)?,
+                month: row.get(1)?,
+                amount: row.get(2)?,
+            })
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(amounts)
+}
+
+/// Set every budget's amount for `to_month` from `from_month`: with `source` `amounts`
+/// (the default) the amounts budgeted then, with `actuals` what was actually spent, or
+/// received for income budgets. Budgets already set for `to_month` are left alone unless
+/// `overwrite` is on. Watch-only budgets have no amount and aren't copied.
+#[tauri::command]
+pub fn copy_budgets(
+    from_month: String,
+    to_month: String,
+    source: Option<String>,
+    overwrite: Option<bool>,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<BudgetCopyResult> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let from_start = parse_month(&from_month)?;
+    let to_start = parse_month(&to_month)?;
+    if from_start == to_start {
+        return Err(AppError::Validation(
+            "Budgets must be copied to a different month".to_string(),
+        ));
+    }
+    let use_actuals = match source.as_deref().unwrap_or("amounts") {
+        "amounts" => false,
+        "actuals" => true,
+        other => {
+            return Err(AppError::Validation(format!(
+                "Unknown budget copy source: {}",
+                other
+            )))
+        }
+    };
+    ensure_month_open(conn, &to_month)?;
+
+    let index = month_index(from_start);
+    let from = month_bounds(index).0.format("%Y-%m-%d").to_string();
+    let after_from = month_bounds(index + 1).0.format("%Y-%m-%d").to_string();
+    let to = to_start.format("%Y-%m-%d").to_string();
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {}, (SELECT category_type FROM categories WHERE id = budgets.category_id)
+         FROM budgets
+         WHERE is_watch_only = 0
+           AND category_id IN (SELECT id FROM categories WHERE deleted_at IS NULL)",
+        BUDGET_COLUMNS
+    ))?;
+    let budgets: Vec<(Budget, String)> = stmt
+        .query_map([], |row| Ok((budget_from_row(row)?, row.get(12)?)))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let mut result = BudgetCopyResult::default();
+
+    let tx = conn.unchecked_transaction()?;
+    for (budget, category_type) in &budgets {
+        if !overwrite.unwrap_or(false) && budget_month_override(&tx, &budget.id, &to)?.is_some() {
+            result.skipped += 1;
+            continue;
+        }
+
+        let amount = if !use_actuals {
+            budget_month_amount(&tx, budget, &from)?
+        } else if category_type == "income" {
+            tx.query_row(
+                &format!(
+                    "{}
+                     SELECT COALESCE(SUM({}), 0) FROM transactions t
+                     WHERE category_id IN (SELECT id FROM budget_categories)
+                       AND date >= ?3 AND date < ?4
+                       AND deleted_at IS NULL AND transfer_id IS NULL
+                       AND {}",
+                    BUDGET_CATEGORIES_CTE,
+                    transaction_base_amount(),
+                    BUDGET_EXCLUSION_FILTER
+                ),
+                rusqlite::params![budget.category_id, budget.include_children, from, after_from],
+                |row| row.get::<_, i64>(0),
+            )?
+            .max(0)
+        } else {
+            budget_spending(&tx, budget, &from, &after_from)
+        };
+
+        set_month_override(&tx, &budget.id, to_start, Some(amount))?;
+        result.copied += 1;
+    }
+    tx.commit()?;
+
+    emit_changed(&app, &[BUDGETS_CHANGED]);
+
+    Ok(result)
+}
+
+/// Budgeted categories as a tree, each parent showing how much of its monthly budget
+/// is handed out to subcategory budgets and how much is left unallocated. Only
+/// categories with a budget somewhere in their subtree are included.
+#[tauri::command]
+pub fn get_budget_allocation_tree(db: State<'_, Mutex<Database>>) -> Result<Vec<BudgetAllocation>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let budgets = monthly_budgets(conn, None)?;
+
+    let mut stmt = conn.prepare(
+        "SELECT id, name, parent_id FROM categories
+         WHERE deleted_at IS NULL
+         ORDER BY display_order, name",
+    )?;
+    let categories: Vec<(String, String, Option<String>)> = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    fn build(
+        category: &(String, String, Option<String>),
+        categories: &[(String, String, Option<String>)],
+        budgets: &HashMap<String, i64>,
+    ) -> Option<BudgetAllocation> {
+        let (id, name, _) = category;
+        let children: Vec<BudgetAllocation> = categories
+            .iter()
+            .filter(|c| c.2.as_deref() == Some(id.as_str()))
+            .filter_map(|c| build(c, categories, budgets))
+            .collect();
+        let budget = budgets.get(id).copied();
+        if budget.is_none() && children.is_empty() {
+            return None;
+        }
+
+        let allocated = children.iter().filter_map(|c| c.budget).sum();
+        Some(BudgetAllocation {
+            category_id: id.clone(),
+            category_name: name.clone(),
+            budget,
+            allocated,
+            unallocated: budget.map(|b| b - allocated),
+            is_over_allocated: budget.is_some_and(|b| allocated > b),
+            children,
+        })
+    }
+
+    Ok(categories
+        .iter()
+        .filter(|c| c.2.is_none())
+        .filter_map(|c| build(c, &categories, &budgets))
+        .collect())
+}
+
+#[tauri::command]
+pub fn get_category_rolling_average(
+    category_id: String,
+    window_months: Option<Vec<u32>>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<CategoryRollingAverage> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let windows = window_months.unwrap_or_else(|| DEFAULT_ROLLING_WINDOWS.to_vec());
+    if windows.iter().any(|w| *w == 0 || *w > 120) {
+        return Err(AppError::Validation(
+            "Rolling windows must be between 1 and 120 months".to_string(),
+        ));
+    }
+
+    let category_type: String = conn
+        .query_row(
+            "SELECT category_type FROM categories WHERE id = ?1 AND deleted_at IS NULL",
+            [&category_id],
+            |row| row.get(0),
+        )
+        .map_err(|_| AppError::NotFound("Category not found".to_string()))?;
+
+    // Income categories average what came in; everything else averages what went out
+    let amount_expr = if category_type == "income" {
+        format!(
+            "SUM(CASE WHEN amount > 0 THEN {} ELSE 0 END)",
+            transaction_base_amount()
+        )
+    } else {
+        format!(
+            "SUM(CASE WHEN amount < 0 THEN -{} ELSE 0 END)",
+            transaction_base_amount()
+        )
+    };
+
+    let today = chrono::Local::now().date_naive();
+    let current = today.year() * 12 + today.month0() as i32;
+    let longest = windows.iter().copied().max().unwrap_or(0) as i32;
+
+    // Totals for the current month and every full month the longest window reaches back to
+    let mut stmt = conn.prepare(&format!(
+        "SELECT substr(date, 1, 7), COALESCE({}, 0)
+         FROM transactions t
+         WHERE category_id = ?1
+           AND date >= ?2
+           AND date < ?3
+           AND deleted_at IS NULL
+           AND transfer_id IS NULL
+         GROUP BY substr(date, 1, 7)",
+        amount_expr
+    ))?;
+
+    let monthly: HashMap<String, i64> = stmt
+        .query_map(
+            rusqlite::params![
+                category_id,
+                format!("{}-01", month_string(current - longest)),
+                format!("{}-01", month_string(current + 1)),
+            ],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let current_month = month_string(current);
+    let current_month_amount = monthly.get(&current_month).copied().unwrap_or(0);
+
+    // Months with no activity count as zero so sparse categories aren't overstated
+    let averages = windows
+        .into_iter()
+        .map(|window| {
+            let total: i64 = (1..=window as i32)
+                .map(|offset| monthly.get(&month_string(current - offset)).copied().unwrap_or(0))
+                .sum();
+            let average = (total as f64 / window as f64).round() as i64;
+            RollingAverage {
+                window_months: window,
+                average,
+                difference: current_month_amount - average,
+            }
+        })
+        .collect();
+
+    Ok(CategoryRollingAverage {
+        category_id,
+        current_month,
+        current_month_amount,
+        averages,
+    })
+}
+
+/// Month-by-month totals for one category, oldest first, ending with the current month.
+/// Subcategory transactions count toward the category, matching what its budget covers.
+#[tauri::command]
+pub fn get_category_history(
+    category_id: String,
+    months: Option<u32>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<CategoryHistory> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let months = months.unwrap_or(DEFAULT_HISTORY_MONTHS);
+    if months == 0 || months > 120 {
+        return Err(AppError::Validation(
+            "History must be between 1 and 120 months".to_string(),
+        ));
+    }
+
+    let category_type: String = conn
+        .query_row(
+            "SELECT category_type FROM categories WHERE id = ?1 AND deleted_at IS NULL",
+            [&category_id],
+            |row| row.get(0),
+        )
+        .map_err(|_| AppError::NotFound("Category not found".to_string()))?;
+
+    // Income categories track what came in; everything else tracks what went out
+    let (amount_expr, sign_filter) = if category_type == "income" {
+        (format!("SUM({})", transaction_base_amount()), "amount > 0")
+    } else {
+        (format!("SUM(-{})", transaction_base_amount()), "amount < 0")
+    };
+
+    let today = chrono::Local::now().date_naive();
+    let current = today.year() * 12 + today.month0() as i32;
+    let first = current - months as i32 + 1;
+
+    let mut stmt = conn.prepare(&format!(
+        "WITH RECURSIVE subtree(id) AS (
+             SELECT ?1
+             UNION
+             SELECT c.id FROM categories c JOIN subtree s ON c.parent_id = s.id
+         )
+         SELECT substr(date, 1, 7), COALESCE({}, 0), COUNT(*)
+         FROM transactions t
+         WHERE category_id IN (SELECT id FROM subtree)
+           AND {}
+           AND date >= ?2
+           AND date < ?3
+           AND deleted_at IS NULL
+           AND transfer_id IS NULL
+         GROUP BY substr(date, 1, 7)",
+        amount_expr, sign_filter
+    ))?;
+
+    let monthly: HashMap<String, (i64, i64)> = stmt
+        .query_map(
+            rusqlite::params![
+                category_id,
+                format!("{}-01", month_string(first)),
+                format!("{}-01", month_string(current + 1)),
+            ],
+            |row| Ok((row.get(0)?, (row.get(1)?, row.get(2)?))),
+        )?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let budget = monthly_budgets(conn, None)?.get(&category_id).copied();
+
+    let months = (first..=current)
+        .map(|index| {
+            let month = month_string(index);
+            let (amount, transaction_count) = monthly.get(&month).copied().unwrap_or((0, 0));
+            CategoryHistoryMonth {
+                month,
+                amount,
+                transaction_count,
+                budget,
+                vs_budget: budget.map(|b| amount - b),
+            }
+        })
+        .collect();
+
+    Ok(CategoryHistory {
+        category_id,
+        months,
+    })
+}
+
+/// Budgeted against actual amounts for a category's budget, month by month, oldest first
+/// and ending with the current month. Amounts are measured the way the monthly summary
+/// measures them, in the base currency at each transaction's date, so the two agree for
+/// any month.
+#[tauri::command]
+pub fn get_budget_performance(
+    category_id: String,
+    months: Option<u32>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<BudgetPerformance> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let months = months.unwrap_or(DEFAULT_HISTORY_MONTHS);
+    if months == 0 || months > 120 {
+        return Err(AppError::Validation(
+            "Performance must cover between 1 and 120 months".to_string(),
+        ));
+    }
+
+    let (budget, category_type): (Budget, String) = conn
+        .query_row(
+            &format!(
+                "SELECT {}, (SELECT category_type FROM categories WHERE id = budgets.category_id)
+                 FROM budgets WHERE category_id = ?1",
+                BUDGET_COLUMNS
+            ),
+            [&category_id],
+            |row| Ok((budget_from_row(row)?, row.get(12)?)),
+        )
+        .optional()?
+        .ok_or_else(|| AppError::NotFound("Category has no budget".to_string()))?;
+    if budget.is_watch_only {
+        return Err(AppError::Validation(
+            "Watch-only budgets have no amount to compare against".to_string(),
+        ));
+    }
+
+    let amount_expr = if category_type == "income" {
+        format!("SUM({})", transaction_base_amount())
+    } else {
+        format!(
+            "SUM(CASE WHEN amount < 0 THEN -{} ELSE 0 END)",
+            transaction_base_amount()
+        )
+    };
+
+    let current = month_index(chrono::Local::now().date_naive());
+    let first = current - months as i32 + 1;
+
+    let mut stmt = conn.prepare(&format!(
+        "{}
+         SELECT substr(date, 1, 7), COALESCE({}, 0)
+         FROM transactions t
+         WHERE category_id IN (SELECT id FROM budget_categories)
+           AND date >= ?3
+           AND date < ?4
+           AND deleted_at IS NULL
+           AND transfer_id IS NULL
+           AND {}
+         GROUP BY substr(date, 1, 7)",
+        BUDGET_CATEGORIES_CTE, amount_expr, BUDGET_EXCLUSION_FILTER
+    ))?;
+    let monthly: HashMap<String, i64> = stmt
+        .query_map(
+            rusqlite::params![
+                category_id,
+                budget.include_children,
+                format!("{}-01", month_string(first)),
+                format!("{}-01", month_string(current + 1)),
+            ],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let mut performance = Vec::new();
+    for index in first..=current {
+        let month = month_string(index);
+        let month_start = format!("{}-01", month);
+        let budgeted = budget_month_amount(conn, &budget, &month_start)?;
+        let actual = monthly.get(&month).copied().unwrap_or(0);
+        performance.push(BudgetPerformanceMonth {
+            month,
+            budgeted,
+            rolled_over: budget_rollover(conn, &budget.id, &month_start)?,
+            actual,
+            difference: actual - budgeted,
+        });
+    }
+
+    let count = performance.len() as f64;
+    let average = |total: i64| (total as f64 / count).round() as i64;
+
+    Ok(BudgetPerformance {
+        budget_id: budget.id,
+        category_id,
+        average_budgeted: average(performance.iter().map(|m| m.budgeted).sum()),
+        average_actual: average(performance.iter().map(|m| m.actual).sum()),
+        months_over: performance.iter().filter(|m| m.difference > 0).count() as i32,
+        months: performance,
+    })
+}
+
+/// Create a starter set of monthly budgets for the categories with the most spending over
+/// the last few full months that don't have a budget yet. With `method` "average" (the
+/// default) each budget is the category's average monthly spending; with "split" half of
+/// average income goes to needs and 30% to wants, shared out by how much each category
+/// spent, leaving 20% for savings. Amounts are rounded up to whole dollars.
+#[tauri::command]
+pub fn generate_budgets(
+    method: Option<String>,
+    months: Option<u32>,
+    limit: Option<usize>,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<GeneratedBudgets> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let method = method.as_deref().unwrap_or("average");
+    if method != "average" && method != "split" {
+        return Err(AppError::Validation(
+            "Budgets are generated from the \"average\" of spending or a 50/30/20 \"split\""
+                .to_string(),
+        ));
+    }
+    let months = months.unwrap_or(DEFAULT_GENERATED_MONTHS);
+    if months == 0 || months > 12 {
+        return Err(AppError::Validation(
+            "Budgets are generated from between 1 and 12 months".to_string(),
+        ));
+    }
+    let limit = limit.unwrap_or(DEFAULT_GENERATED_BUDGETS);
+
+    let current = month_index(chrono::Local::now().date_naive());
+    let start = month_bounds(current - months as i32)
+        .0
+        .format("%Y-%m-%d")
+        .to_string();
+    let end = month_bounds(current).0.format("%Y-%m-%d").to_string();
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT t.category_id, c.parent_id, SUM(-{amount})
+         FROM transactions t
+         JOIN categories c ON c.id = t.category_id
+         WHERE {filter}
+           AND {BUDGET_EXCLUSION_FILTER}
+           AND t.amount < 0
+           AND t.date >= ?1
+           AND t.date < ?2
+           AND c.category_type = 'expense'
+           AND c.deleted_at IS NULL
+           AND c.is_archived = 0
+           AND t.category_id NOT IN (SELECT category_id FROM budgets)
+         GROUP BY t.category_id
+         ORDER BY SUM(-{amount}) DESC
+         LIMIT ?3",
+        amount = transaction_base_amount(),
+        filter = reportable_transaction_filter(),
+    ))?;
+    let spending: Vec<(String, Option<String>, i64)> = stmt
+        .query_map(rusqlite::params![start, end, limit as i64], |row| {
+            Ok((
+                row.get(0)?,
+                row.get(1)?,
+                row.get::<_, i64>(2)? / months as i64,
+            ))
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let income: i64 = conn.query_row(
+        &format!(
+            "SELECT COALESCE(SUM({}), 0) FROM transactions t
+             JOIN categories c ON c.id = t.category_id
+             WHERE {} AND t.date >= ?1 AND t.date < ?2 AND c.category_type = 'income'",
+            transaction_base_amount(),
+            reportable_transaction_filter()
+        ),
+        [&start, &end],
+        |row| row.get(0),
+    )?;
+    let monthly_income = income.max(0) / months as i64;
+
+    let is_need = |category_id: &str, parent_id: &Option<String>| {
+        NEEDS_CATEGORIES.contains(&category_id)
+            || parent_id
+                .as_deref()
+                .is_some_and(|p| NEEDS_CATEGORIES.contains(&p))
+    };
+    let amounts: Vec<(&str, i64)> = if method == "split" {
+        let needs_total: i64 = spending
+            .iter()
+            .filter(|(id, parent, _)| is_need(id, parent))
+            .map(|s| s.2)
+            .sum();
+        let wants_total: i64 = spending
+            .iter()
+            .filter(|(id, parent, _)| !is_need(id, parent))
+            .map(|s| s.2)
+            .sum();
+        spending
+            .iter()
+            .map(|(id, parent, average)| {
+                let (pool, total) = if is_need(id, parent) {
+                    (monthly_income / 2, needs_total)
+                } else {
+                    (monthly_income * 3 / 10, wants_total)
+                };
+                let share = if total > 0 {
+                    (*average as i128 * pool as i128 / total as i128) as i64
+                } else {
+                    0
+                };
+                (id.as_str(), share)
+            })
+            .collect()
+    } else {
+        spending
+            .iter()
+            .map(|(id, _, average)| (id.as_str(), *average))
+            .collect()
+    };
+
+    // Budgets that wouldn't fit under a parent's budget are left for the user to set up
+    let mut budgets = Vec::new();
+    for (category_id, amount) in amounts {
+        let amount = (amount + 99) / 100 * 100;
+        if amount <= 0 {
+            continue;
+        }
+        let data = serde_json::json!({ "categoryId": category_id, "amount": amount });
+        match insert_budget(conn, &data) {
+            Ok(budget) => budgets.push(budget),
+            Err(AppError::Validation(_)) => continue,
+            Err(e) => return Err(e),
+        }
+    }
+
+    emit_changed(&app, &[BUDGETS_CHANGED]);
+
+    Ok(GeneratedBudgets {
+        budgets,
+        monthly_income,
+        savings: if method == "split" {
+            monthly_income - monthly_income / 2 - monthly_income * 3 / 10
+        } else {
+            0
+        },
+    })
+}
@@ -1,37 +1,106 @@
+use crate::commands::usage_stats::record_feature_use;
 use crate::db::Database;
 use crate::error::{AppError, Result};
+use crate::events::{emit_changed, CATEGORIES_CHANGED, TRANSACTIONS_CHANGED};
 use crate::models::Category;
+use rusqlite::{Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
 use std::sync::Mutex;
-use tauri::State;
+use tauri::{AppHandle, State};
 use uuid::Uuid;
 
+/// Columns selected for a full `Category`, in the order `category_from_row` expects
+pub(crate) const CATEGORY_COLUMNS: &str =
+    "id, name, parent_id, category_type, icon, color, is_system, display_order, created_at, updated_at, due_day, is_archived, is_tax_deductible";
+
+const CATEGORY_TYPES: [&str; 3] = ["income", "expense", "transfer"];
+
+/// A category and its subcategories as shared between databases; ids aren't included
+/// since they only mean something in the database they came from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryTreeNode {
+    pub name: String,
+    /// Defaults to the parent's type, or `expense` at the top level
+    #[serde(default)]
+    pub category_type: Option<String>,
+    #[serde(default)]
+    pub icon: Option<String>,
+    #[serde(default)]
+    pub color: Option<String>,
+    #[serde(default)]
+    pub children: Vec<CategoryTreeNode>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryImportResult {
+    pub created: i32,
+    pub merged: i32,
+}
+
+pub(crate) fn category_from_row(row: &rusqlite::Row) -> rusqlite::Result<Category> {
+    Ok(Category {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        parent_id: row.get(2)?,
+        category_type: row.get(3)?,
+        icon: row.get(4)?,
+        color: row.get(5)?,
+        is_system: row.get(6)?,
+        display_order: row.get(7)?,
+        created_at: row.get(8)?,
+        updated_at: row.get(9)?,
+        due_day: row.get(10)?,
+        is_archived: row.get(11)?,
+        is_tax_deductible: row.get(12)?,
+    })
+}
+
+/// Validated `dueDay` from command input: the day of the month a bill is expected
+pub(crate) fn input_due_day(data: &serde_json::Value) -> Result<Option<i32>> {
+    match data["dueDay"].as_i64() {
+        Some(day) if !(1..=31).contains(&day) => Err(AppError::Validation(
+            "Due day must be between 1 and 31".to_string(),
+        )),
+        day => Ok(day.map(|d| d as i32)),
+    }
+}
+
+/// Check that `parent_id` can become the parent of category `id`: it must be an existing
+/// category, and not `id` itself or one of its descendants, which would make a cycle
+fn validate_category_parent(conn: &Connection, id: &str, parent_id: Option<&str>) -> Result<()> {
+    let mut current = parent_id.map(String::from);
+    while let Some(ancestor) = current {
+        if ancestor == id {
+            return Err(AppError::Validation(
+                "A category can't be moved under itself or one of its subcategories".to_string(),
+            ));
+        }
+        current = conn
+            .query_row(
+                "SELECT parent_id FROM categories WHERE id = ?1 AND deleted_at IS NULL",
+                [&ancestor],
+                |row| row.get(0),
+            )
+            .map_err(|_| AppError::NotFound("Parent category not found".to_string()))?;
+    }
+
+    Ok(())
+}
+
 #[tauri::command]
 pub fn list_categories(db: State<'_, Mutex<Database>>) -> Result<Vec<Category>> {
     let database = db.lock().unwrap();
     let conn = database.get_connection()?;
 
-    let mut stmt = conn.prepare(
-        "SELECT id, name, parent_id, category_type, icon, color, is_system, display_order, created_at, updated_at
-         FROM categories
-         WHERE deleted_at IS NULL
-         ORDER BY display_order, name"
-    )?;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM categories WHERE deleted_at IS NULL ORDER BY display_order, name",
+        CATEGORY_COLUMNS
+    ))?;
 
     let categories = stmt
-        .query_map([], |row| {
-            Ok(Category {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                parent_id: row.get(2)?,
-                category_type: row.get(3)?,
-                icon: row.get(4)?,
-                color: row.get(5)?,
-                is_system: row.get(6)?,
-                display_order: row.get(7)?,
-                created_at: row.get(8)?,
-                updated_at: row.get(9)?,
-            })
-        })?
+        .query_map([], category_from_row)?
         .filter_map(|r| r.ok())
         .collect();
 
@@ -41,17 +110,19 @@ pub fn list_categories(db: State<'_, Mutex<Database>>) -> Result<Vec<Category>>
 #[tauri::command]
 pub fn create_category(
     data: serde_json::Value,
+    app: AppHandle,
     db: State<'_, Mutex<Database>>,
 ) -> Result<Category> {
     let database = db.lock().unwrap();
     let conn = database.get_connection()?;
 
+    let due_day = input_due_day(&data)?;
     let id = Uuid::new_v4().to_string();
     let now = chrono::Utc::now().to_rfc3339();
 
     conn.execute(
-        "INSERT INTO categories (id, name, parent_id, category_type, icon, color, is_system, display_order, created_at, updated_at)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, 0, ?7, ?8, ?9)",
+        "INSERT INTO categories (id, name, parent_id, category_type, icon, color, is_system, display_order, due_day, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, 0, ?7, ?8, ?9, ?10)",
         rusqlite::params![
             id,
             data["name"].as_str().unwrap_or(""),
@@ -60,29 +131,18 @@ pub fn create_category(
             data["icon"].as_str(),
             data["color"].as_str(),
             data["displayOrder"].as_i64().unwrap_or(0) as i32,
+            due_day,
             now,
             now,
         ],
     )?;
 
+    emit_changed(&app, &[CATEGORIES_CHANGED]);
+
     conn.query_row(
-        "SELECT id, name, parent_id, category_type, icon, color, is_system, display_order, created_at, updated_at
-         FROM categories WHERE id = ?1",
+        &format!("SELECT {} FROM categories WHERE id = ?1", CATEGORY_COLUMNS),
         [&id],
-        |row| {
-            Ok(Category {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                parent_id: row.get(2)?,
-                category_type: row.get(3)?,
-                icon: row.get(4)?,
-                color: row.get(5)?,
-                is_system: row.get(6)?,
-                display_order: row.get(7)?,
-                created_at: row.get(8)?,
-                updated_at: row.get(9)?,
-            })
-        },
+        category_from_row,
     )
     .map_err(|e| e.into())
 }
@@ -91,55 +151,223 @@ pub fn create_category(
 pub fn update_category(
     id: String,
     data: serde_json::Value,
+    app: AppHandle,
     db: State<'_, Mutex<Database>>,
 ) -> Result<Category> {
     let database = db.lock().unwrap();
     let conn = database.get_connection()?;
 
+    let due_day = input_due_day(&data)?;
+    validate_category_parent(conn, &id, data["parentId"].as_str())?;
     let now = chrono::Utc::now().to_rfc3339();
 
+    // The category form doesn't send a due day, so it's only touched when the key is present
     conn.execute(
         "UPDATE categories SET
             name = COALESCE(?1, name),
             parent_id = ?2,
             icon = ?3,
             color = ?4,
-            updated_at = ?5
-         WHERE id = ?6 AND is_system = 0",
+            due_day = CASE WHEN ?5 THEN ?6 ELSE due_day END,
+            updated_at = ?7
+         WHERE id = ?8 AND is_system = 0",
         rusqlite::params![
             data["name"].as_str(),
             data["parentId"].as_str(),
             data["icon"].as_str(),
             data["color"].as_str(),
+            data.get("dueDay").is_some(),
+            due_day,
             now,
             id,
         ],
     )?;
 
+    emit_changed(&app, &[CATEGORIES_CHANGED]);
+
     conn.query_row(
-        "SELECT id, name, parent_id, category_type, icon, color, is_system, display_order, created_at, updated_at
-         FROM categories WHERE id = ?1",
+        &format!("SELECT {} FROM categories WHERE id = ?1", CATEGORY_COLUMNS),
         [&id],
-        |row| {
-            Ok(Category {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                parent_id: row.get(2)?,
-                category_type: row.get(3)?,
-                icon: row.get(4)?,
-                color: row.get(5)?,
-                is_system: row.get(6)?,
-                display_order: row.get(7)?,
-                created_at: row.get(8)?,
-                updated_at: row.get(9)?,
-            })
-        },
+        category_from_row,
+    )
+    .map_err(|e| e.into())
+}
+
+/// Move a category under a new parent, or to the top level when `new_parent_id` is `None`.
+/// Its subcategories move with it.
+#[tauri::command]
+pub fn move_category(
+    id: String,
+    new_parent_id: Option<String>,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Category> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let is_system: bool = conn
+        .query_row(
+            "SELECT is_system FROM categories WHERE id = ?1 AND deleted_at IS NULL",
+            [&id],
+            |row| row.get(0),
+        )
+        .map_err(|_| AppError::NotFound("Category not found".to_string()))?;
+
+    if is_system {
+        return Err(AppError::Validation("Cannot move system category".to_string()));
+    }
+
+    validate_category_parent(conn, &id, new_parent_id.as_deref())?;
+
+    let now = chrono::Utc::now().to_rfc3339();
+
+    conn.execute(
+        "UPDATE categories SET parent_id = ?1, updated_at = ?2 WHERE id = ?3",
+        rusqlite::params![new_parent_id, now, id],
+    )?;
+
+    emit_changed(&app, &[CATEGORIES_CHANGED]);
+
+    conn.query_row(
+        &format!("SELECT {} FROM categories WHERE id = ?1", CATEGORY_COLUMNS),
+        [&id],
+        category_from_row,
     )
     .map_err(|e| e.into())
 }
 
+/// Set `display_order` to each category's position in `ordered_ids`. Categories sort by
+/// display order and then form a tree, so passing just one parent's children reorders
+/// them within that group.
+#[tauri::command]
+pub fn reorder_categories(
+    ordered_ids: Vec<String>,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<()> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let unique: std::collections::HashSet<&String> = ordered_ids.iter().collect();
+    if unique.len() != ordered_ids.len() {
+        return Err(AppError::Validation(
+            "Category order lists a category more than once".to_string(),
+        ));
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+
+    let tx = conn.unchecked_transaction()?;
+    for (position, id) in ordered_ids.iter().enumerate() {
+        let updated = tx.execute(
+            "UPDATE categories SET display_order = ?1, updated_at = ?2
+             WHERE id = ?3 AND deleted_at IS NULL",
+            rusqlite::params![position as i32, now, id],
+        )?;
+        if updated == 0 {
+            return Err(AppError::NotFound(format!("Category not found: {}", id)));
+        }
+    }
+    tx.commit()?;
+
+    emit_changed(&app, &[CATEGORIES_CHANGED]);
+
+    Ok(())
+}
+
+/// Mark a category as tax deductible or not. Its subcategories count as deductible
+/// whenever it is, and system categories can be marked too.
+#[tauri::command]
+pub fn set_category_tax_deductible(
+    id: String,
+    deductible: bool,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Category> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let updated = conn.execute(
+        "UPDATE categories SET is_tax_deductible = ?1, updated_at = ?2
+         WHERE id = ?3 AND deleted_at IS NULL",
+        rusqlite::params![deductible, chrono::Utc::now().to_rfc3339(), id],
+    )?;
+    if updated == 0 {
+        return Err(AppError::NotFound("Category not found".to_string()));
+    }
+
+    emit_changed(&app, &[CATEGORIES_CHANGED]);
+
+    conn.query_row(
+        &format!("SELECT {} FROM categories WHERE id = ?1", CATEGORY_COLUMNS),
+        [&id],
+        category_from_row,
+    )
+    .map_err(|e| e.into())
+}
+
+/// Archive or unarchive a category together with its subcategories
+fn set_category_archived(conn: &Connection, id: &str, archived: bool) -> Result<()> {
+    let now = chrono::Utc::now().to_rfc3339();
+    let updated = conn.execute(
+        "WITH RECURSIVE subtree(id) AS (
+             SELECT id FROM categories WHERE id = ?1 AND deleted_at IS NULL
+             UNION
+             SELECT c.id FROM categories c JOIN subtree s ON c.parent_id = s.id
+         )
+         UPDATE categories SET is_archived = ?2, updated_at = ?3
+         WHERE id IN (SELECT id FROM subtree)",
+        rusqlite::params![id, archived, now],
+    )?;
+
+    if updated == 0 {
+        return Err(AppError::NotFound("Category not found".to_string()));
+    }
+
+    Ok(())
+}
+
+/// Archive a category and its subcategories: they drop out of pickers and can't be rule
+/// targets any more (rules already pointing at them stop applying), while transactions
+/// keep the category and reports still show it.
 #[tauri::command]
-pub fn delete_category(id: String, db: State<'_, Mutex<Database>>) -> Result<()> {
+pub fn archive_category(
+    id: String,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<()> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    set_category_archived(conn, &id, true)?;
+
+    emit_changed(&app, &[CATEGORIES_CHANGED]);
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn unarchive_category(
+    id: String,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<()> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    set_category_archived(conn, &id, false)?;
+
+    emit_changed(&app, &[CATEGORIES_CHANGED]);
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn delete_category(
+    id: String,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<()> {
     let database = db.lock().unwrap();
     let conn = database.get_connection()?;
 
@@ -161,5 +389,199 @@ pub fn delete_category(id: String, db: State<'_, Mutex<Database>>) -> Result<()>
         [&now, &id],
     )?;
 
+    emit_changed(&app, &[CATEGORIES_CHANGED, TRANSACTIONS_CHANGED]);
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn list_deleted_categories(db: State<'_, Mutex<Database>>) -> Result<Vec<Category>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM categories WHERE deleted_at IS NOT NULL ORDER BY deleted_at DESC",
+        CATEGORY_COLUMNS
+    ))?;
+
+    let categories = stmt
+        .query_map([], category_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(categories)
+}
+
+#[tauri::command]
+pub fn restore_categories(
+    ids: Vec<String>,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<()> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let now = chrono::Utc::now().to_rfc3339();
+
+    let tx = conn.unchecked_transaction()?;
+    for id in &ids {
+        tx.execute(
+            "UPDATE categories SET deleted_at = NULL, updated_at = ?1 WHERE id = ?2 AND deleted_at IS NOT NULL",
+            [&now, id],
+        )?;
+    }
+
+    // A restored child whose parent is still in the trash moves to the top level
+    for id in &ids {
+        tx.execute(
+            "UPDATE categories SET parent_id = NULL, updated_at = ?1
+             WHERE id = ?2
+               AND parent_id IN (SELECT id FROM categories WHERE deleted_at IS NOT NULL)",
+            [&now, id],
+        )?;
+    }
+    tx.commit()?;
+
+    emit_changed(&app, &[CATEGORIES_CHANGED]);
+
     Ok(())
 }
+
+/// Export the category tree (names, types, icons and colors, in display order) as JSON
+/// for the frontend to save
+#[tauri::command]
+pub fn export_categories(db: State<'_, Mutex<Database>>) -> Result<String> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM categories WHERE deleted_at IS NULL ORDER BY display_order, name",
+        CATEGORY_COLUMNS
+    ))?;
+    let categories: Vec<Category> = stmt
+        .query_map([], category_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    fn children_of(categories: &[Category], parent_id: Option<&str>) -> Vec<CategoryTreeNode> {
+        categories
+            .iter()
+            .filter(|c| c.parent_id.as_deref() == parent_id)
+            .map(|c| CategoryTreeNode {
+                name: c.name.clone(),
+                category_type: Some(c.category_type.clone()),
+                icon: c.icon.clone(),
+                color: c.color.clone(),
+                children: children_of(categories, Some(&c.id)),
+            })
+            .collect()
+    }
+
+    record_feature_use(conn, "export_categories");
+
+    Ok(serde_json::to_string_pretty(&children_of(&categories, None))?)
+}
+
+fn import_category_nodes(
+    conn: &Connection,
+    nodes: &[CategoryTreeNode],
+    parent: Option<(&str, &str)>,
+    now: &str,
+    result: &mut CategoryImportResult,
+) -> Result<()> {
+    let parent_id = parent.map(|(id, _)| id);
+
+    for (position, node) in nodes.iter().enumerate() {
+        let name = node.name.trim();
+        if name.is_empty() {
+            return Err(AppError::Validation(
+                "Imported categories must have a name".to_string(),
+            ));
+        }
+        let category_type = node
+            .category_type
+            .as_deref()
+            .or(parent.map(|(_, t)| t))
+            .unwrap_or("expense");
+        if !CATEGORY_TYPES.contains(&category_type) {
+            return Err(AppError::Validation(format!(
+                "Invalid category type for {}: {}",
+                name, category_type
+            )));
+        }
+
+        let existing: Option<(String, String)> = conn
+            .query_row(
+                "SELECT id, category_type FROM categories
+                 WHERE deleted_at IS NULL AND parent_id IS ?1 AND name = ?2 COLLATE NOCASE",
+                rusqlite::params![parent_id, name],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+
+        let (id, category_type) = match existing {
+            Some((id, existing_type)) => {
+                conn.execute(
+                    "UPDATE categories SET icon = COALESCE(?1, icon), color = COALESCE(?2, color),
+                        updated_at = ?3
+                     WHERE id = ?4 AND is_system = 0",
+                    rusqlite::params![node.icon, node.color, now, id],
+                )?;
+                result.merged += 1;
+                (id, existing_type)
+            }
+            None => {
+                let id = Uuid::new_v4().to_string();
+                conn.execute(
+                    "INSERT INTO categories (id, name, parent_id, category_type, icon, color, is_system, display_order, created_at, updated_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, 0, ?7, ?8, ?8)",
+                    rusqlite::params![
+                        id,
+                        name,
+                        parent_id,
+                        category_type,
+                        node.icon,
+                        node.color,
+                        position as i32,
+                        now,
+                    ],
+                )?;
+                result.created += 1;
+                (id, category_type.to_string())
+            }
+        };
+
+        import_category_nodes(conn, &node.children, Some((&id, &category_type)), now, result)?;
+    }
+
+    Ok(())
+}
+
+/// Import a category tree exported by `export_categories`, merging by name: a category
+/// matching an existing one under the same parent (ignoring case) keeps its id and takes
+/// the imported icon and color, unless it is a system category, which stays as it is;
+/// anything else is created.
+#[tauri::command]
+pub fn import_categories(
+    contents: String,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<CategoryImportResult> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let nodes: Vec<CategoryTreeNode> = serde_json::from_str(&contents)
+        .map_err(|e| AppError::Validation(format!("Invalid category file: {}", e)))?;
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let mut result = CategoryImportResult::default();
+
+    let tx = conn.unchecked_transaction()?;
+    import_category_nodes(&tx, &nodes, None, &now, &mut result)?;
+    tx.commit()?;
+    record_feature_use(conn, "import_categories");
+
+    emit_changed(&app, &[CATEGORIES_CHANGED]);
+
+    Ok(result)
+}
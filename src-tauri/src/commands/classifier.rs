@@ -0,0 +1,238 @@
+use crate::commands::rules::matching_rule_category;
+use crate::commands::transactions::{transaction_from_row, TRANSACTION_COLUMNS};
+use crate::db::Database;
+use crate::error::{AppError, Result};
+use crate::models::Transaction;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::State;
+
+/// Upper bounds (in cents) of the amount buckets the classifier uses as a feature
+const AMOUNT_BUCKETS: &[i64] = &[1000, 5000, 10000, 50000, 100000];
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CategorySuggestion {
+    pub transaction_id: String,
+    /// `None` when nothing matched and there's no history to learn from
+    pub category_id: Option<String>,
+    pub category_name: Option<String>,
+    /// 1.0 for a rule match, otherwise the classifier's posterior probability
+    pub confidence: f64,
+    /// `rule` or `classifier`
+    pub source: String,
+}
+
+/// Payee words worth learning from: lowercase, at least two characters, not just digits
+fn payee_tokens(payee: &str) -> Vec<String> {
+    payee
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.len() >= 2 && !w.chars().all(|c| c.is_ascii_digit()))
+        .map(|w| w.to_string())
+        .collect()
+}
+
+/// A coarse size-and-direction token for an amount, e.g. `amount:out:<50.00`
+fn amount_bucket(amount: i64) -> String {
+    let direction = if amount < 0 { "out" } else { "in" };
+    match AMOUNT_BUCKETS.iter().find(|bound| amount.abs() < **bound) {
+        Some(bound) => format!("amount:{}:<{}", direction, bound / 100),
+        None => format!("amount:{}:large", direction),
+    }
+}
+
+fn features(payee: &str, amount: i64) -> Vec<String> {
+    let mut features = payee_tokens(payee);
+    features.push(amount_bucket(amount));
+    features
+}
+
+/// Multinomial naive Bayes over payee tokens and an amount bucket, with add-one smoothing
+#[derive(Debug, Default)]
+struct CategoryClassifier {
+    /// Training examples per category
+    documents: HashMap<String, usize>,
+    /// Feature counts per category, and each category's total feature count
+    features: HashMap<String, HashMap<String, usize>>,
+    totals: HashMap<String, usize>,
+    vocabulary: std::collections::HashSet<String>,
+}
+
+impl CategoryClassifier {
+    fn train(&mut self, category_id: &str, features: &[String]) {
+        *self.documents.entry(category_id.to_string()).or_default() += 1;
+        let counts = self.features.entry(category_id.to_string()).or_default();
+        for feature in features {
+            *counts.entry(feature.clone()).or_default() += 1;
+            self.vocabulary.insert(feature.clone());
+        }
+        *self.totals.entry(category_id.to_string()).or_default() += features.len();
+    }
+
+    /// The most likely category and its posterior probability among all trained ones
+    fn predict(&self, features: &[String]) -> Option<(String, f64)> {
+        let examples: usize = self.documents.values().sum();
+        if examples == 0 {
+            return None;
+        }
+        let vocabulary = self.vocabulary.len() as f64;
+
+        let scores: Vec<(&String, f64)> = self
+            .documents
+            .iter()
+            .map(|(category, documents)| {
+                let counts = &self.features[category];
+                let total = self.totals[category] as f64;
+                let likelihood: f64 = features
+                    .iter()
+                    .map(|f| {
+                        let count = counts.get(f).copied().unwrap_or(0) as f64;
+                        ((count + 1.0) / (total + vocabulary)).ln()
+                    })
+                    .sum();
+                (
+                    category,
+                    (*documents as f64 / examples as f64).ln() + likelihood,
+                )
+            })
+            .collect();
+
+        let (best, best_score) = scores
+            .iter()
+            .max_by(|a, b| a.1.total_cmp(&b.1).then_with(|| b.0.cmp(a.0)))?;
+        let normalizer: f64 = scores.iter().map(|(_, s)| (s - best_score).exp()).sum();
+
+        Some(((*best).clone(), 1.0 / normalizer))
+    }
+}
+
+/// Suggest a category for a transaction. A matching rule wins outright; otherwise a naive
+/// Bayes classifier trained on every other categorized transaction (payee words plus an
+/// amount bucket) picks the likeliest category and reports how confident it is.
+#[tauri::command]
+pub fn suggest_category(
+    transaction_id: String,
+    db: State<'_, Mutex<Database>>,
+) -> Result<CategorySuggestion> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let transaction: Transaction = conn
+        .query_row(
+            &format!(
+                "SELECT {} FROM transactions WHERE id = ?1 AND deleted_at IS NULL",
+                TRANSACTION_COLUMNS
+            ),
+            [&transaction_id],
+            transaction_from_row,
+        )
+        .map_err(|_| AppError::NotFound("Transaction not found".to_string()))?;
+
+    let category_names: HashMap<String, String> = conn
+        .prepare("SELECT id, name FROM categories WHERE is_archived = 0 AND deleted_at IS NULL")?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let suggestion =
+        |category_id: Option<String>, confidence: f64, source: &str| CategorySuggestion {
+            transaction_id: transaction_id.clone(),
+            category_name: category_id
+                .as_ref()
+                .and_then(|id| category_names.get(id).cloned()),
+            category_id,
+            confidence,
+            source: source.to_string(),
+        };
+
+    if let Some(category_id) = matching_rule_category(conn, &transaction)? {
+        return Ok(suggestion(Some(category_id), 1.0, "rule"));
+    }
+
+    // Transfers and splits keep their categories elsewhere, so they aren't learned from
+    let mut stmt = conn.prepare(
+        "SELECT category_id, payee, amount FROM transactions
+         WHERE deleted_at IS NULL AND id != ?1 AND category_id IS NOT NULL
+           AND transfer_id IS NULL AND is_split = 0 AND payee IS NOT NULL",
+    )?;
+    let mut classifier = CategoryClassifier::default();
+    for (category_id, payee, amount) in stmt
+        .query_map([&transaction_id], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, i64>(2)?,
+            ))
+        })?
+        .filter_map(|r| r.ok())
+        .filter(|(category_id, _, _)| category_names.contains_key(category_id))
+    {
+        classifier.train(&category_id, &features(&payee, amount));
+    }
+
+    let payee = transaction.payee.as_deref().unwrap_or("");
+    Ok(
+        match classifier.predict(&features(payee, transaction.amount)) {
+            Some((category_id, confidence)) => {
+                suggestion(Some(category_id), confidence, "classifier")
+            }
+            None => suggestion(None, 0.0, "classifier"),
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trained() -> CategoryClassifier {
+        let mut classifier = CategoryClassifier::default();
+        for (category, payee, amount) in [
+            ("coffee", "STARBUCKS #1234", -550),
+            ("coffee", "Starbucks Store 99", -625),
+            ("coffee", "BLUE BOTTLE COFFEE", -700),
+            ("groceries", "WHOLE FOODS MARKET", -8432),
+            ("groceries", "Trader Joe's", -6210),
+            ("groceries", "WHOLE FOODS #10", -12000),
+        ] {
+            classifier.train(category, &features(payee, amount));
+        }
+        classifier
+    }
+
+    #[test]
+    fn test_features() {
+        assert_eq!(
+            payee_tokens("STARBUCKS #1234 Seattle, WA"),
+            ["starbucks", "seattle", "wa"]
+        );
+        assert_eq!(amount_bucket(-550), "amount:out:<10");
+        assert_eq!(amount_bucket(4999), "amount:in:<50");
+        assert_eq!(amount_bucket(-250000), "amount:out:large");
+    }
+
+    #[test]
+    fn test_predict() {
+        let classifier = trained();
+
+        let (category, confidence) = classifier
+            .predict(&features("STARBUCKS 555", -480))
+            .unwrap();
+        assert_eq!(category, "coffee");
+        assert!(confidence > 0.9);
+
+        let (category, _) = classifier.predict(&features("Whole Foods", -9000)).unwrap();
+        assert_eq!(category, "groceries");
+
+        // Unseen payees fall back to the amount bucket and category frequency
+        let (category, confidence) = classifier.predict(&features("NEW PLACE", -600)).unwrap();
+        assert_eq!(category, "coffee");
+        assert!(confidence < 0.9);
+
+        assert!(CategoryClassifier::default()
+            .predict(&features("x", 1))
+            .is_none());
+    }
+}
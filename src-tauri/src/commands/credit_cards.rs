@@ -0,0 +1,215 @@
+use crate::commands::accounts::{account_columns, account_from_row};
+use crate::commands::recurring::clamped_date;
+use crate::db::Database;
+use crate::error::{AppError, Result};
+use crate::models::Account;
+use chrono::{Datelike, NaiveDate};
+use rusqlite::Connection;
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::State;
+
+/// A payment due this many days away or fewer is flagged as due soon
+const DUE_SOON_DAYS: i64 = 7;
+/// Utilization at or above this percentage of the credit limit is flagged as high
+const HIGH_UTILIZATION_PERCENT: f64 = 30.0;
+
+/// Where a credit card stands in its statement cycle. Amounts owed are positive.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreditCardStatus {
+    pub account_id: String,
+    pub account_name: String,
+    pub balance_owed: i64,
+    pub credit_limit: Option<i64>,
+    /// Balance owed as a percentage of the credit limit
+    pub utilization: Option<f64>,
+    /// The statement cycle fields are only known once the card's closing day is set
+    pub last_statement_date: Option<String>,
+    pub statement_balance: Option<i64>,
+    /// Payments and credits posted since the last statement closed
+    pub paid_since_statement: Option<i64>,
+    pub next_statement_date: Option<String>,
+    pub minimum_payment: Option<i64>,
+    pub payment_due_date: Option<String>,
+    /// Negative once the payment is overdue
+    pub days_until_due: Option<i64>,
+    pub is_due_soon: bool,
+    pub is_overdue: bool,
+    pub is_high_utilization: bool,
+}
+
+/// The first date after `after` that falls on `day` of the month, clamped to short months
+fn next_day_of_month(after: NaiveDate, day: u32) -> NaiveDate {
+    let this_month = clamped_date(after.year(), after.month(), day);
+    match this_month.filter(|d| *d > after) {
+        Some(date) => date,
+        None => {
+            let next = after
+                .checked_add_months(chrono::Months::new(1))
+                .unwrap_or(after);
+            clamped_date(next.year(), next.month(), day).unwrap_or(next)
+        }
+    }
+}
+
+/// The last date on or before `on` that falls on `day` of the month, clamped to short months
+fn previous_day_of_month(on: NaiveDate, day: u32) -> NaiveDate {
+    let this_month = clamped_date(on.year(), on.month(), day);
+    match this_month.filter(|d| *d <= on) {
+        Some(date) => date,
+        None => {
+            let previous = on.checked_sub_months(chrono::Months::new(1)).unwrap_or(on);
+            clamped_date(previous.year(), previous.month(), day).unwrap_or(previous)
+        }
+    }
+}
+
+fn credit_card_status(
+    conn: &Connection,
+    card: &Account,
+    today: NaiveDate,
+) -> Result<CreditCardStatus> {
+    let balance_owed = (-card.current_balance).max(0);
+    let credit_limit = card.credit_limit.filter(|limit| *limit > 0);
+    let utilization = credit_limit.map(|limit| balance_owed as f64 / limit as f64 * 100.0);
+
+    let mut status = CreditCardStatus {
+        account_id: card.id.clone(),
+        account_name: card.name.clone(),
+        balance_owed,
+        credit_limit,
+        utilization,
+        last_statement_date: None,
+        statement_balance: None,
+        paid_since_statement: None,
+        next_statement_date: None,
+        minimum_payment: card.minimum_payment,
+        payment_due_date: None,
+        days_until_due: None,
+        is_due_soon: false,
+        is_overdue: false,
+        is_high_utilization: utilization.is_some_and(|u| u >= HIGH_UTILIZATION_PERCENT),
+    };
+    let Some(closing_day) = card.statement_closing_day else {
+        return Ok(status);
+    };
+
+    let last_statement = previous_day_of_month(today, closing_day as u32);
+    let next_statement = next_day_of_month(last_statement, closing_day as u32);
+
+    // Work the balance back to the closing date, as month close does for net worth
+    let (after_statement, paid_since_statement): (i64, i64) = conn.query_row(
+        "SELECT COALESCE(SUM(amount), 0),
+                COALESCE(SUM(CASE WHEN amount > 0 THEN amount ELSE 0 END), 0)
+         FROM transactions
+         WHERE account_id = ?1 AND deleted_at IS NULL AND date > ?2",
+        rusqlite::params![card.id, last_statement.format("%Y-%m-%d").to_string()],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+    let statement_balance = (-(card.current_balance - after_statement)).max(0);
+    let unpaid = statement_balance - paid_since_statement;
+
+    status.last_statement_date = Some(last_statement.format("%Y-%m-%d").to_string());
+    status.statement_balance = Some(statement_balance);
+    status.paid_since_statement = Some(paid_since_statement);
+    status.next_statement_date = Some(next_statement.format("%Y-%m-%d").to_string());
+
+    if let Some(due_day) = card.payment_due_day {
+        // A paid statement's due date gives way to the next statement's
+        let mut due = next_day_of_month(last_statement, due_day as u32);
+        if due < today && unpaid <= 0 {
+            due = next_day_of_month(next_statement, due_day as u32);
+        }
+        let days_until_due = (due - today).num_days();
+        status.payment_due_date = Some(due.format("%Y-%m-%d").to_string());
+        status.days_until_due = Some(days_until_due);
+        status.is_overdue = days_until_due < 0 && unpaid > 0;
+        status.is_due_soon = (0..=DUE_SOON_DAYS).contains(&days_until_due) && unpaid > 0;
+    }
+
+    Ok(status)
+}
+
+/// Statement cycle, utilization and due date of every active credit card
+#[tauri::command]
+pub fn get_credit_card_statuses(db: State<'_, Mutex<Database>>) -> Result<Vec<CreditCardStatus>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM accounts
+         WHERE deleted_at IS NULL AND is_active = 1 AND account_type = 'credit_card'
+         ORDER BY display_order, name",
+        account_columns()
+    ))?;
+    let cards: Vec<Account> = stmt
+        .query_map([], account_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let today = chrono::Local::now().date_naive();
+    cards
+        .iter()
+        .map(|card| credit_card_status(conn, card, today))
+        .collect()
+}
+
+#[tauri::command]
+pub fn get_credit_card_status(
+    account_id: String,
+    db: State<'_, Mutex<Database>>,
+) -> Result<CreditCardStatus> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let card = conn
+        .query_row(
+            &format!(
+                "SELECT {} FROM accounts WHERE id = ?1 AND deleted_at IS NULL",
+                account_columns()
+            ),
+            [&account_id],
+            account_from_row,
+        )
+        .map_err(|_| AppError::NotFound("Account not found".to_string()))?;
+    if card.account_type != "credit_card" {
+        return Err(AppError::Validation(format!(
+            "{} isn't a credit card",
+            card.name
+        )));
+    }
+
+    credit_card_status(conn, &card, chrono::Local::now().date_naive())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_statement_cycle_days() {
+        let next = [
+            ("2024-01-10", 15, "2024-01-15"),
+            ("2024-01-15", 15, "2024-02-15"),
+            ("2024-01-31", 31, "2024-02-29"),
+            ("2024-02-29", 30, "2024-03-30"),
+        ];
+        for (after, day, expected) in next {
+            assert_eq!(next_day_of_month(date(after), day), date(expected));
+        }
+
+        let previous = [
+            ("2024-03-15", 15, "2024-03-15"),
+            ("2024-03-14", 15, "2024-02-15"),
+            ("2024-03-10", 31, "2024-02-29"),
+        ];
+        for (on, day, expected) in previous {
+            assert_eq!(previous_day_of_month(date(on), day), date(expected));
+        }
+    }
+}
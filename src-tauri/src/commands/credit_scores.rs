@@ -0,0 +1,103 @@
+use crate::db::Database;
+use crate::error::{AppError, Result};
+use crate::events::{emit_changed, CREDIT_SCORES_CHANGED};
+use crate::models::CreditScore;
+use std::sync::Mutex;
+use tauri::{AppHandle, State};
+use uuid::Uuid;
+
+/// FICO and VantageScore both range from 300 to 850
+const MIN_CREDIT_SCORE: i64 = 300;
+const MAX_CREDIT_SCORE: i64 = 850;
+
+#[tauri::command]
+pub fn record_credit_score(
+    date: String,
+    bureau: String,
+    score: i64,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<CreditScore> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    if chrono::NaiveDate::parse_from_str(&date, "%Y-%m-%d").is_err() {
+        return Err(AppError::Validation(format!("Invalid date: {}", date)));
+    }
+    let bureau = bureau.trim();
+    if bureau.is_empty() {
+        return Err(AppError::Validation("Bureau is required".to_string()));
+    }
+    if !(MIN_CREDIT_SCORE..=MAX_CREDIT_SCORE).contains(&score) {
+        return Err(AppError::Validation(format!(
+            "Credit score must be between {} and {}",
+            MIN_CREDIT_SCORE, MAX_CREDIT_SCORE
+        )));
+    }
+
+    let id = Uuid::new_v4().to_string();
+    let now = chrono::Utc::now().to_rfc3339();
+
+    conn.execute(
+        "INSERT INTO credit_scores (id, date, bureau, score, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        rusqlite::params![id, date, bureau, score, now],
+    )?;
+
+    emit_changed(&app, &[CREDIT_SCORES_CHANGED]);
+
+    Ok(CreditScore {
+        id,
+        date,
+        bureau: bureau.to_string(),
+        score,
+        created_at: now,
+    })
+}
+
+/// Recorded scores, oldest first, optionally limited to one bureau
+#[tauri::command]
+pub fn get_credit_score_history(
+    bureau: Option<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<CreditScore>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let mut stmt = conn.prepare(
+        "SELECT id, date, bureau, score, created_at FROM credit_scores
+         WHERE ?1 IS NULL OR bureau = ?1 COLLATE NOCASE
+         ORDER BY date, created_at",
+    )?;
+
+    let scores = stmt
+        .query_map([bureau], |row| {
+            Ok(CreditScore {
+                id: row.get(0)?,
+                date: row.get(1)?,
+                bureau: row.get(2)?,
+                score: row.get(3)?,
+                created_at: row.get(4)?,
+            })
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(scores)
+}
+
+#[tauri::command]
+pub fn delete_credit_score(
+    id: String,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<()> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    conn.execute("DELETE FROM credit_scores WHERE id = ?1", [&id])?;
+
+    emit_changed(&app, &[CREDIT_SCORES_CHANGED]);
+
+    Ok(())
+}
@@ -0,0 +1,374 @@
+use crate::commands::accounts::LIABILITY_ACCOUNT_TYPES;
+use crate::commands::forecast::{month_bounds, month_index};
+use crate::commands::goals::fetch_goal;
+use crate::db::Database;
+use crate::error::{AppError, Result};
+use chrono::NaiveDate;
+use rusqlite::Connection;
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::State;
+
+const PAYOFF_STRATEGIES: &[&str] = &["snowball", "avalanche"];
+
+/// How far ahead a plan looks before treating the debts as never paid off
+const MAX_PAYOFF_MONTHS: i32 = 600;
+
+/// Smallest minimum payment assumed for a debt without one set
+const DEFAULT_MINIMUM_PAYMENT: i64 = 2500;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PayoffDebt {
+    pub account_id: String,
+    pub account_name: String,
+    pub starting_balance: i64,
+    /// APR, in percent
+    pub interest_rate: f64,
+    pub minimum_payment: i64,
+    /// Month the debt is paid off, or `None` if not within 50 years
+    pub payoff_month: Option<String>,
+    pub interest_paid: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DebtPayment {
+    pub account_id: String,
+    pub payment: i64,
+    pub interest: i64,
+    /// What's still owed after the payment
+    pub balance: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PayoffMonth {
+    pub month: String,
+    pub payments: Vec<DebtPayment>,
+    pub total_balance: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DebtPayoffPlan {
+    pub goal_id: String,
+    pub strategy: String,
+    pub monthly_payment: i64,
+    /// Debts in the order the strategy pays them down
+    pub debts: Vec<PayoffDebt>,
+    pub months: Vec<PayoffMonth>,
+    /// Month the last debt is paid off, or `None` if not within 50 years
+    pub payoff_month: Option<String>,
+    pub total_interest: i64,
+    /// The same debts paying only their minimums, for comparison
+    pub minimum_only_payoff_month: Option<String>,
+    pub minimum_only_interest: i64,
+    pub interest_saved: i64,
+}
+
+struct Debt {
+    account_id: String,
+    name: String,
+    balance: i64,
+    rate: f64,
+    minimum: i64,
+}
+
+/// What simulating a payment plan produced: the months, then each debt's payoff month and
+/// interest paid
+struct Simulation {
+    months: Vec<PayoffMonth>,
+    debts: Vec<(Option<String>, i64)>,
+}
+
+pub(crate) fn validate_payoff_strategy(data: &serde_json::Value) -> Result<()> {
+    if let Some(strategy) = data["payoffStrategy"].as_str() {
+        if !PAYOFF_STRATEGIES.contains(&strategy) {
+            return Err(AppError::Validation(format!(
+                "Invalid payoff strategy: {}",
+                strategy
+            )));
+        }
+    }
+    if data["monthlyPayment"].as_i64().is_some_and(|p| p <= 0) {
+        return Err(AppError::Validation(
+            "Monthly payment must be greater than zero".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Replace a goal's debts from command input: `[{ accountId, minimumPayment }]`. Each
+/// account must be a credit card or loan.
+pub(crate) fn save_goal_debts(
+    conn: &Connection,
+    goal_id: &str,
+    debts: &serde_json::Value,
+) -> Result<()> {
+    let mut rows = Vec::new();
+    for debt in debts.as_array().into_iter().flatten() {
+        let account_id = debt["accountId"].as_str().unwrap_or("");
+        let account_type: String = conn
+            .query_row(
+                "SELECT account_type FROM accounts WHERE id = ?1 AND deleted_at IS NULL",
+                [account_id],
+                |row| row.get(0),
+            )
+            .map_err(|_| AppError::NotFound("Account not found".to_string()))?;
+        if !LIABILITY_ACCOUNT_TYPES.contains(&account_type.as_str()) {
+            return Err(AppError::Validation(
+                "Only credit card and loan accounts can be paid off by a goal".to_string(),
+            ));
+        }
+        let minimum_payment = debt["minimumPayment"].as_i64().unwrap_or(0);
+        if minimum_payment < 0 {
+            return Err(AppError::Validation(
+                "Minimum payments can't be negative".to_string(),
+            ));
+        }
+        rows.push((account_id.to_string(), minimum_payment));
+    }
+
+    conn.execute("DELETE FROM goal_debts WHERE goal_id = ?1", [goal_id])?;
+    for (account_id, minimum_payment) in rows {
+        conn.execute(
+            "INSERT OR REPLACE INTO goal_debts (goal_id, account_id, minimum_payment)
+             VALUES (?1, ?2, ?3)",
+            rusqlite::params![goal_id, account_id, minimum_payment],
+        )?;
+    }
+    Ok(())
+}
+
+pub(crate) fn monthly_interest(balance: i64, rate: f64) -> i64 {
+    (balance as f64 * rate / 100.0 / 12.0).round() as i64
+}
+
+fn load_goal_debts(conn: &Connection, goal_id: &str) -> Result<Vec<Debt>> {
+    let mut stmt = conn.prepare(
+        "SELECT a.id, a.name, a.current_balance, COALESCE(a.interest_rate, 0), d.minimum_payment
+         FROM goal_debts d
+         JOIN accounts a ON a.id = d.account_id
+         WHERE d.goal_id = ?1 AND a.deleted_at IS NULL
+         ORDER BY a.name",
+    )?;
+    let debts = stmt
+        .query_map([goal_id], |row| {
+            let balance = row.get::<_, i64>(2)?.abs();
+            let rate: f64 = row.get(3)?;
+            let minimum: i64 = row.get(4)?;
+            // Without a minimum set, assume the usual card minimum: the month's interest
+            // plus 1% of the balance
+            let minimum = if minimum > 0 {
+                minimum
+            } else {
+                (balance / 100 + monthly_interest(balance, rate))
+                    .max(DEFAULT_MINIMUM_PAYMENT)
+                    .min(balance)
+            };
+            Ok(Debt {
+                account_id: row.get(0)?,
+                name: row.get(1)?,
+                balance,
+                rate,
+                minimum,
+            })
+        })?
+        .filter_map(|r| r.ok())
+        .filter(|d: &Debt| d.balance > 0)
+        .collect();
+    Ok(debts)
+}
+
+/// Order debts are paid down in: smallest balance first for the snowball, highest rate
+/// first (then smallest balance) for the avalanche
+fn payoff_order(debts: &[Debt], balances: &[i64], strategy: &str) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..debts.len()).collect();
+    if strategy == "avalanche" {
+        order.sort_by(|&a, &b| {
+            debts[b]
+                .rate
+                .partial_cmp(&debts[a].rate)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(balances[a].cmp(&balances[b]))
+        });
+    } else {
+        order.sort_by_key(|&i| balances[i]);
+    }
+    order
+}
+
+/// Pay the debts month by month: interest accrues, every debt gets its minimum, and with a
+/// strategy whatever is left of the monthly payment (including minimums freed up by debts
+/// already paid off) goes to the debt the strategy puts first. Without a strategy each
+/// debt only ever gets its own minimum.
+fn simulate_payoff(
+    debts: &[Debt],
+    monthly_payment: i64,
+    strategy: Option<&str>,
+    today: NaiveDate,
+) -> Simulation {
+    let mut balances: Vec<i64> = debts.iter().map(|d| d.balance).collect();
+    let mut results: Vec<(Option<String>, i64)> = vec![(None, 0); debts.len()];
+    let mut months = Vec::new();
+
+    for offset in 1..=MAX_PAYOFF_MONTHS {
+        if balances.iter().all(|b| *b <= 0) {
+            break;
+        }
+        let month = month_bounds(month_index(today) + offset)
+            .0
+            .format("%Y-%m")
+            .to_string();
+
+        let mut payments: Vec<DebtPayment> = Vec::new();
+        let mut available = monthly_payment;
+        let mut paid = vec![0; debts.len()];
+        let mut interest = vec![0; debts.len()];
+
+        for (i, debt) in debts.iter().enumerate() {
+            if balances[i] <= 0 {
+                continue;
+            }
+            interest[i] = monthly_interest(balances[i], debt.rate);
+            balances[i] += interest[i];
+            results[i].1 += interest[i];
+
+            let minimum = debt.minimum.min(balances[i]);
+            paid[i] = minimum;
+            balances[i] -= minimum;
+            available -= minimum;
+        }
+
+        if let Some(strategy) = strategy {
+            for i in payoff_order(debts, &balances, strategy) {
+                if available <= 0 {
+                    break;
+                }
+                let extra = available.min(balances[i]);
+                paid[i] += extra;
+                balances[i] -= extra;
+                available -= extra;
+            }
+        }
+
+        for (i, debt) in debts.iter().enumerate() {
+            if paid[i] == 0 && interest[i] == 0 {
+                continue;
+            }
+            if balances[i] <= 0 && results[i].0.is_none() {
+                results[i].0 = Some(month.clone());
+            }
+            payments.push(DebtPayment {
+                account_id: debt.account_id.clone(),
+                payment: paid[i],
+                interest: interest[i],
+                balance: balances[i],
+            });
+        }
+
+        months.push(PayoffMonth {
+            month,
+            payments,
+            total_balance: balances.iter().sum(),
+        });
+    }
+
+    Simulation {
+        months,
+        debts: results,
+    }
+}
+
+/// Payoff month of the last debt, if every debt gets paid off
+fn last_payoff_month(simulation: &Simulation) -> Option<String> {
+    simulation
+        .debts
+        .iter()
+        .map(|(month, _)| month.clone())
+        .collect::<Option<Vec<_>>>()
+        .and_then(|months| months.into_iter().max())
+}
+
+/// Plan paying off a debt payoff goal's debts from their current balances and interest
+/// rates. The strategy and monthly payment default to the goal's own; passing them
+/// compares alternatives. Interest saved is measured against paying only the minimums.
+#[tauri::command]
+pub fn get_debt_payoff_plan(
+    goal_id: String,
+    strategy: Option<String>,
+    monthly_payment: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<DebtPayoffPlan> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let goal = fetch_goal(conn, &goal_id)?;
+    if goal.goal_type != "debt_payoff" {
+        return Err(AppError::Validation(
+            "Only debt payoff goals have a payoff plan".to_string(),
+        ));
+    }
+
+    let strategy = strategy
+        .or(goal.payoff_strategy.clone())
+        .unwrap_or_else(|| "avalanche".to_string());
+    if !PAYOFF_STRATEGIES.contains(&strategy.as_str()) {
+        return Err(AppError::Validation(format!(
+            "Invalid payoff strategy: {}",
+            strategy
+        )));
+    }
+
+    let debts = load_goal_debts(conn, &goal.id)?;
+    if debts.is_empty() {
+        return Err(AppError::Validation(
+            "Add a credit card or loan with a balance to plan its payoff".to_string(),
+        ));
+    }
+
+    let minimums: i64 = debts.iter().map(|d| d.minimum).sum();
+    let monthly_payment = monthly_payment.or(goal.monthly_payment).unwrap_or(minimums);
+    if monthly_payment < minimums {
+        return Err(AppError::Validation(format!(
+            "The monthly payment has to cover the minimum payments of {}.{:02}",
+            minimums / 100,
+            minimums % 100
+        )));
+    }
+
+    let today = chrono::Local::now().date_naive();
+    let plan = simulate_payoff(&debts, monthly_payment, Some(&strategy), today);
+    let minimum_only = simulate_payoff(&debts, minimums, None, today);
+
+    let total_interest: i64 = plan.debts.iter().map(|(_, interest)| interest).sum();
+    let minimum_only_interest: i64 = minimum_only.debts.iter().map(|(_, i)| i).sum();
+
+    let starting_balances: Vec<i64> = debts.iter().map(|d| d.balance).collect();
+    let summaries = payoff_order(&debts, &starting_balances, &strategy)
+        .into_iter()
+        .map(|i| PayoffDebt {
+            account_id: debts[i].account_id.clone(),
+            account_name: debts[i].name.clone(),
+            starting_balance: debts[i].balance,
+            interest_rate: debts[i].rate,
+            minimum_payment: debts[i].minimum,
+            payoff_month: plan.debts[i].0.clone(),
+            interest_paid: plan.debts[i].1,
+        })
+        .collect();
+
+    Ok(DebtPayoffPlan {
+        goal_id: goal.id,
+        strategy,
+        monthly_payment,
+        debts: summaries,
+        payoff_month: last_payoff_month(&plan),
+        months: plan.months,
+        total_interest,
+        minimum_only_payoff_month: last_payoff_month(&minimum_only),
+        minimum_only_interest,
+        interest_saved: minimum_only_interest - total_interest,
+    })
+}
@@ -0,0 +1,519 @@
+use crate::commands::accounts::{account_columns, account_from_row};
+use crate::commands::attachments::attachment_files;
+use crate::commands::transactions::{
+    transaction_filter_clause, transaction_from_row, TRANSACTION_COLUMNS,
+};
+use crate::commands::usage_stats::record_feature_use;
+use crate::db::Database;
+use crate::error::{AppError, Result};
+use crate::export::format_amount;
+use crate::export::ofx::{self, OfxStatement, OfxTransaction};
+use crate::export::qif::{self, QifAccount, QifSplit, QifTransaction};
+use crate::models::{Account, Transaction, TransactionFilters};
+use rusqlite::Connection;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::io::Write;
+use std::sync::Mutex;
+use tauri::State;
+
+/// The accounts to export: one account, or every open account when none is given
+fn export_accounts(conn: &Connection, account_id: Option<&str>) -> Result<Vec<Account>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM accounts
+         WHERE deleted_at IS NULL AND (?1 IS NULL OR id = ?1)
+         ORDER BY display_order, name",
+        account_columns()
+    ))?;
+
+    let accounts: Vec<Account> = stmt
+        .query_map([account_id], account_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    if account_id.is_some() && accounts.is_empty() {
+        return Err(AppError::NotFound("Account not found".to_string()));
+    }
+
+    Ok(accounts)
+}
+
+/// An account's transactions within an optional date range, oldest first.
+/// Pending holds are left out since they aren't real charges yet.
+fn export_transactions(
+    conn: &Connection,
+    account_id: &str,
+    start_date: Option<&str>,
+    end_date: Option<&str>,
+) -> Result<Vec<Transaction>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM transactions
+         WHERE deleted_at IS NULL AND is_hold = 0 AND account_id = ?1
+           AND (?2 IS NULL OR date >= ?2)
+           AND (?3 IS NULL OR date <= ?3)
+         ORDER BY date, created_at",
+        TRANSACTION_COLUMNS
+    ))?;
+
+    let transactions = stmt
+        .query_map(
+            rusqlite::params![account_id, start_date, end_date],
+            transaction_from_row,
+        )?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(transactions)
+}
+
+/// Full category paths by id, `Parent:Child` for subcategories
+fn category_paths(conn: &Connection) -> Result<HashMap<String, String>> {
+    let mut stmt = conn.prepare("SELECT id, name, parent_id FROM categories")?;
+    let rows: Vec<(String, String, Option<String>)> = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let names: HashMap<&str, &str> = rows
+        .iter()
+        .map(|(id, name, _)| (id.as_str(), name.as_str()))
+        .collect();
+
+    Ok(rows
+        .iter()
+        .map(|(id, name, parent_id)| {
+            let path = match parent_id.as_deref().and_then(|p| names.get(p)) {
+                Some(parent) => format!("{}:{}", parent, name),
+                None => name.clone(),
+            };
+            (id.clone(), path)
+        })
+        .collect())
+}
+
+/// Columns available to `export_filtered_transactions`, in their default order
+const EXPORT_COLUMNS: &[&str] = &[
+    "date",
+    "account",
+    "payee",
+    "category",
+    "amount",
+    "status",
+    "memo",
+    "notes",
+    "checkNumber",
+    "postedDate",
+    "originalPayee",
+    "transferAccount",
+    "importSource",
+];
+
+/// Columns exported when none are chosen
+const DEFAULT_EXPORT_COLUMNS: &[&str] =
+    &["date", "account", "payee", "category", "amount", "status", "memo", "notes"];
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaxPackage {
+    pub year: i32,
+    pub path: String,
+    pub files: Vec<String>,
+    pub transaction_count: usize,
+    pub attachment_count: usize,
+}
+
+fn csv_bytes<H, R>(header: &[H], rows: impl IntoIterator<Item = R>) -> Result<Vec<u8>>
+where
+    H: AsRef<[u8]>,
+    R: IntoIterator,
+    R::Item: AsRef<[u8]>,
+{
+    let csv_error = |e: csv::Error| AppError::Other(format!("CSV export failed: {}", e));
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(header).map_err(csv_error)?;
+    for row in rows {
+        writer.write_record(row).map_err(csv_error)?;
+    }
+    writer
+        .into_inner()
+        .map_err(|e| AppError::Other(format!("CSV export failed: {}", e)))
+}
+
+/// One transaction's value for an export column
+fn export_value(
+    transaction: &Transaction,
+    column: &str,
+    account_names: &HashMap<String, String>,
+    categories: &HashMap<String, String>,
+) -> String {
+    let account_name = |id: &Option<String>| {
+        id.as_ref()
+            .and_then(|id| account_names.get(id).cloned())
+            .unwrap_or_default()
+    };
+
+    match column {
+        "date" => transaction.date.clone(),
+        "account" => account_name(&Some(transaction.account_id.clone())),
+        "payee" => transaction.payee.clone().unwrap_or_default(),
+        "category" => transaction
+            .category_id
+            .as_ref()
+            .and_then(|id| categories.get(id).cloned())
+            .unwrap_or_default(),
+        "amount" => format_amount(transaction.amount),
+        "status" => transaction.status.clone(),
+        "memo" => transaction.memo.clone().unwrap_or_default(),
+        "notes" => transaction.notes.clone().unwrap_or_default(),
+        "checkNumber" => transaction.check_number.clone().unwrap_or_default(),
+        "postedDate" => transaction.posted_date.clone().unwrap_or_default(),
+        "originalPayee" => transaction.original_payee.clone().unwrap_or_default(),
+        "transferAccount" => account_name(&transaction.transfer_account_id),
+        "importSource" => transaction.import_source.clone().unwrap_or_default(),
+        _ => String::new(),
+    }
+}
+
+/// Export every transaction matching the transaction list's filters to `path`, newest
+/// first like the list itself (without its 1000-row cap). `format` is `csv` or `json`;
+/// `columns` picks and orders the fields, defaulting to the main list columns.
+/// Returns how many transactions were written.
+#[tauri::command]
+pub fn export_filtered_transactions(
+    filters: TransactionFilters,
+    format: String,
+    path: String,
+    columns: Option<Vec<String>>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<usize> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let columns: Vec<String> = match columns {
+        Some(columns) if !columns.is_empty() => columns,
+        _ => DEFAULT_EXPORT_COLUMNS.iter().map(|c| c.to_string()).collect(),
+    };
+    if let Some(unknown) = columns.iter().find(|c| !EXPORT_COLUMNS.contains(&c.as_str())) {
+        return Err(AppError::Validation(format!(
+            "Unknown export column: {}",
+            unknown
+        )));
+    }
+    if format != "csv" && format != "json" {
+        return Err(AppError::Validation(format!(
+            "Unsupported export format: {}",
+            format
+        )));
+    }
+
+    let (clause, params) = transaction_filter_clause(&filters);
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM transactions WHERE deleted_at IS NULL{}
+         ORDER BY date DESC, created_at DESC",
+        TRANSACTION_COLUMNS, clause
+    ))?;
+    let transactions: Vec<Transaction> = stmt
+        .query_map(rusqlite::params_from_iter(params.iter()), transaction_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let categories = category_paths(conn)?;
+    let account_names: HashMap<String, String> = conn
+        .prepare("SELECT id, name FROM accounts")?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let rows = transactions.iter().map(|t| {
+        columns
+            .iter()
+            .map(|c| export_value(t, c, &account_names, &categories))
+            .collect::<Vec<String>>()
+    });
+
+    let contents = if format == "json" {
+        let records: Vec<serde_json::Map<String, serde_json::Value>> = rows
+            .map(|values| {
+                columns
+                    .iter()
+                    .cloned()
+                    .zip(values.into_iter().map(serde_json::Value::String))
+                    .collect()
+            })
+            .collect();
+        serde_json::to_vec_pretty(&records)?
+    } else {
+        csv_bytes(&columns, rows)?
+    };
+
+    std::fs::write(&path, contents)?;
+    record_feature_use(conn, "export_filtered_transactions");
+
+    Ok(transactions.len())
+}
+
+/// Write a year's tax package to the zip archive at `path` for handing to a preparer: a
+/// per-category summary of the tax deductible categories, a CSV of their transactions and
+/// the files attached to those transactions. Transfers and pending holds are left out.
+#[tauri::command]
+pub fn export_tax_package(
+    year: i32,
+    path: String,
+    db: State<'_, Mutex<Database>>,
+) -> Result<TaxPackage> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    if !(1900..=9999).contains(&year) {
+        return Err(AppError::Validation(format!("Invalid tax year: {}", year)));
+    }
+
+    let transactions = tax_transactions(conn, year)?;
+
+    let categories = category_paths(conn)?;
+    let account_names: HashMap<String, String> = conn
+        .prepare("SELECT id, name FROM accounts")?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    // Category path -> (transaction count, total)
+    let mut totals: std::collections::BTreeMap<String, (usize, i64)> = Default::default();
+    for transaction in &transactions {
+        let category = export_value(transaction, "category", &account_names, &categories);
+        let entry = totals.entry(category).or_default();
+        entry.0 += 1;
+        entry.1 += transaction.amount;
+    }
+
+    let summary = csv_bytes(
+        &["category", "transactions", "total"],
+        totals.iter().map(|(category, (count, total))| {
+            vec![category.clone(), count.to_string(), format_amount(*total)]
+        }),
+    )?;
+    let transaction_rows = csv_bytes(
+        DEFAULT_EXPORT_COLUMNS,
+        transactions.iter().map(|t| {
+            DEFAULT_EXPORT_COLUMNS
+                .iter()
+                .map(|c| export_value(t, c, &account_names, &categories))
+                .collect::<Vec<_>>()
+        }),
+    )?;
+
+    let mut files = vec![
+        (format!("tax-summary-{}.csv", year), summary),
+        (format!("tax-transactions-{}.csv", year), transaction_rows),
+    ];
+    // Attachments go under a folder per transaction, named by date and id so the same
+    // file name on two receipts can't collide
+    let mut attachment_count = 0;
+    for transaction in &transactions {
+        for (file_name, contents) in attachment_files(conn, &transaction.id)? {
+            files.push((
+                format!(
+                    "attachments/{}-{}/{}",
+                    transaction.date, transaction.id, file_name
+                ),
+                contents,
+            ));
+            attachment_count += 1;
+        }
+    }
+
+    write_zip(std::path::Path::new(&path), &files)?;
+    record_feature_use(conn, "export_tax_package");
+
+    Ok(TaxPackage {
+        year,
+        path,
+        files: files.into_iter().map(|(name, _)| name).collect(),
+        transaction_count: transactions.len(),
+        attachment_count,
+    })
+}
+
+/// A year's transactions in tax deductible categories, or under one, oldest first
+fn tax_transactions(conn: &Connection, year: i32) -> Result<Vec<Transaction>> {
+    let mut stmt = conn.prepare(&format!(
+        "WITH RECURSIVE tax_categories(id) AS (
+             SELECT id FROM categories WHERE is_tax_deductible = 1 AND deleted_at IS NULL
+             UNION
+             SELECT c.id FROM categories c JOIN tax_categories t ON c.parent_id = t.id
+         )
+         SELECT {} FROM transactions
+         WHERE deleted_at IS NULL AND is_hold = 0 AND transfer_id IS NULL
+           AND category_id IN (SELECT id FROM tax_categories)
+           AND date >= ?1 AND date <= ?2
+         ORDER BY date, created_at",
+        TRANSACTION_COLUMNS
+    ))?;
+    let transactions = stmt
+        .query_map(
+            [format!("{}-01-01", year), format!("{}-12-31", year)],
+            transaction_from_row,
+        )?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(transactions)
+}
+
+/// Write `files` (archive name, contents) into a new zip archive at `path`
+fn write_zip(path: &std::path::Path, files: &[(String, Vec<u8>)]) -> Result<()> {
+    let zip_error = |e: zip::result::ZipError| AppError::Other(format!("Zip export failed: {}", e));
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(path)?);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    for (name, contents) in files {
+        zip.start_file(name.as_str(), options).map_err(zip_error)?;
+        zip.write_all(contents)?;
+    }
+    zip.finish().map_err(zip_error)?;
+    Ok(())
+}
+
+/// Export transactions as QIF for one account (or all open accounts), optionally limited
+/// to a date range. Returns the file contents for the frontend to save.
+#[tauri::command]
+pub fn export_qif(
+    account_id: Option<String>,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<String> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let categories = category_paths(conn)?;
+    let account_names: HashMap<String, String> = conn
+        .prepare("SELECT id, name FROM accounts")?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let mut split_stmt = conn.prepare(
+        "SELECT category_id, amount, memo FROM transaction_splits
+         WHERE parent_transaction_id = ?1
+         ORDER BY created_at",
+    )?;
+
+    let mut qif_accounts = Vec::new();
+    for account in export_accounts(conn, account_id.as_deref())? {
+        let transactions = export_transactions(
+            conn,
+            &account.id,
+            start_date.as_deref(),
+            end_date.as_deref(),
+        )?;
+
+        let mut qif_transactions = Vec::with_capacity(transactions.len());
+        for transaction in transactions {
+            let splits = if transaction.is_split {
+                split_stmt
+                    .query_map([&transaction.id], |row| {
+                        Ok((
+                            row.get::<_, Option<String>>(0)?,
+                            row.get::<_, i64>(1)?,
+                            row.get::<_, Option<String>>(2)?,
+                        ))
+                    })?
+                    .filter_map(|r| r.ok())
+                    .map(|(category_id, amount, memo)| QifSplit {
+                        category: category_id.and_then(|id| categories.get(&id).cloned()),
+                        amount,
+                        memo,
+                    })
+                    .collect()
+            } else {
+                Vec::new()
+            };
+
+            qif_transactions.push(QifTransaction {
+                date: transaction.date,
+                amount: transaction.amount,
+                payee: transaction.payee,
+                memo: transaction.memo.or(transaction.notes),
+                check_number: transaction.check_number,
+                status: transaction.status,
+                category: transaction
+                    .category_id
+                    .and_then(|id| categories.get(&id).cloned()),
+                transfer_account: transaction
+                    .transfer_account_id
+                    .and_then(|id| account_names.get(&id).cloned()),
+                splits,
+            });
+        }
+
+        qif_accounts.push(QifAccount {
+            name: account.name,
+            account_type: account.account_type,
+            transactions: qif_transactions,
+        });
+    }
+
+    record_feature_use(conn, "export_qif");
+
+    Ok(qif::write_qif(&qif_accounts))
+}
+
+/// Export one account as an OFX 2.2 statement, optionally limited to a date range.
+/// FITIDs come from transaction ids, so re-exporting the same transactions gives the
+/// same ids and importers won't duplicate them.
+#[tauri::command]
+pub fn export_account_ofx(
+    account_id: String,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<String> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let account = export_accounts(conn, Some(&account_id))?.remove(0);
+    let transactions = export_transactions(
+        conn,
+        &account.id,
+        start_date.as_deref(),
+        end_date.as_deref(),
+    )?;
+
+    let today = chrono::Local::now()
+        .date_naive()
+        .format("%Y-%m-%d")
+        .to_string();
+    let start_date = start_date
+        .or_else(|| transactions.first().map(|t| t.date.clone()))
+        .unwrap_or_else(|| today.clone());
+    let end_date = end_date.unwrap_or(today);
+
+    let statement = OfxStatement {
+        account_id: account
+            .ofx_account_id
+            .or(account.account_number_masked)
+            .unwrap_or_else(|| account.id.replace('-', "")),
+        account_type: account.account_type,
+        currency: account.currency,
+        start_date,
+        end_date,
+        ledger_balance: account.current_balance,
+        transactions: transactions
+            .into_iter()
+            .map(|t| OfxTransaction {
+                fitid: t.id.replace('-', ""),
+                date: t.date,
+                amount: t.amount,
+                payee: t.payee,
+                memo: t.memo.or(t.notes),
+                check_number: t.check_number,
+                is_transfer: t.transfer_id.is_some(),
+            })
+            .collect(),
+    };
+
+    let generated_at = chrono::Utc::now().format("%Y%m%d%H%M%S").to_string();
+    record_feature_use(conn, "export_account_ofx");
+
+    Ok(ofx::write_ofx(&statement, &generated_at))
+}
@@ -0,0 +1,324 @@
+use crate::commands::fx::{base_amount_sql, transaction_base_amount};
+use crate::commands::recurring::{
+    clamped_date, is_recurring_bill_paid, occurrences_between, recurring_from_row,
+    RECURRING_COLUMNS,
+};
+use crate::commands::reports::reportable_transaction_filter;
+use crate::db::Database;
+use crate::error::{AppError, Result};
+use crate::models::RecurringTransaction;
+use chrono::{Datelike, NaiveDate};
+use rusqlite::Connection;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use tauri::State;
+
+/// Account types whose balances count as spendable cash
+const CASH_ACCOUNT_TYPES: &str = "'checking', 'savings', 'cash'";
+
+pub(crate) const DEFAULT_FORECAST_MONTHS: u32 = 6;
+pub(crate) const MAX_FORECAST_MONTHS: u32 = 60;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForecastMonth {
+    pub month: String,
+    pub starting_balance: i64,
+    pub income: i64,
+    pub bills: i64,
+    pub budgeted_spending: i64,
+    pub planned_purchases: i64,
+    pub ending_balance: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CashFlowForecast {
+    pub as_of: String,
+    pub starting_balance: i64,
+    pub months: Vec<ForecastMonth>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeToSpend {
+    pub as_of: String,
+    pub cash_balance: i64,
+    pub lowest_balance: i64,
+    pub lowest_balance_month: String,
+    /// Planned purchases inside the forecast window that are already set aside
+    pub planned_purchases: i64,
+    pub safe_to_spend: i64,
+}
+
+/// Index of a date's month counted from year 0 (year * 12 + zero-based month)
+pub(crate) fn month_index(date: NaiveDate) -> i32 {
+    date.year() * 12 + date.month0() as i32
+}
+
+/// First and last day of a month index
+pub(crate) fn month_bounds(index: i32) -> (NaiveDate, NaiveDate) {
+    let (year, month) = (index.div_euclid(12), index.rem_euclid(12) as u32 + 1);
+    let start = NaiveDate::from_ymd_opt(year, month, 1).unwrap_or_default();
+    (start, clamped_date(year, month, 31).unwrap_or(start))
+}
+
+/// Budget amounts are entered per period; the forecast works in months
+pub(crate) fn monthly_budget_amount(
+    period_type: &str,
+    period_days: Option<i64>,
+    amount: i64,
+) -> i64 {
+    match period_type {
+        "weekly" => amount * 52 / 12,
+        "biweekly" => amount * 26 / 12,
+        "quarterly" => amount / 3,
+        "yearly" => amount / 12,
+        "custom" => match period_days {
+            Some(days) if days > 0 => (amount as f64 * 365.25 / 12.0 / days as f64).round() as i64,
+            _ => amount,
+        },
+        _ => amount,
+    }
+}
+
+fn cash_balance(conn: &Connection) -> Result<i64> {
+    conn.query_row(
+        &format!(
+            "SELECT COALESCE(SUM({}), 0) FROM accounts
+             WHERE deleted_at IS NULL AND is_active = 1 AND account_type IN ({})",
+            base_amount_sql("current_balance", "currency", "date('now')"),
+            CASH_ACCOUNT_TYPES
+        ),
+        [],
+        |row| row.get(0),
+    )
+    .map_err(|e| e.into())
+}
+
+/// Project cash balances month by month, starting with the rest of the current month.
+///
+/// Categories with a budget are forecast from the budget (what's left of it this month),
+/// so recurring transactions in those categories aren't counted twice; everything else
+/// comes from active recurring transactions, skipping this month's bills already paid.
+/// Open planned purchases land in the month of their desired date, or this month once
+/// that date has passed. `excluded_purchase` leaves one purchase out, for working out
+/// where it would fit.
+pub(crate) fn build_forecast(
+    conn: &Connection,
+    today: NaiveDate,
+    months: u32,
+    excluded_purchase: Option<&str>,
+) -> Result<CashFlowForecast> {
+    let current_index = month_index(today);
+    let today_str = today.format("%Y-%m-%d").to_string();
+
+    let mut stmt = conn.prepare(
+        "SELECT b.category_id, c.category_type, b.period_type, b.period_days, b.amount, b.id
+         FROM budgets b
+         JOIN categories c ON b.category_id = c.id
+         WHERE c.deleted_at IS NULL AND b.is_watch_only = 0",
+    )?;
+    let budgets: Vec<(String, String, i64, String)> = stmt
+        .query_map([], |row| {
+            let (period_type, period_days, amount): (String, Option<i64>, i64) =
+                (row.get(2)?, row.get(3)?, row.get(4)?);
+            Ok((
+                row.get(0)?,
+                row.get(1)?,
+                monthly_budget_amount(&period_type, period_days, amount),
+                row.get(5)?,
+            ))
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    // Amounts set for particular months replace the usual monthly amount
+    let mut stmt = conn.prepare(
+        "SELECT budget_id, period_start, amount_override FROM budget_periods
+         WHERE amount_override IS NOT NULL",
+    )?;
+    let month_amounts: HashMap<(String, String), i64> = stmt
+        .query_map([], |row| Ok(((row.get(0)?, row.get(1)?), row.get(2)?)))?
+        .filter_map(|r| r.ok())
+        .collect();
+    let budgeted_categories: HashSet<&str> = budgets.iter().map(|b| b.0.as_str()).collect();
+
+    // What has already landed in each budgeted category this month
+    let (month_start, _) = month_bounds(current_index);
+    let mut stmt = conn.prepare(&format!(
+        "SELECT t.category_id, SUM({}) FROM transactions t
+         WHERE {} AND t.date >= ?1 AND t.date <= ?2 AND t.category_id IS NOT NULL
+         GROUP BY t.category_id",
+        transaction_base_amount(),
+        reportable_transaction_filter()
+    ))?;
+    let month_to_date: HashMap<String, i64> = stmt
+        .query_map(
+            rusqlite::params![month_start.format("%Y-%m-%d").to_string(), today_str],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM recurring_transactions WHERE is_active = 1",
+        RECURRING_COLUMNS
+    ))?;
+    let recurring: Vec<RecurringTransaction> = stmt
+        .query_map([], recurring_from_row)?
+        .filter_map(|r| r.ok())
+        .filter(|r: &RecurringTransaction| {
+            r.category_id
+                .as_deref()
+                .is_none_or(|c| !budgeted_categories.contains(c))
+        })
+        .collect();
+
+    let mut stmt = conn.prepare(
+        "SELECT id, target_amount, desired_date FROM planned_purchases
+         WHERE is_purchased = 0",
+    )?;
+    let mut purchases_by_month: HashMap<i32, i64> = HashMap::new();
+    for (id, amount, desired_date) in stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })?
+        .filter_map(|r| r.ok())
+    {
+        if excluded_purchase == Some(id.as_str()) {
+            continue;
+        }
+        let index = NaiveDate::parse_from_str(&desired_date, "%Y-%m-%d")
+            .map(month_index)
+            .unwrap_or(current_index)
+            .max(current_index);
+        *purchases_by_month.entry(index).or_default() += amount;
+    }
+
+    let starting_balance = cash_balance(conn)?;
+    let mut balance = starting_balance;
+    let mut forecast_months = Vec::new();
+
+    for index in current_index..current_index + months.clamp(1, MAX_FORECAST_MONTHS) as i32 {
+        let is_current = index == current_index;
+        let (start, end) = month_bounds(index);
+        let start = if is_current { today } else { start };
+
+        let mut income = 0;
+        let mut bills = 0;
+        for item in &recurring {
+            for due in occurrences_between(item, start, end) {
+                if item.amount >= 0 {
+                    income += item.amount;
+                } else if !(is_current && is_recurring_bill_paid(conn, item, due)?) {
+                    bills -= item.amount;
+                }
+            }
+        }
+
+        let mut budgeted_spending = 0;
+        let month_key = month_bounds(index).0.format("%Y-%m-%d").to_string();
+        for (category_id, category_type, monthly, budget_id) in &budgets {
+            let monthly = month_amounts
+                .get(&(budget_id.clone(), month_key.clone()))
+                .copied()
+                .unwrap_or(*monthly);
+            let so_far = if is_current {
+                month_to_date.get(category_id).copied().unwrap_or(0)
+            } else {
+                0
+            };
+            if category_type == "income" {
+                income += (monthly - so_far).max(0);
+            } else {
+                budgeted_spending += (monthly + so_far).max(0);
+            }
+        }
+
+        let planned_purchases = purchases_by_month.get(&index).copied().unwrap_or(0);
+        let starting = balance;
+        balance += income - bills - budgeted_spending - planned_purchases;
+
+        forecast_months.push(ForecastMonth {
+            month: start.format("%Y-%m").to_string(),
+            starting_balance: starting,
+            income,
+            bills,
+            budgeted_spending,
+            planned_purchases,
+            ending_balance: balance,
+        });
+    }
+
+    Ok(CashFlowForecast {
+        as_of: today_str,
+        starting_balance,
+        months: forecast_months,
+    })
+}
+
+#[tauri::command]
+pub fn get_cash_flow_forecast(
+    months: Option<u32>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<CashFlowForecast> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let months = months.unwrap_or(DEFAULT_FORECAST_MONTHS);
+    if months == 0 || months > MAX_FORECAST_MONTHS {
+        return Err(AppError::Validation(format!(
+            "Forecast length must be between 1 and {} months",
+            MAX_FORECAST_MONTHS
+        )));
+    }
+
+    build_forecast(conn, chrono::Local::now().date_naive(), months, None)
+}
+
+/// How much cash can be spent today without the forecast balance dropping below zero in
+/// any month, covering upcoming bills, budgets and every open planned purchase.
+#[tauri::command]
+pub fn get_safe_to_spend(db: State<'_, Mutex<Database>>) -> Result<SafeToSpend> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let today = chrono::Local::now().date_naive();
+
+    // Look far enough ahead to reach the last planned purchase
+    let last_purchase: Option<String> = conn.query_row(
+        "SELECT MAX(desired_date) FROM planned_purchases WHERE is_purchased = 0",
+        [],
+        |row| row.get(0),
+    )?;
+    let months = last_purchase
+        .and_then(|d| NaiveDate::parse_from_str(&d, "%Y-%m-%d").ok())
+        .map_or(0, |d| month_index(d) - month_index(today) + 1)
+        .clamp(DEFAULT_FORECAST_MONTHS as i32, MAX_FORECAST_MONTHS as i32) as u32;
+
+    let forecast = build_forecast(conn, today, months, None)?;
+    let lowest = forecast
+        .months
+        .iter()
+        .min_by_key(|m| m.ending_balance)
+        .cloned();
+
+    let (lowest_balance, lowest_balance_month) = lowest
+        .map(|m| (m.ending_balance, m.month))
+        .unwrap_or((forecast.starting_balance, today.format("%Y-%m").to_string()));
+
+    Ok(SafeToSpend {
+        as_of: forecast.as_of,
+        cash_balance: forecast.starting_balance,
+        lowest_balance,
+        lowest_balance_month,
+        planned_purchases: forecast.months.iter().map(|m| m.planned_purchases).sum(),
+        safe_to_spend: forecast.starting_balance.min(lowest_balance).max(0),
+    })
+}
@@ -0,0 +1,517 @@
+use crate::commands::settings::read_setting;
+use crate::db::Database;
+use crate::error::{AppError, Result};
+use crate::events::{emit_changed, ACCOUNTS_CHANGED, BUDGETS_CHANGED, TRANSACTIONS_CHANGED};
+use chrono::NaiveDate;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, State};
+use uuid::Uuid;
+
+/// Currency totals are reported in when the `base_currency` setting isn't set
+pub(crate) const DEFAULT_BASE_CURRENCY: &str = "USD";
+
+/// SQL for the base currency, as `base_currency` reads it
+const BASE_CURRENCY_SQL: &str =
+    "COALESCE((SELECT value FROM settings WHERE key = 'base_currency'), 'USD')";
+
+/// Setting holding the URL rates are fetched from. `{date}`, `{base}` and `{symbols}` are
+/// filled in with the rate date, the base currency and a comma-separated list of the
+/// currencies wanted.
+const FX_PROVIDER_URL_SETTING: &str = "fx_provider_url";
+/// The European Central Bank's reference rates, via Frankfurter
+pub(crate) const DEFAULT_FX_PROVIDER_URL: &str =
+    "https://api.frankfurter.app/{date}?from={base}&to={symbols}";
+/// Setting that fetches the day's rates each time the database is unlocked. Off unless set
+/// to "true", so the app stays offline by default.
+const FX_AUTO_FETCH_SETTING: &str = "fx_auto_fetch";
+/// `source` of rates entered by hand
+const MANUAL_SOURCE: &str = "manual";
+
+/// An exchange rate on a date: one unit of `from_currency` buys `rate` of `to_currency`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FxRate {
+    pub id: String,
+    pub from_currency: String,
+    pub to_currency: String,
+    pub rate_date: String,
+    pub rate: f64,
+    /// "manual", or the host of the provider the rate was fetched from
+    pub source: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+const FX_RATE_COLUMNS: &str =
+    "id, from_currency, to_currency, rate_date, rate, source, created_at, updated_at";
+
+fn fx_rate_from_row(row: &rusqlite::Row) -> rusqlite::Result<FxRate> {
+    Ok(FxRate {
+        id: row.get(0)?,
+        from_currency: row.get(1)?,
+        to_currency: row.get(2)?,
+        rate_date: row.get(3)?,
+        rate: row.get(4)?,
+        source: row.get(5)?,
+        created_at: row.get(6)?,
+        updated_at: row.get(7)?,
+    })
+}
+
+/// A three-letter ISO 4217 currency code, uppercased
+pub(crate) fn validate_currency(code: &str) -> Result<String> {
+    let code = code.trim().to_uppercase();
+    if code.len() != 3 || !code.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(AppError::Validation(format!(
+            "Invalid currency: {}. Use a three-letter code like USD",
+            code
+        )));
+    }
+    Ok(code)
+}
+
+/// The currency net worth, budgets and reports are totalled in
+pub(crate) fn base_currency(conn: &Connection) -> Result<String> {
+    Ok(read_setting(conn, "base_currency")?.unwrap_or_else(|| DEFAULT_BASE_CURRENCY.to_string()))
+}
+
+/// SQL expression converting `amount`, held in `currency`, into the base currency at the
+/// rate nearest `date`: the latest on or before it, else the earliest after. Amounts in
+/// the base currency, or in a currency with no rate to it, are left as they are; see
+/// `missing_fx_currencies`. A manual rate wins over a fetched one on the same date.
+pub(crate) fn base_amount_sql(amount: &str, currency: &str, date: &str) -> String {
+    let pair = format!("r.from_currency = {currency} AND r.to_currency = {BASE_CURRENCY_SQL}");
+    format!(
+        "CAST(ROUND({amount} * COALESCE(
+             (SELECT r.rate FROM fx_rate_pairs r WHERE {pair} AND r.rate_date <= {date}
+              ORDER BY r.rate_date DESC, r.is_manual DESC LIMIT 1),
+             (SELECT r.rate FROM fx_rate_pairs r WHERE {pair} AND r.rate_date > {date}
+              ORDER BY r.rate_date, r.is_manual DESC LIMIT 1),
+             1.0
+         )) AS INTEGER)"
+    )
+}
+
+/// Currencies an account is held in that have no rate to the base currency on any date.
+/// Amounts in them are counted unconverted, so totals that include them are off.
+pub(crate) fn missing_fx_currencies(conn: &Connection) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT DISTINCT a.currency FROM accounts a
+         WHERE a.deleted_at IS NULL AND a.currency != {BASE_CURRENCY_SQL}
+           AND NOT EXISTS (
+               SELECT 1 FROM fx_rate_pairs r
+               WHERE r.from_currency = a.currency AND r.to_currency = {BASE_CURRENCY_SQL}
+           )
+         ORDER BY a.currency"
+    ))?;
+    let currencies = stmt
+        .query_map([], |row| row.get(0))?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(currencies)
+}
+
+/// SQL expression for the amount of the transaction `t` in the base currency, converted
+/// at the rate on its date
+pub(crate) fn transaction_base_amount() -> String {
+    base_amount_sql(
+        "t.amount",
+        "(SELECT currency FROM accounts WHERE id = t.account_id)",
+        "t.date",
+    )
+}
+
+#[tauri::command]
+pub fn get_base_currency(db: State<'_, Mutex<Database>>) -> Result<String> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    base_currency(conn)
+}
+
+/// Change the currency totals are reported in. Balances and reports are converted on the
+/// fly, so nothing stored changes.
+#[tauri::command]
+pub fn set_base_currency(
+    currency: String,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<String> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let currency = validate_currency(&currency)?;
+    conn.execute(
+        "INSERT INTO settings (key, value, updated_at) VALUES ('base_currency', ?1, datetime('now'))
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+        [&currency],
+    )?;
+
+    emit_changed(
+        &app,
+        &[ACCOUNTS_CHANGED, TRANSACTIONS_CHANGED, BUDGETS_CHANGED],
+    );
+
+    Ok(currency)
+}
+
+/// Currencies accounts are held in that have no exchange rate to the base currency yet
+#[tauri::command]
+pub fn list_missing_fx_rates(db: State<'_, Mutex<Database>>) -> Result<Vec<String>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    missing_fx_currencies(conn)
+}
+
+/// Exchange rates, optionally only those involving one currency, latest date first
+#[tauri::command]
+pub fn list_fx_rates(
+    currency: Option<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<FxRate>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let currency = currency.map(|c| c.trim().to_uppercase());
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM fx_rates
+         WHERE ?1 IS NULL OR from_currency = ?1 OR to_currency = ?1
+         ORDER BY rate_date DESC, from_currency, to_currency",
+        FX_RATE_COLUMNS
+    ))?;
+    let rates = stmt
+        .query_map([currency], fx_rate_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(rates)
+}
+
+/// Record the exchange rate between two currencies on a date by hand, replacing any rate
+/// already recorded or fetched for that pair and date
+#[tauri::command]
+pub fn set_fx_rate(
+    data: serde_json::Value,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<FxRate> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let from_currency = validate_currency(data["fromCurrency"].as_str().unwrap_or(""))?;
+    let to_currency = validate_currency(data["toCurrency"].as_str().unwrap_or(""))?;
+    if from_currency == to_currency {
+        return Err(AppError::Validation(
+            "An exchange rate needs two different currencies".to_string(),
+        ));
+    }
+    let date = data["date"].as_str().unwrap_or("");
+    if NaiveDate::parse_from_str(date, "%Y-%m-%d").is_err() {
+        return Err(AppError::Validation(format!("Invalid rate date: {}", date)));
+    }
+    let rate = data["rate"].as_f64().unwrap_or(0.0);
+    if !(rate > 0.0 && rate.is_finite()) {
+        return Err(AppError::Validation(
+            "Exchange rate must be greater than zero".to_string(),
+        ));
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    conn.execute(
+        "INSERT INTO fx_rates (id, from_currency, to_currency, rate_date, rate, source,
+                created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+         ON CONFLICT(from_currency, to_currency, rate_date) DO UPDATE SET
+            rate = excluded.rate,
+            source = excluded.source,
+            updated_at = excluded.updated_at",
+        rusqlite::params![
+            Uuid::new_v4().to_string(),
+            from_currency,
+            to_currency,
+            date,
+            rate,
+            MANUAL_SOURCE,
+            now,
+            now,
+        ],
+    )?;
+
+    emit_changed(
+        &app,
+        &[ACCOUNTS_CHANGED, TRANSACTIONS_CHANGED, BUDGETS_CHANGED],
+    );
+
+    Ok(conn.query_row(
+        &format!(
+            "SELECT {} FROM fx_rates
+             WHERE from_currency = ?1 AND to_currency = ?2 AND rate_date = ?3",
+            FX_RATE_COLUMNS
+        ),
+        [&from_currency, &to_currency, date],
+        fx_rate_from_row,
+    )?)
+}
+
+#[tauri::command]
+pub fn delete_fx_rate(id: String, app: AppHandle, db: State<'_, Mutex<Database>>) -> Result<()> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let deleted = conn.execute("DELETE FROM fx_rates WHERE id = ?1", [&id])?;
+    if deleted == 0 {
+        return Err(AppError::NotFound("Exchange rate not found".to_string()));
+    }
+
+    emit_changed(
+        &app,
+        &[ACCOUNTS_CHANGED, TRANSACTIONS_CHANGED, BUDGETS_CHANGED],
+    );
+
+    Ok(())
+}
+
+/// A provider's answer: rates for a date, each one unit of `base` in another currency.
+/// Frankfurter and exchangerate.host, among others, answer in this shape.
+#[derive(Debug, Deserialize)]
+struct ProviderRates {
+    base: Option<String>,
+    date: Option<String>,
+    rates: HashMap<String, f64>,
+}
+
+/// What to ask the provider for
+struct FxRateRequest {
+    url: String,
+    base: String,
+    date: String,
+    source: String,
+}
+
+/// The provider URL with its placeholders filled in
+fn provider_url(template: &str, date: &str, base: &str, symbols: &[String]) -> String {
+    template
+        .replace("{date}", date)
+        .replace("{base}", base)
+        .replace("{symbols}", &symbols.join(","))
+}
+
+/// The host a URL points at, recorded as the source of the rates fetched from it
+fn provider_host(url: &str) -> String {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    rest.split(['/', '?']).next().unwrap_or(rest).to_string()
+}
+
+/// The request for `date`'s rates from the base currency to every other currency an
+/// account is held in, or None when every account is in the base currency
+fn fx_rate_request(conn: &Connection, date: &str) -> Result<Option<FxRateRequest>> {
+    let base = base_currency(conn)?;
+    let mut stmt = conn.prepare(
+        "SELECT DISTINCT currency FROM accounts
+         WHERE deleted_at IS NULL AND currency != ?1
+         ORDER BY currency",
+    )?;
+    let symbols: Vec<String> = stmt
+        .query_map([&base], |row| row.get(0))?
+        .filter_map(|r| r.ok())
+        .collect();
+    if symbols.is_empty() {
+        return Ok(None);
+    }
+
+    let template = read_setting(conn, FX_PROVIDER_URL_SETTING)?
+        .filter(|url| !url.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_FX_PROVIDER_URL.to_string());
+    let url = provider_url(template.trim(), date, &base, &symbols);
+    Ok(Some(FxRateRequest {
+        source: provider_host(&url),
+        url,
+        base,
+        date: date.to_string(),
+    }))
+}
+
+async fn request_rates(url: &str) -> Result<ProviderRates> {
+    let failed =
+        |e: reqwest::Error| AppError::Other(format!("Couldn't fetch exchange rates: {}", e));
+    reqwest::get(url)
+        .await
+        .map_err(failed)?
+        .error_for_status()
+        .map_err(failed)?
+        .json()
+        .await
+        .map_err(failed)
+}
+
+/// Store fetched rates under the date the provider quoted them for, which falls back to the
+/// last business day on weekends and holidays. Rates entered by hand for the same date are
+/// kept. Returns the rates stored.
+fn save_fetched_rates(
+    conn: &Connection,
+    request: &FxRateRequest,
+    fetched: &ProviderRates,
+) -> Result<Vec<FxRate>> {
+    let base = match &fetched.base {
+        Some(base) => validate_currency(base)?,
+        None => request.base.clone(),
+    };
+    let date = fetched.date.as_deref().unwrap_or(&request.date);
+    if NaiveDate::parse_from_str(date, "%Y-%m-%d").is_err() {
+        return Err(AppError::Other(format!(
+            "Exchange rate provider returned an invalid date: {}",
+            date
+        )));
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let tx = conn.unchecked_transaction()?;
+    let mut saved = Vec::new();
+    for (currency, rate) in &fetched.rates {
+        let Ok(currency) = validate_currency(currency) else {
+            continue;
+        };
+        if currency == base || !(*rate > 0.0 && rate.is_finite()) {
+            continue;
+        }
+        tx.execute(
+            "INSERT INTO fx_rates (id, from_currency, to_currency, rate_date, rate, source,
+                    created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+             ON CONFLICT(from_currency, to_currency, rate_date) DO UPDATE SET
+                rate = excluded.rate,
+                source = excluded.source,
+                updated_at = excluded.updated_at
+             WHERE fx_rates.source != 'manual'",
+            rusqlite::params![
+                Uuid::new_v4().to_string(),
+                base,
+                currency,
+                date,
+                rate,
+                request.source,
+                now,
+                now,
+            ],
+        )?;
+        saved.push(tx.query_row(
+            &format!(
+                "SELECT {} FROM fx_rates
+                 WHERE from_currency = ?1 AND to_currency = ?2 AND rate_date = ?3",
+                FX_RATE_COLUMNS
+            ),
+            [&base, &currency, date],
+            fx_rate_from_row,
+        )?);
+    }
+    tx.commit()?;
+
+    saved.sort_by(|a, b| a.to_currency.cmp(&b.to_currency));
+    Ok(saved)
+}
+
+/// Fetch and store `date`'s rates. The database is only locked around the request, not
+/// while it's in flight.
+async fn fetch_and_save_rates(app: &AppHandle, date: &str) -> Result<Vec<FxRate>> {
+    let request = {
+        let db = app.state::<Mutex<Database>>();
+        let database = db.lock().unwrap();
+        fx_rate_request(database.get_connection()?, date)?
+    };
+    let Some(request) = request else {
+        return Ok(Vec::new());
+    };
+
+    let fetched = request_rates(&request.url).await?;
+
+    let db = app.state::<Mutex<Database>>();
+    let database = db.lock().unwrap();
+    let saved = save_fetched_rates(database.get_connection()?, &request, &fetched)?;
+    if !saved.is_empty() {
+        emit_changed(
+            app,
+            &[ACCOUNTS_CHANGED, TRANSACTIONS_CHANGED, BUDGETS_CHANGED],
+        );
+    }
+    Ok(saved)
+}
+
+/// Fetch the exchange rates for a date (today by default) from the configured provider for
+/// every currency an account is held in, adding them to the rate history so reports keep
+/// converting each transaction at the rate of its date
+#[tauri::command]
+pub async fn fetch_fx_rates(date: Option<String>, app: AppHandle) -> Result<Vec<FxRate>> {
+    let date = date.unwrap_or_else(|| chrono::Local::now().format("%Y-%m-%d").to_string());
+    if NaiveDate::parse_from_str(&date, "%Y-%m-%d").is_err() {
+        return Err(AppError::Validation(format!("Invalid rate date: {}", date)));
+    }
+
+    fetch_and_save_rates(&app, &date).await
+}
+
+/// Fetch today's rates in the background once the database is unlocked, when auto-fetch is
+/// on. A failed fetch is left for the next unlock, and earlier rates stay in use meanwhile.
+pub(crate) fn spawn_fx_rate_fetch(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let enabled = {
+            let db = app.state::<Mutex<Database>>();
+            let database = db.lock().unwrap();
+            database
+                .get_connection()
+                .and_then(|conn| read_setting(conn, FX_AUTO_FETCH_SETTING))
+                .is_ok_and(|value| value.as_deref() == Some("true"))
+        };
+        if enabled {
+            let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+            let _ = fetch_and_save_rates(&app, &today).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_provider_url() {
+        let symbols = ["EUR".to_string(), "GBP".to_string()];
+        let url = provider_url(DEFAULT_FX_PROVIDER_URL, "2024-03-15", "USD", &symbols);
+        assert_eq!(
+            url,
+            "https://api.frankfurter.app/2024-03-15?from=USD&to=EUR,GBP"
+        );
+        assert_eq!(provider_host(&url), "api.frankfurter.app");
+        assert_eq!(
+            provider_host("http://localhost:8080?base=USD"),
+            "localhost:8080"
+        );
+    }
+
+    #[test]
+    fn test_missing_fx_currencies() {
+        let conn = Database::in_memory();
+        conn.execute_batch(
+            "INSERT INTO accounts (id, name, account_type, currency) VALUES
+                 ('usd', 'Checking', 'checking', 'USD'),
+                 ('eur', 'Euro Savings', 'savings', 'EUR'),
+                 ('gbp', 'Pound Savings', 'savings', 'GBP'),
+                 ('jpy', 'Yen Savings', 'savings', 'JPY');
+             INSERT INTO fx_rates (id, from_currency, to_currency, rate_date, rate) VALUES
+                 ('r1', 'EUR', 'USD', '2024-01-02', 1.1),
+                 ('r2', 'USD', 'GBP', '2024-01-02', 0.8);",
+        )
+        .unwrap();
+        assert_eq!(missing_fx_currencies(&conn).unwrap(), vec!["JPY"]);
+
+        conn.execute(
+            "UPDATE accounts SET deleted_at = datetime('now') WHERE id = 'jpy'",
+            [],
+        )
+        .unwrap();
+        assert!(missing_fx_currencies(&conn).unwrap().is_empty());
+    }
+}
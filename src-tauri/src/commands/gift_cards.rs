@@ -0,0 +1,483 @@
+use crate::commands::accounts::refresh_available_balance;
+use crate::commands::month_close::ensure_transaction_unlocked;
+use crate::db::Database;
+use crate::error::{AppError, Result};
+use crate::events::{emit_changed, ACCOUNTS_CHANGED, GIFT_CARDS_CHANGED, TRANSACTIONS_CHANGED};
+use crate::models::{GiftCard, GiftCardUsage};
+use rusqlite::{Connection, OptionalExtension};
+use std::sync::Mutex;
+use tauri::{AppHandle, State};
+use uuid::Uuid;
+
+const GIFT_CARD_COLUMNS: &str = "id, name, merchant, initial_balance, balance, expiry_date,
+    notes, is_active, created_at, updated_at";
+
+fn gift_card_from_row(row: &rusqlite::Row) -> rusqlite::Result<GiftCard> {
+    Ok(GiftCard {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        merchant: row.get(2)?,
+        initial_balance: row.get(3)?,
+        balance: row.get(4)?,
+        expiry_date: row.get(5)?,
+        notes: row.get(6)?,
+        is_active: row.get(7)?,
+        created_at: row.get(8)?,
+        updated_at: row.get(9)?,
+    })
+}
+
+fn usage_from_row(row: &rusqlite::Row) -> rusqlite::Result<GiftCardUsage> {
+    Ok(GiftCardUsage {
+        id: row.get(0)?,
+        gift_card_id: row.get(1)?,
+        transaction_id: row.get(2)?,
+        amount: row.get(3)?,
+        created_at: row.get(4)?,
+    })
+}
+
+fn fetch_gift_card(conn: &Connection, id: &str) -> Result<GiftCard> {
+    conn.query_row(
+        &format!("SELECT {} FROM gift_cards WHERE id = ?1", GIFT_CARD_COLUMNS),
+        [id],
+        gift_card_from_row,
+    )
+    .map_err(|_| AppError::NotFound("Gift card not found".to_string()))
+}
+
+#[tauri::command]
+pub fn list_gift_cards(
+    include_inactive: Option<bool>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<GiftCard>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM gift_cards
+         WHERE is_active = 1 OR ?1
+         ORDER BY expiry_date IS NULL, expiry_date, name",
+        GIFT_CARD_COLUMNS
+    ))?;
+
+    let cards = stmt
+        .query_map([include_inactive.unwrap_or(false)], gift_card_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(cards)
+}
+
+#[tauri::command]
+pub fn create_gift_card(
+    data: serde_json::Value,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<GiftCard> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let name = data["name"].as_str().unwrap_or("").trim();
+    if name.is_empty() {
+        return Err(AppError::Validation(
+            "Gift card name is required".to_string(),
+        ));
+    }
+    let balance = data["balance"].as_i64().unwrap_or(0);
+    if balance < 0 {
+        return Err(AppError::Validation(
+            "Gift card balance cannot be negative".to_string(),
+        ));
+    }
+
+    let id = Uuid::new_v4().to_string();
+    let now = chrono::Utc::now().to_rfc3339();
+
+    conn.execute(
+        "INSERT INTO gift_cards (id, name, merchant, initial_balance, balance, expiry_date,
+                notes, is_active, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?4, ?5, ?6, 1, ?7, ?7)",
+        rusqlite::params![
+            id,
+            name,
+            data["merchant"].as_str(),
+            balance,
+            data["expiryDate"].as_str(),
+            data["notes"].as_str(),
+            now,
+        ],
+    )?;
+
+    emit_changed(&app, &[GIFT_CARDS_CHANGED]);
+
+    fetch_gift_card(conn, &id)
+}
+
+#[tauri::command]
+pub fn update_gift_card(
+    id: String,
+    data: serde_json::Value,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<GiftCard> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    if data["balance"].as_i64().is_some_and(|b| b < 0) {
+        return Err(AppError::Validation(
+            "Gift card balance cannot be negative".to_string(),
+        ));
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+
+    conn.execute(
+        "UPDATE gift_cards SET
+            name = COALESCE(?1, name),
+            merchant = COALESCE(?2, merchant),
+            balance = COALESCE(?3, balance),
+            expiry_date = COALESCE(?4, expiry_date),
+            notes = COALESCE(?5, notes),
+            is_active = COALESCE(?6, is_active),
+            updated_at = ?7
+         WHERE id = ?8",
+        rusqlite::params![
+            data["name"].as_str(),
+            data["merchant"].as_str(),
+            data["balance"].as_i64(),
+            data["expiryDate"].as_str(),
+            data["notes"].as_str(),
+            data["isActive"].as_bool(),
+            now,
+            id,
+        ],
+    )?;
+
+    emit_changed(&app, &[GIFT_CARDS_CHANGED]);
+
+    fetch_gift_card(conn, &id)
+}
+
+/// Delete a gift card. Purchases it paid towards are charged in full to their accounts
+/// again first, so deleting the card doesn't leave them reduced by its share.
+#[tauri::command]
+pub fn delete_gift_card(id: String, app: AppHandle, db: State<'_, Mutex<Database>>) -> Result<()> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let tx = conn.unchecked_transaction()?;
+    let mut stmt =
+        tx.prepare("SELECT transaction_id FROM gift_card_usages WHERE gift_card_id = ?1")?;
+    let transaction_ids = stmt
+        .query_map([&id], |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    drop(stmt);
+    for transaction_id in &transaction_ids {
+        ensure_transaction_unlocked(&tx, transaction_id)?;
+        return_gift_card_payment(&tx, transaction_id, &now)?;
+    }
+    tx.execute("DELETE FROM gift_cards WHERE id = ?1", [&id])?;
+    tx.commit()?;
+
+    emit_changed(
+        &app,
+        &[GIFT_CARDS_CHANGED, TRANSACTIONS_CHANGED, ACCOUNTS_CHANGED],
+    );
+
+    Ok(())
+}
+
+/// Give a transaction's gift card payment back to the card and charge the whole purchase
+/// to its account again. Returns the amount given back, or zero if the transaction wasn't
+/// paid with a gift card.
+pub(crate) fn return_gift_card_payment(
+    conn: &Connection,
+    transaction_id: &str,
+    now: &str,
+) -> Result<i64> {
+    let usage = conn
+        .query_row(
+            "SELECT id, gift_card_id, transaction_id, amount, created_at
+             FROM gift_card_usages WHERE transaction_id = ?1",
+            [transaction_id],
+            usage_from_row,
+        )
+        .optional()?;
+    let Some(usage) = usage else {
+        return Ok(0);
+    };
+
+    let account_id: String = conn.query_row(
+        "SELECT account_id FROM transactions WHERE id = ?1",
+        [transaction_id],
+        |row| row.get(0),
+    )?;
+    conn.execute(
+        "UPDATE gift_cards SET balance = balance + ?1, updated_at = ?2 WHERE id = ?3",
+        rusqlite::params![usage.amount, now, usage.gift_card_id],
+    )?;
+    conn.execute(
+        "UPDATE transactions SET amount = amount - ?1, updated_at = ?2 WHERE id = ?3",
+        rusqlite::params![usage.amount, now, transaction_id],
+    )?;
+    conn.execute(
+        "UPDATE accounts SET current_balance = current_balance - ?1, updated_at = ?2 WHERE id = ?3",
+        rusqlite::params![usage.amount, now, account_id],
+    )?;
+    refresh_available_balance(conn, &account_id)?;
+    conn.execute("DELETE FROM gift_card_usages WHERE id = ?1", [&usage.id])?;
+
+    Ok(usage.amount)
+}
+
+/// Pay part or all of a purchase from a gift card. The card's share comes off the card's
+/// balance and off the transaction, so the account and spending only carry what the
+/// account paid.
+fn pay_with_gift_card(
+    conn: &Connection,
+    transaction_id: String,
+    gift_card_id: Option<String>,
+) -> Result<GiftCardUsage> {
+    let (account_id, amount, payee, date): (String, i64, Option<String>, String) = conn
+        .query_row(
+            "SELECT account_id, amount, payee, date FROM transactions
+             WHERE id = ?1 AND deleted_at IS NULL",
+            [&transaction_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )
+        .map_err(|_| AppError::NotFound("Transaction not found".to_string()))?;
+
+    if amount >= 0 {
+        return Err(AppError::Validation(
+            "Only purchases can be paid with a gift card".to_string(),
+        ));
+    }
+    ensure_transaction_unlocked(conn, &transaction_id)?;
+
+    let already_marked: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM gift_card_usages WHERE transaction_id = ?1)",
+        [&transaction_id],
+        |row| row.get(0),
+    )?;
+    if already_marked {
+        return Err(AppError::Validation(
+            "Transaction is already marked as paid with a gift card".to_string(),
+        ));
+    }
+
+    let card = match gift_card_id {
+        Some(id) => fetch_gift_card(conn, &id)?,
+        None => {
+            let payee = payee.unwrap_or_default();
+            let id: Option<String> = conn
+                .query_row(
+                    "SELECT id FROM gift_cards
+                     WHERE is_active = 1 AND balance > 0 AND merchant IS NOT NULL
+                       AND ?1 LIKE '%' || merchant || '%'
+                       AND (expiry_date IS NULL OR expiry_date >= ?2)
+                     ORDER BY expiry_date IS NULL, expiry_date
+                     LIMIT 1",
+                    rusqlite::params![payee, date],
+                    |row| row.get(0),
+                )
+                .optional()?;
+
+            match id {
+                Some(id) => fetch_gift_card(conn, &id)?,
+                None => {
+                    return Err(AppError::NotFound(format!(
+                        "No gift card with a balance matches \"{}\"",
+                        payee
+                    )))
+                }
+            }
+        }
+    };
+
+    if card.balance <= 0 {
+        return Err(AppError::Validation(format!(
+            "Gift card \"{}\" has no balance left",
+            card.name
+        )));
+    }
+
+    let deducted = card.balance.min(-amount);
+    let id = Uuid::new_v4().to_string();
+    let now = chrono::Utc::now().to_rfc3339();
+
+    let tx = conn.unchecked_transaction()?;
+
+    tx.execute(
+        "INSERT INTO gift_card_usages (id, gift_card_id, transaction_id, amount, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        rusqlite::params![id, card.id, transaction_id, deducted, now],
+    )?;
+    tx.execute(
+        "UPDATE gift_cards SET balance = balance - ?1, updated_at = ?2 WHERE id = ?3",
+        rusqlite::params![deducted, now, card.id],
+    )?;
+    tx.execute(
+        "UPDATE transactions SET amount = amount + ?1, updated_at = ?2 WHERE id = ?3",
+        rusqlite::params![deducted, now, transaction_id],
+    )?;
+    tx.execute(
+        "UPDATE accounts SET current_balance = current_balance + ?1, updated_at = ?2 WHERE id = ?3",
+        rusqlite::params![deducted, now, account_id],
+    )?;
+    refresh_available_balance(&tx, &account_id)?;
+
+    tx.commit()?;
+
+    Ok(GiftCardUsage {
+        id,
+        gift_card_id: card.id,
+        transaction_id,
+        amount: deducted,
+        created_at: now,
+    })
+}
+
+/// Mark a purchase as paid from a gift card, deducting it from the card's balance.
+/// Without a `gift_card_id`, the active, unexpired card with a balance whose merchant
+/// matches the transaction's payee is used, soonest-expiring first. A purchase larger
+/// than the remaining balance uses up the card and the rest stays with the bank account.
+#[tauri::command]
+pub fn mark_paid_with_gift_card(
+    transaction_id: String,
+    gift_card_id: Option<String>,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<GiftCardUsage> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let usage = pay_with_gift_card(conn, transaction_id, gift_card_id)?;
+
+    emit_changed(
+        &app,
+        &[GIFT_CARDS_CHANGED, TRANSACTIONS_CHANGED, ACCOUNTS_CHANGED],
+    );
+
+    Ok(usage)
+}
+
+/// Undo `mark_paid_with_gift_card`, returning the deducted amount to the card
+#[tauri::command]
+pub fn unmark_paid_with_gift_card(
+    transaction_id: String,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<()> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    ensure_transaction_unlocked(conn, &transaction_id)?;
+
+    let tx = conn.unchecked_transaction()?;
+    return_gift_card_payment(&tx, &transaction_id, &chrono::Utc::now().to_rfc3339())?;
+    tx.commit()?;
+
+    emit_changed(
+        &app,
+        &[GIFT_CARDS_CHANGED, TRANSACTIONS_CHANGED, ACCOUNTS_CHANGED],
+    );
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_gift_card_usages(
+    gift_card_id: String,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<GiftCardUsage>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let mut stmt = conn.prepare(
+        "SELECT u.id, u.gift_card_id, u.transaction_id, u.amount, u.created_at
+         FROM gift_card_usages u
+         JOIN transactions t ON t.id = u.transaction_id
+         WHERE u.gift_card_id = ?1
+         ORDER BY t.date DESC",
+    )?;
+
+    let usages = stmt
+        .query_map([&gift_card_id], usage_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(usages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::transactions::{insert_transaction, soft_delete_transaction};
+
+    fn balance(conn: &Connection, table: &str, id: &str) -> i64 {
+        conn.query_row(
+            &format!("SELECT balance FROM {} WHERE id = ?1", table),
+            [id],
+            |row| row.get(0),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_gift_card_payment_is_not_counted_twice() {
+        let conn = Database::in_memory();
+        conn.execute(
+            "INSERT INTO accounts (id, name, account_type, current_balance, opening_balance)
+             VALUES ('checking', 'Checking', 'checking', 100000, 100000)",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO gift_cards (id, name, merchant, initial_balance, balance)
+             VALUES ('card', 'Bookshop card', 'Bookshop', 2000, 2000)",
+            [],
+        )
+        .unwrap();
+        let account_balance = |conn: &Connection| {
+            conn.query_row(
+                "SELECT current_balance FROM accounts WHERE id = 'checking'",
+                [],
+                |row| row.get::<_, i64>(0),
+            )
+            .unwrap()
+        };
+
+        let purchase = insert_transaction(
+            &conn,
+            &serde_json::json!({
+                "accountId": "checking",
+                "date": "2024-03-01",
+                "amount": -3000,
+                "payee": "Bookshop",
+            }),
+        )
+        .unwrap();
+        assert_eq!(account_balance(&conn), 97000);
+
+        // The card covers 20.00 of the 30.00 purchase, so the account only pays 10.00
+        let usage = pay_with_gift_card(&conn, purchase.clone(), None).unwrap();
+        assert_eq!(usage.amount, 2000);
+        assert_eq!(balance(&conn, "gift_cards", "card"), 0);
+        assert_eq!(account_balance(&conn), 99000);
+        let amount: i64 = conn
+            .query_row(
+                "SELECT amount FROM transactions WHERE id = ?1",
+                [&purchase],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(amount, -1000);
+
+        // Deleting the purchase gives the card its balance back
+        soft_delete_transaction(&conn, &purchase, "2024-03-02T00:00:00+00:00").unwrap();
+        assert_eq!(account_balance(&conn), 100000);
+        assert_eq!(balance(&conn, "gift_cards", "card"), 2000);
+    }
+}
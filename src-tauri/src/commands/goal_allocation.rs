@@ -0,0 +1,181 @@
+use crate::commands::goal_milestones::raise_goal_milestones;
+use crate::commands::goals::{add_goal_contribution, goal_from_row, GOAL_COLUMNS};
+use crate::db::Database;
+use crate::error::{AppError, Result};
+use crate::events::{emit_changed, GOALS_CHANGED};
+use crate::models::Goal;
+use rusqlite::Connection;
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::{AppHandle, State};
+
+const ALLOCATION_STRATEGIES: &[&str] = &["priority", "weighted"];
+
+/// One goal's share of a surplus
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SurplusAllocation {
+    pub goal_id: String,
+    pub goal_name: String,
+    pub amount: i64,
+}
+
+/// A lump sum split across active goals
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SurplusPlan {
+    pub strategy: String,
+    /// In priority order, leaving out goals that get nothing
+    pub allocations: Vec<SurplusAllocation>,
+    /// What's left once every goal is fully funded
+    pub unallocated: i64,
+}
+
+/// Read a goal's `allocationWeight` input, which has to be at least 1 when given
+pub(crate) fn input_allocation_weight(data: &serde_json::Value) -> Result<Option<i64>> {
+    match data["allocationWeight"].as_i64() {
+        Some(weight) if weight < 1 => Err(AppError::Validation(
+            "Allocation weight must be at least 1".to_string(),
+        )),
+        weight => Ok(weight),
+    }
+}
+
+/// Split `amount` across goals by strategy, never giving a goal more than it still needs.
+/// `priority` funds each goal fully before moving on to the next; `weighted` shares the
+/// amount out by allocation weight, passing on what a filled goal can't take. Returns each
+/// goal's share in the goals' order.
+fn split_surplus(goals: &[Goal], amount: i64, strategy: &str) -> Vec<i64> {
+    let mut needs: Vec<i64> = goals
+        .iter()
+        .map(|g| (g.target_amount - g.current_amount).max(0))
+        .collect();
+    let mut shares = vec![0; goals.len()];
+    let mut left = amount;
+
+    if strategy == "priority" {
+        for (share, need) in shares.iter_mut().zip(&needs) {
+            *share = left.min(*need);
+            left -= *share;
+        }
+        return shares;
+    }
+
+    let mut open: Vec<usize> = (0..goals.len()).filter(|i| needs[*i] > 0).collect();
+    while left > 0 && !open.is_empty() {
+        let total_weight: i64 = open
+            .iter()
+            .map(|i| goals[*i].allocation_weight as i64)
+            .sum();
+        let mut offers: Vec<i64> = open
+            .iter()
+            .map(|i| left * goals[*i].allocation_weight as i64 / total_weight)
+            .collect();
+        // Cents lost to rounding go to the highest priority goals
+        let rounding = left - offers.iter().sum::<i64>();
+        for offer in offers.iter_mut().take(rounding as usize) {
+            *offer += 1;
+        }
+
+        let mut still_open = Vec::new();
+        for (i, offer) in open.iter().zip(offers) {
+            let given = offer.min(needs[*i]);
+            shares[*i] += given;
+            needs[*i] -= given;
+            left -= given;
+            if needs[*i] > 0 {
+                still_open.push(*i);
+            }
+        }
+        open = still_open;
+    }
+    shares
+}
+
+fn plan_surplus(conn: &Connection, amount: i64, strategy: Option<&str>) -> Result<SurplusPlan> {
+    if amount <= 0 {
+        return Err(AppError::Validation(
+            "Surplus amount must be greater than zero".to_string(),
+        ));
+    }
+    let strategy = strategy.unwrap_or("priority");
+    if !ALLOCATION_STRATEGIES.contains(&strategy) {
+        return Err(AppError::Validation(format!(
+            "Invalid allocation strategy: {}",
+            strategy
+        )));
+    }
+
+    // Spending limits aren't saved toward, so they never take a share
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM goals
+         WHERE deleted_at IS NULL AND is_archived = 0 AND is_achieved = 0
+           AND goal_type != 'spending_limit'
+           AND current_amount < target_amount
+         ORDER BY priority DESC, target_date ASC NULLS LAST, created_at ASC",
+        GOAL_COLUMNS
+    ))?;
+    let goals: Vec<Goal> = stmt
+        .query_map([], goal_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let shares = split_surplus(&goals, amount, strategy);
+    let allocations: Vec<SurplusAllocation> = goals
+        .iter()
+        .zip(shares)
+        .filter(|(_, share)| *share > 0)
+        .map(|(goal, share)| SurplusAllocation {
+            goal_id: goal.id.clone(),
+            goal_name: goal.name.clone(),
+            amount: share,
+        })
+        .collect();
+    let unallocated = amount - allocations.iter().map(|a| a.amount).sum::<i64>();
+
+    Ok(SurplusPlan {
+        strategy: strategy.to_string(),
+        allocations,
+        unallocated,
+    })
+}
+
+/// How a lump sum would be split across active goals, without contributing it
+#[tauri::command]
+pub fn preview_surplus_allocation(
+    amount: i64,
+    strategy: Option<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<SurplusPlan> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    plan_surplus(conn, amount, strategy.as_deref())
+}
+
+/// Split a lump sum across active goals, by priority unless `weighted` is asked for, and
+/// contribute each share in one transaction
+#[tauri::command]
+pub fn allocate_surplus(
+    amount: i64,
+    strategy: Option<String>,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<SurplusPlan> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let plan = plan_surplus(conn, amount, strategy.as_deref())?;
+    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+
+    let tx = conn.unchecked_transaction()?;
+    for allocation in &plan.allocations {
+        add_goal_contribution(&tx, &allocation.goal_id, allocation.amount, &today, None)?;
+    }
+    tx.commit()?;
+
+    raise_goal_milestones(&app, conn);
+    emit_changed(&app, &[GOALS_CHANGED]);
+
+    Ok(plan)
+}
@@ -0,0 +1,205 @@
+use crate::commands::goals::{goal_from_row, GOAL_COLUMNS};
+use crate::db::Database;
+use crate::error::{AppError, Result};
+use crate::events::GOAL_MILESTONE;
+use crate::models::Goal;
+use rusqlite::Connection;
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, State};
+
+/// Milestones for a goal that hasn't set its own
+pub(crate) const DEFAULT_GOAL_MILESTONES: [u32; 4] = [25, 50, 75, 100];
+
+/// A goal reaching one of its milestone percentages
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GoalMilestone {
+    pub goal_id: String,
+    pub goal_name: String,
+    pub percent: u32,
+    /// The goal's saved amount and target when the milestone was reached
+    pub amount: i64,
+    pub target_amount: i64,
+    pub reached_at: String,
+}
+
+const GOAL_MILESTONE_COLUMNS: &str =
+    "m.goal_id, g.name, m.percent, m.amount, g.target_amount, m.reached_at";
+
+fn goal_milestone_from_row(row: &rusqlite::Row) -> rusqlite::Result<GoalMilestone> {
+    Ok(GoalMilestone {
+        goal_id: row.get(0)?,
+        goal_name: row.get(1)?,
+        percent: row.get(2)?,
+        amount: row.get(3)?,
+        target_amount: row.get(4)?,
+        reached_at: row.get(5)?,
+    })
+}
+
+/// Read a goal's `milestones` input as stored JSON, sorted without repeats. `None` when
+/// it wasn't given; `Some(None)` when null, which goes back to the default milestones.
+pub(crate) fn input_goal_milestones(data: &serde_json::Value) -> Result<Option<Option<String>>> {
+    if data.get("milestones").is_none() {
+        return Ok(None);
+    }
+    if data["milestones"].is_null() {
+        return Ok(Some(None));
+    }
+    let mut milestones: Vec<u32> = serde_json::from_value(data["milestones"].clone())
+        .map_err(|e| AppError::Validation(format!("Invalid milestones: {}", e)))?;
+    if milestones.iter().any(|m| *m == 0 || *m > 100) {
+        return Err(AppError::Validation(
+            "Milestones must be between 1% and 100%".to_string(),
+        ));
+    }
+    milestones.sort_unstable();
+    milestones.dedup();
+    Ok(Some(Some(serde_json::to_string(&milestones)?)))
+}
+
+/// Bring the reached milestones of one goal, or of every goal, up to date with their
+/// saved amounts. Milestones the goal has fallen back below, or that were taken off its
+/// list, are cleared. Returns the milestones newly reached.
+pub(crate) fn check_goal_milestones(
+    conn: &Connection,
+    goal_id: Option<&str>,
+) -> Result<Vec<GoalMilestone>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM goals
+         WHERE deleted_at IS NULL AND is_archived = 0 AND target_amount > 0
+           AND (?1 IS NULL OR id = ?1)",
+        GOAL_COLUMNS
+    ))?;
+    let goals: Vec<Goal> = stmt
+        .query_map([goal_id], goal_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let mut reached = Vec::new();
+    for goal in &goals {
+        let is_reached =
+            |percent: u32| goal.current_amount * 100 >= goal.target_amount * percent as i64;
+
+        let mut stmt = conn.prepare("SELECT percent FROM goal_milestones WHERE goal_id = ?1")?;
+        let recorded: Vec<u32> = stmt
+            .query_map([&goal.id], |row| row.get(0))?
+            .filter_map(|r| r.ok())
+            .collect();
+        for percent in &recorded {
+            if !is_reached(*percent) || !goal.milestones.contains(percent) {
+                conn.execute(
+                    "DELETE FROM goal_milestones WHERE goal_id = ?1 AND percent = ?2",
+                    rusqlite::params![goal.id, percent],
+                )?;
+            }
+        }
+
+        for percent in &goal.milestones {
+            if !is_reached(*percent) || recorded.contains(percent) {
+                continue;
+            }
+            conn.execute(
+                "INSERT INTO goal_milestones (goal_id, percent, amount, reached_at)
+                 VALUES (?1, ?2, ?3, ?4)",
+                rusqlite::params![goal.id, percent, goal.current_amount, now],
+            )?;
+            reached.push(GoalMilestone {
+                goal_id: goal.id.clone(),
+                goal_name: goal.name.clone(),
+                percent: *percent,
+                amount: goal.current_amount,
+                target_amount: goal.target_amount,
+                reached_at: now.clone(),
+            });
+        }
+    }
+
+    Ok(reached)
+}
+
+/// Check every goal's milestones after a change to its saved amount and let the frontend
+/// celebrate any newly reached. Like change events, a failed check never fails the
+/// command that made the change.
+pub(crate) fn raise_goal_milestones(app: &AppHandle, conn: &Connection) {
+    if let Ok(milestones) = check_goal_milestones(conn, None) {
+        if !milestones.is_empty() {
+            let _ = app.emit(GOAL_MILESTONE, milestones);
+        }
+    }
+}
+
+/// Milestones reached so far, for one goal or all of them, newest first
+#[tauri::command]
+pub fn list_goal_milestones(
+    goal_id: Option<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<GoalMilestone>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM goal_milestones m
+         JOIN goals g ON g.id = m.goal_id
+         WHERE g.deleted_at IS NULL AND (?1 IS NULL OR m.goal_id = ?1)
+         ORDER BY m.reached_at DESC, m.percent DESC",
+        GOAL_MILESTONE_COLUMNS
+    ))?;
+    let milestones = stmt
+        .query_map([goal_id], goal_milestone_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(milestones)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_milestones_passed_before_upgrade_are_not_celebrated() {
+        let conn = Database::in_memory();
+        conn.execute_batch(
+            "INSERT INTO goals (id, name, goal_type, target_amount, current_amount, milestones)
+             VALUES ('house', 'House', 'savings', 100000, 60000, NULL),
+                    ('trip', 'Trip', 'savings', 10000, 9000, '[10, 90, 95]');",
+        )
+        .unwrap();
+        conn.execute_batch(include_str!(
+            "../../migrations/052_goal_milestones_backfill.sql"
+        ))
+        .unwrap();
+
+        let backfilled: Vec<(String, u32)> = conn
+            .prepare("SELECT goal_id, percent FROM goal_milestones ORDER BY goal_id, percent")
+            .unwrap()
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .map(|r| r.unwrap())
+            .collect();
+        assert_eq!(
+            backfilled,
+            vec![
+                ("house".to_string(), 25),
+                ("house".to_string(), 50),
+                ("trip".to_string(), 10),
+                ("trip".to_string(), 90),
+            ]
+        );
+        assert!(check_goal_milestones(&conn, None).unwrap().is_empty());
+
+        conn.execute(
+            "UPDATE goals SET current_amount = 75000 WHERE id = 'house'",
+            [],
+        )
+        .unwrap();
+        let reached = check_goal_milestones(&conn, Some("house")).unwrap();
+        assert_eq!(
+            reached.iter().map(|m| m.percent).collect::<Vec<_>>(),
+            vec![75]
+        );
+    }
+}
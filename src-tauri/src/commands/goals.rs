@@ -1,41 +1,519 @@
+use crate::commands::debt_payoff::{save_goal_debts, validate_payoff_strategy};
+use crate::commands::forecast::month_index;
+use crate::commands::goal_allocation::input_allocation_weight;
+use crate::commands::goal_milestones::{
+    check_goal_milestones, input_goal_milestones, raise_goal_milestones, DEFAULT_GOAL_MILESTONES,
+};
+use crate::commands::round_ups::contribute_round_ups;
+use crate::commands::transactions::{insert_transaction, link_transaction_pair};
 use crate::db::Database;
-use crate::error::Result;
-use crate::models::Goal;
+use crate::error::{AppError, Result};
+use crate::events::{emit_changed, ACCOUNTS_CHANGED, GOALS_CHANGED, TRANSACTIONS_CHANGED};
+use crate::models::{Goal, GoalContribution, GoalContributionSchedule};
+use chrono::{Duration, Months, NaiveDate};
+use rusqlite::{Connection, OptionalExtension};
+use serde::Serialize;
 use std::sync::Mutex;
-use tauri::State;
+use tauri::{AppHandle, Manager, State};
 use uuid::Uuid;
 
+/// Columns selected for a full `Goal`, in the order `goal_from_row` expects
+pub(crate) const GOAL_COLUMNS: &str =
+    "id, name, goal_type, target_amount, current_amount, target_date,
+     linked_account_id, icon, color, is_achieved, achieved_at, created_at, updated_at,
+     contribution_account_id, payoff_strategy, monthly_payment, milestones, priority,
+     allocation_weight, is_archived, archived_at";
+
+pub(crate) fn goal_from_row(row: &rusqlite::Row) -> rusqlite::Result<Goal> {
+    Ok(Goal {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        goal_type: row.get(2)?,
+        target_amount: row.get(3)?,
+        current_amount: row.get(4)?,
+        target_date: row.get(5)?,
+        linked_account_id: row.get(6)?,
+        contribution_account_id: row.get(13)?,
+        payoff_strategy: row.get(14)?,
+        monthly_payment: row.get(15)?,
+        milestones: row
+            .get::<_, Option<String>>(16)?
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_else(|| DEFAULT_GOAL_MILESTONES.to_vec()),
+        priority: row.get(17)?,
+        allocation_weight: row.get(18)?,
+        icon: row.get(7)?,
+        color: row.get(8)?,
+        is_achieved: row.get(9)?,
+        achieved_at: row.get(10)?,
+        is_archived: row.get(19)?,
+        archived_at: row.get(20)?,
+        created_at: row.get(11)?,
+        updated_at: row.get(12)?,
+    })
+}
+
+pub(crate) const SCHEDULE_FREQUENCIES: &[&str] =
+    &["weekly", "biweekly", "monthly", "quarterly", "yearly"];
+
+const SCHEDULE_COLUMNS: &str = "goal_id, amount, frequency, start_date, next_date,
+    from_account_id, is_active, created_at, updated_at";
+
+fn schedule_from_row(row: &rusqlite::Row) -> rusqlite::Result<GoalContributionSchedule> {
+    Ok(GoalContributionSchedule {
+        goal_id: row.get(0)?,
+        amount: row.get(1)?,
+        frequency: row.get(2)?,
+        start_date: row.get(3)?,
+        next_date: row.get(4)?,
+        from_account_id: row.get(5)?,
+        is_active: row.get(6)?,
+        created_at: row.get(7)?,
+        updated_at: row.get(8)?,
+    })
+}
+
+/// Months of contributions averaged for the default projected monthly contribution
+const RECENT_CONTRIBUTION_MONTHS: u32 = 6;
+
+/// How far ahead a projection looks before giving up on a goal being reached
+const MAX_PROJECTION_MONTHS: u32 = 600;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GoalProjectionMonth {
+    pub month: String,
+    pub balance: i64,
+    pub interest: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GoalProjection {
+    pub goal_id: String,
+    pub monthly_contribution: i64,
+    /// The linked account's APY (percent) and how often it compounds; `None` when the
+    /// goal has no linked account with a rate, which leaves the projection linear
+    pub apy: Option<f64>,
+    pub compounding: Option<String>,
+    /// When the goal is reached with interest, or `None` if not within 50 years
+    pub projected_date: Option<String>,
+    /// When the goal is reached from contributions alone
+    pub linear_projected_date: Option<String>,
+    /// Projected balance at the goal's target date, if it has one
+    pub amount_at_target_date: Option<i64>,
+    /// Monthly contribution needed to reach the target by the target date; `None` without
+    /// a target date or once its month has come
+    pub required_monthly_contribution: Option<i64>,
+    /// Interest earned up to the projected date (or the end of the projection)
+    pub interest_earned: i64,
+    pub months: Vec<GoalProjectionMonth>,
+}
+
+pub(crate) fn fetch_goal(conn: &Connection, id: &str) -> Result<Goal> {
+    conn.query_row(
+        &format!(
+            "SELECT {} FROM goals WHERE id = ?1 AND deleted_at IS NULL",
+            GOAL_COLUMNS
+        ),
+        [id],
+        goal_from_row,
+    )
+    .map_err(|_| AppError::NotFound("Goal not found".to_string()))
+}
+
+/// The nth scheduled date counted from a schedule's start. Monthly steps are counted from
+/// the start itself, so a schedule starting on the 31st returns to it after shorter months.
+pub(crate) fn scheduled_date(start: NaiveDate, frequency: &str, n: u32) -> NaiveDate {
+    match frequency {
+        "weekly" => start + Duration::days(7 * n as i64),
+        "biweekly" => start + Duration::days(14 * n as i64),
+        "quarterly" => start + Months::new(3 * n),
+        "yearly" => start + Months::new(12 * n),
+        _ => start + Months::new(n),
+    }
+}
+
+/// First scheduled date on or after `from`
+pub(crate) fn next_scheduled_date(start: NaiveDate, frequency: &str, from: NaiveDate) -> NaiveDate {
+    (0..)
+        .map(|n| scheduled_date(start, frequency, n))
+        .find(|date| *date >= from)
+        .unwrap_or(from)
+}
+
+/// The account a goal's money is saved in, which scheduled transfers pay into
+fn goal_destination_account(goal: &Goal) -> Option<&str> {
+    goal.contribution_account_id
+        .as_deref()
+        .or(goal.linked_account_id.as_deref())
+}
+
+/// Move a scheduled contribution into the goal's account as a linked transfer, returning
+/// the incoming side so the contribution can point at it
+fn insert_contribution_transfer(
+    conn: &Connection,
+    goal: &Goal,
+    from_account_id: &str,
+    to_account_id: &str,
+    amount: i64,
+    date: &str,
+) -> Result<String> {
+    let payee = format!("Goal contribution: {}", goal.name);
+    let outgoing = insert_transaction(
+        conn,
+        &serde_json::json!({
+            "accountId": from_account_id,
+            "date": date,
+            "amount": -amount,
+            "payee": payee,
+        }),
+    )?;
+    let incoming = insert_transaction(
+        conn,
+        &serde_json::json!({
+            "accountId": to_account_id,
+            "date": date,
+            "amount": amount,
+            "payee": payee,
+        }),
+    )?;
+    link_transaction_pair(conn, &outgoing, &incoming)?;
+    Ok(incoming)
+}
+
+/// Whether an account can still be posted to: neither trashed nor closed
+fn account_is_open(conn: &Connection, account_id: &str) -> Result<bool> {
+    Ok(conn.query_row(
+        "SELECT EXISTS(
+             SELECT 1 FROM accounts WHERE id = ?1 AND deleted_at IS NULL AND closed_on IS NULL
+         )",
+        [account_id],
+        |row| row.get(0),
+    )?)
+}
+
+/// Stop the contribution schedules that transfer out of or into an account, for when it is
+/// closed or moved to the trash
+pub(crate) fn deactivate_account_contribution_schedules(
+    conn: &Connection,
+    account_id: &str,
+    now: &str,
+) -> Result<()> {
+    conn.execute(
+        "UPDATE goal_contribution_schedules SET is_active = 0, updated_at = ?1
+         WHERE is_active = 1 AND (
+             from_account_id = ?2
+             OR goal_id IN (
+                 SELECT id FROM goals
+                 WHERE COALESCE(contribution_account_id, linked_account_id) = ?2
+             )
+         )",
+        [now, account_id],
+    )?;
+    Ok(())
+}
+
+/// Make every scheduled contribution that has come due by `today`, including any missed
+/// while the app was closed, and move each schedule on to its next date. Goals stop
+/// receiving contributions once achieved. A schedule whose accounts have been closed or
+/// trashed is stopped instead, and one that fails is left as it was for the next run
+/// without holding up the others. Returns how many contributions were made.
+pub(crate) fn run_contribution_schedules(conn: &Connection, today: NaiveDate) -> Result<usize> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM goal_contribution_schedules
+         WHERE is_active = 1 AND next_date <= ?1
+           AND goal_id IN (
+               SELECT id FROM goals WHERE deleted_at IS NULL AND is_archived = 0 AND is_achieved = 0
+           )",
+        SCHEDULE_COLUMNS
+    ))?;
+    let schedules: Vec<GoalContributionSchedule> = stmt
+        .query_map([today.format("%Y-%m-%d").to_string()], schedule_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let mut made = 0;
+    for schedule in &schedules {
+        let tx = conn.unchecked_transaction()?;
+        if let Ok(count) = run_contribution_schedule(&tx, schedule, today) {
+            tx.commit()?;
+            made += count;
+        }
+    }
+
+    Ok(made)
+}
+
+/// Make one schedule's contributions due by `today` and move it on to its next date
+fn run_contribution_schedule(
+    conn: &Connection,
+    schedule: &GoalContributionSchedule,
+    today: NaiveDate,
+) -> Result<usize> {
+    let goal = fetch_goal(conn, &schedule.goal_id)?;
+    let now = chrono::Utc::now().to_rfc3339();
+    let accounts = schedule
+        .from_account_id
+        .as_deref()
+        .into_iter()
+        .chain(goal_destination_account(&goal));
+    for account_id in accounts {
+        if !account_is_open(conn, account_id)? {
+            conn.execute(
+                "UPDATE goal_contribution_schedules SET is_active = 0, updated_at = ?1
+                 WHERE goal_id = ?2",
+                [&now, &goal.id],
+            )?;
+            return Ok(0);
+        }
+    }
+
+    let parse = |date: &str| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok();
+    let (Some(start), Some(next)) = (parse(&schedule.start_date), parse(&schedule.next_date))
+    else {
+        return Ok(0);
+    };
+
+    let mut made = 0;
+    let mut date = next_scheduled_date(start, &schedule.frequency, next);
+    while date <= today {
+        let achieved: bool = conn.query_row(
+            "SELECT is_achieved FROM goals WHERE id = ?1",
+            [&goal.id],
+            |row| row.get(0),
+        )?;
+        if achieved {
+            break;
+        }
+
+        let date_str = date.format("%Y-%m-%d").to_string();
+        let transaction_id = match (&schedule.from_account_id, goal_destination_account(&goal)) {
+            (Some(from), Some(to)) => Some(insert_contribution_transfer(
+                conn,
+                &goal,
+                from,
+                to,
+                schedule.amount,
+                &date_str,
+            )?),
+            _ => None,
+        };
+        add_goal_contribution(
+            conn,
+            &goal.id,
+            schedule.amount,
+            &date_str,
+            transaction_id.as_deref(),
+        )?;
+        made += 1;
+
+        date = next_scheduled_date(start, &schedule.frequency, date + Duration::days(1));
+    }
+
+    let next_date = next_scheduled_date(start, &schedule.frequency, today + Duration::days(1));
+    conn.execute(
+        "UPDATE goal_contribution_schedules SET next_date = ?1, updated_at = ?2
+         WHERE goal_id = ?3",
+        rusqlite::params![
+            next_date.format("%Y-%m-%d").to_string(),
+            now,
+            schedule.goal_id,
+        ],
+    )?;
+
+    Ok(made)
+}
+
+/// Make due scheduled and round-up contributions on a background thread once the database
+/// is unlocked, so unlocking isn't held up. A failed run is left for the next unlock.
+pub(crate) fn spawn_goal_contribution_sweep(app: AppHandle) {
+    std::thread::spawn(move || {
+        let db = app.state::<Mutex<Database>>();
+        let database = db.lock().unwrap();
+        let Ok(conn) = database.get_connection() else {
+            return;
+        };
+        let today = chrono::Local::now().date_naive();
+        let scheduled = run_contribution_schedules(conn, today).unwrap_or(0);
+        let rounded_up = contribute_round_ups(conn, today).unwrap_or(0);
+        if scheduled > 0 || rounded_up > 0 {
+            raise_goal_milestones(&app, conn);
+            emit_changed(
+                &app,
+                &[GOALS_CHANGED, TRANSACTIONS_CHANGED, ACCOUNTS_CHANGED],
+            );
+        }
+    });
+}
+
+/// Average monthly contribution over the recent months the goal has been contributed to
+fn recent_monthly_contribution(conn: &Connection, goal_id: &str, today: NaiveDate) -> Result<i64> {
+    let since = today - Months::new(RECENT_CONTRIBUTION_MONTHS);
+    let (total, first): (i64, Option<String>) = conn.query_row(
+        "SELECT COALESCE(SUM(amount), 0), MIN(date) FROM goal_contributions
+         WHERE goal_id = ?1 AND date > ?2 AND deleted_at IS NULL",
+        rusqlite::params![goal_id, since.format("%Y-%m-%d").to_string()],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+
+    let months = first
+        .and_then(|d| NaiveDate::parse_from_str(&d, "%Y-%m-%d").ok())
+        .map_or(1, |first| (today - first).num_days() / 30 + 1)
+        .clamp(1, RECENT_CONTRIBUTION_MONTHS as i64);
+
+    Ok(total / months)
+}
+
+/// Interest credited per compounding period for an APY, and how many months apart the
+/// credits land. Daily compounding is credited monthly; the APY already accounts for it.
+pub(crate) fn compounding_period(apy: f64, compounding: &str) -> (f64, u32) {
+    let periods_per_year = match compounding {
+        "annually" => 1,
+        "quarterly" => 4,
+        _ => 12,
+    };
+    (
+        (1.0 + apy / 100.0).powf(1.0 / periods_per_year as f64) - 1.0,
+        12 / periods_per_year,
+    )
+}
+
+/// Project a goal's balance month by month from its current amount, adding the monthly
+/// contribution at each month's start and crediting the linked account's interest on
+/// its compounding schedule. The saved amount is assumed to sit in the linked account.
+fn project_goal(
+    goal: &Goal,
+    monthly_contribution: i64,
+    interest: Option<(f64, &str)>,
+    today: NaiveDate,
+) -> GoalProjection {
+    let date_after = |months: u32| (today + Months::new(months)).format("%Y-%m-%d").to_string();
+    let remaining = goal.target_amount - goal.current_amount;
+
+    let linear_projected_date = if remaining <= 0 {
+        Some(date_after(0))
+    } else if monthly_contribution > 0 {
+        let months = (remaining + monthly_contribution - 1) / monthly_contribution;
+        (months <= MAX_PROJECTION_MONTHS as i64).then(|| date_after(months as u32))
+    } else {
+        None
+    };
+
+    let target_month = goal
+        .target_date
+        .as_deref()
+        .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+        .map(|d| d.format("%Y-%m").to_string());
+    // A target date this month or already passed is measured against today's balance
+    let mut amount_at_target_date = target_month
+        .as_deref()
+        .filter(|month| *month <= &date_after(0)[..7])
+        .map(|_| goal.current_amount);
+
+    let period = interest.map(|(apy, compounding)| compounding_period(apy, compounding));
+    let mut balance = goal.current_amount as f64;
+    let mut interest_earned = 0.0;
+    let mut projected_date = (remaining <= 0).then(|| date_after(0));
+    let mut months = Vec::new();
+
+    for index in 1..=MAX_PROJECTION_MONTHS {
+        if projected_date.is_some() && (target_month.is_none() || amount_at_target_date.is_some()) {
+            break;
+        }
+
+        balance += monthly_contribution as f64;
+        let mut credited = 0.0;
+        if let Some((rate, interval)) = period {
+            if index % interval == 0 && balance > 0.0 {
+                credited = balance * rate;
+                balance += credited;
+            }
+        }
+
+        let date = date_after(index);
+        let month = date[..7].to_string();
+        if target_month.as_deref() == Some(month.as_str()) {
+            amount_at_target_date = Some(balance.round() as i64);
+        }
+        if projected_date.is_none() {
+            interest_earned += credited;
+            if balance >= goal.target_amount as f64 {
+                projected_date = Some(date);
+            }
+        }
+
+        months.push(GoalProjectionMonth {
+            month,
+            balance: balance.round() as i64,
+            interest: credited.round() as i64,
+        });
+    }
+
+    GoalProjection {
+        goal_id: goal.id.clone(),
+        monthly_contribution,
+        apy: interest.map(|(apy, _)| apy),
+        compounding: interest.map(|(_, compounding)| compounding.to_string()),
+        projected_date,
+        linear_projected_date,
+        amount_at_target_date,
+        required_monthly_contribution: None,
+        interest_earned: interest_earned.round() as i64,
+        months,
+    }
+}
+
+/// Smallest monthly contribution that reaches the goal by its target date, earning the
+/// same interest as the projection. Contributions from now until the target month count.
+fn required_monthly_contribution(
+    goal: &Goal,
+    interest: Option<(f64, &str)>,
+    today: NaiveDate,
+) -> Option<i64> {
+    let target = NaiveDate::parse_from_str(goal.target_date.as_deref()?, "%Y-%m-%d").ok()?;
+    let remaining = goal.target_amount - goal.current_amount;
+    if remaining <= 0 {
+        return Some(0);
+    }
+    let months = month_index(target) - month_index(today);
+    if months <= 0 {
+        return None;
+    }
+
+    // Contributing the remainder evenly always gets there; interest can only lower it
+    let reaches = |contribution: i64| {
+        project_goal(goal, contribution, interest, today)
+            .amount_at_target_date
+            .is_some_and(|amount| amount >= goal.target_amount)
+    };
+    let (mut low, mut high) = (0, (remaining + months as i64 - 1) / months as i64);
+    while low < high {
+        let mid = (low + high) / 2;
+        if reaches(mid) {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+    Some(high)
+}
+
 #[tauri::command]
 pub fn list_goals(db: State<'_, Mutex<Database>>) -> Result<Vec<Goal>> {
     let database = db.lock().unwrap();
     let conn = database.get_connection()?;
 
-    let mut stmt = conn.prepare(
-        "SELECT id, name, goal_type, target_amount, current_amount, target_date,
-                linked_account_id, icon, color, is_achieved, achieved_at, created_at, updated_at
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {}
          FROM goals
-         WHERE deleted_at IS NULL
-         ORDER BY is_achieved ASC, target_date ASC NULLS LAST, created_at DESC"
-    )?;
+         WHERE deleted_at IS NULL AND is_archived = 0
+         ORDER BY is_achieved ASC, priority DESC, target_date ASC NULLS LAST, created_at DESC",
+        GOAL_COLUMNS
+    ))?;
 
     let goals = stmt
-        .query_map([], |row| {
-            Ok(Goal {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                goal_type: row.get(2)?,
-                target_amount: row.get(3)?,
-                current_amount: row.get(4)?,
-                target_date: row.get(5)?,
-                linked_account_id: row.get(6)?,
-                icon: row.get(7)?,
-                color: row.get(8)?,
-                is_achieved: row.get(9)?,
-                achieved_at: row.get(10)?,
-                created_at: row.get(11)?,
-                updated_at: row.get(12)?,
-            })
-        })?
+        .query_map([], goal_from_row)?
         .filter_map(|r| r.ok())
         .collect();
 
@@ -45,18 +523,28 @@ pub fn list_goals(db: State<'_, Mutex<Database>>) -> Result<Vec<Goal>> {
 #[tauri::command]
 pub fn create_goal(
     data: serde_json::Value,
+    app: AppHandle,
     db: State<'_, Mutex<Database>>,
 ) -> Result<Goal> {
     let database = db.lock().unwrap();
     let conn = database.get_connection()?;
 
+    validate_payoff_strategy(&data)?;
+    let milestones = input_goal_milestones(&data)?.flatten();
+    let allocation_weight = input_allocation_weight(&data)?;
+
     let id = Uuid::new_v4().to_string();
     let now = chrono::Utc::now().to_rfc3339();
 
-    conn.execute(
+    // The goal isn't kept if its debts turn out to be invalid
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(
         "INSERT INTO goals (id, name, goal_type, target_amount, current_amount, target_date,
-                           linked_account_id, icon, color, is_achieved, created_at, updated_at)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, 0, ?10, ?11)",
+                           linked_account_id, icon, color, is_achieved, created_at, updated_at,
+                           contribution_account_id, payoff_strategy, monthly_payment,
+                           milestones, allocation_weight)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, 0, ?10, ?11, ?12, ?13, ?14, ?15,
+                 COALESCE(?16, 1))",
         rusqlite::params![
             id,
             data["name"].as_str().unwrap_or(""),
@@ -69,31 +557,27 @@ pub fn create_goal(
             data["color"].as_str(),
             now,
             now,
+            data["contributionAccountId"].as_str(),
+            data["payoffStrategy"].as_str(),
+            data["monthlyPayment"].as_i64(),
+            milestones,
+            allocation_weight,
         ],
     )?;
+    if data["debts"].is_array() {
+        save_goal_debts(&tx, &id, &data["debts"])?;
+    }
+    // The starting amount was saved before the goal existed, so milestones it already
+    // covers are recorded without celebrating them
+    check_goal_milestones(&tx, Some(&id))?;
+    tx.commit()?;
+
+    emit_changed(&app, &[GOALS_CHANGED]);
 
     conn.query_row(
-        "SELECT id, name, goal_type, target_amount, current_amount, target_date,
-                linked_account_id, icon, color, is_achieved, achieved_at, created_at, updated_at
-         FROM goals WHERE id = ?1",
+        &format!("SELECT {} FROM goals WHERE id = ?1", GOAL_COLUMNS),
         [&id],
-        |row| {
-            Ok(Goal {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                goal_type: row.get(2)?,
-                target_amount: row.get(3)?,
-                current_amount: row.get(4)?,
-                target_date: row.get(5)?,
-                linked_account_id: row.get(6)?,
-                icon: row.get(7)?,
-                color: row.get(8)?,
-                is_achieved: row.get(9)?,
-                achieved_at: row.get(10)?,
-                created_at: row.get(11)?,
-                updated_at: row.get(12)?,
-            })
-        },
+        goal_from_row,
     )
     .map_err(|e| e.into())
 }
@@ -102,14 +586,24 @@ pub fn create_goal(
 pub fn update_goal(
     id: String,
     data: serde_json::Value,
+    app: AppHandle,
     db: State<'_, Mutex<Database>>,
 ) -> Result<Goal> {
     let database = db.lock().unwrap();
     let conn = database.get_connection()?;
 
+    validate_payoff_strategy(&data)?;
+    let milestones = input_goal_milestones(&data)?;
+    let allocation_weight = input_allocation_weight(&data)?;
+    fetch_goal(conn, &id)?;
+
     let now = chrono::Utc::now().to_rfc3339();
+    let tx = conn.unchecked_transaction()?;
 
-    conn.execute(
+    if data["debts"].is_array() {
+        save_goal_debts(&tx, &id, &data["debts"])?;
+    }
+    tx.execute(
         "UPDATE goals SET
             name = COALESCE(?1, name),
             goal_type = COALESCE(?2, goal_type),
@@ -119,8 +613,12 @@ pub fn update_goal(
             linked_account_id = ?6,
             icon = ?7,
             color = ?8,
-            updated_at = ?9
-         WHERE id = ?10 AND deleted_at IS NULL",
+            contribution_account_id = ?9,
+            payoff_strategy = COALESCE(?12, payoff_strategy),
+            monthly_payment = COALESCE(?13, monthly_payment),
+            allocation_weight = COALESCE(?14, allocation_weight),
+            updated_at = ?10
+         WHERE id = ?11 AND deleted_at IS NULL",
         rusqlite::params![
             data["name"].as_str(),
             data["goalType"].as_str(),
@@ -130,39 +628,39 @@ pub fn update_goal(
             data["linkedAccountId"].as_str(),
             data["icon"].as_str(),
             data["color"].as_str(),
+            data["contributionAccountId"].as_str(),
             now,
             id,
+            data["payoffStrategy"].as_str(),
+            data["monthlyPayment"].as_i64(),
+            allocation_weight,
         ],
     )?;
+    if let Some(milestones) = milestones {
+        tx.execute(
+            "UPDATE goals SET milestones = ?1 WHERE id = ?2",
+            rusqlite::params![milestones, id],
+        )?;
+    }
+    tx.commit()?;
+
+    raise_goal_milestones(&app, conn);
+    emit_changed(&app, &[GOALS_CHANGED]);
 
     conn.query_row(
-        "SELECT id, name, goal_type, target_amount, current_amount, target_date,
-                linked_account_id, icon, color, is_achieved, achieved_at, created_at, updated_at
-         FROM goals WHERE id = ?1",
+        &format!("SELECT {} FROM goals WHERE id = ?1", GOAL_COLUMNS),
         [&id],
-        |row| {
-            Ok(Goal {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                goal_type: row.get(2)?,
-                target_amount: row.get(3)?,
-                current_amount: row.get(4)?,
-                target_date: row.get(5)?,
-                linked_account_id: row.get(6)?,
-                icon: row.get(7)?,
-                color: row.get(8)?,
-                is_achieved: row.get(9)?,
-                achieved_at: row.get(10)?,
-                created_at: row.get(11)?,
-                updated_at: row.get(12)?,
-            })
-        },
+        goal_from_row,
     )
     .map_err(|e| e.into())
 }
 
 #[tauri::command]
-pub fn delete_goal(id: String, db: State<'_, Mutex<Database>>) -> Result<()> {
+pub fn delete_goal(
+    id: String,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<()> {
     let database = db.lock().unwrap();
     let conn = database.get_connection()?;
 
@@ -173,6 +671,208 @@ pub fn delete_goal(id: String, db: State<'_, Mutex<Database>>) -> Result<()> {
         [&now, &id],
     )?;
 
+    emit_changed(&app, &[GOALS_CHANGED]);
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn list_deleted_goals(db: State<'_, Mutex<Database>>) -> Result<Vec<Goal>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM goals WHERE deleted_at IS NOT NULL ORDER BY deleted_at DESC",
+        GOAL_COLUMNS
+    ))?;
+
+    let goals = stmt
+        .query_map([], goal_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(goals)
+}
+
+/// Bring archived or deleted goals back to the goal list, with their contribution history.
+/// A schedule paused by archiving resumes from its next date on or after today, so the
+/// contributions missed meanwhile aren't made up. Returns how many goals came back.
+fn restore_goal_rows(conn: &Connection, ids: &[String], now: &str) -> Result<usize> {
+    let today = chrono::Local::now().date_naive();
+    let tx = conn.unchecked_transaction()?;
+    let mut restored = 0;
+
+    for id in ids {
+        let was_archived: Option<bool> = tx
+            .query_row(
+                "SELECT is_archived FROM goals
+                 WHERE id = ?1 AND (is_archived = 1 OR deleted_at IS NOT NULL)",
+                [id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        let Some(was_archived) = was_archived else {
+            continue;
+        };
+        tx.execute(
+            "UPDATE goals SET is_archived = 0, archived_at = NULL, deleted_at = NULL, updated_at = ?1
+             WHERE id = ?2",
+            [now, id],
+        )?;
+        restored += 1;
+
+        let schedule = tx
+            .query_row(
+                &format!(
+                    "SELECT {} FROM goal_contribution_schedules WHERE goal_id = ?1",
+                    SCHEDULE_COLUMNS
+                ),
+                [id],
+                schedule_from_row,
+            )
+            .optional()?;
+        let Some(schedule) = schedule else {
+            continue;
+        };
+        let parse = |date: &str| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok();
+        let (Some(start), Some(next)) = (parse(&schedule.start_date), parse(&schedule.next_date))
+        else {
+            continue;
+        };
+        let next_date = next_scheduled_date(start, &schedule.frequency, next.max(today));
+        tx.execute(
+            "UPDATE goal_contribution_schedules SET
+                next_date = ?1,
+                is_active = CASE WHEN ?2 THEN 1 ELSE is_active END,
+                updated_at = ?3
+             WHERE goal_id = ?4",
+            rusqlite::params![
+                next_date.format("%Y-%m-%d").to_string(),
+                was_archived,
+                now,
+                id,
+            ],
+        )?;
+    }
+    tx.commit()?;
+
+    Ok(restored)
+}
+
+#[tauri::command]
+pub fn restore_goals(
+    ids: Vec<String>,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<()> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let now = chrono::Utc::now().to_rfc3339();
+    restore_goal_rows(conn, &ids, &now)?;
+
+    emit_changed(&app, &[GOALS_CHANGED]);
+
+    Ok(())
+}
+
+/// Put a goal away without deleting it. Its contributions are kept and its contribution
+/// schedule is paused, so restoring it doesn't make up the contributions it missed.
+#[tauri::command]
+pub fn archive_goal(id: String, app: AppHandle, db: State<'_, Mutex<Database>>) -> Result<()> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let now = chrono::Utc::now().to_rfc3339();
+
+    let archived = conn.execute(
+        "UPDATE goals SET is_archived = 1, archived_at = ?1, updated_at = ?1
+         WHERE id = ?2 AND deleted_at IS NULL AND is_archived = 0",
+        [&now, &id],
+    )?;
+    if archived == 0 {
+        return Err(AppError::NotFound("Goal not found".to_string()));
+    }
+    conn.execute(
+        "UPDATE goal_contribution_schedules SET is_active = 0, updated_at = ?1 WHERE goal_id = ?2",
+        [&now, &id],
+    )?;
+
+    emit_changed(&app, &[GOALS_CHANGED]);
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn list_archived_goals(db: State<'_, Mutex<Database>>) -> Result<Vec<Goal>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM goals
+         WHERE is_archived = 1 AND deleted_at IS NULL
+         ORDER BY archived_at DESC",
+        GOAL_COLUMNS
+    ))?;
+
+    let goals = stmt
+        .query_map([], goal_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(goals)
+}
+
+/// Bring an archived or deleted goal back to the goal list, as `restore_goals` does
+#[tauri::command]
+pub fn restore_goal(id: String, app: AppHandle, db: State<'_, Mutex<Database>>) -> Result<Goal> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let now = chrono::Utc::now().to_rfc3339();
+    if restore_goal_rows(conn, std::slice::from_ref(&id), &now)? == 0 {
+        return Err(AppError::NotFound("Goal not found".to_string()));
+    }
+
+    emit_changed(&app, &[GOALS_CHANGED]);
+
+    fetch_goal(conn, &id)
+}
+
+/// Set goal priorities from their order in one transaction, first goal highest. Goals
+/// left out of the list keep their priority.
+#[tauri::command]
+pub fn reorder_goals(
+    ordered_ids: Vec<String>,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<()> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let unique: std::collections::HashSet<&String> = ordered_ids.iter().collect();
+    if unique.len() != ordered_ids.len() {
+        return Err(AppError::Validation(
+            "Goal order lists a goal more than once".to_string(),
+        ));
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+
+    let tx = conn.unchecked_transaction()?;
+    for (position, id) in ordered_ids.iter().enumerate() {
+        let updated = tx.execute(
+            "UPDATE goals SET priority = ?1, updated_at = ?2 WHERE id = ?3 AND deleted_at IS NULL",
+            rusqlite::params![(ordered_ids.len() - position) as i32, now, id],
+        )?;
+        if updated == 0 {
+            return Err(AppError::NotFound(format!("Goal not found: {}", id)));
+        }
+    }
+    tx.commit()?;
+
+    emit_changed(&app, &[GOALS_CHANGED]);
+
     Ok(())
 }
 
@@ -181,20 +881,38 @@ pub fn contribute_to_goal(
     goal_id: String,
     amount: i64,
     transaction_id: Option<String>,
+    app: AppHandle,
     db: State<'_, Mutex<Database>>,
 ) -> Result<()> {
     let database = db.lock().unwrap();
     let conn = database.get_connection()?;
 
+    let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
+    add_goal_contribution(conn, &goal_id, amount, &today, transaction_id.as_deref())?;
+
+    raise_goal_milestones(&app, conn);
+    emit_changed(&app, &[GOALS_CHANGED]);
+
+    Ok(())
+}
+
+/// Record a contribution, add it to the goal's current amount and mark the goal achieved
+/// once it reaches the target. Returns the contribution's id.
+pub(crate) fn add_goal_contribution(
+    conn: &Connection,
+    goal_id: &str,
+    amount: i64,
+    date: &str,
+    transaction_id: Option<&str>,
+) -> Result<String> {
     let id = Uuid::new_v4().to_string();
     let now = chrono::Utc::now().to_rfc3339();
-    let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
 
     // Insert contribution
     conn.execute(
         "INSERT INTO goal_contributions (id, goal_id, amount, date, transaction_id, created_at)
          VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-        rusqlite::params![id, goal_id, amount, today, transaction_id, now],
+        rusqlite::params![id, goal_id, amount, date, transaction_id, now],
     )?;
 
     // Update goal current_amount
@@ -209,7 +927,7 @@ pub fn contribute_to_goal(
     // Check if goal is achieved
     let (current, target): (i64, i64) = conn.query_row(
         "SELECT current_amount, target_amount FROM goals WHERE id = ?1",
-        [&goal_id],
+        [goal_id],
         |row| Ok((row.get(0)?, row.get(1)?)),
     )?;
 
@@ -220,5 +938,374 @@ pub fn contribute_to_goal(
         )?;
     }
 
+    Ok(id)
+}
+
+/// Record transfers into goals' contribution accounts as goal contributions. Only
+/// transfers dated from the goal's creation on count, since earlier savings are already
+/// in its starting amount, and a transfer already recorded for the goal is skipped, even
+/// if that contribution was since deleted.
+/// Returns how many contributions were added.
+pub(crate) fn record_transfer_contributions(conn: &Connection) -> Result<usize> {
+    let mut stmt = conn.prepare(
+        "SELECT g.id, t.id, t.amount, t.date
+         FROM goals g
+         JOIN transactions t ON t.account_id = g.contribution_account_id
+         WHERE g.deleted_at IS NULL AND g.is_archived = 0 AND g.is_achieved = 0
+           AND t.deleted_at IS NULL AND t.transfer_id IS NOT NULL AND t.amount > 0
+           AND t.date >= substr(g.created_at, 1, 10)
+           AND NOT EXISTS (
+               SELECT 1 FROM goal_contributions c
+               WHERE c.goal_id = g.id AND c.transaction_id = t.id
+           )
+         ORDER BY t.date, t.created_at",
+    )?;
+    let transfers: Vec<(String, String, i64, String)> = stmt
+        .query_map([], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    for (goal_id, transaction_id, amount, date) in &transfers {
+        add_goal_contribution(conn, goal_id, *amount, date, Some(transaction_id))?;
+    }
+
+    Ok(transfers.len())
+}
+
+#[tauri::command]
+pub fn list_goal_contributions(
+    goal_id: String,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<GoalContribution>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let mut stmt = conn.prepare(
+        "SELECT id, goal_id, amount, date, transaction_id, notes, created_at
+         FROM goal_contributions
+         WHERE goal_id = ?1 AND deleted_at IS NULL
+         ORDER BY date DESC, created_at DESC",
+    )?;
+
+    let contributions = stmt
+        .query_map([&goal_id], |row| {
+            Ok(GoalContribution {
+                id: row.get(0)?,
+                goal_id: row.get(1)?,
+                amount: row.get(2)?,
+                date: row.get(3)?,
+                transaction_id: row.get(4)?,
+                notes: row.get(5)?,
+                created_at: row.get(6)?,
+            })
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(contributions)
+}
+
+/// Delete a contribution made by mistake, taking it back off the goal's current amount.
+/// A goal that drops below its target is no longer achieved.
+#[tauri::command]
+pub fn delete_goal_contribution(
+    id: String,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<()> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let (goal_id, amount): (String, i64) = conn
+        .query_row(
+            "SELECT goal_id, amount FROM goal_contributions WHERE id = ?1 AND deleted_at IS NULL",
+            [&id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .map_err(|_| AppError::NotFound("Goal contribution not found".to_string()))?;
+
+    let now = chrono::Utc::now().to_rfc3339();
+
+    conn.execute(
+        "UPDATE goal_contributions SET deleted_at = ?1 WHERE id = ?2",
+        [&now, &id],
+    )?;
+    conn.execute(
+        "UPDATE goals SET
+            current_amount = current_amount - ?1,
+            updated_at = ?2
+         WHERE id = ?3",
+        rusqlite::params![amount, now, goal_id],
+    )?;
+    conn.execute(
+        "UPDATE goals SET is_achieved = 0, achieved_at = NULL
+         WHERE id = ?1 AND is_achieved = 1 AND current_amount < target_amount",
+        [&goal_id],
+    )?;
+    check_goal_milestones(conn, Some(&goal_id))?;
+
+    emit_changed(&app, &[GOALS_CHANGED]);
+
     Ok(())
 }
+
+#[tauri::command]
+pub fn list_goal_contribution_schedules(
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<GoalContributionSchedule>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM goal_contribution_schedules ORDER BY next_date",
+        SCHEDULE_COLUMNS
+    ))?;
+
+    let schedules = stmt
+        .query_map([], schedule_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(schedules)
+}
+
+/// Create or replace a goal's contribution schedule. The first contribution is the first
+/// scheduled date from today on; changing an existing schedule never repeats a date it
+/// already contributed on.
+#[tauri::command]
+pub fn set_goal_contribution_schedule(
+    goal_id: String,
+    data: serde_json::Value,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<GoalContributionSchedule> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let goal = fetch_goal(conn, &goal_id)?;
+
+    let amount = data["amount"].as_i64().unwrap_or(0);
+    if amount <= 0 {
+        return Err(AppError::Validation(
+            "Contribution amount must be greater than zero".to_string(),
+        ));
+    }
+    let frequency = data["frequency"].as_str().unwrap_or("monthly");
+    if !SCHEDULE_FREQUENCIES.contains(&frequency) {
+        return Err(AppError::Validation(format!(
+            "Invalid contribution frequency: {}",
+            frequency
+        )));
+    }
+
+    let today = chrono::Local::now().date_naive();
+    let start = match data["startDate"].as_str() {
+        Some(date) => NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map_err(|_| AppError::Validation(format!("Invalid start date: {}", date)))?,
+        None => today,
+    };
+
+    let from_account_id = data["fromAccountId"].as_str();
+    if let Some(from) = from_account_id {
+        let destination = goal_destination_account(&goal).ok_or_else(|| {
+            AppError::Validation(
+                "Link the goal to an account before transferring contributions into it".to_string(),
+            )
+        })?;
+        if from == destination {
+            return Err(AppError::Validation(
+                "Contributions can't be transferred from the goal's own account".to_string(),
+            ));
+        }
+        let exists: bool = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM accounts WHERE id = ?1 AND deleted_at IS NULL)",
+            [from],
+            |row| row.get(0),
+        )?;
+        if !exists {
+            return Err(AppError::NotFound("Account not found".to_string()));
+        }
+    }
+
+    let previous_next: Option<String> = conn
+        .query_row(
+            "SELECT next_date FROM goal_contribution_schedules WHERE goal_id = ?1",
+            [&goal_id],
+            |row| row.get(0),
+        )
+        .optional()?;
+    let earliest = previous_next
+        .and_then(|d| NaiveDate::parse_from_str(&d, "%Y-%m-%d").ok())
+        .map_or(today, |d| d.max(today));
+    let next_date = next_scheduled_date(start, frequency, earliest);
+
+    let now = chrono::Utc::now().to_rfc3339();
+
+    conn.execute(
+        "INSERT INTO goal_contribution_schedules (goal_id, amount, frequency, start_date,
+                next_date, from_account_id, is_active, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?8)
+         ON CONFLICT(goal_id) DO UPDATE SET
+            amount = excluded.amount,
+            frequency = excluded.frequency,
+            start_date = excluded.start_date,
+            next_date = excluded.next_date,
+            from_account_id = excluded.from_account_id,
+            is_active = excluded.is_active,
+            updated_at = excluded.updated_at",
+        rusqlite::params![
+            goal_id,
+            amount,
+            frequency,
+            start.format("%Y-%m-%d").to_string(),
+            next_date.format("%Y-%m-%d").to_string(),
+            from_account_id,
+            data["isActive"].as_bool().unwrap_or(true),
+            now,
+        ],
+    )?;
+
+    emit_changed(&app, &[GOALS_CHANGED]);
+
+    conn.query_row(
+        &format!(
+            "SELECT {} FROM goal_contribution_schedules WHERE goal_id = ?1",
+            SCHEDULE_COLUMNS
+        ),
+        [&goal_id],
+        schedule_from_row,
+    )
+    .map_err(|e| e.into())
+}
+
+#[tauri::command]
+pub fn delete_goal_contribution_schedule(
+    goal_id: String,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<()> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    conn.execute(
+        "DELETE FROM goal_contribution_schedules WHERE goal_id = ?1",
+        [&goal_id],
+    )?;
+
+    emit_changed(&app, &[GOALS_CHANGED]);
+
+    Ok(())
+}
+
+/// Make any scheduled contributions that are due now. The same sweep runs on unlock.
+#[tauri::command]
+pub fn run_goal_contribution_schedules(
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<usize> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let made = run_contribution_schedules(conn, chrono::Local::now().date_naive())?;
+    if made > 0 {
+        raise_goal_milestones(&app, conn);
+        emit_changed(
+            &app,
+            &[GOALS_CHANGED, TRANSACTIONS_CHANGED, ACCOUNTS_CHANGED],
+        );
+    }
+
+    Ok(made)
+}
+
+/// Project when a goal will be reached, growing the saved amount at the linked account's
+/// APY alongside a monthly contribution. The contribution defaults to the average of the
+/// goal's recent contributions. The linear (no interest) date comes back too for comparison,
+/// along with the monthly contribution it would take to reach the goal by its target date.
+#[tauri::command]
+pub fn get_goal_projection(
+    goal_id: String,
+    monthly_contribution: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<GoalProjection> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let goal = fetch_goal(conn, &goal_id)?;
+    let today = chrono::Local::now().date_naive();
+
+    let monthly_contribution = match monthly_contribution {
+        Some(amount) if amount < 0 => {
+            return Err(AppError::Validation(
+                "Monthly contribution can't be negative".to_string(),
+            ))
+        }
+        Some(amount) => amount,
+        None => recent_monthly_contribution(conn, &goal.id, today)?.max(0),
+    };
+
+    let interest: Option<(f64, String)> = match &goal.linked_account_id {
+        Some(account_id) => conn
+            .query_row(
+                "SELECT interest_rate, interest_compounding FROM accounts
+                 WHERE id = ?1 AND deleted_at IS NULL AND interest_rate > 0",
+                [account_id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .ok(),
+        None => None,
+    };
+
+    let interest = interest
+        .as_ref()
+        .map(|(apy, compounding)| (*apy, compounding.as_str()));
+    let mut projection = project_goal(&goal, monthly_contribution, interest, today);
+    projection.required_monthly_contribution =
+        required_monthly_contribution(&goal, interest, today);
+
+    Ok(projection)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schedules_stop_for_closed_accounts() {
+        let conn = Database::in_memory();
+        conn.execute_batch(
+            "INSERT INTO accounts (id, name, account_type, current_balance, opening_balance, closed_on)
+             VALUES ('checking', 'Checking', 'checking', 100000, 100000, NULL),
+                    ('old', 'Old checking', 'checking', 0, 0, '2024-01-31'),
+                    ('savings', 'Savings', 'savings', 0, 0, NULL);
+             INSERT INTO goals (id, name, goal_type, target_amount, contribution_account_id)
+             VALUES ('trip', 'Trip', 'savings', 100000, 'savings'),
+                    ('car', 'Car', 'savings', 100000, 'savings');
+             INSERT INTO goal_contribution_schedules
+                 (goal_id, amount, frequency, start_date, next_date, from_account_id)
+             VALUES ('trip', 5000, 'monthly', '2024-03-01', '2024-03-01', 'checking'),
+                    ('car', 5000, 'monthly', '2024-03-01', '2024-03-01', 'old');",
+        )
+        .unwrap();
+        let today = NaiveDate::from_ymd_opt(2024, 4, 15).unwrap();
+
+        assert_eq!(run_contribution_schedules(&conn, today).unwrap(), 2);
+        let active = |goal_id: &str| -> bool {
+            conn.query_row(
+                "SELECT is_active FROM goal_contribution_schedules WHERE goal_id = ?1",
+                [goal_id],
+                |row| row.get(0),
+            )
+            .unwrap()
+        };
+        assert!(active("trip"));
+        assert!(!active("car"));
+        assert_eq!(fetch_goal(&conn, "car").unwrap().current_amount, 0);
+
+        // Trashing the account a goal saves into stops its schedule too
+        deactivate_account_contribution_schedules(&conn, "savings", "2024-04-16").unwrap();
+        assert!(!active("trip"));
+    }
+}
@@ -0,0 +1,293 @@
+use crate::commands::month_close::ensure_transaction_unlocked;
+use crate::commands::transactions::{
+    duplicate_payee_similarity, soft_delete_transaction, transaction_from_row, TRANSACTION_COLUMNS,
+};
+use crate::db::Database;
+use crate::error::{AppError, Result};
+use crate::events::{emit_changed, ACCOUNTS_CHANGED, TRANSACTIONS_CHANGED};
+use crate::models::Transaction;
+use chrono::NaiveDate;
+use rusqlite::Connection;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::sync::Mutex;
+use tauri::{AppHandle, State};
+
+/// Holds without an explicit expiry drop off this many days after they were placed
+const DEFAULT_HOLD_DAYS: i64 = 7;
+
+/// Minimum payee similarity for a posted charge to count as a hold's settlement
+const HOLD_SETTLEMENT_MIN_SIMILARITY: f64 = 0.5;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HoldSettlement {
+    pub hold_id: String,
+    pub settlement_id: String,
+    pub hold_amount: i64,
+    pub settled_amount: i64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HoldResolution {
+    pub settled: Vec<HoldSettlement>,
+    pub expired: Vec<String>,
+}
+
+fn default_hold_expiry(date: &str) -> Option<String> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").ok().map(|d| {
+        (d + chrono::Duration::days(DEFAULT_HOLD_DAYS))
+            .format("%Y-%m-%d")
+            .to_string()
+    })
+}
+
+/// Match each active hold to the charge that settled it, or drop it once it has expired.
+/// A settlement is a posted, non-hold charge on the same account with a similar payee,
+/// dated between the hold and its expiry; the amount may differ (a tip, a final fuel total).
+/// The hold is soft-deleted either way, reversing its effect on the account balance, and
+/// its category carries over to an uncategorized settlement. Holds locked by a closed month
+/// or a closing statement are left until they're unlocked.
+pub(crate) fn resolve_holds(conn: &Connection, today: &str) -> Result<HoldResolution> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM transactions
+         WHERE deleted_at IS NULL AND is_hold = 1
+         ORDER BY date",
+        TRANSACTION_COLUMNS
+    ))?;
+    let holds: Vec<Transaction> = stmt
+        .query_map([], transaction_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let mut resolution = HoldResolution::default();
+    if holds.is_empty() {
+        return Ok(resolution);
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let mut used: HashSet<String> = HashSet::new();
+
+    for hold in holds {
+        if ensure_transaction_unlocked(conn, &hold.id).is_err() {
+            continue;
+        }
+        let expires_at = hold
+            .hold_expires_at
+            .clone()
+            .or_else(|| default_hold_expiry(&hold.date))
+            .unwrap_or_else(|| hold.date.clone());
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM transactions
+             WHERE deleted_at IS NULL AND is_hold = 0
+               AND account_id = ?1 AND amount < 0
+               AND date >= ?2 AND date <= ?3
+             ORDER BY date",
+            TRANSACTION_COLUMNS
+        ))?;
+        let settlement = stmt
+            .query_map(
+                rusqlite::params![hold.account_id, hold.date, expires_at],
+                transaction_from_row,
+            )?
+            .filter_map(|r| r.ok())
+            .filter(|t| !used.contains(&t.id))
+            .map(|t| (duplicate_payee_similarity(&hold.payee, &t.payee), t))
+            .filter(|(similarity, _)| *similarity >= HOLD_SETTLEMENT_MIN_SIMILARITY)
+            .max_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(_, t)| t);
+
+        match settlement {
+            Some(settlement) => {
+                soft_delete_transaction(conn, &hold.id, &now)?;
+                if settlement.category_id.is_none() && hold.category_id.is_some() {
+                    conn.execute(
+                        "UPDATE transactions SET category_id = ?1, updated_at = ?2 WHERE id = ?3",
+                        rusqlite::params![hold.category_id, now, settlement.id],
+                    )?;
+                }
+                used.insert(settlement.id.clone());
+                resolution.settled.push(HoldSettlement {
+                    hold_id: hold.id,
+                    settlement_id: settlement.id,
+                    hold_amount: hold.amount,
+                    settled_amount: settlement.amount,
+                });
+            }
+            None if expires_at.as_str() < today => {
+                soft_delete_transaction(conn, &hold.id, &now)?;
+                resolution.expired.push(hold.id);
+            }
+            None => {}
+        }
+    }
+
+    Ok(resolution)
+}
+
+#[tauri::command]
+pub fn list_holds(db: State<'_, Mutex<Database>>) -> Result<Vec<Transaction>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM transactions
+         WHERE deleted_at IS NULL AND is_hold = 1
+         ORDER BY date DESC",
+        TRANSACTION_COLUMNS
+    ))?;
+
+    let holds = stmt
+        .query_map([], transaction_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(holds)
+}
+
+/// Mark transactions as pre-authorization holds. They stay out of spending reports
+/// until `resolve_holds` finds the settled charge or `expires_at` passes (one week
+/// after the hold by default).
+#[tauri::command]
+pub fn mark_as_hold(
+    ids: Vec<String>,
+    expires_at: Option<String>,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<()> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    if let Some(date) = &expires_at {
+        NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map_err(|_| AppError::Validation(format!("Invalid expiry date: {}", date)))?;
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let tx = conn.unchecked_transaction()?;
+
+    for id in &ids {
+        let (date, amount, transfer_id): (String, i64, Option<String>) = tx
+            .query_row(
+                "SELECT date, amount, transfer_id FROM transactions
+                 WHERE id = ?1 AND deleted_at IS NULL",
+                [id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .map_err(|_| AppError::NotFound("Transaction not found".to_string()))?;
+        ensure_transaction_unlocked(&tx, id)?;
+
+        if amount >= 0 || transfer_id.is_some() {
+            return Err(AppError::Validation(
+                "Only card or account charges can be marked as holds".to_string(),
+            ));
+        }
+
+        let expiry = expires_at.clone().or_else(|| default_hold_expiry(&date));
+        tx.execute(
+            "UPDATE transactions SET is_hold = 1, hold_expires_at = ?1, updated_at = ?2
+             WHERE id = ?3",
+            rusqlite::params![expiry, now, id],
+        )?;
+    }
+
+    tx.commit()?;
+
+    emit_changed(&app, &[TRANSACTIONS_CHANGED]);
+
+    Ok(())
+}
+
+/// Turn holds back into ordinary transactions, e.g. when the pre-auth was the final charge
+#[tauri::command]
+pub fn release_hold(
+    ids: Vec<String>,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<()> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    for id in &ids {
+        ensure_transaction_unlocked(conn, id)?;
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+
+    for id in &ids {
+        conn.execute(
+            "UPDATE transactions SET is_hold = 0, hold_expires_at = NULL, updated_at = ?1
+             WHERE id = ?2",
+            rusqlite::params![now, id],
+        )?;
+    }
+
+    emit_changed(&app, &[TRANSACTIONS_CHANGED]);
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn process_holds(app: AppHandle, db: State<'_, Mutex<Database>>) -> Result<HoldResolution> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let today = chrono::Local::now()
+        .date_naive()
+        .format("%Y-%m-%d")
+        .to_string();
+    let tx = conn.unchecked_transaction()?;
+    let resolution = resolve_holds(&tx, &today)?;
+    tx.commit()?;
+
+    if !resolution.settled.is_empty() || !resolution.expired.is_empty() {
+        emit_changed(&app, &[TRANSACTIONS_CHANGED, ACCOUNTS_CHANGED]);
+    }
+
+    Ok(resolution)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::transactions::insert_transaction;
+
+    #[test]
+    fn test_locked_holds_are_left_alone() {
+        let conn = Database::in_memory();
+        conn.execute(
+            "INSERT INTO accounts (id, name, account_type, current_balance, opening_balance)
+             VALUES ('card', 'Card', 'credit_card', 0, 0)",
+            [],
+        )
+        .unwrap();
+        let hold = insert_transaction(
+            &conn,
+            &serde_json::json!({
+                "accountId": "card",
+                "date": "2024-03-01",
+                "amount": -5000,
+                "payee": "Hotel",
+            }),
+        )
+        .unwrap();
+        conn.execute("UPDATE transactions SET is_hold = 1 WHERE id = ?1", [&hold])
+            .unwrap();
+        conn.execute(
+            "INSERT INTO account_statements (id, account_id, statement_date, balance)
+             VALUES ('stmt', 'card', '2024-03-31', -5000)",
+            [],
+        )
+        .unwrap();
+
+        let resolution = resolve_holds(&conn, "2024-04-15").unwrap();
+        assert!(resolution.expired.is_empty());
+
+        conn.execute("UPDATE account_statements SET is_locked = 0", [])
+            .unwrap();
+        let resolution = resolve_holds(&conn, "2024-04-15").unwrap();
+        assert_eq!(resolution.expired, vec![hold]);
+    }
+}
@@ -1,11 +1,32 @@
+use crate::commands::accounts::refresh_available_balance;
+use crate::commands::budget_alerts::raise_budget_alerts;
+use crate::commands::goal_milestones::raise_goal_milestones;
+use crate::commands::goals::record_transfer_contributions;
+use crate::commands::holds::resolve_holds;
+use crate::commands::payees::resolve_payee;
+use crate::commands::rules::{apply_rules, auto_apply_rules_enabled};
+use crate::commands::transactions::input_amount;
+use crate::commands::usage_stats::{record_feature_use, record_parser_result};
 use crate::db::Database;
-use crate::error::Result;
+use crate::error::{AppError, Result};
+use crate::events::{
+    emit_changed, ACCOUNTS_CHANGED, GOALS_CHANGED, IMPORT_PROGRESS, INVESTMENTS_CHANGED,
+    TRANSACTIONS_CHANGED,
+};
 use crate::import::boa_parser::{self, BoaPreview};
 use crate::import::csv_parser::{self, ColumnMapping, CsvPreview, ParsedTransaction};
+use crate::import::empower_parser::{self, EmpowerPreview};
 use crate::import::pdf_parser::{self, PdfPreview};
-use std::path::PathBuf;
-use std::sync::Mutex;
-use tauri::State;
+use crate::import::registry::{
+    ImporterInfo, ImporterRegistry, StatementImporter, StatementPreview,
+};
+use rusqlite::OptionalExtension;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Manager, State};
 use uuid::Uuid;
 
 #[tauri::command]
@@ -20,26 +41,362 @@ pub async fn preview_csv_file(file_path: String) -> Result<CsvPreview> {
 pub async fn parse_csv_file(
     file_path: String,
     mapping: ColumnMapping,
+    db: State<'_, Mutex<Database>>,
 ) -> Result<Vec<ParsedTransaction>> {
     let path = PathBuf::from(&file_path);
-    tokio::task::spawn_blocking(move || csv_parser::parse_csv(&path, &mapping))
+    let result = tokio::task::spawn_blocking(move || csv_parser::parse_csv(&path, &mapping))
         .await
-        .unwrap_or_else(|e| Err(crate::error::AppError::Other(e.to_string())))
+        .unwrap_or_else(|e| Err(crate::error::AppError::Other(e.to_string())));
+    record_parser_result(&db, "csv", result.is_ok());
+    result
 }
 
 #[tauri::command]
 pub fn import_transactions(
     account_id: String,
     transactions: Vec<serde_json::Value>,
+    file_path: Option<String>,
+    app: AppHandle,
     db: State<'_, Mutex<Database>>,
 ) -> Result<ImportResult> {
+    let source = file_path.map(|p| SourceFile::read(Path::new(&p))).transpose()?;
+
     let database = db.lock().unwrap();
     let conn = database.get_connection()?;
 
+    // One transaction, so a failure partway leaves nothing half imported
+    let tx = conn.unchecked_transaction()?;
+    let result = import_into_account(&tx, &account_id, transactions, "csv", source.as_ref())?;
+    tx.commit()?;
+    record_feature_use(conn, "import_transactions");
+
+    raise_budget_alerts(&app, conn);
+    raise_goal_milestones(&app, conn);
+    emit_changed(
+        &app,
+        &[
+            TRANSACTIONS_CHANGED,
+            ACCOUNTS_CHANGED,
+            GOALS_CHANGED,
+            INVESTMENTS_CHANGED,
+        ],
+    );
+
+    Ok(result)
+}
+
+/// Statement file an import batch came from
+struct SourceFile {
+    name: Option<String>,
+    hash: String,
+}
+
+impl SourceFile {
+    fn read(path: &Path) -> Result<Self> {
+        Ok(Self {
+            name: path.file_name().map(|n| n.to_string_lossy().into_owned()),
+            hash: file_fingerprint(path)?,
+        })
+    }
+}
+
+/// SHA-256 of a file's contents, hex encoded
+fn file_fingerprint(path: &Path) -> Result<String> {
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)?;
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect())
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreviousImport {
+    pub batch_id: String,
+    pub account_id: String,
+    pub account_name: Option<String>,
+    pub file_name: Option<String>,
+    pub imported_at: String,
+    pub transactions_imported: i64,
+}
+
+/// Check whether a statement file has been imported before, by its contents rather than
+/// its name. Returns the first import that still has transactions, so a batch whose
+/// transactions were all deleted doesn't count.
+#[tauri::command]
+pub async fn find_previous_import(
+    file_path: String,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Option<PreviousImport>> {
+    let path = PathBuf::from(&file_path);
+    let hash = tokio::task::spawn_blocking(move || file_fingerprint(&path))
+        .await
+        .unwrap_or_else(|e| Err(AppError::Other(e.to_string())))?;
+
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    conn.query_row(
+        "SELECT b.id, b.account_id, a.name, b.file_name, b.import_date, b.transactions_imported
+         FROM import_batches b
+         LEFT JOIN accounts a ON a.id = b.account_id
+         WHERE b.file_hash = ?1
+         AND EXISTS (
+             SELECT 1 FROM transactions t
+             WHERE t.import_batch_id = b.id AND t.deleted_at IS NULL
+         )
+         ORDER BY b.import_date
+         LIMIT 1",
+        [&hash],
+        |row| {
+            Ok(PreviousImport {
+                batch_id: row.get(0)?,
+                account_id: row.get(1)?,
+                account_name: row.get(2)?,
+                file_name: row.get(3)?,
+                imported_at: row.get(4)?,
+                transactions_imported: row.get(5)?,
+            })
+        },
+    )
+    .optional()
+    .map_err(|e| e.into())
+}
+
+/// Rows committed per transaction by a streaming import. The database lock is released
+/// between chunks so the rest of the app stays usable while a large file imports.
+const STREAMING_CHUNK_ROWS: usize = 1000;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportProgress {
+    pub batch_id: String,
+    pub rows_read: usize,
+    pub imported: usize,
+    pub skipped: usize,
+    pub done: bool,
+}
+
+/// Import a CSV file straight into an account without loading it all first: rows are
+/// parsed as they're read and committed in chunks, with an `import:progress` event after
+/// each. If a row fails to parse, the chunks already committed stay imported under the
+/// batch and the error names the row.
+#[tauri::command]
+pub async fn import_csv_file_streaming(
+    account_id: String,
+    file_path: String,
+    mapping: ColumnMapping,
+    app: AppHandle,
+) -> Result<ImportResult> {
+    tokio::task::spawn_blocking(move || {
+        stream_csv_import(&app, &account_id, &PathBuf::from(&file_path), &mapping)
+    })
+    .await
+    .unwrap_or_else(|e| Err(AppError::Other(e.to_string())))
+}
+
+fn stream_csv_import(
+    app: &AppHandle,
+    account_id: &str,
+    path: &std::path::Path,
+    mapping: &ColumnMapping,
+) -> Result<ImportResult> {
+    let db = app.state::<Mutex<Database>>();
+    let source = SourceFile::read(path)?;
+
+    {
+        let database = db.lock().unwrap();
+        let exists: bool = database.get_connection()?.query_row(
+            "SELECT EXISTS(SELECT 1 FROM accounts WHERE id = ?1 AND deleted_at IS NULL)",
+            [account_id],
+            |row| row.get(0),
+        )?;
+        if !exists {
+            return Err(AppError::NotFound("Account not found".to_string()));
+        }
+    }
+
+    let mut progress = ImportProgress {
+        batch_id: Uuid::new_v4().to_string(),
+        rows_read: 0,
+        imported: 0,
+        skipped: 0,
+        done: false,
+    };
+    let mut categorized = 0;
+    let mut errors = Vec::new();
+
+    let mut commit_chunk = |chunk: Vec<serde_json::Value>, progress: &mut ImportProgress| {
+        let database = db.lock().unwrap();
+        let conn = database.get_connection()?;
+        let tx = conn.unchecked_transaction()?;
+        let first_row = progress.rows_read + 1 - chunk.len();
+        let (ids, skipped, row_errors) =
+            insert_import_rows(&tx, account_id, &progress.batch_id, chunk, "csv", first_row)?;
+        progress.imported += ids.len();
+        progress.skipped += skipped;
+        errors.extend(row_errors);
+        categorized += apply_category_rules_internal(&tx, Some(ids))?;
+        tx.commit()?;
+        let _ = app.emit(IMPORT_PROGRESS, progress.clone());
+        Ok::<_, AppError>(())
+    };
+
+    let mut streamed = || -> Result<()> {
+        let mut chunk = Vec::with_capacity(STREAMING_CHUNK_ROWS);
+        for row in csv_parser::stream_csv(path, mapping)? {
+            let row = row.map_err(|e| {
+                AppError::Validation(format!("Row {}: {}", progress.rows_read + 1, e))
+            })?;
+            progress.rows_read += 1;
+            chunk.push(serde_json::json!({
+                "date": row.date,
+                "amount": row.amount,
+                "payee": row.payee,
+                "memo": row.memo,
+                "pdfCategory": row.category_hint,
+                "originalAmount": row.original_amount,
+                "originalCurrency": row.original_currency,
+            }));
+            if chunk.len() == STREAMING_CHUNK_ROWS {
+                commit_chunk(std::mem::take(&mut chunk), &mut progress)?;
+            }
+        }
+        commit_chunk(chunk, &mut progress)
+    };
+    let outcome = streamed();
+    record_parser_result(&db, "csv", outcome.is_ok());
+
+    // Whatever was committed counts toward the balance, even if a later row failed
+    {
+        let database = db.lock().unwrap();
+        let conn = database.get_connection()?;
+        record_import_batch(
+            conn,
+            &progress.batch_id,
+            account_id,
+            "csv",
+            Some(&source),
+            progress.imported,
+            progress.skipped,
+        )?;
+        update_account_balance(conn, account_id)?;
+        let today = chrono::Local::now().date_naive().format("%Y-%m-%d").to_string();
+        resolve_holds(conn, &today)?;
+        record_transfer_contributions(conn)?;
+        if outcome.is_ok() {
+            record_feature_use(conn, "import_csv_file_streaming");
+        }
+        raise_budget_alerts(app, conn);
+        raise_goal_milestones(app, conn);
+    }
+
+    progress.done = true;
+    let _ = app.emit(IMPORT_PROGRESS, progress.clone());
+    emit_changed(
+        app,
+        &[TRANSACTIONS_CHANGED, ACCOUNTS_CHANGED, GOALS_CHANGED],
+    );
+
+    outcome?;
+
+    Ok(ImportResult {
+        imported: progress.imported,
+        skipped: progress.skipped,
+        categorized,
+        batch_id: progress.batch_id,
+        errors,
+    })
+}
+
+/// Insert parsed transactions into one account as a single import batch, skipping
+/// duplicates, then refresh the balance and apply category rules
+fn import_into_account(
+    conn: &rusqlite::Connection,
+    account_id: &str,
+    transactions: Vec<serde_json::Value>,
+    import_source: &str,
+    source_file: Option<&SourceFile>,
+) -> Result<ImportResult> {
     let batch_id = Uuid::new_v4().to_string();
+    let (imported_ids, skipped, errors) =
+        insert_import_rows(conn, account_id, &batch_id, transactions, import_source, 1)?;
+    let imported = imported_ids.len();
+    record_import_batch(
+        conn,
+        &batch_id,
+        account_id,
+        import_source,
+        source_file,
+        imported,
+        skipped,
+    )?;
+
+    // Update account balance
+    update_account_balance(conn, account_id)?;
+
+    // Auto-categorize imported transactions using rules
+    let categorized = apply_category_rules_internal(conn, Some(imported_ids))?;
+
+    // Newly posted charges may settle pre-authorization holds
+    let today = chrono::Local::now().date_naive().format("%Y-%m-%d").to_string();
+    resolve_holds(conn, &today)?;
+
+    // Transfers into a goal's contribution account count toward the goal
+    record_transfer_contributions(conn)?;
+
+    Ok(ImportResult {
+        imported,
+        skipped,
+        categorized,
+        batch_id,
+        errors,
+    })
+}
+
+fn record_import_batch(
+    conn: &rusqlite::Connection,
+    batch_id: &str,
+    account_id: &str,
+    import_source: &str,
+    source_file: Option<&SourceFile>,
+    imported: usize,
+    skipped: usize,
+) -> Result<()> {
+    conn.execute(
+        "INSERT INTO import_batches (id, account_id, import_source, file_name, file_hash,
+                transactions_imported, transactions_skipped)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        rusqlite::params![
+            batch_id,
+            account_id,
+            import_source,
+            source_file.and_then(|f| f.name.as_deref()),
+            source_file.map(|f| f.hash.as_str()),
+            imported as i64,
+            skipped as i64,
+        ],
+    )?;
+    Ok(())
+}
+
+/// Insert parsed transactions into an account under an import batch, skipping duplicates
+/// and rows whose amount can't be read. Returns the ids inserted, how many duplicates were
+/// skipped, and an error per unreadable row, numbered from `first_row`.
+fn insert_import_rows(
+    conn: &rusqlite::Connection,
+    account_id: &str,
+    batch_id: &str,
+    transactions: Vec<serde_json::Value>,
+    import_source: &str,
+    first_row: usize,
+) -> Result<(Vec<String>, usize, Vec<String>)> {
     let now = chrono::Utc::now().to_rfc3339();
-    let mut imported = 0;
     let mut skipped = 0;
+    let mut errors = Vec::new();
 
     let mut imported_ids: Vec<String> = Vec::new();
 
@@ -60,13 +417,27 @@ pub fn import_transactions(
         }
     }
 
-    for tx in transactions {
+    for (index, tx) in transactions.into_iter().enumerate() {
         let date = tx["date"].as_str().unwrap_or("");
-        let amount = tx["amount"].as_i64().unwrap_or(0);
-        let payee = tx["payee"].as_str();
+        let amount = match input_amount(&tx) {
+            Ok(amount) => amount.unwrap_or(0),
+            Err(e) => {
+                errors.push(format!("Row {}: {}", first_row + index, e));
+                continue;
+            }
+        };
+        let original_currency = tx["originalCurrency"].as_str().map(|c| c.trim().to_uppercase());
+        let raw_payee = tx["payee"].as_str();
         let memo = tx["memo"].as_str();
         let mut category_id = tx["categoryId"].as_str().map(|s| s.to_string());
 
+        // Map statement text like "AMZN MKTP" onto its canonical payee
+        let resolved_payee = match raw_payee {
+            Some(raw) => resolve_payee(conn, raw)?,
+            None => None,
+        };
+        let payee = resolved_payee.as_ref().map(|p| p.name.as_str()).or(raw_payee);
+
         // If no categoryId but we have a pdfCategory, try to resolve it
         if category_id.is_none() {
             if let Some(pdf_category) = tx["pdfCategory"].as_str() {
@@ -76,17 +447,25 @@ pub fn import_transactions(
                 }
             }
         }
+        if category_id.is_none() {
+            category_id = resolved_payee.as_ref().and_then(|p| p.default_category_id.clone());
+        }
         let category_id = category_id;
 
-        // Simple duplicate detection: same account, date, amount, payee
+        // Simple duplicate detection: same account, date, amount, payee. A purchase paid
+        // partly by gift card is matched on its whole amount.
         let existing: Option<String> = conn
             .query_row(
                 "SELECT id FROM transactions
-                 WHERE account_id = ?1 AND date = ?2 AND amount = ?3
-                 AND (payee = ?4 OR (payee IS NULL AND ?4 IS NULL))
+                 WHERE account_id = ?1 AND date = ?2
+                 AND (amount = ?3 OR amount - (
+                     SELECT u.amount FROM gift_card_usages u
+                     WHERE u.transaction_id = transactions.id
+                 ) = ?3)
+                 AND (payee = ?4 OR original_payee = ?5 OR (payee IS NULL AND ?4 IS NULL))
                  AND deleted_at IS NULL
                  LIMIT 1",
-                rusqlite::params![account_id, date, amount, payee],
+                rusqlite::params![account_id, date, amount, payee, raw_payee],
                 |row| row.get(0),
             )
             .ok();
@@ -100,184 +479,44 @@ pub fn import_transactions(
         conn.execute(
             "INSERT INTO transactions (
                 id, account_id, date, amount, payee, original_payee, memo,
-                category_id, status, import_source, import_batch_id, created_at, updated_at
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?5, ?6, ?7, 'cleared', 'csv', ?8, ?9, ?9)",
+                category_id, status, import_source, import_batch_id, created_at, updated_at,
+                original_amount, original_currency
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, 'cleared', ?13, ?9, ?10, ?10, ?11, ?12)",
             rusqlite::params![
                 id,
                 account_id,
                 date,
                 amount,
                 payee,
+                raw_payee,
                 memo,
                 category_id.as_deref(),
                 batch_id,
                 now,
+                tx["originalAmount"].as_i64(),
+                original_currency,
+                import_source,
             ],
         )?;
         imported_ids.push(id);
-        imported += 1;
     }
 
-    // Update account balance
-    update_account_balance(conn, &account_id)?;
-
-    // Auto-categorize imported transactions using rules
-    let categorized = apply_category_rules_internal(conn, Some(imported_ids))?;
-
-    Ok(ImportResult {
-        imported,
-        skipped,
-        categorized,
-        batch_id,
-    })
+    Ok((imported_ids, skipped, errors))
 }
 
 /// Internal function to apply category rules to transactions
-/// This is called automatically after import and can also be exposed via commands
+/// This is called automatically after import and can also be exposed via commands.
+/// Rules are skipped when automatic rule application is turned off.
 fn apply_category_rules_internal(
     conn: &rusqlite::Connection,
     transaction_ids: Option<Vec<String>>,
 ) -> Result<i32> {
-    // Get all active rules ordered by priority
-    let mut rules_stmt = conn.prepare(
-        "SELECT id, category_id, rule_type, pattern, amount_min, amount_max, account_id
-         FROM category_rules
-         WHERE is_active = 1
-         ORDER BY priority DESC"
-    )?;
-
-    let rules: Vec<(String, String, String, String, Option<i64>, Option<i64>, Option<String>)> = rules_stmt
-        .query_map([], |row| {
-            Ok((
-                row.get::<_, String>(0)?,
-                row.get::<_, String>(1)?,
-                row.get::<_, String>(2)?,
-                row.get::<_, String>(3)?,
-                row.get::<_, Option<i64>>(4)?,
-                row.get::<_, Option<i64>>(5)?,
-                row.get::<_, Option<String>>(6)?,
-            ))
-        })?
-        .filter_map(|r| r.ok())
-        .collect();
-
-    if rules.is_empty() {
-        return Ok(0);
-    }
-
-    // Get transactions to categorize
-    let tx_query = if let Some(ref ids) = transaction_ids {
-        if ids.is_empty() {
-            return Ok(0);
-        }
-        let placeholders: Vec<String> = ids.iter().map(|_| "?".to_string()).collect();
-        format!(
-            "SELECT id, account_id, payee, amount FROM transactions
-             WHERE id IN ({}) AND category_id IS NULL AND deleted_at IS NULL",
-            placeholders.join(", ")
-        )
-    } else {
-        "SELECT id, account_id, payee, amount FROM transactions
-         WHERE category_id IS NULL AND deleted_at IS NULL".to_string()
-    };
-
-    let mut tx_stmt = conn.prepare(&tx_query)?;
-
-    let transactions: Vec<(String, String, Option<String>, i64)> = if let Some(ref ids) = transaction_ids {
-        tx_stmt
-            .query_map(rusqlite::params_from_iter(ids.iter()), |row| {
-                Ok((
-                    row.get::<_, String>(0)?,
-                    row.get::<_, String>(1)?,
-                    row.get::<_, Option<String>>(2)?,
-                    row.get::<_, i64>(3)?,
-                ))
-            })?
-            .filter_map(|r| r.ok())
-            .collect()
+    let mut categorized_count = if auto_apply_rules_enabled(conn)? {
+        apply_rules(conn, transaction_ids.as_deref(), true)?
     } else {
-        tx_stmt
-            .query_map([], |row| {
-                Ok((
-                    row.get::<_, String>(0)?,
-                    row.get::<_, String>(1)?,
-                    row.get::<_, Option<String>>(2)?,
-                    row.get::<_, i64>(3)?,
-                ))
-            })?
-            .filter_map(|r| r.ok())
-            .collect()
+        0
     };
-
     let now = chrono::Utc::now().to_rfc3339();
-    let mut categorized_count = 0;
-
-    for (tx_id, tx_account_id, tx_payee, tx_amount) in transactions {
-        for (_rule_id, category_id, rule_type, pattern, amount_min, amount_max, rule_account_id) in &rules {
-            // Check account filter
-            if let Some(acc_id) = rule_account_id {
-                if acc_id != &tx_account_id {
-                    continue;
-                }
-            }
-
-            // Check amount range
-            if let Some(min) = amount_min {
-                if tx_amount < *min {
-                    continue;
-                }
-            }
-            if let Some(max) = amount_max {
-                if tx_amount > *max {
-                    continue;
-                }
-            }
-
-            // Check pattern match
-            let matches = match rule_type.as_str() {
-                "payee_contains" => {
-                    if let Some(ref payee) = tx_payee {
-                        payee.to_lowercase().contains(&pattern.to_lowercase())
-                    } else {
-                        false
-                    }
-                }
-                "payee_exact" => {
-                    if let Some(ref payee) = tx_payee {
-                        payee.to_lowercase() == pattern.to_lowercase()
-                    } else {
-                        false
-                    }
-                }
-                "payee_starts_with" => {
-                    if let Some(ref payee) = tx_payee {
-                        payee.to_lowercase().starts_with(&pattern.to_lowercase())
-                    } else {
-                        false
-                    }
-                }
-                "payee_regex" => {
-                    if let Some(ref payee) = tx_payee {
-                        regex::Regex::new(pattern)
-                            .map(|re| re.is_match(payee))
-                            .unwrap_or(false)
-                    } else {
-                        false
-                    }
-                }
-                _ => false,
-            };
-
-            if matches {
-                conn.execute(
-                    "UPDATE transactions SET category_id = ?1, updated_at = ?2 WHERE id = ?3",
-                    rusqlite::params![category_id, now, tx_id],
-                )?;
-                categorized_count += 1;
-                break; // Use first matching rule
-            }
-        }
-    }
 
     // Second pass: learn from existing transactions with same payee
     // Get remaining uncategorized transactions
@@ -339,19 +578,21 @@ fn apply_category_rules_internal(
     Ok(categorized_count)
 }
 
+/// Recompute an account's balance as its opening balance plus all of its transactions
 fn update_account_balance(conn: &rusqlite::Connection, account_id: &str) -> Result<()> {
-    let balance: i64 = conn.query_row(
-        "SELECT COALESCE(SUM(amount), 0) FROM transactions
-         WHERE account_id = ?1 AND deleted_at IS NULL",
-        [account_id],
-        |row| row.get(0),
-    )?;
-
     conn.execute(
-        "UPDATE accounts SET current_balance = ?1, updated_at = ?2 WHERE id = ?3",
-        rusqlite::params![balance, chrono::Utc::now().to_rfc3339(), account_id],
+        "UPDATE accounts SET
+            current_balance = opening_balance + COALESCE((
+                SELECT SUM(amount) FROM transactions
+                WHERE account_id = accounts.id AND deleted_at IS NULL
+            ), 0),
+            updated_at = ?1
+         WHERE id = ?2",
+        rusqlite::params![chrono::Utc::now().to_rfc3339(), account_id],
     )?;
 
+    refresh_available_balance(conn, account_id)?;
+
     Ok(())
 }
 
@@ -362,6 +603,8 @@ pub struct ImportResult {
     pub skipped: usize,
     pub categorized: i32,
     pub batch_id: String,
+    /// One message per row that couldn't be imported, naming the row
+    pub errors: Vec<String>,
 }
 
 // Bank of America text file parser
@@ -374,28 +617,12 @@ pub async fn preview_boa_file(file_path: String) -> Result<BoaPreview> {
 }
 
 #[tauri::command]
-pub async fn parse_boa_file(file_path: String) -> Result<Vec<serde_json::Value>> {
-    let path = PathBuf::from(&file_path);
-    tokio::task::spawn_blocking(move || {
-        let transactions = boa_parser::parse_boa(&path)?;
-
-        // Convert to JSON values for the frontend
-        let result: Vec<serde_json::Value> = transactions
-            .into_iter()
-            .map(|tx| {
-                serde_json::json!({
-                    "date": tx.date,
-                    "amount": tx.amount,
-                    "payee": tx.description,
-                    "memo": tx.description,
-                })
-            })
-            .collect();
-
-        Ok(result)
-    })
-    .await
-    .unwrap_or_else(|e| Err(crate::error::AppError::Other(e.to_string())))
+pub async fn parse_boa_file(
+    file_path: String,
+    registry: State<'_, ImporterRegistry>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<serde_json::Value>> {
+    parse_with_importer(registry.get("boa")?, file_path, &db).await
 }
 
 // PDF file parser
@@ -408,27 +635,304 @@ pub async fn preview_pdf_file(file_path: String) -> Result<PdfPreview> {
 }
 
 #[tauri::command]
-pub async fn parse_pdf_file(file_path: String) -> Result<Vec<serde_json::Value>> {
+pub async fn parse_pdf_file(
+    file_path: String,
+    registry: State<'_, ImporterRegistry>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<serde_json::Value>> {
+    parse_with_importer(registry.get("pdf")?, file_path, &db).await
+}
+
+/// Parse a statement file off the main thread, recording the outcome under the
+/// importer's id
+async fn parse_with_importer(
+    importer: Arc<dyn StatementImporter>,
+    file_path: String,
+    db: &State<'_, Mutex<Database>>,
+) -> Result<Vec<serde_json::Value>> {
+    let id = importer.id();
     let path = PathBuf::from(&file_path);
-    tokio::task::spawn_blocking(move || {
-        let transactions = pdf_parser::parse_pdf(&path)?;
-
-        // Convert to JSON values for the frontend
-        let result: Vec<serde_json::Value> = transactions
-            .into_iter()
-            .map(|tx| {
-                serde_json::json!({
-                    "date": tx.date,
-                    "amount": tx.amount,
-                    "payee": tx.description,
-                    "memo": tx.description,
-                    "pdfCategory": tx.category,
-                })
-            })
-            .collect();
+    let result = tokio::task::spawn_blocking(move || importer.parse(&path))
+        .await
+        .unwrap_or_else(|e| Err(crate::error::AppError::Other(e.to_string())));
+    record_parser_result(db, id, result.is_ok());
+    result
+}
 
-        Ok(result)
-    })
-    .await
-    .unwrap_or_else(|e| Err(crate::error::AppError::Other(e.to_string())))
+/// The importer picked for a statement file: the one named, or the best detected match
+fn statement_importer(
+    registry: &ImporterRegistry,
+    file_path: &str,
+    importer_id: Option<&str>,
+) -> Result<Arc<dyn StatementImporter>> {
+    match importer_id {
+        Some(id) => registry.get(id),
+        None => registry
+            .detect(Path::new(file_path))
+            .ok_or_else(|| AppError::Validation("No importer recognizes this file".to_string())),
+    }
+}
+
+#[tauri::command]
+pub fn list_statement_importers(registry: State<'_, ImporterRegistry>) -> Vec<ImporterInfo> {
+    registry.list()
+}
+
+#[tauri::command]
+pub async fn detect_statement_importer(
+    file_path: String,
+    registry: State<'_, ImporterRegistry>,
+) -> Result<Option<ImporterInfo>> {
+    let Some(importer) = registry.detect(Path::new(&file_path)) else {
+        return Ok(None);
+    };
+    Ok(registry.list().into_iter().find(|i| i.id == importer.id()))
+}
+
+#[tauri::command]
+pub async fn preview_statement_file(
+    file_path: String,
+    importer_id: Option<String>,
+    registry: State<'_, ImporterRegistry>,
+) -> Result<StatementPreview> {
+    let importer = statement_importer(&registry, &file_path, importer_id.as_deref())?;
+    let path = PathBuf::from(&file_path);
+    tokio::task::spawn_blocking(move || importer.preview(&path, 20))
+        .await
+        .unwrap_or_else(|e| Err(crate::error::AppError::Other(e.to_string())))
+}
+
+/// Parse a statement with the named importer, or whichever recognizes the file
+#[tauri::command]
+pub async fn parse_statement_file(
+    file_path: String,
+    importer_id: Option<String>,
+    registry: State<'_, ImporterRegistry>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<serde_json::Value>> {
+    let importer = statement_importer(&registry, &file_path, importer_id.as_deref())?;
+    parse_with_importer(importer, file_path, &db).await
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmpowerImportResult {
+    pub accounts_created: usize,
+    pub transactions_imported: usize,
+    pub transactions_skipped: usize,
+    pub categorized: i32,
+    pub holdings_imported: usize,
+}
+
+/// Guess an account type from an Empower account name like "Chase Sapphire Card" or
+/// "Fidelity Roth IRA"
+fn guess_account_type(name: &str, has_holdings: bool) -> &'static str {
+    let name = name.to_lowercase();
+    let has = |words: &[&str]| words.iter().any(|w| name.contains(w));
+
+    if has(&["401k", "401(k)", "403b", "ira", "roth", "pension", "retirement"]) {
+        "retirement"
+    } else if has_holdings || has(&["brokerage", "invest"]) {
+        "investment"
+    } else if has(&["credit", "card", "visa", "mastercard", "amex"]) {
+        "credit_card"
+    } else if has(&["saving"]) {
+        "savings"
+    } else if has(&["loan", "mortgage"]) {
+        "loan"
+    } else {
+        "checking"
+    }
+}
+
+/// Match an Empower account to an existing account by name or alias, creating it if needed
+fn find_or_create_account(
+    conn: &rusqlite::Connection,
+    name: &str,
+    has_holdings: bool,
+    created: &mut usize,
+) -> Result<String> {
+    let existing: Option<String> = conn
+        .query_row(
+            "SELECT id FROM accounts
+             WHERE deleted_at IS NULL
+               AND (name = ?1 COLLATE NOCASE
+                    OR id IN (SELECT account_id FROM account_aliases WHERE alias = ?1))
+             LIMIT 1",
+            [name],
+            |row| row.get(0),
+        )
+        .optional()?;
+
+    if let Some(id) = existing {
+        return Ok(id);
+    }
+
+    let id = Uuid::new_v4().to_string();
+    let now = chrono::Utc::now().to_rfc3339();
+    conn.execute(
+        "INSERT INTO accounts (id, name, account_type, currency, current_balance, created_at, updated_at)
+         VALUES (?1, ?2, ?3, 'USD', 0, ?4, ?4)",
+        rusqlite::params![id, name, guess_account_type(name, has_holdings), now],
+    )?;
+    *created += 1;
+
+    Ok(id)
+}
+
+// Empower (Personal Capital) export parser
+#[tauri::command]
+pub async fn preview_empower_file(file_path: String) -> Result<EmpowerPreview> {
+    let path = PathBuf::from(&file_path);
+    tokio::task::spawn_blocking(move || empower_parser::preview_empower(&path, 20))
+        .await
+        .unwrap_or_else(|e| Err(crate::error::AppError::Other(e.to_string())))
+}
+
+/// Import one or more Empower exports in a single pass: accounts are matched by name or
+/// created, transactions are imported per account, and holdings replace the positions held
+#[tauri::command]
+pub fn import_empower_files(
+    file_paths: Vec<String>,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<EmpowerImportResult> {
+    let exports = file_paths
+        .iter()
+        .map(|p| {
+            let export = empower_parser::parse_empower(&PathBuf::from(p));
+            record_parser_result(&db, "empower", export.is_ok());
+            export
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let mut result = EmpowerImportResult {
+        accounts_created: 0,
+        transactions_imported: 0,
+        transactions_skipped: 0,
+        categorized: 0,
+        holdings_imported: 0,
+    };
+
+    let tx = conn.unchecked_transaction()?;
+
+    // Group transactions by account so each account gets its own import batch
+    let mut by_account: BTreeMap<String, Vec<serde_json::Value>> = BTreeMap::new();
+    for export in &exports {
+        for t in &export.transactions {
+            by_account.entry(t.account.clone()).or_default().push(serde_json::json!({
+                "date": t.date,
+                "amount": t.amount,
+                "payee": t.description,
+                "memo": t.tags,
+                "pdfCategory": t.category,
+            }));
+        }
+    }
+
+    let holding_accounts: std::collections::HashSet<&str> = exports
+        .iter()
+        .flat_map(|e| e.holdings.iter().map(|h| h.account.as_str()))
+        .collect();
+
+    for (account_name, transactions) in by_account {
+        let has_holdings = holding_accounts.contains(account_name.as_str());
+        let account_id =
+            find_or_create_account(&tx, &account_name, has_holdings, &mut result.accounts_created)?;
+        let imported = import_into_account(&tx, &account_id, transactions, "empower", None)?;
+        result.transactions_imported += imported.imported;
+        result.transactions_skipped += imported.skipped;
+        result.categorized += imported.categorized;
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let mut holdings_value: BTreeMap<String, i64> = BTreeMap::new();
+
+    for holding in exports.iter().flat_map(|e| e.holdings.iter()) {
+        let account_id =
+            find_or_create_account(&tx, &holding.account, true, &mut result.accounts_created)?;
+
+        let price = holding.price.or_else(|| {
+            (holding.quantity != 0.0).then(|| (holding.value as f64 / holding.quantity).round() as i64)
+        });
+
+        tx.execute(
+            "INSERT INTO securities (id, symbol, name, current_price, price_updated_at, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?5, ?5)
+             ON CONFLICT(symbol) DO UPDATE SET
+                name = COALESCE(securities.name, excluded.name),
+                current_price = COALESCE(excluded.current_price, securities.current_price),
+                price_updated_at = excluded.price_updated_at,
+                updated_at = excluded.updated_at",
+            rusqlite::params![Uuid::new_v4().to_string(), holding.symbol, holding.name, price, now],
+        )?;
+
+        let security_id: String = tx.query_row(
+            "SELECT id FROM securities WHERE symbol = ?1",
+            [&holding.symbol],
+            |row| row.get(0),
+        )?;
+
+        let existing: Option<String> = tx
+            .query_row(
+                "SELECT id FROM holdings WHERE account_id = ?1 AND security_id = ?2",
+                [&account_id, &security_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        match existing {
+            Some(id) => {
+                tx.execute(
+                    "UPDATE holdings SET quantity = ?1, cost_basis = COALESCE(?2, cost_basis), updated_at = ?3
+                     WHERE id = ?4",
+                    rusqlite::params![holding.quantity, holding.cost_basis, now, id],
+                )?;
+            }
+            None => {
+                tx.execute(
+                    "INSERT INTO holdings (id, account_id, security_id, quantity, cost_basis, created_at, updated_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?6)",
+                    rusqlite::params![
+                        Uuid::new_v4().to_string(),
+                        account_id,
+                        security_id,
+                        holding.quantity,
+                        holding.cost_basis,
+                        now,
+                    ],
+                )?;
+            }
+        }
+
+        *holdings_value.entry(account_id).or_default() += holding.value;
+        result.holdings_imported += 1;
+    }
+
+    // Investment account balances follow the market value of the holdings just imported.
+    // The opening balance takes up the difference, so a later recompute keeps the value.
+    for (account_id, value) in holdings_value {
+        tx.execute(
+            "UPDATE accounts SET opening_balance = opening_balance + ?1 - current_balance,
+                current_balance = ?1, updated_at = ?2
+             WHERE id = ?3",
+            rusqlite::params![value, now, account_id],
+        )?;
+        refresh_available_balance(&tx, &account_id)?;
+    }
+
+    tx.commit()?;
+    record_feature_use(conn, "import_empower_files");
+
+    raise_budget_alerts(&app, conn);
+    raise_goal_milestones(&app, conn);
+    emit_changed(
+        &app,
+        &[TRANSACTIONS_CHANGED, ACCOUNTS_CHANGED, GOALS_CHANGED],
+    );
+
+    Ok(result)
 }
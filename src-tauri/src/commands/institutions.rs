@@ -0,0 +1,183 @@
+use crate::db::Database;
+use crate::error::{AppError, Result};
+use crate::events::{emit_changed, ACCOUNTS_CHANGED};
+use crate::models::Institution;
+use rusqlite::Connection;
+use std::sync::Mutex;
+use tauri::{AppHandle, State};
+use uuid::Uuid;
+
+const INSTITUTION_COLUMNS: &str = "id, name, ofx_org, ofx_fid, ofx_url, ofx_broker_id, logo_url,
+    primary_color, website, created_at, updated_at";
+
+fn institution_from_row(row: &rusqlite::Row) -> rusqlite::Result<Institution> {
+    Ok(Institution {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        ofx_org: row.get(2)?,
+        ofx_fid: row.get(3)?,
+        ofx_url: row.get(4)?,
+        ofx_broker_id: row.get(5)?,
+        logo_url: row.get(6)?,
+        primary_color: row.get(7)?,
+        website: row.get(8)?,
+        created_at: row.get(9)?,
+        updated_at: row.get(10)?,
+    })
+}
+
+fn fetch_institution(conn: &Connection, id: &str) -> Result<Institution> {
+    conn.query_row(
+        &format!(
+            "SELECT {} FROM institutions WHERE id = ?1",
+            INSTITUTION_COLUMNS
+        ),
+        [id],
+        institution_from_row,
+    )
+    .map_err(|_| AppError::NotFound("Institution not found".to_string()))
+}
+
+/// Check the name and logo color of institution input. The name is required unless
+/// `partial`, for updates that leave it as is.
+fn validate_institution(data: &serde_json::Value, partial: bool) -> Result<()> {
+    match data["name"].as_str().map(str::trim) {
+        Some("") => return Err(AppError::Validation("Name is required".to_string())),
+        None if !partial => return Err(AppError::Validation("Name is required".to_string())),
+        _ => {}
+    }
+    if let Some(color) = data["primaryColor"].as_str() {
+        let is_hex = color.len() == 7
+            && color.starts_with('#')
+            && color[1..].chars().all(|c| c.is_ascii_hexdigit());
+        if !is_hex {
+            return Err(AppError::Validation(format!(
+                "Invalid logo color: {}. Use #rrggbb",
+                color
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn list_institutions(db: State<'_, Mutex<Database>>) -> Result<Vec<Institution>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM institutions ORDER BY name COLLATE NOCASE",
+        INSTITUTION_COLUMNS
+    ))?;
+    let institutions = stmt
+        .query_map([], institution_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(institutions)
+}
+
+#[tauri::command]
+pub fn create_institution(
+    data: serde_json::Value,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Institution> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    validate_institution(&data, false)?;
+
+    let id = Uuid::new_v4().to_string();
+    let now = chrono::Utc::now().to_rfc3339();
+
+    conn.execute(
+        "INSERT INTO institutions (id, name, website, primary_color, logo_url, created_at,
+                updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        rusqlite::params![
+            id,
+            data["name"].as_str().map(str::trim),
+            data["website"].as_str(),
+            data["primaryColor"].as_str(),
+            data["logoUrl"].as_str(),
+            now,
+            now,
+        ],
+    )?;
+
+    emit_changed(&app, &[ACCOUNTS_CHANGED]);
+
+    fetch_institution(conn, &id)
+}
+
+/// Update an institution's name, website, logo color or logo. Fields left out are kept;
+/// the optional ones can be cleared with null.
+#[tauri::command]
+pub fn update_institution(
+    id: String,
+    data: serde_json::Value,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Institution> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    fetch_institution(conn, &id)?;
+    validate_institution(&data, true)?;
+
+    let now = chrono::Utc::now().to_rfc3339();
+
+    conn.execute(
+        "UPDATE institutions SET
+            name = COALESCE(?1, name),
+            website = CASE WHEN ?2 THEN ?3 ELSE website END,
+            primary_color = CASE WHEN ?4 THEN ?5 ELSE primary_color END,
+            logo_url = CASE WHEN ?6 THEN ?7 ELSE logo_url END,
+            updated_at = ?8
+         WHERE id = ?9",
+        rusqlite::params![
+            data["name"].as_str().map(str::trim),
+            data.get("website").is_some(),
+            data["website"].as_str(),
+            data.get("primaryColor").is_some(),
+            data["primaryColor"].as_str(),
+            data.get("logoUrl").is_some(),
+            data["logoUrl"].as_str(),
+            now,
+            id,
+        ],
+    )?;
+
+    emit_changed(&app, &[ACCOUNTS_CHANGED]);
+
+    fetch_institution(conn, &id)
+}
+
+/// Delete an institution. Its accounts are kept, with no institution.
+#[tauri::command]
+pub fn delete_institution(
+    id: String,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<()> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let now = chrono::Utc::now().to_rfc3339();
+
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(
+        "UPDATE accounts SET institution_id = NULL, updated_at = ?1 WHERE institution_id = ?2",
+        [&now, &id],
+    )?;
+    let deleted = tx.execute("DELETE FROM institutions WHERE id = ?1", [&id])?;
+    if deleted == 0 {
+        return Err(AppError::NotFound("Institution not found".to_string()));
+    }
+    tx.commit()?;
+
+    emit_changed(&app, &[ACCOUNTS_CHANGED]);
+
+    Ok(())
+}
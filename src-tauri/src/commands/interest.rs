@@ -0,0 +1,197 @@
+use crate::commands::debt_payoff::monthly_interest;
+use crate::commands::goals::compounding_period;
+use crate::commands::transactions::{insert_transaction, INTEREST_TYPE};
+use crate::db::Database;
+use crate::error::Result;
+use crate::events::{emit_changed, ACCOUNTS_CHANGED, TRANSACTIONS_CHANGED};
+use chrono::{Datelike, Duration, Months, NaiveDate};
+use rusqlite::Connection;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, State};
+
+/// The month before `today`'s, as YYYY-MM: the latest month that's over
+pub(crate) fn last_month_accrued(today: NaiveDate) -> String {
+    let previous = today.with_day(1).unwrap_or(today) - Duration::days(1);
+    previous.format("%Y-%m").to_string()
+}
+
+/// Average of an account's end-of-day balances from `start` to `end`, walking back from
+/// its balance at the end of `end` through the amounts posted each day
+fn average_daily_balance(
+    end_balance: i64,
+    daily_amounts: &HashMap<String, i64>,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> i64 {
+    let mut balance = end_balance;
+    let mut total = 0;
+    let mut day = end;
+    while day >= start {
+        total += balance;
+        balance -= daily_amounts
+            .get(&day.format("%Y-%m-%d").to_string())
+            .copied()
+            .unwrap_or(0);
+        day -= Duration::days(1);
+    }
+    let days = (end - start).num_days() + 1;
+    (total as f64 / days as f64).round() as i64
+}
+
+/// Post one month's estimated interest on the account's average daily balance, dated the
+/// last day of the month. A savings account's rate is its APY, credited monthly whatever
+/// its compounding; a loan's is its APR. Returns whether a transaction was posted.
+fn accrue_month(
+    conn: &Connection,
+    account_id: &str,
+    account_type: &str,
+    rate: f64,
+    start: NaiveDate,
+) -> Result<bool> {
+    let end = start + Months::new(1) - Duration::days(1);
+    let end_date = end.format("%Y-%m-%d").to_string();
+
+    let (current_balance, after_end): (i64, i64) = conn.query_row(
+        "SELECT current_balance, COALESCE((
+             SELECT SUM(amount) FROM transactions
+             WHERE account_id = accounts.id AND deleted_at IS NULL AND date > ?2
+         ), 0)
+         FROM accounts WHERE id = ?1",
+        [account_id, &end_date],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+    let mut stmt = conn.prepare(
+        "SELECT date, SUM(amount) FROM transactions
+         WHERE account_id = ?1 AND deleted_at IS NULL AND date >= ?2 AND date <= ?3
+         GROUP BY date",
+    )?;
+    let daily_amounts: HashMap<String, i64> = stmt
+        .query_map(
+            [account_id, &start.format("%Y-%m-%d").to_string(), &end_date],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let average = average_daily_balance(current_balance - after_end, &daily_amounts, start, end);
+    // Interest grows the balance owed on a loan, and is only earned on money in savings
+    let amount = match account_type {
+        "savings" if average > 0 => {
+            (average as f64 * compounding_period(rate, "monthly").0).round() as i64
+        }
+        "loan" => monthly_interest(average, rate),
+        _ => 0,
+    };
+    if amount == 0 {
+        return Ok(false);
+    }
+
+    insert_transaction(
+        conn,
+        &serde_json::json!({
+            "accountId": account_id,
+            "date": end_date,
+            "amount": amount,
+            "payee": "Estimated Interest",
+            "notes": format!(
+                "Estimated at {}% on an average balance of {:.2}",
+                rate,
+                average as f64 / 100.0
+            ),
+            "transactionType": INTEREST_TYPE,
+        }),
+    )?;
+    Ok(true)
+}
+
+/// Post estimated interest for every month that's over since each opted-in savings and
+/// loan account was last accrued, catching up months missed while the app was closed.
+/// Returns the number of interest transactions posted.
+pub(crate) fn accrue_interest(conn: &Connection, today: NaiveDate) -> Result<i64> {
+    let through = last_month_accrued(today);
+    let mut stmt = conn.prepare(
+        "SELECT id, account_type, interest_rate, interest_accrued_through FROM accounts
+         WHERE deleted_at IS NULL AND is_active = 1 AND accrue_interest = 1
+           AND account_type IN ('savings', 'loan') AND interest_rate > 0",
+    )?;
+    let accounts: Vec<(String, String, f64, Option<String>)> = stmt
+        .query_map([], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let tx = conn.unchecked_transaction()?;
+    let mut posted = 0;
+    for (account_id, account_type, rate, accrued_through) in accounts {
+        let last = accrued_through
+            .and_then(|m| NaiveDate::parse_from_str(&format!("{}-01", m), "%Y-%m-%d").ok());
+        if let Some(last) = last {
+            let mut month = last + Months::new(1);
+            while month.format("%Y-%m").to_string() <= through {
+                if accrue_month(&tx, &account_id, &account_type, rate, month)? {
+                    posted += 1;
+                }
+                month = month + Months::new(1);
+            }
+        }
+        tx.execute(
+            "UPDATE accounts SET interest_accrued_through = ?1 WHERE id = ?2",
+            [&through, &account_id],
+        )?;
+    }
+    tx.commit()?;
+
+    Ok(posted)
+}
+
+/// Post due interest on a background thread once the database is unlocked, as for goal
+/// contributions. A failed run is left for the next unlock.
+pub(crate) fn spawn_interest_accrual(app: AppHandle) {
+    std::thread::spawn(move || {
+        let db = app.state::<Mutex<Database>>();
+        let database = db.lock().unwrap();
+        let Ok(conn) = database.get_connection() else {
+            return;
+        };
+        if accrue_interest(conn, chrono::Local::now().date_naive()).unwrap_or(0) > 0 {
+            emit_changed(&app, &[TRANSACTIONS_CHANGED, ACCOUNTS_CHANGED]);
+        }
+    });
+}
+
+/// Post any estimated interest that's due. The same job runs on unlock.
+#[tauri::command]
+pub fn run_interest_accrual(app: AppHandle, db: State<'_, Mutex<Database>>) -> Result<i64> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let posted = accrue_interest(conn, chrono::Local::now().date_naive())?;
+    if posted > 0 {
+        emit_changed(&app, &[TRANSACTIONS_CHANGED, ACCOUNTS_CHANGED]);
+    }
+
+    Ok(posted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_average_daily_balance() {
+        // 1,000 for the first 10 days of April, then 4,000 after a deposit on the 11th
+        let daily_amounts = HashMap::from([("2024-04-11".to_string(), 3000)]);
+        assert_eq!(
+            average_daily_balance(4000, &daily_amounts, date("2024-04-01"), date("2024-04-30")),
+            3000
+        );
+        assert_eq!(last_month_accrued(date("2024-03-15")), "2024-02");
+        assert_eq!(last_month_accrued(date("2024-01-01")), "2023-12");
+    }
+}
@@ -0,0 +1,737 @@
+use crate::commands::accounts::fetch_account;
+use crate::commands::investments::{
+    adjust_holding, fetch_security, holding_position, QUANTITY_EPSILON,
+};
+use crate::commands::settings::read_setting;
+use crate::commands::transactions::{
+    insert_transaction, soft_delete_transaction, DIVIDEND_TYPE, INVESTMENT_FEE_TYPE,
+    INVESTMENT_TRADE_TYPE,
+};
+use crate::db::Database;
+use crate::error::{AppError, Result};
+use crate::events::{emit_changed, ACCOUNTS_CHANGED, INVESTMENTS_CHANGED, TRANSACTIONS_CHANGED};
+use crate::models::InvestmentTransaction;
+use chrono::NaiveDate;
+use rusqlite::{Connection, OptionalExtension};
+use std::sync::Mutex;
+use tauri::{AppHandle, State};
+use uuid::Uuid;
+
+/// Kinds of investment transaction
+const INVESTMENT_TRANSACTION_TYPES: &[&str] = &["buy", "sell", "dividend", "split", "fee"];
+
+const INVESTMENT_TRANSACTION_COLUMNS: &str = "id, account_id, security_id,
+     (SELECT symbol FROM securities WHERE id = investment_transactions.security_id),
+     transaction_type, date, quantity, price_per_unit, total_amount, COALESCE(fees, 0),
+     quantity_change, cost_basis_change, transaction_id, notes, created_at, updated_at,
+     realized_gain";
+
+fn investment_transaction_from_row(row: &rusqlite::Row) -> rusqlite::Result<InvestmentTransaction> {
+    Ok(InvestmentTransaction {
+        id: row.get(0)?,
+        account_id: row.get(1)?,
+        security_id: row.get(2)?,
+        symbol: row.get(3)?,
+        transaction_type: row.get(4)?,
+        date: row.get(5)?,
+        quantity: row.get(6)?,
+        price_per_unit: row.get(7)?,
+        total_amount: row.get(8)?,
+        fees: row.get(9)?,
+        quantity_change: row.get(10)?,
+        cost_basis_change: row.get(11)?,
+        transaction_id: row.get(12)?,
+        notes: row.get(13)?,
+        created_at: row.get(14)?,
+        updated_at: row.get(15)?,
+        realized_gain: row.get(16)?,
+    })
+}
+
+fn fetch_investment_transaction(conn: &Connection, id: &str) -> Result<InvestmentTransaction> {
+    conn.query_row(
+        &format!(
+            "SELECT {} FROM investment_transactions WHERE id = ?1",
+            INVESTMENT_TRANSACTION_COLUMNS
+        ),
+        [id],
+        investment_transaction_from_row,
+    )
+    .map_err(|_| AppError::NotFound("Investment transaction not found".to_string()))
+}
+
+/// Setting choosing how a sale's cost basis is taken from lots: "fifo" or "average"
+pub(crate) const COST_BASIS_METHOD_SETTING: &str = "cost_basis_method";
+
+/// How sales take their cost basis from lots: first in, first out unless the setting asks
+/// for average cost
+pub(crate) fn cost_basis_method(conn: &Connection) -> Result<&'static str> {
+    Ok(
+        match read_setting(conn, COST_BASIS_METHOD_SETTING)?.as_deref() {
+            Some("average") => "average",
+            _ => "fifo",
+        },
+    )
+}
+
+/// Put any units of a holding that no lot accounts for, because they were imported or
+/// entered by hand, into a lot of their own acquired when the holding was, so every unit
+/// held is in a lot
+fn cover_holding_with_lots(
+    conn: &Connection,
+    account_id: &str,
+    security_id: &str,
+    date: &str,
+    now: &str,
+) -> Result<()> {
+    let (held, held_cost) = holding_position(conn, account_id, security_id)?;
+    let (in_lots, lot_cost): (f64, i64) = conn.query_row(
+        "SELECT COALESCE(SUM(remaining_quantity), 0), COALESCE(SUM(remaining_cost), 0)
+         FROM investment_lots WHERE account_id = ?1 AND security_id = ?2",
+        [account_id, security_id],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+    if held - in_lots <= QUANTITY_EPSILON {
+        return Ok(());
+    }
+
+    let acquired_on: Option<String> = conn
+        .query_row(
+            "SELECT acquisition_date FROM holdings WHERE account_id = ?1 AND security_id = ?2",
+            [account_id, security_id],
+            |row| row.get(0),
+        )
+        .optional()?
+        .flatten();
+    let cost = (held_cost - lot_cost).max(0);
+    conn.execute(
+        "INSERT INTO investment_lots (id, account_id, security_id, acquired_on, quantity,
+                cost, remaining_quantity, remaining_cost, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?5, ?6, ?7)",
+        rusqlite::params![
+            Uuid::new_v4().to_string(),
+            account_id,
+            security_id,
+            acquired_on.as_deref().unwrap_or(date),
+            held - in_lots,
+            cost,
+            now,
+        ],
+    )?;
+
+    Ok(())
+}
+
+/// Take `quantity` units out of a holding's lots for the sale `sale_id` on `date`, oldest
+/// lot first or evenly across all of them at the average cost, recording what came out of
+/// each. Only lots acquired by the sale date can be sold from. Returns the cost basis of
+/// the units sold.
+fn sell_from_lots(
+    conn: &Connection,
+    sale_id: &str,
+    account_id: &str,
+    security_id: &str,
+    date: &str,
+    quantity: f64,
+    method: &str,
+) -> Result<i64> {
+    let mut stmt = conn.prepare(
+        "SELECT id, remaining_quantity, remaining_cost FROM investment_lots
+         WHERE account_id = ?1 AND security_id = ?2 AND remaining_quantity > ?3
+           AND acquired_on <= ?4
+         ORDER BY acquired_on, created_at",
+    )?;
+    let lots: Vec<(String, f64, i64)> = stmt
+        .query_map(
+            rusqlite::params![account_id, security_id, QUANTITY_EPSILON, date],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )?
+        .filter_map(|r| r.ok())
+        .collect();
+    let in_lots: f64 = lots.iter().map(|(_, remaining, _)| remaining).sum();
+    if quantity > in_lots + QUANTITY_EPSILON {
+        return Err(AppError::Validation(format!(
+            "Only {} units were held on {}",
+            in_lots, date
+        )));
+    }
+
+    let mut left = quantity;
+    let mut total_cost = 0;
+    for (lot_id, remaining, remaining_cost) in &lots {
+        let take = match method {
+            "average" => remaining * (quantity / in_lots).min(1.0),
+            _ => left.min(*remaining),
+        };
+        if take <= QUANTITY_EPSILON {
+            continue;
+        }
+        // Emptying a lot takes all of its cost, so no cents are stranded by rounding
+        let cost = if remaining - take <= QUANTITY_EPSILON {
+            *remaining_cost
+        } else {
+            (*remaining_cost as f64 * take / remaining).round() as i64
+        };
+        conn.execute(
+            "UPDATE investment_lots SET remaining_quantity = remaining_quantity - ?1,
+                remaining_cost = remaining_cost - ?2
+             WHERE id = ?3",
+            rusqlite::params![take, cost, lot_id],
+        )?;
+        conn.execute(
+            "INSERT INTO investment_lot_sales (investment_transaction_id, lot_id, quantity, cost)
+             VALUES (?1, ?2, ?3, ?4)",
+            rusqlite::params![sale_id, lot_id, take, cost],
+        )?;
+        left -= take;
+        total_cost += cost;
+    }
+
+    Ok(total_cost)
+}
+
+/// Put back into their lots the units and cost a sale took out
+fn restore_lots(conn: &Connection, sale_id: &str) -> Result<()> {
+    conn.execute(
+        "UPDATE investment_lots SET
+            remaining_quantity = remaining_quantity + (
+                SELECT quantity FROM investment_lot_sales
+                WHERE investment_transaction_id = ?1 AND lot_id = investment_lots.id
+            ),
+            remaining_cost = remaining_cost + (
+                SELECT cost FROM investment_lot_sales
+                WHERE investment_transaction_id = ?1 AND lot_id = investment_lots.id
+            )
+         WHERE id IN (SELECT lot_id FROM investment_lot_sales WHERE investment_transaction_id = ?1)",
+        [sale_id],
+    )?;
+    conn.execute(
+        "DELETE FROM investment_lot_sales WHERE investment_transaction_id = ?1",
+        [sale_id],
+    )?;
+
+    Ok(())
+}
+
+/// Multiply the units of every lot of a holding acquired by a split on `date`, along with
+/// the units sold out of them, by `ratio`, leaving their cost. Lots created after
+/// `created_at` came after the split.
+fn scale_lots(
+    conn: &Connection,
+    account_id: &str,
+    security_id: &str,
+    date: &str,
+    created_at: &str,
+    ratio: f64,
+) -> Result<()> {
+    let lots = "SELECT id FROM investment_lots
+                WHERE account_id = ?2 AND security_id = ?3 AND acquired_on <= ?4
+                  AND created_at <= ?5";
+    conn.execute(
+        &format!(
+            "UPDATE investment_lot_sales SET quantity = quantity * ?1 WHERE lot_id IN ({})",
+            lots
+        ),
+        rusqlite::params![ratio, account_id, security_id, date, created_at],
+    )?;
+    conn.execute(
+        &format!(
+            "UPDATE investment_lots SET quantity = quantity * ?1,
+                remaining_quantity = remaining_quantity * ?1
+             WHERE id IN ({})",
+            lots
+        ),
+        rusqlite::params![ratio, account_id, security_id, date, created_at],
+    )?;
+
+    Ok(())
+}
+
+/// Investment transactions, newest first, optionally only those in one account or security
+#[tauri::command]
+pub fn list_investment_transactions(
+    account_id: Option<String>,
+    security_id: Option<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<InvestmentTransaction>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM investment_transactions
+         WHERE (?1 IS NULL OR account_id = ?1) AND (?2 IS NULL OR security_id = ?2)
+         ORDER BY date DESC, created_at DESC",
+        INVESTMENT_TRANSACTION_COLUMNS
+    ))?;
+    let transactions = stmt
+        .query_map([account_id, security_id], investment_transaction_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(transactions)
+}
+
+/// Record a buy, sell, dividend, split or fee in an investment account. Buys and sells
+/// change the holding and post the cash they cost or raised, fees included, to the
+/// account. A buy opens a lot; a sale takes its cost basis out of the lots by the cost
+/// basis method and records its realized gain. A split multiplies the units held by
+/// `quantity`, the new units per old unit, leaving the cost basis. Dividends and fees
+/// post `totalAmount` in or out of the account's cash.
+#[tauri::command]
+pub fn create_investment_transaction(
+    data: serde_json::Value,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<InvestmentTransaction> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let id = record_investment_transaction(conn, &data)?;
+
+    emit_changed(
+        &app,
+        &[ACCOUNTS_CHANGED, INVESTMENTS_CHANGED, TRANSACTIONS_CHANGED],
+    );
+
+    fetch_investment_transaction(conn, &id)
+}
+
+fn record_investment_transaction(conn: &Connection, data: &serde_json::Value) -> Result<String> {
+    let transaction_type = data["transactionType"].as_str().unwrap_or("");
+    if !INVESTMENT_TRANSACTION_TYPES.contains(&transaction_type) {
+        return Err(AppError::Validation(format!(
+            "Unknown investment transaction type: {}",
+            transaction_type
+        )));
+    }
+    let date = data["date"].as_str().unwrap_or("");
+    if NaiveDate::parse_from_str(date, "%Y-%m-%d").is_err() {
+        return Err(AppError::Validation(format!("Invalid date: {}", date)));
+    }
+    let account = fetch_account(conn, data["accountId"].as_str().unwrap_or(""))?;
+    if account.closed_on.is_some() {
+        return Err(AppError::Validation(format!("{} is closed", account.name)));
+    }
+    let security = data["securityId"]
+        .as_str()
+        .map(|id| fetch_security(conn, id))
+        .transpose()?;
+    let fees = data["fees"].as_i64().unwrap_or(0);
+    if fees < 0 {
+        return Err(AppError::Validation("Fees can't be negative".to_string()));
+    }
+
+    // Trades and splits are in a security; a dividend or fee can be for the whole account
+    let trades_units = matches!(transaction_type, "buy" | "sell" | "split");
+    let quantity = match (trades_units, data["quantity"].as_f64()) {
+        (false, _) => None,
+        (true, Some(quantity)) if quantity.is_finite() && quantity > 0.0 => Some(quantity),
+        (true, _) => {
+            return Err(AppError::Validation(
+                "Quantity must be more than zero".to_string(),
+            ))
+        }
+    };
+    let (held, _) = match (&security, trades_units) {
+        (Some(security), _) => holding_position(conn, &account.id, &security.id)?,
+        (None, true) => {
+            return Err(AppError::Validation(format!(
+                "A {} needs a security",
+                transaction_type
+            )))
+        }
+        (None, false) => (0.0, 0),
+    };
+    let symbol = security.as_ref().map(|s| s.symbol.as_str()).unwrap_or("");
+    let price = data["pricePerUnit"].as_i64();
+
+    // What the transaction is worth, the units it adds to the holding, and the cash it
+    // posts. A sale's cost basis comes out of the lots below.
+    let (total_amount, quantity_change, cash) = match transaction_type {
+        "buy" | "sell" => {
+            let quantity = quantity.unwrap_or(0.0);
+            let Some(price) = price.filter(|p| *p >= 0) else {
+                return Err(AppError::Validation(
+                    "Price per unit is required".to_string(),
+                ));
+            };
+            let total = (quantity * price as f64).round() as i64;
+            if transaction_type == "buy" {
+                (total, quantity, -(total + fees))
+            } else {
+                if quantity > held + QUANTITY_EPSILON {
+                    return Err(AppError::Validation(format!(
+                        "{} only holds {} of {}",
+                        account.name, held, symbol
+                    )));
+                }
+                (total, -quantity, total - fees)
+            }
+        }
+        "split" => {
+            if held <= QUANTITY_EPSILON {
+                return Err(AppError::Validation(format!(
+                    "{} doesn't hold {}",
+                    account.name, symbol
+                )));
+            }
+            (0, held * (quantity.unwrap_or(1.0) - 1.0), 0)
+        }
+        _ => {
+            let Some(amount) = data["totalAmount"].as_i64().filter(|a| *a > 0) else {
+                return Err(AppError::Validation(
+                    "Amount must be more than zero".to_string(),
+                ));
+            };
+            if transaction_type == "dividend" {
+                (amount, 0.0, amount - fees)
+            } else {
+                (amount, 0.0, -(amount + fees))
+            }
+        }
+    };
+
+    let id = Uuid::new_v4().to_string();
+    let now = chrono::Utc::now().to_rfc3339();
+    let tx = conn.unchecked_transaction()?;
+    let (cost_basis_change, realized_gain) = match &security {
+        Some(security) => {
+            cover_holding_with_lots(&tx, &account.id, &security.id, date, &now)?;
+            match transaction_type {
+                "buy" => {
+                    let cost = total_amount + fees;
+                    tx.execute(
+                        "INSERT INTO investment_lots (id, account_id, security_id,
+                                investment_transaction_id, acquired_on, quantity, cost,
+                                remaining_quantity, remaining_cost, created_at)
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?6, ?7, ?8)",
+                        rusqlite::params![
+                            Uuid::new_v4().to_string(),
+                            account.id,
+                            security.id,
+                            id,
+                            date,
+                            quantity_change,
+                            cost,
+                            now,
+                        ],
+                    )?;
+                    (cost, None)
+                }
+                "sell" => {
+                    let method = cost_basis_method(&tx)?;
+                    let cost = sell_from_lots(
+                        &tx,
+                        &id,
+                        &account.id,
+                        &security.id,
+                        date,
+                        -quantity_change,
+                        method,
+                    )?;
+                    (-cost, Some(total_amount - fees - cost))
+                }
+                "split" => {
+                    scale_lots(
+                        &tx,
+                        &account.id,
+                        &security.id,
+                        date,
+                        &now,
+                        quantity.unwrap_or(1.0),
+                    )?;
+                    (0, None)
+                }
+                _ => (0, None),
+            }
+        }
+        None => (0, None),
+    };
+    if let Some(security) = &security {
+        adjust_holding(
+            &tx,
+            &account.id,
+            &security.id,
+            quantity_change,
+            cost_basis_change,
+            date,
+        )?;
+    }
+    let transaction_id = if cash != 0 {
+        let (payee, cash_type) = match transaction_type {
+            "buy" => (
+                format!("Buy {} {}", quantity.unwrap_or(0.0), symbol),
+                INVESTMENT_TRADE_TYPE,
+            ),
+            "sell" => (
+                format!("Sell {} {}", quantity.unwrap_or(0.0), symbol),
+                INVESTMENT_TRADE_TYPE,
+            ),
+            "dividend" => (format!("Dividend {}", symbol), DIVIDEND_TYPE),
+            _ => (format!("Fee {}", symbol), INVESTMENT_FEE_TYPE),
+        };
+        Some(insert_transaction(
+            &tx,
+            &serde_json::json!({
+                "accountId": account.id,
+                "date": date,
+                "amount": cash,
+                "payee": payee.trim_end(),
+                "notes": data["notes"].as_str(),
+                "transactionType": cash_type,
+            }),
+        )?)
+    } else {
+        None
+    };
+
+    tx.execute(
+        "INSERT INTO investment_transactions (id, account_id, security_id, transaction_type,
+                date, quantity, price_per_unit, total_amount, fees, quantity_change,
+                cost_basis_change, realized_gain, transaction_id, notes, created_at,
+                updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?15)",
+        rusqlite::params![
+            id,
+            account.id,
+            security.as_ref().map(|s| &s.id),
+            transaction_type,
+            date,
+            quantity,
+            price.filter(|_| matches!(transaction_type, "buy" | "sell")),
+            total_amount,
+            fees,
+            quantity_change,
+            cost_basis_change,
+            realized_gain,
+            transaction_id,
+            data["notes"].as_str(),
+            now,
+        ],
+    )?;
+    tx.commit()?;
+
+    Ok(id)
+}
+
+/// Delete an investment transaction, undoing its change to the holding and deleting the
+/// cash transaction it posted. A buy whose units have since been sold can't be deleted
+/// until the sale is.
+#[tauri::command]
+pub fn delete_investment_transaction(
+    id: String,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<()> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    remove_investment_transaction(conn, &id)?;
+
+    emit_changed(
+        &app,
+        &[ACCOUNTS_CHANGED, INVESTMENTS_CHANGED, TRANSACTIONS_CHANGED],
+    );
+
+    Ok(())
+}
+
+fn remove_investment_transaction(conn: &Connection, id: &str) -> Result<()> {
+    let transaction = fetch_investment_transaction(conn, id)?;
+
+    let tx = conn.unchecked_transaction()?;
+    if let Some(security_id) = &transaction.security_id {
+        let (held, _) = holding_position(&tx, &transaction.account_id, security_id)?;
+        // A buy can't go while sales still hold units taken from its lot
+        let lot_sold: bool = tx.query_row(
+            "SELECT EXISTS(SELECT 1 FROM investment_lot_sales s
+                 JOIN investment_lots l ON l.id = s.lot_id
+                 WHERE l.investment_transaction_id = ?1)",
+            [id],
+            |row| row.get(0),
+        )?;
+        if lot_sold || held - transaction.quantity_change < -QUANTITY_EPSILON {
+            return Err(AppError::Validation(format!(
+                "Delete the later sales of {} first",
+                transaction.symbol.as_deref().unwrap_or("the security")
+            )));
+        }
+        match transaction.transaction_type.as_str() {
+            "buy" => {
+                tx.execute(
+                    "DELETE FROM investment_lots WHERE investment_transaction_id = ?1",
+                    [id],
+                )?;
+            }
+            "sell" => restore_lots(&tx, id)?,
+            "split" => scale_lots(
+                &tx,
+                &transaction.account_id,
+                security_id,
+                &transaction.date,
+                &transaction.created_at,
+                1.0 / transaction.quantity.unwrap_or(1.0),
+            )?,
+            _ => {}
+        }
+        adjust_holding(
+            &tx,
+            &transaction.account_id,
+            security_id,
+            -transaction.quantity_change,
+            -transaction.cost_basis_change,
+            &transaction.date,
+        )?;
+    }
+    // The cash side may already have been deleted on its own
+    if let Some(transaction_id) = &transaction.transaction_id {
+        let live: Option<bool> = tx
+            .query_row(
+                "SELECT deleted_at IS NULL FROM transactions WHERE id = ?1",
+                [transaction_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if live == Some(true) {
+            soft_delete_transaction(&tx, transaction_id, &chrono::Utc::now().to_rfc3339())?;
+        }
+    }
+    tx.execute("DELETE FROM investment_transactions WHERE id = ?1", [id])?;
+    tx.commit()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn brokerage() -> Connection {
+        let conn = Database::in_memory();
+        conn.execute_batch(
+            "INSERT INTO accounts (id, name, account_type, current_balance, opening_balance)
+             VALUES ('brokerage', 'Brokerage', 'investment', 1000000, 1000000);
+             INSERT INTO securities (id, symbol, name) VALUES ('abc', 'ABC', 'ABC Corp');",
+        )
+        .unwrap();
+        conn
+    }
+
+    fn record(conn: &Connection, kind: &str, date: &str, quantity: f64, price: i64) -> String {
+        record_investment_transaction(
+            conn,
+            &serde_json::json!({
+                "accountId": "brokerage",
+                "securityId": "abc",
+                "transactionType": kind,
+                "date": date,
+                "quantity": quantity,
+                "pricePerUnit": price,
+            }),
+        )
+        .unwrap()
+    }
+
+    fn realized_gain(conn: &Connection, id: &str) -> Option<i64> {
+        fetch_investment_transaction(conn, id)
+            .unwrap()
+            .realized_gain
+    }
+
+    #[test]
+    fn test_fifo_and_average_realized_gain() {
+        let fifo = brokerage();
+        let average = brokerage();
+        average
+            .execute(
+                "INSERT INTO settings (key, value) VALUES (?1, 'average')",
+                [COST_BASIS_METHOD_SETTING],
+            )
+            .unwrap();
+
+        let mut sales = Vec::new();
+        for conn in [&fifo, &average] {
+            record(conn, "buy", "2024-01-02", 10.0, 10000);
+            record(conn, "buy", "2024-02-01", 10.0, 20000);
+            sales.push(record(conn, "sell", "2024-03-01", 10.0, 30000));
+        }
+
+        // FIFO sells the $100 lot; average cost is $150 a unit
+        assert_eq!(realized_gain(&fifo, &sales[0]), Some(200000));
+        assert_eq!(realized_gain(&average, &sales[1]), Some(150000));
+        assert_eq!(
+            holding_position(&fifo, "brokerage", "abc").unwrap(),
+            (10.0, 200000)
+        );
+        assert_eq!(
+            holding_position(&average, "brokerage", "abc").unwrap(),
+            (10.0, 150000)
+        );
+    }
+
+    #[test]
+    fn test_sales_only_use_lots_held_by_the_sale_date() {
+        let conn = brokerage();
+        conn.execute(
+            "INSERT INTO settings (key, value) VALUES (?1, 'average')",
+            [COST_BASIS_METHOD_SETTING],
+        )
+        .unwrap();
+        record(&conn, "buy", "2024-01-02", 10.0, 10000);
+        record(&conn, "buy", "2024-03-01", 10.0, 20000);
+
+        // Only the January lot was held in February, so it's all the average comes from
+        let sale = record(&conn, "sell", "2024-02-01", 5.0, 30000);
+        assert_eq!(realized_gain(&conn, &sale), Some(100000));
+
+        let oversold = record_investment_transaction(
+            &conn,
+            &serde_json::json!({
+                "accountId": "brokerage",
+                "securityId": "abc",
+                "transactionType": "sell",
+                "date": "2024-02-15",
+                "quantity": 10.0,
+                "pricePerUnit": 30000,
+            }),
+        );
+        assert!(matches!(oversold, Err(AppError::Validation(_))));
+        assert_eq!(holding_position(&conn, "brokerage", "abc").unwrap().0, 15.0);
+    }
+
+    #[test]
+    fn test_split_then_sale_and_deleting_them() {
+        let conn = brokerage();
+        record(&conn, "buy", "2024-01-02", 10.0, 10000);
+        let split = record(&conn, "split", "2024-02-01", 2.0, 0);
+        let sale = record(&conn, "sell", "2024-03-01", 5.0, 6000);
+
+        // The split doubled the lot's units without touching its $1,000 cost
+        assert_eq!(realized_gain(&conn, &sale), Some(30000 - 25000));
+        let lot = |conn: &Connection| -> (f64, f64, i64) {
+            conn.query_row(
+                "SELECT quantity, remaining_quantity, remaining_cost FROM investment_lots",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .unwrap()
+        };
+        assert_eq!(lot(&conn), (20.0, 15.0, 75000));
+
+        remove_investment_transaction(&conn, &sale).unwrap();
+        assert_eq!(lot(&conn), (20.0, 20.0, 100000));
+        let sold: i64 = conn
+            .query_row("SELECT COUNT(*) FROM investment_lot_sales", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(sold, 0);
+
+        remove_investment_transaction(&conn, &split).unwrap();
+        assert_eq!(lot(&conn), (10.0, 10.0, 100000));
+        assert_eq!(
+            holding_position(&conn, "brokerage", "abc").unwrap(),
+            (10.0, 100000)
+        );
+    }
+}
@@ -1,8 +1,14 @@
+use crate::commands::accounts::fetch_account;
+use crate::commands::investment_transactions::cost_basis_method;
 use crate::db::Database;
-use crate::error::Result;
-use std::sync::Mutex;
-use tauri::State;
+use crate::error::{AppError, Result};
+use crate::events::{emit_changed, INVESTMENTS_CHANGED};
+use crate::models::Security;
+use rusqlite::{Connection, OptionalExtension};
 use serde::Serialize;
+use std::sync::Mutex;
+use tauri::{AppHandle, State};
+use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -10,6 +16,7 @@ pub struct Holding {
     pub id: String,
     pub account_id: String,
     pub account_name: String,
+    pub security_id: String,
     pub symbol: String,
     pub name: Option<String>,
     pub security_type: Option<String>,
@@ -19,6 +26,7 @@ pub struct Holding {
     pub market_value: i64,
     pub gain_loss: Option<i64>,
     pub gain_loss_percent: Option<f64>,
+    pub acquisition_date: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -26,8 +34,15 @@ pub struct Holding {
 pub struct InvestmentSummary {
     pub total_value: i64,
     pub total_cost_basis: i64,
+    /// Gains locked in by sales, net of fees
+    pub realized_gain_loss: i64,
+    /// Market value less cost basis of what's still held
+    pub unrealized_gain_loss: i64,
+    /// Realized plus unrealized gains
     pub total_gain_loss: i64,
     pub total_gain_loss_percent: f64,
+    /// How sales take their cost basis from lots: "fifo" or "average"
+    pub cost_basis_method: String,
     pub holdings_by_type: Vec<HoldingsByType>,
 }
 
@@ -39,101 +54,298 @@ pub struct HoldingsByType {
     pub percentage: f64,
 }
 
+const HOLDING_QUERY: &str =
+    "SELECT h.id, h.account_id, a.name as account_name, h.security_id, s.symbol, s.name,
+            s.security_type, h.quantity, s.current_price, h.cost_basis, h.acquisition_date
+     FROM holdings h
+     JOIN accounts a ON h.account_id = a.id
+     JOIN securities s ON h.security_id = s.id";
+
+fn holding_from_row(row: &rusqlite::Row) -> rusqlite::Result<Holding> {
+    let quantity: f64 = row.get(7)?;
+    let current_price: Option<i64> = row.get(8)?;
+    let cost_basis: Option<i64> = row.get(9)?;
+
+    let market_value = current_price
+        .map(|p| (quantity * p as f64) as i64)
+        .unwrap_or(0);
+    let gain_loss = cost_basis.map(|cb| market_value - cb);
+    let gain_loss_percent = cost_basis.and_then(|cb| {
+        if cb != 0 {
+            Some((market_value - cb) as f64 / cb as f64 * 100.0)
+        } else {
+            None
+        }
+    });
+
+    Ok(Holding {
+        id: row.get(0)?,
+        account_id: row.get(1)?,
+        account_name: row.get(2)?,
+        security_id: row.get(3)?,
+        symbol: row.get(4)?,
+        name: row.get(5)?,
+        security_type: row.get(6)?,
+        quantity,
+        current_price,
+        cost_basis,
+        market_value,
+        gain_loss,
+        gain_loss_percent,
+        acquisition_date: row.get(10)?,
+    })
+}
+
+fn fetch_holding(conn: &Connection, id: &str) -> Result<Holding> {
+    conn.query_row(
+        &format!("{} WHERE h.id = ?1", HOLDING_QUERY),
+        [id],
+        holding_from_row,
+    )
+    .map_err(|_| AppError::NotFound("Holding not found".to_string()))
+}
+
+/// Units below this are rounding left over from selling a whole position
+pub(crate) const QUANTITY_EPSILON: f64 = 1e-9;
+
+/// Units and cost basis an account holds of a security, or zero if it holds none
+pub(crate) fn holding_position(
+    conn: &Connection,
+    account_id: &str,
+    security_id: &str,
+) -> Result<(f64, i64)> {
+    Ok(conn
+        .query_row(
+            "SELECT quantity, COALESCE(cost_basis, 0) FROM holdings
+             WHERE account_id = ?1 AND security_id = ?2",
+            [account_id, security_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()?
+        .unwrap_or((0.0, 0)))
+}
+
+/// Change the units and cost basis an account holds of a security, opening the holding
+/// on `date` if there's none and removing it once nothing's left. A change that would
+/// leave the holding negative fails.
+pub(crate) fn adjust_holding(
+    conn: &Connection,
+    account_id: &str,
+    security_id: &str,
+    quantity_change: f64,
+    cost_basis_change: i64,
+    date: &str,
+) -> Result<()> {
+    let existing: Option<String> = conn
+        .query_row(
+            "SELECT id FROM holdings WHERE account_id = ?1 AND security_id = ?2",
+            [account_id, security_id],
+            |row| row.get(0),
+        )
+        .optional()?;
+    let (held, cost_basis) = holding_position(conn, account_id, security_id)?;
+    let quantity = held + quantity_change;
+    if quantity < -QUANTITY_EPSILON {
+        return Err(AppError::Validation(format!(
+            "Only {} units are held",
+            held
+        )));
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    match existing {
+        Some(id) if quantity <= QUANTITY_EPSILON => {
+            conn.execute("DELETE FROM holdings WHERE id = ?1", [&id])?;
+        }
+        Some(id) => {
+            conn.execute(
+                "UPDATE holdings SET quantity = ?1, cost_basis = ?2, updated_at = ?3 WHERE id = ?4",
+                rusqlite::params![quantity, cost_basis + cost_basis_change, now, id],
+            )?;
+        }
+        None if quantity > QUANTITY_EPSILON => {
+            conn.execute(
+                "INSERT INTO holdings (id, account_id, security_id, quantity, cost_basis,
+                        acquisition_date, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?7)",
+                rusqlite::params![
+                    Uuid::new_v4().to_string(),
+                    account_id,
+                    security_id,
+                    quantity,
+                    cost_basis_change,
+                    date,
+                    now,
+                ],
+            )?;
+        }
+        None => {}
+    }
+
+    Ok(())
+}
+
 #[tauri::command]
-pub fn list_holdings(account_id: Option<String>, db: State<'_, Mutex<Database>>) -> Result<Vec<Holding>> {
+pub fn list_holdings(
+    account_id: Option<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<Holding>> {
     let database = db.lock().unwrap();
     let conn = database.get_connection()?;
 
-    let query = if account_id.is_some() {
-        "SELECT h.id, h.account_id, a.name as account_name, s.symbol, s.name, s.security_type,
-                h.quantity, s.current_price, h.cost_basis
-         FROM holdings h
-         JOIN accounts a ON h.account_id = a.id
-         JOIN securities s ON h.security_id = s.id
-         WHERE h.account_id = ?1
-         ORDER BY s.symbol"
-    } else {
-        "SELECT h.id, h.account_id, a.name as account_name, s.symbol, s.name, s.security_type,
-                h.quantity, s.current_price, h.cost_basis
-         FROM holdings h
-         JOIN accounts a ON h.account_id = a.id
-         JOIN securities s ON h.security_id = s.id
-         ORDER BY a.name, s.symbol"
-    };
-
-    let mut stmt = conn.prepare(query)?;
-
-    let holdings: Vec<Holding> = if let Some(ref acc_id) = account_id {
-        stmt.query_map([acc_id], |row| {
-            let quantity: f64 = row.get(6)?;
-            let current_price: Option<i64> = row.get(7)?;
-            let cost_basis: Option<i64> = row.get(8)?;
-
-            let market_value = current_price.map(|p| (quantity * p as f64) as i64).unwrap_or(0);
-            let gain_loss = cost_basis.map(|cb| market_value - cb);
-            let gain_loss_percent = cost_basis.and_then(|cb| {
-                if cb != 0 {
-                    Some((market_value - cb) as f64 / cb as f64 * 100.0)
-                } else {
-                    None
-                }
-            });
-
-            Ok(Holding {
-                id: row.get(0)?,
-                account_id: row.get(1)?,
-                account_name: row.get(2)?,
-                symbol: row.get(3)?,
-                name: row.get(4)?,
-                security_type: row.get(5)?,
-                quantity,
-                current_price,
-                cost_basis,
-                market_value,
-                gain_loss,
-                gain_loss_percent,
-            })
-        })?
-        .filter_map(|r| r.ok())
-        .collect()
-    } else {
-        stmt.query_map([], |row| {
-            let quantity: f64 = row.get(6)?;
-            let current_price: Option<i64> = row.get(7)?;
-            let cost_basis: Option<i64> = row.get(8)?;
-
-            let market_value = current_price.map(|p| (quantity * p as f64) as i64).unwrap_or(0);
-            let gain_loss = cost_basis.map(|cb| market_value - cb);
-            let gain_loss_percent = cost_basis.and_then(|cb| {
-                if cb != 0 {
-                    Some((market_value - cb) as f64 / cb as f64 * 100.0)
-                } else {
-                    None
-                }
-            });
-
-            Ok(Holding {
-                id: row.get(0)?,
-                account_id: row.get(1)?,
-                account_name: row.get(2)?,
-                symbol: row.get(3)?,
-                name: row.get(4)?,
-                security_type: row.get(5)?,
-                quantity,
-                current_price,
-                cost_basis,
-                market_value,
-                gain_loss,
-                gain_loss_percent,
-            })
-        })?
+    let mut stmt = conn.prepare(&format!(
+        "{} WHERE ?1 IS NULL OR h.account_id = ?1 ORDER BY a.name, s.symbol",
+        HOLDING_QUERY
+    ))?;
+    let holdings = stmt
+        .query_map([account_id], holding_from_row)?
         .filter_map(|r| r.ok())
-        .collect()
-    };
+        .collect();
 
     Ok(holdings)
 }
 
+/// Check the quantity, cost basis and acquisition date of holding input. The quantity is
+/// required unless `partial`, for updates that leave it as is.
+fn validate_holding(data: &serde_json::Value, partial: bool) -> Result<()> {
+    match data["quantity"].as_f64() {
+        Some(quantity) if !quantity.is_finite() || quantity < 0.0 => {
+            return Err(AppError::Validation(
+                "Quantity can't be negative".to_string(),
+            ))
+        }
+        None if !partial => return Err(AppError::Validation("Quantity is required".to_string())),
+        _ => {}
+    }
+    if data["costBasis"].as_i64().is_some_and(|cost| cost < 0) {
+        return Err(AppError::Validation(
+            "Cost basis can't be negative".to_string(),
+        ));
+    }
+    if let Some(date) = data["acquisitionDate"].as_str() {
+        if chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").is_err() {
+            return Err(AppError::Validation(format!(
+                "Invalid acquisition date: {}",
+                date
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Add a position in a security to an investment account. An account holds each security
+/// once; add to an existing position by updating it.
+#[tauri::command]
+pub fn create_holding(
+    data: serde_json::Value,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Holding> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    validate_holding(&data, false)?;
+    let account = fetch_account(conn, data["accountId"].as_str().unwrap_or(""))?;
+    if account.closed_on.is_some() {
+        return Err(AppError::Validation(format!("{} is closed", account.name)));
+    }
+    let security = fetch_security(conn, data["securityId"].as_str().unwrap_or(""))?;
+    let existing: Option<String> = conn
+        .query_row(
+            "SELECT id FROM holdings WHERE account_id = ?1 AND security_id = ?2",
+            [&account.id, &security.id],
+            |row| row.get(0),
+        )
+        .optional()?;
+    if existing.is_some() {
+        return Err(AppError::Validation(format!(
+            "{} already holds {}",
+            account.name, security.symbol
+        )));
+    }
+
+    let id = Uuid::new_v4().to_string();
+    let now = chrono::Utc::now().to_rfc3339();
+
+    conn.execute(
+        "INSERT INTO holdings (id, account_id, security_id, quantity, cost_basis,
+                acquisition_date, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?7)",
+        rusqlite::params![
+            id,
+            account.id,
+            security.id,
+            data["quantity"].as_f64(),
+            data["costBasis"].as_i64(),
+            data["acquisitionDate"].as_str(),
+            now,
+        ],
+    )?;
+
+    emit_changed(&app, &[INVESTMENTS_CHANGED]);
+
+    fetch_holding(conn, &id)
+}
+
+/// Update a holding's quantity, cost basis or acquisition date. Fields left out are kept;
+/// the cost basis and acquisition date can be cleared with null.
+#[tauri::command]
+pub fn update_holding(
+    id: String,
+    data: serde_json::Value,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Holding> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    fetch_holding(conn, &id)?;
+    validate_holding(&data, true)?;
+
+    conn.execute(
+        "UPDATE holdings SET
+            quantity = COALESCE(?1, quantity),
+            cost_basis = CASE WHEN ?2 THEN ?3 ELSE cost_basis END,
+            acquisition_date = CASE WHEN ?4 THEN ?5 ELSE acquisition_date END,
+            updated_at = ?6
+         WHERE id = ?7",
+        rusqlite::params![
+            data["quantity"].as_f64(),
+            data.get("costBasis").is_some(),
+            data["costBasis"].as_i64(),
+            data.get("acquisitionDate").is_some(),
+            data["acquisitionDate"].as_str(),
+            chrono::Utc::now().to_rfc3339(),
+            id,
+        ],
+    )?;
+
+    emit_changed(&app, &[INVESTMENTS_CHANGED]);
+
+    fetch_holding(conn, &id)
+}
+
+#[tauri::command]
+pub fn delete_holding(id: String, app: AppHandle, db: State<'_, Mutex<Database>>) -> Result<()> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let holding = fetch_holding(conn, &id)?;
+    let tx = conn.unchecked_transaction()?;
+    tx.execute("DELETE FROM holdings WHERE id = ?1", [&id])?;
+    // The units are gone, so nothing is left in the holding's lots to sell
+    tx.execute(
+        "UPDATE investment_lots SET remaining_quantity = 0, remaining_cost = 0
+         WHERE account_id = ?1 AND security_id = ?2",
+        [&holding.account_id, &holding.security_id],
+    )?;
+    tx.commit()?;
+
+    emit_changed(&app, &[INVESTMENTS_CHANGED]);
+
+    Ok(())
+}
+
 #[tauri::command]
 pub fn get_investment_summary(db: State<'_, Mutex<Database>>) -> Result<InvestmentSummary> {
     let database = db.lock().unwrap();
@@ -168,7 +380,13 @@ pub fn get_investment_summary(db: State<'_, Mutex<Database>>) -> Result<Investme
         *type_values.entry(type_name).or_insert(0) += market_value;
     });
 
-    let total_gain_loss = total_value - total_cost_basis;
+    let realized_gain_loss: i64 = conn.query_row(
+        "SELECT COALESCE(SUM(realized_gain), 0) FROM investment_transactions",
+        [],
+        |row| row.get(0),
+    )?;
+    let unrealized_gain_loss = total_value - total_cost_basis;
+    let total_gain_loss = realized_gain_loss + unrealized_gain_loss;
     let total_gain_loss_percent = if total_cost_basis != 0 {
         total_gain_loss as f64 / total_cost_basis as f64 * 100.0
     } else {
@@ -194,14 +412,22 @@ pub fn get_investment_summary(db: State<'_, Mutex<Database>>) -> Result<Investme
     Ok(InvestmentSummary {
         total_value,
         total_cost_basis,
+        realized_gain_loss,
+        unrealized_gain_loss,
         total_gain_loss,
         total_gain_loss_percent,
+        cost_basis_method: cost_basis_method(conn)?.to_string(),
         holdings_by_type,
     })
 }
 
 #[tauri::command]
-pub fn update_security_price(symbol: String, price: i64, db: State<'_, Mutex<Database>>) -> Result<()> {
+pub fn update_security_price(
+    symbol: String,
+    price: i64,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<()> {
     let database = db.lock().unwrap();
     let conn = database.get_connection()?;
 
@@ -212,5 +438,199 @@ pub fn update_security_price(symbol: String, price: i64, db: State<'_, Mutex<Dat
         rusqlite::params![price, now, now, symbol],
     )?;
 
+    emit_changed(&app, &[INVESTMENTS_CHANGED]);
+
+    Ok(())
+}
+
+/// Kinds of security, for asset allocation
+const SECURITY_TYPES: &[&str] = &["stock", "etf", "mutual_fund", "bond", "crypto", "other"];
+
+const SECURITY_COLUMNS: &str =
+    "id, symbol, name, security_type, current_price, price_updated_at, created_at, updated_at";
+
+fn security_from_row(row: &rusqlite::Row) -> rusqlite::Result<Security> {
+    Ok(Security {
+        id: row.get(0)?,
+        symbol: row.get(1)?,
+        name: row.get(2)?,
+        security_type: row.get(3)?,
+        current_price: row.get(4)?,
+        price_updated_at: row.get(5)?,
+        created_at: row.get(6)?,
+        updated_at: row.get(7)?,
+    })
+}
+
+pub(crate) fn fetch_security(conn: &Connection, id: &str) -> Result<Security> {
+    conn.query_row(
+        &format!("SELECT {} FROM securities WHERE id = ?1", SECURITY_COLUMNS),
+        [id],
+        security_from_row,
+    )
+    .map_err(|_| AppError::NotFound("Security not found".to_string()))
+}
+
+/// Check the symbol, type and price of security input, returning the symbol upper cased.
+/// The symbol is required unless `partial`, for updates that leave it as is. A symbol
+/// can't be taken by another security.
+fn validate_security(
+    conn: &Connection,
+    data: &serde_json::Value,
+    id: Option<&str>,
+    partial: bool,
+) -> Result<Option<String>> {
+    let symbol = data["symbol"].as_str().map(|s| s.trim().to_uppercase());
+    match symbol.as_deref() {
+        Some("") => return Err(AppError::Validation("Symbol is required".to_string())),
+        None if !partial => return Err(AppError::Validation("Symbol is required".to_string())),
+        Some(symbol) => {
+            let taken: bool = conn.query_row(
+                "SELECT EXISTS(SELECT 1 FROM securities WHERE symbol = ?1 AND id != ?2)",
+                rusqlite::params![symbol, id.unwrap_or("")],
+                |row| row.get(0),
+            )?;
+            if taken {
+                return Err(AppError::Validation(format!(
+                    "A security with the symbol {} already exists",
+                    symbol
+                )));
+            }
+        }
+        None => {}
+    }
+    if let Some(security_type) = data["securityType"].as_str() {
+        if !SECURITY_TYPES.contains(&security_type) {
+            return Err(AppError::Validation(format!(
+                "Unknown security type: {}",
+                security_type
+            )));
+        }
+    }
+    if data["currentPrice"].as_i64().is_some_and(|price| price < 0) {
+        return Err(AppError::Validation("Price can't be negative".to_string()));
+    }
+    Ok(symbol)
+}
+
+#[tauri::command]
+pub fn list_securities(db: State<'_, Mutex<Database>>) -> Result<Vec<Security>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM securities ORDER BY symbol",
+        SECURITY_COLUMNS
+    ))?;
+    let securities = stmt
+        .query_map([], security_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(securities)
+}
+
+#[tauri::command]
+pub fn create_security(
+    data: serde_json::Value,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Security> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let symbol = validate_security(conn, &data, None, false)?;
+
+    let id = Uuid::new_v4().to_string();
+    let now = chrono::Utc::now().to_rfc3339();
+    let current_price = data["currentPrice"].as_i64();
+
+    conn.execute(
+        "INSERT INTO securities (id, symbol, name, security_type, current_price,
+                price_updated_at, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?7)",
+        rusqlite::params![
+            id,
+            symbol,
+            data["name"].as_str().map(str::trim),
+            data["securityType"].as_str(),
+            current_price,
+            current_price.map(|_| &now),
+            now,
+        ],
+    )?;
+
+    emit_changed(&app, &[INVESTMENTS_CHANGED]);
+
+    fetch_security(conn, &id)
+}
+
+/// Update a security's symbol, name, type or price. Fields left out are kept; the name,
+/// type and price can be cleared with null. A new price is stamped with the time.
+#[tauri::command]
+pub fn update_security(
+    id: String,
+    data: serde_json::Value,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Security> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    fetch_security(conn, &id)?;
+    let symbol = validate_security(conn, &data, Some(&id), true)?;
+
+    conn.execute(
+        "UPDATE securities SET
+            symbol = COALESCE(?1, symbol),
+            name = CASE WHEN ?2 THEN ?3 ELSE name END,
+            security_type = CASE WHEN ?4 THEN ?5 ELSE security_type END,
+            price_updated_at = CASE WHEN ?6 THEN ?8 ELSE price_updated_at END,
+            current_price = CASE WHEN ?6 THEN ?7 ELSE current_price END,
+            updated_at = ?8
+         WHERE id = ?9",
+        rusqlite::params![
+            symbol,
+            data.get("name").is_some(),
+            data["name"].as_str().map(str::trim),
+            data.get("securityType").is_some(),
+            data["securityType"].as_str(),
+            data.get("currentPrice").is_some(),
+            data["currentPrice"].as_i64(),
+            chrono::Utc::now().to_rfc3339(),
+            id,
+        ],
+    )?;
+
+    emit_changed(&app, &[INVESTMENTS_CHANGED]);
+
+    fetch_security(conn, &id)
+}
+
+/// Delete a security. One that's still held, or has investment transactions, can't be
+/// deleted.
+#[tauri::command]
+pub fn delete_security(id: String, app: AppHandle, db: State<'_, Mutex<Database>>) -> Result<()> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let security = fetch_security(conn, &id)?;
+    let (holdings, transactions): (i64, i64) = conn.query_row(
+        "SELECT (SELECT COUNT(*) FROM holdings WHERE security_id = ?1),
+                (SELECT COUNT(*) FROM investment_transactions WHERE security_id = ?1)",
+        [&id],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+    if holdings > 0 || transactions > 0 {
+        return Err(AppError::Validation(format!(
+            "{} is still held or has investment transactions",
+            security.symbol
+        )));
+    }
+
+    conn.execute("DELETE FROM securities WHERE id = ?1", [&id])?;
+
+    emit_changed(&app, &[INVESTMENTS_CHANGED]);
+
     Ok(())
 }
@@ -8,6 +8,38 @@ pub mod budgets;
 pub mod goals;
 pub mod recurring;
 pub mod investments;
+pub mod investment_transactions;
+pub mod reports;
+pub mod payees;
+pub mod note_templates;
+pub mod quick_entry;
+pub mod transaction_templates;
+pub mod credit_scores;
+pub mod policies;
+pub mod gift_cards;
+pub mod rewards;
+pub mod holds;
+pub mod export;
+pub mod forecast;
+pub mod planned_purchases;
+pub mod usage_stats;
+pub mod classifier;
+pub mod month_close;
+pub mod budget_alerts;
+pub mod budget_groups;
+pub mod debt_payoff;
+pub mod goal_milestones;
+pub mod round_ups;
+pub mod goal_allocation;
+pub mod account_statements;
+pub mod institutions;
+pub mod account_groups;
+pub mod credit_cards;
+pub mod interest;
+pub mod fx;
+pub mod account_closing;
+pub mod net_worth;
+pub mod attachments;
 
 pub use settings::*;
 pub use accounts::*;
@@ -19,3 +51,35 @@ pub use budgets::*;
 pub use goals::*;
 pub use recurring::*;
 pub use investments::*;
+pub use investment_transactions::*;
+pub use reports::*;
+pub use payees::*;
+pub use note_templates::*;
+pub use quick_entry::*;
+pub use transaction_templates::*;
+pub use credit_scores::*;
+pub use policies::*;
+pub use gift_cards::*;
+pub use rewards::*;
+pub use holds::*;
+pub use export::*;
+pub use forecast::*;
+pub use planned_purchases::*;
+pub use usage_stats::*;
+pub use classifier::*;
+pub use month_close::*;
+pub use budget_alerts::*;
+pub use budget_groups::*;
+pub use debt_payoff::*;
+pub use goal_milestones::*;
+pub use round_ups::*;
+pub use goal_allocation::*;
+pub use account_statements::*;
+pub use institutions::*;
+pub use account_groups::*;
+pub use credit_cards::*;
+pub use interest::*;
+pub use fx::*;
+pub use account_closing::*;
+pub use net_worth::*;
+pub use attachments::*;
@@ -0,0 +1,374 @@
+use crate::commands::account_statements::ensure_statement_unlocked;
+use crate::commands::budgets::{
+    budget_from_row, budget_month_amount, budget_rollover, budget_spending, BUDGET_COLUMNS,
+};
+use crate::commands::forecast::{month_bounds, month_index};
+use crate::commands::fx::transaction_base_amount;
+use crate::commands::net_worth::snapshot_net_worth;
+use crate::commands::reports::{reportable_transaction_filter, spending_by_category};
+use crate::commands::transactions::{transaction_from_row, TRANSACTION_COLUMNS};
+use crate::db::Database;
+use crate::error::{AppError, Result};
+use crate::events::{emit_changed, BUDGETS_CHANGED, TRANSACTIONS_CHANGED};
+use crate::models::{Budget, SpendingByCategory, Transaction};
+use chrono::NaiveDate;
+use rusqlite::{Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::{AppHandle, State};
+use uuid::Uuid;
+
+/// Income and spending for a closed month, stored as it stood when the month was closed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonthlyReport {
+    pub income: i64,
+    pub expenses: i64,
+    pub net: i64,
+    pub spending_by_category: Vec<SpendingByCategory>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonthClose {
+    pub month: String,
+    pub net_worth_snapshot_id: String,
+    pub total_assets: i64,
+    pub total_liabilities: i64,
+    pub net_worth: i64,
+    /// Rollover budgets whose unspent amount was carried into the next month
+    pub rolled_over_budgets: i64,
+    pub report: MonthlyReport,
+    /// Uncategorized transactions from the month, dropped from the review queue
+    pub archived_review_count: i64,
+    /// Reconciled transactions that can no longer be edited or deleted
+    pub locked_count: i64,
+    pub closed_at: String,
+}
+
+const MONTH_CLOSE_COLUMNS: &str = "m.month, m.net_worth_snapshot_id, n.total_assets,
+    n.total_liabilities, n.net_worth, m.rolled_over_budgets, m.report,
+    m.archived_review_count, m.locked_count, m.closed_at";
+
+fn month_close_from_row(row: &rusqlite::Row) -> rusqlite::Result<MonthClose> {
+    let report: String = row.get(6)?;
+    Ok(MonthClose {
+        month: row.get(0)?,
+        net_worth_snapshot_id: row.get(1)?,
+        total_assets: row.get(2)?,
+        total_liabilities: row.get(3)?,
+        net_worth: row.get(4)?,
+        rolled_over_budgets: row.get(5)?,
+        report: serde_json::from_str(&report).map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(6, rusqlite::types::Type::Text, Box::new(e))
+        })?,
+        archived_review_count: row.get(7)?,
+        locked_count: row.get(8)?,
+        closed_at: row.get(9)?,
+    })
+}
+
+fn fetch_month_close(conn: &Connection, month: &str) -> Result<Option<MonthClose>> {
+    conn.query_row(
+        &format!(
+            "SELECT {} FROM month_closes m
+             JOIN net_worth_snapshots n ON n.id = m.net_worth_snapshot_id
+             WHERE m.month = ?1",
+            MONTH_CLOSE_COLUMNS
+        ),
+        [month],
+        month_close_from_row,
+    )
+    .optional()
+    .map_err(|e| e.into())
+}
+
+/// First day of a `YYYY-MM` month
+pub(crate) fn parse_month(month: &str) -> Result<NaiveDate> {
+    let invalid = || AppError::Validation(format!("Invalid month: {}. Use YYYY-MM", month));
+    if month.len() != 7 {
+        return Err(invalid());
+    }
+    NaiveDate::parse_from_str(&format!("{}-01", month), "%Y-%m-%d").map_err(|_| invalid())
+}
+
+/// SQL predicate on the `t` alias for transactions waiting in the review queue: live,
+/// settled spending or income with no category. Split parents are categorized through
+/// their splits and transfers don't need a category.
+fn review_queue_filter() -> &'static str {
+    "t.deleted_at IS NULL
+     AND t.category_id IS NULL
+     AND t.is_split = 0
+     AND t.is_hold = 0
+     AND t.transfer_id IS NULL"
+}
+
+/// Reconciled transactions in a closed month are locked; reopen the month to change them.
+/// Transactions covered by a locked closing statement are locked too.
+pub(crate) fn ensure_transaction_unlocked(conn: &Connection, id: &str) -> Result<()> {
+    let closed_month: Option<String> = conn
+        .query_row(
+            "SELECT m.month FROM transactions t
+             JOIN month_closes m ON m.month = substr(t.date, 1, 7)
+             WHERE t.id = ?1 AND t.status = 'reconciled'",
+            [id],
+            |row| row.get(0),
+        )
+        .optional()?;
+
+    match closed_month {
+        Some(month) => Err(AppError::Validation(format!(
+            "Transaction is reconciled in {}, which is closed. Reopen the month to change it",
+            month
+        ))),
+        None => {
+            let found: Option<(String, String)> = conn
+                .query_row(
+                    "SELECT account_id, date FROM transactions WHERE id = ?1",
+                    [id],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .optional()?;
+            match found {
+                Some((account_id, date)) => ensure_statement_unlocked(conn, &account_id, &date),
+                None => Ok(()),
+            }
+        }
+    }
+}
+
+/// Record a budget period, keeping an existing period's rollover unless one is given
+fn upsert_budget_period(
+    conn: &Connection,
+    budget: &Budget,
+    (start, end): (&str, &str),
+    rollover_amount: Option<i64>,
+    now: &str,
+) -> Result<()> {
+    let budgeted_amount = budget_month_amount(conn, budget, start)?;
+    conn.execute(
+        "INSERT INTO budget_periods (id, budget_id, period_start, period_end, budgeted_amount,
+                rollover_amount, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, COALESCE(?6, 0), ?7)
+         ON CONFLICT(budget_id, period_start) DO UPDATE SET
+            budgeted_amount = excluded.budgeted_amount,
+            rollover_amount = COALESCE(?6, rollover_amount)",
+        rusqlite::params![
+            Uuid::new_v4().to_string(),
+            budget.id,
+            start,
+            end,
+            budgeted_amount,
+            rollover_amount,
+            now,
+        ],
+    )?;
+    Ok(())
+}
+
+/// Carry what's left of each rollover spending budget (its amount plus anything rolled
+/// into this month, less spending) into the next month's budget period. Overspending
+/// isn't carried as debt. Returns how many budgets rolled over.
+fn finalize_rollovers(conn: &Connection, month_start: NaiveDate, now: &str) -> Result<i64> {
+    let index = month_index(month_start);
+    let (_, month_end) = month_bounds(index);
+    let (next_start, next_end) = month_bounds(index + 1);
+    let (month_start, month_end) = (
+        month_start.format("%Y-%m-%d").to_string(),
+        month_end.format("%Y-%m-%d").to_string(),
+    );
+    let (next_start, next_end) = (
+        next_start.format("%Y-%m-%d").to_string(),
+        next_end.format("%Y-%m-%d").to_string(),
+    );
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM budgets
+         WHERE rollover = 1
+           AND is_watch_only = 0
+           AND category_id IN (
+               SELECT id FROM categories WHERE deleted_at IS NULL AND category_type != 'income'
+           )",
+        BUDGET_COLUMNS
+    ))?;
+    let budgets: Vec<Budget> = stmt
+        .query_map([], budget_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    for budget in &budgets {
+        let carried_in = budget_rollover(conn, &budget.id, &month_start)?;
+        let spent = budget_spending(conn, budget, &month_start, &next_start);
+        let amount = budget_month_amount(conn, budget, &month_start)?;
+        let unspent = (amount + carried_in - spent).max(0);
+
+        upsert_budget_period(conn, budget, (&month_start, &month_end), None, now)?;
+        upsert_budget_period(conn, budget, (&next_start, &next_end), Some(unspent), now)?;
+    }
+
+    Ok(budgets.len() as i64)
+}
+
+fn monthly_report(conn: &Connection, start: &str, end: &str) -> Result<MonthlyReport> {
+    let (income, expenses): (i64, i64) = conn.query_row(
+        &format!(
+            "SELECT COALESCE(SUM(CASE WHEN t.amount > 0 THEN {amount} ELSE 0 END), 0),
+                    COALESCE(SUM(CASE WHEN t.amount < 0 THEN -{amount} ELSE 0 END), 0)
+             FROM transactions t
+             WHERE {} AND t.date >= ?1 AND t.date <= ?2",
+            reportable_transaction_filter(),
+            amount = transaction_base_amount()
+        ),
+        [start, end],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+
+    Ok(MonthlyReport {
+        income,
+        expenses,
+        net: income - expenses,
+        spending_by_category: spending_by_category(conn, start, end, false)?,
+    })
+}
+
+/// Close a finished month in one step: snapshot net worth as of its last day, carry
+/// rollover budgets into the next month, store the month's report, drop its uncategorized
+/// transactions from the review queue and lock its reconciled transactions.
+#[tauri::command]
+pub fn close_month(
+    month: String,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<MonthClose> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let month_start = parse_month(&month)?;
+    let (_, month_end) = month_bounds(month_index(month_start));
+    if month_end >= chrono::Local::now().date_naive() {
+        return Err(AppError::Validation(format!(
+            "{} hasn't ended yet and can't be closed",
+            month
+        )));
+    }
+    if fetch_month_close(conn, &month)?.is_some() {
+        return Err(AppError::Validation(format!("{} is already closed", month)));
+    }
+
+    let start = month_start.format("%Y-%m-%d").to_string();
+    let end = month_end.format("%Y-%m-%d").to_string();
+    let now = chrono::Utc::now().to_rfc3339();
+
+    let tx = conn.unchecked_transaction()?;
+
+    let snapshot_id = snapshot_net_worth(&tx, &end, &now)?;
+    let rolled_over_budgets = finalize_rollovers(&tx, month_start, &now)?;
+    let report = monthly_report(&tx, &start, &end)?;
+
+    let count_in_month = |filter: &str| -> Result<i64> {
+        tx.query_row(
+            &format!(
+                "SELECT COUNT(*) FROM transactions t WHERE {} AND t.date >= ?1 AND t.date <= ?2",
+                filter
+            ),
+            [&start, &end],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.into())
+    };
+    let archived_review_count = count_in_month(review_queue_filter())?;
+    let locked_count = count_in_month("t.deleted_at IS NULL AND t.status = 'reconciled'")?;
+
+    tx.execute(
+        "INSERT INTO month_closes (month, net_worth_snapshot_id, rolled_over_budgets, report,
+                archived_review_count, locked_count, closed_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        rusqlite::params![
+            month,
+            snapshot_id,
+            rolled_over_budgets,
+            serde_json::to_string(&report)?,
+            archived_review_count,
+            locked_count,
+            now,
+        ],
+    )?;
+
+    tx.commit()?;
+
+    emit_changed(&app, &[TRANSACTIONS_CHANGED, BUDGETS_CHANGED]);
+
+    fetch_month_close(conn, &month)?
+        .ok_or_else(|| AppError::NotFound("Month close not found".to_string()))
+}
+
+/// Undo a month close: unlock its transactions, return them to the review queue and drop
+/// its net worth snapshot. Rollovers stay in place and are recalculated on the next close.
+#[tauri::command]
+pub fn reopen_month(month: String, app: AppHandle, db: State<'_, Mutex<Database>>) -> Result<()> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let close = fetch_month_close(conn, &month)?
+        .ok_or_else(|| AppError::NotFound(format!("{} isn't closed", month)))?;
+
+    let tx = conn.unchecked_transaction()?;
+    tx.execute("DELETE FROM month_closes WHERE month = ?1", [&month])?;
+    tx.execute(
+        "DELETE FROM account_snapshots WHERE net_worth_snapshot_id = ?1",
+        [&close.net_worth_snapshot_id],
+    )?;
+    tx.execute(
+        "DELETE FROM net_worth_snapshots WHERE id = ?1",
+        [&close.net_worth_snapshot_id],
+    )?;
+    tx.commit()?;
+
+    emit_changed(&app, &[TRANSACTIONS_CHANGED, BUDGETS_CHANGED]);
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn list_month_closes(db: State<'_, Mutex<Database>>) -> Result<Vec<MonthClose>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM month_closes m
+         JOIN net_worth_snapshots n ON n.id = m.net_worth_snapshot_id
+         ORDER BY m.month DESC",
+        MONTH_CLOSE_COLUMNS
+    ))?;
+
+    let closes = stmt
+        .query_map([], month_close_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(closes)
+}
+
+/// Uncategorized transactions still waiting for review, oldest first. Closing a month
+/// archives its part of the queue.
+#[tauri::command]
+pub fn list_review_queue(db: State<'_, Mutex<Database>>) -> Result<Vec<Transaction>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM transactions t
+         WHERE {}
+           AND substr(t.date, 1, 7) NOT IN (SELECT month FROM month_closes)
+         ORDER BY t.date, t.created_at",
+        TRANSACTION_COLUMNS,
+        review_queue_filter()
+    ))?;
+
+    let transactions = stmt
+        .query_map([], transaction_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(transactions)
+}
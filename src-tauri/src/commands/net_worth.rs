@@ -0,0 +1,192 @@
+use crate::commands::accounts::asset_liability_totals;
+use crate::commands::forecast::{month_bounds, month_index};
+use crate::commands::fx::base_amount_sql;
+use crate::db::Database;
+use crate::error::{AppError, Result};
+use crate::events::{emit_changed, ACCOUNTS_CHANGED};
+use crate::models::NetWorthSnapshot;
+use chrono::NaiveDate;
+use rusqlite::Connection;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, State};
+use uuid::Uuid;
+
+const NET_WORTH_SNAPSHOT_COLUMNS: &str =
+    "id, snapshot_date, total_assets, total_liabilities, net_worth, created_at";
+
+fn net_worth_snapshot_from_row(row: &rusqlite::Row) -> rusqlite::Result<NetWorthSnapshot> {
+    Ok(NetWorthSnapshot {
+        id: row.get(0)?,
+        snapshot_date: row.get(1)?,
+        total_assets: row.get(2)?,
+        total_liabilities: row.get(3)?,
+        net_worth: row.get(4)?,
+        created_at: row.get(5)?,
+    })
+}
+
+fn fetch_net_worth_snapshot(conn: &Connection, id: &str) -> Result<NetWorthSnapshot> {
+    conn.query_row(
+        &format!(
+            "SELECT {} FROM net_worth_snapshots WHERE id = ?1",
+            NET_WORTH_SNAPSHOT_COLUMNS
+        ),
+        [id],
+        net_worth_snapshot_from_row,
+    )
+    .map_err(|_| AppError::NotFound("Net worth snapshot not found".to_string()))
+}
+
+/// Record every account's balance on `date`, worked back from its current balance by
+/// taking out transactions dated later. Account balances are kept in the account's
+/// currency; the totals are in the base currency at that date's rate. Accounts closed
+/// since still count, and accounts not yet opened don't. Returns the snapshot id.
+pub(crate) fn snapshot_net_worth(conn: &Connection, date: &str, now: &str) -> Result<String> {
+    let balance = "(a.current_balance - COALESCE((
+                        SELECT SUM(t.amount) FROM transactions t
+                        WHERE t.account_id = a.id AND t.deleted_at IS NULL AND t.date > ?1
+                    ), 0))";
+    let mut stmt = conn.prepare(&format!(
+        "SELECT a.id, a.account_type, {}, {}
+         FROM accounts a
+         WHERE a.deleted_at IS NULL AND (a.is_active = 1 OR a.closed_on > ?1)
+           AND (a.opening_date IS NULL OR a.opening_date <= ?1)",
+        balance,
+        base_amount_sql(balance, "a.currency", "?1")
+    ))?;
+    let balances: Vec<(String, String, i64, i64)> = stmt
+        .query_map([date], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let (total_assets, total_liabilities) =
+        asset_liability_totals(balances.iter().map(|(_, t, _, base)| (t.as_str(), *base)));
+
+    let snapshot_id = Uuid::new_v4().to_string();
+    conn.execute(
+        "INSERT INTO net_worth_snapshots (id, snapshot_date, total_assets, total_liabilities,
+                net_worth, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        rusqlite::params![
+            snapshot_id,
+            date,
+            total_assets,
+            total_liabilities,
+            total_assets - total_liabilities,
+            now,
+        ],
+    )?;
+    for (account_id, _, balance, _) in &balances {
+        conn.execute(
+            "INSERT INTO account_snapshots (id, net_worth_snapshot_id, account_id, balance, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            rusqlite::params![Uuid::new_v4().to_string(), snapshot_id, account_id, balance, now],
+        )?;
+    }
+
+    Ok(snapshot_id)
+}
+
+/// Snapshot net worth at the end of every finished month that doesn't have a snapshot yet,
+/// from the month of the earliest transaction, so the net worth chart has history from the
+/// start. Returns the number of snapshots taken.
+pub(crate) fn snapshot_finished_months(conn: &Connection, today: NaiveDate) -> Result<i64> {
+    let earliest: Option<String> = conn.query_row(
+        "SELECT MIN(date) FROM transactions WHERE deleted_at IS NULL",
+        [],
+        |row| row.get(0),
+    )?;
+    let Some(earliest) = earliest.and_then(|d| NaiveDate::parse_from_str(&d, "%Y-%m-%d").ok())
+    else {
+        return Ok(0);
+    };
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let tx = conn.unchecked_transaction()?;
+    let mut taken = 0;
+    for index in month_index(earliest)..month_index(today) {
+        let month_end = month_bounds(index).1.format("%Y-%m-%d").to_string();
+        let exists: bool = tx.query_row(
+            "SELECT EXISTS(SELECT 1 FROM net_worth_snapshots WHERE snapshot_date = ?1)",
+            [&month_end],
+            |row| row.get(0),
+        )?;
+        if !exists {
+            snapshot_net_worth(&tx, &month_end, &now)?;
+            taken += 1;
+        }
+    }
+    tx.commit()?;
+
+    Ok(taken)
+}
+
+/// Take any month-end snapshots that are due on a background thread once the database is
+/// unlocked, as for interest accrual. A failed run is left for the next unlock.
+pub(crate) fn spawn_net_worth_snapshots(app: AppHandle) {
+    std::thread::spawn(move || {
+        let db = app.state::<Mutex<Database>>();
+        let database = db.lock().unwrap();
+        if let Ok(conn) = database.get_connection() {
+            let _ = snapshot_finished_months(conn, chrono::Local::now().date_naive());
+        }
+    });
+}
+
+/// Snapshot net worth as of a date, today by default
+#[tauri::command]
+pub fn create_net_worth_snapshot(
+    date: Option<String>,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<NetWorthSnapshot> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let date = date.unwrap_or_else(|| chrono::Local::now().format("%Y-%m-%d").to_string());
+    if NaiveDate::parse_from_str(&date, "%Y-%m-%d").is_err() {
+        return Err(AppError::Validation(format!(
+            "Invalid snapshot date: {}",
+            date
+        )));
+    }
+
+    let id = snapshot_net_worth(conn, &date, &chrono::Utc::now().to_rfc3339())?;
+
+    emit_changed(&app, &[ACCOUNTS_CHANGED]);
+
+    fetch_net_worth_snapshot(conn, &id)
+}
+
+/// Net worth snapshots between two dates, oldest first. Where a date was snapshotted more
+/// than once, e.g. by month close and the monthly job, only the latest counts.
+#[tauri::command]
+pub fn list_net_worth_snapshots(
+    start_date: Option<String>,
+    end_date: Option<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<NetWorthSnapshot>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM net_worth_snapshots n
+         WHERE (?1 IS NULL OR snapshot_date >= ?1) AND (?2 IS NULL OR snapshot_date <= ?2)
+           AND NOT EXISTS (
+               SELECT 1 FROM net_worth_snapshots later
+               WHERE later.snapshot_date = n.snapshot_date
+                 AND (later.created_at > n.created_at
+                      OR (later.created_at = n.created_at AND later.id > n.id))
+           )
+         ORDER BY snapshot_date",
+        NET_WORTH_SNAPSHOT_COLUMNS
+    ))?;
+    let snapshots = stmt
+        .query_map([start_date, end_date], net_worth_snapshot_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(snapshots)
+}
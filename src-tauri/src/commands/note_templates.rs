@@ -0,0 +1,277 @@
+use crate::db::Database;
+use crate::error::{AppError, Result};
+use crate::events::{emit_changed, NOTE_TEMPLATES_CHANGED};
+use crate::models::NoteTemplate;
+use rusqlite::Connection;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, State};
+use uuid::Uuid;
+
+fn fetch_note_template(conn: &Connection, id: &str) -> Result<NoteTemplate> {
+    conn.query_row(
+        "SELECT id, name, template, created_at, updated_at FROM note_templates WHERE id = ?1",
+        [id],
+        |row| {
+            Ok(NoteTemplate {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                template: row.get(2)?,
+                created_at: row.get(3)?,
+                updated_at: row.get(4)?,
+            })
+        },
+    )
+    .map_err(|_| AppError::NotFound("Note template not found".to_string()))
+}
+
+/// Format cents as a plain decimal amount, e.g. 1250 -> "12.50"
+fn format_amount(cents: i64) -> String {
+    let sign = if cents < 0 { "-" } else { "" };
+    format!("{}{}.{:02}", sign, cents.abs() / 100, cents.abs() % 100)
+}
+
+/// Replace each `{name}` placeholder with its value, failing on any variable left unfilled
+fn expand_template(template: &str, variables: &HashMap<String, String>) -> Result<String> {
+    let mut output = String::with_capacity(template.len());
+    let mut missing = Vec::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+
+        match after.find('}') {
+            Some(end) => {
+                let name = after[..end].trim();
+                match variables.get(name) {
+                    Some(value) => output.push_str(value),
+                    None => missing.push(name.to_string()),
+                }
+                rest = &after[end + 1..];
+            }
+            None => {
+                output.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+    output.push_str(rest);
+
+    if !missing.is_empty() {
+        return Err(AppError::Validation(format!(
+            "Missing template variables: {}",
+            missing.join(", ")
+        )));
+    }
+
+    Ok(output)
+}
+
+/// Expand a note template for a transaction being created. Built-in variables describe the
+/// transaction itself (`amount`, `half`, `payee`, `date`); caller-supplied variables fill the rest
+/// and take precedence.
+pub(crate) fn render_note_template(
+    conn: &Connection,
+    template_id: &str,
+    variables: Option<&serde_json::Map<String, serde_json::Value>>,
+    amount: i64,
+    payee: Option<&str>,
+    date: &str,
+) -> Result<String> {
+    let template = fetch_note_template(conn, template_id)?;
+
+    let mut values = HashMap::new();
+    values.insert("amount".to_string(), format_amount(amount.abs()));
+    values.insert("half".to_string(), format_amount(amount.abs() / 2));
+    values.insert("payee".to_string(), payee.unwrap_or("").to_string());
+    values.insert("date".to_string(), date.to_string());
+
+    for (name, value) in variables.into_iter().flatten() {
+        let value = match value {
+            serde_json::Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+        values.insert(name.clone(), value);
+    }
+
+    expand_template(&template.template, &values)
+}
+
+#[tauri::command]
+pub fn list_note_templates(db: State<'_, Mutex<Database>>) -> Result<Vec<NoteTemplate>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let mut stmt = conn.prepare(
+        "SELECT id, name, template, created_at, updated_at FROM note_templates ORDER BY name",
+    )?;
+
+    let templates = stmt
+        .query_map([], |row| {
+            Ok(NoteTemplate {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                template: row.get(2)?,
+                created_at: row.get(3)?,
+                updated_at: row.get(4)?,
+            })
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(templates)
+}
+
+#[tauri::command]
+pub fn create_note_template(
+    data: serde_json::Value,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<NoteTemplate> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let name = data["name"].as_str().unwrap_or("").trim();
+    let template = data["template"].as_str().unwrap_or("");
+    if name.is_empty() || template.is_empty() {
+        return Err(AppError::Validation(
+            "Template name and text are required".to_string(),
+        ));
+    }
+
+    let id = Uuid::new_v4().to_string();
+    let now = chrono::Utc::now().to_rfc3339();
+
+    conn.execute(
+        "INSERT INTO note_templates (id, name, template, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        rusqlite::params![id, name, template, now, now],
+    )?;
+
+    emit_changed(&app, &[NOTE_TEMPLATES_CHANGED]);
+
+    fetch_note_template(conn, &id)
+}
+
+#[tauri::command]
+pub fn update_note_template(
+    id: String,
+    data: serde_json::Value,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<NoteTemplate> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let now = chrono::Utc::now().to_rfc3339();
+
+    conn.execute(
+        "UPDATE note_templates SET
+            name = COALESCE(?1, name),
+            template = COALESCE(?2, template),
+            updated_at = ?3
+         WHERE id = ?4",
+        rusqlite::params![data["name"].as_str(), data["template"].as_str(), now, id],
+    )?;
+
+    emit_changed(&app, &[NOTE_TEMPLATES_CHANGED]);
+
+    fetch_note_template(conn, &id)
+}
+
+#[tauri::command]
+pub fn delete_note_template(
+    id: String,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<()> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    conn.execute("DELETE FROM note_templates WHERE id = ?1", [&id])?;
+
+    emit_changed(&app, &[NOTE_TEMPLATES_CHANGED]);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_expand_template() {
+        let values = vars(&[("payee", "Bistro"), ("half", "21.00"), ("who", "Sam")]);
+        assert_eq!(
+            expand_template("Split {half} at {payee} with { who }", &values).unwrap(),
+            "Split 21.00 at Bistro with Sam"
+        );
+        assert_eq!(
+            expand_template("No variables", &values).unwrap(),
+            "No variables"
+        );
+        // An unclosed brace is kept as written
+        assert_eq!(
+            expand_template("{payee} {unclosed", &values).unwrap(),
+            "Bistro {unclosed"
+        );
+    }
+
+    #[test]
+    fn test_expand_template_unknown_placeholders() {
+        let values = vars(&[("payee", "Bistro")]);
+        match expand_template("{payee} for {who} on {trip}", &values) {
+            Err(AppError::Validation(message)) => {
+                assert_eq!(message, "Missing template variables: who, trip")
+            }
+            other => panic!("expected a validation error, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn test_render_note_template() {
+        let conn = Database::in_memory();
+        conn.execute(
+            "INSERT INTO note_templates (id, name, template)
+             VALUES ('t1', 'Split', '{payee} on {date}: {amount}, half {half} for {who}')",
+            [],
+        )
+        .unwrap();
+
+        let mut variables = serde_json::Map::new();
+        variables.insert("who".to_string(), serde_json::json!("Sam"));
+        let note = render_note_template(
+            &conn,
+            "t1",
+            Some(&variables),
+            -4201,
+            Some("Bistro"),
+            "2024-05-01",
+        )
+        .unwrap();
+        assert_eq!(note, "Bistro on 2024-05-01: 42.01, half 21.00 for Sam");
+
+        // Caller-supplied variables win over the built-in ones
+        variables.insert("payee".to_string(), serde_json::json!("Dinner"));
+        let note = render_note_template(
+            &conn,
+            "t1",
+            Some(&variables),
+            -4201,
+            Some("Bistro"),
+            "2024-05-01",
+        )
+        .unwrap();
+        assert!(note.starts_with("Dinner on"));
+
+        assert!(render_note_template(&conn, "t1", None, -4201, None, "2024-05-01").is_err());
+    }
+}
@@ -0,0 +1,507 @@
+use crate::db::Database;
+use crate::error::{AppError, Result};
+use crate::events::{emit_changed, PAYEES_CHANGED, TRANSACTIONS_CHANGED};
+use crate::models::Payee;
+use rusqlite::{Connection, OptionalExtension};
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::{AppHandle, State};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PayeeSuggestion {
+    pub payee_id: String,
+    pub name: String,
+    pub default_category_id: Option<String>,
+    pub last_amount: Option<i64>,
+    pub last_category_id: Option<String>,
+    pub last_date: Option<String>,
+    pub frequency: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PayeeStats {
+    pub payee: Payee,
+    pub transaction_count: i64,
+    pub total_amount: i64,
+    pub average_amount: i64,
+    pub first_date: Option<String>,
+    pub last_date: Option<String>,
+    pub most_used_category_id: Option<String>,
+}
+
+fn fetch_payee(conn: &Connection, id: &str) -> Result<Payee> {
+    let mut payee = conn
+        .query_row(
+            "SELECT id, name, default_category_id, created_at, updated_at
+             FROM payees WHERE id = ?1",
+            [id],
+            |row| {
+                Ok(Payee {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    default_category_id: row.get(2)?,
+                    aliases: Vec::new(),
+                    created_at: row.get(3)?,
+                    updated_at: row.get(4)?,
+                })
+            },
+        )
+        .map_err(|_| AppError::NotFound("Payee not found".to_string()))?;
+
+    payee.aliases = fetch_aliases(conn, id)?;
+
+    Ok(payee)
+}
+
+fn fetch_aliases(conn: &Connection, payee_id: &str) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare(
+        "SELECT alias FROM payee_aliases WHERE payee_id = ?1 ORDER BY alias",
+    )?;
+
+    let aliases = stmt
+        .query_map([payee_id], |row| row.get(0))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(aliases)
+}
+
+/// Canonical payee resolved from the raw text on a transaction
+pub(crate) struct ResolvedPayee {
+    pub name: String,
+    pub default_category_id: Option<String>,
+}
+
+/// Map a raw payee string to its canonical payee via aliases or name, creating the payee
+/// if it has never been seen before
+pub(crate) fn resolve_payee(conn: &Connection, raw: &str) -> Result<Option<ResolvedPayee>> {
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return Ok(None);
+    }
+
+    let existing = conn
+        .query_row(
+            "SELECT p.name, p.default_category_id
+             FROM payees p
+             LEFT JOIN payee_aliases a ON a.payee_id = p.id AND a.alias = ?1
+             WHERE p.name = ?1 OR a.alias IS NOT NULL
+             LIMIT 1",
+            [raw],
+            |row| {
+                Ok(ResolvedPayee {
+                    name: row.get(0)?,
+                    default_category_id: row.get(1)?,
+                })
+            },
+        )
+        .optional()?;
+
+    if let Some(resolved) = existing {
+        return Ok(Some(resolved));
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    conn.execute(
+        "INSERT INTO payees (id, name, created_at, updated_at) VALUES (?1, ?2, ?3, ?3)",
+        rusqlite::params![Uuid::new_v4().to_string(), raw, now],
+    )?;
+
+    Ok(Some(ResolvedPayee {
+        name: raw.to_string(),
+        default_category_id: None,
+    }))
+}
+
+/// Point every transaction using `from_name` at `to_name` instead
+fn rename_transaction_payees(conn: &Connection, from_name: &str, to_name: &str, now: &str) -> Result<usize> {
+    let updated = conn.execute(
+        "UPDATE transactions SET payee = ?1, updated_at = ?2
+         WHERE payee = ?3 COLLATE NOCASE AND deleted_at IS NULL",
+        rusqlite::params![to_name, now, from_name],
+    )?;
+
+    Ok(updated)
+}
+
+/// Record `alias` as another name for a payee. An alias can only belong to one payee, so
+/// one that's already another payee's is refused rather than taken from it. An alias left
+/// behind by a payee that no longer exists is taken over.
+fn add_alias(conn: &Connection, payee_id: &str, alias: &str, now: &str) -> Result<()> {
+    let owner: Option<(String, Option<String>)> = conn
+        .query_row(
+            "SELECT a.payee_id, p.name FROM payee_aliases a
+             LEFT JOIN payees p ON p.id = a.payee_id
+             WHERE a.alias = ?1",
+            [alias],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()?;
+
+    match owner {
+        Some((owner_id, _)) if owner_id == payee_id => {}
+        Some((_, Some(owner_name))) => {
+            return Err(AppError::Validation(format!(
+                "'{}' is already an alias of {}",
+                alias, owner_name
+            )))
+        }
+        Some((_, None)) => {
+            conn.execute(
+                "UPDATE payee_aliases SET payee_id = ?1, created_at = ?2 WHERE alias = ?3",
+                [payee_id, now, alias],
+            )?;
+        }
+        None => {
+            conn.execute(
+                "INSERT INTO payee_aliases (id, payee_id, alias, created_at) VALUES (?1, ?2, ?3, ?4)",
+                rusqlite::params![Uuid::new_v4().to_string(), payee_id, alias, now],
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn list_payees(db: State<'_, Mutex<Database>>) -> Result<Vec<Payee>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let mut stmt = conn.prepare(
+        "SELECT id, name, default_category_id, created_at, updated_at
+         FROM payees
+         ORDER BY name",
+    )?;
+
+    let mut payees: Vec<Payee> = stmt
+        .query_map([], |row| {
+            Ok(Payee {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                default_category_id: row.get(2)?,
+                aliases: Vec::new(),
+                created_at: row.get(3)?,
+                updated_at: row.get(4)?,
+            })
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    for payee in &mut payees {
+        payee.aliases = fetch_aliases(conn, &payee.id)?;
+    }
+
+    Ok(payees)
+}
+
+#[tauri::command]
+pub fn create_payee(
+    data: serde_json::Value,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Payee> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let name = data["name"].as_str().unwrap_or("").trim();
+    if name.is_empty() {
+        return Err(AppError::Validation("Payee name is required".to_string()));
+    }
+
+    let id = Uuid::new_v4().to_string();
+    let now = chrono::Utc::now().to_rfc3339();
+
+    conn.execute(
+        "INSERT INTO payees (id, name, default_category_id, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        rusqlite::params![id, name, data["defaultCategoryId"].as_str(), now, now],
+    )
+    .map_err(|_| AppError::Validation(format!("Payee '{}' already exists", name)))?;
+
+    emit_changed(&app, &[PAYEES_CHANGED]);
+
+    fetch_payee(conn, &id)
+}
+
+#[tauri::command]
+pub fn update_payee(
+    id: String,
+    data: serde_json::Value,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Payee> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let current = fetch_payee(conn, &id)?;
+    let now = chrono::Utc::now().to_rfc3339();
+
+    let tx = conn.unchecked_transaction()?;
+
+    if let Some(new_name) = data["name"].as_str().map(str::trim).filter(|n| !n.is_empty()) {
+        if new_name != current.name {
+            tx.execute(
+                "UPDATE payees SET name = ?1, updated_at = ?2 WHERE id = ?3",
+                rusqlite::params![new_name, now, id],
+            )
+            .map_err(|_| AppError::Validation(format!("Payee '{}' already exists", new_name)))?;
+
+            rename_transaction_payees(&tx, &current.name, new_name, &now)?;
+
+            // Keep the old name so future imports still resolve to this payee
+            if !current.name.eq_ignore_ascii_case(new_name) {
+                add_alias(&tx, &id, &current.name, &now)?;
+            }
+        }
+    }
+
+    // An omitted default category is kept; null clears it
+    tx.execute(
+        "UPDATE payees SET
+            default_category_id = CASE WHEN ?1 THEN ?2 ELSE default_category_id END,
+            updated_at = ?3
+         WHERE id = ?4",
+        rusqlite::params![
+            data.get("defaultCategoryId").is_some(),
+            data["defaultCategoryId"].as_str(),
+            now,
+            id
+        ],
+    )?;
+
+    tx.commit()?;
+
+    emit_changed(&app, &[PAYEES_CHANGED]);
+
+    fetch_payee(conn, &id)
+}
+
+#[tauri::command]
+pub fn delete_payee(
+    id: String,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<()> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    conn.execute("DELETE FROM payee_aliases WHERE payee_id = ?1", [&id])?;
+    conn.execute("DELETE FROM payees WHERE id = ?1", [&id])?;
+
+    emit_changed(&app, &[PAYEES_CHANGED]);
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn add_payee_alias(
+    payee_id: String,
+    alias: String,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Payee> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let payee = fetch_payee(conn, &payee_id)?;
+    let alias = alias.trim();
+    if alias.is_empty() {
+        return Err(AppError::Validation("Alias is required".to_string()));
+    }
+
+    let other_payee: Option<String> = conn
+        .query_row(
+            "SELECT id FROM payees WHERE name = ?1 AND id != ?2",
+            [alias, &payee_id],
+            |row| row.get(0),
+        )
+        .optional()?;
+
+    if other_payee.is_some() {
+        return Err(AppError::Validation(format!(
+            "'{}' is already a separate payee; merge the payees instead",
+            alias
+        )));
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    add_alias(conn, &payee_id, alias, &now)?;
+    rename_transaction_payees(conn, alias, &payee.name, &now)?;
+
+    emit_changed(&app, &[PAYEES_CHANGED, TRANSACTIONS_CHANGED]);
+
+    fetch_payee(conn, &payee_id)
+}
+
+#[tauri::command]
+pub fn remove_payee_alias(
+    payee_id: String,
+    alias: String,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Payee> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    conn.execute(
+        "DELETE FROM payee_aliases WHERE payee_id = ?1 AND alias = ?2",
+        [&payee_id, &alias],
+    )?;
+
+    emit_changed(&app, &[PAYEES_CHANGED]);
+
+    fetch_payee(conn, &payee_id)
+}
+
+#[tauri::command]
+pub fn merge_payees(
+    source_ids: Vec<String>,
+    target_id: String,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Payee> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let target = fetch_payee(conn, &target_id)?;
+    let now = chrono::Utc::now().to_rfc3339();
+
+    let tx = conn.unchecked_transaction()?;
+
+    for source_id in source_ids.iter().filter(|id| **id != target_id) {
+        let source = fetch_payee(&tx, source_id)?;
+
+        rename_transaction_payees(&tx, &source.name, &target.name, &now)?;
+
+        tx.execute(
+            "UPDATE payee_aliases SET payee_id = ?1 WHERE payee_id = ?2",
+            [&target_id, source_id],
+        )?;
+        tx.execute("DELETE FROM payees WHERE id = ?1", [source_id])?;
+        add_alias(&tx, &target_id, &source.name, &now)?;
+
+        // Inherit a default category if the target doesn't have one yet
+        tx.execute(
+            "UPDATE payees SET default_category_id = COALESCE(default_category_id, ?1), updated_at = ?2
+             WHERE id = ?3",
+            rusqlite::params![source.default_category_id, now, target_id],
+        )?;
+    }
+
+    tx.commit()?;
+
+    emit_changed(&app, &[PAYEES_CHANGED, TRANSACTIONS_CHANGED]);
+
+    fetch_payee(conn, &target_id)
+}
+
+#[tauri::command]
+pub fn suggest_payees(
+    prefix: String,
+    limit: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<PayeeSuggestion>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let prefix = prefix.trim();
+    if prefix.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // Escape LIKE wildcards so the prefix is matched literally
+    let pattern = format!(
+        "{}%",
+        prefix.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+    );
+
+    let mut stmt = conn.prepare(
+        "WITH stats AS (
+             SELECT payee, COUNT(*) AS frequency, MAX(date) AS last_date
+             FROM transactions
+             WHERE deleted_at IS NULL AND payee IS NOT NULL
+             GROUP BY payee COLLATE NOCASE
+         )
+         SELECT p.id, p.name, p.default_category_id,
+                (SELECT t.amount FROM transactions t
+                 WHERE t.payee = p.name AND t.deleted_at IS NULL
+                 ORDER BY t.date DESC, t.created_at DESC LIMIT 1),
+                (SELECT t.category_id FROM transactions t
+                 WHERE t.payee = p.name AND t.deleted_at IS NULL
+                 ORDER BY t.date DESC, t.created_at DESC LIMIT 1),
+                s.last_date,
+                COALESCE(s.frequency, 0) AS frequency
+         FROM payees p
+         LEFT JOIN stats s ON s.payee = p.name
+         WHERE p.name LIKE ?1 ESCAPE '\\'
+            OR p.id IN (SELECT payee_id FROM payee_aliases WHERE alias LIKE ?1 ESCAPE '\\')
+         ORDER BY frequency DESC, s.last_date DESC, p.name
+         LIMIT ?2",
+    )?;
+
+    let suggestions = stmt
+        .query_map(rusqlite::params![pattern, limit.unwrap_or(10)], |row| {
+            Ok(PayeeSuggestion {
+                payee_id: row.get(0)?,
+                name: row.get(1)?,
+                default_category_id: row.get(2)?,
+                last_amount: row.get(3)?,
+                last_category_id: row.get(4)?,
+                last_date: row.get(5)?,
+                frequency: row.get(6)?,
+            })
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(suggestions)
+}
+
+#[tauri::command]
+pub fn get_payee_stats(payee_id: String, db: State<'_, Mutex<Database>>) -> Result<PayeeStats> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let payee = fetch_payee(conn, &payee_id)?;
+
+    let (transaction_count, total_amount, first_date, last_date): (i64, i64, Option<String>, Option<String>) =
+        conn.query_row(
+            "SELECT COUNT(*), COALESCE(SUM(amount), 0), MIN(date), MAX(date)
+             FROM transactions
+             WHERE payee = ?1 AND deleted_at IS NULL",
+            [&payee.name],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )?;
+
+    let most_used_category_id: Option<String> = conn
+        .query_row(
+            "SELECT category_id FROM transactions
+             WHERE payee = ?1 AND category_id IS NOT NULL AND deleted_at IS NULL
+             GROUP BY category_id
+             ORDER BY COUNT(*) DESC, MAX(date) DESC
+             LIMIT 1",
+            [&payee.name],
+            |row| row.get(0),
+        )
+        .optional()?;
+
+    let average_amount = if transaction_count > 0 {
+        total_amount / transaction_count
+    } else {
+        0
+    };
+
+    Ok(PayeeStats {
+        payee,
+        transaction_count,
+        total_amount,
+        average_amount,
+        first_date,
+        last_date,
+        most_used_category_id,
+    })
+}
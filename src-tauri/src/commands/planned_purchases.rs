@@ -0,0 +1,250 @@
+use crate::commands::forecast::{build_forecast, month_index, MAX_FORECAST_MONTHS};
+use crate::db::Database;
+use crate::error::{AppError, Result};
+use crate::events::{emit_changed, PLANNED_PURCHASES_CHANGED};
+use crate::models::PlannedPurchase;
+use chrono::NaiveDate;
+use rusqlite::Connection;
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::{AppHandle, State};
+use uuid::Uuid;
+
+/// Months past the desired date to keep looking for room in the forecast
+const RECOMMENDATION_LOOKAHEAD_MONTHS: i32 = 12;
+
+const PLANNED_PURCHASE_COLUMNS: &str = "id, name, target_amount, desired_date, category_id,
+    notes, is_purchased, created_at, updated_at";
+
+fn planned_purchase_from_row(row: &rusqlite::Row) -> rusqlite::Result<PlannedPurchase> {
+    Ok(PlannedPurchase {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        target_amount: row.get(2)?,
+        desired_date: row.get(3)?,
+        category_id: row.get(4)?,
+        notes: row.get(5)?,
+        is_purchased: row.get(6)?,
+        created_at: row.get(7)?,
+        updated_at: row.get(8)?,
+    })
+}
+
+fn fetch_planned_purchase(conn: &Connection, id: &str) -> Result<PlannedPurchase> {
+    conn.query_row(
+        &format!(
+            "SELECT {} FROM planned_purchases WHERE id = ?1",
+            PLANNED_PURCHASE_COLUMNS
+        ),
+        [id],
+        planned_purchase_from_row,
+    )
+    .map_err(|_| AppError::NotFound("Planned purchase not found".to_string()))
+}
+
+fn validate_desired_date(date: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map_err(|_| AppError::Validation(format!("Invalid desired date: {}", date)))
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PurchaseRecommendation {
+    pub purchase_id: String,
+    pub desired_month: String,
+    /// First month from the desired one on where buying leaves every later forecast
+    /// month with a non-negative balance; `None` if nothing fits within a year of it
+    pub recommended_month: Option<String>,
+    pub fits_desired_month: bool,
+}
+
+#[tauri::command]
+pub fn list_planned_purchases(
+    include_purchased: Option<bool>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<PlannedPurchase>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM planned_purchases
+         WHERE is_purchased = 0 OR ?1
+         ORDER BY desired_date, name",
+        PLANNED_PURCHASE_COLUMNS
+    ))?;
+
+    let purchases = stmt
+        .query_map([include_purchased.unwrap_or(false)], planned_purchase_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(purchases)
+}
+
+#[tauri::command]
+pub fn create_planned_purchase(
+    data: serde_json::Value,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<PlannedPurchase> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let name = data["name"].as_str().unwrap_or("").trim();
+    if name.is_empty() {
+        return Err(AppError::Validation(
+            "Planned purchase name is required".to_string(),
+        ));
+    }
+    let target_amount = data["targetAmount"].as_i64().unwrap_or(0);
+    if target_amount <= 0 {
+        return Err(AppError::Validation(
+            "Target amount must be greater than zero".to_string(),
+        ));
+    }
+    let desired_date = data["desiredDate"].as_str().unwrap_or("");
+    validate_desired_date(desired_date)?;
+
+    let id = Uuid::new_v4().to_string();
+    let now = chrono::Utc::now().to_rfc3339();
+
+    conn.execute(
+        "INSERT INTO planned_purchases (id, name, target_amount, desired_date, category_id,
+                notes, is_purchased, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, 0, ?7, ?7)",
+        rusqlite::params![
+            id,
+            name,
+            target_amount,
+            desired_date,
+            data["categoryId"].as_str(),
+            data["notes"].as_str(),
+            now,
+        ],
+    )?;
+
+    emit_changed(&app, &[PLANNED_PURCHASES_CHANGED]);
+
+    fetch_planned_purchase(conn, &id)
+}
+
+#[tauri::command]
+pub fn update_planned_purchase(
+    id: String,
+    data: serde_json::Value,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<PlannedPurchase> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    if data["targetAmount"].as_i64().is_some_and(|a| a <= 0) {
+        return Err(AppError::Validation(
+            "Target amount must be greater than zero".to_string(),
+        ));
+    }
+    if let Some(date) = data["desiredDate"].as_str() {
+        validate_desired_date(date)?;
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+
+    conn.execute(
+        "UPDATE planned_purchases SET
+            name = COALESCE(?1, name),
+            target_amount = COALESCE(?2, target_amount),
+            desired_date = COALESCE(?3, desired_date),
+            category_id = COALESCE(?4, category_id),
+            notes = COALESCE(?5, notes),
+            is_purchased = COALESCE(?6, is_purchased),
+            updated_at = ?7
+         WHERE id = ?8",
+        rusqlite::params![
+            data["name"].as_str(),
+            data["targetAmount"].as_i64(),
+            data["desiredDate"].as_str(),
+            data["categoryId"].as_str(),
+            data["notes"].as_str(),
+            data["isPurchased"].as_bool(),
+            now,
+            id,
+        ],
+    )?;
+
+    emit_changed(&app, &[PLANNED_PURCHASES_CHANGED]);
+
+    fetch_planned_purchase(conn, &id)
+}
+
+#[tauri::command]
+pub fn delete_planned_purchase(
+    id: String,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<()> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    conn.execute("DELETE FROM planned_purchases WHERE id = ?1", [&id])?;
+
+    emit_changed(&app, &[PLANNED_PURCHASES_CHANGED]);
+
+    Ok(())
+}
+
+/// Find the month a planned purchase fits: forecast everything else, then take the first
+/// month (the desired one, or later) where paying the full amount keeps that month and
+/// every month after it at or above zero, so no budget or bill goes unfunded.
+#[tauri::command]
+pub fn recommend_purchase_month(
+    id: String,
+    db: State<'_, Mutex<Database>>,
+) -> Result<PurchaseRecommendation> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let purchase = fetch_planned_purchase(conn, &id)?;
+    let desired = validate_desired_date(&purchase.desired_date)?;
+
+    let today = chrono::Local::now().date_naive();
+    let current_index = month_index(today);
+    let desired_index = month_index(desired).max(current_index);
+    let months = (desired_index - current_index + 1 + RECOMMENDATION_LOOKAHEAD_MONTHS)
+        .min(MAX_FORECAST_MONTHS as i32) as u32;
+
+    let forecast = build_forecast(conn, today, months, Some(&purchase.id))?;
+
+    // Lowest balance from each month to the end of the forecast
+    let mut lowest_from: Vec<i64> = forecast
+        .months
+        .iter()
+        .rev()
+        .scan(i64::MAX, |lowest, m| {
+            *lowest = (*lowest).min(m.ending_balance);
+            Some(*lowest)
+        })
+        .collect();
+    lowest_from.reverse();
+
+    let offset = (desired_index - current_index) as usize;
+    let recommended = forecast
+        .months
+        .iter()
+        .zip(lowest_from)
+        .skip(offset)
+        .find(|(_, lowest)| *lowest >= purchase.target_amount)
+        .map(|(m, _)| m.month.clone());
+
+    let desired_month = forecast
+        .months
+        .get(offset)
+        .map(|m| m.month.clone())
+        .unwrap_or_else(|| desired.format("%Y-%m").to_string());
+
+    Ok(PurchaseRecommendation {
+        purchase_id: purchase.id,
+        fits_desired_month: recommended.as_deref() == Some(desired_month.as_str()),
+        desired_month,
+        recommended_month: recommended,
+    })
+}
@@ -0,0 +1,358 @@
+use crate::db::Database;
+use crate::error::{AppError, Result};
+use crate::events::{emit_changed, POLICIES_CHANGED, RECURRING_CHANGED};
+use crate::models::InsurancePolicy;
+use chrono::{Months, NaiveDate};
+use rusqlite::Connection;
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::{AppHandle, State};
+use uuid::Uuid;
+
+const POLICY_TYPES: &[&str] = &[
+    "auto",
+    "home",
+    "renters",
+    "life",
+    "health",
+    "disability",
+    "umbrella",
+    "pet",
+    "other",
+];
+
+/// Renewals within this many days are listed when no window is given
+const DEFAULT_RENEWAL_WINDOW_DAYS: i64 = 30;
+
+const POLICY_COLUMNS: &str = "id, name, policy_type, provider, policy_number, premium,
+    premium_frequency, term_months, renewal_date, recurring_transaction_id, notes, is_active,
+    created_at, updated_at";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PolicyRenewal {
+    pub policy: InsurancePolicy,
+    pub renewal_date: String,
+    pub days_until: i64,
+    pub annual_premium: i64,
+}
+
+fn policy_from_row(row: &rusqlite::Row) -> rusqlite::Result<InsurancePolicy> {
+    Ok(InsurancePolicy {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        policy_type: row.get(2)?,
+        provider: row.get(3)?,
+        policy_number: row.get(4)?,
+        premium: row.get(5)?,
+        premium_frequency: row.get(6)?,
+        term_months: row.get(7)?,
+        renewal_date: row.get(8)?,
+        recurring_transaction_id: row.get(9)?,
+        notes: row.get(10)?,
+        is_active: row.get(11)?,
+        created_at: row.get(12)?,
+        updated_at: row.get(13)?,
+    })
+}
+
+fn fetch_policy(conn: &Connection, id: &str) -> Result<InsurancePolicy> {
+    conn.query_row(
+        &format!(
+            "SELECT {} FROM insurance_policies WHERE id = ?1",
+            POLICY_COLUMNS
+        ),
+        [id],
+        policy_from_row,
+    )
+    .map_err(|_| AppError::NotFound("Policy not found".to_string()))
+}
+
+/// How many times a premium is paid per year, using the recurring transaction frequencies
+fn payments_per_year(frequency: &str) -> Result<i64> {
+    match frequency {
+        "weekly" => Ok(52),
+        "biweekly" => Ok(26),
+        "monthly" => Ok(12),
+        "quarterly" => Ok(4),
+        "yearly" => Ok(1),
+        _ => Err(AppError::Validation(format!(
+            "Invalid premium frequency: {}",
+            frequency
+        ))),
+    }
+}
+
+fn validate_policy_type(policy_type: &str) -> Result<()> {
+    if POLICY_TYPES.contains(&policy_type) {
+        Ok(())
+    } else {
+        Err(AppError::Validation(format!(
+            "Invalid policy type: {}",
+            policy_type
+        )))
+    }
+}
+
+/// The first renewal on or after `today`, stepping a past renewal date forward one term at a time
+fn next_renewal(renewal_date: NaiveDate, term_months: u32, today: NaiveDate) -> Option<NaiveDate> {
+    let mut date = renewal_date;
+    while date < today {
+        date = date.checked_add_months(Months::new(term_months.max(1)))?;
+    }
+    Some(date)
+}
+
+#[tauri::command]
+pub fn list_policies(db: State<'_, Mutex<Database>>) -> Result<Vec<InsurancePolicy>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM insurance_policies ORDER BY is_active DESC, name",
+        POLICY_COLUMNS
+    ))?;
+
+    let policies = stmt
+        .query_map([], policy_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(policies)
+}
+
+/// Create a policy. When `accountId` is given (and no existing `recurringTransactionId`),
+/// a recurring premium payment is created from that account so the premium shows up in
+/// cash-flow forecasts.
+#[tauri::command]
+pub fn create_policy(
+    data: serde_json::Value,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<InsurancePolicy> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let name = data["name"].as_str().unwrap_or("").trim();
+    if name.is_empty() {
+        return Err(AppError::Validation("Policy name is required".to_string()));
+    }
+    let policy_type = data["policyType"].as_str().unwrap_or("other");
+    validate_policy_type(policy_type)?;
+    let premium_frequency = data["premiumFrequency"].as_str().unwrap_or("yearly");
+    payments_per_year(premium_frequency)?;
+    let premium = data["premium"].as_i64().unwrap_or(0).abs();
+
+    let id = Uuid::new_v4().to_string();
+    let now = chrono::Utc::now().to_rfc3339();
+
+    let tx = conn.unchecked_transaction()?;
+
+    let mut recurring_transaction_id = data["recurringTransactionId"].as_str().map(String::from);
+    if let (None, Some(account_id)) = (&recurring_transaction_id, data["accountId"].as_str()) {
+        let recurring_id = Uuid::new_v4().to_string();
+        let start_date = data["renewalDate"]
+            .as_str()
+            .map(String::from)
+            .unwrap_or_else(|| {
+                chrono::Local::now()
+                    .date_naive()
+                    .format("%Y-%m-%d")
+                    .to_string()
+            });
+
+        tx.execute(
+            "INSERT INTO recurring_transactions (id, account_id, payee, amount, category_id,
+                    frequency, start_date, next_expected_date, is_auto_detected, is_active,
+                    created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?7, 0, 1, ?8, ?8)",
+            rusqlite::params![
+                recurring_id,
+                account_id,
+                data["provider"].as_str().unwrap_or(name),
+                -premium,
+                data["categoryId"].as_str(),
+                premium_frequency,
+                start_date,
+                now,
+            ],
+        )?;
+        recurring_transaction_id = Some(recurring_id);
+    }
+
+    tx.execute(
+        "INSERT INTO insurance_policies (id, name, policy_type, provider, policy_number, premium,
+                premium_frequency, term_months, renewal_date, recurring_transaction_id, notes,
+                is_active, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, 1, ?12, ?13)",
+        rusqlite::params![
+            id,
+            name,
+            policy_type,
+            data["provider"].as_str(),
+            data["policyNumber"].as_str(),
+            premium,
+            premium_frequency,
+            data["termMonths"].as_i64().unwrap_or(12),
+            data["renewalDate"].as_str(),
+            recurring_transaction_id,
+            data["notes"].as_str(),
+            now,
+            now,
+        ],
+    )?;
+
+    tx.commit()?;
+
+    if recurring_transaction_id.is_some() {
+        emit_changed(&app, &[POLICIES_CHANGED, RECURRING_CHANGED]);
+    } else {
+        emit_changed(&app, &[POLICIES_CHANGED]);
+    }
+
+    fetch_policy(conn, &id)
+}
+
+#[tauri::command]
+pub fn update_policy(
+    id: String,
+    data: serde_json::Value,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<InsurancePolicy> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    if let Some(policy_type) = data["policyType"].as_str() {
+        validate_policy_type(policy_type)?;
+    }
+    if let Some(frequency) = data["premiumFrequency"].as_str() {
+        payments_per_year(frequency)?;
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+
+    conn.execute(
+        "UPDATE insurance_policies SET
+            name = COALESCE(?1, name),
+            policy_type = COALESCE(?2, policy_type),
+            provider = COALESCE(?3, provider),
+            policy_number = COALESCE(?4, policy_number),
+            premium = COALESCE(?5, premium),
+            premium_frequency = COALESCE(?6, premium_frequency),
+            term_months = COALESCE(?7, term_months),
+            renewal_date = COALESCE(?8, renewal_date),
+            recurring_transaction_id = COALESCE(?9, recurring_transaction_id),
+            notes = COALESCE(?10, notes),
+            is_active = COALESCE(?11, is_active),
+            updated_at = ?12
+         WHERE id = ?13",
+        rusqlite::params![
+            data["name"].as_str(),
+            data["policyType"].as_str(),
+            data["provider"].as_str(),
+            data["policyNumber"].as_str(),
+            data["premium"].as_i64().map(i64::abs),
+            data["premiumFrequency"].as_str(),
+            data["termMonths"].as_i64(),
+            data["renewalDate"].as_str(),
+            data["recurringTransactionId"].as_str(),
+            data["notes"].as_str(),
+            data["isActive"].as_bool(),
+            now,
+            id,
+        ],
+    )?;
+
+    emit_changed(&app, &[POLICIES_CHANGED]);
+
+    fetch_policy(conn, &id)
+}
+
+/// Delete a policy. Its recurring premium payment stops too, unless another policy still
+/// uses it; it's deactivated rather than deleted so the bill's history stays.
+#[tauri::command]
+pub fn delete_policy(id: String, app: AppHandle, db: State<'_, Mutex<Database>>) -> Result<()> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let policy = fetch_policy(conn, &id)?;
+    let now = chrono::Utc::now().to_rfc3339();
+
+    let tx = conn.unchecked_transaction()?;
+    tx.execute("DELETE FROM insurance_policies WHERE id = ?1", [&id])?;
+    let stopped = match &policy.recurring_transaction_id {
+        Some(recurring_id) => tx.execute(
+            "UPDATE recurring_transactions SET is_active = 0, updated_at = ?1
+             WHERE id = ?2 AND is_active = 1
+               AND NOT EXISTS (
+                   SELECT 1 FROM insurance_policies WHERE recurring_transaction_id = ?2
+               )",
+            [&now, recurring_id],
+        )?,
+        None => 0,
+    };
+    tx.commit()?;
+
+    if stopped > 0 {
+        emit_changed(&app, &[POLICIES_CHANGED, RECURRING_CHANGED]);
+    } else {
+        emit_changed(&app, &[POLICIES_CHANGED]);
+    }
+
+    Ok(())
+}
+
+/// Active policies renewing within the next `days` days (30 by default), soonest first.
+/// Renewal dates already past are rolled forward by the policy term.
+#[tauri::command]
+pub fn get_upcoming_renewals(
+    days: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<PolicyRenewal>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let today = chrono::Local::now().date_naive();
+    let window = days.unwrap_or(DEFAULT_RENEWAL_WINDOW_DAYS);
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM insurance_policies WHERE is_active = 1 AND renewal_date IS NOT NULL",
+        POLICY_COLUMNS
+    ))?;
+
+    let policies: Vec<InsurancePolicy> = stmt
+        .query_map([], policy_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let mut renewals = Vec::new();
+    for policy in policies {
+        let renewal_date = match policy
+            .renewal_date
+            .as_deref()
+            .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+            .and_then(|d| next_renewal(d, policy.term_months.max(1) as u32, today))
+        {
+            Some(date) => date,
+            None => continue,
+        };
+
+        let days_until = (renewal_date - today).num_days();
+        if days_until > window {
+            continue;
+        }
+
+        let annual_premium = policy.premium * payments_per_year(&policy.premium_frequency)?;
+        renewals.push(PolicyRenewal {
+            policy,
+            renewal_date: renewal_date.format("%Y-%m-%d").to_string(),
+            days_until,
+            annual_premium,
+        });
+    }
+
+    renewals.sort_by_key(|r| r.days_until);
+
+    Ok(renewals)
+}
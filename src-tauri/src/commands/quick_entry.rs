@@ -0,0 +1,354 @@
+use crate::db::Database;
+use crate::error::{AppError, Result};
+use chrono::{Datelike, Duration, NaiveDate};
+use rusqlite::{Connection, OptionalExtension};
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::State;
+
+/// Common shorthand words mapped to the seeded category they usually mean
+const CATEGORY_KEYWORDS: &[(&str, &str)] = &[
+    ("breakfast", "Restaurants"),
+    ("lunch", "Restaurants"),
+    ("dinner", "Restaurants"),
+    ("coffee", "Coffee Shops"),
+    ("groceries", "Groceries"),
+    ("grocery", "Groceries"),
+    ("gas", "Gas & Fuel"),
+    ("fuel", "Gas & Fuel"),
+    ("parking", "Parking"),
+    ("uber", "Rideshare"),
+    ("lyft", "Rideshare"),
+    ("taxi", "Rideshare"),
+    ("drinks", "Bars & Alcohol"),
+    ("beer", "Bars & Alcohol"),
+    ("delivery", "Food Delivery"),
+];
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickEntryDraft {
+    pub amount: Option<i64>,
+    pub payee: Option<String>,
+    pub category_id: Option<String>,
+    pub date: String,
+    pub tags: Vec<String>,
+    pub memo: Option<String>,
+}
+
+/// Tokens pulled out of a quick entry before any database lookups
+#[derive(Debug, Default)]
+struct QuickEntryTokens {
+    amount: Option<i64>,
+    is_income: bool,
+    date: Option<NaiveDate>,
+    tags: Vec<String>,
+    words: Vec<String>,
+    /// Words after "at" or "from", which name the payee explicitly
+    payee_words: Vec<String>,
+}
+
+/// Words that introduce the payee, as in "coffee 4.50 at starbucks"
+const PAYEE_MARKERS: &[&str] = &["at", "from", "@"];
+
+/// Words that only introduce a date, as in "on friday" or "last tuesday"
+const DATE_MARKERS: &[&str] = &["on", "last"];
+
+/// Parse an amount token like "12.50", "$12", or "+1,200" into cents
+fn parse_amount(token: &str) -> Option<i64> {
+    let cleaned = token.trim_start_matches(['+', '-']).replace(['$', ','], "");
+    if cleaned.is_empty() || !cleaned.chars().any(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let amount: f64 = cleaned.parse().ok()?;
+    Some((amount * 100.0).round() as i64)
+}
+
+/// Parse a date word relative to `today`: today/yesterday, a weekday name (most recent
+/// occurrence, including today), YYYY-MM-DD, or M/D with an optional year
+fn parse_date(token: &str, today: NaiveDate) -> Option<NaiveDate> {
+    match token {
+        "today" => return Some(today),
+        "yesterday" => return Some(today - Duration::days(1)),
+        _ => {}
+    }
+
+    let weekdays = ["mon", "tue", "wed", "thu", "fri", "sat", "sun"];
+    let full_names = ["monday", "tuesday", "wednesday", "thursday", "friday", "saturday", "sunday"];
+    if let Some(index) = weekdays
+        .iter()
+        .zip(full_names.iter())
+        .position(|(short, full)| token == *short || token == *full)
+    {
+        let current = today.weekday().num_days_from_monday() as i64;
+        let days_back = (current - index as i64).rem_euclid(7);
+        return Some(today - Duration::days(days_back));
+    }
+
+    if let Ok(date) = NaiveDate::parse_from_str(token, "%Y-%m-%d") {
+        return Some(date);
+    }
+
+    let parts: Vec<&str> = token.split('/').collect();
+    if parts.len() == 2 || parts.len() == 3 {
+        let month: u32 = parts[0].parse().ok()?;
+        let day: u32 = parts[1].parse().ok()?;
+        let year: i32 = match parts.get(2) {
+            Some(y) if y.len() == 2 => 2000 + y.parse::<i32>().ok()?,
+            Some(y) => y.parse().ok()?,
+            None => today.year(),
+        };
+        return NaiveDate::from_ymd_opt(year, month, day);
+    }
+
+    None
+}
+
+fn tokenize(text: &str, today: NaiveDate) -> QuickEntryTokens {
+    let mut tokens = QuickEntryTokens::default();
+    let mut in_payee = false;
+    let mut raws = text.split_whitespace().peekable();
+
+    while let Some(raw) = raws.next() {
+        let lower = raw.to_lowercase();
+
+        if let Some(tag) = raw.strip_prefix('#') {
+            if !tag.is_empty() {
+                tokens.tags.push(tag.to_string());
+            }
+            continue;
+        }
+
+        if tokens.amount.is_none() {
+            if let Some(amount) = parse_amount(&lower) {
+                tokens.amount = Some(amount);
+                tokens.is_income = lower.starts_with('+');
+                continue;
+            }
+        }
+
+        if tokens.date.is_none() {
+            if let Some(date) = parse_date(&lower, today) {
+                tokens.date = Some(date);
+                continue;
+            }
+        }
+
+        if PAYEE_MARKERS.contains(&lower.as_str()) && raws.peek().is_some() {
+            in_payee = true;
+            continue;
+        }
+
+        let date_follows = raws
+            .peek()
+            .is_some_and(|next| parse_date(&next.to_lowercase(), today).is_some());
+        if tokens.date.is_none() && date_follows && DATE_MARKERS.contains(&lower.as_str()) {
+            continue;
+        }
+
+        if in_payee {
+            tokens.payee_words.push(raw.to_string());
+        } else {
+            tokens.words.push(raw.to_string());
+        }
+    }
+
+    tokens
+}
+
+/// Find a category named by `word`, either directly or through the shorthand keyword table
+fn find_category(conn: &Connection, word: &str) -> Result<Option<(String, String)>> {
+    let lower = word.to_lowercase();
+    let name = CATEGORY_KEYWORDS
+        .iter()
+        .find(|(keyword, _)| *keyword == lower)
+        .map(|(_, category)| *category)
+        .unwrap_or(word);
+
+    let category = conn
+        .query_row(
+            "SELECT id, category_type FROM categories
+             WHERE name = ?1 COLLATE NOCASE AND deleted_at IS NULL
+             LIMIT 1",
+            [name],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()?;
+
+    Ok(category)
+}
+
+/// Match typed text to a known payee (by name, alias, or name prefix) without creating one
+fn find_payee(conn: &Connection, text: &str) -> Result<Option<(String, Option<String>)>> {
+    // Escape LIKE wildcards so the text is matched literally as a prefix
+    let pattern = format!(
+        "{}%",
+        text.replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_")
+    );
+
+    let payee = conn
+        .query_row(
+            "SELECT p.name, p.default_category_id
+             FROM payees p
+             WHERE p.name = ?1
+                OR p.id IN (SELECT payee_id FROM payee_aliases WHERE alias = ?1)
+                OR p.name LIKE ?2 ESCAPE '\\'
+             ORDER BY p.name = ?1 DESC,
+                      (SELECT COUNT(*) FROM transactions t
+                       WHERE t.payee = p.name AND t.deleted_at IS NULL) DESC
+             LIMIT 1",
+            [text, pattern.as_str()],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()?;
+
+    Ok(payee)
+}
+
+#[tauri::command]
+pub fn parse_quick_entry(text: String, db: State<'_, Mutex<Database>>) -> Result<QuickEntryDraft> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    if text.trim().is_empty() {
+        return Err(AppError::Validation("Nothing to parse".to_string()));
+    }
+
+    let today = chrono::Local::now().date_naive();
+    let tokens = tokenize(&text, today);
+
+    // The first word that names a category is the category; the rest describe the payee,
+    // unless the payee was named explicitly ("at starbucks"), in which case they become the memo
+    let mut category: Option<(String, String)> = None;
+    let mut other_words = Vec::new();
+    for word in &tokens.words {
+        if category.is_none() {
+            if let Some(found) = find_category(conn, word)? {
+                category = Some(found);
+                continue;
+            }
+        }
+        other_words.push(word.as_str());
+    }
+
+    let (typed_payee, memo) = if tokens.payee_words.is_empty() {
+        (other_words.join(" "), None)
+    } else {
+        let memo = Some(other_words.join(" ")).filter(|m| !m.is_empty());
+        (tokens.payee_words.join(" "), memo)
+    };
+    let mut payee = None;
+    let mut category_id = category.as_ref().map(|(id, _)| id.clone());
+
+    if !typed_payee.is_empty() {
+        // Prefer the canonical payee, and fall back on its default or most-used category
+        let known = find_payee(conn, &typed_payee)?;
+        if let Some((_, default_category_id)) = &known {
+            if category_id.is_none() {
+                category_id = default_category_id.clone();
+            }
+        }
+        let name = known.map(|(name, _)| name).unwrap_or(typed_payee);
+
+        if category_id.is_none() {
+            category_id = conn
+                .query_row(
+                    "SELECT category_id FROM transactions
+                     WHERE payee = ?1 AND category_id IS NOT NULL AND deleted_at IS NULL
+                     GROUP BY category_id
+                     ORDER BY COUNT(*) DESC
+                     LIMIT 1",
+                    [&name],
+                    |row| row.get(0),
+                )
+                .optional()?;
+        }
+        payee = Some(name);
+    }
+
+    let is_income = tokens.is_income || matches!(&category, Some((_, kind)) if kind == "income");
+    let amount = tokens.amount.map(|a| if is_income { a } else { -a });
+
+    Ok(QuickEntryDraft {
+        amount,
+        payee,
+        category_id,
+        date: tokens.date.unwrap_or(today).format("%Y-%m-%d").to_string(),
+        tags: tokens.tags,
+        memo,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_tokenize() {
+        // 2024-05-15 is a Wednesday
+        let today = day(2024, 5, 15);
+        let tokens = tokenize("12.50 lunch chipotle yesterday #work", today);
+        assert_eq!(tokens.amount, Some(1250));
+        assert!(!tokens.is_income);
+        assert_eq!(tokens.date, Some(day(2024, 5, 14)));
+        assert_eq!(tokens.tags, vec!["work"]);
+        assert_eq!(tokens.words, vec!["lunch", "chipotle"]);
+
+        let tokens = tokenize("+$1,200 paycheck 5/1", today);
+        assert_eq!(tokens.amount, Some(120000));
+        assert!(tokens.is_income);
+        assert_eq!(tokens.date, Some(day(2024, 5, 1)));
+    }
+
+    #[test]
+    fn test_tokenize_payee_marker() {
+        let today = day(2024, 5, 15);
+        let tokens = tokenize("coffee 4.50 yesterday at starbucks", today);
+        assert_eq!(tokens.amount, Some(450));
+        assert_eq!(tokens.date, Some(day(2024, 5, 14)));
+        assert_eq!(tokens.words, vec!["coffee"]);
+        assert_eq!(tokens.payee_words, vec!["starbucks"]);
+
+        let tokens = tokenize("iced latte $6 from blue bottle on monday", today);
+        assert_eq!(tokens.date, Some(day(2024, 5, 13)));
+        assert_eq!(tokens.words, vec!["iced", "latte"]);
+        assert_eq!(tokens.payee_words, vec!["blue", "bottle"]);
+
+        // A trailing marker is just a word
+        let tokens = tokenize("5 eat at", today);
+        assert_eq!(tokens.words, vec!["eat", "at"]);
+        assert!(tokens.payee_words.is_empty());
+    }
+
+    #[test]
+    fn test_parse_date() {
+        let today = day(2024, 5, 15);
+        assert_eq!(parse_date("monday", today), Some(day(2024, 5, 13)));
+        assert_eq!(parse_date("wed", today), Some(today));
+        assert_eq!(parse_date("2024-01-31", today), Some(day(2024, 1, 31)));
+        assert_eq!(parse_date("12/25/23", today), Some(day(2023, 12, 25)));
+        assert_eq!(parse_date("chipotle", today), None);
+    }
+
+    #[test]
+    fn test_find_payee_matches_wildcards_literally() {
+        let conn = Database::in_memory();
+        conn.execute_batch(
+            "INSERT INTO payees (id, name) VALUES ('p1', 'Abc Market'), ('p2', '100% Juice');",
+        )
+        .unwrap();
+
+        let name = |text: &str| find_payee(&conn, text).unwrap().map(|(name, _)| name);
+        assert_eq!(name("abc"), Some("Abc Market".to_string()));
+        assert_eq!(name("a_c"), None);
+        assert_eq!(name("%"), None);
+        assert_eq!(name("100%"), Some("100% Juice".to_string()));
+    }
+}
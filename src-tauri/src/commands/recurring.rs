@@ -1,9 +1,15 @@
+use crate::commands::categories::input_due_day;
+use crate::commands::reports::reportable_transaction_filter;
+use crate::commands::transactions::duplicate_payee_similarity;
 use crate::db::Database;
-use crate::error::Result;
+use crate::error::{AppError, Result};
+use crate::events::{emit_changed, RECURRING_CHANGED};
 use crate::models::RecurringTransaction;
-use std::collections::HashMap;
+use chrono::{Datelike, NaiveDate};
+use rusqlite::Connection;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::Mutex;
-use tauri::State;
+use tauri::{AppHandle, State};
 use uuid::Uuid;
 use serde::Serialize;
 
@@ -32,41 +38,48 @@ pub struct TransactionSummary {
     pub amount: i64,
 }
 
+/// Columns selected for a full `RecurringTransaction`, in the order `recurring_from_row` expects
+pub(crate) const RECURRING_COLUMNS: &str =
+    "id, account_id, payee, amount, category_id, frequency, start_date, end_date,
+     next_expected_date, last_matched_transaction_id, tolerance_days, tolerance_amount,
+     is_auto_detected, is_active, created_at, updated_at, due_day";
+
+pub(crate) fn recurring_from_row(row: &rusqlite::Row) -> rusqlite::Result<RecurringTransaction> {
+    Ok(RecurringTransaction {
+        id: row.get(0)?,
+        account_id: row.get(1)?,
+        payee: row.get(2)?,
+        amount: row.get(3)?,
+        category_id: row.get(4)?,
+        frequency: row.get(5)?,
+        start_date: row.get(6)?,
+        end_date: row.get(7)?,
+        next_expected_date: row.get(8)?,
+        last_matched_transaction_id: row.get(9)?,
+        tolerance_days: row.get(10)?,
+        tolerance_amount: row.get(11)?,
+        is_auto_detected: row.get(12)?,
+        is_active: row.get(13)?,
+        created_at: row.get(14)?,
+        updated_at: row.get(15)?,
+        due_day: row.get(16)?,
+    })
+}
+
 #[tauri::command]
 pub fn list_recurring_transactions(db: State<'_, Mutex<Database>>) -> Result<Vec<RecurringTransaction>> {
     let database = db.lock().unwrap();
     let conn = database.get_connection()?;
 
-    let mut stmt = conn.prepare(
-        "SELECT id, account_id, payee, amount, category_id, frequency, start_date, end_date,
-                next_expected_date, last_matched_transaction_id, tolerance_days, tolerance_amount,
-                is_auto_detected, is_active, created_at, updated_at
-         FROM recurring_transactions
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM recurring_transactions
          WHERE is_active = 1
-         ORDER BY next_expected_date ASC NULLS LAST"
-    )?;
+         ORDER BY next_expected_date ASC NULLS LAST",
+        RECURRING_COLUMNS
+    ))?;
 
     let recurring = stmt
-        .query_map([], |row| {
-            Ok(RecurringTransaction {
-                id: row.get(0)?,
-                account_id: row.get(1)?,
-                payee: row.get(2)?,
-                amount: row.get(3)?,
-                category_id: row.get(4)?,
-                frequency: row.get(5)?,
-                start_date: row.get(6)?,
-                end_date: row.get(7)?,
-                next_expected_date: row.get(8)?,
-                last_matched_transaction_id: row.get(9)?,
-                tolerance_days: row.get(10)?,
-                tolerance_amount: row.get(11)?,
-                is_auto_detected: row.get(12)?,
-                is_active: row.get(13)?,
-                created_at: row.get(14)?,
-                updated_at: row.get(15)?,
-            })
-        })?
+        .query_map([], recurring_from_row)?
         .filter_map(|r| r.ok())
         .collect();
 
@@ -74,7 +87,7 @@ pub fn list_recurring_transactions(db: State<'_, Mutex<Database>>) -> Result<Vec
 }
 
 /// Normalize payee name by removing dates, numbers, and common suffixes
-fn normalize_payee(payee: &str) -> String {
+pub(crate) fn normalize_payee(payee: &str) -> String {
     let mut normalized = payee.to_lowercase();
 
     // Remove common date patterns
@@ -263,19 +276,21 @@ pub fn detect_recurring_transactions(db: State<'_, Mutex<Database>>) -> Result<V
 #[tauri::command]
 pub fn create_recurring_transaction(
     data: serde_json::Value,
+    app: AppHandle,
     db: State<'_, Mutex<Database>>,
 ) -> Result<RecurringTransaction> {
     let database = db.lock().unwrap();
     let conn = database.get_connection()?;
 
+    let due_day = input_due_day(&data)?;
     let id = Uuid::new_v4().to_string();
     let now = chrono::Utc::now().to_rfc3339();
 
     conn.execute(
         "INSERT INTO recurring_transactions (id, account_id, payee, amount, category_id, frequency,
                 start_date, end_date, next_expected_date, tolerance_days, tolerance_amount,
-                is_auto_detected, is_active, created_at, updated_at)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, 1, ?13, ?14)",
+                is_auto_detected, is_active, due_day, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, 1, ?13, ?14, ?15)",
         rusqlite::params![
             id,
             data["accountId"].as_str().unwrap_or(""),
@@ -289,37 +304,18 @@ pub fn create_recurring_transaction(
             data["toleranceDays"].as_i64().unwrap_or(3) as i32,
             data["toleranceAmount"].as_i64().unwrap_or(0),
             data["isAutoDetected"].as_bool().unwrap_or(false),
+            due_day,
             now,
             now,
         ],
     )?;
 
+    emit_changed(&app, &[RECURRING_CHANGED]);
+
     conn.query_row(
-        "SELECT id, account_id, payee, amount, category_id, frequency, start_date, end_date,
-                next_expected_date, last_matched_transaction_id, tolerance_days, tolerance_amount,
-                is_auto_detected, is_active, created_at, updated_at
-         FROM recurring_transactions WHERE id = ?1",
+        &format!("SELECT {} FROM recurring_transactions WHERE id = ?1", RECURRING_COLUMNS),
         [&id],
-        |row| {
-            Ok(RecurringTransaction {
-                id: row.get(0)?,
-                account_id: row.get(1)?,
-                payee: row.get(2)?,
-                amount: row.get(3)?,
-                category_id: row.get(4)?,
-                frequency: row.get(5)?,
-                start_date: row.get(6)?,
-                end_date: row.get(7)?,
-                next_expected_date: row.get(8)?,
-                last_matched_transaction_id: row.get(9)?,
-                tolerance_days: row.get(10)?,
-                tolerance_amount: row.get(11)?,
-                is_auto_detected: row.get(12)?,
-                is_active: row.get(13)?,
-                created_at: row.get(14)?,
-                updated_at: row.get(15)?,
-            })
-        },
+        recurring_from_row,
     )
     .map_err(|e| e.into())
 }
@@ -328,11 +324,13 @@ pub fn create_recurring_transaction(
 pub fn update_recurring_transaction(
     id: String,
     data: serde_json::Value,
+    app: AppHandle,
     db: State<'_, Mutex<Database>>,
 ) -> Result<RecurringTransaction> {
     let database = db.lock().unwrap();
     let conn = database.get_connection()?;
 
+    let due_day = input_due_day(&data)?;
     let now = chrono::Utc::now().to_rfc3339();
 
     conn.execute(
@@ -344,8 +342,9 @@ pub fn update_recurring_transaction(
             next_expected_date = ?5,
             end_date = ?6,
             is_active = COALESCE(?7, is_active),
-            updated_at = ?8
-         WHERE id = ?9",
+            due_day = CASE WHEN ?11 THEN ?8 ELSE due_day END,
+            updated_at = ?9
+         WHERE id = ?10",
         rusqlite::params![
             data["payee"].as_str(),
             data["amount"].as_i64(),
@@ -354,47 +353,272 @@ pub fn update_recurring_transaction(
             data["nextExpectedDate"].as_str(),
             data["endDate"].as_str(),
             data["isActive"].as_bool(),
+            due_day,
             now,
             id,
+            data.get("dueDay").is_some(),
         ],
     )?;
 
+    emit_changed(&app, &[RECURRING_CHANGED]);
+
     conn.query_row(
-        "SELECT id, account_id, payee, amount, category_id, frequency, start_date, end_date,
-                next_expected_date, last_matched_transaction_id, tolerance_days, tolerance_amount,
-                is_auto_detected, is_active, created_at, updated_at
-         FROM recurring_transactions WHERE id = ?1",
+        &format!("SELECT {} FROM recurring_transactions WHERE id = ?1", RECURRING_COLUMNS),
         [&id],
-        |row| {
-            Ok(RecurringTransaction {
-                id: row.get(0)?,
-                account_id: row.get(1)?,
-                payee: row.get(2)?,
-                amount: row.get(3)?,
-                category_id: row.get(4)?,
-                frequency: row.get(5)?,
-                start_date: row.get(6)?,
-                end_date: row.get(7)?,
-                next_expected_date: row.get(8)?,
-                last_matched_transaction_id: row.get(9)?,
-                tolerance_days: row.get(10)?,
-                tolerance_amount: row.get(11)?,
-                is_auto_detected: row.get(12)?,
-                is_active: row.get(13)?,
-                created_at: row.get(14)?,
-                updated_at: row.get(15)?,
-            })
-        },
+        recurring_from_row,
     )
     .map_err(|e| e.into())
 }
 
 #[tauri::command]
-pub fn delete_recurring_transaction(id: String, db: State<'_, Mutex<Database>>) -> Result<()> {
+pub fn delete_recurring_transaction(
+    id: String,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<()> {
     let database = db.lock().unwrap();
     let conn = database.get_connection()?;
 
     conn.execute("DELETE FROM recurring_transactions WHERE id = ?1", [&id])?;
 
+    emit_changed(&app, &[RECURRING_CHANGED]);
+
     Ok(())
 }
+
+/// Minimum payee similarity for a transaction to count as a recurring bill's payment
+const BILL_PAYMENT_MIN_SIMILARITY: f64 = 0.5;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarBill {
+    /// "recurring" for a recurring transaction, "category" for a bill category's budget
+    pub source: String,
+    pub id: String,
+    pub name: String,
+    /// Amount due, positive; `None` for a bill category without a monthly budget
+    pub amount: Option<i64>,
+    pub category_id: Option<String>,
+    pub account_id: Option<String>,
+    pub is_paid: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BillsCalendarDay {
+    pub date: String,
+    pub bills: Vec<CalendarBill>,
+    pub total: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BillsCalendar {
+    pub month: String,
+    pub days: Vec<BillsCalendarDay>,
+    pub total: i64,
+}
+
+/// The given day of a month, clamped to the month's last day (a bill due on the 31st
+/// falls on the 30th in April)
+pub(crate) fn clamped_date(year: i32, month: u32, day: u32) -> Option<NaiveDate> {
+    (1..=day.min(31))
+        .rev()
+        .find_map(|d| NaiveDate::from_ymd_opt(year, month, d))
+}
+
+/// Dates a recurring transaction is expected between two dates (inclusive). Monthly,
+/// quarterly and yearly bills land on their due day (or the anchor's day of month) in
+/// every nth month counted from the anchor; weekly and biweekly bills step from the anchor.
+pub(crate) fn occurrences_between(
+    recurring: &RecurringTransaction,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Vec<NaiveDate> {
+    let parse = |date: &str| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok();
+    let Some(anchor) = recurring
+        .next_expected_date
+        .as_deref()
+        .and_then(parse)
+        .or_else(|| parse(&recurring.start_date))
+    else {
+        return Vec::new();
+    };
+    let first = parse(&recurring.start_date).map_or(start, |d| d.max(start));
+    let last = recurring
+        .end_date
+        .as_deref()
+        .and_then(parse)
+        .map_or(end, |d| d.min(end));
+
+    let mut dates = Vec::new();
+    match recurring.frequency.as_str() {
+        "weekly" | "biweekly" => {
+            let step = if recurring.frequency == "weekly" { 7 } else { 14 };
+            let offset = (first - anchor).num_days().div_euclid(step);
+            let mut date = anchor + chrono::Duration::days(offset * step);
+            while date <= last {
+                if date >= first {
+                    dates.push(date);
+                }
+                date += chrono::Duration::days(step);
+            }
+        }
+        frequency => {
+            let every = match frequency {
+                "quarterly" => 3,
+                "yearly" => 12,
+                _ => 1,
+            };
+            let day = recurring.due_day.map_or(anchor.day(), |d| d as u32);
+            let anchor_index = anchor.year() * 12 + anchor.month0() as i32;
+            let mut index = first.year() * 12 + first.month0() as i32;
+            while index <= last.year() * 12 + last.month0() as i32 {
+                if (index - anchor_index).rem_euclid(every) == 0 {
+                    let date = clamped_date(index.div_euclid(12), index.rem_euclid(12) as u32 + 1, day);
+                    if let Some(date) = date.filter(|d| *d >= first && *d <= last) {
+                        dates.push(date);
+                    }
+                }
+                index += 1;
+            }
+        }
+    }
+
+    dates
+}
+
+/// Whether a charge from the bill's payee posted to its account within tolerance of the due date
+pub(crate) fn is_recurring_bill_paid(
+    conn: &Connection,
+    recurring: &RecurringTransaction,
+    due: NaiveDate,
+) -> Result<bool> {
+    let tolerance = chrono::Duration::days(recurring.tolerance_days.max(0) as i64);
+    let mut stmt = conn.prepare(
+        "SELECT payee FROM transactions
+         WHERE deleted_at IS NULL AND is_hold = 0
+           AND account_id = ?1 AND amount < 0
+           AND date >= ?2 AND date <= ?3",
+    )?;
+    let payees: Vec<Option<String>> = stmt
+        .query_map(
+            rusqlite::params![
+                recurring.account_id,
+                (due - tolerance).format("%Y-%m-%d").to_string(),
+                (due + tolerance).format("%Y-%m-%d").to_string(),
+            ],
+            |row| row.get(0),
+        )?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let bill_payee = Some(recurring.payee.clone());
+    Ok(payees
+        .iter()
+        .any(|p| duplicate_payee_similarity(&bill_payee, p) >= BILL_PAYMENT_MIN_SIMILARITY))
+}
+
+/// Every bill expected in a month (YYYY-MM), grouped by due date, for a bill calendar.
+/// Bills are active recurring outflows plus categories with a due day that no recurring
+/// bill already covers that month; those use the category's monthly budget as the amount
+/// and count as paid once anything has been spent in the category that month.
+#[tauri::command]
+pub fn get_bills_calendar(month: String, db: State<'_, Mutex<Database>>) -> Result<BillsCalendar> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let start = NaiveDate::parse_from_str(&format!("{}-01", month), "%Y-%m-%d")
+        .map_err(|_| AppError::Validation("Invalid month format. Use YYYY-MM".to_string()))?;
+    let end = clamped_date(start.year(), start.month(), 31).unwrap_or(start);
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM recurring_transactions
+         WHERE is_active = 1 AND amount < 0",
+        RECURRING_COLUMNS
+    ))?;
+    let recurring: Vec<RecurringTransaction> = stmt
+        .query_map([], recurring_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let mut by_date: BTreeMap<NaiveDate, Vec<CalendarBill>> = BTreeMap::new();
+    let mut covered_categories: HashSet<String> = HashSet::new();
+
+    for bill in &recurring {
+        for due in occurrences_between(bill, start, end) {
+            if let Some(category_id) = &bill.category_id {
+                covered_categories.insert(category_id.clone());
+            }
+            by_date.entry(due).or_default().push(CalendarBill {
+                source: "recurring".to_string(),
+                id: bill.id.clone(),
+                name: bill.payee.clone(),
+                amount: Some(-bill.amount),
+                category_id: bill.category_id.clone(),
+                account_id: Some(bill.account_id.clone()),
+                is_paid: is_recurring_bill_paid(conn, bill, due)?,
+            });
+        }
+    }
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT c.id, c.name, c.due_day,
+                (SELECT b.amount FROM budgets b
+                 WHERE b.category_id = c.id AND b.period_type = 'monthly'
+                   AND b.is_watch_only = 0
+                 ORDER BY b.created_at DESC LIMIT 1),
+                (SELECT COUNT(*) FROM transactions t
+                 WHERE t.category_id = c.id AND t.amount < 0
+                   AND t.date >= ?1 AND t.date <= ?2
+                   AND {})
+         FROM categories c
+         WHERE c.deleted_at IS NULL AND c.due_day IS NOT NULL
+         ORDER BY c.display_order, c.name",
+        reportable_transaction_filter()
+    ))?;
+    let categories: Vec<(String, String, i32, Option<i64>, i64)> = stmt
+        .query_map(
+            rusqlite::params![
+                start.format("%Y-%m-%d").to_string(),
+                end.format("%Y-%m-%d").to_string(),
+            ],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?)),
+        )?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    for (id, name, due_day, budget, spent_count) in categories {
+        if covered_categories.contains(&id) {
+            continue;
+        }
+        let Some(due) = clamped_date(start.year(), start.month(), due_day as u32) else {
+            continue;
+        };
+        by_date.entry(due).or_default().push(CalendarBill {
+            source: "category".to_string(),
+            id: id.clone(),
+            name,
+            amount: budget,
+            category_id: Some(id),
+            account_id: None,
+            is_paid: spent_count > 0,
+        });
+    }
+
+    let days: Vec<BillsCalendarDay> = by_date
+        .into_iter()
+        .map(|(date, bills)| BillsCalendarDay {
+            date: date.format("%Y-%m-%d").to_string(),
+            total: bills.iter().filter_map(|b| b.amount).sum(),
+            bills,
+        })
+        .collect();
+    let total = days.iter().map(|d| d.total).sum();
+
+    Ok(BillsCalendar {
+        month: start.format("%Y-%m").to_string(),
+        days,
+        total,
+    })
+}
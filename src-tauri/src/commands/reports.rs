@@ -0,0 +1,783 @@
+use crate::commands::account_groups::{net_worth_by_group, AccountGroupNetWorth};
+use crate::commands::accounts::{account_columns, account_from_row, asset_liability_totals};
+use crate::commands::fx::{base_currency, missing_fx_currencies, transaction_base_amount};
+use crate::commands::recurring::{recurring_from_row, RECURRING_COLUMNS};
+use crate::commands::settings::read_setting;
+use crate::commands::transactions::{
+    transaction_from_row, BALANCE_ADJUSTMENT_TYPE, CREDIT_CARD_PAYMENT_TYPE, INVESTMENT_TRADE_TYPE,
+    TRANSACTION_COLUMNS,
+};
+use crate::db::Database;
+use crate::error::{AppError, Result};
+use crate::models::{Account, CashFlowData, RecurringTransaction, SpendingByCategory, Transaction};
+use chrono::{Datelike, NaiveDate, Weekday};
+use rusqlite::Connection;
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::State;
+
+/// Bundled reference rates (percent) used when the user hasn't entered their own:
+/// a typical high-yield savings APY and a typical low-rate credit card APR
+const DEFAULT_SAVINGS_REFERENCE_APY: f64 = 4.0;
+const DEFAULT_CREDIT_CARD_REFERENCE_APR: f64 = 15.0;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RateComparison {
+    pub account_id: String,
+    pub account_name: String,
+    pub account_type: String,
+    pub balance: i64,
+    pub account_rate: f64,
+    pub reference_rate: f64,
+    pub annual_opportunity: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RateComparisonReport {
+    pub savings_reference_rate: f64,
+    pub credit_card_reference_rate: f64,
+    pub accounts: Vec<RateComparison>,
+    pub total_annual_opportunity: i64,
+}
+
+/// How far ahead the dashboard looks for recurring bills, and how many recent transactions it shows
+const DASHBOARD_UPCOMING_BILL_DAYS: i64 = 14;
+const DASHBOARD_RECENT_TRANSACTIONS: i64 = 10;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DashboardSummary {
+    pub accounts: Vec<Account>,
+    /// The currency the totals are in
+    pub base_currency: String,
+    /// Currencies with no exchange rate to the base currency, counted unconverted
+    pub missing_fx_currencies: Vec<String>,
+    pub total_assets: i64,
+    pub total_liabilities: i64,
+    pub net_worth: i64,
+    /// Net worth sectioned by account group
+    pub account_groups: Vec<AccountGroupNetWorth>,
+    pub month_to_date_spending: Vec<SpendingByCategory>,
+    pub upcoming_bills: Vec<RecurringTransaction>,
+    pub recent_transactions: Vec<Transaction>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpendingMatrixColumn {
+    pub id: String,
+    pub name: String,
+    pub total: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpendingMatrixRow {
+    pub category_id: String,
+    pub category_name: String,
+    pub color: Option<String>,
+    /// Spending per column, in the same order as `SpendingMatrix::columns`
+    pub amounts: Vec<i64>,
+    pub total: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpendingMatrix {
+    pub group_by: String,
+    pub columns: Vec<SpendingMatrixColumn>,
+    pub rows: Vec<SpendingMatrixRow>,
+    pub total: i64,
+}
+
+/// Essential categories priced into personal inflation, with the quantity proxy each one
+/// is normalized by: spending per grocery trip or fill-up rather than per month, so buying
+/// more often doesn't read as prices going up
+const ESSENTIAL_CATEGORIES: [(&str, Option<&str>); 8] = [
+    ("cat_food_groceries", Some("trip")),
+    ("cat_transport_gas", Some("fill-up")),
+    ("cat_housing_rent", None),
+    ("cat_housing_utilities", None),
+    ("cat_housing_insurance", None),
+    ("cat_transport_insurance", None),
+    ("cat_health_insurance", None),
+    ("cat_health_pharmacy", None),
+];
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InflationCategory {
+    pub category_id: String,
+    pub category_name: String,
+    pub spending_a: i64,
+    pub spending_b: i64,
+    pub count_a: i64,
+    pub count_b: i64,
+    /// What spending is divided by before comparing ("trip", "fill-up", "charge");
+    /// `None` compares spending per month
+    pub unit: Option<String>,
+    pub change_percent: f64,
+    /// Share of period A's basket spending
+    pub weight: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PersonalInflation {
+    pub period_a: String,
+    pub period_b: String,
+    pub categories: Vec<InflationCategory>,
+    pub inflation_percent: f64,
+}
+
+/// How weekly reports group days into weeks, from the `week_start` ("sunday" or
+/// "monday") and `week_numbering` ("calendar" or "iso") settings. ISO weeks always
+/// start on Monday; unknown setting values fall back to Sunday calendar weeks.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct WeekSettings {
+    pub start: Weekday,
+    pub iso: bool,
+}
+
+impl WeekSettings {
+    pub(crate) fn read(conn: &Connection) -> Result<Self> {
+        let iso = read_setting(conn, "week_numbering")?.as_deref() == Some("iso");
+        let start = if iso || read_setting(conn, "week_start")?.as_deref() == Some("monday") {
+            Weekday::Mon
+        } else {
+            Weekday::Sun
+        };
+        Ok(WeekSettings { start, iso })
+    }
+
+    /// First day of the week containing `date`
+    pub(crate) fn week_start(&self, date: NaiveDate) -> NaiveDate {
+        date.week(self.start).first_day()
+    }
+
+    /// Period label for the week containing `date`: `YYYY-Www` for ISO weeks, otherwise
+    /// the week's first day
+    pub(crate) fn label(&self, date: NaiveDate) -> String {
+        if self.iso {
+            let week = date.iso_week();
+            format!("{:04}-W{:02}", week.year(), week.week())
+        } else {
+            self.week_start(date).format("%Y-%m-%d").to_string()
+        }
+    }
+}
+
+/// SQL predicate on the `t` alias for transactions that count as real income or spending.
+/// Transfers and credit card payments only move money between accounts, so counting them
+/// would double-count the card purchases they pay off. Pre-authorization holds are left
+/// out until the real charge settles, balance adjustments only correct the books, and
+/// investment trades only swap cash for securities.
+pub(crate) fn reportable_transaction_filter() -> String {
+    format!(
+        "t.deleted_at IS NULL
+         AND t.transfer_id IS NULL
+         AND t.is_hold = 0
+         AND COALESCE(t.transaction_type, '') NOT IN ('{}', '{}', '{}')",
+        CREDIT_CARD_PAYMENT_TYPE, BALANCE_ADJUSTMENT_TYPE, INVESTMENT_TRADE_TYPE
+    )
+}
+
+/// SQL predicate on the `t` alias leaving out spending kept out of budgets and spending
+/// reports, whether the transaction itself or its whole account is excluded. Pending
+/// holds wait until the real charge settles.
+pub(crate) const BUDGET_EXCLUSION_FILTER: &str = "t.exclude_from_budget = 0
+     AND t.is_hold = 0
+     AND t.account_id NOT IN (SELECT id FROM accounts WHERE exclude_from_budget = 1)";
+
+/// The join and expression giving the category a transaction `t` is reported under: its
+/// own, or with `rollup` its top-level ancestor so subcategory spending counts toward
+/// the parent. The walk starts from top-level categories, so it always terminates.
+fn reporting_category(rollup: bool) -> (&'static str, &'static str) {
+    if rollup {
+        (
+            "LEFT JOIN (
+                WITH RECURSIVE category_roots(id, root_id) AS (
+                    SELECT id, id FROM categories WHERE parent_id IS NULL
+                    UNION ALL
+                    SELECT child.id, category_roots.root_id
+                    FROM categories child
+                    JOIN category_roots ON child.parent_id = category_roots.id
+                )
+                SELECT id, root_id FROM category_roots
+             ) rc ON rc.id = t.category_id",
+            "COALESCE(rc.root_id, t.category_id)",
+        )
+    } else {
+        ("", "t.category_id")
+    }
+}
+
+/// Spending (as positive amounts) per category between two dates, largest first
+pub(crate) fn spending_by_category(
+    conn: &Connection,
+    start_date: &str,
+    end_date: &str,
+    rollup: bool,
+) -> Result<Vec<SpendingByCategory>> {
+    let (rollup_join, category) = reporting_category(rollup);
+    let mut stmt = conn.prepare(&format!(
+        "SELECT COALESCE({category}, 'uncategorized'),
+                COALESCE(c.name, 'Uncategorized'),
+                c.color,
+                SUM(-{amount}) AS spent
+         FROM transactions t
+         {rollup_join}
+         LEFT JOIN categories c ON {category} = c.id
+         WHERE {}
+           AND {BUDGET_EXCLUSION_FILTER}
+           AND t.amount < 0
+           AND t.date >= ?1
+           AND t.date <= ?2
+         GROUP BY COALESCE({category}, 'uncategorized')
+         ORDER BY spent DESC",
+        reportable_transaction_filter(),
+        amount = transaction_base_amount()
+    ))?;
+
+    let rows: Vec<(String, String, Option<String>, i64)> = stmt
+        .query_map([start_date, end_date], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let total: i64 = rows.iter().map(|(_, _, _, amount)| amount).sum();
+
+    let spending = rows
+        .into_iter()
+        .map(|(category_id, category_name, color, amount)| SpendingByCategory {
+            category_id,
+            category_name,
+            amount,
+            percentage: if total != 0 {
+                amount as f64 / total as f64 * 100.0
+            } else {
+                0.0
+            },
+            color,
+        })
+        .collect();
+
+    Ok(spending)
+}
+
+/// Spending per category; with `rollup`, subcategory spending is reported under its
+/// top-level category
+#[tauri::command]
+pub fn get_spending_by_category(
+    start_date: String,
+    end_date: String,
+    rollup: Option<bool>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<SpendingByCategory>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    spending_by_category(conn, &start_date, &end_date, rollup.unwrap_or(false))
+}
+
+/// Spending per category broken down by the account (or account type, with
+/// `group_by = "accountType"`) it was paid from. Columns and rows are largest first.
+/// `rollup` reports subcategory spending under its top-level category.
+#[tauri::command]
+pub fn get_spending_by_account_matrix(
+    start_date: String,
+    end_date: String,
+    group_by: Option<String>,
+    rollup: Option<bool>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<SpendingMatrix> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let group_by = group_by.unwrap_or_else(|| "account".to_string());
+    let (column_id, column_name) = match group_by.as_str() {
+        "account" => ("a.id", "a.name"),
+        "accountType" => ("a.account_type", "a.account_type"),
+        _ => {
+            return Err(AppError::Validation(format!(
+                "Invalid grouping: {}",
+                group_by
+            )))
+        }
+    };
+
+    let (rollup_join, category) = reporting_category(rollup.unwrap_or(false));
+    let mut stmt = conn.prepare(&format!(
+        "SELECT COALESCE({category}, 'uncategorized'),
+                COALESCE(c.name, 'Uncategorized'),
+                c.color,
+                {column_id},
+                {column_name},
+                SUM(-{amount})
+         FROM transactions t
+         JOIN accounts a ON t.account_id = a.id
+         {rollup_join}
+         LEFT JOIN categories c ON {category} = c.id
+         WHERE {}
+           AND {BUDGET_EXCLUSION_FILTER}
+           AND t.amount < 0
+           AND t.date >= ?1
+           AND t.date <= ?2
+         GROUP BY COALESCE({category}, 'uncategorized'), {column_id}",
+        reportable_transaction_filter(),
+        amount = transaction_base_amount()
+    ))?;
+
+    let cells: Vec<(String, String, Option<String>, String, String, i64)> = stmt
+        .query_map([&start_date, &end_date], |row| {
+            Ok((
+                row.get(0)?,
+                row.get(1)?,
+                row.get(2)?,
+                row.get(3)?,
+                row.get(4)?,
+                row.get(5)?,
+            ))
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let mut columns: Vec<SpendingMatrixColumn> = Vec::new();
+    for (_, _, _, id, name, amount) in &cells {
+        match columns.iter_mut().find(|c| &c.id == id) {
+            Some(column) => column.total += amount,
+            None => columns.push(SpendingMatrixColumn {
+                id: id.clone(),
+                name: name.clone(),
+                total: *amount,
+            }),
+        }
+    }
+    columns.sort_by_key(|c| std::cmp::Reverse(c.total));
+
+    let mut rows: Vec<SpendingMatrixRow> = Vec::new();
+    for (category_id, category_name, color, column_id, _, amount) in cells {
+        let index = columns.iter().position(|c| c.id == column_id).unwrap_or(0);
+        let row = match rows.iter().position(|r| r.category_id == category_id) {
+            Some(i) => &mut rows[i],
+            None => {
+                rows.push(SpendingMatrixRow {
+                    category_id,
+                    category_name,
+                    color,
+                    amounts: vec![0; columns.len()],
+                    total: 0,
+                });
+                rows.last_mut().unwrap()
+            }
+        };
+        row.amounts[index] += amount;
+        row.total += amount;
+    }
+    rows.sort_by_key(|r| std::cmp::Reverse(r.total));
+
+    let total = columns.iter().map(|c| c.total).sum();
+
+    Ok(SpendingMatrix {
+        group_by,
+        columns,
+        rows,
+        total,
+    })
+}
+
+#[tauri::command]
+pub fn get_cash_flow(
+    start_date: String,
+    end_date: String,
+    group_by: String,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<CashFlowData>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    // Weeks depend on the week settings, so they're gathered by day and grouped below
+    let period_expr = match group_by.as_str() {
+        "day" | "week" => "t.date",
+        "month" => "substr(t.date, 1, 7)",
+        _ => {
+            return Err(AppError::Validation(
+                "Invalid grouping. Use day, week, or month".to_string(),
+            ))
+        }
+    };
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} AS period,
+                COALESCE(SUM(CASE WHEN t.amount > 0 THEN {amount} ELSE 0 END), 0),
+                COALESCE(SUM(CASE WHEN t.amount < 0 THEN -{amount} ELSE 0 END), 0)
+         FROM transactions t
+         WHERE {}
+           AND t.date >= ?1
+           AND t.date <= ?2
+         GROUP BY period
+         ORDER BY period",
+        period_expr,
+        reportable_transaction_filter(),
+        amount = transaction_base_amount()
+    ))?;
+
+    let cash_flow: Vec<CashFlowData> = stmt
+        .query_map([&start_date, &end_date], |row| {
+            let income: i64 = row.get(1)?;
+            let expenses: i64 = row.get(2)?;
+            Ok(CashFlowData {
+                period: row.get(0)?,
+                income,
+                expenses,
+                net: income - expenses,
+            })
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    if group_by != "week" {
+        return Ok(cash_flow);
+    }
+
+    // Days arrive in order, so each week's days are next to each other
+    let weeks = WeekSettings::read(conn)?;
+    let mut weekly: Vec<CashFlowData> = Vec::new();
+    for day in cash_flow {
+        let period = NaiveDate::parse_from_str(&day.period, "%Y-%m-%d")
+            .map(|d| weeks.label(d))
+            .unwrap_or(day.period);
+        match weekly.last_mut() {
+            Some(week) if week.period == period => {
+                week.income += day.income;
+                week.expenses += day.expenses;
+                week.net += day.net;
+            }
+            _ => weekly.push(CashFlowData { period, ..day }),
+        }
+    }
+
+    Ok(weekly)
+}
+
+#[tauri::command]
+pub fn get_rate_comparison(db: State<'_, Mutex<Database>>) -> Result<RateComparisonReport> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    // User-entered reference rates live in settings, falling back to the bundled table
+    let reference_rate = |key: &str, default: f64| -> Result<f64> {
+        Ok(read_setting(conn, key)?
+            .and_then(|v| v.parse::<f64>().ok())
+            .unwrap_or(default))
+    };
+    let savings_reference_rate = reference_rate("reference_rate_savings", DEFAULT_SAVINGS_REFERENCE_APY)?;
+    let credit_card_reference_rate =
+        reference_rate("reference_rate_credit_card", DEFAULT_CREDIT_CARD_REFERENCE_APR)?;
+
+    let mut stmt = conn.prepare(
+        "SELECT id, name, account_type, current_balance, interest_rate
+         FROM accounts
+         WHERE deleted_at IS NULL
+           AND is_active = 1
+           AND interest_rate IS NOT NULL
+           AND account_type IN ('savings', 'credit_card')
+         ORDER BY display_order, name",
+    )?;
+
+    let rows: Vec<(String, String, String, i64, f64)> = stmt
+        .query_map([], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?))
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let mut accounts: Vec<RateComparison> = rows
+        .into_iter()
+        .map(|(account_id, account_name, account_type, balance, account_rate)| {
+            // Savings lose out when earning below the reference APY; cards lose out when
+            // the balance owed is charged above the reference APR
+            let (reference_rate, principal, rate_gap) = if account_type == "savings" {
+                (savings_reference_rate, balance.max(0), savings_reference_rate - account_rate)
+            } else {
+                (
+                    credit_card_reference_rate,
+                    (-balance).max(0),
+                    account_rate - credit_card_reference_rate,
+                )
+            };
+
+            let annual_opportunity = if rate_gap > 0.0 {
+                (principal as f64 * rate_gap / 100.0).round() as i64
+            } else {
+                0
+            };
+
+            RateComparison {
+                account_id,
+                account_name,
+                account_type,
+                balance,
+                account_rate,
+                reference_rate,
+                annual_opportunity,
+            }
+        })
+        .collect();
+
+    accounts.sort_by_key(|a| std::cmp::Reverse(a.annual_opportunity));
+
+    let total_annual_opportunity = accounts.iter().map(|a| a.annual_opportunity).sum();
+
+    Ok(RateComparisonReport {
+        savings_reference_rate,
+        credit_card_reference_rate,
+        accounts,
+        total_annual_opportunity,
+    })
+}
+
+/// Everything the dashboard shows on startup, read in one call: account balances and totals,
+/// month-to-date spending by category, recurring bills due soon, and the latest transactions
+#[tauri::command]
+pub fn get_dashboard_summary(db: State<'_, Mutex<Database>>) -> Result<DashboardSummary> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let today = chrono::Local::now().date_naive();
+    let today_str = today.format("%Y-%m-%d").to_string();
+    let month_start = today.format("%Y-%m-01").to_string();
+    let bills_until = (today + chrono::Duration::days(DASHBOARD_UPCOMING_BILL_DAYS))
+        .format("%Y-%m-%d")
+        .to_string();
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM accounts WHERE deleted_at IS NULL ORDER BY display_order, name",
+        account_columns()
+    ))?;
+    let accounts: Vec<Account> = stmt
+        .query_map([], account_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let (total_assets, total_liabilities) = asset_liability_totals(
+        accounts
+            .iter()
+            .filter(|a| a.is_active)
+            .map(|a| (a.account_type.as_str(), a.base_balance)),
+    );
+
+    let account_groups = net_worth_by_group(conn, &accounts)?;
+    let month_to_date_spending = spending_by_category(conn, &month_start, &today_str, false)?;
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM recurring_transactions
+         WHERE is_active = 1
+           AND amount < 0
+           AND next_expected_date IS NOT NULL
+           AND next_expected_date <= ?1
+         ORDER BY next_expected_date",
+        RECURRING_COLUMNS
+    ))?;
+    let upcoming_bills = stmt
+        .query_map([&bills_until], recurring_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM transactions
+         WHERE deleted_at IS NULL
+         ORDER BY date DESC, created_at DESC
+         LIMIT ?1",
+        TRANSACTION_COLUMNS
+    ))?;
+    let recent_transactions = stmt
+        .query_map([DASHBOARD_RECENT_TRANSACTIONS], transaction_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(DashboardSummary {
+        accounts,
+        base_currency: base_currency(conn)?,
+        missing_fx_currencies: missing_fx_currencies(conn)?,
+        total_assets,
+        total_liabilities,
+        net_worth: total_assets - total_liabilities,
+        account_groups,
+        month_to_date_spending,
+        upcoming_bills,
+        recent_transactions,
+    })
+}
+
+/// First and last date of a `YYYY` or `YYYY-MM` period, with its length in months
+fn period_bounds(period: &str) -> Result<(String, String, i64)> {
+    let invalid = || AppError::Validation(format!("Invalid period: {}. Use YYYY or YYYY-MM", period));
+    let (start, months) = match period.len() {
+        4 => (format!("{}-01-01", period), 12),
+        7 => (format!("{}-01", period), 1),
+        _ => return Err(invalid()),
+    };
+    let start = chrono::NaiveDate::parse_from_str(&start, "%Y-%m-%d").map_err(|_| invalid())?;
+    let end = start
+        .checked_add_months(chrono::Months::new(months as u32))
+        .and_then(|d| d.pred_opt())
+        .ok_or_else(invalid)?;
+
+    Ok((
+        start.format("%Y-%m-%d").to_string(),
+        end.format("%Y-%m-%d").to_string(),
+        months,
+    ))
+}
+
+/// Compare what the same basket of essentials cost between two periods (`YYYY` or `YYYY-MM`).
+///
+/// The basket is the essential categories plus any category with an active recurring bill.
+/// Groceries and gas are compared per trip and recurring bills per charge, so the result
+/// tracks price changes rather than how often things were bought; other categories are
+/// compared per month. Each category's change is weighted by its share of period A's
+/// basket spending, and categories without spending in both periods are left out.
+#[tauri::command]
+pub fn get_personal_inflation(
+    period_a: String,
+    period_b: String,
+    db: State<'_, Mutex<Database>>,
+) -> Result<PersonalInflation> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let bounds_a = period_bounds(&period_a)?;
+    let bounds_b = period_bounds(&period_b)?;
+
+    let mut basket: Vec<(String, Option<String>)> = ESSENTIAL_CATEGORIES
+        .iter()
+        .map(|(id, unit)| (id.to_string(), unit.map(String::from)))
+        .collect();
+    let mut stmt = conn.prepare(
+        "SELECT DISTINCT category_id FROM recurring_transactions
+         WHERE is_active = 1 AND amount < 0 AND category_id IS NOT NULL",
+    )?;
+    let recurring_categories: Vec<String> = stmt
+        .query_map([], |row| row.get(0))?
+        .filter_map(|r| r.ok())
+        .collect();
+    for category_id in recurring_categories {
+        match basket.iter_mut().find(|(id, _)| *id == category_id) {
+            Some((_, unit)) => {
+                unit.get_or_insert_with(|| "charge".to_string());
+            }
+            None => basket.push((category_id, Some("charge".to_string()))),
+        }
+    }
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT COALESCE(SUM(-t.amount), 0), COUNT(*) FROM transactions t
+         WHERE {}
+           AND t.amount < 0
+           AND t.category_id = ?1
+           AND t.date >= ?2
+           AND t.date <= ?3",
+        reportable_transaction_filter()
+    ))?;
+    let mut spending = |category_id: &str, (start, end, _): &(String, String, i64)| {
+        stmt.query_row(rusqlite::params![category_id, start, end], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?))
+        })
+    };
+
+    let mut categories = Vec::new();
+    for (category_id, unit) in basket {
+        let (spending_a, count_a) = spending(&category_id, &bounds_a)?;
+        let (spending_b, count_b) = spending(&category_id, &bounds_b)?;
+        if spending_a <= 0 || spending_b <= 0 {
+            continue;
+        }
+
+        let (price_a, price_b) = match unit {
+            Some(_) => (
+                spending_a as f64 / count_a as f64,
+                spending_b as f64 / count_b as f64,
+            ),
+            None => (
+                spending_a as f64 / bounds_a.2 as f64,
+                spending_b as f64 / bounds_b.2 as f64,
+            ),
+        };
+
+        let category_name: String = conn
+            .query_row(
+                "SELECT name FROM categories WHERE id = ?1",
+                [&category_id],
+                |row| row.get(0),
+            )
+            .unwrap_or_else(|_| category_id.clone());
+
+        categories.push(InflationCategory {
+            category_id,
+            category_name,
+            spending_a,
+            spending_b,
+            count_a,
+            count_b,
+            unit,
+            change_percent: (price_b / price_a - 1.0) * 100.0,
+            weight: 0.0,
+        });
+    }
+
+    let basket_total: i64 = categories.iter().map(|c| c.spending_a).sum();
+    for category in &mut categories {
+        category.weight = category.spending_a as f64 / basket_total as f64;
+    }
+    categories.sort_by(|a, b| b.weight.total_cmp(&a.weight));
+
+    let inflation_percent = categories
+        .iter()
+        .map(|c| c.change_percent * c.weight)
+        .sum();
+
+    Ok(PersonalInflation {
+        period_a,
+        period_b,
+        categories,
+        inflation_percent,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_week_labels() {
+        // 2024-01-06 is a Saturday, 2024-01-07 a Sunday
+        let sunday = WeekSettings {
+            start: Weekday::Sun,
+            iso: false,
+        };
+        assert_eq!(sunday.label(date("2024-01-06")), "2023-12-31");
+        assert_eq!(sunday.label(date("2024-01-07")), "2024-01-07");
+
+        let monday = WeekSettings {
+            start: Weekday::Mon,
+            iso: false,
+        };
+        assert_eq!(monday.label(date("2024-01-07")), "2024-01-01");
+
+        // ISO weeks belong to the year their Thursday falls in
+        let iso = WeekSettings {
+            start: Weekday::Mon,
+            iso: true,
+        };
+        assert_eq!(iso.label(date("2024-12-30")), "2025-W01");
+        assert_eq!(iso.label(date("2021-01-03")), "2020-W53");
+    }
+}
@@ -0,0 +1,438 @@
+use crate::commands::reports::reportable_transaction_filter;
+use crate::commands::transactions::{transaction_from_row, TRANSACTION_COLUMNS};
+use crate::db::Database;
+use crate::error::{AppError, Result};
+use crate::events::{emit_changed, REWARDS_CHANGED};
+use crate::models::{RewardRule, Transaction};
+use rusqlite::Connection;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use tauri::{AppHandle, State};
+use uuid::Uuid;
+
+/// Words in a payee or memo that mark a credit as the issuer paying out rewards
+const REWARD_CREDIT_KEYWORDS: &[&str] = &[
+    "cash back",
+    "cashback",
+    "reward",
+    "statement credit",
+    "redemption",
+    "points",
+];
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RewardsPeriod {
+    pub period: String,
+    pub spend: i64,
+    pub estimated: i64,
+    pub posted: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryRewardEstimate {
+    pub category_id: Option<String>,
+    pub category_name: String,
+    pub rate: f64,
+    pub spend: i64,
+    pub estimated: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RewardsSummary {
+    pub account_id: String,
+    pub account_name: String,
+    pub estimated_total: i64,
+    pub posted_total: i64,
+    pub difference: i64,
+    pub periods: Vec<RewardsPeriod>,
+    pub categories: Vec<CategoryRewardEstimate>,
+    pub posted_credits: Vec<Transaction>,
+}
+
+fn reward_rule_from_row(row: &rusqlite::Row) -> rusqlite::Result<RewardRule> {
+    Ok(RewardRule {
+        id: row.get(0)?,
+        account_id: row.get(1)?,
+        category_id: row.get(2)?,
+        rate: row.get(3)?,
+        created_at: row.get(4)?,
+        updated_at: row.get(5)?,
+    })
+}
+
+fn fetch_reward_rule(conn: &Connection, id: &str) -> Result<RewardRule> {
+    conn.query_row(
+        "SELECT id, account_id, category_id, rate, created_at, updated_at
+         FROM reward_rules WHERE id = ?1",
+        [id],
+        reward_rule_from_row,
+    )
+    .map_err(|_| AppError::NotFound("Reward rule not found".to_string()))
+}
+
+fn validate_rate(rate: f64) -> Result<()> {
+    if (0.0..=100.0).contains(&rate) {
+        Ok(())
+    } else {
+        Err(AppError::Validation(
+            "Reward rate must be a percentage between 0 and 100".to_string(),
+        ))
+    }
+}
+
+fn is_reward_credit(transaction: &Transaction) -> bool {
+    let text = format!(
+        "{} {}",
+        transaction.payee.as_deref().unwrap_or(""),
+        transaction.memo.as_deref().unwrap_or("")
+    )
+    .to_lowercase();
+    REWARD_CREDIT_KEYWORDS.iter().any(|k| text.contains(k))
+}
+
+/// The rule that applies to a category: its own rule, then its parent's, then the base rate
+fn matching_rule<'a>(
+    rules: &'a [RewardRule],
+    category_id: Option<&str>,
+    parents: &HashMap<String, String>,
+) -> Option<&'a RewardRule> {
+    let parent_id = category_id
+        .and_then(|id| parents.get(id))
+        .map(|s| s.as_str());
+    [category_id, parent_id]
+        .into_iter()
+        .flatten()
+        .find_map(|id| rules.iter().find(|r| r.category_id.as_deref() == Some(id)))
+        .or_else(|| rules.iter().find(|r| r.category_id.is_none()))
+}
+
+#[tauri::command]
+pub fn list_reward_rules(
+    account_id: Option<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<RewardRule>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let mut stmt = conn.prepare(
+        "SELECT id, account_id, category_id, rate, created_at, updated_at
+         FROM reward_rules
+         WHERE ?1 IS NULL OR account_id = ?1
+         ORDER BY account_id, category_id IS NULL, rate DESC",
+    )?;
+
+    let rules = stmt
+        .query_map([account_id], reward_rule_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(rules)
+}
+
+#[tauri::command]
+pub fn create_reward_rule(
+    data: serde_json::Value,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<RewardRule> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let account_id = data["accountId"]
+        .as_str()
+        .ok_or_else(|| AppError::Validation("An account is required".to_string()))?;
+    let category_id = data["categoryId"].as_str();
+    let rate = data["rate"].as_f64().unwrap_or(0.0);
+    validate_rate(rate)?;
+
+    let duplicate: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM reward_rules WHERE account_id = ?1 AND category_id IS ?2)",
+        rusqlite::params![account_id, category_id],
+        |row| row.get(0),
+    )?;
+    if duplicate {
+        return Err(AppError::Validation(
+            "This card already has a reward rule for that category".to_string(),
+        ));
+    }
+
+    let id = Uuid::new_v4().to_string();
+    let now = chrono::Utc::now().to_rfc3339();
+
+    conn.execute(
+        "INSERT INTO reward_rules (id, account_id, category_id, rate, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?5)",
+        rusqlite::params![id, account_id, category_id, rate, now],
+    )?;
+
+    emit_changed(&app, &[REWARDS_CHANGED]);
+
+    fetch_reward_rule(conn, &id)
+}
+
+#[tauri::command]
+pub fn update_reward_rule(
+    id: String,
+    data: serde_json::Value,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<RewardRule> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let rate = data["rate"].as_f64();
+    if let Some(rate) = rate {
+        validate_rate(rate)?;
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+
+    conn.execute(
+        "UPDATE reward_rules SET rate = COALESCE(?1, rate), updated_at = ?2 WHERE id = ?3",
+        rusqlite::params![rate, now, id],
+    )?;
+
+    emit_changed(&app, &[REWARDS_CHANGED]);
+
+    fetch_reward_rule(conn, &id)
+}
+
+#[tauri::command]
+pub fn delete_reward_rule(
+    id: String,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<()> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    conn.execute("DELETE FROM reward_rules WHERE id = ?1", [&id])?;
+
+    emit_changed(&app, &[REWARDS_CHANGED]);
+
+    Ok(())
+}
+
+/// Estimate rewards earned per month on each card with reward rules, and compare them
+/// with the reward credits that actually posted to the card over the same dates
+#[tauri::command]
+pub fn get_rewards_summary(
+    start_date: String,
+    end_date: String,
+    account_id: Option<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<RewardsSummary>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    rewards_summary(conn, &start_date, &end_date, account_id.as_deref())
+}
+
+fn rewards_summary(
+    conn: &Connection,
+    start_date: &str,
+    end_date: &str,
+    account_id: Option<&str>,
+) -> Result<Vec<RewardsSummary>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, account_id, category_id, rate, created_at, updated_at
+         FROM reward_rules
+         WHERE ?1 IS NULL OR account_id = ?1",
+    )?;
+    let mut rules_by_account: BTreeMap<String, Vec<RewardRule>> = BTreeMap::new();
+    for rule in stmt
+        .query_map([&account_id], reward_rule_from_row)?
+        .filter_map(|r| r.ok())
+    {
+        rules_by_account
+            .entry(rule.account_id.clone())
+            .or_default()
+            .push(rule);
+    }
+
+    let mut stmt = conn.prepare("SELECT id, name, parent_id FROM categories")?;
+    let mut category_names: HashMap<String, String> = HashMap::new();
+    let mut parents: HashMap<String, String> = HashMap::new();
+    for (id, name, parent_id) in stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, Option<String>>(2)?,
+            ))
+        })?
+        .filter_map(|r| r.ok())
+    {
+        if let Some(parent_id) = parent_id {
+            parents.insert(id.clone(), parent_id);
+        }
+        category_names.insert(id, name);
+    }
+
+    let mut summaries = Vec::new();
+    for (account_id, rules) in rules_by_account {
+        let account_name: String = match conn.query_row(
+            "SELECT name FROM accounts WHERE id = ?1 AND deleted_at IS NULL",
+            [&account_id],
+            |row| row.get(0),
+        ) {
+            Ok(name) => name,
+            Err(_) => continue,
+        };
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM transactions t
+             WHERE {} AND t.account_id = ?1 AND t.date >= ?2 AND t.date <= ?3
+             ORDER BY t.date",
+            TRANSACTION_COLUMNS,
+            reportable_transaction_filter()
+        ))?;
+        let transactions: Vec<Transaction> = stmt
+            .query_map(
+                [account_id.as_str(), start_date, end_date],
+                transaction_from_row,
+            )?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        let mut periods: BTreeMap<String, RewardsPeriod> = BTreeMap::new();
+        let mut categories: BTreeMap<Option<String>, CategoryRewardEstimate> = BTreeMap::new();
+        let mut posted_credits = Vec::new();
+
+        for transaction in transactions {
+            let month = transaction.date.get(..7).unwrap_or("").to_string();
+            let period = periods
+                .entry(month.clone())
+                .or_insert_with(|| RewardsPeriod {
+                    period: month,
+                    spend: 0,
+                    estimated: 0,
+                    posted: 0,
+                });
+
+            if transaction.amount > 0 && is_reward_credit(&transaction) {
+                period.posted += transaction.amount;
+                posted_credits.push(transaction);
+                continue;
+            }
+
+            // Refunds and returns take back what the purchase earned, so other credits
+            // net against spend at the same rate
+            let rule = match matching_rule(&rules, transaction.category_id.as_deref(), &parents) {
+                Some(rule) => rule,
+                None => continue,
+            };
+            let spend = -transaction.amount;
+            let estimated = (spend as f64 * rule.rate / 100.0).round() as i64;
+
+            period.spend += spend;
+            period.estimated += estimated;
+
+            let estimate = categories
+                .entry(rule.category_id.clone())
+                .or_insert_with(|| CategoryRewardEstimate {
+                    category_id: rule.category_id.clone(),
+                    category_name: rule
+                        .category_id
+                        .as_ref()
+                        .and_then(|id| category_names.get(id).cloned())
+                        .unwrap_or_else(|| "Everything else".to_string()),
+                    rate: rule.rate,
+                    spend: 0,
+                    estimated: 0,
+                });
+            estimate.spend += spend;
+            estimate.estimated += estimated;
+        }
+
+        let periods: Vec<RewardsPeriod> = periods.into_values().collect();
+        let mut categories: Vec<CategoryRewardEstimate> = categories.into_values().collect();
+        categories.sort_by_key(|c| std::cmp::Reverse(c.estimated));
+
+        let estimated_total = periods.iter().map(|p| p.estimated).sum();
+        let posted_total = periods.iter().map(|p| p.posted).sum();
+
+        summaries.push(RewardsSummary {
+            account_id,
+            account_name,
+            estimated_total,
+            posted_total,
+            difference: posted_total - estimated_total,
+            periods,
+            categories,
+            posted_credits,
+        });
+    }
+
+    Ok(summaries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card() -> Connection {
+        let conn = Database::in_memory();
+        conn.execute_batch(
+            "INSERT INTO accounts (id, name, account_type) VALUES ('card', 'Card', 'credit_card');
+             INSERT INTO categories (id, name, category_type) VALUES ('dining', 'Dining', 'expense');
+             INSERT INTO reward_rules (id, account_id, category_id, rate)
+             VALUES ('dining-rule', 'card', 'dining', 3.0), ('base-rule', 'card', NULL, 1.0);",
+        )
+        .unwrap();
+        conn
+    }
+
+    fn add(conn: &Connection, date: &str, amount: i64, payee: &str, category_id: Option<&str>) {
+        conn.execute(
+            "INSERT INTO transactions (id, account_id, date, amount, payee, category_id)
+             VALUES (?1, 'card', ?2, ?3, ?4, ?5)",
+            rusqlite::params![Uuid::new_v4().to_string(), date, amount, payee, category_id],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_refunds_net_against_spend() {
+        let conn = card();
+        add(&conn, "2024-03-05", -10000, "Bistro", Some("dining"));
+        add(&conn, "2024-03-09", 4000, "Bistro", Some("dining"));
+        add(&conn, "2024-03-10", -5000, "Hardware Store", None);
+        add(&conn, "2024-03-12", 5000, "Hardware Store", None);
+
+        let summary = rewards_summary(&conn, "2024-03-01", "2024-03-31", None).unwrap();
+        assert_eq!(summary.len(), 1);
+        let period = &summary[0].periods[0];
+        assert_eq!(
+            (period.spend, period.estimated, period.posted),
+            (6000, 180, 0)
+        );
+
+        let dining = &summary[0].categories[0];
+        assert_eq!(dining.category_id.as_deref(), Some("dining"));
+        assert_eq!((dining.spend, dining.estimated), (6000, 180));
+        let base = &summary[0].categories[1];
+        assert_eq!((base.spend, base.estimated), (0, 0));
+        assert!(summary[0].posted_credits.is_empty());
+    }
+
+    #[test]
+    fn test_reward_credits_are_posted_not_netted() {
+        let conn = card();
+        add(&conn, "2024-03-05", -10000, "Bistro", Some("dining"));
+        add(&conn, "2024-03-20", 250, "CASH BACK REWARD", None);
+
+        let summary = rewards_summary(&conn, "2024-03-01", "2024-03-31", Some("card")).unwrap();
+        let period = &summary[0].periods[0];
+        assert_eq!(
+            (period.spend, period.estimated, period.posted),
+            (10000, 300, 250)
+        );
+        assert_eq!(summary[0].difference, -50);
+        assert_eq!(summary[0].posted_credits.len(), 1);
+    }
+}
@@ -0,0 +1,268 @@
+use crate::commands::goal_milestones::raise_goal_milestones;
+use crate::commands::goals::{
+    add_goal_contribution, fetch_goal, next_scheduled_date, scheduled_date, SCHEDULE_FREQUENCIES,
+};
+use crate::db::Database;
+use crate::error::{AppError, Result};
+use crate::events::{emit_changed, GOALS_CHANGED};
+use chrono::{Duration, NaiveDate};
+use rusqlite::{Connection, OptionalExtension};
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::{AppHandle, State};
+
+/// The round-up savings setup, along with the round-ups set aside since the last
+/// contribution
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoundUpSettings {
+    pub is_enabled: bool,
+    /// The goal round-ups are contributed to
+    pub goal_id: Option<String>,
+    /// Spending on these accounts is rounded up
+    pub account_ids: Vec<String>,
+    /// How often round-ups are contributed, as for contribution schedules
+    pub frequency: String,
+    pub start_date: String,
+    /// When the set-aside round-ups are next contributed
+    pub next_date: String,
+    pub pending_amount: i64,
+    pub pending_count: i64,
+}
+
+/// Set aside a round-up for each new spending transaction on the selected accounts, and
+/// drop pending round-ups of transactions that have since been deleted. Spending that
+/// comes to whole dollars has nothing to round up.
+fn collect_round_ups(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "DELETE FROM round_ups
+         WHERE contribution_id IS NULL
+           AND transaction_id IN (SELECT id FROM transactions WHERE deleted_at IS NOT NULL)",
+        [],
+    )?;
+    conn.execute(
+        "INSERT INTO round_ups (transaction_id, amount)
+         SELECT t.id, 100 - (-t.amount) % 100
+         FROM transactions t, round_up_settings s
+         WHERE s.is_enabled = 1
+           AND t.account_id IN (SELECT account_id FROM round_up_accounts)
+           AND t.amount < 0 AND (-t.amount) % 100 != 0
+           AND t.transfer_id IS NULL AND t.is_hold = 0 AND t.parent_transaction_id IS NULL
+           AND t.deleted_at IS NULL AND t.date >= s.start_date
+           AND t.id NOT IN (SELECT transaction_id FROM round_ups)",
+        [],
+    )?;
+    Ok(())
+}
+
+fn load_round_up_settings(conn: &Connection) -> Result<RoundUpSettings> {
+    let today = chrono::Local::now()
+        .date_naive()
+        .format("%Y-%m-%d")
+        .to_string();
+    let settings = conn
+        .query_row(
+            "SELECT is_enabled, goal_id, frequency, start_date, next_date
+             FROM round_up_settings WHERE id = 1",
+            [],
+            |row| {
+                Ok(RoundUpSettings {
+                    is_enabled: row.get(0)?,
+                    goal_id: row.get(1)?,
+                    account_ids: Vec::new(),
+                    frequency: row.get(2)?,
+                    start_date: row.get(3)?,
+                    next_date: row.get(4)?,
+                    pending_amount: 0,
+                    pending_count: 0,
+                })
+            },
+        )
+        .optional()?;
+    let mut settings = settings.unwrap_or(RoundUpSettings {
+        is_enabled: false,
+        goal_id: None,
+        account_ids: Vec::new(),
+        frequency: "weekly".to_string(),
+        start_date: today.clone(),
+        next_date: today,
+        pending_amount: 0,
+        pending_count: 0,
+    });
+
+    let mut stmt = conn.prepare("SELECT account_id FROM round_up_accounts ORDER BY account_id")?;
+    settings.account_ids = stmt
+        .query_map([], |row| row.get(0))?
+        .filter_map(|r| r.ok())
+        .collect();
+    (settings.pending_amount, settings.pending_count) = conn.query_row(
+        "SELECT COALESCE(SUM(amount), 0), COUNT(*) FROM round_ups WHERE contribution_id IS NULL",
+        [],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+
+    Ok(settings)
+}
+
+/// Contribute the set-aside round-ups to the goal once their date comes, as a single
+/// contribution covering any periods missed while the app was closed. Round-ups wait while
+/// the goal is achieved, archived or gone. Returns the amount contributed.
+pub(crate) fn contribute_round_ups(conn: &Connection, today: NaiveDate) -> Result<i64> {
+    collect_round_ups(conn)?;
+    let settings = load_round_up_settings(conn)?;
+    let parse = |date: &str| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok();
+    let (Some(start), Some(next)) = (parse(&settings.start_date), parse(&settings.next_date))
+    else {
+        return Ok(0);
+    };
+    if !settings.is_enabled || next > today {
+        return Ok(0);
+    }
+    let Some(goal) = settings
+        .goal_id
+        .and_then(|id| fetch_goal(conn, &id).ok())
+        .filter(|goal| !goal.is_achieved && !goal.is_archived)
+    else {
+        return Ok(0);
+    };
+
+    let tx = conn.unchecked_transaction()?;
+    if settings.pending_amount > 0 {
+        let contribution_id = add_goal_contribution(
+            &tx,
+            &goal.id,
+            settings.pending_amount,
+            &today.format("%Y-%m-%d").to_string(),
+            None,
+        )?;
+        tx.execute(
+            "UPDATE round_ups SET contribution_id = ?1 WHERE contribution_id IS NULL",
+            [&contribution_id],
+        )?;
+    }
+    let next_date = next_scheduled_date(start, &settings.frequency, today + Duration::days(1));
+    tx.execute(
+        "UPDATE round_up_settings SET next_date = ?1, updated_at = datetime('now') WHERE id = 1",
+        [next_date.format("%Y-%m-%d").to_string()],
+    )?;
+    tx.commit()?;
+
+    Ok(settings.pending_amount)
+}
+
+#[tauri::command]
+pub fn get_round_up_settings(db: State<'_, Mutex<Database>>) -> Result<RoundUpSettings> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    collect_round_ups(conn)?;
+    load_round_up_settings(conn)
+}
+
+/// Save the round-up setup. Turning round-ups on starts rounding up spending from today,
+/// with the first contribution one period later.
+#[tauri::command]
+pub fn set_round_up_settings(
+    data: serde_json::Value,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<RoundUpSettings> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let is_enabled = data["isEnabled"].as_bool().unwrap_or(false);
+    let goal_id = data["goalId"].as_str();
+    if let Some(goal_id) = goal_id {
+        fetch_goal(conn, goal_id)?;
+    }
+    let account_ids: Vec<String> = match &data["accountIds"] {
+        serde_json::Value::Null => Vec::new(),
+        ids => serde_json::from_value(ids.clone())
+            .map_err(|e| AppError::Validation(format!("Invalid accounts: {}", e)))?,
+    };
+    for account_id in &account_ids {
+        let exists: bool = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM accounts WHERE id = ?1 AND deleted_at IS NULL)",
+            [account_id],
+            |row| row.get(0),
+        )?;
+        if !exists {
+            return Err(AppError::NotFound("Account not found".to_string()));
+        }
+    }
+    if is_enabled && (goal_id.is_none() || account_ids.is_empty()) {
+        return Err(AppError::Validation(
+            "Choose a goal and at least one account to round up".to_string(),
+        ));
+    }
+    let frequency = data["frequency"].as_str().unwrap_or("weekly");
+    if !SCHEDULE_FREQUENCIES.contains(&frequency) {
+        return Err(AppError::Validation(format!(
+            "Invalid round-up frequency: {}",
+            frequency
+        )));
+    }
+
+    let previous = load_round_up_settings(conn)?;
+    let today = chrono::Local::now().date_naive();
+    let start = if is_enabled && !previous.is_enabled {
+        today
+    } else {
+        NaiveDate::parse_from_str(&previous.start_date, "%Y-%m-%d").unwrap_or(today)
+    };
+    // Changing the setup never contributes twice for the same date
+    let next_date = if start == today {
+        scheduled_date(start, frequency, 1)
+    } else {
+        let earliest = NaiveDate::parse_from_str(&previous.next_date, "%Y-%m-%d")
+            .map_or(today, |d| d.max(today));
+        next_scheduled_date(start, frequency, earliest)
+    };
+
+    conn.execute(
+        "INSERT INTO round_up_settings (id, goal_id, frequency, start_date, next_date,
+                is_enabled, updated_at)
+         VALUES (1, ?1, ?2, ?3, ?4, ?5, datetime('now'))
+         ON CONFLICT(id) DO UPDATE SET
+            goal_id = excluded.goal_id,
+            frequency = excluded.frequency,
+            start_date = excluded.start_date,
+            next_date = excluded.next_date,
+            is_enabled = excluded.is_enabled,
+            updated_at = excluded.updated_at",
+        rusqlite::params![
+            goal_id,
+            frequency,
+            start.format("%Y-%m-%d").to_string(),
+            next_date.format("%Y-%m-%d").to_string(),
+            is_enabled,
+        ],
+    )?;
+    conn.execute("DELETE FROM round_up_accounts", [])?;
+    for account_id in &account_ids {
+        conn.execute(
+            "INSERT INTO round_up_accounts (account_id) VALUES (?1)",
+            [account_id],
+        )?;
+    }
+
+    emit_changed(&app, &[GOALS_CHANGED]);
+
+    collect_round_ups(conn)?;
+    load_round_up_settings(conn)
+}
+
+/// Contribute set-aside round-ups if they're due. The same sweep runs on unlock.
+#[tauri::command]
+pub fn run_round_ups(app: AppHandle, db: State<'_, Mutex<Database>>) -> Result<i64> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let contributed = contribute_round_ups(conn, chrono::Local::now().date_naive())?;
+    if contributed > 0 {
+        raise_goal_milestones(&app, conn);
+        emit_changed(&app, &[GOALS_CHANGED]);
+    }
+
+    Ok(contributed)
+}
@@ -1,63 +1,851 @@
+use crate::commands::budget_alerts::raise_budget_alerts;
+use crate::commands::recurring::normalize_payee;
+use crate::commands::settings::read_setting;
+use crate::commands::transactions::{
+    change_transaction_status, insert_transaction, link_transaction_pair, status_rank,
+    transaction_from_row, TRANSACTION_COLUMNS,
+};
+use crate::commands::usage_stats::record_feature_use;
 use crate::db::Database;
-use crate::error::Result;
-use crate::models::CategoryRule;
+use crate::error::{AppError, Result};
+use crate::events::{emit_changed, ACCOUNTS_CHANGED, RULES_CHANGED, TRANSACTIONS_CHANGED};
+use crate::models::{CategoryRule, RuleAction, RuleCondition, Transaction};
+use rusqlite::{Connection, OptionalExtension};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::Mutex;
-use tauri::State;
+use tauri::{AppHandle, State};
 use uuid::Uuid;
 
+/// Columns selected for a full `CategoryRule`, in the order `rule_from_row` expects
+const RULE_COLUMNS: &str = "id, category_id, rule_type, pattern, amount_min, amount_max,
+    account_id, priority, is_active, created_at, updated_at, transfer_account_id, conditions,
+    actions, match_mode";
+
+/// Rule types a rule or a rule condition can match on
+const RULE_TYPES: &[&str] = &[
+    "payee_contains",
+    "payee_exact",
+    "payee_starts_with",
+    "payee_regex",
+    "memo_contains",
+    "amount_equals",
+    "check_number",
+    "transaction_type",
+    "atm_withdrawal",
+];
+
+/// How a compound rule's conditions combine: every one of them, or any one
+const RULE_MATCH_MODES: &[&str] = &["all", "any"];
+
+/// Edits a rule can make to a matching transaction besides setting its category
+const RULE_ACTIONS: &[&str] = &[
+    "rename_payee",
+    "append_note",
+    "add_tag",
+    "mark_transfer",
+    "set_status",
+];
+
+/// Category given to rule-created transfers when the rule doesn't name one
+const TRANSFER_CATEGORY_ID: &str = "cat_transfer";
+
+/// Matches returned by a rule preview when no limit is given
+const DEFAULT_PREVIEW_LIMIT: usize = 100;
+
+/// Categorized transactions a suggested rule has to match before it's worth proposing
+const MIN_SUGGESTION_MATCHES: usize = 3;
+
+/// Share of a suggested rule's categorized matches that must already be in its category
+const MIN_SUGGESTION_CONSISTENCY: f64 = 0.9;
+
+/// Shortest payee pattern worth suggesting; anything shorter matches too much
+const MIN_SUGGESTION_PATTERN_LEN: usize = 3;
+
+/// Phrases banks use for cash withdrawals, matched by the `atm_withdrawal` rule type
+const ATM_WITHDRAWAL_PHRASES: &[&str] = &["cash withdrawal", "cash wd", "atm wd", "withdrawal atm"];
+
+fn rule_from_row(row: &rusqlite::Row) -> rusqlite::Result<CategoryRule> {
+    Ok(CategoryRule {
+        id: row.get(0)?,
+        category_id: row.get(1)?,
+        rule_type: row.get(2)?,
+        pattern: row.get(3)?,
+        amount_min: row.get(4)?,
+        amount_max: row.get(5)?,
+        account_id: row.get(6)?,
+        priority: row.get(7)?,
+        is_active: row.get(8)?,
+        created_at: row.get(9)?,
+        updated_at: row.get(10)?,
+        transfer_account_id: row.get(11)?,
+        conditions: row
+            .get::<_, Option<String>>(12)?
+            .and_then(|json| serde_json::from_str(&json).ok()),
+        actions: row
+            .get::<_, Option<String>>(13)?
+            .and_then(|json| serde_json::from_str(&json).ok()),
+        match_mode: row.get(14)?,
+    })
+}
+
+fn fetch_rule(conn: &Connection, id: &str) -> Result<CategoryRule> {
+    conn.query_row(
+        &format!("SELECT {} FROM category_rules WHERE id = ?1", RULE_COLUMNS),
+        [id],
+        rule_from_row,
+    )
+    .map_err(|e| e.into())
+}
+
+fn validate_transfer_account(conn: &Connection, account_id: Option<&str>) -> Result<()> {
+    if let Some(account_id) = account_id {
+        let exists: bool = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM accounts WHERE id = ?1 AND deleted_at IS NULL)",
+            [account_id],
+            |row| row.get(0),
+        )?;
+        if !exists {
+            return Err(AppError::NotFound("Transfer account not found".to_string()));
+        }
+    }
+    Ok(())
+}
+
+fn validate_rule_category(conn: &Connection, category_id: Option<&str>) -> Result<()> {
+    if let Some(category_id) = category_id {
+        let is_archived: bool = conn
+            .query_row(
+                "SELECT is_archived FROM categories WHERE id = ?1",
+                [category_id],
+                |row| row.get(0),
+            )
+            .optional()?
+            .unwrap_or(false);
+        if is_archived {
+            return Err(AppError::Validation(
+                "Archived categories can't be rule targets".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Read and check a rule's `conditions` input. An empty list means no conditions.
+fn input_conditions(
+    conn: &Connection,
+    data: &serde_json::Value,
+) -> Result<Option<Vec<RuleCondition>>> {
+    if data["conditions"].is_null() {
+        return Ok(None);
+    }
+    let conditions: Vec<RuleCondition> = serde_json::from_value(data["conditions"].clone())
+        .map_err(|e| AppError::Validation(format!("Invalid rule conditions: {}", e)))?;
+
+    for condition in &conditions {
+        match condition.condition_type.as_str() {
+            "amount_between" => match (condition.amount_min, condition.amount_max) {
+                (None, None) => {
+                    return Err(AppError::Validation(
+                        "Amount conditions need a minimum or maximum".to_string(),
+                    ))
+                }
+                (Some(min), Some(max)) if min > max => {
+                    return Err(AppError::Validation(
+                        "Amount condition minimum is above its maximum".to_string(),
+                    ))
+                }
+                _ => {}
+            },
+            "account" => {
+                let exists: bool = conn.query_row(
+                    "SELECT EXISTS(SELECT 1 FROM accounts WHERE id = ?1 AND deleted_at IS NULL)",
+                    [condition.account_id.as_deref().unwrap_or("")],
+                    |row| row.get(0),
+                )?;
+                if !exists {
+                    return Err(AppError::NotFound(
+                        "Account in rule condition not found".to_string(),
+                    ));
+                }
+            }
+            rule_type if RULE_TYPES.contains(&rule_type) => {
+                validate_rule_pattern(rule_type, &condition.pattern)?
+            }
+            other => {
+                return Err(AppError::Validation(format!(
+                    "Unknown rule condition: {}",
+                    other
+                )))
+            }
+        }
+    }
+
+    Ok(if conditions.is_empty() {
+        None
+    } else {
+        Some(conditions)
+    })
+}
+
+/// Read and check a rule's `matchMode` input, if given
+fn input_match_mode(data: &serde_json::Value) -> Result<Option<&str>> {
+    match data["matchMode"].as_str() {
+        Some(mode) if !RULE_MATCH_MODES.contains(&mode) => Err(AppError::Validation(format!(
+            "Unknown rule match mode: {}",
+            mode
+        ))),
+        mode => Ok(mode),
+    }
+}
+
+/// Read and check a rule's `actions` input. An empty list means no actions.
+fn input_actions(conn: &Connection, data: &serde_json::Value) -> Result<Option<Vec<RuleAction>>> {
+    if data["actions"].is_null() {
+        return Ok(None);
+    }
+    let mut actions: Vec<RuleAction> = serde_json::from_value(data["actions"].clone())
+        .map_err(|e| AppError::Validation(format!("Invalid rule actions: {}", e)))?;
+
+    for action in &mut actions {
+        action.value = action.value.trim().to_string();
+        if !RULE_ACTIONS.contains(&action.action_type.as_str()) {
+            return Err(AppError::Validation(format!(
+                "Unknown rule action: {}",
+                action.action_type
+            )));
+        }
+        if action.value.is_empty() {
+            return Err(AppError::Validation(format!(
+                "Rule action {} needs a value",
+                action.action_type
+            )));
+        }
+        match action.action_type.as_str() {
+            "mark_transfer" => validate_transfer_account(conn, Some(&action.value))?,
+            "set_status" => {
+                status_rank(&action.value)?;
+            }
+            _ => {}
+        }
+    }
+
+    Ok(if actions.is_empty() {
+        None
+    } else {
+        Some(actions)
+    })
+}
+
+/// The fields of a transaction that rules look at
+struct RuleTarget {
+    id: String,
+    account_id: String,
+    date: String,
+    payee: Option<String>,
+    memo: Option<String>,
+    amount: i64,
+    transfer_id: Option<String>,
+    check_number: Option<String>,
+    transaction_type: Option<String>,
+}
+
+impl From<&Transaction> for RuleTarget {
+    fn from(t: &Transaction) -> Self {
+        RuleTarget {
+            id: t.id.clone(),
+            account_id: t.account_id.clone(),
+            date: t.date.clone(),
+            payee: t.payee.clone(),
+            memo: t.memo.clone(),
+            amount: t.amount,
+            transfer_id: t.transfer_id.clone(),
+            check_number: t.check_number.clone(),
+            transaction_type: t.transaction_type.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RulePreview {
+    pub match_count: usize,
+    /// Most recent matches first, up to the preview limit
+    pub transactions: Vec<Transaction>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleSuggestion {
+    /// Always `payee_contains`; with `pattern` and `category_id` it's ready for
+    /// `create_category_rule`
+    pub rule_type: String,
+    pub pattern: String,
+    pub category_id: String,
+    pub category_name: String,
+    /// Categorized transactions the pattern matches, and the share already in the category
+    pub match_count: usize,
+    pub consistency: f64,
+    /// Uncategorized transactions the rule would pick up
+    pub uncategorized_count: usize,
+    pub sample_payees: Vec<String>,
+}
+
+/// A rule as shared between databases. Categories and accounts are named rather than
+/// referenced by id, since ids only mean something in the database they came from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedRule {
+    /// Missing for rules that only run actions
+    #[serde(default)]
+    pub category: Option<String>,
+    /// Tells apart subcategories sharing a name
+    #[serde(default)]
+    pub parent_category: Option<String>,
+    pub rule_type: String,
+    #[serde(default)]
+    pub pattern: String,
+    #[serde(default)]
+    pub amount_min: Option<i64>,
+    #[serde(default)]
+    pub amount_max: Option<i64>,
+    #[serde(default)]
+    pub account: Option<String>,
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default = "default_true")]
+    pub is_active: bool,
+    #[serde(default)]
+    pub transfer_account: Option<String>,
+    #[serde(default)]
+    pub conditions: Option<Vec<ExportedCondition>>,
+    #[serde(default = "default_match_mode")]
+    pub match_mode: String,
+    /// `mark_transfer` values hold the account name
+    #[serde(default)]
+    pub actions: Option<Vec<RuleAction>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedCondition {
+    pub condition_type: String,
+    #[serde(default)]
+    pub pattern: String,
+    #[serde(default)]
+    pub amount_min: Option<i64>,
+    #[serde(default)]
+    pub amount_max: Option<i64>,
+    #[serde(default)]
+    pub account: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleBacktestEntry {
+    pub rule_id: String,
+    pub category_id: String,
+    /// Categorized transactions the rule matches, and how many are already in its category
+    pub match_count: usize,
+    pub agreements: usize,
+    pub conflicts: usize,
+    /// Matches a higher-priority rule categorizes first, so this rule never decides them
+    pub shadowed_count: usize,
+    /// Other rules matching some of the same transactions with a different category
+    pub competing_rule_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleBacktest {
+    pub transaction_count: usize,
+    /// Transactions some rule would categorize, and how the winning rule compares with
+    /// the category they already have
+    pub matched_count: usize,
+    pub agreement_count: usize,
+    pub conflict_count: usize,
+    /// In priority order
+    pub rules: Vec<RuleBacktestEntry>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleImportResult {
+    pub created: i32,
+    /// Rules identical to one already in the database
+    pub duplicates: i32,
+    /// Rules naming a category or account this database doesn't have, described for the user
+    pub skipped: Vec<String>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_match_mode() -> String {
+    "all".to_string()
+}
+
+/// Parse an `amount_equals` pattern, a dollar amount like `15.99` or `-2500`. Returns the
+/// amount in cents and whether it was signed; unsigned amounts match either direction.
+fn pattern_amount(pattern: &str) -> Option<(i64, bool)> {
+    let pattern = pattern.trim().replace([',', '$'], "");
+    let amount: f64 = pattern.parse().ok()?;
+    if !amount.is_finite() {
+        return None;
+    }
+    Some((
+        (amount * 100.0).round() as i64,
+        pattern.starts_with(['-', '+']),
+    ))
+}
+
+fn validate_rule_pattern(rule_type: &str, pattern: &str) -> Result<()> {
+    if rule_type == "amount_equals" && pattern_amount(pattern).is_none() {
+        return Err(AppError::Validation(format!(
+            "Amount rules need an amount to match, not \"{}\"",
+            pattern
+        )));
+    }
+    if rule_type == "payee_regex" {
+        regex::Regex::new(pattern)
+            .map_err(|e| AppError::Validation(format!("Invalid regex: {}", e)))?;
+    }
+    Ok(())
+}
+
+fn is_atm_withdrawal(target: &RuleTarget) -> bool {
+    if target.amount >= 0 {
+        return false;
+    }
+    let text = format!(
+        "{} {}",
+        target.payee.as_deref().unwrap_or(""),
+        target.memo.as_deref().unwrap_or("")
+    )
+    .to_lowercase();
+
+    text.split(|c: char| !c.is_alphanumeric()).any(|w| w == "atm")
+        || ATM_WITHDRAWAL_PHRASES.iter().any(|p| text.contains(p))
+}
+
+/// Whether a transaction matches a single rule type and pattern
+fn pattern_matches(rule_type: &str, raw_pattern: &str, target: &RuleTarget) -> bool {
+    let pattern = raw_pattern.to_lowercase();
+    match (rule_type, &target.payee) {
+        ("payee_contains", Some(payee)) => payee.to_lowercase().contains(&pattern),
+        ("payee_exact", Some(payee)) => payee.to_lowercase() == pattern,
+        ("payee_starts_with", Some(payee)) => payee.to_lowercase().starts_with(&pattern),
+        ("payee_regex", Some(payee)) => regex::Regex::new(raw_pattern)
+            .map(|re| re.is_match(payee))
+            .unwrap_or(false),
+        ("memo_contains", _) => target
+            .memo
+            .as_deref()
+            .is_some_and(|memo| memo.to_lowercase().contains(&pattern)),
+        ("amount_equals", _) => match pattern_amount(raw_pattern) {
+            Some((amount, true)) => target.amount == amount,
+            Some((amount, false)) => target.amount.abs() == amount.abs(),
+            None => false,
+        },
+        // An empty pattern matches any check
+        ("check_number", _) => target.check_number.as_deref().is_some_and(|number| {
+            let number = number.trim();
+            !number.is_empty() && (pattern.is_empty() || number == pattern.trim())
+        }),
+        ("transaction_type", _) => target
+            .transaction_type
+            .as_deref()
+            .is_some_and(|t| t.eq_ignore_ascii_case(pattern.trim())),
+        ("atm_withdrawal", _) => {
+            is_atm_withdrawal(target)
+                && (pattern.is_empty()
+                    || target
+                        .payee
+                        .as_deref()
+                        .is_some_and(|p| p.to_lowercase().contains(&pattern)))
+        }
+        _ => false,
+    }
+}
+
+fn condition_matches(condition: &RuleCondition, target: &RuleTarget) -> bool {
+    match condition.condition_type.as_str() {
+        "amount_between" => {
+            condition.amount_min.is_none_or(|min| target.amount >= min)
+                && condition.amount_max.is_none_or(|max| target.amount <= max)
+        }
+        "account" => condition.account_id.as_deref() == Some(target.account_id.as_str()),
+        rule_type => pattern_matches(rule_type, &condition.pattern, target),
+    }
+}
+
+fn rule_matches(rule: &CategoryRule, target: &RuleTarget) -> bool {
+    // Check account filter
+    if let Some(acc_id) = &rule.account_id {
+        if acc_id != &target.account_id {
+            return false;
+        }
+    }
+
+    // Check amount range
+    if rule.amount_min.is_some_and(|min| target.amount < min) {
+        return false;
+    }
+    if rule.amount_max.is_some_and(|max| target.amount > max) {
+        return false;
+    }
+
+    // Compound rules need every condition, or any one of them in "any" mode; otherwise
+    // check the rule's own pattern
+    match &rule.conditions {
+        Some(conditions) if !conditions.is_empty() => {
+            if rule.match_mode == "any" {
+                conditions.iter().any(|c| condition_matches(c, target))
+            } else {
+                conditions.iter().all(|c| condition_matches(c, target))
+            }
+        }
+        _ => pattern_matches(&rule.rule_type, &rule.pattern, target),
+    }
+}
+
+/// The pattern a group of payees has in common: their shared lowercase prefix, cut back
+/// to whole words and stripped of trailing punctuation (`starbucks #` -> `starbucks`)
+fn common_payee_pattern(payees: &[&str]) -> String {
+    let lowered: Vec<String> = payees.iter().map(|p| p.to_lowercase()).collect();
+    let Some(first) = lowered.first() else {
+        return String::new();
+    };
+
+    let mut prefix_len = first.len();
+    for payee in &lowered[1..] {
+        let common = first
+            .char_indices()
+            .zip(payee.chars())
+            .find(|((_, a), b)| a != b)
+            .map_or(first.len().min(payee.len()), |((i, _), _)| i);
+        prefix_len = prefix_len.min(common);
+    }
+    let mut prefix = &first[..prefix_len];
+
+    // Don't stop partway through a word in any payee
+    let splits_word = lowered.iter().any(|p| {
+        p[prefix.len()..]
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_alphanumeric())
+            && prefix.chars().last().is_some_and(|c| c.is_alphanumeric())
+    });
+    if splits_word {
+        prefix = prefix
+            .rfind(char::is_whitespace)
+            .map_or("", |i| &prefix[..i]);
+    }
+
+    prefix
+        .trim_end_matches(|c: char| !c.is_alphanumeric())
+        .trim_start()
+        .to_string()
+}
+
+/// The category the highest-priority active rule gives a transaction, if any rule that
+/// sets a category matches it
+pub(crate) fn matching_rule_category(
+    conn: &Connection,
+    transaction: &Transaction,
+) -> Result<Option<String>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM category_rules
+         WHERE is_active = 1 AND category_id != ''
+           AND category_id NOT IN (SELECT id FROM categories WHERE is_archived = 1)
+         ORDER BY priority DESC",
+        RULE_COLUMNS
+    ))?;
+    let target = RuleTarget::from(transaction);
+    let category_id = stmt
+        .query_map([], rule_from_row)?
+        .filter_map(|r| r.ok())
+        .find(|rule| rule_matches(rule, &target))
+        .map(|rule| rule.category_id);
+
+    Ok(category_id)
+}
+
+/// Turn a transaction into a transfer with another account (e.g. an ATM withdrawal into
+/// the cash wallet) by recording the opposite side there and linking the pair.
+/// Returns false when the transaction can't become a transfer.
+fn apply_transfer_action(
+    conn: &Connection,
+    target: &mut RuleTarget,
+    transfer_account_id: &str,
+    category_id: &str,
+) -> Result<bool> {
+    if target.transfer_id.is_some()
+        || target.amount == 0
+        || target.account_id == transfer_account_id
+    {
+        return Ok(false);
+    }
+
+    let counterpart_id = insert_transaction(
+        conn,
+        &serde_json::json!({
+            "accountId": transfer_account_id,
+            "date": target.date,
+            "amount": -target.amount,
+            "payee": target.payee,
+            "categoryId": category_id,
+            "importSource": "rule",
+        }),
+    )?;
+    target.transfer_id = Some(link_transaction_pair(conn, &target.id, &counterpart_id)?);
+
+    Ok(true)
+}
+
+/// Make a rule's edits to a matching transaction. A status the transaction can't move to
+/// from its current one is left alone rather than failing the whole run.
+fn apply_rule_actions(
+    conn: &Connection,
+    target: &mut RuleTarget,
+    rule: &CategoryRule,
+    now: &str,
+) -> Result<()> {
+    for action in rule.actions.iter().flatten() {
+        match action.action_type.as_str() {
+            "rename_payee" => {
+                conn.execute(
+                    "UPDATE transactions SET
+                        original_payee = COALESCE(original_payee, payee),
+                        payee = ?1,
+                        updated_at = ?2
+                     WHERE id = ?3",
+                    rusqlite::params![action.value, now, target.id],
+                )?;
+            }
+            "append_note" => {
+                // Skip notes already there so re-running rules doesn't repeat them
+                conn.execute(
+                    "UPDATE transactions SET
+                        notes = CASE
+                            WHEN notes IS NULL OR notes = '' THEN ?1
+                            WHEN instr(notes, ?1) > 0 THEN notes
+                            ELSE notes || char(10) || ?1
+                        END,
+                        updated_at = ?2
+                     WHERE id = ?3",
+                    rusqlite::params![action.value, now, target.id],
+                )?;
+            }
+            "add_tag" => {
+                let tags: Option<String> = conn.query_row(
+                    "SELECT tags FROM transactions WHERE id = ?1",
+                    [&target.id],
+                    |row| row.get(0),
+                )?;
+                let mut tags: Vec<String> = tags
+                    .and_then(|json| serde_json::from_str(&json).ok())
+                    .unwrap_or_default();
+                if !tags.contains(&action.value) {
+                    tags.push(action.value.clone());
+                    conn.execute(
+                        "UPDATE transactions SET tags = ?1, updated_at = ?2 WHERE id = ?3",
+                        rusqlite::params![serde_json::to_string(&tags)?, now, target.id],
+                    )?;
+                }
+            }
+            "mark_transfer" => {
+                let category_id = if rule.category_id.is_empty() {
+                    TRANSFER_CATEGORY_ID
+                } else {
+                    &rule.category_id
+                };
+                apply_transfer_action(conn, target, &action.value, category_id)?;
+            }
+            "set_status" => match change_transaction_status(conn, &target.id, &action.value, now) {
+                Err(AppError::Validation(_)) => {}
+                other => {
+                    other?;
+                }
+            },
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Setting that runs category rules on newly imported transactions and on ones an edit
+/// leaves uncategorized. On unless set to "false".
+const AUTO_APPLY_RULES_SETTING: &str = "auto_apply_category_rules";
+
+pub(crate) fn auto_apply_rules_enabled(conn: &Connection) -> Result<bool> {
+    Ok(read_setting(conn, AUTO_APPLY_RULES_SETTING)?.as_deref() != Some("false"))
+}
+
+/// Run active rules, highest priority first, over the given transactions (or every
+/// uncategorized transaction). Rules targeting an archived category are skipped. The first
+/// matching rule with a category sets it, turns a withdrawal into a transfer when the rule
+/// has a transfer account, and makes the rule's other edits; matching action-only rules
+/// above it make their edits too. Returns how many transactions a rule matched.
+pub(crate) fn apply_rules(
+    conn: &Connection,
+    transaction_ids: Option<&[String]>,
+    uncategorized_only: bool,
+) -> Result<i32> {
+    // Get all active rules ordered by priority
+    let mut rules_stmt = conn.prepare(&format!(
+        "SELECT {} FROM category_rules
+         WHERE is_active = 1
+           AND category_id NOT IN (SELECT id FROM categories WHERE is_archived = 1)
+         ORDER BY priority DESC",
+        RULE_COLUMNS
+    ))?;
+
+    let rules: Vec<CategoryRule> = rules_stmt
+        .query_map([], rule_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    if rules.is_empty() {
+        return Ok(0);
+    }
+
+    let mut tx_query = "SELECT id, account_id, date, payee, memo, amount, transfer_id,
+                check_number, transaction_type
+         FROM transactions
+         WHERE deleted_at IS NULL"
+        .to_string();
+    if let Some(ids) = transaction_ids {
+        if ids.is_empty() {
+            return Ok(0);
+        }
+        let placeholders: Vec<String> = ids.iter().map(|_| "?".to_string()).collect();
+        tx_query.push_str(&format!(" AND id IN ({})", placeholders.join(", ")));
+    }
+    if uncategorized_only || transaction_ids.is_none() {
+        tx_query.push_str(" AND category_id IS NULL");
+    }
+
+    let mut tx_stmt = conn.prepare(&tx_query)?;
+
+    let transactions: Vec<RuleTarget> = tx_stmt
+        .query_map(
+            rusqlite::params_from_iter(transaction_ids.unwrap_or_default()),
+            |row| {
+                Ok(RuleTarget {
+                    id: row.get(0)?,
+                    account_id: row.get(1)?,
+                    date: row.get(2)?,
+                    payee: row.get(3)?,
+                    memo: row.get(4)?,
+                    amount: row.get(5)?,
+                    transfer_id: row.get(6)?,
+                    check_number: row.get(7)?,
+                    transaction_type: row.get(8)?,
+                })
+            },
+        )?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let mut categorized_count = 0;
+
+    for mut target in transactions {
+        // Action-only rules make their edits and let lower-priority rules still
+        // categorize; the first rule with a category is the last one applied
+        let mut matched = false;
+        for rule in &rules {
+            if !rule_matches(rule, &target) {
+                continue;
+            }
+            matched = true;
+
+            if let Some(transfer_account_id) = &rule.transfer_account_id {
+                if target.amount < 0 {
+                    apply_transfer_action(
+                        conn,
+                        &mut target,
+                        transfer_account_id,
+                        &rule.category_id,
+                    )?;
+                }
+            }
+            apply_rule_actions(conn, &mut target, rule, &now)?;
+
+            if !rule.category_id.is_empty() {
+                conn.execute(
+                    "UPDATE transactions SET category_id = ?1, updated_at = ?2 WHERE id = ?3",
+                    rusqlite::params![rule.category_id, now, target.id],
+                )?;
+                break;
+            }
+        }
+        if !matched {
+            continue;
+        }
+        categorized_count += 1;
+    }
+
+    Ok(categorized_count)
+}
+
 #[tauri::command]
 pub fn list_category_rules(db: State<'_, Mutex<Database>>) -> Result<Vec<CategoryRule>> {
     let database = db.lock().unwrap();
     let conn = database.get_connection()?;
 
-    let mut stmt = conn.prepare(
-        "SELECT id, category_id, rule_type, pattern, amount_min, amount_max,
-                account_id, priority, is_active, created_at, updated_at
-         FROM category_rules
-         ORDER BY priority DESC, created_at DESC"
-    )?;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM category_rules
+         ORDER BY priority DESC, created_at DESC",
+        RULE_COLUMNS
+    ))?;
 
     let rules = stmt
-        .query_map([], |row| {
-            Ok(CategoryRule {
-                id: row.get(0)?,
-                category_id: row.get(1)?,
-                rule_type: row.get(2)?,
-                pattern: row.get(3)?,
-                amount_min: row.get(4)?,
-                amount_max: row.get(5)?,
-                account_id: row.get(6)?,
-                priority: row.get(7)?,
-                is_active: row.get(8)?,
-                created_at: row.get(9)?,
-                updated_at: row.get(10)?,
-            })
-        })?
+        .query_map([], rule_from_row)?
         .filter_map(|r| r.ok())
         .collect();
 
     Ok(rules)
 }
 
-#[tauri::command]
-pub fn create_category_rule(
-    data: serde_json::Value,
-    db: State<'_, Mutex<Database>>,
-) -> Result<CategoryRule> {
-    let database = db.lock().unwrap();
-    let conn = database.get_connection()?;
+/// Check a new rule's input and insert it, returning the new id
+fn insert_rule(conn: &Connection, data: &serde_json::Value) -> Result<String> {
+    let transfer_account_id = data["transferAccountId"].as_str();
+    validate_transfer_account(conn, transfer_account_id)?;
+
+    // Rules that move money into another account default to the Transfer category
+    let category_id = match (data["categoryId"].as_str(), transfer_account_id) {
+        (Some(category_id), _) => category_id,
+        (None, Some(_)) => TRANSFER_CATEGORY_ID,
+        (None, None) => "",
+    };
+    validate_rule_category(conn, Some(category_id))?;
+    let rule_type = data["ruleType"].as_str().unwrap_or("payee_contains");
+    let pattern = data["pattern"].as_str().unwrap_or("");
+    validate_rule_pattern(rule_type, pattern)?;
+    let conditions = input_conditions(conn, data)?;
+    let match_mode = input_match_mode(data)?.unwrap_or("all");
+    let actions = input_actions(conn, data)?;
+    if category_id.is_empty() && actions.is_none() {
+        return Err(AppError::Validation(
+            "Rules need a category or at least one action".to_string(),
+        ));
+    }
 
     let id = Uuid::new_v4().to_string();
     let now = chrono::Utc::now().to_rfc3339();
 
     conn.execute(
-        "INSERT INTO category_rules (id, category_id, rule_type, pattern, amount_min, amount_max, account_id, priority, is_active, created_at, updated_at)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+        "INSERT INTO category_rules (id, category_id, rule_type, pattern, amount_min, amount_max, account_id, priority, is_active, created_at, updated_at, transfer_account_id, conditions, actions, match_mode)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
         rusqlite::params![
             id,
-            data["categoryId"].as_str().unwrap_or(""),
-            data["ruleType"].as_str().unwrap_or("payee_contains"),
-            data["pattern"].as_str().unwrap_or(""),
+            category_id,
+            rule_type,
+            pattern,
             data["amountMin"].as_i64(),
             data["amountMax"].as_i64(),
             data["accountId"].as_str(),
@@ -65,42 +853,54 @@ pub fn create_category_rule(
             data["isActive"].as_bool().unwrap_or(true),
             now,
             now,
+            transfer_account_id,
+            conditions.as_ref().map(serde_json::to_string).transpose()?,
+            actions.as_ref().map(serde_json::to_string).transpose()?,
+            match_mode,
         ],
     )?;
 
-    conn.query_row(
-        "SELECT id, category_id, rule_type, pattern, amount_min, amount_max,
-                account_id, priority, is_active, created_at, updated_at
-         FROM category_rules WHERE id = ?1",
-        [&id],
-        |row| {
-            Ok(CategoryRule {
-                id: row.get(0)?,
-                category_id: row.get(1)?,
-                rule_type: row.get(2)?,
-                pattern: row.get(3)?,
-                amount_min: row.get(4)?,
-                amount_max: row.get(5)?,
-                account_id: row.get(6)?,
-                priority: row.get(7)?,
-                is_active: row.get(8)?,
-                created_at: row.get(9)?,
-                updated_at: row.get(10)?,
-            })
-        },
-    )
-    .map_err(|e| e.into())
+    Ok(id)
+}
+
+#[tauri::command]
+pub fn create_category_rule(
+    data: serde_json::Value,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<CategoryRule> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let id = insert_rule(conn, &data)?;
+
+    emit_changed(&app, &[RULES_CHANGED]);
+
+    fetch_rule(conn, &id)
 }
 
 #[tauri::command]
 pub fn update_category_rule(
     id: String,
     data: serde_json::Value,
+    app: AppHandle,
     db: State<'_, Mutex<Database>>,
 ) -> Result<CategoryRule> {
     let database = db.lock().unwrap();
     let conn = database.get_connection()?;
 
+    validate_transfer_account(conn, data["transferAccountId"].as_str())?;
+    validate_rule_category(conn, data["categoryId"].as_str())?;
+    let existing = fetch_rule(conn, &id)
+        .map_err(|_| AppError::NotFound("Rule not found".to_string()))?;
+    validate_rule_pattern(
+        data["ruleType"].as_str().unwrap_or(&existing.rule_type),
+        data["pattern"].as_str().unwrap_or(&existing.pattern),
+    )?;
+    let conditions = input_conditions(conn, &data)?;
+    let match_mode = input_match_mode(&data)?;
+    let actions = input_actions(conn, &data)?;
+
     let now = chrono::Utc::now().to_rfc3339();
 
     conn.execute(
@@ -113,8 +913,12 @@ pub fn update_category_rule(
             account_id = ?6,
             priority = COALESCE(?7, priority),
             is_active = COALESCE(?8, is_active),
-            updated_at = ?9
-         WHERE id = ?10",
+            transfer_account_id = ?9,
+            conditions = CASE WHEN ?10 THEN ?11 ELSE conditions END,
+            actions = CASE WHEN ?12 THEN ?13 ELSE actions END,
+            match_mode = COALESCE(?16, match_mode),
+            updated_at = ?14
+         WHERE id = ?15",
         rusqlite::params![
             data["categoryId"].as_str(),
             data["ruleType"].as_str(),
@@ -124,193 +928,700 @@ pub fn update_category_rule(
             data["accountId"].as_str(),
             data["priority"].as_i64().map(|v| v as i32),
             data["isActive"].as_bool(),
+            data["transferAccountId"].as_str(),
+            data.get("conditions").is_some(),
+            conditions.as_ref().map(serde_json::to_string).transpose()?,
+            data.get("actions").is_some(),
+            actions.as_ref().map(serde_json::to_string).transpose()?,
             now,
             id,
+            match_mode,
         ],
     )?;
 
+    emit_changed(&app, &[RULES_CHANGED]);
+
+    fetch_rule(conn, &id)
+}
+
+#[tauri::command]
+pub fn delete_category_rule(
+    id: String,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<()> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    conn.execute("DELETE FROM category_rules WHERE id = ?1", [&id])?;
+
+    emit_changed(&app, &[RULES_CHANGED]);
+
+    Ok(())
+}
+
+/// Set rule priorities from their order in one transaction, first rule checked first.
+/// Rules left out of the list keep their priority.
+#[tauri::command]
+pub fn reorder_category_rules(
+    ordered_ids: Vec<String>,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<()> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let unique: std::collections::HashSet<&String> = ordered_ids.iter().collect();
+    if unique.len() != ordered_ids.len() {
+        return Err(AppError::Validation(
+            "Rule order lists a rule more than once".to_string(),
+        ));
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+
+    let tx = conn.unchecked_transaction()?;
+    for (position, id) in ordered_ids.iter().enumerate() {
+        let updated = tx.execute(
+            "UPDATE category_rules SET priority = ?1, updated_at = ?2 WHERE id = ?3",
+            rusqlite::params![(ordered_ids.len() - position) as i32, now, id],
+        )?;
+        if updated == 0 {
+            return Err(AppError::NotFound(format!("Rule not found: {}", id)));
+        }
+    }
+    tx.commit()?;
+
+    emit_changed(&app, &[RULES_CHANGED]);
+
+    Ok(())
+}
+
+/// Export every rule as JSON for the frontend to save, in priority order
+#[tauri::command]
+pub fn export_category_rules(db: State<'_, Mutex<Database>>) -> Result<String> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let mut stmt = conn.prepare(
+        "SELECT c.id, c.name, p.name FROM categories c
+         LEFT JOIN categories p ON c.parent_id = p.id",
+    )?;
+    let categories: HashMap<String, (String, Option<String>)> = stmt
+        .query_map([], |row| Ok((row.get(0)?, (row.get(1)?, row.get(2)?))))?
+        .filter_map(|r| r.ok())
+        .collect();
+    let mut stmt = conn.prepare("SELECT id, name FROM accounts")?;
+    let accounts: HashMap<String, String> = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .filter_map(|r| r.ok())
+        .collect();
+    let account_name = |id: &Option<String>| id.as_ref().and_then(|id| accounts.get(id).cloned());
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM category_rules ORDER BY priority DESC, created_at",
+        RULE_COLUMNS
+    ))?;
+    let rules: Vec<CategoryRule> = stmt
+        .query_map([], rule_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let exported: Vec<ExportedRule> = rules
+        .into_iter()
+        .map(|rule| {
+            let (category, parent_category) = categories
+                .get(&rule.category_id)
+                .cloned()
+                .map_or((None, None), |(name, parent)| (Some(name), parent));
+            ExportedRule {
+                category,
+                parent_category,
+                account: account_name(&rule.account_id),
+                transfer_account: account_name(&rule.transfer_account_id),
+                conditions: rule.conditions.map(|conditions| {
+                    conditions
+                        .into_iter()
+                        .map(|c| ExportedCondition {
+                            account: account_name(&c.account_id),
+                            condition_type: c.condition_type,
+                            pattern: c.pattern,
+                            amount_min: c.amount_min,
+                            amount_max: c.amount_max,
+                        })
+                        .collect()
+                }),
+                actions: rule.actions.map(|actions| {
+                    actions
+                        .into_iter()
+                        .map(|mut a| {
+                            if a.action_type == "mark_transfer" {
+                                a.value = accounts.get(&a.value).cloned().unwrap_or_default();
+                            }
+                            a
+                        })
+                        .collect()
+                }),
+                match_mode: rule.match_mode,
+                rule_type: rule.rule_type,
+                pattern: rule.pattern,
+                amount_min: rule.amount_min,
+                amount_max: rule.amount_max,
+                priority: rule.priority,
+                is_active: rule.is_active,
+            }
+        })
+        .collect();
+
+    record_feature_use(conn, "export_category_rules");
+
+    Ok(serde_json::to_string_pretty(&exported)?)
+}
+
+/// Id of the category with the given name (and parent name, if any), ignoring case
+fn category_id_by_name(
+    conn: &Connection,
+    name: &str,
+    parent: Option<&str>,
+) -> Result<Option<String>> {
     conn.query_row(
-        "SELECT id, category_id, rule_type, pattern, amount_min, amount_max,
-                account_id, priority, is_active, created_at, updated_at
-         FROM category_rules WHERE id = ?1",
-        [&id],
-        |row| {
-            Ok(CategoryRule {
-                id: row.get(0)?,
-                category_id: row.get(1)?,
-                rule_type: row.get(2)?,
-                pattern: row.get(3)?,
-                amount_min: row.get(4)?,
-                amount_max: row.get(5)?,
-                account_id: row.get(6)?,
-                priority: row.get(7)?,
-                is_active: row.get(8)?,
-                created_at: row.get(9)?,
-                updated_at: row.get(10)?,
-            })
-        },
+        "SELECT c.id FROM categories c
+         LEFT JOIN categories p ON c.parent_id = p.id
+         WHERE c.deleted_at IS NULL AND c.is_archived = 0 AND c.name = ?1 COLLATE NOCASE
+           AND (?2 IS NULL OR p.name = ?2 COLLATE NOCASE)
+         ORDER BY c.parent_id IS NOT NULL, c.display_order
+         LIMIT 1",
+        rusqlite::params![name.trim(), parent.map(str::trim)],
+        |row| row.get(0),
     )
+    .optional()
     .map_err(|e| e.into())
 }
 
+/// Id of the open account with the given name, ignoring case
+fn account_id_by_name(conn: &Connection, name: &str) -> Result<Option<String>> {
+    conn.query_row(
+        "SELECT id FROM accounts WHERE deleted_at IS NULL AND name = ?1 COLLATE NOCASE
+         ORDER BY is_active DESC, display_order LIMIT 1",
+        [name.trim()],
+        |row| row.get(0),
+    )
+    .optional()
+    .map_err(|e| e.into())
+}
+
+/// Turn an exported rule back into `create_category_rule` input, or describe the name
+/// that couldn't be matched
+fn exported_rule_input(
+    conn: &Connection,
+    rule: &ExportedRule,
+) -> Result<std::result::Result<serde_json::Value, String>> {
+    let label = format!("{} \"{}\"", rule.rule_type, rule.pattern);
+    let account = |name: &Option<String>| -> Result<std::result::Result<Option<String>, String>> {
+        match name {
+            None => Ok(Ok(None)),
+            Some(name) => Ok(account_id_by_name(conn, name)?
+                .map(Some)
+                .ok_or_else(|| format!("{}: no account named {}", label, name))),
+        }
+    };
+
+    let category_id = match &rule.category {
+        None => None,
+        Some(name) => match category_id_by_name(conn, name, rule.parent_category.as_deref())? {
+            Some(id) => Some(id),
+            None => return Ok(Err(format!("{}: no category named {}", label, name))),
+        },
+    };
+    let account_id = match account(&rule.account)? {
+        Ok(id) => id,
+        Err(message) => return Ok(Err(message)),
+    };
+    let transfer_account_id = match account(&rule.transfer_account)? {
+        Ok(id) => id,
+        Err(message) => return Ok(Err(message)),
+    };
+
+    let mut conditions = Vec::new();
+    for condition in rule.conditions.iter().flatten() {
+        let account_id = match account(&condition.account)? {
+            Ok(id) => id,
+            Err(message) => return Ok(Err(message)),
+        };
+        conditions.push(RuleCondition {
+            condition_type: condition.condition_type.clone(),
+            pattern: condition.pattern.clone(),
+            amount_min: condition.amount_min,
+            amount_max: condition.amount_max,
+            account_id,
+        });
+    }
+
+    let mut actions = Vec::new();
+    for action in rule.actions.iter().flatten() {
+        let mut action = action.clone();
+        if action.action_type == "mark_transfer" {
+            match account(&Some(action.value.clone()))? {
+                Ok(id) => action.value = id.unwrap_or_default(),
+                Err(message) => return Ok(Err(message)),
+            }
+        }
+        actions.push(action);
+    }
+
+    Ok(Ok(serde_json::json!({
+        "categoryId": category_id,
+        "ruleType": rule.rule_type,
+        "pattern": rule.pattern,
+        "amountMin": rule.amount_min,
+        "amountMax": rule.amount_max,
+        "accountId": account_id,
+        "priority": rule.priority,
+        "isActive": rule.is_active,
+        "transferAccountId": transfer_account_id,
+        "conditions": (!conditions.is_empty()).then_some(conditions),
+        "matchMode": rule.match_mode,
+        "actions": (!actions.is_empty()).then_some(actions),
+    })))
+}
+
+/// A list input as `insert_rule` stores it, for comparing against existing rules
+fn stored_list<T: DeserializeOwned + Serialize>(
+    value: &serde_json::Value,
+) -> Result<Option<String>> {
+    if value.is_null() {
+        return Ok(None);
+    }
+    let list: Vec<T> = serde_json::from_value(value.clone())?;
+    Ok(Some(serde_json::to_string(&list)?))
+}
+
+/// Import rules exported by `export_category_rules`, matching categories and accounts by
+/// name. Rules naming something this database doesn't have are skipped and listed in the
+/// result; rules identical to an existing one aren't added again.
 #[tauri::command]
-pub fn delete_category_rule(id: String, db: State<'_, Mutex<Database>>) -> Result<()> {
+pub fn import_category_rules(
+    contents: String,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<RuleImportResult> {
     let database = db.lock().unwrap();
     let conn = database.get_connection()?;
 
-    conn.execute("DELETE FROM category_rules WHERE id = ?1", [&id])?;
+    let rules: Vec<ExportedRule> = serde_json::from_str(&contents)
+        .map_err(|e| AppError::Validation(format!("Invalid rule file: {}", e)))?;
 
-    Ok(())
+    let mut result = RuleImportResult::default();
+
+    let tx = conn.unchecked_transaction()?;
+    for rule in &rules {
+        let data = match exported_rule_input(&tx, rule)? {
+            Ok(data) => data,
+            Err(message) => {
+                result.skipped.push(message);
+                continue;
+            }
+        };
+
+        let conditions = stored_list::<RuleCondition>(&data["conditions"])?;
+        let actions = stored_list::<RuleAction>(&data["actions"])?;
+        let duplicate: bool = tx.query_row(
+            "SELECT EXISTS(SELECT 1 FROM category_rules
+             WHERE category_id = COALESCE(?1, CASE WHEN ?2 IS NULL THEN '' ELSE ?3 END)
+               AND rule_type = ?4 AND pattern = ?5
+               AND amount_min IS ?6 AND amount_max IS ?7 AND account_id IS ?8
+               AND transfer_account_id IS ?2 AND conditions IS ?9 AND actions IS ?10
+               AND match_mode = ?11)",
+            rusqlite::params![
+                data["categoryId"].as_str(),
+                data["transferAccountId"].as_str(),
+                TRANSFER_CATEGORY_ID,
+                rule.rule_type,
+                rule.pattern,
+                rule.amount_min,
+                rule.amount_max,
+                data["accountId"].as_str(),
+                conditions,
+                actions,
+                rule.match_mode,
+            ],
+            |row| row.get(0),
+        )?;
+        if duplicate {
+            result.duplicates += 1;
+            continue;
+        }
+
+        insert_rule(&tx, &data)?;
+        result.created += 1;
+    }
+    tx.commit()?;
+    record_feature_use(conn, "import_category_rules");
+
+    emit_changed(&app, &[RULES_CHANGED]);
+
+    Ok(result)
 }
 
 #[tauri::command]
 pub fn apply_category_rules(
     transaction_ids: Option<Vec<String>>,
+    app: AppHandle,
     db: State<'_, Mutex<Database>>,
 ) -> Result<i32> {
     let database = db.lock().unwrap();
     let conn = database.get_connection()?;
 
-    // Get all active rules ordered by priority
-    let mut rules_stmt = conn.prepare(
-        "SELECT id, category_id, rule_type, pattern, amount_min, amount_max, account_id
-         FROM category_rules
-         WHERE is_active = 1
-         ORDER BY priority DESC"
-    )?;
+    let tx = conn.unchecked_transaction()?;
+    let categorized_count = apply_rules(&tx, transaction_ids.as_deref(), false)?;
+    tx.commit()?;
+    record_feature_use(conn, "apply_category_rules");
+
+    raise_budget_alerts(&app, conn);
+    emit_changed(&app, &[TRANSACTIONS_CHANGED, ACCOUNTS_CHANGED]);
+
+    Ok(categorized_count)
+}
+
+/// Show which existing transactions a rule would match, without saving or applying it.
+/// Takes the same input as `create_category_rule`; only the matching fields are used,
+/// so a category isn't needed yet. Every transaction is checked, categorized or not.
+#[tauri::command]
+pub fn preview_category_rule(
+    rule: serde_json::Value,
+    limit: Option<usize>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<RulePreview> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let rule_type = rule["ruleType"].as_str().unwrap_or("payee_contains");
+    let pattern = rule["pattern"].as_str().unwrap_or("");
+    validate_rule_pattern(rule_type, pattern)?;
+    let conditions = input_conditions(conn, &rule)?;
+    let match_mode = input_match_mode(&rule)?.unwrap_or("all");
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let candidate = CategoryRule {
+        id: String::new(),
+        category_id: String::new(),
+        rule_type: rule_type.to_string(),
+        pattern: pattern.to_string(),
+        amount_min: rule["amountMin"].as_i64(),
+        amount_max: rule["amountMax"].as_i64(),
+        account_id: rule["accountId"].as_str().map(|s| s.to_string()),
+        priority: 0,
+        is_active: true,
+        created_at: now.clone(),
+        updated_at: now,
+        transfer_account_id: None,
+        conditions,
+        actions: None,
+        match_mode: match_mode.to_string(),
+    };
 
-    let rules: Vec<(String, String, String, String, Option<i64>, Option<i64>, Option<String>)> = rules_stmt
-        .query_map([], |row| {
-            Ok((
-                row.get::<_, String>(0)?,
-                row.get::<_, String>(1)?,
-                row.get::<_, String>(2)?,
-                row.get::<_, String>(3)?,
-                row.get::<_, Option<i64>>(4)?,
-                row.get::<_, Option<i64>>(5)?,
-                row.get::<_, Option<String>>(6)?,
-            ))
-        })?
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM transactions
+         WHERE deleted_at IS NULL
+         ORDER BY date DESC, created_at DESC",
+        TRANSACTION_COLUMNS
+    ))?;
+    let matches: Vec<Transaction> = stmt
+        .query_map([], transaction_from_row)?
         .filter_map(|r| r.ok())
+        .filter(|t| rule_matches(&candidate, &RuleTarget::from(t)))
         .collect();
 
-    if rules.is_empty() {
-        return Ok(0);
+    Ok(RulePreview {
+        match_count: matches.len(),
+        transactions: matches
+            .into_iter()
+            .take(limit.unwrap_or(DEFAULT_PREVIEW_LIMIT))
+            .collect(),
+    })
+}
+
+/// Propose payee rules from how transactions are already categorized. Payees are grouped
+/// by their first words once dates and reference numbers are stripped; a group whose
+/// shared payee text matches at least a few categorized transactions, nearly all in one
+/// category, becomes a `payee_contains` suggestion. Payees an active rule already
+/// categorizes are skipped. Most-matched suggestions come first.
+#[tauri::command]
+pub fn suggest_category_rules(db: State<'_, Mutex<Database>>) -> Result<Vec<RuleSuggestion>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM category_rules
+         WHERE is_active = 1 AND category_id != ''
+         ORDER BY priority DESC",
+        RULE_COLUMNS
+    ))?;
+    let rules: Vec<CategoryRule> = stmt
+        .query_map([], rule_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let category_names: HashMap<String, String> = conn
+        .prepare("SELECT id, name FROM categories WHERE is_archived = 0 AND deleted_at IS NULL")?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    // Transfers and splits carry their categories elsewhere, so they're left out
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM transactions
+         WHERE deleted_at IS NULL AND transfer_id IS NULL AND is_split = 0
+           AND payee IS NOT NULL AND TRIM(payee) != ''
+         ORDER BY date DESC, created_at DESC",
+        TRANSACTION_COLUMNS
+    ))?;
+    let transactions: Vec<Transaction> = stmt
+        .query_map([], transaction_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let mut groups: HashMap<String, Vec<&Transaction>> = HashMap::new();
+    for transaction in transactions.iter().filter(|t| t.category_id.is_some()) {
+        let payee = transaction.payee.as_deref().unwrap_or("");
+        let key = normalize_payee(payee)
+            .split_whitespace()
+            .take(2)
+            .collect::<Vec<_>>()
+            .join(" ");
+        if !key.is_empty() {
+            groups.entry(key).or_default().push(transaction);
+        }
     }
 
-    // Get uncategorized transactions
-    let tx_query = if let Some(ref ids) = transaction_ids {
-        if ids.is_empty() {
-            return Ok(0);
+    let mut suggestions: Vec<RuleSuggestion> = Vec::new();
+    for group in groups.values() {
+        // Only payees no active rule already categorizes count towards a suggestion
+        let group: Vec<&Transaction> = group
+            .iter()
+            .filter(|t| {
+                !rules
+                    .iter()
+                    .any(|rule| rule_matches(rule, &RuleTarget::from(**t)))
+            })
+            .copied()
+            .collect();
+        if group.len() < MIN_SUGGESTION_MATCHES {
+            continue;
         }
-        let placeholders: Vec<String> = ids.iter().map(|_| "?".to_string()).collect();
-        format!(
-            "SELECT id, account_id, payee, amount FROM transactions
-             WHERE id IN ({}) AND deleted_at IS NULL",
-            placeholders.join(", ")
-        )
-    } else {
-        "SELECT id, account_id, payee, amount FROM transactions
-         WHERE category_id IS NULL AND deleted_at IS NULL".to_string()
-    };
 
-    let mut tx_stmt = conn.prepare(&tx_query)?;
+        let payees: Vec<&str> = group.iter().filter_map(|t| t.payee.as_deref()).collect();
+        let pattern = common_payee_pattern(&payees);
+        if pattern.chars().count() < MIN_SUGGESTION_PATTERN_LEN
+            || suggestions.iter().any(|s| s.pattern == pattern)
+        {
+            continue;
+        }
 
-    let transactions: Vec<(String, String, Option<String>, i64)> = if let Some(ref ids) = transaction_ids {
-        tx_stmt
-            .query_map(rusqlite::params_from_iter(ids.iter()), |row| {
-                Ok((
-                    row.get::<_, String>(0)?,
-                    row.get::<_, String>(1)?,
-                    row.get::<_, Option<String>>(2)?,
-                    row.get::<_, i64>(3)?,
-                ))
-            })?
-            .filter_map(|r| r.ok())
-            .collect()
-    } else {
-        tx_stmt
-            .query_map([], |row| {
-                Ok((
-                    row.get::<_, String>(0)?,
-                    row.get::<_, String>(1)?,
-                    row.get::<_, Option<String>>(2)?,
-                    row.get::<_, i64>(3)?,
-                ))
-            })?
-            .filter_map(|r| r.ok())
-            .collect()
-    };
+        // Judge the pattern on everything it matches, not just this group
+        let matches: Vec<&Transaction> = transactions
+            .iter()
+            .filter(|t| {
+                t.payee
+                    .as_deref()
+                    .is_some_and(|p| p.to_lowercase().contains(&pattern))
+            })
+            .collect();
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for category_id in matches.iter().filter_map(|t| t.category_id.as_deref()) {
+            *counts.entry(category_id).or_default() += 1;
+        }
+        let Some((category_id, agreeing)) = counts.iter().max_by_key(|(_, count)| **count) else {
+            continue;
+        };
+        let Some(category_name) = category_names.get(*category_id) else {
+            continue;
+        };
 
-    let now = chrono::Utc::now().to_rfc3339();
-    let mut categorized_count = 0;
+        let match_count: usize = counts.values().sum();
+        let consistency = *agreeing as f64 / match_count as f64;
+        if match_count < MIN_SUGGESTION_MATCHES || consistency < MIN_SUGGESTION_CONSISTENCY {
+            continue;
+        }
 
-    for (tx_id, tx_account_id, tx_payee, tx_amount) in transactions {
-        for (_rule_id, category_id, rule_type, pattern, amount_min, amount_max, rule_account_id) in &rules {
-            // Check account filter
-            if let Some(acc_id) = rule_account_id {
-                if acc_id != &tx_account_id {
-                    continue;
-                }
+        let mut sample_payees: Vec<String> = Vec::new();
+        for payee in &payees {
+            if sample_payees.len() < 3 && !sample_payees.iter().any(|p| p == payee) {
+                sample_payees.push(payee.to_string());
             }
+        }
 
-            // Check amount range
-            if let Some(min) = amount_min {
-                if tx_amount < *min {
-                    continue;
-                }
+        suggestions.push(RuleSuggestion {
+            rule_type: "payee_contains".to_string(),
+            pattern,
+            category_id: category_id.to_string(),
+            category_name: category_name.clone(),
+            match_count,
+            consistency,
+            uncategorized_count: matches.len() - match_count,
+            sample_payees,
+        });
+    }
+
+    suggestions.sort_by(|a, b| {
+        b.match_count
+            .cmp(&a.match_count)
+            .then_with(|| a.pattern.cmp(&b.pattern))
+    });
+
+    Ok(suggestions)
+}
+
+/// Replay every active categorizing rule against transactions that already have a
+/// category and report, per rule, how often it agrees with them. Every matching rule is
+/// scored, not only the one that would win, so rules fighting over the same transactions
+/// show up as competitors.
+#[tauri::command]
+pub fn backtest_category_rules(db: State<'_, Mutex<Database>>) -> Result<RuleBacktest> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM category_rules
+         WHERE is_active = 1 AND category_id != ''
+           AND category_id NOT IN (SELECT id FROM categories WHERE is_archived = 1)
+         ORDER BY priority DESC",
+        RULE_COLUMNS
+    ))?;
+    let rules: Vec<CategoryRule> = stmt
+        .query_map([], rule_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    // Transfers and splits carry their categories elsewhere, so they're left out
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM transactions
+         WHERE deleted_at IS NULL AND transfer_id IS NULL AND is_split = 0
+           AND category_id IS NOT NULL",
+        TRANSACTION_COLUMNS
+    ))?;
+    let transactions: Vec<Transaction> = stmt
+        .query_map([], transaction_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let mut entries: Vec<RuleBacktestEntry> = rules
+        .iter()
+        .map(|rule| RuleBacktestEntry {
+            rule_id: rule.id.clone(),
+            category_id: rule.category_id.clone(),
+            match_count: 0,
+            agreements: 0,
+            conflicts: 0,
+            shadowed_count: 0,
+            competing_rule_ids: Vec::new(),
+        })
+        .collect();
+    let (mut matched_count, mut agreement_count) = (0, 0);
+
+    for transaction in &transactions {
+        let target = RuleTarget::from(transaction);
+        let matching: Vec<usize> = (0..rules.len())
+            .filter(|&i| rule_matches(&rules[i], &target))
+            .collect();
+        let Some(&winner) = matching.first() else {
+            continue;
+        };
+        matched_count += 1;
+        let category_id = transaction.category_id.as_deref().unwrap_or("");
+        if rules[winner].category_id == category_id {
+            agreement_count += 1;
+        }
+
+        for (position, &i) in matching.iter().enumerate() {
+            let entry = &mut entries[i];
+            entry.match_count += 1;
+            if rules[i].category_id == category_id {
+                entry.agreements += 1;
+            } else {
+                entry.conflicts += 1;
+            }
+            if position > 0 {
+                entry.shadowed_count += 1;
             }
-            if let Some(max) = amount_max {
-                if tx_amount > *max {
-                    continue;
+            for &other in &matching {
+                if rules[other].category_id != rules[i].category_id
+                    && !entry.competing_rule_ids.contains(&rules[other].id)
+                {
+                    entry.competing_rule_ids.push(rules[other].id.clone());
                 }
             }
+        }
+    }
 
-            // Check pattern match
-            let matches = match rule_type.as_str() {
-                "payee_contains" => {
-                    if let Some(ref payee) = tx_payee {
-                        payee.to_lowercase().contains(&pattern.to_lowercase())
-                    } else {
-                        false
-                    }
-                }
-                "payee_exact" => {
-                    if let Some(ref payee) = tx_payee {
-                        payee.to_lowercase() == pattern.to_lowercase()
-                    } else {
-                        false
-                    }
-                }
-                "payee_starts_with" => {
-                    if let Some(ref payee) = tx_payee {
-                        payee.to_lowercase().starts_with(&pattern.to_lowercase())
-                    } else {
-                        false
-                    }
-                }
-                "payee_regex" => {
-                    if let Some(ref payee) = tx_payee {
-                        regex::Regex::new(pattern)
-                            .map(|re| re.is_match(payee))
-                            .unwrap_or(false)
-                    } else {
-                        false
-                    }
-                }
-                _ => false,
-            };
+    Ok(RuleBacktest {
+        transaction_count: transactions.len(),
+        matched_count,
+        agreement_count,
+        conflict_count: matched_count - agreement_count,
+        rules: entries,
+    })
+}
 
-            if matches {
-                conn.execute(
-                    "UPDATE transactions SET category_id = ?1, updated_at = ?2 WHERE id = ?3",
-                    rusqlite::params![category_id, now, tx_id],
-                )?;
-                categorized_count += 1;
-                break; // Use first matching rule
-            }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn condition(condition_type: &str, pattern: &str) -> RuleCondition {
+        RuleCondition {
+            condition_type: condition_type.to_string(),
+            pattern: pattern.to_string(),
+            amount_min: None,
+            amount_max: None,
+            account_id: None,
         }
     }
 
-    Ok(categorized_count)
+    fn target(payee: &str) -> RuleTarget {
+        RuleTarget {
+            id: "t".to_string(),
+            account_id: "checking".to_string(),
+            date: "2024-03-01".to_string(),
+            payee: Some(payee.to_string()),
+            memo: None,
+            amount: -1250,
+            transfer_id: None,
+            check_number: None,
+            transaction_type: None,
+        }
+    }
+
+    #[test]
+    fn test_rule_match_modes() {
+        let mut rule = CategoryRule {
+            id: "r".to_string(),
+            category_id: "cat_dining".to_string(),
+            rule_type: "payee_contains".to_string(),
+            pattern: String::new(),
+            amount_min: None,
+            amount_max: None,
+            account_id: None,
+            priority: 0,
+            is_active: true,
+            created_at: String::new(),
+            updated_at: String::new(),
+            transfer_account_id: None,
+            conditions: Some(vec![
+                condition("payee_contains", "starbucks"),
+                condition("payee_contains", "dunkin"),
+            ]),
+            actions: None,
+            match_mode: "all".to_string(),
+        };
+        assert!(!rule_matches(&rule, &target("STARBUCKS #123")));
+
+        rule.match_mode = "any".to_string();
+        assert!(rule_matches(&rule, &target("STARBUCKS #123")));
+        assert!(rule_matches(&rule, &target("Dunkin Donuts")));
+        assert!(!rule_matches(&rule, &target("Peet's Coffee")));
+
+        // The rule's own account filter still applies to every match
+        rule.account_id = Some("savings".to_string());
+        assert!(!rule_matches(&rule, &target("Dunkin Donuts")));
+    }
 }
@@ -1,16 +1,32 @@
+use crate::commands::fx::spawn_fx_rate_fetch;
+use crate::commands::goals::spawn_goal_contribution_sweep;
+use crate::commands::interest::spawn_interest_accrual;
+use crate::commands::net_worth::spawn_net_worth_snapshots;
+use crate::commands::usage_stats::record_feature_use;
 use crate::config::AppConfig;
 use crate::db::Database;
 use crate::error::Result;
+use rusqlite::Connection;
 use std::sync::Mutex;
-use tauri::State;
+use tauri::{AppHandle, State};
 
 #[tauri::command]
 pub fn unlock_database(
     password: String,
+    app: AppHandle,
     db: State<'_, Mutex<Database>>,
 ) -> Result<bool> {
     let mut database = db.lock().unwrap();
-    database.unlock(&password)
+    let unlocked = database.unlock(&password)?;
+
+    if unlocked {
+        spawn_goal_contribution_sweep(app.clone());
+        spawn_interest_accrual(app.clone());
+        spawn_fx_rate_fetch(app.clone());
+        spawn_net_worth_snapshots(app);
+    }
+
+    Ok(unlocked)
 }
 
 #[tauri::command]
@@ -36,9 +52,14 @@ pub fn get_setting(
     let database = db.lock().unwrap();
     let conn = database.get_connection()?;
 
+    read_setting(conn, &key)
+}
+
+/// Read a setting value directly from a connection, for use by other commands
+pub(crate) fn read_setting(conn: &Connection, key: &str) -> Result<Option<String>> {
     let result = conn.query_row(
         "SELECT value FROM settings WHERE key = ?1",
-        [&key],
+        [key],
         |row| row.get::<_, String>(0),
     );
 
@@ -118,6 +139,7 @@ pub fn export_to_json(db: State<'_, Mutex<Database>>) -> Result<String> {
         .filter_map(|r| r.ok())
         .collect();
     export.insert("categories".to_string(), serde_json::Value::Array(categories));
+    record_feature_use(conn, "export_to_json");
 
     Ok(serde_json::to_string_pretty(&export)?)
 }
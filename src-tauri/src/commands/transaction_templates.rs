@@ -0,0 +1,323 @@
+use crate::commands::transactions::{get_transaction, insert_transaction};
+use crate::db::Database;
+use crate::error::{AppError, Result};
+use crate::events::{emit_changed, ACCOUNTS_CHANGED, TRANSACTIONS_CHANGED};
+use crate::models::{Transaction, TransactionTemplate, TransactionTemplateSplit};
+use rusqlite::Connection;
+use std::sync::Mutex;
+use tauri::{AppHandle, State};
+use uuid::Uuid;
+
+const TEMPLATE_COLUMNS: &str =
+    "id, name, account_id, payee, amount, category_id, notes, created_at, updated_at";
+
+fn template_from_row(row: &rusqlite::Row) -> rusqlite::Result<TransactionTemplate> {
+    Ok(TransactionTemplate {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        account_id: row.get(2)?,
+        payee: row.get(3)?,
+        amount: row.get(4)?,
+        category_id: row.get(5)?,
+        notes: row.get(6)?,
+        splits: Vec::new(),
+        created_at: row.get(7)?,
+        updated_at: row.get(8)?,
+    })
+}
+
+fn fetch_template_splits(
+    conn: &Connection,
+    template_id: &str,
+) -> Result<Vec<TransactionTemplateSplit>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, template_id, category_id, amount, memo
+         FROM transaction_template_splits WHERE template_id = ?1 ORDER BY rowid",
+    )?;
+
+    let splits = stmt
+        .query_map([template_id], |row| {
+            Ok(TransactionTemplateSplit {
+                id: row.get(0)?,
+                template_id: row.get(1)?,
+                category_id: row.get(2)?,
+                amount: row.get(3)?,
+                memo: row.get(4)?,
+            })
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(splits)
+}
+
+fn fetch_transaction_template(conn: &Connection, id: &str) -> Result<TransactionTemplate> {
+    let mut template = conn
+        .query_row(
+            &format!(
+                "SELECT {} FROM transaction_templates WHERE id = ?1",
+                TEMPLATE_COLUMNS
+            ),
+            [id],
+            template_from_row,
+        )
+        .map_err(|_| AppError::NotFound("Transaction template not found".to_string()))?;
+
+    template.splits = fetch_template_splits(conn, id)?;
+    Ok(template)
+}
+
+/// Replace a template's splits with the `splits` array from command input
+/// (each entry has `amount` in cents and optional `categoryId` and `memo`)
+fn save_template_splits(
+    conn: &Connection,
+    template_id: &str,
+    splits: &[serde_json::Value],
+) -> Result<()> {
+    conn.execute(
+        "DELETE FROM transaction_template_splits WHERE template_id = ?1",
+        [template_id],
+    )?;
+
+    for split in splits {
+        let amount = split["amount"]
+            .as_i64()
+            .ok_or_else(|| AppError::Validation("Each split needs an amount".to_string()))?;
+
+        conn.execute(
+            "INSERT INTO transaction_template_splits (id, template_id, category_id, amount, memo)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            rusqlite::params![
+                Uuid::new_v4().to_string(),
+                template_id,
+                split["categoryId"].as_str(),
+                amount,
+                split["memo"].as_str(),
+            ],
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Splits must add up to the amount they divide
+fn check_split_total(amount: Option<i64>, splits: &[TransactionTemplateSplit]) -> Result<()> {
+    let total: i64 = splits.iter().map(|s| s.amount).sum();
+    match amount {
+        Some(amount) if !splits.is_empty() && total != amount => Err(AppError::Validation(
+            format!("Splits total {} but the amount is {}", total, amount),
+        )),
+        _ => Ok(()),
+    }
+}
+
+#[tauri::command]
+pub fn list_transaction_templates(
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<TransactionTemplate>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM transaction_templates ORDER BY name",
+        TEMPLATE_COLUMNS
+    ))?;
+
+    let mut templates: Vec<TransactionTemplate> = stmt
+        .query_map([], template_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    for template in &mut templates {
+        template.splits = fetch_template_splits(conn, &template.id)?;
+    }
+
+    Ok(templates)
+}
+
+#[tauri::command]
+pub fn create_transaction_template(
+    data: serde_json::Value,
+    db: State<'_, Mutex<Database>>,
+) -> Result<TransactionTemplate> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let name = data["name"].as_str().unwrap_or("").trim();
+    if name.is_empty() {
+        return Err(AppError::Validation(
+            "Template name is required".to_string(),
+        ));
+    }
+
+    let id = Uuid::new_v4().to_string();
+    let now = chrono::Utc::now().to_rfc3339();
+
+    let tx = conn.unchecked_transaction()?;
+
+    tx.execute(
+        "INSERT INTO transaction_templates (
+            id, name, account_id, payee, amount, category_id, notes, created_at, updated_at
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        rusqlite::params![
+            id,
+            name,
+            data["accountId"].as_str(),
+            data["payee"].as_str(),
+            data["amount"].as_i64(),
+            data["categoryId"].as_str(),
+            data["notes"].as_str(),
+            now,
+            now,
+        ],
+    )?;
+
+    if let Some(splits) = data["splits"].as_array() {
+        save_template_splits(&tx, &id, splits)?;
+    }
+
+    let template = fetch_transaction_template(&tx, &id)?;
+    check_split_total(template.amount, &template.splits)?;
+
+    tx.commit()?;
+
+    Ok(template)
+}
+
+#[tauri::command]
+pub fn update_transaction_template(
+    id: String,
+    data: serde_json::Value,
+    db: State<'_, Mutex<Database>>,
+) -> Result<TransactionTemplate> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let now = chrono::Utc::now().to_rfc3339();
+
+    let tx = conn.unchecked_transaction()?;
+
+    tx.execute(
+        "UPDATE transaction_templates SET
+            name = COALESCE(?1, name),
+            account_id = COALESCE(?2, account_id),
+            payee = COALESCE(?3, payee),
+            amount = COALESCE(?4, amount),
+            category_id = COALESCE(?5, category_id),
+            notes = COALESCE(?6, notes),
+            updated_at = ?7
+         WHERE id = ?8",
+        rusqlite::params![
+            data["name"].as_str(),
+            data["accountId"].as_str(),
+            data["payee"].as_str(),
+            data["amount"].as_i64(),
+            data["categoryId"].as_str(),
+            data["notes"].as_str(),
+            now,
+            id,
+        ],
+    )?;
+
+    if let Some(splits) = data["splits"].as_array() {
+        save_template_splits(&tx, &id, splits)?;
+    }
+
+    let template = fetch_transaction_template(&tx, &id)?;
+    check_split_total(template.amount, &template.splits)?;
+
+    tx.commit()?;
+
+    Ok(template)
+}
+
+#[tauri::command]
+pub fn delete_transaction_template(id: String, db: State<'_, Mutex<Database>>) -> Result<()> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    conn.execute(
+        "DELETE FROM transaction_template_splits WHERE template_id = ?1",
+        [&id],
+    )?;
+    conn.execute("DELETE FROM transaction_templates WHERE id = ?1", [&id])?;
+
+    Ok(())
+}
+
+/// Create a transaction from a saved template. `data` may override the template's
+/// `accountId`, `payee`, `amount`, `categoryId`, and `notes`, and sets the `date`
+/// (today when omitted).
+#[tauri::command]
+pub fn create_from_template(
+    template_id: String,
+    data: serde_json::Value,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Transaction> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let template = fetch_transaction_template(conn, &template_id)?;
+
+    let account_id = data["accountId"]
+        .as_str()
+        .map(|s| s.to_string())
+        .or(template.account_id.clone())
+        .ok_or_else(|| AppError::Validation("An account is required".to_string()))?;
+
+    let split_total: i64 = template.splits.iter().map(|s| s.amount).sum();
+    let amount = data["amount"]
+        .as_i64()
+        .or(template.amount)
+        .or_else(|| (!template.splits.is_empty()).then_some(split_total))
+        .ok_or_else(|| AppError::Validation("An amount is required".to_string()))?;
+    check_split_total(Some(amount), &template.splits)?;
+
+    let date = data["date"]
+        .as_str()
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| {
+            chrono::Local::now()
+                .date_naive()
+                .format("%Y-%m-%d")
+                .to_string()
+        });
+
+    let input = serde_json::json!({
+        "accountId": account_id,
+        "date": date,
+        "amount": amount,
+        "payee": data["payee"].as_str().or(template.payee.as_deref()),
+        "categoryId": data["categoryId"].as_str().or(template.category_id.as_deref()),
+        "notes": data["notes"].as_str().or(template.notes.as_deref()),
+        "isSplit": !template.splits.is_empty(),
+    });
+
+    let tx = conn.unchecked_transaction()?;
+
+    let id = insert_transaction(&tx, &input)?;
+
+    let now = chrono::Utc::now().to_rfc3339();
+    for split in &template.splits {
+        tx.execute(
+            "INSERT INTO transaction_splits (id, parent_transaction_id, category_id, amount, memo, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            rusqlite::params![
+                Uuid::new_v4().to_string(),
+                id,
+                split.category_id,
+                split.amount,
+                split.memo,
+                now,
+            ],
+        )?;
+    }
+
+    tx.commit()?;
+
+    emit_changed(&app, &[TRANSACTIONS_CHANGED, ACCOUNTS_CHANGED]);
+
+    drop(database);
+    get_transaction(id, db)
+}
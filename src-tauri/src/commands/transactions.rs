@@ -1,10 +1,264 @@
+use crate::commands::account_statements::ensure_statement_unlocked;
+use crate::commands::accounts::refresh_available_balance;
+use crate::commands::budget_alerts::raise_budget_alerts;
+use crate::commands::gift_cards::return_gift_card_payment;
+use crate::commands::month_close::ensure_transaction_unlocked;
+use crate::commands::note_templates::render_note_template;
+use crate::commands::payees::resolve_payee;
+use crate::commands::rules::{apply_rules, auto_apply_rules_enabled};
 use crate::db::Database;
 use crate::error::{AppError, Result};
-use crate::models::{Transaction, TransactionFilters, TransferCandidate};
+use crate::events::{emit_changed, ACCOUNTS_CHANGED, TRANSACTIONS_CHANGED};
+use crate::models::{Transaction, TransactionFilters, TransactionStatusChange, TransferCandidate};
+use rusqlite::{Connection, OptionalExtension};
+use serde::Serialize;
 use std::sync::Mutex;
-use tauri::State;
+use tauri::{AppHandle, State};
 use uuid::Uuid;
 
+/// Jumps in check numbers wider than this are treated as a new checkbook, not missing checks
+const MAX_CHECK_NUMBER_GAP: i64 = 25;
+
+/// Same-amount transactions this many days apart or closer may be duplicates
+const DUPLICATE_MAX_DAYS_APART: i64 = 3;
+/// Minimum pair score for two transactions to be grouped as duplicates
+const DUPLICATE_MIN_CONFIDENCE: f64 = 0.6;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckNumberGap {
+    pub account_id: String,
+    pub account_name: String,
+    pub first_missing: i64,
+    pub last_missing: i64,
+    pub missing_count: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountBalanceImpact {
+    pub account_id: String,
+    pub account_name: Option<String>,
+    pub count: i64,
+    pub balance_change: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkDeleteResult {
+    pub dry_run: bool,
+    pub count: i64,
+    pub accounts: Vec<AccountBalanceImpact>,
+}
+
+/// Aggregates over every transaction matching a filter; outflow is reported as a positive amount
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionStats {
+    pub count: i64,
+    pub total_inflow: i64,
+    pub total_outflow: i64,
+    pub net: i64,
+    pub average: i64,
+}
+
+/// Transactions that look like copies of each other, with the most complete one first
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateGroup {
+    pub amount: i64,
+    pub confidence: f64,
+    pub transactions: Vec<Transaction>,
+}
+
+/// Statuses in the order a transaction moves through them
+const TRANSACTION_STATUSES: [&str; 3] = ["pending", "cleared", "reconciled"];
+
+pub(crate) fn status_rank(status: &str) -> Result<usize> {
+    TRANSACTION_STATUSES
+        .iter()
+        .position(|s| *s == status)
+        .ok_or_else(|| AppError::Validation(format!("Unknown transaction status '{}'", status)))
+}
+
+/// Move a transaction to a new status, recording the transition. Statuses advance one step
+/// at a time (pending → cleared → reconciled) and may step back one to correct a mistake.
+/// Returns false when the transaction already has the requested status.
+pub(crate) fn change_transaction_status(
+    conn: &Connection,
+    id: &str,
+    status: &str,
+    now: &str,
+) -> Result<bool> {
+    let (account_id, current): (String, String) = conn
+        .query_row(
+            "SELECT account_id, status FROM transactions WHERE id = ?1 AND deleted_at IS NULL",
+            [id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .map_err(|_| AppError::NotFound("Transaction not found".to_string()))?;
+
+    if current == status {
+        return Ok(false);
+    }
+    ensure_transaction_unlocked(conn, id)?;
+
+    if status_rank(&current)?.abs_diff(status_rank(status)?) != 1 {
+        return Err(AppError::Validation(format!(
+            "Cannot change status from {} to {}",
+            current, status
+        )));
+    }
+
+    conn.execute(
+        "UPDATE transactions SET status = ?1, updated_at = ?2 WHERE id = ?3",
+        rusqlite::params![status, now, id],
+    )?;
+
+    conn.execute(
+        "INSERT INTO transaction_status_history (id, transaction_id, from_status, to_status, changed_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        rusqlite::params![Uuid::new_v4().to_string(), id, current, status, now],
+    )?;
+
+    // Pending amounts are held out of the available balance
+    refresh_available_balance(conn, &account_id)?;
+
+    Ok(true)
+}
+
+/// Columns selected for a full `Transaction`, in the order `transaction_from_row` expects
+pub(crate) const TRANSACTION_COLUMNS: &str =
+    "id, account_id, date, posted_date, amount, payee, original_payee,
+     category_id, notes, memo, check_number, transaction_type, status,
+     is_recurring, recurring_transaction_id, transfer_id, transfer_account_id,
+     import_id, import_source, import_batch_id, is_split, parent_transaction_id,
+     created_at, updated_at, original_amount, original_currency, is_hold, hold_expires_at,
+     tags, exclude_from_budget";
+
+pub(crate) fn transaction_from_row(row: &rusqlite::Row) -> rusqlite::Result<Transaction> {
+    Ok(Transaction {
+        id: row.get(0)?,
+        account_id: row.get(1)?,
+        date: row.get(2)?,
+        posted_date: row.get(3)?,
+        amount: row.get(4)?,
+        payee: row.get(5)?,
+        original_payee: row.get(6)?,
+        category_id: row.get(7)?,
+        notes: row.get(8)?,
+        memo: row.get(9)?,
+        check_number: row.get(10)?,
+        transaction_type: row.get(11)?,
+        status: row.get(12)?,
+        is_recurring: row.get(13)?,
+        recurring_transaction_id: row.get(14)?,
+        transfer_id: row.get(15)?,
+        transfer_account_id: row.get(16)?,
+        import_id: row.get(17)?,
+        import_source: row.get(18)?,
+        import_batch_id: row.get(19)?,
+        is_split: row.get(20)?,
+        parent_transaction_id: row.get(21)?,
+        created_at: row.get(22)?,
+        updated_at: row.get(23)?,
+        original_amount: row.get(24)?,
+        original_currency: row.get(25)?,
+        is_hold: row.get(26)?,
+        hold_expires_at: row.get(27)?,
+        tags: row
+            .get::<_, Option<String>>(28)?
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default(),
+        exclude_from_budget: row.get(29)?,
+    })
+}
+
+/// Account-currency amount for a transaction input. Foreign purchases may send only the
+/// original amount and currency plus the exchange rate, which is converted here.
+pub(crate) fn input_amount(data: &serde_json::Value) -> Result<Option<i64>> {
+    if data["originalAmount"].is_i64() && data["originalCurrency"].as_str().is_none() {
+        return Err(AppError::Validation(
+            "Original currency is required with an original amount".to_string(),
+        ));
+    }
+
+    match (data["amount"].as_i64(), data["originalAmount"].as_i64()) {
+        (Some(amount), _) => Ok(Some(amount)),
+        (None, Some(original)) => match data["exchangeRate"].as_f64() {
+            Some(rate) if rate > 0.0 => Ok(Some((original as f64 * rate).round() as i64)),
+            _ => Err(AppError::Validation(
+                "An exchange rate is required to convert the original amount".to_string(),
+            )),
+        },
+        (None, None) => Ok(None),
+    }
+}
+
+/// SQL conditions (each prefixed with ` AND `) and their parameters for a set of filters.
+/// Amount bounds compare against the absolute amount, matching how the filter bar enters them.
+pub(crate) fn transaction_filter_clause(
+    f: &TransactionFilters,
+) -> (String, Vec<rusqlite::types::Value>) {
+    use rusqlite::types::Value;
+
+    let mut clause = String::new();
+    let mut params: Vec<Value> = vec![];
+
+    if let Some(ref account_id) = f.account_id {
+        clause.push_str(" AND account_id = ?");
+        params.push(Value::Text(account_id.clone()));
+    }
+    if let Some(ref category_id) = f.category_id {
+        clause.push_str(" AND category_id = ?");
+        params.push(Value::Text(category_id.clone()));
+    }
+    if let Some(ref start_date) = f.start_date {
+        clause.push_str(" AND date >= ?");
+        params.push(Value::Text(start_date.clone()));
+    }
+    if let Some(ref end_date) = f.end_date {
+        clause.push_str(" AND date <= ?");
+        params.push(Value::Text(end_date.clone()));
+    }
+    if let Some(ref status) = f.status {
+        clause.push_str(" AND status = ?");
+        params.push(Value::Text(status.clone()));
+    }
+    if let Some(min_amount) = f.min_amount {
+        clause.push_str(" AND ABS(amount) >= ?");
+        params.push(Value::Integer(min_amount));
+    }
+    if let Some(max_amount) = f.max_amount {
+        clause.push_str(" AND ABS(amount) <= ?");
+        params.push(Value::Integer(max_amount));
+    }
+    if let Some(is_transfer) = f.is_transfer {
+        clause.push_str(if is_transfer {
+            " AND transfer_id IS NOT NULL"
+        } else {
+            " AND transfer_id IS NULL"
+        });
+    }
+    if let Some(ref check_number) = f.check_number {
+        if !check_number.is_empty() {
+            clause.push_str(" AND check_number = ?");
+            params.push(Value::Text(check_number.trim().to_string()));
+        }
+    }
+    if let Some(ref search) = f.search_query {
+        if !search.is_empty() {
+            clause.push_str(" AND (payee LIKE ? OR notes LIKE ? OR memo LIKE ?)");
+            let pattern = format!("%{}%", search);
+            params.push(Value::Text(pattern.clone()));
+            params.push(Value::Text(pattern.clone()));
+            params.push(Value::Text(pattern));
+        }
+    }
+
+    (clause, params)
+}
+
 #[tauri::command]
 pub fn list_transactions(
     filters: Option<TransactionFilters>,
@@ -13,141 +267,136 @@ pub fn list_transactions(
     let database = db.lock().unwrap();
     let conn = database.get_connection()?;
 
-    let mut query = String::from(
-        "SELECT id, account_id, date, posted_date, amount, payee, original_payee,
-                category_id, notes, memo, check_number, transaction_type, status,
-                is_recurring, recurring_transaction_id, transfer_id, transfer_account_id,
-                import_id, import_source, import_batch_id, is_split, parent_transaction_id,
-                created_at, updated_at
-         FROM transactions
-         WHERE deleted_at IS NULL"
+    let mut query = format!(
+        "SELECT {} FROM transactions WHERE deleted_at IS NULL",
+        TRANSACTION_COLUMNS
     );
 
-    let mut params: Vec<String> = vec![];
-
-    if let Some(ref f) = filters {
-        if let Some(ref account_id) = f.account_id {
-            query.push_str(" AND account_id = ?");
-            params.push(account_id.clone());
-        }
-        if let Some(ref category_id) = f.category_id {
-            query.push_str(" AND category_id = ?");
-            params.push(category_id.clone());
-        }
-        if let Some(ref start_date) = f.start_date {
-            query.push_str(" AND date >= ?");
-            params.push(start_date.clone());
-        }
-        if let Some(ref end_date) = f.end_date {
-            query.push_str(" AND date <= ?");
-            params.push(end_date.clone());
-        }
-        if let Some(ref search) = f.search_query {
-            if !search.is_empty() {
-                query.push_str(" AND (payee LIKE ? OR notes LIKE ? OR memo LIKE ?)");
-                let pattern = format!("%{}%", search);
-                params.push(pattern.clone());
-                params.push(pattern.clone());
-                params.push(pattern);
-            }
-        }
-    }
+    let (clause, params) = filters
+        .as_ref()
+        .map(transaction_filter_clause)
+        .unwrap_or_default();
+    query.push_str(&clause);
 
     query.push_str(" ORDER BY date DESC, created_at DESC LIMIT 1000");
 
     let mut stmt = conn.prepare(&query)?;
 
     let transactions = stmt
-        .query_map(rusqlite::params_from_iter(params.iter()), |row| {
-            Ok(Transaction {
-                id: row.get(0)?,
-                account_id: row.get(1)?,
-                date: row.get(2)?,
-                posted_date: row.get(3)?,
-                amount: row.get(4)?,
-                payee: row.get(5)?,
-                original_payee: row.get(6)?,
-                category_id: row.get(7)?,
-                notes: row.get(8)?,
-                memo: row.get(9)?,
-                check_number: row.get(10)?,
-                transaction_type: row.get(11)?,
-                status: row.get(12)?,
-                is_recurring: row.get(13)?,
-                recurring_transaction_id: row.get(14)?,
-                transfer_id: row.get(15)?,
-                transfer_account_id: row.get(16)?,
-                import_id: row.get(17)?,
-                import_source: row.get(18)?,
-                import_batch_id: row.get(19)?,
-                is_split: row.get(20)?,
-                parent_transaction_id: row.get(21)?,
-                created_at: row.get(22)?,
-                updated_at: row.get(23)?,
-            })
-        })?
+        .query_map(rusqlite::params_from_iter(params.iter()), transaction_from_row)?
         .filter_map(|r| r.ok())
         .collect();
 
     Ok(transactions)
 }
 
+#[tauri::command]
+pub fn get_transaction_stats(
+    filters: Option<TransactionFilters>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<TransactionStats> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let (clause, params) = filters
+        .as_ref()
+        .map(transaction_filter_clause)
+        .unwrap_or_default();
+
+    let (count, total_inflow, total_outflow): (i64, i64, i64) = conn.query_row(
+        &format!(
+            "SELECT COUNT(*),
+                    COALESCE(SUM(CASE WHEN amount > 0 THEN amount ELSE 0 END), 0),
+                    COALESCE(SUM(CASE WHEN amount < 0 THEN -amount ELSE 0 END), 0)
+             FROM transactions WHERE deleted_at IS NULL{}",
+            clause
+        ),
+        rusqlite::params_from_iter(params.iter()),
+        |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+    )?;
+
+    let net = total_inflow - total_outflow;
+    let average = if count > 0 { net / count } else { 0 };
+
+    Ok(TransactionStats {
+        count,
+        total_inflow,
+        total_outflow,
+        net,
+        average,
+    })
+}
+
 #[tauri::command]
 pub fn get_transaction(id: String, db: State<'_, Mutex<Database>>) -> Result<Transaction> {
     let database = db.lock().unwrap();
     let conn = database.get_connection()?;
 
     conn.query_row(
-        "SELECT id, account_id, date, posted_date, amount, payee, original_payee,
-                category_id, notes, memo, check_number, transaction_type, status,
-                is_recurring, recurring_transaction_id, transfer_id, transfer_account_id,
-                import_id, import_source, import_batch_id, is_split, parent_transaction_id,
-                created_at, updated_at
-         FROM transactions
-         WHERE id = ?1 AND deleted_at IS NULL",
+        &format!(
+            "SELECT {} FROM transactions WHERE id = ?1 AND deleted_at IS NULL",
+            TRANSACTION_COLUMNS
+        ),
         [&id],
-        |row| {
-            Ok(Transaction {
-                id: row.get(0)?,
-                account_id: row.get(1)?,
-                date: row.get(2)?,
-                posted_date: row.get(3)?,
-                amount: row.get(4)?,
-                payee: row.get(5)?,
-                original_payee: row.get(6)?,
-                category_id: row.get(7)?,
-                notes: row.get(8)?,
-                memo: row.get(9)?,
-                check_number: row.get(10)?,
-                transaction_type: row.get(11)?,
-                status: row.get(12)?,
-                is_recurring: row.get(13)?,
-                recurring_transaction_id: row.get(14)?,
-                transfer_id: row.get(15)?,
-                transfer_account_id: row.get(16)?,
-                import_id: row.get(17)?,
-                import_source: row.get(18)?,
-                import_batch_id: row.get(19)?,
-                is_split: row.get(20)?,
-                parent_transaction_id: row.get(21)?,
-                created_at: row.get(22)?,
-                updated_at: row.get(23)?,
-            })
-        },
+        transaction_from_row,
     )
     .map_err(|_| AppError::NotFound("Transaction not found".to_string()))
 }
 
-#[tauri::command]
-pub fn create_transaction(
-    data: serde_json::Value,
-    db: State<'_, Mutex<Database>>,
-) -> Result<Transaction> {
-    let database = db.lock().unwrap();
-    let conn = database.get_connection()?;
+/// Tags from command input as stored: trimmed, blanks and repeats dropped, JSON encoded.
+/// `None` when the input has no tags.
+fn input_tags(data: &serde_json::Value) -> Option<String> {
+    let mut tags: Vec<&str> = Vec::new();
+    for tag in data["tags"].as_array()?.iter().filter_map(|t| t.as_str()) {
+        let tag = tag.trim();
+        if !tag.is_empty() && !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    (!tags.is_empty()).then(|| serde_json::json!(tags).to_string())
+}
 
+/// Insert a transaction from command input and apply it to the account balance,
+/// returning the new transaction's id. Nothing can be added on or before a locked closing
+/// statement.
+pub(crate) fn insert_transaction(conn: &Connection, data: &serde_json::Value) -> Result<String> {
     let id = Uuid::new_v4().to_string();
     let now = chrono::Utc::now().to_rfc3339();
+    let amount = input_amount(data)?.unwrap_or(0);
+    let original_currency = data["originalCurrency"].as_str().map(|c| c.trim().to_uppercase());
+    let status = data["status"].as_str().unwrap_or("cleared");
+    status_rank(status)?;
+    ensure_statement_unlocked(
+        conn,
+        data["accountId"].as_str().unwrap_or(""),
+        data["date"].as_str().unwrap_or(""),
+    )?;
+
+    // Resolve the typed payee to its canonical name, falling back to its default category
+    let raw_payee = data["payee"].as_str();
+    let resolved = match raw_payee {
+        Some(raw) => resolve_payee(conn, raw)?,
+        None => None,
+    };
+    let payee = resolved.as_ref().map(|p| p.name.as_str()).or(raw_payee);
+    let original_payee = data["originalPayee"].as_str().or(raw_payee);
+    let category_id = data["categoryId"]
+        .as_str()
+        .map(|s| s.to_string())
+        .or_else(|| resolved.as_ref().and_then(|p| p.default_category_id.clone()));
+
+    // Notes from a template are expanded once here and stored as plain text
+    let notes = match data["noteTemplateId"].as_str() {
+        Some(template_id) => Some(render_note_template(
+            conn,
+            template_id,
+            data["noteVariables"].as_object(),
+            amount,
+            payee,
+            data["date"].as_str().unwrap_or(""),
+        )?),
+        None => data["notes"].as_str().map(|s| s.to_string()),
+    };
 
     conn.execute(
         "INSERT INTO transactions (
@@ -155,22 +404,22 @@ pub fn create_transaction(
             category_id, notes, memo, check_number, transaction_type, status,
             is_recurring, recurring_transaction_id, transfer_id, transfer_account_id,
             import_id, import_source, import_batch_id, is_split, parent_transaction_id,
-            created_at, updated_at
-        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24)",
+            created_at, updated_at, original_amount, original_currency, tags, exclude_from_budget
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27, ?28)",
         rusqlite::params![
             id,
             data["accountId"].as_str().unwrap_or(""),
             data["date"].as_str().unwrap_or(""),
             data["postedDate"].as_str(),
-            data["amount"].as_i64().unwrap_or(0),
-            data["payee"].as_str(),
-            data["originalPayee"].as_str(),
-            data["categoryId"].as_str(),
-            data["notes"].as_str(),
+            amount,
+            payee,
+            original_payee,
+            category_id,
+            notes,
             data["memo"].as_str(),
             data["checkNumber"].as_str(),
             data["transactionType"].as_str(),
-            data["status"].as_str().unwrap_or("cleared"),
+            status,
             data["isRecurring"].as_bool().unwrap_or(false),
             data["recurringTransactionId"].as_str(),
             data["transferId"].as_str(),
@@ -182,17 +431,38 @@ pub fn create_transaction(
             data["parentTransactionId"].as_str(),
             now,
             now,
+            data["originalAmount"].as_i64(),
+            original_currency,
+            input_tags(data),
+            data["excludeFromBudget"].as_bool().unwrap_or(false),
         ],
     )?;
 
     // Update account balance
-    let amount = data["amount"].as_i64().unwrap_or(0);
     let account_id = data["accountId"].as_str().unwrap_or("");
 
     conn.execute(
         "UPDATE accounts SET current_balance = current_balance + ?1, updated_at = ?2 WHERE id = ?3",
         rusqlite::params![amount, now, account_id],
     )?;
+    refresh_available_balance(conn, account_id)?;
+
+    Ok(id)
+}
+
+#[tauri::command]
+pub fn create_transaction(
+    data: serde_json::Value,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Transaction> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let id = insert_transaction(conn, &data)?;
+
+    raise_budget_alerts(&app, conn);
+    emit_changed(&app, &[TRANSACTIONS_CHANGED, ACCOUNTS_CHANGED]);
 
     drop(database);
     get_transaction(id, db)
@@ -202,6 +472,7 @@ pub fn create_transaction(
 pub fn update_transaction(
     id: String,
     data: serde_json::Value,
+    app: AppHandle,
     db: State<'_, Mutex<Database>>,
 ) -> Result<Transaction> {
     let database = db.lock().unwrap();
@@ -209,12 +480,30 @@ pub fn update_transaction(
 
     let now = chrono::Utc::now().to_rfc3339();
 
+    ensure_transaction_unlocked(conn, &id)?;
+
     // Get old amount for balance adjustment
-    let old_amount: i64 = conn.query_row(
-        "SELECT amount FROM transactions WHERE id = ?1",
+    let (account_id, old_amount): (String, i64) = conn.query_row(
+        "SELECT account_id, amount FROM transactions WHERE id = ?1",
         [&id],
-        |row| row.get(0),
+        |row| Ok((row.get(0)?, row.get(1)?)),
     )?;
+    // Nor can a transaction be moved back into a locked statement period
+    if let Some(date) = data["date"].as_str() {
+        ensure_statement_unlocked(conn, &account_id, date)?;
+    }
+
+    let payee = match data["payee"].as_str() {
+        Some(raw) => resolve_payee(conn, raw)?.map(|p| p.name),
+        None => None,
+    };
+    let amount = input_amount(&data)?;
+
+    // Status changes go through the same transition rules as set_transaction_status,
+    // checked first so a rejected transition leaves the transaction untouched
+    if let Some(status) = data["status"].as_str() {
+        change_transaction_status(conn, &id, status, &now)?;
+    }
 
     conn.execute(
         "UPDATE transactions SET
@@ -223,31 +512,32 @@ pub fn update_transaction(
             payee = COALESCE(?3, payee),
             category_id = ?4,
             notes = ?5,
-            status = COALESCE(?6, status),
-            updated_at = ?7
-         WHERE id = ?8",
+            original_amount = COALESCE(?6, original_amount),
+            original_currency = COALESCE(?7, original_currency),
+            tags = CASE WHEN ?8 THEN ?9 ELSE tags END,
+            updated_at = ?10,
+            exclude_from_budget = COALESCE(?12, exclude_from_budget)
+         WHERE id = ?11",
         rusqlite::params![
             data["date"].as_str(),
-            data["amount"].as_i64(),
-            data["payee"].as_str(),
+            amount,
+            payee,
             data["categoryId"].as_str(),
             data["notes"].as_str(),
-            data["status"].as_str(),
+            data["originalAmount"].as_i64(),
+            data["originalCurrency"].as_str().map(|c| c.trim().to_uppercase()),
+            data.get("tags").is_some(),
+            input_tags(&data),
             now,
             id,
+            data["excludeFromBudget"].as_bool(),
         ],
     )?;
 
     // Adjust account balance if amount changed
-    if let Some(new_amount) = data["amount"].as_i64() {
+    if let Some(new_amount) = amount {
         let diff = new_amount - old_amount;
         if diff != 0 {
-            let account_id: String = conn.query_row(
-                "SELECT account_id FROM transactions WHERE id = ?1",
-                [&id],
-                |row| row.get(0),
-            )?;
-
             conn.execute(
                 "UPDATE accounts SET current_balance = current_balance + ?1, updated_at = ?2 WHERE id = ?3",
                 rusqlite::params![diff, now, account_id],
@@ -255,45 +545,315 @@ pub fn update_transaction(
         }
     }
 
+    // An edit that leaves the transaction uncategorized gives the rules another go
+    if auto_apply_rules_enabled(conn)? {
+        apply_rules(conn, Some(std::slice::from_ref(&id)), true)?;
+    }
+
+    refresh_available_balance(conn, &account_id)?;
+
+    raise_budget_alerts(&app, conn);
+    emit_changed(&app, &[TRANSACTIONS_CHANGED, ACCOUNTS_CHANGED]);
+
     drop(database);
     get_transaction(id, db)
 }
 
+/// Soft delete one transaction and reverse its effect on the account balance
+pub(crate) fn soft_delete_transaction(conn: &Connection, id: &str, now: &str) -> Result<()> {
+    ensure_transaction_unlocked(conn, id)?;
+    return_gift_card_payment(conn, id, now)?;
+
+    // Get transaction for balance adjustment
+    let (account_id, amount): (String, i64) = conn.query_row(
+        "SELECT account_id, amount FROM transactions WHERE id = ?1",
+        [id],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+
+    // Soft delete
+    conn.execute(
+        "UPDATE transactions SET deleted_at = ?1 WHERE id = ?2",
+        [now, id],
+    )?;
+
+    // Reverse balance
+    conn.execute(
+        "UPDATE accounts SET current_balance = current_balance - ?1, updated_at = ?2 WHERE id = ?3",
+        rusqlite::params![amount, now, account_id],
+    )?;
+    refresh_available_balance(conn, &account_id)?;
+
+    Ok(())
+}
+
 #[tauri::command]
-pub fn delete_transactions(ids: Vec<String>, db: State<'_, Mutex<Database>>) -> Result<()> {
+pub fn delete_transactions(
+    ids: Vec<String>,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<()> {
     let database = db.lock().unwrap();
     let conn = database.get_connection()?;
 
     let now = chrono::Utc::now().to_rfc3339();
 
     for id in ids {
-        // Get transaction for balance adjustment
-        let (account_id, amount): (String, i64) = conn.query_row(
-            "SELECT account_id, amount FROM transactions WHERE id = ?1",
-            [&id],
-            |row| Ok((row.get(0)?, row.get(1)?)),
-        )?;
+        soft_delete_transaction(conn, &id, &now)?;
+    }
 
-        // Soft delete
-        conn.execute(
-            "UPDATE transactions SET deleted_at = ?1 WHERE id = ?2",
-            [&now, &id],
-        )?;
+    emit_changed(&app, &[TRANSACTIONS_CHANGED, ACCOUNTS_CHANGED]);
 
-        // Reverse balance
-        conn.execute(
-            "UPDATE accounts SET current_balance = current_balance - ?1, updated_at = ?2 WHERE id = ?3",
-            rusqlite::params![amount, now, account_id],
+    Ok(())
+}
+
+#[tauri::command]
+pub fn delete_transactions_by_filter(
+    filters: TransactionFilters,
+    dry_run: bool,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<BulkDeleteResult> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let (clause, params) = transaction_filter_clause(&filters);
+    if clause.is_empty() {
+        return Err(AppError::Validation(
+            "At least one filter is required to delete by filter".to_string(),
+        ));
+    }
+    // Reconciled transactions in closed months and transactions covered by a locked
+    // statement are locked, so they're left in place
+    let clause = format!(
+        "{} AND NOT (status = 'reconciled' AND substr(date, 1, 7) IN (SELECT month FROM month_closes))
+         AND NOT EXISTS (
+             SELECT 1 FROM account_statements s
+             WHERE s.account_id = transactions.account_id AND s.is_locked = 1
+               AND s.statement_date >= transactions.date
+         )",
+        clause
+    );
+
+    // Deleting reverses each amount, so the balance change is the negated sum
+    let mut stmt = conn.prepare(&format!(
+        "SELECT account_id,
+                (SELECT a.name FROM accounts a WHERE a.id = transactions.account_id),
+                COUNT(*),
+                -COALESCE(SUM(amount), 0)
+         FROM transactions
+         WHERE deleted_at IS NULL{}
+         GROUP BY account_id
+         ORDER BY account_id",
+        clause
+    ))?;
+
+    let accounts: Vec<AccountBalanceImpact> = stmt
+        .query_map(rusqlite::params_from_iter(params.iter()), |row| {
+            Ok(AccountBalanceImpact {
+                account_id: row.get(0)?,
+                account_name: row.get(1)?,
+                count: row.get(2)?,
+                balance_change: row.get(3)?,
+            })
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let count = accounts.iter().map(|a| a.count).sum();
+
+    if !dry_run && count > 0 {
+        let now = chrono::Utc::now().to_rfc3339();
+        let tx = conn.unchecked_transaction()?;
+
+        // Gift card payments go back to their cards, charging the whole purchases to the
+        // accounts again, so those amounts are reversed along with the rest
+        let mut returned: std::collections::HashMap<String, i64> = std::collections::HashMap::new();
+        let paid_by_card: Vec<(String, String)> = tx
+            .prepare(&format!(
+                "SELECT id, account_id FROM transactions
+                 WHERE deleted_at IS NULL{}
+                   AND id IN (SELECT transaction_id FROM gift_card_usages)",
+                clause
+            ))?
+            .query_map(rusqlite::params_from_iter(params.iter()), |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+        for (id, account_id) in paid_by_card {
+            *returned.entry(account_id).or_insert(0) += return_gift_card_payment(&tx, &id, &now)?;
+        }
+
+        let mut delete_params = vec![rusqlite::types::Value::Text(now.clone())];
+        delete_params.extend(params);
+        tx.execute(
+            &format!(
+                "UPDATE transactions SET deleted_at = ? WHERE deleted_at IS NULL{}",
+                clause
+            ),
+            rusqlite::params_from_iter(delete_params.iter()),
         )?;
+
+        for impact in &accounts {
+            tx.execute(
+                "UPDATE accounts SET current_balance = current_balance + ?1, updated_at = ?2 WHERE id = ?3",
+                rusqlite::params![
+                    impact.balance_change + returned.get(&impact.account_id).copied().unwrap_or(0),
+                    now,
+                    impact.account_id
+                ],
+            )?;
+            refresh_available_balance(&tx, &impact.account_id)?;
+        }
+
+        tx.commit()?;
+
+        emit_changed(&app, &[TRANSACTIONS_CHANGED, ACCOUNTS_CHANGED]);
+    }
+
+    Ok(BulkDeleteResult {
+        dry_run,
+        count,
+        accounts,
+    })
+}
+
+#[tauri::command]
+pub fn list_deleted_transactions(db: State<'_, Mutex<Database>>) -> Result<Vec<Transaction>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM transactions
+         WHERE deleted_at IS NOT NULL
+         ORDER BY deleted_at DESC, date DESC
+         LIMIT 1000",
+        TRANSACTION_COLUMNS
+    ))?;
+
+    let transactions = stmt
+        .query_map([], transaction_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(transactions)
+}
+
+/// Bring one transaction back from the trash and put its amount back on the account
+/// balance. A transaction that isn't in the trash is left alone, so its amount isn't
+/// counted twice.
+pub(crate) fn restore_transaction(conn: &Connection, id: &str, now: &str) -> Result<()> {
+    let deleted: Option<(String, String, i64)> = conn
+        .query_row(
+            "SELECT account_id, date, amount FROM transactions WHERE id = ?1 AND deleted_at IS NOT NULL",
+            [id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .optional()?;
+    let Some((account_id, date, amount)) = deleted else {
+        return Ok(());
+    };
+    ensure_statement_unlocked(conn, &account_id, &date)?;
+
+    conn.execute(
+        "UPDATE transactions SET deleted_at = NULL, updated_at = ?1 WHERE id = ?2",
+        [now, id],
+    )?;
+
+    // Re-apply the amount that was reversed on delete
+    conn.execute(
+        "UPDATE accounts SET current_balance = current_balance + ?1, updated_at = ?2 WHERE id = ?3",
+        rusqlite::params![amount, now, account_id],
+    )?;
+    refresh_available_balance(conn, &account_id)?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn restore_transactions(
+    ids: Vec<String>,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<()> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let now = chrono::Utc::now().to_rfc3339();
+
+    // All-or-nothing, so a locked statement partway through leaves nothing restored
+    let tx = conn.unchecked_transaction()?;
+    for id in &ids {
+        restore_transaction(&tx, id, &now)?;
+    }
+    tx.commit()?;
+
+    emit_changed(&app, &[TRANSACTIONS_CHANGED, ACCOUNTS_CHANGED]);
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn set_transaction_status(
+    ids: Vec<String>,
+    status: String,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<()> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let now = chrono::Utc::now().to_rfc3339();
+
+    // All-or-nothing so one invalid transition doesn't leave a half-updated selection
+    let tx = conn.unchecked_transaction()?;
+    for id in &ids {
+        change_transaction_status(&tx, id, &status, &now)?;
     }
+    tx.commit()?;
+
+    emit_changed(&app, &[TRANSACTIONS_CHANGED, ACCOUNTS_CHANGED]);
 
     Ok(())
 }
 
+#[tauri::command]
+pub fn get_transaction_status_history(
+    transaction_id: String,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<TransactionStatusChange>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let mut stmt = conn.prepare(
+        "SELECT id, transaction_id, from_status, to_status, changed_at
+         FROM transaction_status_history
+         WHERE transaction_id = ?1
+         ORDER BY changed_at, rowid",
+    )?;
+
+    let history = stmt
+        .query_map([&transaction_id], |row| {
+            Ok(TransactionStatusChange {
+                id: row.get(0)?,
+                transaction_id: row.get(1)?,
+                from_status: row.get(2)?,
+                to_status: row.get(3)?,
+                changed_at: row.get(4)?,
+            })
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(history)
+}
+
 #[tauri::command]
 pub fn bulk_categorize(
     ids: Vec<String>,
     category_id: String,
+    app: AppHandle,
     db: State<'_, Mutex<Database>>,
 ) -> Result<()> {
     let database = db.lock().unwrap();
@@ -301,6 +861,9 @@ pub fn bulk_categorize(
 
     let now = chrono::Utc::now().to_rfc3339();
 
+    for id in &ids {
+        ensure_transaction_unlocked(conn, id)?;
+    }
     for id in ids {
         conn.execute(
             "UPDATE transactions SET category_id = ?1, updated_at = ?2 WHERE id = ?3",
@@ -308,57 +871,100 @@ pub fn bulk_categorize(
         )?;
     }
 
+    raise_budget_alerts(&app, conn);
+    emit_changed(&app, &[TRANSACTIONS_CHANGED]);
+
     Ok(())
 }
 
+#[tauri::command]
+pub fn next_check_number(account_id: String, db: State<'_, Mutex<Database>>) -> Result<Option<String>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    // Only purely numeric check numbers can be incremented
+    let last: Option<i64> = conn.query_row(
+        "SELECT MAX(CAST(check_number AS INTEGER))
+         FROM transactions
+         WHERE account_id = ?1
+           AND deleted_at IS NULL
+           AND check_number GLOB '[0-9]*'
+           AND check_number NOT GLOB '*[^0-9]*'",
+        [&account_id],
+        |row| row.get(0),
+    )?;
+
+    Ok(last.map(|n| (n + 1).to_string()))
+}
+
+#[tauri::command]
+pub fn detect_check_number_gaps(
+    account_id: Option<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<CheckNumberGap>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let mut stmt = conn.prepare(
+        "SELECT DISTINCT a.id, a.name, CAST(t.check_number AS INTEGER) AS num
+         FROM transactions t
+         JOIN accounts a ON t.account_id = a.id
+         WHERE t.deleted_at IS NULL
+           AND a.deleted_at IS NULL
+           AND a.account_type = 'checking'
+           AND (?1 IS NULL OR a.id = ?1)
+           AND t.check_number GLOB '[0-9]*'
+           AND t.check_number NOT GLOB '*[^0-9]*'
+         ORDER BY a.id, num",
+    )?;
+
+    let rows: Vec<(String, String, i64)> = stmt
+        .query_map([&account_id], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let gaps = rows
+        .windows(2)
+        .filter(|pair| pair[0].0 == pair[1].0)
+        .filter_map(|pair| {
+            let (ref account_id, ref account_name, prev) = pair[0];
+            let next = pair[1].2;
+            let missing_count = next - prev - 1;
+
+            if !(1..=MAX_CHECK_NUMBER_GAP).contains(&missing_count) {
+                return None;
+            }
+
+            Some(CheckNumberGap {
+                account_id: account_id.clone(),
+                account_name: account_name.clone(),
+                first_missing: prev + 1,
+                last_missing: next - 1,
+                missing_count,
+            })
+        })
+        .collect();
+
+    Ok(gaps)
+}
+
 #[tauri::command]
 pub fn detect_transfers(db: State<'_, Mutex<Database>>) -> Result<Vec<TransferCandidate>> {
     let database = db.lock().unwrap();
     let conn = database.get_connection()?;
 
     // Get unlinked transactions from the last 90 days
-    let mut stmt = conn.prepare(
-        "SELECT id, account_id, date, posted_date, amount, payee, original_payee,
-                category_id, notes, memo, check_number, transaction_type, status,
-                is_recurring, recurring_transaction_id, transfer_id, transfer_account_id,
-                import_id, import_source, import_batch_id, is_split, parent_transaction_id,
-                created_at, updated_at
-         FROM transactions
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM transactions
          WHERE deleted_at IS NULL
            AND transfer_id IS NULL
            AND date >= date('now', '-90 days')
-         ORDER BY date DESC"
-    )?;
+         ORDER BY date DESC",
+        TRANSACTION_COLUMNS
+    ))?;
 
     let transactions: Vec<Transaction> = stmt
-        .query_map([], |row| {
-            Ok(Transaction {
-                id: row.get(0)?,
-                account_id: row.get(1)?,
-                date: row.get(2)?,
-                posted_date: row.get(3)?,
-                amount: row.get(4)?,
-                payee: row.get(5)?,
-                original_payee: row.get(6)?,
-                category_id: row.get(7)?,
-                notes: row.get(8)?,
-                memo: row.get(9)?,
-                check_number: row.get(10)?,
-                transaction_type: row.get(11)?,
-                status: row.get(12)?,
-                is_recurring: row.get(13)?,
-                recurring_transaction_id: row.get(14)?,
-                transfer_id: row.get(15)?,
-                transfer_account_id: row.get(16)?,
-                import_id: row.get(17)?,
-                import_source: row.get(18)?,
-                import_batch_id: row.get(19)?,
-                is_split: row.get(20)?,
-                parent_transaction_id: row.get(21)?,
-                created_at: row.get(22)?,
-                updated_at: row.get(23)?,
-            })
-        })?
+        .query_map([], transaction_from_row)?
         .filter_map(|r| r.ok())
         .collect();
 
@@ -407,6 +1013,239 @@ pub fn detect_transfers(db: State<'_, Mutex<Database>>) -> Result<Vec<TransferCa
     Ok(candidates.into_iter().take(20).collect())
 }
 
+/// Lowercase alphabetic words of a payee, dropping store numbers, dates, and reference codes
+fn payee_words(payee: &str) -> Vec<String> {
+    payee
+        .to_lowercase()
+        .split(|c: char| !c.is_alphabetic())
+        .filter(|w| w.len() > 1)
+        .map(|w| w.to_string())
+        .collect()
+}
+
+/// How alike two payees are, from 0 to 1: shared words over total words, with full credit
+/// when one payee's words are a prefix of the other's ("AMAZON" vs "AMAZON MKTPLACE 123")
+pub(crate) fn duplicate_payee_similarity(payee_a: &Option<String>, payee_b: &Option<String>) -> f64 {
+    let (a, b) = match (payee_a, payee_b) {
+        (Some(a), Some(b)) => (payee_words(a), payee_words(b)),
+        (None, None) => return 0.5,
+        _ => return 0.0,
+    };
+    if a.is_empty() || b.is_empty() {
+        return if a == b { 0.5 } else { 0.0 };
+    }
+    if a.starts_with(&b) || b.starts_with(&a) {
+        return 1.0;
+    }
+
+    let shared = a.iter().filter(|w| b.contains(w)).count();
+    let total = a.len() + b.len() - shared;
+    shared as f64 / total as f64
+}
+
+/// Score how likely two same-amount transactions are the same real transaction
+fn duplicate_score(a: &Transaction, b: &Transaction, days_apart: i64) -> Option<f64> {
+    // Different checks, or two halves of a transfer, are never duplicates
+    if let (Some(check_a), Some(check_b)) = (&a.check_number, &b.check_number) {
+        if check_a != check_b {
+            return None;
+        }
+    }
+    if a.transfer_id.is_some() && a.transfer_id == b.transfer_id {
+        return None;
+    }
+
+    let date_score = 1.0 - days_apart as f64 / (DUPLICATE_MAX_DAYS_APART + 1) as f64;
+    let payee_score = duplicate_payee_similarity(&a.payee, &b.payee);
+    let score = date_score * 0.4 + payee_score * 0.6;
+
+    (score >= DUPLICATE_MIN_CONFIDENCE).then_some(score)
+}
+
+/// Rank a transaction by how much the user has filled in, so merges keep the richest copy
+fn completeness(transaction: &Transaction) -> usize {
+    [
+        transaction.category_id.is_some(),
+        transaction.notes.is_some(),
+        transaction.memo.is_some(),
+        transaction.check_number.is_some(),
+        transaction.transfer_id.is_some(),
+        transaction.status == "reconciled",
+    ]
+    .iter()
+    .filter(|filled| **filled)
+    .count()
+}
+
+/// Scan every account for likely duplicate transactions: the same amount within a few days
+/// and a similar payee. Pairs are chained into groups, each ready for `merge_transactions`.
+#[tauri::command]
+pub fn find_duplicate_transactions(db: State<'_, Mutex<Database>>) -> Result<Vec<DuplicateGroup>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM transactions
+         WHERE deleted_at IS NULL AND is_hold = 0 AND amount != 0
+         ORDER BY amount, date",
+        TRANSACTION_COLUMNS
+    ))?;
+
+    let transactions: Vec<Transaction> = stmt
+        .query_map([], transaction_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+    let dates: Vec<Option<chrono::NaiveDate>> = transactions
+        .iter()
+        .map(|t| chrono::NaiveDate::parse_from_str(&t.date, "%Y-%m-%d").ok())
+        .collect();
+
+    // Union-find over matching pairs, so A~B and B~C land in one group
+    let mut parent: Vec<usize> = (0..transactions.len()).collect();
+    fn root(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+
+    let mut scores: std::collections::HashMap<usize, Vec<f64>> = std::collections::HashMap::new();
+    for i in 0..transactions.len() {
+        for j in i + 1..transactions.len() {
+            // Sorted by amount then date, so stop at the first different amount or distant date
+            if transactions[j].amount != transactions[i].amount {
+                break;
+            }
+            let days_apart = match (dates[i], dates[j]) {
+                (Some(a), Some(b)) => (b - a).num_days().abs(),
+                _ => continue,
+            };
+            if days_apart > DUPLICATE_MAX_DAYS_APART {
+                break;
+            }
+
+            if let Some(score) = duplicate_score(&transactions[i], &transactions[j], days_apart) {
+                let (root_i, root_j) = (root(&mut parent, i), root(&mut parent, j));
+                if root_i != root_j {
+                    parent[root_j] = root_i;
+                }
+                scores.entry(i).or_default().push(score);
+            }
+        }
+    }
+
+    let mut members: std::collections::BTreeMap<usize, Vec<usize>> =
+        std::collections::BTreeMap::new();
+    for i in 0..transactions.len() {
+        let r = root(&mut parent, i);
+        members.entry(r).or_default().push(i);
+    }
+
+    let mut groups: Vec<DuplicateGroup> = members
+        .into_values()
+        .filter(|indices| indices.len() > 1)
+        .map(|indices| {
+            let pair_scores: Vec<f64> = indices
+                .iter()
+                .flat_map(|i| scores.get(i).cloned().unwrap_or_default())
+                .collect();
+            let confidence = pair_scores.iter().sum::<f64>() / pair_scores.len().max(1) as f64;
+
+            let mut group: Vec<Transaction> =
+                indices.into_iter().map(|i| transactions[i].clone()).collect();
+            group.sort_by_key(|t| std::cmp::Reverse(completeness(t)));
+
+            DuplicateGroup {
+                amount: group[0].amount,
+                confidence,
+                transactions: group,
+            }
+        })
+        .collect();
+
+    groups.sort_by(|a, b| {
+        b.confidence
+            .partial_cmp(&a.confidence)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    Ok(groups)
+}
+
+/// Merge duplicates into the transaction being kept: blank category, notes, memo, and check
+/// number on the kept transaction are filled from the duplicates, which are then deleted
+#[tauri::command]
+pub fn merge_transactions(
+    keep_id: String,
+    duplicate_ids: Vec<String>,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Transaction> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    if duplicate_ids.contains(&keep_id) {
+        return Err(AppError::Validation(
+            "A transaction cannot be merged into itself".to_string(),
+        ));
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let tx = conn.unchecked_transaction()?;
+
+    // The kept transaction has to exist before anything is deleted into it
+    let keep_exists: bool = tx.query_row(
+        "SELECT EXISTS(SELECT 1 FROM transactions WHERE id = ?1 AND deleted_at IS NULL)",
+        [&keep_id],
+        |row| row.get(0),
+    )?;
+    if !keep_exists {
+        return Err(AppError::NotFound("Transaction not found".to_string()));
+    }
+    ensure_transaction_unlocked(&tx, &keep_id)?;
+
+    for duplicate_id in &duplicate_ids {
+        let duplicate = tx
+            .query_row(
+                &format!(
+                    "SELECT {} FROM transactions WHERE id = ?1 AND deleted_at IS NULL",
+                    TRANSACTION_COLUMNS
+                ),
+                [duplicate_id],
+                transaction_from_row,
+            )
+            .map_err(|_| AppError::NotFound("Transaction not found".to_string()))?;
+
+        tx.execute(
+            "UPDATE transactions SET
+                category_id = COALESCE(category_id, ?1),
+                notes = COALESCE(notes, ?2),
+                memo = COALESCE(memo, ?3),
+                check_number = COALESCE(check_number, ?4),
+                updated_at = ?5
+             WHERE id = ?6",
+            rusqlite::params![
+                duplicate.category_id,
+                duplicate.notes,
+                duplicate.memo,
+                duplicate.check_number,
+                now,
+                keep_id,
+            ],
+        )?;
+
+        soft_delete_transaction(&tx, duplicate_id, &now)?;
+    }
+
+    tx.commit()?;
+
+    emit_changed(&app, &[TRANSACTIONS_CHANGED, ACCOUNTS_CHANGED]);
+
+    drop(database);
+    get_transaction(keep_id, db)
+}
+
 fn calculate_payee_similarity(payee_a: &Option<String>, payee_b: &Option<String>) -> f64 {
     let transfer_keywords = ["transfer", "xfer", "payment", "ach", "wire", "zelle", "venmo"];
 
@@ -434,24 +1273,40 @@ fn calculate_payee_similarity(payee_a: &Option<String>, payee_b: &Option<String>
 pub fn link_transfer(
     transaction_a_id: String,
     transaction_b_id: String,
+    app: AppHandle,
     db: State<'_, Mutex<Database>>,
 ) -> Result<()> {
     let database = db.lock().unwrap();
     let conn = database.get_connection()?;
 
+    ensure_transaction_unlocked(conn, &transaction_a_id)?;
+    ensure_transaction_unlocked(conn, &transaction_b_id)?;
+    link_transaction_pair(conn, &transaction_a_id, &transaction_b_id)?;
+
+    emit_changed(&app, &[TRANSACTIONS_CHANGED]);
+
+    Ok(())
+}
+
+/// Link two transactions as the two sides of one transfer, returning the new transfer ID
+pub(crate) fn link_transaction_pair(
+    conn: &Connection,
+    transaction_a_id: &str,
+    transaction_b_id: &str,
+) -> Result<String> {
     let transfer_id = Uuid::new_v4().to_string();
     let now = chrono::Utc::now().to_rfc3339();
 
     // Get account IDs
     let account_a: String = conn.query_row(
         "SELECT account_id FROM transactions WHERE id = ?1",
-        [&transaction_a_id],
+        [transaction_a_id],
         |row| row.get(0),
     )?;
 
     let account_b: String = conn.query_row(
         "SELECT account_id FROM transactions WHERE id = ?1",
-        [&transaction_b_id],
+        [transaction_b_id],
         |row| row.get(0),
     )?;
 
@@ -467,11 +1322,177 @@ pub fn link_transfer(
         rusqlite::params![transfer_id, account_a, now, transaction_b_id],
     )?;
 
+    Ok(transfer_id)
+}
+
+/// Transaction type given to both sides of a linked credit card payment
+pub(crate) const CREDIT_CARD_PAYMENT_TYPE: &str = "credit_card_payment";
+
+/// Transaction type of the entry recording a manual balance adjustment
+pub(crate) const BALANCE_ADJUSTMENT_TYPE: &str = "balance_adjustment";
+
+/// Transaction type of estimated interest posted by interest accrual
+pub(crate) const INTEREST_TYPE: &str = "interest";
+
+/// Transaction type of the cash side of an investment buy or sell. The money stays in the
+/// account as the security, so it isn't income or spending.
+pub(crate) const INVESTMENT_TRADE_TYPE: &str = "investment_trade";
+
+/// Transaction type of a dividend paid into an investment account
+pub(crate) const DIVIDEND_TYPE: &str = "dividend";
+
+/// Transaction type of a fee charged by an investment account
+pub(crate) const INVESTMENT_FEE_TYPE: &str = "investment_fee";
+
+#[tauri::command]
+pub fn detect_credit_card_payments(db: State<'_, Mutex<Database>>) -> Result<Vec<TransferCandidate>> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    // Unlinked debits from cash accounts and credits to card accounts from the last 90 days
+    let load = |account_types: &str, amount_sign: &str| -> Result<Vec<Transaction>> {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM transactions
+             WHERE deleted_at IS NULL
+               AND transfer_id IS NULL
+               AND amount {} 0
+               AND date >= date('now', '-90 days')
+               AND account_id IN (
+                   SELECT id FROM accounts
+                   WHERE account_type IN ({}) AND deleted_at IS NULL
+               )
+             ORDER BY date DESC",
+            TRANSACTION_COLUMNS, amount_sign, account_types
+        ))?;
+
+        let transactions = stmt
+            .query_map([], transaction_from_row)?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(transactions)
+    };
+
+    let payments = load("'checking', 'savings'", "<")?;
+    let card_credits = load("'credit_card'", ">")?;
+
+    let mut candidates = Vec::new();
+
+    for payment in &payments {
+        for credit in &card_credits {
+            if payment.amount != -credit.amount {
+                continue;
+            }
+
+            let date_a = chrono::NaiveDate::parse_from_str(&payment.date, "%Y-%m-%d");
+            let date_b = chrono::NaiveDate::parse_from_str(&credit.date, "%Y-%m-%d");
+
+            if let (Ok(paid), Ok(posted)) = (date_a, date_b) {
+                // Card issuers post the credit on or a few days after the bank debit
+                let days_diff = (posted - paid).num_days();
+                if !(-1..=7).contains(&days_diff) {
+                    continue;
+                }
+
+                let date_score = 1.0 - (days_diff.abs() as f64 / 7.0);
+                let payee_score = credit_card_payment_payee_score(&payment.payee, &credit.payee);
+                let confidence = date_score * 0.5 + payee_score * 0.5;
+
+                if confidence > 0.5 {
+                    candidates.push(TransferCandidate {
+                        transaction_a: payment.clone(),
+                        transaction_b: credit.clone(),
+                        confidence,
+                    });
+                }
+            }
+        }
+    }
+
+    candidates.sort_by(|a, b| b.confidence.partial_cmp(&a.confidence).unwrap_or(std::cmp::Ordering::Equal));
+
+    // Each transaction can only be one side of a single payment
+    let mut used: std::collections::HashSet<String> = std::collections::HashSet::new();
+    let candidates = candidates
+        .into_iter()
+        .filter(|c| {
+            if used.contains(&c.transaction_a.id) || used.contains(&c.transaction_b.id) {
+                return false;
+            }
+            used.insert(c.transaction_a.id.clone());
+            used.insert(c.transaction_b.id.clone());
+            true
+        })
+        .collect();
+
+    Ok(candidates)
+}
+
+fn credit_card_payment_payee_score(payment_payee: &Option<String>, credit_payee: &Option<String>) -> f64 {
+    let payment_keywords = ["payment", "pymt", "autopay", "epay", "card", "crd", "thank you"];
+
+    let has_keyword = |payee: &Option<String>| {
+        payee
+            .as_ref()
+            .map(|p| {
+                let lower = p.to_lowercase();
+                payment_keywords.iter().any(|k| lower.contains(k))
+            })
+            .unwrap_or(false)
+    };
+
+    match (has_keyword(payment_payee), has_keyword(credit_payee)) {
+        (true, true) => 1.0,
+        (true, false) | (false, true) => 0.7,
+        (false, false) => 0.3,
+    }
+}
+
+#[tauri::command]
+pub fn link_credit_card_payment(
+    payment_transaction_id: String,
+    card_transaction_id: String,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<()> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let card_account_type: String = conn
+        .query_row(
+            "SELECT a.account_type FROM transactions t
+             JOIN accounts a ON t.account_id = a.id
+             WHERE t.id = ?1",
+            [&card_transaction_id],
+            |row| row.get(0),
+        )
+        .map_err(|_| AppError::NotFound("Transaction not found".to_string()))?;
+
+    if card_account_type != "credit_card" {
+        return Err(AppError::Validation(
+            "Card side of a payment must belong to a credit card account".to_string(),
+        ));
+    }
+
+    link_transaction_pair(conn, &payment_transaction_id, &card_transaction_id)?;
+
+    let now = chrono::Utc::now().to_rfc3339();
+    conn.execute(
+        "UPDATE transactions SET transaction_type = ?1, updated_at = ?2 WHERE id IN (?3, ?4)",
+        rusqlite::params![CREDIT_CARD_PAYMENT_TYPE, now, payment_transaction_id, card_transaction_id],
+    )?;
+
+    emit_changed(&app, &[TRANSACTIONS_CHANGED]);
+
     Ok(())
 }
 
 #[tauri::command]
-pub fn unlink_transfer(transaction_id: String, db: State<'_, Mutex<Database>>) -> Result<()> {
+pub fn unlink_transfer(
+    transaction_id: String,
+    app: AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<()> {
     let database = db.lock().unwrap();
     let conn = database.get_connection()?;
 
@@ -486,10 +1507,55 @@ pub fn unlink_transfer(transaction_id: String, db: State<'_, Mutex<Database>>) -
         let now = chrono::Utc::now().to_rfc3339();
 
         conn.execute(
-            "UPDATE transactions SET transfer_id = NULL, transfer_account_id = NULL, updated_at = ?1 WHERE transfer_id = ?2",
-            [&now, &tid],
+            "UPDATE transactions SET
+                transfer_id = NULL,
+                transfer_account_id = NULL,
+                transaction_type = CASE WHEN transaction_type = ?1 THEN NULL ELSE transaction_type END,
+                updated_at = ?2
+             WHERE transfer_id = ?3",
+            rusqlite::params![CREDIT_CARD_PAYMENT_TYPE, now, tid],
         )?;
     }
 
+    emit_changed(&app, &[TRANSACTIONS_CHANGED]);
+
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locked_statement_refuses_new_transactions() {
+        let conn = Database::in_memory();
+        conn.execute_batch(
+            "INSERT INTO accounts (id, name, account_type, current_balance, opening_balance)
+             VALUES ('checking', 'Checking', 'checking', 0, 0);
+             INSERT INTO account_statements (id, account_id, statement_date, balance)
+             VALUES ('stmt', 'checking', '2024-03-31', 0);",
+        )
+        .unwrap();
+        let input = |date: &str| {
+            serde_json::json!({
+                "accountId": "checking",
+                "date": date,
+                "amount": -1200,
+                "payee": "Bakery",
+            })
+        };
+
+        let err = insert_transaction(&conn, &input("2024-03-15")).unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+        let balance: i64 = conn
+            .query_row(
+                "SELECT current_balance FROM accounts WHERE id = 'checking'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(balance, 0);
+
+        insert_transaction(&conn, &input("2024-04-01")).unwrap();
+    }
+}
@@ -0,0 +1,130 @@
+use crate::db::Database;
+use crate::error::Result;
+use rusqlite::Connection;
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::State;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeatureUsage {
+    pub feature: String,
+    pub uses: i64,
+    pub first_used_at: String,
+    pub last_used_at: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParserStats {
+    pub parser: String,
+    pub runs: i64,
+    pub failures: i64,
+    /// Share of runs that parsed, 0.0 to 1.0
+    pub success_rate: f64,
+    pub last_used_at: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalUsageStats {
+    pub features: Vec<FeatureUsage>,
+    pub parsers: Vec<ParserStats>,
+}
+
+/// Usage stats stay in the local database and are only ever shown to the user. Recording
+/// is best-effort: a failed write never fails the command being counted.
+fn record(conn: &Connection, kind: &str, name: &str, failed: bool) {
+    let now = chrono::Utc::now().to_rfc3339();
+    let _ = conn.execute(
+        "INSERT INTO usage_stats (kind, name, uses, failures, first_used_at, last_used_at)
+         VALUES (?1, ?2, 1, ?3, ?4, ?4)
+         ON CONFLICT(kind, name) DO UPDATE SET
+            uses = uses + 1,
+            failures = failures + excluded.failures,
+            last_used_at = excluded.last_used_at",
+        rusqlite::params![kind, name, failed as i64, now],
+    );
+}
+
+/// Count one use of a feature
+pub(crate) fn record_feature_use(conn: &Connection, feature: &str) {
+    record(conn, "feature", name_or_unknown(feature), false);
+}
+
+/// Count one run of a statement parser and whether it succeeded. Takes the database
+/// rather than a connection since parsers run before the database lock is taken.
+pub(crate) fn record_parser_result(db: &Mutex<Database>, parser: &str, success: bool) {
+    let database = db.lock().unwrap();
+    if let Ok(conn) = database.get_connection() {
+        record(conn, "parser", name_or_unknown(parser), !success);
+    }
+}
+
+fn name_or_unknown(name: &str) -> &str {
+    if name.is_empty() {
+        "unknown"
+    } else {
+        name
+    }
+}
+
+#[tauri::command]
+pub fn get_local_usage_stats(db: State<'_, Mutex<Database>>) -> Result<LocalUsageStats> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    let mut stmt = conn.prepare(
+        "SELECT name, uses, first_used_at, last_used_at FROM usage_stats
+         WHERE kind = 'feature'
+         ORDER BY uses DESC, name",
+    )?;
+    let features = stmt
+        .query_map([], |row| {
+            Ok(FeatureUsage {
+                feature: row.get(0)?,
+                uses: row.get(1)?,
+                first_used_at: row.get(2)?,
+                last_used_at: row.get(3)?,
+            })
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    // Parsers that fail most come first
+    let mut stmt = conn.prepare(
+        "SELECT name, uses, failures, last_used_at FROM usage_stats
+         WHERE kind = 'parser'
+         ORDER BY CAST(failures AS REAL) / uses DESC, failures DESC, name",
+    )?;
+    let parsers = stmt
+        .query_map([], |row| {
+            let runs: i64 = row.get(1)?;
+            let failures: i64 = row.get(2)?;
+            Ok(ParserStats {
+                parser: row.get(0)?,
+                runs,
+                failures,
+                success_rate: if runs > 0 {
+                    (runs - failures) as f64 / runs as f64
+                } else {
+                    0.0
+                },
+                last_used_at: row.get(3)?,
+            })
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(LocalUsageStats { features, parsers })
+}
+
+#[tauri::command]
+pub fn reset_local_usage_stats(db: State<'_, Mutex<Database>>) -> Result<()> {
+    let database = db.lock().unwrap();
+    let conn = database.get_connection()?;
+
+    conn.execute("DELETE FROM usage_stats", [])?;
+
+    Ok(())
+}
@@ -59,7 +59,7 @@ impl Database {
         match conn.pragma_query_value(None, "schema_version", |_| Ok(())) {
             Ok(_) => {
                 // Run migrations if this is a new database
-                self.run_migrations(&conn)?;
+                Self::run_migrations(&conn)?;
                 self.conn = Some(conn);
                 Ok(true)
             }
@@ -103,7 +103,15 @@ impl Database {
         Ok(())
     }
 
-    fn run_migrations(&self, conn: &Connection) -> Result<()> {
+    /// A fresh in-memory database with every migration applied, for tests
+    #[cfg(test)]
+    pub(crate) fn in_memory() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        Self::run_migrations(&conn).unwrap();
+        conn
+    }
+
+    fn run_migrations(conn: &Connection) -> Result<()> {
         // Create tables if they don't exist
         conn.execute_batch(include_str!("../../migrations/001_initial_schema.sql"))?;
 
@@ -118,10 +126,76 @@ impl Database {
             conn.execute_batch(include_str!("../../migrations/002_seed_categories.sql"))?;
         }
 
+        // Apply incremental migrations not yet recorded in the schema version
+        let version: usize = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+
+        for (index, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+            let tx = conn.unchecked_transaction()?;
+            tx.execute_batch(migration)?;
+            tx.pragma_update(None, "user_version", index + 1)?;
+            tx.commit()?;
+        }
+
         Ok(())
     }
 }
 
+/// Incremental schema changes applied in order after the base schema.
+/// The number of applied migrations is tracked in SQLite's `user_version`.
+const MIGRATIONS: &[&str] = &[
+    include_str!("../../migrations/003_payees.sql"),
+    include_str!("../../migrations/004_note_templates.sql"),
+    include_str!("../../migrations/005_transaction_original_currency.sql"),
+    include_str!("../../migrations/006_available_balance.sql"),
+    include_str!("../../migrations/007_transaction_status_history.sql"),
+    include_str!("../../migrations/008_transaction_templates.sql"),
+    include_str!("../../migrations/009_credit_scores.sql"),
+    include_str!("../../migrations/010_insurance_policies.sql"),
+    include_str!("../../migrations/011_gift_cards.sql"),
+    include_str!("../../migrations/012_reward_rules.sql"),
+    include_str!("../../migrations/013_transaction_holds.sql"),
+    include_str!("../../migrations/014_rule_transfer_account.sql"),
+    include_str!("../../migrations/015_bill_due_days.sql"),
+    include_str!("../../migrations/016_planned_purchases.sql"),
+    include_str!("../../migrations/017_account_aliases.sql"),
+    include_str!("../../migrations/018_category_archive.sql"),
+    include_str!("../../migrations/019_usage_stats.sql"),
+    include_str!("../../migrations/020_rule_conditions.sql"),
+    include_str!("../../migrations/021_import_file_hashes.sql"),
+    include_str!("../../migrations/022_rule_actions.sql"),
+    include_str!("../../migrations/023_account_interest_compounding.sql"),
+    include_str!("../../migrations/024_goal_contribution_accounts.sql"),
+    include_str!("../../migrations/025_watch_only_budgets.sql"),
+    include_str!("../../migrations/026_month_closes.sql"),
+    include_str!("../../migrations/027_custom_budget_periods.sql"),
+    include_str!("../../migrations/028_budget_month_amounts.sql"),
+    include_str!("../../migrations/029_budget_alerts.sql"),
+    include_str!("../../migrations/030_budget_include_children.sql"),
+    include_str!("../../migrations/031_exclude_from_budget.sql"),
+    include_str!("../../migrations/032_budget_groups.sql"),
+    include_str!("../../migrations/033_goal_contribution_deletes.sql"),
+    include_str!("../../migrations/034_goal_contribution_schedules.sql"),
+    include_str!("../../migrations/035_debt_payoff_goals.sql"),
+    include_str!("../../migrations/036_goal_milestones.sql"),
+    include_str!("../../migrations/037_round_ups.sql"),
+    include_str!("../../migrations/038_goal_priorities.sql"),
+    include_str!("../../migrations/039_goal_archive.sql"),
+    include_str!("../../migrations/040_account_statements.sql"),
+    include_str!("../../migrations/041_account_groups.sql"),
+    include_str!("../../migrations/042_credit_card_cycles.sql"),
+    include_str!("../../migrations/043_interest_accrual.sql"),
+    include_str!("../../migrations/044_fx_rates.sql"),
+    include_str!("../../migrations/045_fx_rate_sources.sql"),
+    include_str!("../../migrations/046_account_closing.sql"),
+    include_str!("../../migrations/047_account_types.sql"),
+    include_str!("../../migrations/048_opening_balance.sql"),
+    include_str!("../../migrations/049_investment_transactions.sql"),
+    include_str!("../../migrations/050_investment_lots.sql"),
+    include_str!("../../migrations/051_rule_match_mode.sql"),
+    include_str!("../../migrations/052_goal_milestones_backfill.sql"),
+    include_str!("../../migrations/053_tax_package.sql"),
+];
+
 fn derive_key(password: &str) -> String {
     // Use a fixed salt for SQLCipher (the actual key derivation happens in SQLCipher)
     // This is just to normalize the password into a hex key
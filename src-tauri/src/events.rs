@@ -0,0 +1,41 @@
+//! Change events emitted after mutations so every open view can refresh its data.
+//! Their payload is empty; listeners refetch whatever they display.
+
+use tauri::{AppHandle, Emitter};
+
+pub const ACCOUNTS_CHANGED: &str = "accounts:changed";
+pub const TRANSACTIONS_CHANGED: &str = "transactions:changed";
+pub const CATEGORIES_CHANGED: &str = "categories:changed";
+pub const PAYEES_CHANGED: &str = "payees:changed";
+pub const RULES_CHANGED: &str = "rules:changed";
+pub const BUDGETS_CHANGED: &str = "budgets:changed";
+pub const GOALS_CHANGED: &str = "goals:changed";
+pub const RECURRING_CHANGED: &str = "recurring:changed";
+pub const GIFT_CARDS_CHANGED: &str = "gift_cards:changed";
+pub const POLICIES_CHANGED: &str = "policies:changed";
+pub const NOTE_TEMPLATES_CHANGED: &str = "note_templates:changed";
+pub const REWARDS_CHANGED: &str = "rewards:changed";
+pub const PLANNED_PURCHASES_CHANGED: &str = "planned_purchases:changed";
+/// Holdings or securities changed, including their prices
+pub const INVESTMENTS_CHANGED: &str = "investments:changed";
+pub const CREDIT_SCORES_CHANGED: &str = "credit_scores:changed";
+
+/// Progress of a streaming import, emitted after each committed chunk. Unlike the change
+/// events above it carries a payload (`ImportProgress`).
+pub const IMPORT_PROGRESS: &str = "import:progress";
+
+/// A budget crossed one of its alert thresholds. Carries the new alerts
+/// (`Vec<BudgetAlert>`) for the frontend to show as notifications.
+pub const BUDGET_ALERT: &str = "budgets:alert";
+
+/// A goal reached one of its milestones. Carries the milestones reached
+/// (`Vec<GoalMilestone>`) so the frontend can celebrate them.
+pub const GOAL_MILESTONE: &str = "goals:milestone";
+
+/// Emit each event to all windows. A failed emit only means a view misses a refresh,
+/// so it never fails the command that made the change.
+pub fn emit_changed(app: &AppHandle, events: &[&str]) {
+    for event in events {
+        let _ = app.emit(event, ());
+    }
+}
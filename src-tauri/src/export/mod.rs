@@ -0,0 +1,8 @@
+pub mod ofx;
+pub mod qif;
+
+/// Format cents as a plain decimal amount ("-1234.50"), the form QIF and OFX both expect
+pub fn format_amount(cents: i64) -> String {
+    let sign = if cents < 0 { "-" } else { "" };
+    format!("{}{}.{:02}", sign, cents.abs() / 100, cents.abs() % 100)
+}
@@ -0,0 +1,224 @@
+//! OFX 2.2 (XML) bank and credit card statement writer.
+//!
+//! Only the statement download response is produced; sign-on is a fixed success block
+//! since there is no server session behind the file.
+
+use super::format_amount;
+
+pub struct OfxTransaction {
+    /// Stable id the importing tool uses to skip transactions it has already seen
+    pub fitid: String,
+    /// ISO date, `YYYY-MM-DD`
+    pub date: String,
+    pub amount: i64,
+    pub payee: Option<String>,
+    pub memo: Option<String>,
+    pub check_number: Option<String>,
+    pub is_transfer: bool,
+}
+
+pub struct OfxStatement {
+    pub account_id: String,
+    /// Tally account type; credit cards get a credit card statement, everything else a bank one
+    pub account_type: String,
+    pub currency: String,
+    /// ISO dates bounding the statement
+    pub start_date: String,
+    pub end_date: String,
+    pub ledger_balance: i64,
+    pub transactions: Vec<OfxTransaction>,
+}
+
+/// OFX limits NAME to 32 characters
+const MAX_NAME_LENGTH: usize = 32;
+
+/// Routing number placeholder; Tally doesn't store one and importers only need it present
+const PLACEHOLDER_BANK_ID: &str = "000000000";
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace(['\r', '\n'], " ")
+}
+
+fn element(out: &mut String, tag: &str, value: &str) {
+    out.push_str(&format!("<{}>{}</{}>\n", tag, escape(value.trim()), tag));
+}
+
+fn open(out: &mut String, tag: &str) {
+    out.push_str(&format!("<{}>\n", tag));
+}
+
+fn close(out: &mut String, tag: &str) {
+    out.push_str(&format!("</{}>\n", tag));
+}
+
+/// OFX dates are `YYYYMMDD`
+fn ofx_date(date: &str) -> String {
+    date.chars()
+        .filter(|c| c.is_ascii_digit())
+        .take(8)
+        .collect()
+}
+
+fn bank_account_type(account_type: &str) -> &'static str {
+    match account_type {
+        "savings" => "SAVINGS",
+        "loan" => "CREDITLINE",
+        "investment" | "retirement" => "MONEYMRKT",
+        _ => "CHECKING",
+    }
+}
+
+fn transaction_type(transaction: &OfxTransaction) -> &'static str {
+    if transaction.check_number.is_some() && transaction.amount < 0 {
+        "CHECK"
+    } else if transaction.is_transfer {
+        "XFER"
+    } else if transaction.amount < 0 {
+        "DEBIT"
+    } else {
+        "CREDIT"
+    }
+}
+
+fn write_status(out: &mut String) {
+    open(out, "STATUS");
+    element(out, "CODE", "0");
+    element(out, "SEVERITY", "INFO");
+    close(out, "STATUS");
+}
+
+fn write_transaction(out: &mut String, transaction: &OfxTransaction) {
+    open(out, "STMTTRN");
+    element(out, "TRNTYPE", transaction_type(transaction));
+    element(out, "DTPOSTED", &ofx_date(&transaction.date));
+    element(out, "TRNAMT", &format_amount(transaction.amount));
+    element(out, "FITID", &transaction.fitid);
+    if let Some(check_number) = &transaction.check_number {
+        element(out, "CHECKNUM", check_number);
+    }
+    if let Some(payee) = &transaction.payee {
+        let name: String = payee.chars().take(MAX_NAME_LENGTH).collect();
+        element(out, "NAME", &name);
+    }
+    if let Some(memo) = &transaction.memo {
+        element(out, "MEMO", memo);
+    }
+    close(out, "STMTTRN");
+}
+
+/// Write a single-account statement as an OFX 2.2 document. `generated_at` is the
+/// `YYYYMMDDHHMMSS` timestamp used for the server time and balance date.
+pub fn write_ofx(statement: &OfxStatement, generated_at: &str) -> String {
+    let is_credit_card = statement.account_type == "credit_card";
+    let (message_set, transaction_response, statement_response) = if is_credit_card {
+        ("CREDITCARDMSGSRSV1", "CCSTMTTRNRS", "CCSTMTRS")
+    } else {
+        ("BANKMSGSRSV1", "STMTTRNRS", "STMTRS")
+    };
+
+    let mut out = String::new();
+    out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"no\"?>\n");
+    out.push_str(
+        "<?OFX OFXHEADER=\"200\" VERSION=\"220\" SECURITY=\"NONE\" OLDFILEUID=\"NONE\" NEWFILEUID=\"NONE\"?>\n",
+    );
+    open(&mut out, "OFX");
+
+    open(&mut out, "SIGNONMSGSRSV1");
+    open(&mut out, "SONRS");
+    write_status(&mut out);
+    element(&mut out, "DTSERVER", generated_at);
+    element(&mut out, "LANGUAGE", "ENG");
+    close(&mut out, "SONRS");
+    close(&mut out, "SIGNONMSGSRSV1");
+
+    open(&mut out, message_set);
+    open(&mut out, transaction_response);
+    element(&mut out, "TRNUID", "0");
+    write_status(&mut out);
+    open(&mut out, statement_response);
+    element(&mut out, "CURDEF", &statement.currency);
+
+    if is_credit_card {
+        open(&mut out, "CCACCTFROM");
+        element(&mut out, "ACCTID", &statement.account_id);
+        close(&mut out, "CCACCTFROM");
+    } else {
+        open(&mut out, "BANKACCTFROM");
+        element(&mut out, "BANKID", PLACEHOLDER_BANK_ID);
+        element(&mut out, "ACCTID", &statement.account_id);
+        element(
+            &mut out,
+            "ACCTTYPE",
+            bank_account_type(&statement.account_type),
+        );
+        close(&mut out, "BANKACCTFROM");
+    }
+
+    open(&mut out, "BANKTRANLIST");
+    element(&mut out, "DTSTART", &ofx_date(&statement.start_date));
+    element(&mut out, "DTEND", &ofx_date(&statement.end_date));
+    for transaction in &statement.transactions {
+        write_transaction(&mut out, transaction);
+    }
+    close(&mut out, "BANKTRANLIST");
+
+    open(&mut out, "LEDGERBAL");
+    element(&mut out, "BALAMT", &format_amount(statement.ledger_balance));
+    element(&mut out, "DTASOF", generated_at);
+    close(&mut out, "LEDGERBAL");
+
+    close(&mut out, statement_response);
+    close(&mut out, transaction_response);
+    close(&mut out, message_set);
+    close(&mut out, "OFX");
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn statement(account_type: &str) -> OfxStatement {
+        OfxStatement {
+            account_id: "1234".to_string(),
+            account_type: account_type.to_string(),
+            currency: "USD".to_string(),
+            start_date: "2024-03-01".to_string(),
+            end_date: "2024-03-31".to_string(),
+            ledger_balance: 150075,
+            transactions: vec![OfxTransaction {
+                fitid: "abc123".to_string(),
+                date: "2024-03-05".to_string(),
+                amount: -1234,
+                payee: Some("Ben & Jerry's Ice Cream Shop Downtown".to_string()),
+                memo: None,
+                check_number: None,
+                is_transfer: false,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_write_bank_statement() {
+        let ofx = write_ofx(&statement("checking"), "20240401120000");
+        assert!(ofx.contains("<BANKMSGSRSV1>\n<STMTTRNRS>\n"));
+        assert!(ofx.contains("<ACCTTYPE>CHECKING</ACCTTYPE>\n"));
+        assert!(ofx.contains("<TRNTYPE>DEBIT</TRNTYPE>\n<DTPOSTED>20240305</DTPOSTED>\n"));
+        assert!(ofx.contains("<TRNAMT>-12.34</TRNAMT>\n<FITID>abc123</FITID>\n"));
+        assert!(ofx.contains("<NAME>Ben &amp; Jerry's Ice Cream Shop Dow</NAME>\n"));
+        assert!(ofx.contains("<BALAMT>1500.75</BALAMT>\n"));
+        assert!(ofx.ends_with("</STMTRS>\n</STMTTRNRS>\n</BANKMSGSRSV1>\n</OFX>\n"));
+    }
+
+    #[test]
+    fn test_write_credit_card_statement() {
+        let ofx = write_ofx(&statement("credit_card"), "20240401120000");
+        assert!(ofx.contains("<CREDITCARDMSGSRSV1>\n<CCSTMTTRNRS>\n"));
+        assert!(ofx.contains("<CCACCTFROM>\n<ACCTID>1234</ACCTID>\n</CCACCTFROM>\n"));
+        assert!(!ofx.contains("BANKACCTFROM"));
+    }
+}
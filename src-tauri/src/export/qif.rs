@@ -0,0 +1,201 @@
+//! QIF (Quicken Interchange Format) writer, readable by Quicken and GnuCash.
+//!
+//! Each account is written as an `!Account` block followed by its transactions. Transfers
+//! use the `[Account Name]` category form so the importing tool can pair both sides, and
+//! split transactions list one `S`/`$`/`E` group per split line.
+
+use super::format_amount;
+
+pub struct QifSplit {
+    pub category: Option<String>,
+    pub amount: i64,
+    pub memo: Option<String>,
+}
+
+pub struct QifTransaction {
+    /// ISO date, `YYYY-MM-DD`
+    pub date: String,
+    pub amount: i64,
+    pub payee: Option<String>,
+    pub memo: Option<String>,
+    pub check_number: Option<String>,
+    pub status: String,
+    /// Full category path, `Parent:Child`
+    pub category: Option<String>,
+    /// Name of the other account when this is one side of a transfer
+    pub transfer_account: Option<String>,
+    pub splits: Vec<QifSplit>,
+}
+
+pub struct QifAccount {
+    pub name: String,
+    pub account_type: String,
+    pub transactions: Vec<QifTransaction>,
+}
+
+/// QIF account type for a Tally account type
+fn qif_account_type(account_type: &str) -> &'static str {
+    match account_type {
+        "credit_card" => "CCard",
+        "cash" => "Cash",
+        "loan" => "Oth L",
+        "investment" | "retirement" | "property" | "other" => "Oth A",
+        _ => "Bank",
+    }
+}
+
+/// QIF dates are `MM/DD/YYYY`
+fn qif_date(date: &str) -> String {
+    match chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+        Ok(d) => d.format("%m/%d/%Y").to_string(),
+        Err(_) => date.to_string(),
+    }
+}
+
+/// Cleared flag: `*` for cleared, `X` for reconciled, nothing for pending
+fn qif_cleared(status: &str) -> Option<&'static str> {
+    match status {
+        "cleared" => Some("*"),
+        "reconciled" => Some("X"),
+        _ => None,
+    }
+}
+
+/// QIF fields are line-based, so embedded newlines would start a new field
+fn clean(text: &str) -> String {
+    text.replace(['\r', '\n'], " ").trim().to_string()
+}
+
+fn push_field(out: &mut String, code: char, value: &str) {
+    out.push(code);
+    out.push_str(&clean(value));
+    out.push('\n');
+}
+
+fn write_transaction(out: &mut String, transaction: &QifTransaction) {
+    push_field(out, 'D', &qif_date(&transaction.date));
+    push_field(out, 'T', &format_amount(transaction.amount));
+    if let Some(cleared) = qif_cleared(&transaction.status) {
+        push_field(out, 'C', cleared);
+    }
+    if let Some(check_number) = &transaction.check_number {
+        push_field(out, 'N', check_number);
+    }
+    if let Some(payee) = &transaction.payee {
+        push_field(out, 'P', payee);
+    }
+    if let Some(memo) = &transaction.memo {
+        push_field(out, 'M', memo);
+    }
+
+    match (&transaction.transfer_account, &transaction.category) {
+        (Some(account), _) => push_field(out, 'L', &format!("[{}]", clean(account))),
+        (None, Some(category)) if transaction.splits.is_empty() => push_field(out, 'L', category),
+        _ => {}
+    }
+
+    for split in &transaction.splits {
+        push_field(out, 'S', split.category.as_deref().unwrap_or(""));
+        if let Some(memo) = &split.memo {
+            push_field(out, 'E', memo);
+        }
+        push_field(out, '$', &format_amount(split.amount));
+    }
+
+    out.push_str("^\n");
+}
+
+/// Write accounts and their transactions as a QIF file. With more than one account the
+/// file uses `AutoSwitch` so importers file each block under its own account.
+pub fn write_qif(accounts: &[QifAccount]) -> String {
+    let mut out = String::new();
+    let multiple = accounts.len() > 1;
+
+    if multiple {
+        out.push_str("!Option:AutoSwitch\n");
+        out.push_str("!Account\n");
+        for account in accounts {
+            push_field(&mut out, 'N', &account.name);
+            push_field(&mut out, 'T', qif_account_type(&account.account_type));
+            out.push_str("^\n");
+        }
+        out.push_str("!Clear:AutoSwitch\n");
+    }
+
+    for account in accounts {
+        let account_type = qif_account_type(&account.account_type);
+        out.push_str("!Account\n");
+        push_field(&mut out, 'N', &account.name);
+        push_field(&mut out, 'T', account_type);
+        out.push_str("^\n");
+        out.push_str(&format!("!Type:{}\n", account_type));
+        for transaction in &account.transactions {
+            write_transaction(&mut out, transaction);
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transaction(amount: i64) -> QifTransaction {
+        QifTransaction {
+            date: "2024-03-05".to_string(),
+            amount,
+            payee: Some("Grocer".to_string()),
+            memo: None,
+            check_number: None,
+            status: "cleared".to_string(),
+            category: Some("Food:Groceries".to_string()),
+            transfer_account: None,
+            splits: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_write_transaction() {
+        let qif = write_qif(&[QifAccount {
+            name: "Checking".to_string(),
+            account_type: "checking".to_string(),
+            transactions: vec![transaction(-1234)],
+        }]);
+        assert_eq!(
+            qif,
+            "!Account\nNChecking\nTBank\n^\n!Type:Bank\n\
+             D03/05/2024\nT-12.34\nC*\nPGrocer\nLFood:Groceries\n^\n"
+        );
+    }
+
+    #[test]
+    fn test_write_transfer_and_splits() {
+        let mut transfer = transaction(-5000);
+        transfer.transfer_account = Some("Savings".to_string());
+
+        let mut split = transaction(-3000);
+        split.splits = vec![
+            QifSplit {
+                category: Some("Food".to_string()),
+                amount: -2000,
+                memo: Some("lunch".to_string()),
+            },
+            QifSplit {
+                category: Some("Home".to_string()),
+                amount: -1000,
+                memo: None,
+            },
+        ];
+
+        let qif = write_qif(&[QifAccount {
+            name: "Checking".to_string(),
+            account_type: "checking".to_string(),
+            transactions: vec![transfer, split],
+        }]);
+
+        assert!(qif.contains("T-50.00\nC*\nPGrocer\nL[Savings]\n^\n"));
+        assert!(qif.contains("SFood\nElunch\n$-20.00\nSHome\n$-10.00\n^\n"));
+        assert!(!qif.contains("LFood:Groceries\nSFood"));
+    }
+}
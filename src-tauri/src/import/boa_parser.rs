@@ -1,3 +1,4 @@
+use super::registry::StatementImporter;
 use crate::error::{AppError, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -249,6 +250,53 @@ pub fn to_parsed_transactions(transactions: Vec<BoaTransaction>) -> Vec<HashMap<
         .collect()
 }
 
+/// Bank of America text statements
+pub struct BoaImporter;
+
+impl StatementImporter for BoaImporter {
+    fn id(&self) -> &'static str {
+        "boa"
+    }
+
+    fn name(&self) -> &'static str {
+        "Bank of America (text)"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["txt"]
+    }
+
+    fn detect(&self, path: &Path) -> f32 {
+        let Ok(content) = fs::read_to_string(path) else {
+            return 0.0;
+        };
+        if content.contains("Beginning balance as of") {
+            0.9
+        } else if content.lines().any(|l| {
+            let l = l.trim();
+            l.starts_with("Date") && l.contains("Description") && l.contains("Amount")
+        }) {
+            0.5
+        } else {
+            0.0
+        }
+    }
+
+    fn parse(&self, path: &Path) -> Result<Vec<serde_json::Value>> {
+        Ok(parse_boa(path)?
+            .into_iter()
+            .map(|tx| {
+                serde_json::json!({
+                    "date": tx.date,
+                    "amount": tx.amount,
+                    "payee": tx.description,
+                    "memo": tx.description,
+                })
+            })
+            .collect())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -21,6 +21,8 @@ pub struct ColumnMapping {
     pub payee_column: Option<usize>,
     pub memo_column: Option<usize>,
     pub category_column: Option<usize>,
+    pub original_amount_column: Option<usize>,
+    pub original_currency_column: Option<usize>,
     pub date_format: String,
     pub invert_amounts: bool,
 }
@@ -33,22 +35,14 @@ pub struct ParsedTransaction {
     pub payee: Option<String>,
     pub memo: Option<String>,
     pub category_hint: Option<String>,
+    pub original_amount: Option<i64>,
+    pub original_currency: Option<String>,
     pub raw_data: HashMap<String, String>,
 }
 
 /// Preview a CSV file - read headers and first N rows
 pub fn preview_csv(file_path: &Path, max_rows: usize) -> Result<CsvPreview> {
-    let mut reader = csv::ReaderBuilder::new()
-        .flexible(true)
-        .from_path(file_path)
-        .map_err(|e| AppError::Other(format!("Failed to open CSV: {}", e)))?;
-
-    let headers: Vec<String> = reader
-        .headers()
-        .map_err(|e| AppError::Other(format!("Failed to read headers: {}", e)))?
-        .iter()
-        .map(|s| s.to_string())
-        .collect();
+    let (mut reader, headers) = open_csv(file_path)?;
 
     let mut rows = Vec::new();
     let mut total_rows = 0;
@@ -69,8 +63,7 @@ pub fn preview_csv(file_path: &Path, max_rows: usize) -> Result<CsvPreview> {
     })
 }
 
-/// Parse a CSV file with the given column mapping
-pub fn parse_csv(file_path: &Path, mapping: &ColumnMapping) -> Result<Vec<ParsedTransaction>> {
+fn open_csv(file_path: &Path) -> Result<(csv::Reader<std::fs::File>, Vec<String>)> {
     let mut reader = csv::ReaderBuilder::new()
         .flexible(true)
         .from_path(file_path)
@@ -83,76 +76,123 @@ pub fn parse_csv(file_path: &Path, mapping: &ColumnMapping) -> Result<Vec<Parsed
         .map(|s| s.to_string())
         .collect();
 
-    let mut transactions = Vec::new();
+    Ok((reader, headers))
+}
 
-    for result in reader.records() {
-        let record = result.map_err(|e| AppError::Other(format!("Failed to read record: {}", e)))?;
-        let fields: Vec<&str> = record.iter().collect();
-
-        // Parse date
-        let date_str = fields.get(mapping.date_column).unwrap_or(&"").trim();
-        let parsed_date = parse_date(date_str, &mapping.date_format)?;
-
-        // Parse amount
-        let amount = if let (Some(debit_col), Some(credit_col)) =
-            (mapping.debit_column, mapping.credit_column)
-        {
-            // Separate debit/credit columns
-            let debit = parse_amount(fields.get(debit_col).unwrap_or(&""));
-            let credit = parse_amount(fields.get(credit_col).unwrap_or(&""));
-            credit - debit
+/// Parse one record with the given column mapping. `raw_data` is only filled in when
+/// headers are passed.
+fn parse_record(
+    record: &csv::StringRecord,
+    headers: Option<&[String]>,
+    mapping: &ColumnMapping,
+) -> Result<ParsedTransaction> {
+    let fields: Vec<&str> = record.iter().collect();
+
+    // Parse date
+    let date_str = fields.get(mapping.date_column).unwrap_or(&"").trim();
+    let parsed_date = parse_date(date_str, &mapping.date_format)?;
+
+    // Parse amount
+    let amount = if let (Some(debit_col), Some(credit_col)) =
+        (mapping.debit_column, mapping.credit_column)
+    {
+        // Separate debit/credit columns
+        let debit = parse_amount(fields.get(debit_col).unwrap_or(&""));
+        let credit = parse_amount(fields.get(credit_col).unwrap_or(&""));
+        credit - debit
+    } else {
+        // Single amount column
+        let raw_amount = parse_amount(fields.get(mapping.amount_column).unwrap_or(&""));
+        if mapping.invert_amounts {
+            -raw_amount
         } else {
-            // Single amount column
-            let raw_amount = parse_amount(fields.get(mapping.amount_column).unwrap_or(&""));
-            if mapping.invert_amounts {
-                -raw_amount
-            } else {
-                raw_amount
-            }
-        };
-
-        // Parse optional fields
-        let payee = mapping
-            .payee_column
-            .and_then(|col| fields.get(col))
-            .map(|s| s.trim().to_string())
-            .filter(|s| !s.is_empty());
-
-        let memo = mapping
-            .memo_column
-            .and_then(|col| fields.get(col))
-            .map(|s| s.trim().to_string())
-            .filter(|s| !s.is_empty());
-
-        let category_hint = mapping
-            .category_column
-            .and_then(|col| fields.get(col))
-            .map(|s| s.trim().to_string())
-            .filter(|s| !s.is_empty());
-
-        // Build raw data map
-        let mut raw_data = HashMap::new();
-        for (i, header) in headers.iter().enumerate() {
-            if let Some(value) = fields.get(i) {
-                raw_data.insert(header.clone(), value.to_string());
-            }
+            raw_amount
         }
+    };
 
-        transactions.push(ParsedTransaction {
-            date: parsed_date,
-            amount,
-            payee,
-            memo,
-            category_hint,
-            raw_data,
-        });
+    // Parse optional fields
+    let payee = mapping
+        .payee_column
+        .and_then(|col| fields.get(col))
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty());
+
+    let memo = mapping
+        .memo_column
+        .and_then(|col| fields.get(col))
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty());
+
+    let category_hint = mapping
+        .category_column
+        .and_then(|col| fields.get(col))
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty());
+
+    // Foreign purchases may list the amount in the currency actually charged, carrying
+    // the same sign as the account-currency amount
+    let original_currency = mapping
+        .original_currency_column
+        .and_then(|col| fields.get(col))
+        .map(|s| s.trim().to_uppercase())
+        .filter(|s| !s.is_empty());
+
+    let original_amount = mapping
+        .original_amount_column
+        .and_then(|col| fields.get(col))
+        .filter(|s| !s.trim().is_empty() && original_currency.is_some())
+        .map(|s| parse_amount(s).abs() * amount.signum());
+
+    // Build raw data map
+    let mut raw_data = HashMap::new();
+    for (i, header) in headers.unwrap_or_default().iter().enumerate() {
+        if let Some(value) = fields.get(i) {
+            raw_data.insert(header.clone(), value.to_string());
+        }
+    }
+
+    Ok(ParsedTransaction {
+        date: parsed_date,
+        amount,
+        payee,
+        memo,
+        category_hint,
+        original_amount,
+        original_currency,
+        raw_data,
+    })
+}
+
+/// Parse a CSV file with the given column mapping
+pub fn parse_csv(file_path: &Path, mapping: &ColumnMapping) -> Result<Vec<ParsedTransaction>> {
+    let (mut reader, headers) = open_csv(file_path)?;
+
+    let mut transactions = Vec::new();
+
+    for result in reader.records() {
+        let record = result.map_err(|e| AppError::Other(format!("Failed to read record: {}", e)))?;
+        transactions.push(parse_record(&record, Some(&headers), mapping)?);
     }
 
     Ok(transactions)
 }
 
+/// Parse a CSV file one record at a time as it's read, for files too large to hold in
+/// memory. Rows come without `raw_data`.
+pub fn stream_csv<'a>(
+    file_path: &Path,
+    mapping: &'a ColumnMapping,
+) -> Result<impl Iterator<Item = Result<ParsedTransaction>> + 'a> {
+    let (reader, _) = open_csv(file_path)?;
+
+    Ok(reader.into_records().map(move |result| {
+        let record = result.map_err(|e| AppError::Other(format!("Failed to read record: {}", e)))?;
+        parse_record(&record, None, mapping)
+    }))
+}
+
 /// Parse an amount string to cents (i64)
-fn parse_amount(s: &str) -> i64 {
+pub(crate) fn parse_amount(s: &str) -> i64 {
     let cleaned: String = s
         .trim()
         .replace('$', "")
@@ -174,7 +214,7 @@ fn parse_amount(s: &str) -> i64 {
 }
 
 /// Parse a date string with the given format
-fn parse_date(s: &str, format: &str) -> Result<String> {
+pub(crate) fn parse_date(s: &str, format: &str) -> Result<String> {
     use chrono::NaiveDate;
 
     let trimmed = s.trim();
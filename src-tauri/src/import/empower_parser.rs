@@ -0,0 +1,243 @@
+use crate::error::{AppError, Result};
+use crate::import::csv_parser::{parse_amount, parse_date};
+use serde::Serialize;
+use std::io::Read;
+use std::path::Path;
+
+/// Which Empower (formerly Personal Capital) export a CSV file holds
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmpowerExportKind {
+    Transactions,
+    Holdings,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmpowerTransaction {
+    pub date: String,
+    pub account: String,
+    pub description: String,
+    pub category: Option<String>,
+    pub tags: Option<String>,
+    pub amount: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmpowerHolding {
+    pub account: String,
+    pub symbol: String,
+    pub name: Option<String>,
+    pub quantity: f64,
+    pub price: Option<i64>,
+    pub value: i64,
+    pub cost_basis: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmpowerExport {
+    pub kind: EmpowerExportKind,
+    pub transactions: Vec<EmpowerTransaction>,
+    pub holdings: Vec<EmpowerHolding>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmpowerPreview {
+    pub kind: EmpowerExportKind,
+    pub accounts: Vec<String>,
+    pub transaction_count: usize,
+    pub holding_count: usize,
+    pub transactions: Vec<EmpowerTransaction>,
+    pub holdings: Vec<EmpowerHolding>,
+}
+
+/// Position of the first header matching any of the candidate names (case-insensitive)
+fn find_column(headers: &[String], candidates: &[&str]) -> Option<usize> {
+    headers.iter().position(|h| {
+        let h = h.trim().to_lowercase();
+        candidates.iter().any(|c| h == *c)
+    })
+}
+
+fn field(record: &csv::StringRecord, column: Option<usize>) -> Option<String> {
+    column
+        .and_then(|c| record.get(c))
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+fn parse_quantity(s: &str) -> Option<f64> {
+    s.trim().replace(',', "").parse().ok()
+}
+
+/// Parse an Empower CSV export, detecting from the headers whether it lists transactions
+/// (Date, Account, Description, Category, Tags, Amount) or holdings (Account, Ticker,
+/// Shares, Price, Value, and optionally Cost Basis)
+pub fn parse_empower_reader<R: Read>(reader: R) -> Result<EmpowerExport> {
+    let mut reader = csv::ReaderBuilder::new().flexible(true).from_reader(reader);
+
+    let headers: Vec<String> = reader
+        .headers()
+        .map_err(|e| AppError::Other(format!("Failed to read headers: {}", e)))?
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+
+    let account_col = find_column(&headers, &["account", "account name"]);
+    let ticker_col = find_column(&headers, &["ticker", "symbol"]);
+    let shares_col = find_column(&headers, &["shares", "quantity"]);
+
+    let kind = if ticker_col.is_some() && shares_col.is_some() {
+        EmpowerExportKind::Holdings
+    } else if find_column(&headers, &["date"]).is_some()
+        && find_column(&headers, &["amount"]).is_some()
+    {
+        EmpowerExportKind::Transactions
+    } else {
+        return Err(AppError::Validation(
+            "File is not a recognized Empower transactions or holdings export".to_string(),
+        ));
+    };
+
+    let mut export = EmpowerExport {
+        kind,
+        transactions: Vec::new(),
+        holdings: Vec::new(),
+    };
+
+    match kind {
+        EmpowerExportKind::Transactions => {
+            let date_col = find_column(&headers, &["date"]);
+            let description_col = find_column(&headers, &["description", "payee"]);
+            let category_col = find_column(&headers, &["category"]);
+            let tags_col = find_column(&headers, &["tags"]);
+            let amount_col = find_column(&headers, &["amount"]);
+
+            for result in reader.records() {
+                let record =
+                    result.map_err(|e| AppError::Other(format!("Failed to read record: {}", e)))?;
+
+                let date = match field(&record, date_col) {
+                    Some(d) => parse_date(&d, "")?,
+                    None => continue,
+                };
+
+                export.transactions.push(EmpowerTransaction {
+                    date,
+                    account: field(&record, account_col).unwrap_or_else(|| "Empower".to_string()),
+                    description: field(&record, description_col).unwrap_or_default(),
+                    category: field(&record, category_col),
+                    tags: field(&record, tags_col),
+                    amount: parse_amount(&field(&record, amount_col).unwrap_or_default()),
+                });
+            }
+        }
+        EmpowerExportKind::Holdings => {
+            let name_col = find_column(&headers, &["holding", "name", "description"]);
+            let price_col = find_column(&headers, &["price"]);
+            let value_col = find_column(&headers, &["value", "market value"]);
+            let cost_basis_col = find_column(&headers, &["cost basis", "cost"]);
+
+            for result in reader.records() {
+                let record =
+                    result.map_err(|e| AppError::Other(format!("Failed to read record: {}", e)))?;
+
+                // Cash and total rows carry no ticker or share count
+                let (symbol, quantity) = match (
+                    field(&record, ticker_col),
+                    field(&record, shares_col).as_deref().and_then(parse_quantity),
+                ) {
+                    (Some(symbol), Some(quantity)) => (symbol.to_uppercase(), quantity),
+                    _ => continue,
+                };
+
+                let price = field(&record, price_col).map(|p| parse_amount(&p));
+                let value = field(&record, value_col)
+                    .map(|v| parse_amount(&v))
+                    .or_else(|| price.map(|p| (p as f64 * quantity).round() as i64))
+                    .unwrap_or(0);
+
+                export.holdings.push(EmpowerHolding {
+                    account: field(&record, account_col).unwrap_or_else(|| "Empower".to_string()),
+                    symbol,
+                    name: field(&record, name_col),
+                    quantity,
+                    price,
+                    value,
+                    cost_basis: field(&record, cost_basis_col).map(|c| parse_amount(&c)),
+                });
+            }
+        }
+    }
+
+    Ok(export)
+}
+
+pub fn parse_empower(path: &Path) -> Result<EmpowerExport> {
+    let file = std::fs::File::open(path)?;
+    parse_empower_reader(file)
+}
+
+/// Preview an Empower export: its kind, the accounts it covers, and the first N rows
+pub fn preview_empower(path: &Path, limit: usize) -> Result<EmpowerPreview> {
+    let export = parse_empower(path)?;
+
+    let mut accounts: Vec<String> = export
+        .transactions
+        .iter()
+        .map(|t| t.account.clone())
+        .chain(export.holdings.iter().map(|h| h.account.clone()))
+        .collect();
+    accounts.sort();
+    accounts.dedup();
+
+    Ok(EmpowerPreview {
+        kind: export.kind,
+        accounts,
+        transaction_count: export.transactions.len(),
+        holding_count: export.holdings.len(),
+        transactions: export.transactions.into_iter().take(limit).collect(),
+        holdings: export.holdings.into_iter().take(limit).collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_transactions_export() {
+        let csv = "Date,Account,Description,Category,Tags,Amount\n\
+                   2024-01-05,Chase Checking,Coffee Shop,Restaurants,,-4.50\n\
+                   2024-01-06,Chase Checking,Payroll,Paychecks/Salary,work,\"2,500.00\"\n";
+        let export = parse_empower_reader(csv.as_bytes()).unwrap();
+        assert_eq!(export.kind, EmpowerExportKind::Transactions);
+        assert_eq!(export.transactions.len(), 2);
+        assert_eq!(export.transactions[0].amount, -450);
+        assert_eq!(export.transactions[1].amount, 250000);
+        assert_eq!(export.transactions[1].tags.as_deref(), Some("work"));
+    }
+
+    #[test]
+    fn test_parse_holdings_export() {
+        let csv = "Account,Ticker,Holding,Shares,Price,Value,Cost Basis\n\
+                   Fidelity 401k,vti,Vanguard Total Stock Market,10.5,$250.00,\"$2,625.00\",\"$2,000.00\"\n\
+                   Fidelity 401k,,Cash,,,$100.00,\n";
+        let export = parse_empower_reader(csv.as_bytes()).unwrap();
+        assert_eq!(export.kind, EmpowerExportKind::Holdings);
+        assert_eq!(export.holdings.len(), 1);
+        let holding = &export.holdings[0];
+        assert_eq!(holding.symbol, "VTI");
+        assert_eq!(holding.quantity, 10.5);
+        assert_eq!(holding.value, 262500);
+        assert_eq!(holding.cost_basis, Some(200000));
+    }
+
+    #[test]
+    fn test_rejects_unknown_export() {
+        assert!(parse_empower_reader("Foo,Bar\n1,2\n".as_bytes()).is_err());
+    }
+}
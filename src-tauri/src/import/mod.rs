@@ -1,3 +1,5 @@
 pub mod boa_parser;
 pub mod csv_parser;
+pub mod empower_parser;
 pub mod pdf_parser;
+pub mod registry;
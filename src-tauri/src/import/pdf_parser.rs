@@ -1,3 +1,4 @@
+use super::registry::StatementImporter;
 use crate::error::{AppError, Result};
 use pdfium::PdfiumDocument;
 use regex::Regex;
@@ -495,8 +496,11 @@ fn detect_format(text: &str) -> (Option<String>, Vec<String>) {
 
 /// Preview a PDF statement
 pub fn preview_pdf(path: &Path, limit: usize) -> Result<PdfPreview> {
-    let text = extract_text(path)?;
+    preview_pdf_text(&extract_text(path)?, limit)
+}
 
+/// Preview a statement from the text already extracted from its PDF
+pub fn preview_pdf_text(text: &str, limit: usize) -> Result<PdfPreview> {
     // Check if we got meaningful text
     if text.trim().len() < 100 {
         return Err(AppError::Other(
@@ -504,7 +508,7 @@ pub fn preview_pdf(path: &Path, limit: usize) -> Result<PdfPreview> {
         ));
     }
 
-    let (detected_format, detected_columns) = detect_format(&text);
+    let (detected_format, detected_columns) = detect_format(text);
 
     let lines: Vec<&str> = text.lines().collect();
     let mut transactions = Vec::new();
@@ -615,10 +619,52 @@ pub fn preview_pdf(path: &Path, limit: usize) -> Result<PdfPreview> {
 
 /// Parse all transactions from a PDF statement
 pub fn parse_pdf(path: &Path) -> Result<Vec<PdfTransaction>> {
-    let preview = preview_pdf(path, usize::MAX)?;
+    parse_pdf_text(&extract_text(path)?)
+}
+
+/// Parse all transactions from the text extracted from a PDF statement
+pub fn parse_pdf_text(text: &str) -> Result<Vec<PdfTransaction>> {
+    let preview = preview_pdf_text(text, usize::MAX)?;
     Ok(preview.transactions)
 }
 
+/// PDF statements from any bank whose text layer lists one transaction per line
+pub struct PdfImporter;
+
+impl StatementImporter for PdfImporter {
+    fn id(&self) -> &'static str {
+        "pdf"
+    }
+
+    fn name(&self) -> &'static str {
+        "PDF statement"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["pdf"]
+    }
+
+    /// Reading the text layer is slow, so any PDF is a plausible match
+    fn detect(&self, _path: &Path) -> f32 {
+        0.5
+    }
+
+    fn parse(&self, path: &Path) -> Result<Vec<serde_json::Value>> {
+        Ok(parse_pdf(path)?
+            .into_iter()
+            .map(|tx| {
+                serde_json::json!({
+                    "date": tx.date,
+                    "amount": tx.amount,
+                    "payee": tx.description,
+                    "memo": tx.description,
+                    "pdfCategory": tx.category,
+                })
+            })
+            .collect())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,117 @@
+//! Statement importers behind one interface, so a new bank format is a self-contained
+//! module implementing `StatementImporter` plus one `register` call.
+//!
+//! CSV files need a column mapping from the user and Empower exports span several
+//! accounts, so those keep their own commands; single-account statement files whose
+//! layout is known (BoA text, PDF) go through the registry.
+
+use crate::error::{AppError, Result};
+use serde::Serialize;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Detection confidence below which a file isn't handed to an importer
+const MIN_DETECT_CONFIDENCE: f32 = 0.1;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImporterInfo {
+    pub id: String,
+    pub name: String,
+    pub extensions: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatementPreview {
+    pub importer_id: String,
+    /// The first rows, in the same shape `parse` returns
+    pub transactions: Vec<serde_json::Value>,
+    pub total_rows: usize,
+}
+
+pub trait StatementImporter: Send + Sync {
+    /// Stable id, also used as the import source and for parser usage stats
+    fn id(&self) -> &'static str;
+
+    fn name(&self) -> &'static str;
+
+    /// File extensions this importer reads, lowercase without the dot
+    fn extensions(&self) -> &'static [&'static str];
+
+    /// How sure the importer is that it can read the file, from 0 (not at all) to 1
+    fn detect(&self, path: &Path) -> f32;
+
+    /// Rows ready for `import_transactions`: `date`, `amount`, `payee`, `memo` and any
+    /// importer-specific hints such as `pdfCategory`
+    fn parse(&self, path: &Path) -> Result<Vec<serde_json::Value>>;
+
+    fn preview(&self, path: &Path, limit: usize) -> Result<StatementPreview> {
+        let rows = self.parse(path)?;
+        Ok(StatementPreview {
+            importer_id: self.id().to_string(),
+            total_rows: rows.len(),
+            transactions: rows.into_iter().take(limit).collect(),
+        })
+    }
+}
+
+/// Whether a path has one of the given extensions, ignoring case
+pub(crate) fn has_extension(path: &Path, extensions: &[&str]) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| extensions.iter().any(|x| e.eq_ignore_ascii_case(x)))
+}
+
+pub struct ImporterRegistry {
+    importers: Vec<Arc<dyn StatementImporter>>,
+}
+
+impl ImporterRegistry {
+    /// Registry holding the importers that ship with the app
+    pub fn with_builtins() -> Self {
+        let mut registry = ImporterRegistry {
+            importers: Vec::new(),
+        };
+        registry.register(Arc::new(super::boa_parser::BoaImporter));
+        registry.register(Arc::new(super::pdf_parser::PdfImporter));
+        registry
+    }
+
+    /// Add an importer, replacing any registered under the same id
+    pub fn register(&mut self, importer: Arc<dyn StatementImporter>) {
+        self.importers.retain(|i| i.id() != importer.id());
+        self.importers.push(importer);
+    }
+
+    pub fn list(&self) -> Vec<ImporterInfo> {
+        self.importers
+            .iter()
+            .map(|i| ImporterInfo {
+                id: i.id().to_string(),
+                name: i.name().to_string(),
+                extensions: i.extensions().iter().map(|e| e.to_string()).collect(),
+            })
+            .collect()
+    }
+
+    pub fn get(&self, id: &str) -> Result<Arc<dyn StatementImporter>> {
+        self.importers
+            .iter()
+            .find(|i| i.id() == id)
+            .cloned()
+            .ok_or_else(|| AppError::NotFound(format!("No importer named '{}'", id)))
+    }
+
+    /// The importer most confident it can read the file. Only importers claiming the
+    /// file's extension are asked.
+    pub fn detect(&self, path: &Path) -> Option<Arc<dyn StatementImporter>> {
+        self.importers
+            .iter()
+            .filter(|i| has_extension(path, i.extensions()))
+            .map(|i| (i, i.detect(path)))
+            .filter(|(_, confidence)| *confidence >= MIN_DETECT_CONFIDENCE)
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(i, _)| i.clone())
+    }
+}
@@ -2,6 +2,8 @@ pub mod commands;
 pub mod config;
 pub mod db;
 pub mod error;
+pub mod events;
+pub mod export;
 pub mod import;
 pub mod models;
 
@@ -33,6 +35,7 @@ pub fn run() {
             Ok(())
         })
         .manage(Mutex::new(Database::new()))
+        .manage(import::registry::ImporterRegistry::with_builtins())
         .invoke_handler(tauri::generate_handler![
             // Settings
             commands::unlock_database,
@@ -51,58 +54,264 @@ pub fn run() {
             commands::get_account,
             commands::create_account,
             commands::update_account,
+            commands::reorder_accounts,
+            commands::adjust_account_balance,
             commands::delete_account,
+            commands::list_deleted_accounts,
+            commands::restore_accounts,
+            commands::add_account_alias,
+            commands::remove_account_alias,
+            commands::merge_accounts,
+            // Institutions
+            commands::list_institutions,
+            commands::create_institution,
+            commands::update_institution,
+            commands::delete_institution,
+            // Account groups
+            commands::list_account_groups,
+            commands::create_account_group,
+            commands::update_account_group,
+            commands::delete_account_group,
+            commands::get_net_worth_by_group,
+            // Credit cards
+            commands::get_credit_card_statuses,
+            commands::get_credit_card_status,
+            // Interest accrual
+            commands::run_interest_accrual,
+            // Currencies and exchange rates
+            commands::get_base_currency,
+            commands::set_base_currency,
+            commands::list_missing_fx_rates,
+            commands::list_fx_rates,
+            commands::set_fx_rate,
+            commands::delete_fx_rate,
+            commands::fetch_fx_rates,
+            // Account closing
+            commands::close_account,
+            commands::reopen_account,
+            commands::get_account_closure,
+            // Net worth snapshots
+            commands::create_net_worth_snapshot,
+            commands::list_net_worth_snapshots,
             // Transactions
             commands::list_transactions,
+            commands::get_transaction_stats,
             commands::get_transaction,
             commands::create_transaction,
             commands::update_transaction,
             commands::delete_transactions,
+            commands::delete_transactions_by_filter,
+            commands::list_deleted_transactions,
+            commands::restore_transactions,
+            commands::set_transaction_status,
+            commands::get_transaction_status_history,
             commands::bulk_categorize,
+            commands::parse_quick_entry,
+            commands::next_check_number,
+            commands::detect_check_number_gaps,
             commands::detect_transfers,
+            commands::find_duplicate_transactions,
+            commands::merge_transactions,
             commands::link_transfer,
             commands::unlink_transfer,
+            commands::detect_credit_card_payments,
+            commands::link_credit_card_payment,
+            // Attachments
+            commands::add_transaction_attachment,
+            commands::list_transaction_attachments,
+            commands::delete_transaction_attachment,
+            // Holds
+            commands::list_holds,
+            commands::mark_as_hold,
+            commands::release_hold,
+            commands::process_holds,
             // Categories
             commands::list_categories,
             commands::create_category,
             commands::update_category,
+            commands::move_category,
+            commands::reorder_categories,
+            commands::archive_category,
+            commands::unarchive_category,
+            commands::set_category_tax_deductible,
             commands::delete_category,
+            commands::list_deleted_categories,
+            commands::restore_categories,
+            commands::export_categories,
+            commands::import_categories,
+            // Payees
+            commands::list_payees,
+            commands::create_payee,
+            commands::update_payee,
+            commands::delete_payee,
+            commands::add_payee_alias,
+            commands::remove_payee_alias,
+            commands::merge_payees,
+            commands::suggest_payees,
+            commands::get_payee_stats,
+            // Note Templates
+            commands::list_note_templates,
+            commands::create_note_template,
+            commands::update_note_template,
+            commands::delete_note_template,
+            // Transaction Templates
+            commands::list_transaction_templates,
+            commands::create_transaction_template,
+            commands::update_transaction_template,
+            commands::delete_transaction_template,
+            commands::create_from_template,
             // Category Rules
             commands::list_category_rules,
             commands::create_category_rule,
             commands::update_category_rule,
             commands::delete_category_rule,
+            commands::reorder_category_rules,
+            commands::export_category_rules,
+            commands::import_category_rules,
+            commands::backtest_category_rules,
             commands::apply_category_rules,
+            commands::preview_category_rule,
+            commands::suggest_category_rules,
+            commands::suggest_category,
             // Import
             commands::preview_csv_file,
             commands::parse_csv_file,
             commands::import_transactions,
+            commands::import_csv_file_streaming,
+            commands::find_previous_import,
             commands::preview_boa_file,
             commands::parse_boa_file,
             commands::preview_pdf_file,
             commands::parse_pdf_file,
+            commands::preview_empower_file,
+            commands::import_empower_files,
+            commands::list_statement_importers,
+            commands::detect_statement_importer,
+            commands::preview_statement_file,
+            commands::parse_statement_file,
             // Budgets
             commands::list_budgets,
             commands::get_budget_summary,
             commands::create_budget,
             commands::update_budget,
             commands::delete_budget,
+            commands::set_budget_month_amount,
+            commands::list_budget_month_amounts,
+            commands::copy_budgets,
+            commands::generate_budgets,
+            commands::list_budget_alerts,
+            commands::dismiss_budget_alerts,
+            commands::list_budget_groups,
+            commands::create_budget_group,
+            commands::update_budget_group,
+            commands::delete_budget_group,
+            commands::get_budget_allocation_tree,
+            commands::get_category_rolling_average,
+            commands::get_category_history,
+            commands::get_budget_performance,
+            // Month Close
+            commands::close_month,
+            commands::reopen_month,
+            commands::list_month_closes,
+            commands::record_account_statement,
+            commands::list_account_statements,
+            commands::unlock_account_statement,
+            commands::list_review_queue,
             // Goals
             commands::list_goals,
             commands::create_goal,
             commands::update_goal,
             commands::delete_goal,
+            commands::list_deleted_goals,
+            commands::restore_goals,
+            commands::archive_goal,
+            commands::list_archived_goals,
+            commands::restore_goal,
+            commands::reorder_goals,
             commands::contribute_to_goal,
+            commands::list_goal_contributions,
+            commands::delete_goal_contribution,
+            commands::list_goal_contribution_schedules,
+            commands::set_goal_contribution_schedule,
+            commands::delete_goal_contribution_schedule,
+            commands::run_goal_contribution_schedules,
+            commands::get_goal_projection,
+            commands::get_debt_payoff_plan,
+            commands::list_goal_milestones,
+            commands::get_round_up_settings,
+            commands::set_round_up_settings,
+            commands::run_round_ups,
+            commands::preview_surplus_allocation,
+            commands::allocate_surplus,
             // Recurring Transactions
             commands::list_recurring_transactions,
             commands::detect_recurring_transactions,
             commands::create_recurring_transaction,
             commands::update_recurring_transaction,
             commands::delete_recurring_transaction,
+            commands::get_bills_calendar,
+            // Insurance Policies
+            commands::list_policies,
+            commands::create_policy,
+            commands::update_policy,
+            commands::delete_policy,
+            commands::get_upcoming_renewals,
+            // Gift Cards
+            commands::list_gift_cards,
+            commands::create_gift_card,
+            commands::update_gift_card,
+            commands::delete_gift_card,
+            commands::mark_paid_with_gift_card,
+            commands::unmark_paid_with_gift_card,
+            commands::get_gift_card_usages,
+            // Rewards
+            commands::list_reward_rules,
+            commands::create_reward_rule,
+            commands::update_reward_rule,
+            commands::delete_reward_rule,
+            commands::get_rewards_summary,
             // Investments
             commands::list_holdings,
             commands::get_investment_summary,
             commands::update_security_price,
+            commands::create_holding,
+            commands::update_holding,
+            commands::delete_holding,
+            commands::list_securities,
+            commands::create_security,
+            commands::update_security,
+            commands::delete_security,
+            commands::list_investment_transactions,
+            commands::create_investment_transaction,
+            commands::delete_investment_transaction,
+            // Reports
+            commands::get_spending_by_category,
+            commands::get_spending_by_account_matrix,
+            commands::get_cash_flow,
+            commands::get_rate_comparison,
+            commands::get_dashboard_summary,
+            commands::get_personal_inflation,
+            // Forecast
+            commands::get_cash_flow_forecast,
+            commands::get_safe_to_spend,
+            // Planned Purchases
+            commands::list_planned_purchases,
+            commands::create_planned_purchase,
+            commands::update_planned_purchase,
+            commands::delete_planned_purchase,
+            commands::recommend_purchase_month,
+            // Export
+            commands::export_qif,
+            commands::export_account_ofx,
+            commands::export_filtered_transactions,
+            commands::export_tax_package,
+            // Credit Scores
+            commands::record_credit_score,
+            commands::get_credit_score_history,
+            commands::delete_credit_score,
+            // Usage Stats
+            commands::get_local_usage_stats,
+            commands::reset_local_usage_stats,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -13,12 +13,83 @@ pub struct Account {
     pub available_balance: Option<i64>,
     pub credit_limit: Option<i64>,
     pub interest_rate: Option<f64>,
+    pub interest_compounding: String,
     pub is_active: bool,
     pub is_hidden: bool,
+    /// Keeps the account's spending out of budgets and spending reports
+    pub exclude_from_budget: bool,
     pub display_order: i32,
     pub ofx_account_id: Option<String>,
     pub last_sync_at: Option<String>,
     pub notes: Option<String>,
+    pub aliases: Vec<String>,
+    /// Name of the institution the account is held at
+    pub institution_name: Option<String>,
+    /// The account group net worth sections the account under
+    pub account_group_id: Option<String>,
+    /// Day of the month a credit card statement closes
+    pub statement_closing_day: Option<i32>,
+    /// Day of the month a credit card payment is due
+    pub payment_due_day: Option<i32>,
+    pub minimum_payment: Option<i64>,
+    /// Whether estimated interest is posted to the account each month
+    pub accrue_interest: bool,
+    /// The last month interest was posted for, as YYYY-MM
+    pub interest_accrued_through: Option<String>,
+    /// Current balance in the base currency, at today's exchange rate
+    pub base_balance: i64,
+    /// Date the account was closed. Closed accounts are also inactive.
+    pub closed_on: Option<String>,
+    /// Balance before any of the account's transactions. The current balance is this plus
+    /// everything posted to the account.
+    pub opening_balance: i64,
+    /// Date the opening balance stands at
+    pub opening_date: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Where an account stood when it was closed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountClosure {
+    pub account_id: String,
+    pub closed_on: String,
+    /// Balance before any transfer out
+    pub closing_balance: i64,
+    /// The account the closing balance was transferred to
+    pub transfer_account_id: Option<String>,
+    pub transfer_id: Option<String>,
+    pub transaction_count: i64,
+    pub first_transaction_date: Option<String>,
+    pub last_transaction_date: Option<String>,
+    pub notes: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountGroup {
+    pub id: String,
+    pub name: String,
+    pub display_order: i32,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Institution {
+    pub id: String,
+    pub name: String,
+    pub ofx_org: Option<String>,
+    pub ofx_fid: Option<String>,
+    pub ofx_url: Option<String>,
+    pub ofx_broker_id: Option<String>,
+    pub logo_url: Option<String>,
+    /// Logo color, as `#rrggbb`
+    pub primary_color: Option<String>,
+    pub website: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -50,6 +121,13 @@ pub struct Transaction {
     pub parent_transaction_id: Option<String>,
     pub created_at: String,
     pub updated_at: String,
+    pub original_amount: Option<i64>,
+    pub original_currency: Option<String>,
+    pub is_hold: bool,
+    pub hold_expires_at: Option<String>,
+    pub tags: Vec<String>,
+    /// Kept out of budgets and spending reports, e.g. an expense that gets reimbursed
+    pub exclude_from_budget: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -63,6 +141,77 @@ pub struct Category {
     pub color: Option<String>,
     pub is_system: bool,
     pub display_order: i32,
+    pub due_day: Option<i32>,
+    pub is_archived: bool,
+    /// Counted in the tax package, along with its subcategories
+    pub is_tax_deductible: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// A file kept with a transaction. Its contents are only read back for exports.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionAttachment {
+    pub id: String,
+    pub transaction_id: String,
+    pub file_name: String,
+    pub size: i64,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NoteTemplate {
+    pub id: String,
+    pub name: String,
+    pub template: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionTemplate {
+    pub id: String,
+    pub name: String,
+    pub account_id: Option<String>,
+    pub payee: Option<String>,
+    pub amount: Option<i64>,
+    pub category_id: Option<String>,
+    pub notes: Option<String>,
+    pub splits: Vec<TransactionTemplateSplit>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionTemplateSplit {
+    pub id: String,
+    pub template_id: String,
+    pub category_id: Option<String>,
+    pub amount: i64,
+    pub memo: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreditScore {
+    pub id: String,
+    pub date: String,
+    pub bureau: String,
+    pub score: i64,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Payee {
+    pub id: String,
+    pub name: String,
+    pub default_category_id: Option<String>,
+    pub aliases: Vec<String>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -81,6 +230,40 @@ pub struct CategoryRule {
     pub is_active: bool,
     pub created_at: String,
     pub updated_at: String,
+    pub transfer_account_id: Option<String>,
+    /// When set, the conditions decide the match and `rule_type`/`pattern` are ignored
+    pub conditions: Option<Vec<RuleCondition>>,
+    /// Edits made to matching transactions besides setting the category
+    pub actions: Option<Vec<RuleAction>>,
+    /// "all" when every condition must match, "any" when one is enough
+    pub match_mode: String,
+}
+
+/// One condition of a compound rule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleCondition {
+    /// Any rule type (`payee_contains`, `amount_equals`, ...), or `amount_between` / `account`
+    pub condition_type: String,
+    #[serde(default)]
+    pub pattern: String,
+    #[serde(default)]
+    pub amount_min: Option<i64>,
+    #[serde(default)]
+    pub amount_max: Option<i64>,
+    #[serde(default)]
+    pub account_id: Option<String>,
+}
+
+/// One edit a rule makes to a matching transaction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleAction {
+    /// `rename_payee`, `append_note`, `add_tag`, `mark_transfer` or `set_status`
+    pub action_type: String,
+    /// New payee, note text, tag, transfer account id or status
+    #[serde(default)]
+    pub value: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -91,6 +274,29 @@ pub struct Budget {
     pub period_type: String,
     pub amount: i64,
     pub rollover: bool,
+    /// Tracks spending without a limit; `amount` is unused
+    pub is_watch_only: bool,
+    pub created_at: String,
+    pub updated_at: String,
+    /// Length of a `custom` period in days
+    pub period_days: Option<i64>,
+    /// Date bi-weekly and custom periods count from
+    pub period_anchor: Option<String>,
+    /// Percentages of the monthly amount that raise an alert once spending reaches them
+    pub alert_thresholds: Vec<u32>,
+    /// Counts spending in the category's subcategories too
+    pub include_children: bool,
+}
+
+/// Several expense categories sharing one monthly budget
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetGroup {
+    pub id: String,
+    pub name: String,
+    /// Monthly amount for the whole group
+    pub amount: i64,
+    pub category_ids: Vec<String>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -105,14 +311,70 @@ pub struct Goal {
     pub current_amount: i64,
     pub target_date: Option<String>,
     pub linked_account_id: Option<String>,
+    /// Transfers into this account are recorded as contributions when imported
+    pub contribution_account_id: Option<String>,
+    /// `snowball` or `avalanche`, for debt payoff goals
+    pub payoff_strategy: Option<String>,
+    /// Total paid toward a debt payoff goal's debts each month
+    pub monthly_payment: Option<i64>,
+    /// Percentages of the target celebrated when reached
+    pub milestones: Vec<u32>,
+    /// Goals with a higher priority come first when splitting a surplus
+    pub priority: i32,
+    /// Share of a surplus split by weight
+    pub allocation_weight: u32,
     pub icon: Option<String>,
     pub color: Option<String>,
     pub is_achieved: bool,
     pub achieved_at: Option<String>,
+    /// Archived goals are hidden from the goal list but keep their history
+    pub is_archived: bool,
+    pub archived_at: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GoalContribution {
+    pub id: String,
+    pub goal_id: String,
+    pub amount: i64,
+    pub date: String,
+    /// The transfer this contribution was recorded from, if any
+    pub transaction_id: Option<String>,
+    pub notes: Option<String>,
+    pub created_at: String,
+}
+
+/// A contribution made to a goal automatically on a schedule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GoalContributionSchedule {
+    pub goal_id: String,
+    pub amount: i64,
+    /// `weekly`, `biweekly`, `monthly`, `quarterly` or `yearly`
+    pub frequency: String,
+    pub start_date: String,
+    /// When the next contribution is due
+    pub next_date: String,
+    /// Each contribution transfers the money from this account, when set
+    pub from_account_id: Option<String>,
+    pub is_active: bool,
     pub created_at: String,
     pub updated_at: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionStatusChange {
+    pub id: String,
+    pub transaction_id: String,
+    pub from_status: String,
+    pub to_status: String,
+    pub changed_at: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TransactionFilters {
@@ -125,6 +387,7 @@ pub struct TransactionFilters {
     pub min_amount: Option<i64>,
     pub max_amount: Option<i64>,
     pub is_transfer: Option<bool>,
+    pub check_number: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -145,6 +408,15 @@ pub struct SpendingByCategory {
     pub color: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CashFlowData {
+    pub period: String,
+    pub income: i64,
+    pub expenses: i64,
+    pub net: i64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct NetWorthSnapshot {
@@ -156,6 +428,50 @@ pub struct NetWorthSnapshot {
     pub created_at: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Security {
+    pub id: String,
+    /// Ticker symbol, upper case and unique
+    pub symbol: String,
+    pub name: Option<String>,
+    pub security_type: Option<String>,
+    /// Latest price per unit, in cents
+    pub current_price: Option<i64>,
+    pub price_updated_at: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// A buy, sell, dividend, split or fee in an investment account
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InvestmentTransaction {
+    pub id: String,
+    pub account_id: String,
+    pub security_id: Option<String>,
+    pub symbol: Option<String>,
+    pub transaction_type: String,
+    pub date: String,
+    /// Units bought or sold, or for a split the new units per old unit
+    pub quantity: Option<f64>,
+    pub price_per_unit: Option<i64>,
+    /// Value of the trade before fees, or the dividend or fee amount
+    pub total_amount: i64,
+    pub fees: i64,
+    /// Units the holding gained (or lost)
+    pub quantity_change: f64,
+    /// Cost basis the holding gained (or lost)
+    pub cost_basis_change: i64,
+    /// A sale's proceeds after fees less the cost basis of the units sold
+    pub realized_gain: Option<i64>,
+    /// The cash transaction posted to the account, if any
+    pub transaction_id: Option<String>,
+    pub notes: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RecurringTransaction {
@@ -173,6 +489,76 @@ pub struct RecurringTransaction {
     pub tolerance_amount: i64,
     pub is_auto_detected: bool,
     pub is_active: bool,
+    pub due_day: Option<i32>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InsurancePolicy {
+    pub id: String,
+    pub name: String,
+    pub policy_type: String,
+    pub provider: Option<String>,
+    pub policy_number: Option<String>,
+    pub premium: i64,
+    pub premium_frequency: String,
+    pub term_months: i32,
+    pub renewal_date: Option<String>,
+    pub recurring_transaction_id: Option<String>,
+    pub notes: Option<String>,
+    pub is_active: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GiftCard {
+    pub id: String,
+    pub name: String,
+    pub merchant: Option<String>,
+    pub initial_balance: i64,
+    pub balance: i64,
+    pub expiry_date: Option<String>,
+    pub notes: Option<String>,
+    pub is_active: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GiftCardUsage {
+    pub id: String,
+    pub gift_card_id: String,
+    pub transaction_id: String,
+    pub amount: i64,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RewardRule {
+    pub id: String,
+    pub account_id: String,
+    pub category_id: Option<String>,
+    pub rate: f64,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlannedPurchase {
+    pub id: String,
+    pub name: String,
+    pub target_amount: i64,
+    pub desired_date: String,
+    pub category_id: Option<String>,
+    pub notes: Option<String>,
+    pub is_purchased: bool,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -0,0 +1,176 @@
+//! Conformance suite for the statement parsers, driven by the files under
+//! `tests/fixtures/parsers/`. Each parser has a directory of anonymized statements, each
+//! next to a `<name>.json` describing what the parser should produce; see the README
+//! there for the format. Adding a bank means adding files, not code.
+
+use serde_json::{json, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tally_lib::import::csv_parser::{self, ColumnMapping};
+use tally_lib::import::registry::ImporterRegistry;
+use tally_lib::import::{boa_parser, empower_parser, pdf_parser};
+
+/// Keys in an expected file that configure the run rather than describe the output
+const SETTINGS_KEYS: &[&str] = &["description", "mapping"];
+
+fn fixtures_dir(parser: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/parsers")
+        .join(parser)
+}
+
+/// Statement files in a parser's directory paired with their expected output
+fn fixtures(parser: &str) -> Vec<(PathBuf, Value)> {
+    let Ok(entries) = fs::read_dir(fixtures_dir(parser)) else {
+        return Vec::new();
+    };
+    let mut statements: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| {
+            p.is_file()
+                && !matches!(
+                    p.extension().and_then(|e| e.to_str()),
+                    Some("json") | Some("md")
+                )
+        })
+        .collect();
+    statements.sort();
+
+    statements
+        .into_iter()
+        .map(|statement| {
+            let expected_path = statement.with_extension("json");
+            let expected = fs::read_to_string(&expected_path)
+                .unwrap_or_else(|e| panic!("{}: {}", expected_path.display(), e));
+            let expected = serde_json::from_str(&expected)
+                .unwrap_or_else(|e| panic!("{}: {}", expected_path.display(), e));
+            (statement, expected)
+        })
+        .collect()
+}
+
+/// Check that everything in `expected` appears in `actual`. Objects may have extra keys
+/// in the output; arrays must have the same length.
+fn compare(expected: &Value, actual: &Value, path: &str, errors: &mut Vec<String>) {
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) => {
+            for (key, value) in expected {
+                let field = format!("{}.{}", path, key);
+                match actual.get(key) {
+                    Some(actual) => compare(value, actual, &field, errors),
+                    None => errors.push(format!("{}: missing from output", field)),
+                }
+            }
+        }
+        (Value::Array(expected), Value::Array(actual)) => {
+            if expected.len() != actual.len() {
+                errors.push(format!(
+                    "{}: expected {} items, parsed {}",
+                    path,
+                    expected.len(),
+                    actual.len()
+                ));
+            }
+            for (i, (expected, actual)) in expected.iter().zip(actual).enumerate() {
+                compare(expected, actual, &format!("{}[{}]", path, i), errors);
+            }
+        }
+        (Value::Number(expected), Value::Number(actual))
+            if expected.as_f64() == actual.as_f64() => {}
+        _ if expected == actual => {}
+        _ => errors.push(format!(
+            "{}: expected {}, parsed {}",
+            path, expected, actual
+        )),
+    }
+}
+
+/// Run a parser over each of its fixtures and fail listing every mismatch
+fn check_parser(parser: &str, parse: impl Fn(&Path, &Value) -> Result<Value, String>) {
+    let mut failures = Vec::new();
+
+    for (statement, mut expected) in fixtures(parser) {
+        let name = statement
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .into_owned();
+        let actual = match parse(&statement, &expected) {
+            Ok(actual) => actual,
+            Err(e) => {
+                failures.push(format!("{}/{}: parser failed: {}", parser, name, e));
+                continue;
+            }
+        };
+
+        if let Some(expected) = expected.as_object_mut() {
+            expected.retain(|key, _| !SETTINGS_KEYS.contains(&key.as_str()));
+        }
+        let mut errors = Vec::new();
+        compare(&expected, &actual, "", &mut errors);
+        failures.extend(
+            errors
+                .into_iter()
+                .map(|e| format!("{}/{}: {}", parser, name, e)),
+        );
+    }
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+#[test]
+fn boa_fixtures() {
+    check_parser("boa", |path, _| {
+        let preview = boa_parser::preview_boa(path, usize::MAX).map_err(|e| e.to_string())?;
+        serde_json::to_value(preview).map_err(|e| e.to_string())
+    });
+}
+
+/// PDF fixtures are the statement's extracted text, since a PDF is hard to anonymize
+#[test]
+fn pdf_fixtures() {
+    check_parser("pdf", |path, _| {
+        let text = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        let preview = pdf_parser::preview_pdf_text(&text, usize::MAX).map_err(|e| e.to_string())?;
+        serde_json::to_value(preview).map_err(|e| e.to_string())
+    });
+}
+
+/// CSV fixtures carry the column mapping the user would pick in their expected file
+#[test]
+fn csv_fixtures() {
+    check_parser("csv", |path, expected| {
+        let mapping: ColumnMapping = serde_json::from_value(expected["mapping"].clone())
+            .map_err(|e| format!("invalid mapping: {}", e))?;
+        let transactions = csv_parser::parse_csv(path, &mapping).map_err(|e| e.to_string())?;
+        Ok(json!({ "transactions": transactions }))
+    });
+}
+
+#[test]
+fn empower_fixtures() {
+    check_parser("empower", |path, _| {
+        let export = empower_parser::parse_empower(path).map_err(|e| e.to_string())?;
+        serde_json::to_value(export).map_err(|e| e.to_string())
+    });
+}
+
+/// Every statement fixture is picked up by its own importer, and the mapping-driven
+/// CSV files by none
+#[test]
+fn registry_detects_fixtures() {
+    let registry = ImporterRegistry::with_builtins();
+    for (statement, _) in fixtures("boa") {
+        let importer = registry
+            .detect(&statement)
+            .expect("BoA statement not detected");
+        assert_eq!(importer.id(), "boa");
+        let preview = importer.preview(&statement, 1).unwrap();
+        let parsed = boa_parser::parse_boa(&statement).unwrap();
+        assert_eq!(preview.total_rows, parsed.len());
+        assert!(preview.transactions.len() <= 1);
+    }
+    for (statement, _) in fixtures("csv") {
+        assert!(registry.detect(&statement).is_none());
+    }
+}
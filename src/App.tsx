@@ -1,7 +1,13 @@
+import { useEffect } from "react";
 import { RouterProvider } from "react-router-dom";
 import { router } from "./router";
 import { Unlock } from "@/pages/Unlock";
 import { useAppStore } from "@/stores/useAppStore";
+import {
+  subscribeStoresToChanges,
+  subscribeToBudgetAlerts,
+  subscribeToGoalMilestones,
+} from "@/lib/events";
 
 function App() {
   const { isUnlocked } = useAppStore();
@@ -9,6 +15,18 @@ function App() {
   // For development without Tauri backend, skip unlock
   const isDev = import.meta.env.DEV && !window.__TAURI_INTERNALS__;
 
+  useEffect(() => {
+    if (!isUnlocked) return;
+    const subscriptions = [
+      subscribeStoresToChanges(),
+      subscribeToBudgetAlerts(),
+      subscribeToGoalMilestones(),
+    ];
+    return () => {
+      subscriptions.forEach((subscription) => subscription.then((unsubscribe) => unsubscribe()));
+    };
+  }, [isUnlocked]);
+
   if (!isUnlocked && !isDev) {
     return <Unlock />;
   }
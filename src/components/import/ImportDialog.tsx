@@ -35,6 +35,7 @@ import {
   previewCsvFile,
   parseCsvFile,
   importTransactions,
+  findPreviousImport,
   previewBoaFile,
   parseBoaFile,
   previewPdfFile,
@@ -48,7 +49,7 @@ import {
   type PdfPreview,
 } from "@/lib/tauri";
 import type { TransferCandidate } from "@/types";
-import { formatMoney } from "@/lib/formatters";
+import { formatDate, formatMoney } from "@/lib/formatters";
 import { cn } from "@/lib/utils";
 
 type Step = "upload" | "review" | "transfers" | "complete";
@@ -62,7 +63,7 @@ interface ImportDialogProps {
 
 export function ImportDialog({ open: isOpen, onOpenChange, onComplete }: ImportDialogProps) {
   const navigate = useNavigate();
-  const { accounts, fetchAccounts } = useAccountStore();
+  const { accounts, fetchAccounts, getOpenAccounts } = useAccountStore();
   const [step, setStep] = useState<Step>("upload");
   const [filePath, setFilePath] = useState<string | null>(null);
   const [fileName, setFileName] = useState<string | null>(null);
@@ -84,7 +85,7 @@ export function ImportDialog({ open: isOpen, onOpenChange, onComplete }: ImportD
   const [parsedTransactions, setParsedTransactions] = useState<ParsedTransaction[]>([]);
   const [selectedTransactions, setSelectedTransactions] = useState<Set<number>>(new Set());
   const [lastClickedIndex, setLastClickedIndex] = useState<number | null>(null);
-  const [importResult, setImportResult] = useState<{ imported: number; skipped: number; categorized: number; transfersLinked: number } | null>(null);
+  const [importResult, setImportResult] = useState<{ imported: number; skipped: number; categorized: number; transfersLinked: number; errors: string[] } | null>(null);
   const [transferCandidates, setTransferCandidates] = useState<TransferCandidate[]>([]);
   const [error, setError] = useState<string | null>(null);
   const [loading, setLoading] = useState(false);
@@ -171,6 +172,18 @@ export function ImportDialog({ open: isOpen, onOpenChange, onComplete }: ImportD
       });
 
       if (selected && typeof selected === "string") {
+        // Same file contents as an earlier import: ask before going through it again
+        const previous = await findPreviousImport(selected).catch(() => null);
+        if (
+          previous &&
+          !confirm(
+            `This file was already imported on ${formatDate(previous.importedAt.slice(0, 10))}` +
+              `${previous.accountName ? ` into ${previous.accountName}` : ""}. Re-run anyway?`
+          )
+        ) {
+          return;
+        }
+
         setFilePath(selected);
         const name = selected.split("/").pop() || selected;
         setFileName(name);
@@ -383,10 +396,12 @@ export function ImportDialog({ open: isOpen, onOpenChange, onComplete }: ImportD
           payee: tx.payee,
           memo: tx.memo,
           pdfCategory: tx.categoryHint,
+          originalAmount: tx.originalAmount,
+          originalCurrency: tx.originalCurrency,
         }));
 
-      const result = await importTransactions(accountId, transactionsToImport);
-      setImportResult({ imported: result.imported, skipped: result.skipped, categorized: result.categorized, transfersLinked: 0 });
+      const result = await importTransactions(accountId, transactionsToImport, filePath ?? undefined);
+      setImportResult({ imported: result.imported, skipped: result.skipped, categorized: result.categorized, transfersLinked: 0, errors: result.errors });
 
       // Detect potential transfers after import
       if (result.imported > 0) {
@@ -407,7 +422,7 @@ export function ImportDialog({ open: isOpen, onOpenChange, onComplete }: ImportD
     } finally {
       setLoading(false);
     }
-  }, [accountId, parsedTransactions, selectedTransactions]);
+  }, [accountId, parsedTransactions, selectedTransactions, filePath]);
 
   const toggleTransaction = (index: number, event?: React.MouseEvent) => {
     if (event?.shiftKey && lastClickedIndex !== null) {
@@ -611,7 +626,7 @@ export function ImportDialog({ open: isOpen, onOpenChange, onComplete }: ImportD
                       <SelectValue placeholder="Select an account" />
                     </SelectTrigger>
                     <SelectContent>
-                      {accounts.map((account) => (
+                      {getOpenAccounts(accountId).map((account) => (
                         <SelectItem key={account.id} value={account.id}>
                           {account.name}
                         </SelectItem>
@@ -835,7 +850,7 @@ export function ImportDialog({ open: isOpen, onOpenChange, onComplete }: ImportD
                       <SelectValue placeholder="Select an account" />
                     </SelectTrigger>
                     <SelectContent>
-                      {accounts.map((account) => (
+                      {getOpenAccounts(accountId).map((account) => (
                         <SelectItem key={account.id} value={account.id}>
                           {account.name}
                         </SelectItem>
@@ -898,7 +913,7 @@ export function ImportDialog({ open: isOpen, onOpenChange, onComplete }: ImportD
                       <SelectValue placeholder="Select an account" />
                     </SelectTrigger>
                     <SelectContent>
-                      {accounts.map((account) => (
+                      {getOpenAccounts(accountId).map((account) => (
                         <SelectItem key={account.id} value={account.id}>
                           {account.name}
                         </SelectItem>
@@ -1141,6 +1156,16 @@ export function ImportDialog({ open: isOpen, onOpenChange, onComplete }: ImportD
                   {importResult.transfersLinked} transfer{importResult.transfersLinked === 1 ? " was" : "s were"} linked between accounts
                 </p>
               )}
+              {importResult.errors.length > 0 && (
+                <div className="text-sm text-red-600 mt-2">
+                  <p>
+                    {importResult.errors.length} row{importResult.errors.length === 1 ? " was" : "s were"} not imported:
+                  </p>
+                  {importResult.errors.map((error) => (
+                    <p key={error}>{error}</p>
+                  ))}
+                </div>
+              )}
             </div>
           )}
         </div>
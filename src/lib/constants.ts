@@ -6,11 +6,23 @@ export const ACCOUNT_TYPES = {
   retirement: { label: "Retirement", icon: "TrendingUp", color: "amber" },
   loan: { label: "Loan", icon: "Landmark", color: "red" },
   cash: { label: "Cash", icon: "Banknote", color: "emerald" },
+  property: { label: "Property", icon: "Home", color: "teal" },
   other: { label: "Other", icon: "CircleDollarSign", color: "gray" },
 } as const;
 
 export type AccountType = keyof typeof ACCOUNT_TYPES;
 
+// Account types counted as assets and as liabilities in net worth, as on the backend
+export const ASSET_ACCOUNT_TYPES: AccountType[] = [
+  "checking",
+  "savings",
+  "investment",
+  "retirement",
+  "property",
+  "cash",
+];
+export const LIABILITY_ACCOUNT_TYPES: AccountType[] = ["credit_card", "loan"];
+
 export const TRANSACTION_STATUS = {
   pending: { label: "Pending", color: "yellow" },
   cleared: { label: "Cleared", color: "green" },
@@ -33,10 +45,20 @@ export const BUDGET_PERIODS = {
   monthly: { label: "Monthly", days: 30 },
   quarterly: { label: "Quarterly", days: 90 },
   yearly: { label: "Yearly", days: 365 },
+  custom: { label: "Custom", days: null },
 } as const;
 
 export type BudgetPeriod = keyof typeof BUDGET_PERIODS;
 
+export const INTEREST_COMPOUNDING = {
+  daily: { label: "Daily" },
+  monthly: { label: "Monthly" },
+  quarterly: { label: "Quarterly" },
+  annually: { label: "Annually" },
+} as const;
+
+export type InterestCompounding = keyof typeof INTEREST_COMPOUNDING;
+
 export const NAV_ITEMS = [
   { path: "/", label: "Dashboard", icon: "LayoutDashboard" },
   { path: "/accounts", label: "Accounts", icon: "Wallet" },
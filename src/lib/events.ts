@@ -0,0 +1,109 @@
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+import { useAccountStore } from "@/stores/useAccountStore";
+import { useCategoryStore } from "@/stores/useCategoryStore";
+import { useTransactionStore } from "@/stores/useTransactionStore";
+import { formatMoney } from "@/lib/formatters";
+import type { ImportProgress } from "@/lib/tauri";
+import type { BudgetAlert, GoalMilestone } from "@/types";
+
+// Emitted by the backend after a command changes data (see src-tauri/src/events.rs)
+export const ACCOUNTS_CHANGED = "accounts:changed";
+export const TRANSACTIONS_CHANGED = "transactions:changed";
+export const CATEGORIES_CHANGED = "categories:changed";
+export const PAYEES_CHANGED = "payees:changed";
+export const RULES_CHANGED = "rules:changed";
+export const BUDGETS_CHANGED = "budgets:changed";
+export const GOALS_CHANGED = "goals:changed";
+export const RECURRING_CHANGED = "recurring:changed";
+export const GIFT_CARDS_CHANGED = "gift_cards:changed";
+export const POLICIES_CHANGED = "policies:changed";
+export const NOTE_TEMPLATES_CHANGED = "note_templates:changed";
+export const REWARDS_CHANGED = "rewards:changed";
+export const PLANNED_PURCHASES_CHANGED = "planned_purchases:changed";
+// Holdings or securities changed, including their prices
+export const INVESTMENTS_CHANGED = "investments:changed";
+export const CREDIT_SCORES_CHANGED = "credit_scores:changed";
+
+// Carries an ImportProgress payload, unlike the change events above
+export const IMPORT_PROGRESS = "import:progress";
+// Carries the BudgetAlerts raised by an import or edit
+export const BUDGET_ALERT = "budgets:alert";
+// Carries the GoalMilestones just reached
+export const GOAL_MILESTONE = "goals:milestone";
+
+export function onChanged(event: string, handler: () => void): Promise<UnlistenFn> {
+  return listen(event, handler);
+}
+
+export function onImportProgress(
+  handler: (progress: ImportProgress) => void
+): Promise<UnlistenFn> {
+  return listen<ImportProgress>(IMPORT_PROGRESS, (event) => handler(event.payload));
+}
+
+export function onBudgetAlert(handler: (alerts: BudgetAlert[]) => void): Promise<UnlistenFn> {
+  return listen<BudgetAlert[]>(BUDGET_ALERT, (event) => handler(event.payload));
+}
+
+export function onGoalMilestone(
+  handler: (milestones: GoalMilestone[]) => void
+): Promise<UnlistenFn> {
+  return listen<GoalMilestone[]>(GOAL_MILESTONE, (event) => handler(event.payload));
+}
+
+// Show a system notification for each budget alert the backend raises
+export async function subscribeToBudgetAlerts(): Promise<UnlistenFn> {
+  if ("Notification" in window && Notification.permission === "default") {
+    await Notification.requestPermission().catch(() => undefined);
+  }
+  return onBudgetAlert((alerts) => {
+    if (!("Notification" in window) || Notification.permission !== "granted") return;
+    for (const alert of alerts) {
+      new Notification(`${alert.categoryName} budget at ${alert.threshold}%`, {
+        body: `${formatMoney(alert.spent)} spent of ${formatMoney(alert.budgeted)} this month`,
+      });
+    }
+  });
+}
+
+// Show a system notification for each goal milestone reached
+export async function subscribeToGoalMilestones(): Promise<UnlistenFn> {
+  return onGoalMilestone((milestones) => {
+    if (!("Notification" in window) || Notification.permission !== "granted") return;
+    for (const milestone of milestones) {
+      new Notification(
+        milestone.percent === 100
+          ? `${milestone.goalName} reached!`
+          : `${milestone.goalName} is ${milestone.percent}% there`,
+        {
+          body: `${formatMoney(milestone.amount)} saved of ${formatMoney(milestone.targetAmount)}`,
+        }
+      );
+    }
+  });
+}
+
+// Keep the shared stores in sync with the backend. Stores that were never loaded
+// are left alone; they fetch fresh data when a page first needs them.
+export async function subscribeStoresToChanges(): Promise<UnlistenFn> {
+  const unlisteners = await Promise.all([
+    onChanged(ACCOUNTS_CHANGED, () => {
+      const store = useAccountStore.getState();
+      if (store.lastFetchedAt) store.fetchAccounts();
+    }),
+    onChanged(TRANSACTIONS_CHANGED, () => {
+      const store = useTransactionStore.getState();
+      if (store.lastFetchedAt) store.fetchTransactions();
+    }),
+    onChanged(CATEGORIES_CHANGED, () => {
+      const store = useCategoryStore.getState();
+      if (store.lastFetchedAt) store.fetchCategories();
+    }),
+    onChanged(RULES_CHANGED, () => {
+      const store = useCategoryStore.getState();
+      if (store.rules.length > 0) store.fetchRules();
+    }),
+  ]);
+
+  return () => unlisteners.forEach((unlisten) => unlisten());
+}
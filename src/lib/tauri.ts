@@ -1,20 +1,93 @@
 import { invoke } from "@tauri-apps/api/core";
 import type {
   Account,
+  AccountClosure,
+  AccountDeleteResult,
+  AccountDeleteTransactions,
+  AccountGroup,
+  AccountGroupNetWorth,
+  AccountMergeResult,
+  CreditCardStatus,
+  FxRate,
+  Institution,
   Transaction,
+  TransactionAttachment,
   Category,
   Budget,
+  BudgetAllocation,
+  BudgetMonthAmount,
+  BudgetAlert,
+  BudgetGroup,
+  BudgetCopyResult,
+  GeneratedBudgets,
+  MonthBudgetSummary,
+  MonthClose,
+  AccountStatement,
+  CategoryRollingAverage,
+  CategoryHistory,
+  BudgetPerformance,
+  CategoryImportResult,
   Goal,
+  GoalDebt,
+  DebtPayoffPlan,
+  PayoffStrategy,
+  GoalContribution,
+  GoalContributionSchedule,
+  GoalMilestone,
+  RoundUpSettings,
+  AllocationStrategy,
+  SurplusPlan,
+  GoalProjection,
   RecurringTransaction,
+  BillsCalendar,
   DetectedRecurring,
   TransactionFilters,
+  TaxPackage,
+  LocalUsageStats,
+  TransactionStatusChange,
   TransferCandidate,
+  BulkDeleteResult,
+  TransactionStats,
+  DuplicateGroup,
+  HoldResolution,
+  CheckNumberGap,
+  QuickEntryDraft,
   SpendingByCategory,
+  SpendingMatrix,
+  PersonalInflation,
   CashFlowData,
   NetWorthSnapshot,
+  CreditScore,
+  InsurancePolicy,
+  PolicyRenewal,
+  GiftCard,
+  GiftCardUsage,
+  PlannedPurchase,
+  PurchaseRecommendation,
+  CashFlowForecast,
+  SafeToSpend,
+  RewardRule,
+  RewardsSummary,
   CategoryRule,
+  RulePreview,
+  RuleSuggestion,
+  RuleImportResult,
+  RuleBacktest,
+  CategorySuggestion,
   Holding,
+  Security,
+  InvestmentTransaction,
+  InvestmentTransactionType,
+  InvestmentSummary,
+  Payee,
+  NoteTemplate,
+  TransactionTemplate,
+  PayeeSuggestion,
+  PayeeStats,
+  RateComparisonReport,
+  DashboardSummary,
 } from "@/types";
+import type { TransactionStatus } from "@/lib/constants";
 
 // Database commands
 export async function unlockDatabase(password: string): Promise<boolean> {
@@ -34,15 +107,17 @@ export async function databaseExists(): Promise<boolean> {
 }
 
 // Account commands
-export async function listAccounts(): Promise<Account[]> {
-  return invoke("list_accounts");
+export async function listAccounts(query?: string): Promise<Account[]> {
+  return invoke("list_accounts", { query: query ?? null });
 }
 
 export async function getAccount(id: string): Promise<Account> {
   return invoke("get_account", { id });
 }
 
-export async function createAccount(data: Omit<Account, "id" | "createdAt" | "updatedAt">): Promise<Account> {
+export async function createAccount(
+  data: Omit<Account, "id" | "aliases" | "createdAt" | "updatedAt">
+): Promise<Account> {
   return invoke("create_account", { data });
 }
 
@@ -50,8 +125,163 @@ export async function updateAccount(id: string, data: Partial<Account>): Promise
   return invoke("update_account", { id, data });
 }
 
-export async function deleteAccount(id: string): Promise<void> {
-  return invoke("delete_account", { id });
+export async function reorderAccounts(orderedIds: string[]): Promise<void> {
+  return invoke("reorder_accounts", { orderedIds });
+}
+
+export async function adjustAccountBalance(
+  accountId: string,
+  newBalance: number,
+  date?: string
+): Promise<Transaction> {
+  return invoke("adjust_account_balance", { accountId, newBalance, date });
+}
+
+export async function deleteAccount(
+  id: string,
+  options?: {
+    transactions?: AccountDeleteTransactions;
+    reassignToAccountId?: string;
+    dryRun?: boolean;
+  }
+): Promise<AccountDeleteResult> {
+  return invoke("delete_account", {
+    id,
+    transactions: options?.transactions ?? null,
+    reassignToAccountId: options?.reassignToAccountId ?? null,
+    dryRun: options?.dryRun ?? null,
+  });
+}
+
+export async function mergeAccounts(
+  sourceId: string,
+  targetId: string
+): Promise<AccountMergeResult> {
+  return invoke("merge_accounts", { sourceId, targetId });
+}
+
+export async function closeAccount(
+  accountId: string,
+  data: { date?: string; transferAccountId?: string; notes?: string }
+): Promise<AccountClosure> {
+  return invoke("close_account", { accountId, data });
+}
+
+export async function reopenAccount(accountId: string): Promise<Account> {
+  return invoke("reopen_account", { accountId });
+}
+
+export async function getAccountClosure(accountId: string): Promise<AccountClosure | null> {
+  return invoke("get_account_closure", { accountId });
+}
+
+export async function addAccountAlias(accountId: string, alias: string): Promise<Account> {
+  return invoke("add_account_alias", { accountId, alias });
+}
+
+export async function removeAccountAlias(accountId: string, alias: string): Promise<Account> {
+  return invoke("remove_account_alias", { accountId, alias });
+}
+
+export async function listDeletedAccounts(): Promise<Account[]> {
+  return invoke("list_deleted_accounts");
+}
+
+export async function restoreAccounts(ids: string[]): Promise<void> {
+  return invoke("restore_accounts", { ids });
+}
+
+// Institution commands
+export async function listInstitutions(): Promise<Institution[]> {
+  return invoke("list_institutions");
+}
+
+export async function createInstitution(data: Partial<Institution>): Promise<Institution> {
+  return invoke("create_institution", { data });
+}
+
+export async function updateInstitution(
+  id: string,
+  data: Partial<Institution>
+): Promise<Institution> {
+  return invoke("update_institution", { id, data });
+}
+
+export async function deleteInstitution(id: string): Promise<void> {
+  return invoke("delete_institution", { id });
+}
+
+// Account group commands
+export async function listAccountGroups(): Promise<AccountGroup[]> {
+  return invoke("list_account_groups");
+}
+
+export async function createAccountGroup(data: Partial<AccountGroup>): Promise<AccountGroup> {
+  return invoke("create_account_group", { data });
+}
+
+export async function updateAccountGroup(
+  id: string,
+  data: Partial<AccountGroup>
+): Promise<AccountGroup> {
+  return invoke("update_account_group", { id, data });
+}
+
+export async function deleteAccountGroup(id: string): Promise<void> {
+  return invoke("delete_account_group", { id });
+}
+
+export async function getNetWorthByGroup(): Promise<AccountGroupNetWorth[]> {
+  return invoke("get_net_worth_by_group");
+}
+
+// Credit card commands
+export async function getCreditCardStatuses(): Promise<CreditCardStatus[]> {
+  return invoke("get_credit_card_statuses");
+}
+
+export async function getCreditCardStatus(accountId: string): Promise<CreditCardStatus> {
+  return invoke("get_credit_card_status", { accountId });
+}
+
+// Interest accrual commands
+export async function runInterestAccrual(): Promise<number> {
+  return invoke("run_interest_accrual");
+}
+
+// Currency commands
+export async function getBaseCurrency(): Promise<string> {
+  return invoke("get_base_currency");
+}
+
+export async function setBaseCurrency(currency: string): Promise<string> {
+  return invoke("set_base_currency", { currency });
+}
+
+// Account currencies with no exchange rate to the base currency yet
+export async function listMissingFxRates(): Promise<string[]> {
+  return invoke("list_missing_fx_rates");
+}
+
+export async function listFxRates(currency?: string): Promise<FxRate[]> {
+  return invoke("list_fx_rates", { currency: currency ?? null });
+}
+
+export async function setFxRate(data: {
+  fromCurrency: string;
+  toCurrency: string;
+  date: string;
+  rate: number;
+}): Promise<FxRate> {
+  return invoke("set_fx_rate", { data });
+}
+
+export async function deleteFxRate(id: string): Promise<void> {
+  return invoke("delete_fx_rate", { id });
+}
+
+export async function fetchFxRates(date?: string): Promise<FxRate[]> {
+  return invoke("fetch_fx_rates", { date: date ?? null });
 }
 
 // Transaction commands
@@ -59,12 +289,22 @@ export async function listTransactions(filters: Partial<TransactionFilters> = {}
   return invoke("list_transactions", { filters });
 }
 
+export async function getTransactionStats(filters: Partial<TransactionFilters> = {}): Promise<TransactionStats> {
+  return invoke("get_transaction_stats", { filters });
+}
+
 export async function getTransaction(id: string): Promise<Transaction> {
   return invoke("get_transaction", { id });
 }
 
 export async function createTransaction(
-  data: Omit<Transaction, "id" | "createdAt" | "updatedAt">
+  data: Omit<Transaction, "id" | "createdAt" | "updatedAt" | "originalAmount" | "originalCurrency"> & {
+    originalAmount?: number | null;
+    originalCurrency?: string | null;
+    exchangeRate?: number;
+    noteTemplateId?: string;
+    noteVariables?: Record<string, string | number>;
+  }
 ): Promise<Transaction> {
   return invoke("create_transaction", { data });
 }
@@ -77,14 +317,57 @@ export async function deleteTransactions(ids: string[]): Promise<void> {
   return invoke("delete_transactions", { ids });
 }
 
+export async function deleteTransactionsByFilter(
+  filters: Partial<TransactionFilters>,
+  dryRun: boolean
+): Promise<BulkDeleteResult> {
+  return invoke("delete_transactions_by_filter", { filters, dryRun });
+}
+
+export async function listDeletedTransactions(): Promise<Transaction[]> {
+  return invoke("list_deleted_transactions");
+}
+
+export async function restoreTransactions(ids: string[]): Promise<void> {
+  return invoke("restore_transactions", { ids });
+}
+
+export async function setTransactionStatus(ids: string[], status: TransactionStatus): Promise<void> {
+  return invoke("set_transaction_status", { ids, status });
+}
+
+export async function getTransactionStatusHistory(transactionId: string): Promise<TransactionStatusChange[]> {
+  return invoke("get_transaction_status_history", { transactionId });
+}
+
 export async function bulkCategorize(ids: string[], categoryId: string): Promise<void> {
   return invoke("bulk_categorize", { ids, categoryId });
 }
 
+export async function parseQuickEntry(text: string): Promise<QuickEntryDraft> {
+  return invoke("parse_quick_entry", { text });
+}
+
+export async function nextCheckNumber(accountId: string): Promise<string | null> {
+  return invoke("next_check_number", { accountId });
+}
+
+export async function detectCheckNumberGaps(accountId?: string): Promise<CheckNumberGap[]> {
+  return invoke("detect_check_number_gaps", { accountId });
+}
+
 export async function detectTransfers(): Promise<TransferCandidate[]> {
   return invoke("detect_transfers");
 }
 
+export async function findDuplicateTransactions(): Promise<DuplicateGroup[]> {
+  return invoke("find_duplicate_transactions");
+}
+
+export async function mergeTransactions(keepId: string, duplicateIds: string[]): Promise<Transaction> {
+  return invoke("merge_transactions", { keepId, duplicateIds });
+}
+
 export async function linkTransfer(transactionAId: string, transactionBId: string): Promise<void> {
   return invoke("link_transfer", { transactionAId, transactionBId });
 }
@@ -93,6 +376,44 @@ export async function unlinkTransfer(transactionId: string): Promise<void> {
   return invoke("unlink_transfer", { transactionId });
 }
 
+export async function detectCreditCardPayments(): Promise<TransferCandidate[]> {
+  return invoke("detect_credit_card_payments");
+}
+
+export async function linkCreditCardPayment(paymentTransactionId: string, cardTransactionId: string): Promise<void> {
+  return invoke("link_credit_card_payment", { paymentTransactionId, cardTransactionId });
+}
+
+// Attachment commands
+export async function addTransactionAttachment(transactionId: string, path: string): Promise<TransactionAttachment> {
+  return invoke("add_transaction_attachment", { transactionId, path });
+}
+
+export async function listTransactionAttachments(transactionId: string): Promise<TransactionAttachment[]> {
+  return invoke("list_transaction_attachments", { transactionId });
+}
+
+export async function deleteTransactionAttachment(id: string): Promise<void> {
+  return invoke("delete_transaction_attachment", { id });
+}
+
+// Hold commands
+export async function listHolds(): Promise<Transaction[]> {
+  return invoke("list_holds");
+}
+
+export async function markAsHold(ids: string[], expiresAt?: string): Promise<void> {
+  return invoke("mark_as_hold", { ids, expiresAt });
+}
+
+export async function releaseHold(ids: string[]): Promise<void> {
+  return invoke("release_hold", { ids });
+}
+
+export async function processHolds(): Promise<HoldResolution> {
+  return invoke("process_holds");
+}
+
 // Category commands
 export async function listCategories(): Promise<Category[]> {
   return invoke("list_categories");
@@ -108,10 +429,152 @@ export async function updateCategory(id: string, data: Partial<Category>): Promi
   return invoke("update_category", { id, data });
 }
 
+export async function moveCategory(id: string, newParentId: string | null): Promise<Category> {
+  return invoke("move_category", { id, newParentId });
+}
+
+export async function reorderCategories(orderedIds: string[]): Promise<void> {
+  return invoke("reorder_categories", { orderedIds });
+}
+
+export async function archiveCategory(id: string): Promise<void> {
+  return invoke("archive_category", { id });
+}
+
+export async function unarchiveCategory(id: string): Promise<void> {
+  return invoke("unarchive_category", { id });
+}
+
+export async function setCategoryTaxDeductible(id: string, deductible: boolean): Promise<Category> {
+  return invoke("set_category_tax_deductible", { id, deductible });
+}
+
 export async function deleteCategory(id: string): Promise<void> {
   return invoke("delete_category", { id });
 }
 
+export async function listDeletedCategories(): Promise<Category[]> {
+  return invoke("list_deleted_categories");
+}
+
+export async function restoreCategories(ids: string[]): Promise<void> {
+  return invoke("restore_categories", { ids });
+}
+
+export async function exportCategories(): Promise<string> {
+  return invoke("export_categories");
+}
+
+export async function importCategories(contents: string): Promise<CategoryImportResult> {
+  return invoke("import_categories", { contents });
+}
+
+// Payee commands
+export async function listPayees(): Promise<Payee[]> {
+  return invoke("list_payees");
+}
+
+export async function createPayee(data: { name: string; defaultCategoryId?: string | null }): Promise<Payee> {
+  return invoke("create_payee", { data });
+}
+
+export async function updatePayee(
+  id: string,
+  data: { name?: string; defaultCategoryId?: string | null }
+): Promise<Payee> {
+  return invoke("update_payee", { id, data });
+}
+
+export async function deletePayee(id: string): Promise<void> {
+  return invoke("delete_payee", { id });
+}
+
+export async function addPayeeAlias(payeeId: string, alias: string): Promise<Payee> {
+  return invoke("add_payee_alias", { payeeId, alias });
+}
+
+export async function removePayeeAlias(payeeId: string, alias: string): Promise<Payee> {
+  return invoke("remove_payee_alias", { payeeId, alias });
+}
+
+export async function mergePayees(sourceIds: string[], targetId: string): Promise<Payee> {
+  return invoke("merge_payees", { sourceIds, targetId });
+}
+
+export async function suggestPayees(prefix: string, limit?: number): Promise<PayeeSuggestion[]> {
+  return invoke("suggest_payees", { prefix, limit });
+}
+
+export async function getPayeeStats(payeeId: string): Promise<PayeeStats> {
+  return invoke("get_payee_stats", { payeeId });
+}
+
+// Note template commands
+export async function listNoteTemplates(): Promise<NoteTemplate[]> {
+  return invoke("list_note_templates");
+}
+
+export async function createNoteTemplate(data: { name: string; template: string }): Promise<NoteTemplate> {
+  return invoke("create_note_template", { data });
+}
+
+export async function updateNoteTemplate(
+  id: string,
+  data: { name?: string; template?: string }
+): Promise<NoteTemplate> {
+  return invoke("update_note_template", { id, data });
+}
+
+export async function deleteNoteTemplate(id: string): Promise<void> {
+  return invoke("delete_note_template", { id });
+}
+
+// Transaction template commands
+export interface TransactionTemplateInput {
+  name?: string;
+  accountId?: string;
+  payee?: string;
+  amount?: number;
+  categoryId?: string;
+  notes?: string;
+  splits?: Array<{ categoryId?: string; amount: number; memo?: string }>;
+}
+
+export async function listTransactionTemplates(): Promise<TransactionTemplate[]> {
+  return invoke("list_transaction_templates");
+}
+
+export async function createTransactionTemplate(
+  data: TransactionTemplateInput & { name: string }
+): Promise<TransactionTemplate> {
+  return invoke("create_transaction_template", { data });
+}
+
+export async function updateTransactionTemplate(
+  id: string,
+  data: TransactionTemplateInput
+): Promise<TransactionTemplate> {
+  return invoke("update_transaction_template", { id, data });
+}
+
+export async function deleteTransactionTemplate(id: string): Promise<void> {
+  return invoke("delete_transaction_template", { id });
+}
+
+export async function createFromTemplate(
+  templateId: string,
+  data: {
+    accountId?: string;
+    date?: string;
+    payee?: string;
+    amount?: number;
+    categoryId?: string;
+    notes?: string;
+  } = {}
+): Promise<Transaction> {
+  return invoke("create_from_template", { templateId, data });
+}
+
 // Category rule commands
 export async function listCategoryRules(): Promise<CategoryRule[]> {
   return invoke("list_category_rules");
@@ -131,6 +594,41 @@ export async function deleteCategoryRule(id: string): Promise<void> {
   return invoke("delete_category_rule", { id });
 }
 
+// Rules listed first get the highest priority
+export async function reorderCategoryRules(orderedIds: string[]): Promise<void> {
+  return invoke("reorder_category_rules", { orderedIds });
+}
+
+export async function exportCategoryRules(): Promise<string> {
+  return invoke("export_category_rules");
+}
+
+export async function importCategoryRules(contents: string): Promise<RuleImportResult> {
+  return invoke("import_category_rules", { contents });
+}
+
+export async function backtestCategoryRules(): Promise<RuleBacktest> {
+  return invoke("backtest_category_rules");
+}
+
+// Which existing transactions a rule would match, without saving or applying it
+export async function previewCategoryRule(
+  rule: Partial<CategoryRule>,
+  limit?: number
+): Promise<RulePreview> {
+  return invoke("preview_category_rule", { rule, limit });
+}
+
+// Payee rules proposed from how transactions are already categorized
+export async function suggestCategoryRules(): Promise<RuleSuggestion[]> {
+  return invoke("suggest_category_rules");
+}
+
+// A matching rule's category, or a guess learned from categorized history
+export async function suggestCategory(transactionId: string): Promise<CategorySuggestion> {
+  return invoke("suggest_category", { transactionId });
+}
+
 export async function applyCategoryRules(transactionIds?: string[]): Promise<number> {
   return invoke("apply_category_rules", { transactionIds });
 }
@@ -140,14 +638,7 @@ export async function listBudgets(): Promise<Budget[]> {
   return invoke("list_budgets");
 }
 
-export async function getBudgetSummary(month: string): Promise<
-  Array<{
-    budget: Budget;
-    category: Category;
-    spent: number;
-    remaining: number;
-  }>
-> {
+export async function getBudgetSummary(month: string): Promise<MonthBudgetSummary> {
   return invoke("get_budget_summary", { month });
 }
 
@@ -163,27 +654,263 @@ export async function deleteBudget(id: string): Promise<void> {
   return invoke("delete_budget", { id });
 }
 
+export async function setBudgetMonthAmount(
+  budgetId: string,
+  month: string,
+  amount: number | null
+): Promise<void> {
+  return invoke("set_budget_month_amount", { budgetId, month, amount });
+}
+
+export async function listBudgetMonthAmounts(budgetId?: string): Promise<BudgetMonthAmount[]> {
+  return invoke("list_budget_month_amounts", { budgetId });
+}
+
+export async function copyBudgets(
+  fromMonth: string,
+  toMonth: string,
+  source?: "amounts" | "actuals",
+  overwrite?: boolean
+): Promise<BudgetCopyResult> {
+  return invoke("copy_budgets", { fromMonth, toMonth, source, overwrite });
+}
+
+export async function generateBudgets(
+  method?: "average" | "split",
+  months?: number,
+  limit?: number
+): Promise<GeneratedBudgets> {
+  return invoke("generate_budgets", { method, months, limit });
+}
+
+export async function listBudgetAlerts(includeDismissed?: boolean): Promise<BudgetAlert[]> {
+  return invoke("list_budget_alerts", { includeDismissed });
+}
+
+export async function dismissBudgetAlerts(ids: string[]): Promise<void> {
+  return invoke("dismiss_budget_alerts", { ids });
+}
+
+export async function listBudgetGroups(): Promise<BudgetGroup[]> {
+  return invoke("list_budget_groups");
+}
+
+export async function createBudgetGroup(
+  data: Pick<BudgetGroup, "name" | "amount" | "categoryIds">
+): Promise<BudgetGroup> {
+  return invoke("create_budget_group", { data });
+}
+
+export async function updateBudgetGroup(
+  id: string,
+  data: Partial<Pick<BudgetGroup, "name" | "amount" | "categoryIds">>
+): Promise<BudgetGroup> {
+  return invoke("update_budget_group", { id, data });
+}
+
+export async function deleteBudgetGroup(id: string): Promise<void> {
+  return invoke("delete_budget_group", { id });
+}
+
+export async function getBudgetAllocationTree(): Promise<BudgetAllocation[]> {
+  return invoke("get_budget_allocation_tree");
+}
+
+export async function getCategoryRollingAverage(
+  categoryId: string,
+  windowMonths?: number[]
+): Promise<CategoryRollingAverage> {
+  return invoke("get_category_rolling_average", { categoryId, windowMonths });
+}
+
+export async function getCategoryHistory(categoryId: string, months?: number): Promise<CategoryHistory> {
+  return invoke("get_category_history", { categoryId, months });
+}
+
+export async function getBudgetPerformance(
+  categoryId: string,
+  months?: number
+): Promise<BudgetPerformance> {
+  return invoke("get_budget_performance", { categoryId, months });
+}
+
+// Month close commands
+export async function closeMonth(month: string): Promise<MonthClose> {
+  return invoke("close_month", { month });
+}
+
+export async function reopenMonth(month: string): Promise<void> {
+  return invoke("reopen_month", { month });
+}
+
+export async function recordAccountStatement(
+  accountId: string,
+  statementDate: string,
+  balance: number
+): Promise<AccountStatement> {
+  return invoke("record_account_statement", { accountId, statementDate, balance });
+}
+
+export async function listAccountStatements(accountId?: string): Promise<AccountStatement[]> {
+  return invoke("list_account_statements", { accountId });
+}
+
+export async function unlockAccountStatement(id: string): Promise<AccountStatement> {
+  return invoke("unlock_account_statement", { id });
+}
+
+export async function listMonthCloses(): Promise<MonthClose[]> {
+  return invoke("list_month_closes");
+}
+
+export async function listReviewQueue(): Promise<Transaction[]> {
+  return invoke("list_review_queue");
+}
+
 // Goal commands
 export async function listGoals(): Promise<Goal[]> {
   return invoke("list_goals");
 }
 
-export async function createGoal(data: Omit<Goal, "id" | "createdAt" | "updatedAt" | "isAchieved" | "achievedAt">): Promise<Goal> {
+// Debt accounts for payoff goals. Leaving out milestones, or passing null, uses the
+// default 25/50/75/100.
+type GoalInputExtras = { milestones?: number[] | null; debts?: GoalDebt[] };
+
+export async function createGoal(
+  data: Omit<
+    Goal,
+    | "id"
+    | "createdAt"
+    | "updatedAt"
+    | "isAchieved"
+    | "achievedAt"
+    | "isArchived"
+    | "archivedAt"
+    | "milestones"
+    | "priority"
+  > &
+    GoalInputExtras
+): Promise<Goal> {
   return invoke("create_goal", { data });
 }
 
-export async function updateGoal(id: string, data: Partial<Goal>): Promise<Goal> {
+export async function updateGoal(
+  id: string,
+  data: Omit<Partial<Goal>, "milestones"> & GoalInputExtras
+): Promise<Goal> {
   return invoke("update_goal", { id, data });
 }
 
+export async function archiveGoal(id: string): Promise<void> {
+  return invoke("archive_goal", { id });
+}
+
+export async function listArchivedGoals(): Promise<Goal[]> {
+  return invoke("list_archived_goals");
+}
+
+// Brings back an archived or deleted goal
+export async function restoreGoal(id: string): Promise<Goal> {
+  return invoke("restore_goal", { id });
+}
+
+// Higher priority first; goals left out keep their priority
+export async function reorderGoals(orderedIds: string[]): Promise<void> {
+  return invoke("reorder_goals", { orderedIds });
+}
+
 export async function deleteGoal(id: string): Promise<void> {
   return invoke("delete_goal", { id });
 }
 
+export async function listDeletedGoals(): Promise<Goal[]> {
+  return invoke("list_deleted_goals");
+}
+
+export async function restoreGoals(ids: string[]): Promise<void> {
+  return invoke("restore_goals", { ids });
+}
+
 export async function contributeToGoal(goalId: string, amount: number, transactionId?: string): Promise<void> {
   return invoke("contribute_to_goal", { goalId, amount, transactionId });
 }
 
+export async function listGoalContributions(goalId: string): Promise<GoalContribution[]> {
+  return invoke("list_goal_contributions", { goalId });
+}
+
+export async function deleteGoalContribution(id: string): Promise<void> {
+  return invoke("delete_goal_contribution", { id });
+}
+
+export async function listGoalContributionSchedules(): Promise<GoalContributionSchedule[]> {
+  return invoke("list_goal_contribution_schedules");
+}
+
+export async function setGoalContributionSchedule(
+  goalId: string,
+  data: Pick<GoalContributionSchedule, "amount" | "frequency" | "fromAccountId"> &
+    Partial<Pick<GoalContributionSchedule, "startDate" | "isActive">>
+): Promise<GoalContributionSchedule> {
+  return invoke("set_goal_contribution_schedule", { goalId, data });
+}
+
+export async function deleteGoalContributionSchedule(goalId: string): Promise<void> {
+  return invoke("delete_goal_contribution_schedule", { goalId });
+}
+
+export async function runGoalContributionSchedules(): Promise<number> {
+  return invoke("run_goal_contribution_schedules");
+}
+
+export async function getGoalProjection(
+  goalId: string,
+  monthlyContribution?: number
+): Promise<GoalProjection> {
+  return invoke("get_goal_projection", { goalId, monthlyContribution });
+}
+
+export async function getDebtPayoffPlan(
+  goalId: string,
+  strategy?: PayoffStrategy,
+  monthlyPayment?: number
+): Promise<DebtPayoffPlan> {
+  return invoke("get_debt_payoff_plan", { goalId, strategy, monthlyPayment });
+}
+
+export async function listGoalMilestones(goalId?: string): Promise<GoalMilestone[]> {
+  return invoke("list_goal_milestones", { goalId });
+}
+
+export async function getRoundUpSettings(): Promise<RoundUpSettings> {
+  return invoke("get_round_up_settings");
+}
+
+export async function setRoundUpSettings(
+  data: Pick<RoundUpSettings, "isEnabled" | "goalId" | "accountIds" | "frequency">
+): Promise<RoundUpSettings> {
+  return invoke("set_round_up_settings", { data });
+}
+
+// Contributes set-aside round-ups if due; returns the amount contributed
+export async function runRoundUps(): Promise<number> {
+  return invoke("run_round_ups");
+}
+
+export async function previewSurplusAllocation(
+  amount: number,
+  strategy?: AllocationStrategy
+): Promise<SurplusPlan> {
+  return invoke("preview_surplus_allocation", { amount, strategy });
+}
+
+export async function allocateSurplus(
+  amount: number,
+  strategy?: AllocationStrategy
+): Promise<SurplusPlan> {
+  return invoke("allocate_surplus", { amount, strategy });
+}
+
 // Recurring transaction commands
 export async function listRecurringTransactions(): Promise<RecurringTransaction[]> {
   return invoke("list_recurring_transactions");
@@ -210,6 +937,140 @@ export async function deleteRecurringTransaction(id: string): Promise<void> {
   return invoke("delete_recurring_transaction", { id });
 }
 
+export async function getBillsCalendar(month: string): Promise<BillsCalendar> {
+  return invoke("get_bills_calendar", { month });
+}
+
+// Insurance policy commands
+export async function listPolicies(): Promise<InsurancePolicy[]> {
+  return invoke("list_policies");
+}
+
+export async function createPolicy(
+  data: Partial<Omit<InsurancePolicy, "id" | "createdAt" | "updatedAt">> & {
+    name: string;
+    accountId?: string;
+    categoryId?: string;
+  }
+): Promise<InsurancePolicy> {
+  return invoke("create_policy", { data });
+}
+
+export async function updatePolicy(id: string, data: Partial<InsurancePolicy>): Promise<InsurancePolicy> {
+  return invoke("update_policy", { id, data });
+}
+
+export async function deletePolicy(id: string): Promise<void> {
+  return invoke("delete_policy", { id });
+}
+
+export async function getUpcomingRenewals(days?: number): Promise<PolicyRenewal[]> {
+  return invoke("get_upcoming_renewals", { days: days ?? null });
+}
+
+// Gift card commands
+export async function listGiftCards(includeInactive = false): Promise<GiftCard[]> {
+  return invoke("list_gift_cards", { includeInactive });
+}
+
+export async function createGiftCard(data: {
+  name: string;
+  merchant?: string;
+  balance: number;
+  expiryDate?: string;
+  notes?: string;
+}): Promise<GiftCard> {
+  return invoke("create_gift_card", { data });
+}
+
+export async function updateGiftCard(id: string, data: Partial<GiftCard>): Promise<GiftCard> {
+  return invoke("update_gift_card", { id, data });
+}
+
+export async function deleteGiftCard(id: string): Promise<void> {
+  return invoke("delete_gift_card", { id });
+}
+
+export async function markPaidWithGiftCard(transactionId: string, giftCardId?: string): Promise<GiftCardUsage> {
+  return invoke("mark_paid_with_gift_card", { transactionId, giftCardId: giftCardId ?? null });
+}
+
+export async function unmarkPaidWithGiftCard(transactionId: string): Promise<void> {
+  return invoke("unmark_paid_with_gift_card", { transactionId });
+}
+
+export async function getGiftCardUsages(giftCardId: string): Promise<GiftCardUsage[]> {
+  return invoke("get_gift_card_usages", { giftCardId });
+}
+
+// Planned purchase commands
+export async function listPlannedPurchases(includePurchased = false): Promise<PlannedPurchase[]> {
+  return invoke("list_planned_purchases", { includePurchased });
+}
+
+export async function createPlannedPurchase(data: {
+  name: string;
+  targetAmount: number;
+  desiredDate: string;
+  categoryId?: string;
+  notes?: string;
+}): Promise<PlannedPurchase> {
+  return invoke("create_planned_purchase", { data });
+}
+
+export async function updatePlannedPurchase(
+  id: string,
+  data: Partial<PlannedPurchase>
+): Promise<PlannedPurchase> {
+  return invoke("update_planned_purchase", { id, data });
+}
+
+export async function deletePlannedPurchase(id: string): Promise<void> {
+  return invoke("delete_planned_purchase", { id });
+}
+
+export async function recommendPurchaseMonth(id: string): Promise<PurchaseRecommendation> {
+  return invoke("recommend_purchase_month", { id });
+}
+
+// Forecast commands
+export async function getCashFlowForecast(months?: number): Promise<CashFlowForecast> {
+  return invoke("get_cash_flow_forecast", { months: months ?? null });
+}
+
+export async function getSafeToSpend(): Promise<SafeToSpend> {
+  return invoke("get_safe_to_spend");
+}
+
+// Reward commands
+export async function listRewardRules(accountId?: string): Promise<RewardRule[]> {
+  return invoke("list_reward_rules", { accountId: accountId ?? null });
+}
+
+export async function createRewardRule(data: {
+  accountId: string;
+  categoryId?: string | null;
+  rate: number;
+}): Promise<RewardRule> {
+  return invoke("create_reward_rule", { data });
+}
+
+export async function updateRewardRule(id: string, data: { rate?: number }): Promise<RewardRule> {
+  return invoke("update_reward_rule", { id, data });
+}
+
+export async function deleteRewardRule(id: string): Promise<void> {
+  return invoke("delete_reward_rule", { id });
+}
+
+export async function getRewardsSummary(
+  startDate: string,
+  endDate: string,
+  accountId?: string
+): Promise<RewardsSummary[]> {
+  return invoke("get_rewards_summary", { startDate, endDate, accountId: accountId ?? null });
+}
+
 // Investment commands
 export async function listHoldings(accountId?: string): Promise<Holding[]> {
   return invoke("list_holdings", { accountId });
@@ -219,21 +1080,139 @@ export async function updateSecurityPrice(symbol: string, price: number): Promis
   return invoke("update_security_price", { symbol, price });
 }
 
+export async function createHolding(data: {
+  accountId: string;
+  securityId: string;
+  quantity: number;
+  costBasis?: number | null;
+  acquisitionDate?: string | null;
+}): Promise<Holding> {
+  return invoke("create_holding", { data });
+}
+
+export async function updateHolding(
+  id: string,
+  data: { quantity?: number; costBasis?: number | null; acquisitionDate?: string | null }
+): Promise<Holding> {
+  return invoke("update_holding", { id, data });
+}
+
+export async function deleteHolding(id: string): Promise<void> {
+  return invoke("delete_holding", { id });
+}
+
+export async function listSecurities(): Promise<Security[]> {
+  return invoke("list_securities");
+}
+
+export async function createSecurity(
+  data: Partial<Pick<Security, "symbol" | "name" | "securityType" | "currentPrice">>
+): Promise<Security> {
+  return invoke("create_security", { data });
+}
+
+export async function updateSecurity(
+  id: string,
+  data: Partial<Pick<Security, "symbol" | "name" | "securityType" | "currentPrice">>
+): Promise<Security> {
+  return invoke("update_security", { id, data });
+}
+
+export async function deleteSecurity(id: string): Promise<void> {
+  return invoke("delete_security", { id });
+}
+
+export async function getInvestmentSummary(): Promise<InvestmentSummary> {
+  return invoke("get_investment_summary");
+}
+
+export async function listInvestmentTransactions(
+  accountId?: string,
+  securityId?: string
+): Promise<InvestmentTransaction[]> {
+  return invoke("list_investment_transactions", {
+    accountId: accountId ?? null,
+    securityId: securityId ?? null,
+  });
+}
+
+export async function createInvestmentTransaction(data: {
+  accountId: string;
+  transactionType: InvestmentTransactionType;
+  date: string;
+  securityId?: string | null;
+  quantity?: number | null;
+  pricePerUnit?: number | null;
+  totalAmount?: number | null;
+  fees?: number;
+  notes?: string | null;
+}): Promise<InvestmentTransaction> {
+  return invoke("create_investment_transaction", { data });
+}
+
+export async function deleteInvestmentTransaction(id: string): Promise<void> {
+  return invoke("delete_investment_transaction", { id });
+}
+
 // Report commands
-export async function getSpendingByCategory(startDate: string, endDate: string): Promise<SpendingByCategory[]> {
-  return invoke("get_spending_by_category", { startDate, endDate });
+export async function getSpendingByCategory(
+  startDate: string,
+  endDate: string,
+  rollup = false
+): Promise<SpendingByCategory[]> {
+  return invoke("get_spending_by_category", { startDate, endDate, rollup });
+}
+
+export async function getSpendingByAccountMatrix(
+  startDate: string,
+  endDate: string,
+  groupBy?: "account" | "accountType",
+  rollup = false
+): Promise<SpendingMatrix> {
+  return invoke("get_spending_by_account_matrix", { startDate, endDate, groupBy, rollup });
+}
+
+export async function getPersonalInflation(periodA: string, periodB: string): Promise<PersonalInflation> {
+  return invoke("get_personal_inflation", { periodA, periodB });
 }
 
 export async function getCashFlow(startDate: string, endDate: string, groupBy: "day" | "week" | "month"): Promise<CashFlowData[]> {
   return invoke("get_cash_flow", { startDate, endDate, groupBy });
 }
 
-export async function getNetWorthHistory(startDate: string, endDate: string): Promise<NetWorthSnapshot[]> {
-  return invoke("get_net_worth_history", { startDate, endDate });
+export async function listNetWorthSnapshots(
+  startDate?: string,
+  endDate?: string
+): Promise<NetWorthSnapshot[]> {
+  return invoke("list_net_worth_snapshots", {
+    startDate: startDate ?? null,
+    endDate: endDate ?? null,
+  });
+}
+
+export async function createNetWorthSnapshot(date?: string): Promise<NetWorthSnapshot> {
+  return invoke("create_net_worth_snapshot", { date: date ?? null });
+}
+
+export async function getRateComparison(): Promise<RateComparisonReport> {
+  return invoke("get_rate_comparison");
+}
+
+export async function getDashboardSummary(): Promise<DashboardSummary> {
+  return invoke("get_dashboard_summary");
+}
+
+// Credit score commands
+export async function recordCreditScore(date: string, bureau: string, score: number): Promise<CreditScore> {
+  return invoke("record_credit_score", { date, bureau, score });
+}
+
+export async function getCreditScoreHistory(bureau?: string): Promise<CreditScore[]> {
+  return invoke("get_credit_score_history", { bureau: bureau ?? null });
 }
 
-export async function takeNetWorthSnapshot(): Promise<NetWorthSnapshot> {
-  return invoke("take_net_worth_snapshot");
+export async function deleteCreditScore(id: string): Promise<void> {
+  return invoke("delete_credit_score", { id });
 }
 
 // Import commands
@@ -251,6 +1230,8 @@ export interface ColumnMapping {
   payeeColumn?: number;
   memoColumn?: number;
   categoryColumn?: number;
+  originalAmountColumn?: number;
+  originalCurrencyColumn?: number;
   dateFormat: string;
   invertAmounts: boolean;
 }
@@ -261,6 +1242,8 @@ export interface ParsedTransaction {
   payee?: string;
   memo?: string;
   categoryHint?: string;
+  originalAmount?: number | null;
+  originalCurrency?: string | null;
   rawData: Record<string, string>;
 }
 
@@ -269,6 +1252,7 @@ export interface ImportResult {
   skipped: number;
   categorized: number;
   batchId: string;
+  errors: string[];
 }
 
 export async function previewCsvFile(filePath: string): Promise<CsvPreview> {
@@ -290,9 +1274,44 @@ export async function importTransactions(
     payee?: string;
     memo?: string;
     categoryId?: string;
-  }>
+    originalAmount?: number | null;
+    originalCurrency?: string | null;
+  }>,
+  // Statement file the transactions came from, fingerprinted so a re-import is caught
+  filePath?: string
+): Promise<ImportResult> {
+  return invoke("import_transactions", { accountId, transactions, filePath });
+}
+
+export interface PreviousImport {
+  batchId: string;
+  accountId: string;
+  accountName: string | null;
+  fileName: string | null;
+  importedAt: string;
+  transactionsImported: number;
+}
+
+export async function findPreviousImport(filePath: string): Promise<PreviousImport | null> {
+  return invoke("find_previous_import", { filePath });
+}
+
+export interface ImportProgress {
+  batchId: string;
+  rowsRead: number;
+  imported: number;
+  skipped: number;
+  done: boolean;
+}
+
+// Imports straight from the file in chunks, emitting IMPORT_PROGRESS as it goes;
+// for exports too large to preview and parse in one go
+export async function importCsvFileStreaming(
+  accountId: string,
+  filePath: string,
+  mapping: ColumnMapping
 ): Promise<ImportResult> {
-  return invoke("import_transactions", { accountId, transactions });
+  return invoke("import_csv_file_streaming", { accountId, filePath, mapping });
 }
 
 // Bank of America text file parser
@@ -355,9 +1374,109 @@ export async function parsePdfFile(filePath: string): Promise<Array<{
   return invoke("parse_pdf_file", { filePath });
 }
 
+// Statement importers: single-account statement formats registered on the backend
+export interface ImporterInfo {
+  id: string;
+  name: string;
+  extensions: string[];
+}
+
+export interface StatementRow {
+  date: string;
+  amount: number;
+  payee: string;
+  memo: string;
+  pdfCategory?: string;
+}
+
+export interface StatementPreview {
+  importerId: string;
+  transactions: StatementRow[];
+  totalRows: number;
+}
+
+export async function listStatementImporters(): Promise<ImporterInfo[]> {
+  return invoke("list_statement_importers");
+}
+
+export async function detectStatementImporter(filePath: string): Promise<ImporterInfo | null> {
+  return invoke("detect_statement_importer", { filePath });
+}
+
+// Without an importer id the best match for the file is used
+export async function previewStatementFile(filePath: string, importerId?: string): Promise<StatementPreview> {
+  return invoke("preview_statement_file", { filePath, importerId });
+}
+
+export async function parseStatementFile(filePath: string, importerId?: string): Promise<StatementRow[]> {
+  return invoke("parse_statement_file", { filePath, importerId });
+}
+
+// Empower (Personal Capital) export importer
+export interface EmpowerTransaction {
+  date: string;
+  account: string;
+  description: string;
+  category: string | null;
+  tags: string | null;
+  amount: number;
+}
+
+export interface EmpowerHolding {
+  account: string;
+  symbol: string;
+  name: string | null;
+  quantity: number;
+  price: number | null;
+  value: number;
+  costBasis: number | null;
+}
+
+export interface EmpowerPreview {
+  kind: "transactions" | "holdings";
+  accounts: string[];
+  transactionCount: number;
+  holdingCount: number;
+  transactions: EmpowerTransaction[];
+  holdings: EmpowerHolding[];
+}
+
+export interface EmpowerImportResult {
+  accountsCreated: number;
+  transactionsImported: number;
+  transactionsSkipped: number;
+  categorized: number;
+  holdingsImported: number;
+}
+
+export async function previewEmpowerFile(filePath: string): Promise<EmpowerPreview> {
+  return invoke("preview_empower_file", { filePath });
+}
+
+export async function importEmpowerFiles(filePaths: string[]): Promise<EmpowerImportResult> {
+  return invoke("import_empower_files", { filePaths });
+}
+
 // Export commands
-export async function exportToCsv(filters: Partial<TransactionFilters>): Promise<string> {
-  return invoke("export_to_csv", { filters });
+export async function exportFilteredTransactions(
+  filters: Partial<TransactionFilters>,
+  format: "csv" | "json",
+  path: string,
+  columns?: string[]
+): Promise<number> {
+  return invoke("export_filtered_transactions", { filters, format, path, columns });
+}
+
+export async function exportTaxPackage(year: number, path: string): Promise<TaxPackage> {
+  return invoke("export_tax_package", { year, path });
+}
+
+export async function exportQif(accountId?: string, startDate?: string, endDate?: string): Promise<string> {
+  return invoke("export_qif", { accountId, startDate, endDate });
+}
+
+export async function exportAccountOfx(accountId: string, startDate?: string, endDate?: string): Promise<string> {
+  return invoke("export_account_ofx", { accountId, startDate, endDate });
 }
 
 export async function exportToJson(): Promise<string> {
@@ -389,3 +1508,12 @@ export async function setDatabasePath(path: string | null): Promise<string> {
 export async function deleteDatabase(): Promise<void> {
   return invoke("delete_database");
 }
+
+// Usage stats commands
+export async function getLocalUsageStats(): Promise<LocalUsageStats> {
+  return invoke("get_local_usage_stats");
+}
+
+export async function resetLocalUsageStats(): Promise<void> {
+  return invoke("reset_local_usage_stats");
+}
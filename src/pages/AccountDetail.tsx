@@ -9,6 +9,8 @@ import {
   ArrowUpRight,
   ArrowDownRight,
   RefreshCw,
+  Lock,
+  Unlock,
 } from "lucide-react";
 import {
   AreaChart,
@@ -22,6 +24,8 @@ import {
 import { Card, CardContent, CardHeader, CardTitle } from "@/components/ui/card";
 import { Button } from "@/components/ui/button";
 import { Badge } from "@/components/ui/badge";
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
 import {
   Table,
   TableBody,
@@ -35,10 +39,10 @@ import { PageContainer } from "@/components/layout/PageContainer";
 import { useAccountStore } from "@/stores/useAccountStore";
 import { useCategoryStore } from "@/stores/useCategoryStore";
 import * as api from "@/lib/tauri";
-import { formatMoney, formatDate } from "@/lib/formatters";
+import { formatMoney, formatDate, parseMoney } from "@/lib/formatters";
 import { ACCOUNT_TYPES } from "@/lib/constants";
 import { cn } from "@/lib/utils";
-import type { Transaction } from "@/types";
+import type { AccountStatement, Transaction } from "@/types";
 
 export function AccountDetail() {
   const { id } = useParams<{ id: string }>();
@@ -47,6 +51,9 @@ export function AccountDetail() {
   const { categories, fetchCategories } = useCategoryStore();
   const [transactions, setTransactions] = useState<Transaction[]>([]);
   const [loading, setLoading] = useState(true);
+  const [statements, setStatements] = useState<AccountStatement[]>([]);
+  const [statementForm, setStatementForm] = useState({ statementDate: "", balance: "" });
+  const [statementError, setStatementError] = useState<string | null>(null);
 
   const account = accounts.find((a) => a.id === id);
 
@@ -58,9 +65,47 @@ export function AccountDetail() {
   useEffect(() => {
     if (id) {
       loadTransactions();
+      loadStatements();
     }
   }, [id]);
 
+  const loadStatements = async () => {
+    if (!id) return;
+    try {
+      setStatements(await api.listAccountStatements(id));
+    } catch (err) {
+      console.error("Failed to load statements:", err);
+    }
+  };
+
+  const handleRecordStatement = async (e: React.FormEvent) => {
+    e.preventDefault();
+    if (!id) return;
+    setStatementError(null);
+    try {
+      await api.recordAccountStatement(
+        id,
+        statementForm.statementDate,
+        parseMoney(statementForm.balance)
+      );
+      setStatementForm({ statementDate: "", balance: "" });
+      await loadStatements();
+    } catch (err) {
+      setStatementError(String(err));
+    }
+  };
+
+  const handleUnlockStatement = async (statementId: string) => {
+    if (
+      confirm(
+        "Unlock this statement? Transactions on or before its date can be edited and deleted again."
+      )
+    ) {
+      await api.unlockAccountStatement(statementId);
+      await loadStatements();
+    }
+  };
+
   const loadTransactions = async () => {
     if (!id) return;
     setLoading(true);
@@ -309,6 +354,100 @@ export function AccountDetail() {
           </Card>
         )}
 
+        {/* Closing Statements */}
+        <Card className="mb-6">
+          <CardHeader>
+            <CardTitle className="flex items-center gap-2">
+              <Lock className="h-5 w-5" />
+              Closing Statements
+            </CardTitle>
+          </CardHeader>
+          <CardContent className="space-y-4">
+            <form onSubmit={handleRecordStatement} className="flex items-end gap-4">
+              <div className="space-y-2">
+                <Label htmlFor="statementDate">Statement Date</Label>
+                <Input
+                  id="statementDate"
+                  type="date"
+                  value={statementForm.statementDate}
+                  onChange={(e) =>
+                    setStatementForm({ ...statementForm, statementDate: e.target.value })
+                  }
+                  required
+                />
+              </div>
+              <div className="space-y-2">
+                <Label htmlFor="statementBalance">Closing Balance</Label>
+                <Input
+                  id="statementBalance"
+                  type="number"
+                  step="0.01"
+                  value={statementForm.balance}
+                  onChange={(e) =>
+                    setStatementForm({ ...statementForm, balance: e.target.value })
+                  }
+                  placeholder="0.00"
+                  required
+                />
+              </div>
+              <Button type="submit">Record &amp; Lock</Button>
+            </form>
+            {statementError && (
+              <p className="text-sm text-destructive">{statementError}</p>
+            )}
+            {statements.length > 0 && (
+              <Table>
+                <TableHeader>
+                  <TableRow>
+                    <TableHead>Statement Date</TableHead>
+                    <TableHead className="text-right">Statement Balance</TableHead>
+                    <TableHead className="text-right">Ledger Balance</TableHead>
+                    <TableHead>Status</TableHead>
+                    <TableHead />
+                  </TableRow>
+                </TableHeader>
+                <TableBody>
+                  {statements.map((statement) => (
+                    <TableRow key={statement.id}>
+                      <TableCell className="font-medium">
+                        {formatDate(statement.statementDate)}
+                      </TableCell>
+                      <TableCell className="text-right">
+                        {formatMoney(statement.balance)}
+                      </TableCell>
+                      <TableCell
+                        className={cn(
+                          "text-right",
+                          statement.ledgerBalance !== statement.balance && "text-red-600"
+                        )}
+                      >
+                        {formatMoney(statement.ledgerBalance)}
+                      </TableCell>
+                      <TableCell>
+                        <Badge variant={statement.isLocked ? "secondary" : "outline"}>
+                          {statement.isLocked ? "Locked" : "Unlocked"}
+                        </Badge>
+                      </TableCell>
+                      <TableCell className="text-right">
+                        {statement.isLocked && (
+                          <Button
+                            variant="ghost"
+                            size="sm"
+                            onClick={() => handleUnlockStatement(statement.id)}
+                          >
+                            <Unlock className="h-4 w-4 mr-2" />
+                            Unlock
+                          </Button>
+                        )}
+                      </TableCell>
+                    </TableRow>
+                  ))}
+                </TableBody>
+              </Table>
+            )}
+          </CardContent>
+        </Card>
+
         {/* Transactions Table */}
         <Card>
           <CardHeader>
@@ -14,6 +14,13 @@ import {
   Trash2,
   EyeOff,
   ExternalLink,
+  Target,
+  Building2,
+  Layers,
+  Archive,
+  ArchiveRestore,
+  Merge,
+  Home,
 } from "lucide-react";
 import { Card, CardContent, CardHeader, CardTitle } from "@/components/ui/card";
 import { Button } from "@/components/ui/button";
@@ -34,6 +41,7 @@ import {
 } from "@/components/ui/dropdown-menu";
 import { Input } from "@/components/ui/input";
 import { Label } from "@/components/ui/label";
+import { Switch } from "@/components/ui/switch";
 import {
   Select,
   SelectContent,
@@ -44,10 +52,24 @@ import {
 import { Header } from "@/components/layout/Header";
 import { PageContainer } from "@/components/layout/PageContainer";
 import { useAccountStore } from "@/stores/useAccountStore";
+import * as api from "@/lib/tauri";
 import { formatMoney, parseMoney } from "@/lib/formatters";
-import { ACCOUNT_TYPES, type AccountType } from "@/lib/constants";
+import {
+  ACCOUNT_TYPES,
+  ASSET_ACCOUNT_TYPES,
+  INTEREST_COMPOUNDING,
+  LIABILITY_ACCOUNT_TYPES,
+  type AccountType,
+  type InterestCompounding,
+} from "@/lib/constants";
 import { cn } from "@/lib/utils";
-import type { Account } from "@/types";
+import type {
+  Account,
+  AccountDeleteResult,
+  AccountDeleteTransactions,
+  AccountGroup,
+  Institution,
+} from "@/types";
 
 const iconMap = {
   Wallet,
@@ -57,25 +79,140 @@ const iconMap = {
   Landmark,
   Banknote,
   CircleDollarSign,
+  Home,
 };
 
 export function Accounts() {
   const navigate = useNavigate();
-  const { accounts, fetchAccounts, createAccount, updateAccount, deleteAccount } =
-    useAccountStore();
+  const {
+    accounts,
+    fetchAccounts,
+    createAccount,
+    updateAccount,
+    adjustAccountBalance,
+    deleteAccount,
+    getOpenAccounts,
+  } = useAccountStore();
   const [isDialogOpen, setIsDialogOpen] = useState(false);
   const [editingAccount, setEditingAccount] = useState<Account | null>(null);
   const [formData, setFormData] = useState({
     name: "",
     accountType: "checking" as AccountType,
     currentBalance: "",
+    openingDate: "",
+    interestRate: "",
+    interestCompounding: "monthly" as InterestCompounding,
+    institutionId: "none",
+    accountGroupId: "none",
+    creditLimit: "",
+    statementClosingDay: "",
+    paymentDueDay: "",
+    minimumPayment: "",
+    accrueInterest: false,
+    currency: "",
     notes: "",
   });
+  const [accountGroups, setAccountGroups] = useState<AccountGroup[]>([]);
+  const [isGroupsOpen, setIsGroupsOpen] = useState(false);
+  const [newGroupName, setNewGroupName] = useState("");
+  const [institutions, setInstitutions] = useState<Institution[]>([]);
+  const [isInstitutionsOpen, setIsInstitutionsOpen] = useState(false);
+  const [institutionForm, setInstitutionForm] = useState({
+    name: "",
+    website: "",
+    primaryColor: "#3b82f6",
+  });
+
+  // Closing an account: its balance must be zero or be transferred out as part of closing
+  const [closingAccount, setClosingAccount] = useState<Account | null>(null);
+  const [closeForm, setCloseForm] = useState({
+    date: new Date().toISOString().slice(0, 10),
+    transferAccountId: "none",
+    notes: "",
+  });
+  const [closeError, setCloseError] = useState<string | null>(null);
+
+  // Deleting an account, with what happens to its transactions previewed first
+  const [deletingAccount, setDeletingAccount] = useState<Account | null>(null);
+  const [deleteForm, setDeleteForm] = useState({
+    transactions: "keep" as AccountDeleteTransactions,
+    reassignToAccountId: "none",
+  });
+  const [deletePreview, setDeletePreview] = useState<AccountDeleteResult | null>(null);
+  const [deleteError, setDeleteError] = useState<string | null>(null);
+
+  // Merging a duplicate account into the one it duplicates
+  const [mergingAccount, setMergingAccount] = useState<Account | null>(null);
+  const [mergeTargetId, setMergeTargetId] = useState("none");
+  const [mergeError, setMergeError] = useState<string | null>(null);
 
   useEffect(() => {
     fetchAccounts();
+    loadInstitutions();
+    loadAccountGroups();
   }, [fetchAccounts]);
 
+  const loadAccountGroups = async () => {
+    try {
+      setAccountGroups(await api.listAccountGroups());
+    } catch (err) {
+      console.error("Failed to load account groups:", err);
+    }
+  };
+
+  const handleAddAccountGroup = async (e: React.FormEvent) => {
+    e.preventDefault();
+    await api.createAccountGroup({ name: newGroupName });
+    setNewGroupName("");
+    await loadAccountGroups();
+  };
+
+  const handleRenameAccountGroup = async (id: string, name: string) => {
+    await api.updateAccountGroup(id, { name });
+    await loadAccountGroups();
+  };
+
+  const handleDeleteAccountGroup = async (id: string) => {
+    if (confirm("Delete this group? Its accounts are kept and become ungrouped.")) {
+      await api.deleteAccountGroup(id);
+      await loadAccountGroups();
+      await fetchAccounts();
+    }
+  };
+
+  const loadInstitutions = async () => {
+    try {
+      setInstitutions(await api.listInstitutions());
+    } catch (err) {
+      console.error("Failed to load institutions:", err);
+    }
+  };
+
+  const handleAddInstitution = async (e: React.FormEvent) => {
+    e.preventDefault();
+    await api.createInstitution({
+      name: institutionForm.name,
+      website: institutionForm.website || null,
+      primaryColor: institutionForm.primaryColor,
+    });
+    setInstitutionForm({ name: "", website: "", primaryColor: "#3b82f6" });
+    await loadInstitutions();
+  };
+
+  const handleUpdateInstitution = async (id: string, data: Partial<Institution>) => {
+    await api.updateInstitution(id, data);
+    await loadInstitutions();
+    await fetchAccounts();
+  };
+
+  const handleDeleteInstitution = async (id: string) => {
+    if (confirm("Delete this institution? Its accounts are kept.")) {
+      await api.deleteInstitution(id);
+      await loadInstitutions();
+      await fetchAccounts();
+    }
+  };
+
   const handleOpenDialog = (account?: Account) => {
     if (account) {
       setEditingAccount(account);
@@ -83,6 +220,19 @@ export function Accounts() {
         name: account.name,
         accountType: account.accountType,
         currentBalance: (account.currentBalance / 100).toFixed(2),
+        openingDate: account.openingDate ?? "",
+        interestRate: account.interestRate?.toString() ?? "",
+        interestCompounding: account.interestCompounding,
+        institutionId: account.institutionId ?? "none",
+        accountGroupId: account.accountGroupId ?? "none",
+        creditLimit:
+          account.creditLimit != null ? (account.creditLimit / 100).toFixed(2) : "",
+        statementClosingDay: account.statementClosingDay?.toString() ?? "",
+        paymentDueDay: account.paymentDueDay?.toString() ?? "",
+        minimumPayment:
+          account.minimumPayment != null ? (account.minimumPayment / 100).toFixed(2) : "",
+        accrueInterest: account.accrueInterest,
+        currency: account.currency,
         notes: account.notes || "",
       });
     } else {
@@ -91,6 +241,17 @@ export function Accounts() {
         name: "",
         accountType: "checking",
         currentBalance: "",
+        openingDate: "",
+        interestRate: "",
+        interestCompounding: "monthly",
+        institutionId: "none",
+        accountGroupId: "none",
+        creditLimit: "",
+        statementClosingDay: "",
+        paymentDueDay: "",
+        minimumPayment: "",
+        accrueInterest: false,
+        currency: "",
         notes: "",
       });
     }
@@ -99,26 +260,46 @@ export function Accounts() {
 
   const handleSubmit = async (e: React.FormEvent) => {
     e.preventDefault();
+    const isCreditCard = formData.accountType === "credit_card";
     const data = {
       name: formData.name,
       accountType: formData.accountType,
       currentBalance: parseMoney(formData.currentBalance),
+      // Left blank, a new account opens today
+      openingDate: formData.openingDate || undefined,
       notes: formData.notes || null,
-      institutionId: null,
+      institutionId: formData.institutionId === "none" ? null : formData.institutionId,
+      accountGroupId: formData.accountGroupId === "none" ? null : formData.accountGroupId,
       accountNumberMasked: null,
-      currency: "USD",
+      // Left blank, new accounts are held in the base currency
+      currency: formData.currency.trim() ? formData.currency.trim().toUpperCase() : undefined,
       availableBalance: null,
-      creditLimit: null,
-      interestRate: null,
-      isActive: true,
+      creditLimit: isCreditCard && formData.creditLimit ? parseMoney(formData.creditLimit) : null,
+      statementClosingDay:
+        isCreditCard && formData.statementClosingDay ? parseInt(formData.statementClosingDay) : null,
+      paymentDueDay:
+        isCreditCard && formData.paymentDueDay ? parseInt(formData.paymentDueDay) : null,
+      minimumPayment:
+        isCreditCard && formData.minimumPayment ? parseMoney(formData.minimumPayment) : null,
+      interestRate: formData.interestRate ? parseFloat(formData.interestRate) : null,
+      interestCompounding: formData.interestCompounding,
+      accrueInterest:
+        ["savings", "loan"].includes(formData.accountType) && formData.accrueInterest,
+      isActive: editingAccount?.isActive ?? true,
       isHidden: false,
+      excludeFromBudget: editingAccount?.excludeFromBudget ?? false,
       displayOrder: accounts.length,
       ofxAccountId: null,
       lastSyncAt: null,
     };
 
     if (editingAccount) {
-      await updateAccount(editingAccount.id, data);
+      // A changed balance is recorded as an adjustment transaction, not overwritten
+      const { currentBalance, ...rest } = data;
+      await updateAccount(editingAccount.id, rest);
+      if (currentBalance !== editingAccount.currentBalance) {
+        await adjustAccountBalance(editingAccount.id, currentBalance);
+      }
     } else {
       await createAccount(data);
     }
@@ -126,19 +307,96 @@ export function Accounts() {
   };
 
   const handleDelete = async (id: string) => {
-    if (confirm("Are you sure you want to delete this account? This action cannot be undone.")) {
-      await deleteAccount(id);
+    const account = accounts.find((a) => a.id === id);
+    if (!account) return;
+    setDeletingAccount(account);
+    setDeleteForm({ transactions: "keep", reassignToAccountId: "none" });
+    setDeleteError(null);
+    try {
+      setDeletePreview(await api.deleteAccount(id, { dryRun: true }));
+    } catch (err) {
+      setDeleteError(String(err));
+    }
+  };
+
+  const handleConfirmDelete = async (e: React.FormEvent) => {
+    e.preventDefault();
+    if (!deletingAccount) return;
+    setDeleteError(null);
+    try {
+      await deleteAccount(
+        deletingAccount.id,
+        deleteForm.transactions,
+        deleteForm.reassignToAccountId === "none" ? undefined : deleteForm.reassignToAccountId
+      );
+      setDeletingAccount(null);
+    } catch (err) {
+      setDeleteError(String(err));
+    }
+  };
+
+  const handleOpenCloseDialog = (account: Account) => {
+    setClosingAccount(account);
+    setCloseForm({
+      date: new Date().toISOString().slice(0, 10),
+      transferAccountId: "none",
+      notes: "",
+    });
+    setCloseError(null);
+  };
+
+  const handleCloseAccount = async (e: React.FormEvent) => {
+    e.preventDefault();
+    if (!closingAccount) return;
+    setCloseError(null);
+    try {
+      await api.closeAccount(closingAccount.id, {
+        date: closeForm.date,
+        transferAccountId:
+          closeForm.transferAccountId === "none" ? undefined : closeForm.transferAccountId,
+        notes: closeForm.notes || undefined,
+      });
+      setClosingAccount(null);
+      await fetchAccounts();
+    } catch (err) {
+      setCloseError(String(err));
     }
   };
 
+  const handleReopenAccount = async (id: string) => {
+    try {
+      await api.reopenAccount(id);
+      await fetchAccounts();
+    } catch (err) {
+      console.error("Failed to reopen account:", err);
+    }
+  };
+
+  const handleOpenMergeDialog = (account: Account) => {
+    setMergingAccount(account);
+    setMergeTargetId("none");
+    setMergeError(null);
+  };
+
+  const handleMergeAccounts = async (e: React.FormEvent) => {
+    e.preventDefault();
+    if (!mergingAccount || mergeTargetId === "none") return;
+    setMergeError(null);
+    try {
+      await api.mergeAccounts(mergingAccount.id, mergeTargetId);
+      setMergingAccount(null);
+      await fetchAccounts();
+    } catch (err) {
+      setMergeError(String(err));
+    }
+  };
+
+  const openAccounts = accounts.filter((a) => !a.closedOn);
   const groupedAccounts = {
-    assets: accounts.filter((a) =>
-      ["checking", "savings", "investment", "cash"].includes(a.accountType)
-    ),
-    liabilities: accounts.filter((a) =>
-      ["credit_card", "loan"].includes(a.accountType)
-    ),
-    other: accounts.filter((a) => a.accountType === "other"),
+    assets: openAccounts.filter((a) => ASSET_ACCOUNT_TYPES.includes(a.accountType)),
+    liabilities: openAccounts.filter((a) => LIABILITY_ACCOUNT_TYPES.includes(a.accountType)),
+    other: openAccounts.filter((a) => a.accountType === "other"),
+    closed: accounts.filter((a) => a.closedOn),
   };
 
   return (
@@ -146,10 +404,20 @@ export function Accounts() {
       <Header
         title="Accounts"
         actions={
-          <Button onClick={() => handleOpenDialog()}>
-            <Plus className="h-4 w-4 mr-2" />
-            Add Account
-          </Button>
+          <div className="flex gap-2">
+            <Button variant="outline" onClick={() => setIsGroupsOpen(true)}>
+              <Layers className="h-4 w-4 mr-2" />
+              Groups
+            </Button>
+            <Button variant="outline" onClick={() => setIsInstitutionsOpen(true)}>
+              <Building2 className="h-4 w-4 mr-2" />
+              Institutions
+            </Button>
+            <Button onClick={() => handleOpenDialog()}>
+              <Plus className="h-4 w-4 mr-2" />
+              Add Account
+            </Button>
+          </div>
         }
       />
       <PageContainer>
@@ -165,7 +433,12 @@ export function Accounts() {
                   onEdit={() => handleOpenDialog(account)}
                   onDelete={() => handleDelete(account.id)}
                   onHide={() => updateAccount(account.id, { isHidden: !account.isHidden })}
+                  onToggleBudget={() =>
+                    updateAccount(account.id, { excludeFromBudget: !account.excludeFromBudget })
+                  }
                   onViewDetails={() => navigate(`/accounts/${account.id}`)}
+                  onCloseAccount={() => handleOpenCloseDialog(account)}
+                  onMerge={() => handleOpenMergeDialog(account)}
                 />
               ))}
               {groupedAccounts.assets.length === 0 && (
@@ -197,7 +470,12 @@ export function Accounts() {
                   onEdit={() => handleOpenDialog(account)}
                   onDelete={() => handleDelete(account.id)}
                   onHide={() => updateAccount(account.id, { isHidden: !account.isHidden })}
+                  onToggleBudget={() =>
+                    updateAccount(account.id, { excludeFromBudget: !account.excludeFromBudget })
+                  }
                   onViewDetails={() => navigate(`/accounts/${account.id}`)}
+                  onCloseAccount={() => handleOpenCloseDialog(account)}
+                  onMerge={() => handleOpenMergeDialog(account)}
                 />
               ))}
               {groupedAccounts.liabilities.length === 0 && (
@@ -217,6 +495,30 @@ export function Accounts() {
               )}
             </div>
           </section>
+
+          {/* Closed accounts keep their history for reports */}
+          {groupedAccounts.closed.length > 0 && (
+            <section>
+              <h2 className="text-lg font-semibold mb-4">Closed</h2>
+              <div className="grid gap-4 sm:grid-cols-2 lg:grid-cols-3">
+                {groupedAccounts.closed.map((account) => (
+                  <AccountCard
+                    key={account.id}
+                    account={account}
+                    onEdit={() => handleOpenDialog(account)}
+                    onDelete={() => handleDelete(account.id)}
+                    onHide={() => updateAccount(account.id, { isHidden: !account.isHidden })}
+                    onToggleBudget={() =>
+                      updateAccount(account.id, { excludeFromBudget: !account.excludeFromBudget })
+                    }
+                    onViewDetails={() => navigate(`/accounts/${account.id}`)}
+                    onCloseAccount={() => handleReopenAccount(account.id)}
+                    onMerge={() => handleOpenMergeDialog(account)}
+                  />
+                ))}
+              </div>
+            </section>
+          )}
         </div>
       </PageContainer>
 
@@ -269,24 +571,220 @@ export function Accounts() {
             </div>
 
             <div className="space-y-2">
-              <Label htmlFor="balance">Current Balance</Label>
-              <div className="relative">
-                <span className="absolute left-3 top-1/2 -translate-y-1/2 text-muted-foreground">
-                  $
-                </span>
+              <Label htmlFor="institution">Institution</Label>
+              <Select
+                value={formData.institutionId}
+                onValueChange={(value) => setFormData({ ...formData, institutionId: value })}
+              >
+                <SelectTrigger id="institution">
+                  <SelectValue />
+                </SelectTrigger>
+                <SelectContent>
+                  <SelectItem value="none">None</SelectItem>
+                  {institutions.map((institution) => (
+                    <SelectItem key={institution.id} value={institution.id}>
+                      {institution.name}
+                    </SelectItem>
+                  ))}
+                </SelectContent>
+              </Select>
+            </div>
+
+            <div className="space-y-2">
+              <Label htmlFor="accountGroup">Group</Label>
+              <Select
+                value={formData.accountGroupId}
+                onValueChange={(value) => setFormData({ ...formData, accountGroupId: value })}
+              >
+                <SelectTrigger id="accountGroup">
+                  <SelectValue />
+                </SelectTrigger>
+                <SelectContent>
+                  <SelectItem value="none">Ungrouped</SelectItem>
+                  {accountGroups.map((group) => (
+                    <SelectItem key={group.id} value={group.id}>
+                      {group.name}
+                    </SelectItem>
+                  ))}
+                </SelectContent>
+              </Select>
+            </div>
+
+            <div className="space-y-2">
+              <Label htmlFor="currency">Currency</Label>
+              <Input
+                id="currency"
+                value={formData.currency}
+                onChange={(e) => setFormData({ ...formData, currency: e.target.value })}
+                maxLength={3}
+                placeholder="Base currency"
+              />
+            </div>
+
+            <div className="grid grid-cols-2 gap-4">
+              <div className="space-y-2">
+                <Label htmlFor="balance">
+                  {editingAccount ? "Current Balance" : "Opening Balance"}
+                </Label>
+                <div className="relative">
+                  <span className="absolute left-3 top-1/2 -translate-y-1/2 text-muted-foreground">
+                    $
+                  </span>
+                  <Input
+                    id="balance"
+                    type="number"
+                    step="0.01"
+                    value={formData.currentBalance}
+                    onChange={(e) =>
+                      setFormData({ ...formData, currentBalance: e.target.value })
+                    }
+                    className="pl-7"
+                    placeholder="0.00"
+                  />
+                </div>
+              </div>
+              <div className="space-y-2">
+                <Label htmlFor="openingDate">Opening Date</Label>
                 <Input
-                  id="balance"
+                  id="openingDate"
+                  type="date"
+                  value={formData.openingDate}
+                  onChange={(e) => setFormData({ ...formData, openingDate: e.target.value })}
+                />
+              </div>
+            </div>
+
+            {formData.accountType === "credit_card" && (
+              <div className="grid grid-cols-2 gap-4">
+                <div className="space-y-2">
+                  <Label htmlFor="creditLimit">Credit Limit (optional)</Label>
+                  <Input
+                    id="creditLimit"
+                    type="number"
+                    step="0.01"
+                    min="0"
+                    value={formData.creditLimit}
+                    onChange={(e) =>
+                      setFormData({ ...formData, creditLimit: e.target.value })
+                    }
+                    placeholder="5000.00"
+                  />
+                </div>
+                <div className="space-y-2">
+                  <Label htmlFor="minimumPayment">Minimum Payment (optional)</Label>
+                  <Input
+                    id="minimumPayment"
+                    type="number"
+                    step="0.01"
+                    min="0"
+                    value={formData.minimumPayment}
+                    onChange={(e) =>
+                      setFormData({ ...formData, minimumPayment: e.target.value })
+                    }
+                    placeholder="35.00"
+                  />
+                </div>
+                <div className="space-y-2">
+                  <Label htmlFor="statementClosingDay">Statement Closes On Day</Label>
+                  <Input
+                    id="statementClosingDay"
+                    type="number"
+                    min="1"
+                    max="31"
+                    value={formData.statementClosingDay}
+                    onChange={(e) =>
+                      setFormData({ ...formData, statementClosingDay: e.target.value })
+                    }
+                    placeholder="15"
+                  />
+                </div>
+                <div className="space-y-2">
+                  <Label htmlFor="paymentDueDay">Payment Due On Day</Label>
+                  <Input
+                    id="paymentDueDay"
+                    type="number"
+                    min="1"
+                    max="31"
+                    value={formData.paymentDueDay}
+                    onChange={(e) =>
+                      setFormData({ ...formData, paymentDueDay: e.target.value })
+                    }
+                    placeholder="10"
+                  />
+                </div>
+              </div>
+            )}
+
+            {formData.accountType === "savings" && (
+              <div className="grid grid-cols-2 gap-4">
+                <div className="space-y-2">
+                  <Label htmlFor="interestRate">APY % (optional)</Label>
+                  <Input
+                    id="interestRate"
+                    type="number"
+                    step="0.01"
+                    min="0"
+                    value={formData.interestRate}
+                    onChange={(e) =>
+                      setFormData({ ...formData, interestRate: e.target.value })
+                    }
+                    placeholder="4.25"
+                  />
+                </div>
+                <div className="space-y-2">
+                  <Label>Compounding</Label>
+                  <Select
+                    value={formData.interestCompounding}
+                    onValueChange={(value) =>
+                      setFormData({
+                        ...formData,
+                        interestCompounding: value as InterestCompounding,
+                      })
+                    }
+                  >
+                    <SelectTrigger>
+                      <SelectValue />
+                    </SelectTrigger>
+                    <SelectContent>
+                      {Object.entries(INTEREST_COMPOUNDING).map(([key, { label }]) => (
+                        <SelectItem key={key} value={key}>
+                          {label}
+                        </SelectItem>
+                      ))}
+                    </SelectContent>
+                  </Select>
+                </div>
+              </div>
+            )}
+
+            {formData.accountType === "loan" && (
+              <div className="space-y-2">
+                <Label htmlFor="interestRate">APR % (optional)</Label>
+                <Input
+                  id="interestRate"
                   type="number"
                   step="0.01"
-                  value={formData.currentBalance}
+                  min="0"
+                  value={formData.interestRate}
                   onChange={(e) =>
-                    setFormData({ ...formData, currentBalance: e.target.value })
+                    setFormData({ ...formData, interestRate: e.target.value })
                   }
-                  className="pl-7"
-                  placeholder="0.00"
+                  placeholder="6.50"
                 />
               </div>
-            </div>
+            )}
+
+            {["savings", "loan"].includes(formData.accountType) && (
+              <div className="flex items-center gap-2">
+                <Switch
+                  checked={formData.accrueInterest}
+                  onCheckedChange={(checked) =>
+                    setFormData({ ...formData, accrueInterest: checked })
+                  }
+                />
+                <Label>Post estimated interest each month</Label>
+              </div>
+            )}
 
             <div className="space-y-2">
               <Label htmlFor="notes">Notes (optional)</Label>
@@ -315,6 +813,359 @@ export function Accounts() {
           </form>
         </DialogContent>
       </Dialog>
+
+      {/* Close Account Dialog */}
+      <Dialog
+        open={closingAccount !== null}
+        onOpenChange={(open) => !open && setClosingAccount(null)}
+      >
+        <DialogContent>
+          <DialogHeader>
+            <DialogTitle>Close {closingAccount?.name}</DialogTitle>
+            <DialogDescription>
+              A closed account is left out of account pickers and totals. Its transactions
+              stay in reports, and you can reopen it later.
+            </DialogDescription>
+          </DialogHeader>
+          <form onSubmit={handleCloseAccount}>
+            <div className="space-y-4 py-4">
+              <p className="text-sm">
+                Current balance:{" "}
+                <span className="font-medium">
+                  {closingAccount &&
+                    formatMoney(closingAccount.currentBalance, closingAccount.currency)}
+                </span>
+              </p>
+              <div className="space-y-2">
+                <Label htmlFor="closedOn">Closing Date</Label>
+                <Input
+                  id="closedOn"
+                  type="date"
+                  value={closeForm.date}
+                  onChange={(e) => setCloseForm({ ...closeForm, date: e.target.value })}
+                />
+              </div>
+              {closingAccount && closingAccount.currentBalance !== 0 && (
+                <div className="space-y-2">
+                  <Label>Transfer Balance To</Label>
+                  <Select
+                    value={closeForm.transferAccountId}
+                    onValueChange={(v) => setCloseForm({ ...closeForm, transferAccountId: v })}
+                  >
+                    <SelectTrigger>
+                      <SelectValue />
+                    </SelectTrigger>
+                    <SelectContent>
+                      <SelectItem value="none">Select an account</SelectItem>
+                      {getOpenAccounts()
+                        .filter(
+                          (a) =>
+                            a.id !== closingAccount.id && a.currency === closingAccount.currency
+                        )
+                        .map((account) => (
+                          <SelectItem key={account.id} value={account.id}>
+                            {account.name}
+                          </SelectItem>
+                        ))}
+                    </SelectContent>
+                  </Select>
+                  <p className="text-sm text-muted-foreground">
+                    The balance must be zero or transferred out before the account can close.
+                  </p>
+                </div>
+              )}
+              <div className="space-y-2">
+                <Label htmlFor="closeNotes">Notes</Label>
+                <Input
+                  id="closeNotes"
+                  value={closeForm.notes}
+                  onChange={(e) => setCloseForm({ ...closeForm, notes: e.target.value })}
+                  placeholder="Optional"
+                />
+              </div>
+              {closeError && <p className="text-sm text-destructive">{closeError}</p>}
+            </div>
+            <DialogFooter>
+              <Button type="button" variant="outline" onClick={() => setClosingAccount(null)}>
+                Cancel
+              </Button>
+              <Button type="submit">Close Account</Button>
+            </DialogFooter>
+          </form>
+        </DialogContent>
+      </Dialog>
+
+      {/* Delete Account Dialog */}
+      <Dialog
+        open={deletingAccount !== null}
+        onOpenChange={(open) => !open && setDeletingAccount(null)}
+      >
+        <DialogContent>
+          <DialogHeader>
+            <DialogTitle>Delete {deletingAccount?.name}</DialogTitle>
+            <DialogDescription>
+              The account moves to the trash, where it can be restored.
+            </DialogDescription>
+          </DialogHeader>
+          <form onSubmit={handleConfirmDelete}>
+            <div className="space-y-4 py-4">
+              {deletePreview && (
+                <p className="text-sm">
+                  {deletePreview.transactionCount === 1
+                    ? "1 transaction"
+                    : `${deletePreview.transactionCount} transactions`}{" "}
+                  totalling{" "}
+                  {deletingAccount &&
+                    formatMoney(deletePreview.transactionTotal, deletingAccount.currency)}
+                </p>
+              )}
+              {deletePreview && deletePreview.transactionCount > 0 && (
+                <div className="space-y-2">
+                  <Label>Transactions</Label>
+                  <Select
+                    value={deleteForm.transactions}
+                    onValueChange={(v) =>
+                      setDeleteForm({
+                        ...deleteForm,
+                        transactions: v as AccountDeleteTransactions,
+                      })
+                    }
+                  >
+                    <SelectTrigger>
+                      <SelectValue />
+                    </SelectTrigger>
+                    <SelectContent>
+                      <SelectItem value="keep">Keep them with the deleted account</SelectItem>
+                      <SelectItem value="delete">Move them to the trash too</SelectItem>
+                      <SelectItem value="reassign">Move them to another account</SelectItem>
+                    </SelectContent>
+                  </Select>
+                </div>
+              )}
+              {deleteForm.transactions === "reassign" && (
+                <div className="space-y-2">
+                  <Label>Move To</Label>
+                  <Select
+                    value={deleteForm.reassignToAccountId}
+                    onValueChange={(v) => setDeleteForm({ ...deleteForm, reassignToAccountId: v })}
+                  >
+                    <SelectTrigger>
+                      <SelectValue />
+                    </SelectTrigger>
+                    <SelectContent>
+                      <SelectItem value="none">Select an account</SelectItem>
+                      {getOpenAccounts()
+                        .filter(
+                          (a) =>
+                            a.id !== deletingAccount?.id &&
+                            a.currency === deletingAccount?.currency
+                        )
+                        .map((account) => (
+                          <SelectItem key={account.id} value={account.id}>
+                            {account.name}
+                          </SelectItem>
+                        ))}
+                    </SelectContent>
+                  </Select>
+                </div>
+              )}
+              {deleteError && <p className="text-sm text-destructive">{deleteError}</p>}
+            </div>
+            <DialogFooter>
+              <Button type="button" variant="outline" onClick={() => setDeletingAccount(null)}>
+                Cancel
+              </Button>
+              <Button
+                type="submit"
+                variant="destructive"
+                disabled={
+                  deleteForm.transactions === "reassign" &&
+                  deleteForm.reassignToAccountId === "none"
+                }
+              >
+                Delete Account
+              </Button>
+            </DialogFooter>
+          </form>
+        </DialogContent>
+      </Dialog>
+
+      {/* Merge Account Dialog */}
+      <Dialog
+        open={mergingAccount !== null}
+        onOpenChange={(open) => !open && setMergingAccount(null)}
+      >
+        <DialogContent>
+          <DialogHeader>
+            <DialogTitle>Merge {mergingAccount?.name}</DialogTitle>
+            <DialogDescription>
+              Moves this account&apos;s transactions, recurring items and holdings to another
+              account and deletes this one. Use it when the same account was added twice.
+            </DialogDescription>
+          </DialogHeader>
+          <form onSubmit={handleMergeAccounts}>
+            <div className="space-y-4 py-4">
+              <div className="space-y-2">
+                <Label>Merge Into</Label>
+                <Select value={mergeTargetId} onValueChange={setMergeTargetId}>
+                  <SelectTrigger>
+                    <SelectValue />
+                  </SelectTrigger>
+                  <SelectContent>
+                    <SelectItem value="none">Select an account</SelectItem>
+                    {accounts
+                      .filter(
+                        (a) =>
+                          a.id !== mergingAccount?.id && a.currency === mergingAccount?.currency
+                      )
+                      .map((account) => (
+                        <SelectItem key={account.id} value={account.id}>
+                          {account.name}
+                        </SelectItem>
+                      ))}
+                  </SelectContent>
+                </Select>
+              </div>
+              {mergeError && <p className="text-sm text-destructive">{mergeError}</p>}
+            </div>
+            <DialogFooter>
+              <Button type="button" variant="outline" onClick={() => setMergingAccount(null)}>
+                Cancel
+              </Button>
+              <Button type="submit" disabled={mergeTargetId === "none"}>
+                Merge Accounts
+              </Button>
+            </DialogFooter>
+          </form>
+        </DialogContent>
+      </Dialog>
+
+      {/* Account Groups Dialog */}
+      <Dialog open={isGroupsOpen} onOpenChange={setIsGroupsOpen}>
+        <DialogContent>
+          <DialogHeader>
+            <DialogTitle>Account Groups</DialogTitle>
+            <DialogDescription>
+              Net worth on the dashboard is broken down by these groups
+            </DialogDescription>
+          </DialogHeader>
+          <div className="space-y-2">
+            {accountGroups.map((group) => (
+              <div key={group.id} className="flex items-center gap-2">
+                <Input
+                  defaultValue={group.name}
+                  onBlur={(e) =>
+                    e.target.value.trim() &&
+                    e.target.value !== group.name &&
+                    handleRenameAccountGroup(group.id, e.target.value)
+                  }
+                />
+                <Button
+                  variant="ghost"
+                  size="icon"
+                  onClick={() => handleDeleteAccountGroup(group.id)}
+                >
+                  <Trash2 className="h-4 w-4" />
+                </Button>
+              </div>
+            ))}
+            {accountGroups.length === 0 && (
+              <p className="text-sm text-muted-foreground">No groups yet</p>
+            )}
+          </div>
+          <form onSubmit={handleAddAccountGroup} className="flex items-center gap-2">
+            <Input
+              value={newGroupName}
+              onChange={(e) => setNewGroupName(e.target.value)}
+              placeholder="e.g., Retirement"
+              required
+            />
+            <Button type="submit">
+              <Plus className="h-4 w-4" />
+            </Button>
+          </form>
+        </DialogContent>
+      </Dialog>
+
+      {/* Institutions Dialog */}
+      <Dialog open={isInstitutionsOpen} onOpenChange={setIsInstitutionsOpen}>
+        <DialogContent>
+          <DialogHeader>
+            <DialogTitle>Institutions</DialogTitle>
+            <DialogDescription>
+              The banks and brokerages your accounts are held at
+            </DialogDescription>
+          </DialogHeader>
+          <div className="space-y-2">
+            {institutions.map((institution) => (
+              <div key={institution.id} className="flex items-center gap-2">
+                <Input
+                  type="color"
+                  value={institution.primaryColor ?? "#3b82f6"}
+                  onChange={(e) =>
+                    handleUpdateInstitution(institution.id, { primaryColor: e.target.value })
+                  }
+                  className="h-9 w-12 p-1"
+                />
+                <Input
+                  defaultValue={institution.name}
+                  onBlur={(e) =>
+                    e.target.value.trim() &&
+                    e.target.value !== institution.name &&
+                    handleUpdateInstitution(institution.id, { name: e.target.value })
+                  }
+                />
+                <Input
+                  defaultValue={institution.website ?? ""}
+                  placeholder="Website"
+                  onBlur={(e) =>
+                    e.target.value !== (institution.website ?? "") &&
+                    handleUpdateInstitution(institution.id, {
+                      website: e.target.value || null,
+                    })
+                  }
+                />
+                <Button
+                  variant="ghost"
+                  size="icon"
+                  onClick={() => handleDeleteInstitution(institution.id)}
+                >
+                  <Trash2 className="h-4 w-4" />
+                </Button>
+              </div>
+            ))}
+            {institutions.length === 0 && (
+              <p className="text-sm text-muted-foreground">No institutions yet</p>
+            )}
+          </div>
+          <form onSubmit={handleAddInstitution} className="flex items-center gap-2">
+            <Input
+              type="color"
+              value={institutionForm.primaryColor}
+              onChange={(e) =>
+                setInstitutionForm({ ...institutionForm, primaryColor: e.target.value })
+              }
+              className="h-9 w-12 p-1"
+            />
+            <Input
+              value={institutionForm.name}
+              onChange={(e) => setInstitutionForm({ ...institutionForm, name: e.target.value })}
+              placeholder="e.g., Chase"
+              required
+            />
+            <Input
+              value={institutionForm.website}
+              onChange={(e) =>
+                setInstitutionForm({ ...institutionForm, website: e.target.value })
+              }
+              placeholder="Website"
+            />
+            <Button type="submit">
+              <Plus className="h-4 w-4" />
+            </Button>
+          </form>
+        </DialogContent>
+      </Dialog>
     </>
   );
 }
@@ -324,10 +1175,23 @@ interface AccountCardProps {
   onEdit: () => void;
   onDelete: () => void;
   onHide: () => void;
+  onToggleBudget: () => void;
   onViewDetails: () => void;
+  // Closes an open account, or reopens a closed one
+  onCloseAccount: () => void;
+  onMerge: () => void;
 }
 
-function AccountCard({ account, onEdit, onDelete, onHide, onViewDetails }: AccountCardProps) {
+function AccountCard({
+  account,
+  onEdit,
+  onDelete,
+  onHide,
+  onToggleBudget,
+  onViewDetails,
+  onCloseAccount,
+  onMerge,
+}: AccountCardProps) {
   const typeInfo = ACCOUNT_TYPES[account.accountType];
   const Icon = iconMap[typeInfo.icon as keyof typeof iconMap] || Wallet;
 
@@ -340,7 +1204,11 @@ function AccountCard({ account, onEdit, onDelete, onHide, onViewDetails }: Accou
           </div>
           <div>
             <CardTitle className="text-base">{account.name}</CardTitle>
-            <p className="text-sm text-muted-foreground">{typeInfo.label}</p>
+            <p className="text-sm text-muted-foreground">
+              {account.institutionName
+                ? `${typeInfo.label} · ${account.institutionName}`
+                : typeInfo.label}
+            </p>
           </div>
         </div>
         <DropdownMenu>
@@ -362,6 +1230,22 @@ function AccountCard({ account, onEdit, onDelete, onHide, onViewDetails }: Accou
               <EyeOff className="h-4 w-4 mr-2" />
               {account.isHidden ? "Show" : "Hide"}
             </DropdownMenuItem>
+            <DropdownMenuItem onClick={onToggleBudget}>
+              <Target className="h-4 w-4 mr-2" />
+              {account.excludeFromBudget ? "Include in Budgets" : "Exclude from Budgets"}
+            </DropdownMenuItem>
+            <DropdownMenuItem onClick={onCloseAccount}>
+              {account.closedOn ? (
+                <ArchiveRestore className="h-4 w-4 mr-2" />
+              ) : (
+                <Archive className="h-4 w-4 mr-2" />
+              )}
+              {account.closedOn ? "Reopen Account" : "Close Account"}
+            </DropdownMenuItem>
+            <DropdownMenuItem onClick={onMerge}>
+              <Merge className="h-4 w-4 mr-2" />
+              Merge Into...
+            </DropdownMenuItem>
             <DropdownMenuSeparator />
             <DropdownMenuItem onClick={onDelete} className="text-destructive">
               <Trash2 className="h-4 w-4 mr-2" />
@@ -377,11 +1261,17 @@ function AccountCard({ account, onEdit, onDelete, onHide, onViewDetails }: Accou
             account.currentBalance < 0 ? "text-red-600" : "text-foreground"
           )}
         >
-          {formatMoney(account.currentBalance)}
+          {formatMoney(account.currentBalance, account.currency)}
         </p>
         {account.notes && (
           <p className="text-sm text-muted-foreground mt-1">{account.notes}</p>
         )}
+        {account.excludeFromBudget && (
+          <p className="text-xs text-muted-foreground mt-1">Excluded from budgets</p>
+        )}
+        {account.closedOn && (
+          <p className="text-xs text-muted-foreground mt-1">Closed on {account.closedOn}</p>
+        )}
       </CardContent>
     </Card>
   );
@@ -1,10 +1,28 @@
 import { useEffect, useState, useMemo } from "react";
-import { Plus, Pencil, Trash2, Target, ChevronLeft, ChevronRight } from "lucide-react";
+import {
+  Plus,
+  Pencil,
+  Trash2,
+  Target,
+  ChevronLeft,
+  ChevronRight,
+  Eye,
+  TrendingUp,
+  TrendingDown,
+  Lock,
+  LockOpen,
+  CalendarDays,
+  Copy,
+  DollarSign,
+  Bell,
+  Users,
+} from "lucide-react";
 import { Card, CardContent, CardHeader, CardTitle, CardDescription } from "@/components/ui/card";
 import { Button } from "@/components/ui/button";
 import { Input } from "@/components/ui/input";
 import { Label } from "@/components/ui/label";
 import { Switch } from "@/components/ui/switch";
+import { Checkbox } from "@/components/ui/checkbox";
 import { Progress } from "@/components/ui/progress";
 import {
   Select,
@@ -21,6 +39,12 @@ import {
   DialogHeader,
   DialogTitle,
 } from "@/components/ui/dialog";
+import {
+  DropdownMenu,
+  DropdownMenuContent,
+  DropdownMenuItem,
+  DropdownMenuTrigger,
+} from "@/components/ui/dropdown-menu";
 import {
   AlertDialog,
   AlertDialogAction,
@@ -40,26 +64,53 @@ import {
   createBudget,
   updateBudget,
   deleteBudget,
+  closeMonth,
+  reopenMonth,
+  listMonthCloses,
+  setBudgetMonthAmount,
+  copyBudgets,
+  generateBudgets,
+  listBudgetAlerts,
+  dismissBudgetAlerts,
+  createBudgetGroup,
+  updateBudgetGroup,
+  deleteBudgetGroup,
 } from "@/lib/tauri";
-import { formatMoney } from "@/lib/formatters";
+import { onBudgetAlert, onChanged, BUDGETS_CHANGED } from "@/lib/events";
+import { formatDate, formatMoney } from "@/lib/formatters";
+import { BUDGET_PERIODS, type BudgetPeriod } from "@/lib/constants";
 import { cn } from "@/lib/utils";
-import type { Budget, Category } from "@/types";
-
-interface BudgetSummary {
-  budget: Budget;
-  category: Category;
-  spent: number;
-  remaining: number;
-}
+import type {
+  Budget,
+  BudgetAlert,
+  BudgetGroup,
+  BudgetGroupSummary,
+  BudgetSummary,
+  IncomeBudgetSummary,
+  MonthClose,
+  WatchedBudgetSummary,
+} from "@/types";
 
 export function Budgets() {
   const { categories, fetchCategories } = useCategoryStore();
   const [budgets, setBudgets] = useState<Budget[]>([]);
   const [summaries, setSummaries] = useState<BudgetSummary[]>([]);
+  const [watched, setWatched] = useState<WatchedBudgetSummary[]>([]);
+  const [income, setIncome] = useState<IncomeBudgetSummary[]>([]);
+  const [alerts, setAlerts] = useState<BudgetAlert[]>([]);
+  const [groups, setGroups] = useState<BudgetGroupSummary[]>([]);
+  const [groupFormOpen, setGroupFormOpen] = useState(false);
+  const [selectedGroup, setSelectedGroup] = useState<BudgetGroup | null>(null);
+  const [groupForm, setGroupForm] = useState({ name: "", amount: "", categoryIds: [] as string[] });
+  const [groupError, setGroupError] = useState<string | null>(null);
   const [loading, setLoading] = useState(true);
   const [formOpen, setFormOpen] = useState(false);
   const [deleteDialogOpen, setDeleteDialogOpen] = useState(false);
   const [selectedBudget, setSelectedBudget] = useState<Budget | null>(null);
+  const [monthCloses, setMonthCloses] = useState<MonthClose[]>([]);
+  const [closeError, setCloseError] = useState<string | null>(null);
+  const [monthAmountSummary, setMonthAmountSummary] = useState<BudgetSummary | null>(null);
+  const [monthAmountInput, setMonthAmountInput] = useState("");
 
   // Month navigation
   const [currentMonth, setCurrentMonth] = useState(() => {
@@ -71,19 +122,37 @@ export function Budgets() {
   const [formData, setFormData] = useState({
     categoryId: "",
     amount: "",
-    periodType: "monthly",
+    periodType: "monthly" as BudgetPeriod,
+    periodDays: "",
+    periodAnchor: "",
     rollover: false,
+    isWatchOnly: false,
+    alertThresholds: "",
+    includeChildren: false,
   });
 
   useEffect(() => {
     fetchCategories();
     loadBudgets();
+    loadMonthCloses();
+    loadAlerts();
   }, [fetchCategories]);
 
   useEffect(() => {
-    if (budgets.length > 0) {
-      loadSummaries();
-    }
+    const subscriptions = [
+      onBudgetAlert(() => {
+        loadAlerts();
+        loadSummaries();
+      }),
+      onChanged(BUDGETS_CHANGED, loadAlerts),
+    ];
+    return () => {
+      subscriptions.forEach((subscription) => subscription.then((unsubscribe) => unsubscribe()));
+    };
+  }, [currentMonth]);
+
+  useEffect(() => {
+    loadSummaries();
   }, [budgets, currentMonth]);
 
   const loadBudgets = async () => {
@@ -97,25 +166,125 @@ export function Budgets() {
     }
   };
 
+  const loadMonthCloses = async () => {
+    try {
+      setMonthCloses(await listMonthCloses());
+    } catch (err) {
+      console.error("Failed to load month closes:", err);
+    }
+  };
+
+  const currentClose = monthCloses.find((c) => c.month === currentMonth);
+  const isPastMonth = (() => {
+    const now = new Date();
+    return currentMonth < `${now.getFullYear()}-${String(now.getMonth() + 1).padStart(2, "0")}`;
+  })();
+
+  const handleToggleClose = async () => {
+    setCloseError(null);
+    try {
+      if (currentClose) {
+        await reopenMonth(currentMonth);
+      } else {
+        await closeMonth(currentMonth);
+      }
+      await loadMonthCloses();
+      await loadSummaries();
+    } catch (err) {
+      setCloseError(String(err));
+    }
+  };
+
+  const handleEditMonthAmount = (summary: BudgetSummary) => {
+    setMonthAmountSummary(summary);
+    setMonthAmountInput(String(summary.monthlyAmount / 100));
+  };
+
+  // A null amount goes back to the budget's usual amount
+  const saveMonthAmount = async (amount: number | null) => {
+    if (!monthAmountSummary) return;
+    try {
+      await setBudgetMonthAmount(monthAmountSummary.budget.id, currentMonth, amount);
+      await loadSummaries();
+      setMonthAmountSummary(null);
+    } catch (err) {
+      console.error("Failed to set month amount:", err);
+    }
+  };
+
+  const previousMonth = (() => {
+    const [year = 0, month = 1] = currentMonth.split("-").map(Number);
+    return month === 1 ? `${year - 1}-12` : `${year}-${String(month - 1).padStart(2, "0")}`;
+  })();
+
+  // Amounts already set for this month are kept
+  const handleCopyPrevious = async (source: "amounts" | "actuals") => {
+    setCloseError(null);
+    try {
+      await copyBudgets(previousMonth, currentMonth, source);
+      await loadSummaries();
+    } catch (err) {
+      setCloseError(String(err));
+    }
+  };
+
+  // Starter budgets from the last three months of spending
+  const handleGenerate = async (method: "average" | "split") => {
+    setCloseError(null);
+    try {
+      await generateBudgets(method, 3);
+      await loadBudgets();
+    } catch (err) {
+      setCloseError(String(err));
+    }
+  };
+
+  const loadAlerts = async () => {
+    try {
+      setAlerts(await listBudgetAlerts());
+    } catch (err) {
+      console.error("Failed to load budget alerts:", err);
+    }
+  };
+
+  const handleDismissAlerts = async (ids: string[]) => {
+    try {
+      await dismissBudgetAlerts(ids);
+      await loadAlerts();
+    } catch (err) {
+      console.error("Failed to dismiss budget alerts:", err);
+    }
+  };
+
   const loadSummaries = async () => {
     try {
       const data = await getBudgetSummary(currentMonth);
-      setSummaries(data);
+      setSummaries(data.expenses);
+      setWatched(data.watched);
+      setIncome(data.income);
+      setGroups(data.groups);
     } catch (err) {
       console.error("Failed to load budget summaries:", err);
     }
   };
 
-  const expenseCategories = useMemo(
-    () => categories.filter((c) => c.categoryType === "expense"),
+  // Income categories get budgets for the income expected each month
+  const budgetCategories = useMemo(
+    () =>
+      categories.filter(
+        (c) => (c.categoryType === "expense" || c.categoryType === "income") && !c.isArchived
+      ),
     [categories]
   );
 
   const unusedCategories = useMemo(
-    () => expenseCategories.filter((c) => !budgets.some((b) => b.categoryId === c.id)),
-    [expenseCategories, budgets]
+    () => budgetCategories.filter((c) => !budgets.some((b) => b.categoryId === c.id)),
+    [budgetCategories, budgets]
   );
 
+  const isIncomeForm =
+    categories.find((c) => c.id === formData.categoryId)?.categoryType === "income";
+
   const navigateMonth = (direction: "prev" | "next") => {
     const parts = currentMonth.split("-").map(Number);
     const year = parts[0] ?? new Date().getFullYear();
@@ -150,7 +319,12 @@ export function Budgets() {
       categoryId: unusedCategories[0]?.id || "",
       amount: "",
       periodType: "monthly",
+      periodDays: "",
+      periodAnchor: "",
       rollover: false,
+      isWatchOnly: false,
+      alertThresholds: "",
+      includeChildren: false,
     });
     setFormOpen(true);
   };
@@ -161,7 +335,12 @@ export function Budgets() {
       categoryId: budget.categoryId,
       amount: String(budget.amount / 100),
       periodType: budget.periodType,
+      periodDays: budget.periodDays ? String(budget.periodDays) : "",
+      periodAnchor: budget.periodAnchor || "",
       rollover: budget.rollover,
+      isWatchOnly: budget.isWatchOnly,
+      alertThresholds: budget.alertThresholds.join(", "),
+      includeChildren: budget.includeChildren,
     });
     setFormOpen(true);
   };
@@ -186,9 +365,20 @@ export function Budgets() {
     try {
       const data = {
         categoryId: formData.categoryId,
-        amount: Math.round(parseFloat(formData.amount) * 100),
-        periodType: formData.periodType as "weekly" | "biweekly" | "monthly" | "quarterly" | "yearly",
-        rollover: formData.rollover,
+        amount: formData.isWatchOnly && !isIncomeForm ? 0 : Math.round(parseFloat(formData.amount) * 100),
+        periodType: formData.periodType,
+        periodDays: formData.periodType === "custom" ? parseInt(formData.periodDays, 10) || null : null,
+        periodAnchor: formData.periodAnchor || null,
+        rollover: formData.rollover && !isIncomeForm,
+        isWatchOnly: formData.isWatchOnly && !isIncomeForm,
+        includeChildren: formData.includeChildren,
+        alertThresholds:
+          formData.isWatchOnly || isIncomeForm
+            ? []
+            : formData.alertThresholds
+                .split(",")
+                .map((t) => parseInt(t, 10))
+                .filter((t) => t > 0),
       };
 
       if (selectedBudget) {
@@ -203,8 +393,56 @@ export function Budgets() {
     }
   };
 
+  const handleEditGroup = (group: BudgetGroup | null) => {
+    setSelectedGroup(group);
+    setGroupForm({
+      name: group?.name || "",
+      amount: group ? String(group.amount / 100) : "",
+      categoryIds: group?.categoryIds || [],
+    });
+    setGroupError(null);
+    setGroupFormOpen(true);
+  };
+
+  const toggleGroupCategory = (categoryId: string) => {
+    setGroupForm((p) => ({
+      ...p,
+      categoryIds: p.categoryIds.includes(categoryId)
+        ? p.categoryIds.filter((id) => id !== categoryId)
+        : [...p.categoryIds, categoryId],
+    }));
+  };
+
+  const handleSubmitGroup = async () => {
+    try {
+      const data = {
+        name: groupForm.name,
+        amount: Math.round(parseFloat(groupForm.amount) * 100),
+        categoryIds: groupForm.categoryIds,
+      };
+      if (selectedGroup) {
+        await updateBudgetGroup(selectedGroup.id, data);
+      } else {
+        await createBudgetGroup(data);
+      }
+      await loadSummaries();
+      setGroupFormOpen(false);
+    } catch (err) {
+      setGroupError(String(err));
+    }
+  };
+
+  const handleDeleteGroup = async (group: BudgetGroup) => {
+    try {
+      await deleteBudgetGroup(group.id);
+      await loadSummaries();
+    } catch (err) {
+      console.error("Failed to delete budget group:", err);
+    }
+  };
+
   // Calculate totals
-  const totalBudgeted = summaries.reduce((sum, s) => sum + s.budget.amount, 0);
+  const totalBudgeted = summaries.reduce((sum, s) => sum + s.monthlyAmount, 0);
   const totalSpent = summaries.reduce((sum, s) => sum + s.spent, 0);
   const totalRemaining = totalBudgeted - totalSpent;
 
@@ -231,7 +469,85 @@ export function Budgets() {
           <Button variant="outline" size="icon" onClick={() => navigateMonth("next")}>
             <ChevronRight className="h-4 w-4" />
           </Button>
+          {!currentClose && budgets.length > 0 && (
+            <DropdownMenu>
+              <DropdownMenuTrigger asChild>
+                <Button variant="outline">
+                  <Copy className="h-4 w-4 mr-2" />
+                  Copy From {formatMonthDisplay(previousMonth)}
+                </Button>
+              </DropdownMenuTrigger>
+              <DropdownMenuContent>
+                <DropdownMenuItem onClick={() => handleCopyPrevious("amounts")}>
+                  Budgeted amounts
+                </DropdownMenuItem>
+                <DropdownMenuItem onClick={() => handleCopyPrevious("actuals")}>
+                  Actual spending
+                </DropdownMenuItem>
+              </DropdownMenuContent>
+            </DropdownMenu>
+          )}
+          {isPastMonth && (
+            <Button variant="outline" onClick={handleToggleClose}>
+              {currentClose ? (
+                <>
+                  <LockOpen className="h-4 w-4 mr-2" />
+                  Reopen Month
+                </>
+              ) : (
+                <>
+                  <Lock className="h-4 w-4 mr-2" />
+                  Close Month
+                </>
+              )}
+            </Button>
+          )}
         </div>
+        {closeError && <p className="text-sm text-red-600 text-center -mt-4 mb-6">{closeError}</p>}
+        {currentClose && (
+          <p className="text-sm text-muted-foreground text-center -mt-4 mb-6">
+            Closed with a net worth of {formatMoney(currentClose.netWorth)}.{" "}
+            {currentClose.lockedCount} reconciled transactions locked,{" "}
+            {currentClose.archivedReviewCount} left uncategorized.
+          </p>
+        )}
+
+        {alerts.length > 0 && (
+          <Card className="mb-6 border-amber-300">
+            <CardHeader className="pb-2">
+              <CardTitle className="flex items-center gap-2 text-base">
+                <Bell className="h-4 w-4 text-amber-600" />
+                Budget Alerts
+                <span className="rounded-full bg-amber-100 px-2 text-xs font-medium text-amber-700">
+                  {alerts.length}
+                </span>
+              </CardTitle>
+            </CardHeader>
+            <CardContent className="space-y-2">
+              {alerts.map((alert) => (
+                <div key={alert.id} className="flex items-center justify-between text-sm">
+                  <span>
+                    <span className="font-medium">{alert.categoryName}</span> reached{" "}
+                    {alert.threshold}% of its {formatMonthDisplay(alert.month)} budget (
+                    {formatMoney(alert.spent)} of {formatMoney(alert.budgeted)})
+                  </span>
+                  <Button variant="ghost" size="sm" onClick={() => handleDismissAlerts([alert.id])}>
+                    Dismiss
+                  </Button>
+                </div>
+              ))}
+              {alerts.length > 1 && (
+                <Button
+                  variant="outline"
+                  size="sm"
+                  onClick={() => handleDismissAlerts(alerts.map((a) => a.id))}
+                >
+                  Dismiss All
+                </Button>
+              )}
+            </CardContent>
+          </Card>
+        )}
 
         {/* Summary Cards */}
         <div className="grid gap-4 md:grid-cols-3 mb-6">
@@ -288,25 +604,38 @@ export function Budgets() {
           <CardContent>
             {loading ? (
               <p className="text-muted-foreground">Loading budgets...</p>
-            ) : summaries.length === 0 ? (
+            ) : summaries.length === 0 && watched.length === 0 && income.length === 0 ? (
               <div className="text-center py-8">
                 <Target className="h-12 w-12 mx-auto mb-4 text-muted-foreground" />
                 <p className="text-muted-foreground mb-4">
                   No budgets set. Create a budget to track your spending.
                 </p>
-                <Button onClick={handleCreate} disabled={unusedCategories.length === 0}>
-                  <Plus className="h-4 w-4 mr-2" />
-                  Create First Budget
-                </Button>
+                <div className="flex justify-center gap-2">
+                  <Button onClick={handleCreate} disabled={unusedCategories.length === 0}>
+                    <Plus className="h-4 w-4 mr-2" />
+                    Create First Budget
+                  </Button>
+                  <DropdownMenu>
+                    <DropdownMenuTrigger asChild>
+                      <Button variant="outline">Generate From History</Button>
+                    </DropdownMenuTrigger>
+                    <DropdownMenuContent>
+                      <DropdownMenuItem onClick={() => handleGenerate("average")}>
+                        Average spending, last 3 months
+                      </DropdownMenuItem>
+                      <DropdownMenuItem onClick={() => handleGenerate("split")}>
+                        50/30/20 split of income
+                      </DropdownMenuItem>
+                    </DropdownMenuContent>
+                  </DropdownMenu>
+                </div>
               </div>
             ) : (
               <div className="space-y-4">
                 {summaries.map((summary) => {
-                  const percentUsed = Math.min(
-                    (summary.spent / summary.budget.amount) * 100,
-                    100
-                  );
-                  const isOverBudget = summary.spent > summary.budget.amount;
+                  const available = summary.monthlyAmount + summary.rolledOver;
+                  const percentUsed = Math.min((summary.spent / available) * 100, 100);
+                  const isOverBudget = summary.spent > available;
 
                   return (
                     <div
@@ -322,6 +651,15 @@ export function Budgets() {
                           <span className="font-medium">{summary.category.name}</span>
                         </div>
                         <div className="flex items-center gap-2">
+                          <Button
+                            variant="ghost"
+                            size="icon"
+                            title="Set amount for this month"
+                            disabled={!!currentClose}
+                            onClick={() => handleEditMonthAmount(summary)}
+                          >
+                            <CalendarDays className="h-4 w-4" />
+                          </Button>
                           <Button variant="ghost" size="icon" onClick={() => handleEdit(summary.budget)}>
                             <Pencil className="h-4 w-4" />
                           </Button>
@@ -344,9 +682,43 @@ export function Budgets() {
                             {formatMoney(summary.spent)} spent
                           </span>
                           <span className="text-muted-foreground">
-                            {formatMoney(summary.budget.amount)} budgeted
+                            {formatMoney(summary.monthlyAmount)} budgeted
+                            {summary.isMonthAmount && " this month"}
+                            {summary.rolledOver > 0 && ` + ${formatMoney(summary.rolledOver)} rolled over`}
                           </span>
                         </div>
+                        {summary.budget.periodType !== "monthly" && (
+                          <p className="text-xs text-muted-foreground">
+                            {BUDGET_PERIODS[summary.budget.periodType].label} budget of{" "}
+                            {formatMoney(summary.budget.amount)}: {formatMoney(summary.periodSpent)} spent{" "}
+                            {formatDate(summary.periodStart)} – {formatDate(summary.periodEnd)}
+                          </p>
+                        )}
+                        {summary.forecast && (
+                          <p
+                            className={cn(
+                              "text-xs",
+                              summary.forecast.projectedOver > 0
+                                ? "text-amber-600"
+                                : "text-muted-foreground"
+                            )}
+                          >
+                            {summary.forecast.projectedOver > 0
+                              ? `On pace to overspend by ${formatMoney(summary.forecast.projectedOver)}`
+                              : `On pace to spend ${formatMoney(summary.forecast.projectedSpent)}`}
+                            {summary.forecast.upcomingRecurring > 0 &&
+                              `, including ${formatMoney(summary.forecast.upcomingRecurring)} in upcoming bills`}
+                          </p>
+                        )}
+                        {summary.children.some((child) => child.spent > 0) && (
+                          <p className="text-xs text-muted-foreground">
+                            Includes{" "}
+                            {summary.children
+                              .filter((child) => child.spent > 0)
+                              .map((child) => `${child.categoryName} ${formatMoney(child.spent)}`)
+                              .join(", ")}
+                          </p>
+                        )}
                         <div className="text-sm">
                           <span
                             className={cn(
@@ -363,18 +735,198 @@ export function Budgets() {
                     </div>
                   );
                 })}
+
+                {watched.length > 0 && (
+                  <div className="space-y-2">
+                    <h3 className="flex items-center gap-2 text-sm font-medium text-muted-foreground">
+                      <Eye className="h-4 w-4" />
+                      Watching
+                    </h3>
+                    {watched.map((summary) => (
+                      <div
+                        key={summary.budget.id}
+                        className="flex items-center justify-between p-4 border rounded-lg hover:bg-accent/50 transition-colors"
+                      >
+                        <div className="flex items-center gap-3">
+                          <div
+                            className="h-4 w-4 rounded-full"
+                            style={{ backgroundColor: summary.category.color || "#6b7280" }}
+                          />
+                          <span className="font-medium">{summary.category.name}</span>
+                        </div>
+                        <div className="flex items-center gap-4">
+                          <div className="text-right text-sm">
+                            <p>{formatMoney(summary.spent)} spent</p>
+                            <p
+                              className={cn(
+                                "flex items-center justify-end gap-1",
+                                summary.trend > 0 ? "text-red-600" : "text-green-600"
+                              )}
+                            >
+                              {summary.trend > 0 ? (
+                                <TrendingUp className="h-3 w-3" />
+                              ) : (
+                                <TrendingDown className="h-3 w-3" />
+                              )}
+                              {formatMoney(Math.abs(summary.trend))}{" "}
+                              {summary.trend > 0 ? "above" : "below"} the{" "}
+                              {formatMoney(summary.average)} average
+                            </p>
+                          </div>
+                          <div className="flex items-center gap-2">
+                            <Button variant="ghost" size="icon" onClick={() => handleEdit(summary.budget)}>
+                              <Pencil className="h-4 w-4" />
+                            </Button>
+                            <Button variant="ghost" size="icon" onClick={() => handleDelete(summary.budget)}>
+                              <Trash2 className="h-4 w-4" />
+                            </Button>
+                          </div>
+                        </div>
+                      </div>
+                    ))}
+                  </div>
+                )}
               </div>
             )}
           </CardContent>
         </Card>
 
+        <Card className="mt-6">
+          <CardHeader>
+            <div className="flex items-center justify-between">
+              <div className="space-y-1.5">
+                <CardTitle className="flex items-center gap-2">
+                  <Users className="h-5 w-5" />
+                  Shared Budgets
+                </CardTitle>
+                <CardDescription>
+                  One budget shared by several categories, like fun money for dining and hobbies
+                </CardDescription>
+              </div>
+              <Button variant="outline" onClick={() => handleEditGroup(null)}>
+                <Plus className="h-4 w-4 mr-2" />
+                Add Shared Budget
+              </Button>
+            </div>
+          </CardHeader>
+          {groups.length > 0 && (
+            <CardContent className="space-y-4">
+              {groups.map((summary) => (
+                <div
+                  key={summary.group.id}
+                  className="p-4 border rounded-lg hover:bg-accent/50 transition-colors"
+                >
+                  <div className="flex items-center justify-between mb-2">
+                    <span className="font-medium">{summary.group.name}</span>
+                    <div className="flex items-center gap-2">
+                      <Button variant="ghost" size="icon" onClick={() => handleEditGroup(summary.group)}>
+                        <Pencil className="h-4 w-4" />
+                      </Button>
+                      <Button variant="ghost" size="icon" onClick={() => handleDeleteGroup(summary.group)}>
+                        <Trash2 className="h-4 w-4" />
+                      </Button>
+                    </div>
+                  </div>
+                  <div className="space-y-2">
+                    <Progress
+                      value={summary.group.amount > 0 ? Math.min((summary.spent / summary.group.amount) * 100, 100) : 100}
+                      className={cn("h-3", summary.remaining < 0 && "[&>div]:bg-red-500")}
+                    />
+                    <div className="flex justify-between text-sm">
+                      <span>{formatMoney(summary.spent)} spent</span>
+                      <span className="text-muted-foreground">{formatMoney(summary.group.amount)} budget</span>
+                    </div>
+                    <p className="text-xs text-muted-foreground">
+                      {summary.categories
+                        .map((c) => `${c.categoryName} ${formatMoney(c.spent)}`)
+                        .join(" · ")}
+                    </p>
+                    <p
+                      className={cn(
+                        "text-sm font-medium",
+                        summary.remaining >= 0 ? "text-green-600" : "text-red-600"
+                      )}
+                    >
+                      {summary.remaining >= 0
+                        ? `${formatMoney(summary.remaining)} remaining`
+                        : `${formatMoney(Math.abs(summary.remaining))} over budget`}
+                    </p>
+                  </div>
+                </div>
+              ))}
+            </CardContent>
+          )}
+        </Card>
+
+        {income.length > 0 && (
+          <Card className="mt-6">
+            <CardHeader>
+              <CardTitle className="flex items-center gap-2">
+                <DollarSign className="h-5 w-5" />
+                Expected Income
+              </CardTitle>
+              <CardDescription>
+                {formatMoney(income.reduce((sum, s) => sum + s.received, 0))} of{" "}
+                {formatMoney(income.reduce((sum, s) => sum + s.expected, 0))} received this month
+              </CardDescription>
+            </CardHeader>
+            <CardContent className="space-y-4">
+              {income.map((summary) => (
+                <div
+                  key={summary.budget.id}
+                  className="p-4 border rounded-lg hover:bg-accent/50 transition-colors"
+                >
+                  <div className="flex items-center justify-between mb-2">
+                    <div className="flex items-center gap-3">
+                      <div
+                        className="h-4 w-4 rounded-full"
+                        style={{ backgroundColor: summary.category.color || "#6b7280" }}
+                      />
+                      <span className="font-medium">{summary.category.name}</span>
+                    </div>
+                    <div className="flex items-center gap-2">
+                      <Button variant="ghost" size="icon" onClick={() => handleEdit(summary.budget)}>
+                        <Pencil className="h-4 w-4" />
+                      </Button>
+                      <Button variant="ghost" size="icon" onClick={() => handleDelete(summary.budget)}>
+                        <Trash2 className="h-4 w-4" />
+                      </Button>
+                    </div>
+                  </div>
+                  <div className="space-y-2">
+                    <Progress
+                      value={summary.expected > 0 ? Math.min((summary.received / summary.expected) * 100, 100) : 100}
+                      className={cn("h-3", summary.outstanding === 0 && "[&>div]:bg-green-500")}
+                    />
+                    <div className="flex justify-between text-sm">
+                      <span>{formatMoney(summary.received)} received</span>
+                      <span className="text-muted-foreground">{formatMoney(summary.expected)} expected</span>
+                    </div>
+                    <p
+                      className={cn(
+                        "text-sm font-medium",
+                        summary.outstanding === 0 ? "text-green-600" : "text-muted-foreground"
+                      )}
+                    >
+                      {summary.outstanding === 0
+                        ? "All expected income has landed"
+                        : `${formatMoney(summary.outstanding)} still to come`}
+                    </p>
+                  </div>
+                </div>
+              ))}
+            </CardContent>
+          </Card>
+        )}
+
         {/* Create/Edit Dialog */}
         <Dialog open={formOpen} onOpenChange={setFormOpen}>
           <DialogContent>
             <DialogHeader>
               <DialogTitle>{selectedBudget ? "Edit Budget" : "Create Budget"}</DialogTitle>
               <DialogDescription>
-                Set a monthly spending limit for a category.
+                Set a spending limit for a category. Non-monthly budgets are prorated in the
+                monthly view.
               </DialogDescription>
             </DialogHeader>
 
@@ -391,19 +943,89 @@ export function Budgets() {
                   </SelectTrigger>
                   <SelectContent>
                     {(selectedBudget
-                      ? expenseCategories
+                      ? budgetCategories
                       : unusedCategories
                     ).map((category) => (
                       <SelectItem key={category.id} value={category.id}>
                         {category.name}
+                        {category.categoryType === "income" && " (income)"}
                       </SelectItem>
                     ))}
                   </SelectContent>
                 </Select>
               </div>
 
+              {!isIncomeForm && (
+              <div className="flex items-center gap-2">
+                <Switch
+                  checked={formData.isWatchOnly}
+                  onCheckedChange={(checked) => setFormData((p) => ({ ...p, isWatchOnly: checked }))}
+                />
+                <Label>Watch only (track spending without a limit)</Label>
+              </div>
+              )}
+
+              {categories.some((c) => c.parentId === formData.categoryId) && (
+                <div className="flex items-center gap-2">
+                  <Switch
+                    checked={formData.includeChildren}
+                    onCheckedChange={(checked) => setFormData((p) => ({ ...p, includeChildren: checked }))}
+                  />
+                  <Label>Include subcategory spending</Label>
+                </div>
+              )}
+
+              {!formData.isWatchOnly && (
+              <>
               <div className="space-y-2">
-                <Label>Monthly Amount</Label>
+                <Label>Period</Label>
+                <Select
+                  value={formData.periodType}
+                  onValueChange={(v) => setFormData((p) => ({ ...p, periodType: v as BudgetPeriod }))}
+                >
+                  <SelectTrigger>
+                    <SelectValue />
+                  </SelectTrigger>
+                  <SelectContent>
+                    {Object.entries(BUDGET_PERIODS).map(([value, { label }]) => (
+                      <SelectItem key={value} value={value}>
+                        {label}
+                      </SelectItem>
+                    ))}
+                  </SelectContent>
+                </Select>
+              </div>
+
+              {(formData.periodType === "custom" || formData.periodType === "biweekly") && (
+                <div className="grid grid-cols-2 gap-4">
+                  {formData.periodType === "custom" && (
+                    <div className="space-y-2">
+                      <Label>Period Length (days)</Label>
+                      <Input
+                        type="number"
+                        min="1"
+                        max="366"
+                        value={formData.periodDays}
+                        onChange={(e) => setFormData((p) => ({ ...p, periodDays: e.target.value }))}
+                      />
+                    </div>
+                  )}
+                  <div className="space-y-2">
+                    <Label>First Period Starts</Label>
+                    <Input
+                      type="date"
+                      value={formData.periodAnchor}
+                      onChange={(e) => setFormData((p) => ({ ...p, periodAnchor: e.target.value }))}
+                    />
+                  </div>
+                </div>
+              )}
+
+              <div className="space-y-2">
+                <Label>
+                  {isIncomeForm ? "Expected " : ""}
+                  {BUDGET_PERIODS[formData.periodType].label} Amount
+                </Label>
                 <div className="relative">
                   <span className="absolute left-3 top-1/2 -translate-y-1/2 text-muted-foreground">
                     $
@@ -419,6 +1041,7 @@ export function Budgets() {
                 </div>
               </div>
 
+              {!isIncomeForm && (
               <div className="flex items-center gap-2">
                 <Switch
                   checked={formData.rollover}
@@ -426,6 +1049,23 @@ export function Budgets() {
                 />
                 <Label>Roll over unused budget to next month</Label>
               </div>
+              )}
+
+              {!isIncomeForm && (
+                <div className="space-y-2">
+                  <Label>Alert Thresholds (%)</Label>
+                  <Input
+                    value={formData.alertThresholds}
+                    onChange={(e) => setFormData((p) => ({ ...p, alertThresholds: e.target.value }))}
+                    placeholder="80, 100"
+                  />
+                  <p className="text-xs text-muted-foreground">
+                    Get notified when spending reaches these percentages of the budget
+                  </p>
+                </div>
+              )}
+              </>
+              )}
             </div>
 
             <DialogFooter>
@@ -434,7 +1074,11 @@ export function Budgets() {
               </Button>
               <Button
                 onClick={handleSubmit}
-                disabled={!formData.categoryId || !formData.amount}
+                disabled={
+                  !formData.categoryId ||
+                  (!formData.isWatchOnly && !formData.amount) ||
+                  (!formData.isWatchOnly && formData.periodType === "custom" && !formData.periodDays)
+                }
               >
                 {selectedBudget ? "Update" : "Create"}
               </Button>
@@ -442,6 +1086,119 @@ export function Budgets() {
           </DialogContent>
         </Dialog>
 
+        {/* Shared Budget Dialog */}
+        <Dialog open={groupFormOpen} onOpenChange={setGroupFormOpen}>
+          <DialogContent>
+            <DialogHeader>
+              <DialogTitle>{selectedGroup ? "Edit Shared Budget" : "Create Shared Budget"}</DialogTitle>
+              <DialogDescription>
+                Spending in every chosen category counts against one monthly amount.
+              </DialogDescription>
+            </DialogHeader>
+
+            <div className="space-y-4 py-4">
+              <div className="space-y-2">
+                <Label>Name</Label>
+                <Input
+                  value={groupForm.name}
+                  onChange={(e) => setGroupForm((p) => ({ ...p, name: e.target.value }))}
+                  placeholder="Fun money"
+                />
+              </div>
+
+              <div className="space-y-2">
+                <Label>Monthly Amount</Label>
+                <div className="relative">
+                  <span className="absolute left-3 top-1/2 -translate-y-1/2 text-muted-foreground">
+                    $
+                  </span>
+                  <Input
+                    type="number"
+                    step="0.01"
+                    value={groupForm.amount}
+                    onChange={(e) => setGroupForm((p) => ({ ...p, amount: e.target.value }))}
+                    placeholder="0.00"
+                    className="pl-7"
+                  />
+                </div>
+              </div>
+
+              <div className="space-y-2">
+                <Label>Categories</Label>
+                <div className="max-h-48 overflow-y-auto space-y-2 border rounded-lg p-3">
+                  {budgetCategories
+                    .filter((c) => c.categoryType === "expense")
+                    .map((category) => (
+                      <div key={category.id} className="flex items-center gap-2">
+                        <Checkbox
+                          checked={groupForm.categoryIds.includes(category.id)}
+                          onCheckedChange={() => toggleGroupCategory(category.id)}
+                        />
+                        <Label className="font-normal">{category.name}</Label>
+                      </div>
+                    ))}
+                </div>
+              </div>
+
+              {groupError && <p className="text-sm text-red-600">{groupError}</p>}
+            </div>
+
+            <DialogFooter>
+              <Button variant="outline" onClick={() => setGroupFormOpen(false)}>
+                Cancel
+              </Button>
+              <Button
+                onClick={handleSubmitGroup}
+                disabled={!groupForm.name.trim() || !groupForm.amount || groupForm.categoryIds.length === 0}
+              >
+                {selectedGroup ? "Update" : "Create"}
+              </Button>
+            </DialogFooter>
+          </DialogContent>
+        </Dialog>
+
+        {/* Month Amount Dialog */}
+        <Dialog open={!!monthAmountSummary} onOpenChange={(open) => !open && setMonthAmountSummary(null)}>
+          <DialogContent>
+            <DialogHeader>
+              <DialogTitle>{monthAmountSummary?.category.name} in {formatMonthDisplay(currentMonth)}</DialogTitle>
+              <DialogDescription>
+                Budget a different amount for this month only. Other months keep the usual amount.
+              </DialogDescription>
+            </DialogHeader>
+
+            <div className="space-y-2 py-4">
+              <Label>Amount</Label>
+              <div className="relative">
+                <span className="absolute left-3 top-1/2 -translate-y-1/2 text-muted-foreground">
+                  $
+                </span>
+                <Input
+                  type="number"
+                  step="0.01"
+                  value={monthAmountInput}
+                  onChange={(e) => setMonthAmountInput(e.target.value)}
+                  className="pl-7"
+                />
+              </div>
+            </div>
+
+            <DialogFooter>
+              {monthAmountSummary?.isMonthAmount && (
+                <Button variant="outline" onClick={() => saveMonthAmount(null)}>
+                  Use Usual Amount
+                </Button>
+              )}
+              <Button
+                onClick={() => saveMonthAmount(Math.round(parseFloat(monthAmountInput) * 100))}
+                disabled={!monthAmountInput}
+              >
+                Save
+              </Button>
+            </DialogFooter>
+          </DialogContent>
+        </Dialog>
+
         {/* Delete Confirmation */}
         <AlertDialog open={deleteDialogOpen} onOpenChange={setDeleteDialogOpen}>
           <AlertDialogContent>
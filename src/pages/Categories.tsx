@@ -1,5 +1,5 @@
 import { useEffect, useState } from "react";
-import { Plus, Pencil, Trash2, ChevronRight, ChevronDown } from "lucide-react";
+import { Plus, Pencil, Trash2, ChevronRight, ChevronDown, Archive, ArchiveRestore } from "lucide-react";
 import { Card, CardContent, CardHeader, CardTitle, CardDescription } from "@/components/ui/card";
 import { Button } from "@/components/ui/button";
 import { Input } from "@/components/ui/input";
@@ -49,6 +49,7 @@ export function Categories() {
     createCategory,
     updateCategory,
     deleteCategory,
+    setCategoryArchived,
   } = useCategoryStore();
   const [loading, setLoading] = useState(true);
   const [formOpen, setFormOpen] = useState(false);
@@ -140,6 +141,14 @@ export function Categories() {
     setDeleteDialogOpen(false);
   };
 
+  const handleToggleArchived = async (category: Category) => {
+    try {
+      await setCategoryArchived(category.id, !category.isArchived);
+    } catch (err) {
+      console.error("Failed to archive category:", err);
+    }
+  };
+
   const handleSubmit = async () => {
     try {
       const data = {
@@ -192,9 +201,21 @@ export function Categories() {
               className="h-4 w-4 rounded-full shrink-0"
               style={{ backgroundColor: category.color || "#6b7280" }}
             />
-            <span className="font-medium truncate">{category.name}</span>
+            <span className={cn("font-medium truncate", category.isArchived && "text-muted-foreground")}>
+              {category.name}
+            </span>
+            {category.isArchived && <span className="text-xs text-muted-foreground">Archived</span>}
           </div>
           <div className="flex items-center gap-1 shrink-0">
+            <Button
+              variant="ghost"
+              size="icon"
+              className="h-8 w-8"
+              title={category.isArchived ? "Unarchive" : "Archive"}
+              onClick={() => handleToggleArchived(category)}
+            >
+              {category.isArchived ? <ArchiveRestore className="h-4 w-4" /> : <Archive className="h-4 w-4" />}
+            </Button>
             {!category.isSystem ? (
               <>
                 <Button variant="ghost" size="icon" className="h-8 w-8" onClick={() => handleCreate(category.id)}>
@@ -222,7 +243,7 @@ export function Categories() {
   };
 
   // Get top-level categories for parent selection
-  const topLevelCategories = categories.filter((c) => !c.parentId);
+  const topLevelCategories = categories.filter((c) => !c.parentId && !c.isArchived);
 
   return (
     <>
@@ -16,150 +16,106 @@ import { PageContainer } from "@/components/layout/PageContainer";
 import { CashFlowChart } from "@/components/charts/CashFlowChart";
 import { SpendingChart } from "@/components/charts/SpendingChart";
 import { NetWorthChart } from "@/components/charts/NetWorthChart";
-import { useAccountStore } from "@/stores/useAccountStore";
-import { useTransactionStore } from "@/stores/useTransactionStore";
 import { formatMoney, formatRelativeDate } from "@/lib/formatters";
 import { cn } from "@/lib/utils";
-import { useCategoryStore } from "@/stores/useCategoryStore";
-import type { CashFlowData, SpendingByCategory, NetWorthSnapshot } from "@/types";
+import * as api from "@/lib/tauri";
+import {
+  ACCOUNTS_CHANGED,
+  CATEGORIES_CHANGED,
+  RECURRING_CHANGED,
+  TRANSACTIONS_CHANGED,
+  onChanged,
+} from "@/lib/events";
+import type {
+  CashFlowData,
+  CreditCardStatus,
+  DashboardSummary,
+  NetWorthSnapshot,
+} from "@/types";
 
 export function Dashboard() {
-  const { accounts, fetchAccounts, getTotalAssets, getTotalLiabilities, getNetWorth } =
-    useAccountStore();
-  const { transactions, fetchTransactions } = useTransactionStore();
-  const { categories, fetchCategories } = useCategoryStore();
+  const [summary, setSummary] = useState<DashboardSummary | null>(null);
   const [cashFlowData, setCashFlowData] = useState<CashFlowData[]>([]);
-  const [spendingData, setSpendingData] = useState<SpendingByCategory[]>([]);
   const [netWorthHistory, setNetWorthHistory] = useState<NetWorthSnapshot[]>([]);
+  const [creditCardStatuses, setCreditCardStatuses] = useState<CreditCardStatus[]>([]);
 
+  // Balances, totals, this month's spending and the latest transactions all come from
+  // one summary call, reloaded whenever anything it covers changes
   useEffect(() => {
-    fetchAccounts();
-    fetchTransactions();
-    fetchCategories();
-  }, [fetchAccounts, fetchTransactions, fetchCategories]);
-
-  // Compute chart data from transactions
-  useEffect(() => {
-    if (transactions.length === 0) return;
-
-    // Get last 6 months
-    const now = new Date();
-    const months: string[] = [];
-    for (let i = 5; i >= 0; i--) {
-      const d = new Date(now.getFullYear(), now.getMonth() - i, 1);
-      months.push(d.toISOString().slice(0, 7)); // "YYYY-MM"
-    }
-
-    // Compute cash flow by month
-    const cashFlowMap = new Map<string, { income: number; expenses: number }>();
-    months.forEach((m) => cashFlowMap.set(m, { income: 0, expenses: 0 }));
+    const loadSummary = () => {
+      api.getDashboardSummary().then(setSummary).catch(console.error);
+    };
+    loadSummary();
+    const subscriptions = [
+      ACCOUNTS_CHANGED,
+      TRANSACTIONS_CHANGED,
+      CATEGORIES_CHANGED,
+      RECURRING_CHANGED,
+    ].map((event) => onChanged(event, loadSummary));
+    return () => {
+      subscriptions.forEach((subscription) => subscription.then((unsubscribe) => unsubscribe()));
+    };
+  }, []);
 
-    transactions.forEach((tx) => {
-      const month = tx.date.slice(0, 7);
-      if (cashFlowMap.has(month)) {
-        const entry = cashFlowMap.get(month)!;
-        if (tx.amount >= 0) {
-          entry.income += tx.amount;
-        } else {
-          entry.expenses += tx.amount;
-        }
-      }
-    });
+  const accounts = summary?.accounts ?? [];
+  const baseCurrency = summary?.baseCurrency ?? "USD";
+  const missingFxCurrencies = summary?.missingFxCurrencies ?? [];
+  const totalAssets = summary?.totalAssets ?? 0;
+  const totalLiabilities = summary?.totalLiabilities ?? 0;
+  const netWorth = summary?.netWorth ?? 0;
+  const netWorthByGroup = summary?.accountGroups ?? [];
+  const spendingData = summary?.monthToDateSpending ?? [];
+  const recentTransactions = summary?.recentTransactions ?? [];
 
-    const cashFlow: CashFlowData[] = months.map((period) => {
-      const entry = cashFlowMap.get(period)!;
-      return {
-        period,
-        income: entry.income,
-        expenses: entry.expenses,
-        net: entry.income + entry.expenses,
-      };
-    });
-    setCashFlowData(cashFlow);
+  const creditCardWarnings = creditCardStatuses.filter(
+    (card) => card.isOverdue || card.isDueSoon || card.isHighUtilization
+  );
 
-    // Compute spending by category (last 6 months)
+  // Cash flow for the last 6 months, and the month-end snapshots taken in the background
+  // on unlock with today's totals as the last point
+  useEffect(() => {
+    if (!summary) return;
+    const now = new Date();
+    const today = now.toISOString().split("T")[0] as string;
     const sixMonthsAgo = new Date(now.getFullYear(), now.getMonth() - 5, 1)
       .toISOString()
       .split("T")[0] as string;
-    const spendingMap = new Map<string, number>();
-
-    transactions
-      .filter((tx) => tx.date >= sixMonthsAgo && tx.amount < 0)
-      .forEach((tx) => {
-        const catId = tx.categoryId || "uncategorized";
-        spendingMap.set(catId, (spendingMap.get(catId) || 0) + Math.abs(tx.amount));
-      });
-
-    const totalSpending = Array.from(spendingMap.values()).reduce((a, b) => a + b, 0);
-    const spending: SpendingByCategory[] = Array.from(spendingMap.entries())
-      .map(([categoryId, amount]) => {
-        const cat = categories.find((c) => c.id === categoryId);
-        return {
-          categoryId,
-          categoryName: cat?.name || "Uncategorized",
-          amount,
-          percentage: totalSpending > 0 ? (amount / totalSpending) * 100 : 0,
-          color: cat?.color || null,
-        };
-      })
-      .sort((a, b) => b.amount - a.amount);
-    setSpendingData(spending);
-
-    // Compute net worth history by month
-    // Start with current balances and work backwards
-    const currentNetWorth = accounts.reduce((sum, a) => sum + a.currentBalance, 0);
-    const currentAssets = accounts
-      .filter((a) => ["checking", "savings", "investment", "cash"].includes(a.accountType))
-      .reduce((sum, a) => sum + a.currentBalance, 0);
-    const currentLiabilities = accounts
-      .filter((a) => ["credit_card", "loan"].includes(a.accountType))
-      .reduce((sum, a) => sum + Math.abs(a.currentBalance), 0);
-
-    // Group transactions by month to calculate deltas
-    const monthlyDeltas = new Map<string, number>();
-    transactions.forEach((tx) => {
-      const month = tx.date.slice(0, 7);
-      monthlyDeltas.set(month, (monthlyDeltas.get(month) || 0) + tx.amount);
-    });
-
-    // Build history working backwards from current
-    const netWorthHistoryData: NetWorthSnapshot[] = [];
-    let runningNetWorth = currentNetWorth;
-
-    // Start from current month and go back
-    for (let i = 0; i <= 5; i++) {
-      const d = new Date(now.getFullYear(), now.getMonth() - i, 1);
-      const month = d.toISOString().slice(0, 7);
-      const snapshotDate = `${month}-01`;
-
-      netWorthHistoryData.unshift({
-        id: month,
-        snapshotDate,
-        totalAssets: currentAssets, // Simplified - would need per-account tracking for accuracy
-        totalLiabilities: currentLiabilities,
-        netWorth: runningNetWorth,
-        createdAt: snapshotDate,
-      });
-
-      // Subtract this month's transactions to get previous month's balance
-      const delta = monthlyDeltas.get(month) || 0;
-      runningNetWorth -= delta;
-    }
-
-    setNetWorthHistory(netWorthHistoryData);
-  }, [transactions, accounts, categories]);
-
-  const totalAssets = getTotalAssets();
-  const totalLiabilities = getTotalLiabilities();
-  const netWorth = getNetWorth();
+    const yearAgo = new Date(now.getFullYear(), now.getMonth() - 12, 1)
+      .toISOString()
+      .split("T")[0] as string;
 
-  const recentTransactions = transactions.slice(0, 10);
+    api.getCashFlow(sixMonthsAgo, today, "month").then(setCashFlowData).catch(console.error);
+    api.getCreditCardStatuses().then(setCreditCardStatuses).catch(console.error);
+    api
+      .listNetWorthSnapshots(yearAgo, today)
+      .then((snapshots) =>
+        setNetWorthHistory([
+          ...snapshots.filter((s) => s.snapshotDate < today),
+          {
+            id: "current",
+            snapshotDate: today,
+            totalAssets: summary.totalAssets,
+            totalLiabilities: summary.totalLiabilities,
+            netWorth: summary.netWorth,
+            createdAt: today,
+          },
+        ])
+      )
+      .catch(console.error);
+  }, [summary]);
 
   return (
     <>
       <Header title="Dashboard" />
       <PageContainer>
         <div className="space-y-6">
+          {missingFxCurrencies.length > 0 && (
+            <p className="text-sm text-amber-600">
+              No exchange rate to {baseCurrency} for {missingFxCurrencies.join(", ")}. Those
+              balances are counted unconverted until a rate is added in Settings.
+            </p>
+          )}
+
           {/* Net Worth Cards */}
           <div className="grid gap-4 md:grid-cols-3">
             <Card>
@@ -171,7 +127,7 @@ export function Dashboard() {
               </CardHeader>
               <CardContent>
                 <div className="text-2xl font-bold text-green-600">
-                  {formatMoney(totalAssets)}
+                  {formatMoney(totalAssets, baseCurrency)}
                 </div>
                 <p className="text-xs text-muted-foreground mt-1">
                   Checking, savings, investments
@@ -188,7 +144,7 @@ export function Dashboard() {
               </CardHeader>
               <CardContent>
                 <div className="text-2xl font-bold text-red-600">
-                  {formatMoney(totalLiabilities)}
+                  {formatMoney(totalLiabilities, baseCurrency)}
                 </div>
                 <p className="text-xs text-muted-foreground mt-1">
                   Credit cards, loans
@@ -210,7 +166,7 @@ export function Dashboard() {
                     netWorth >= 0 ? "text-green-600" : "text-red-600"
                   )}
                 >
-                  {formatMoney(netWorth)}
+                  {formatMoney(netWorth, baseCurrency)}
                 </div>
                 <p className="text-xs text-muted-foreground mt-1">
                   Assets minus liabilities
@@ -219,6 +175,93 @@ export function Dashboard() {
             </Card>
           </div>
 
+          {/* Credit cards that are overdue, due soon or running a high balance */}
+          {creditCardWarnings.length > 0 && (
+            <Card>
+              <CardHeader>
+                <CardTitle className="text-base">Credit Card Alerts</CardTitle>
+              </CardHeader>
+              <CardContent>
+                <div className="space-y-3">
+                  {creditCardWarnings.map((card) => (
+                    <div key={card.accountId} className="flex items-center justify-between">
+                      <div>
+                        <p className="font-medium">{card.accountName}</p>
+                        <p className="text-xs text-muted-foreground">
+                          {card.isOverdue
+                            ? `Payment overdue by ${-(card.daysUntilDue ?? 0)} days`
+                            : card.isDueSoon
+                              ? card.daysUntilDue === 0
+                                ? "Payment due today"
+                                : `Payment due in ${card.daysUntilDue} days`
+                              : `Statement closes ${card.nextStatementDate ?? "monthly"}`}
+                          {card.minimumPayment != null &&
+                            (card.isOverdue || card.isDueSoon) &&
+                            ` · ${formatMoney(card.minimumPayment)} minimum`}
+                        </p>
+                      </div>
+                      <div className="text-right">
+                        <p
+                          className={cn(
+                            "font-semibold",
+                            card.isOverdue && "text-red-600"
+                          )}
+                        >
+                          {formatMoney(card.statementBalance ?? card.balanceOwed)}
+                        </p>
+                        {card.utilization != null && (
+                          <p
+                            className={cn(
+                              "text-xs",
+                              card.isHighUtilization ? "text-red-600" : "text-muted-foreground"
+                            )}
+                          >
+                            {card.utilization.toFixed(0)}% utilization
+                          </p>
+                        )}
+                      </div>
+                    </div>
+                  ))}
+                </div>
+              </CardContent>
+            </Card>
+          )}
+
+          {/* Net Worth by Group, once the user has made groups */}
+          {netWorthByGroup.some((g) => g.groupId !== null) && (
+            <Card>
+              <CardHeader>
+                <CardTitle className="text-base">Net Worth by Group</CardTitle>
+              </CardHeader>
+              <CardContent>
+                <div className="space-y-3">
+                  {netWorthByGroup.map((group) => (
+                    <div
+                      key={group.groupId ?? "ungrouped"}
+                      className="flex items-center justify-between"
+                    >
+                      <div>
+                        <p className="font-medium">{group.name}</p>
+                        <p className="text-xs text-muted-foreground">
+                          {formatMoney(group.totalAssets, baseCurrency)} assets ·{" "}
+                          {formatMoney(group.totalLiabilities, baseCurrency)} liabilities
+                        </p>
+                      </div>
+                      <span
+                        className={cn(
+                          "font-semibold",
+                          group.netWorth >= 0 ? "text-green-600" : "text-red-600"
+                        )}
+                      >
+                        {formatMoney(group.netWorth, baseCurrency)}
+                      </span>
+                    </div>
+                  ))}
+                </div>
+              </CardContent>
+            </Card>
+          )}
+
           {/* Net Worth Chart */}
           <Card>
             <CardHeader>
@@ -246,7 +289,7 @@ export function Dashboard() {
 
             <Card>
               <CardHeader>
-                <CardTitle className="text-base">Spending by Category</CardTitle>
+                <CardTitle className="text-base">Spending This Month</CardTitle>
               </CardHeader>
               <CardContent>
                 <div className="h-[250px]">
@@ -308,7 +351,7 @@ export function Dashboard() {
                                     : "text-foreground"
                                 )}
                               >
-                                {formatMoney(account.currentBalance)}
+                                {formatMoney(account.currentBalance, account.currency)}
                               </span>
                             </Link>
                           ))
@@ -1,10 +1,30 @@
 import { useEffect, useState } from "react";
-import { Plus, Pencil, Trash2, Flag, Check, DollarSign } from "lucide-react";
+import {
+  Plus,
+  Pencil,
+  Trash2,
+  Flag,
+  Check,
+  DollarSign,
+  History,
+  Repeat,
+  ListOrdered,
+  PartyPopper,
+  X,
+  Coins,
+  ChevronUp,
+  ChevronDown,
+  Split,
+  Archive,
+  ArchiveRestore,
+} from "lucide-react";
 import { Card, CardContent, CardHeader, CardTitle, CardDescription } from "@/components/ui/card";
 import { Button } from "@/components/ui/button";
 import { Input } from "@/components/ui/input";
 import { Label } from "@/components/ui/label";
 import { Progress } from "@/components/ui/progress";
+import { Checkbox } from "@/components/ui/checkbox";
+import { Switch } from "@/components/ui/switch";
 import { Badge } from "@/components/ui/badge";
 import {
   Select,
@@ -40,10 +60,38 @@ import {
   updateGoal,
   deleteGoal,
   contributeToGoal,
+  listGoalContributions,
+  deleteGoalContribution,
+  listGoalContributionSchedules,
+  setGoalContributionSchedule,
+  deleteGoalContributionSchedule,
+  getGoalProjection,
+  getDebtPayoffPlan,
+  listGoalMilestones,
+  getRoundUpSettings,
+  setRoundUpSettings,
+  reorderGoals,
+  previewSurplusAllocation,
+  allocateSurplus,
+  archiveGoal,
+  listArchivedGoals,
+  restoreGoal,
 } from "@/lib/tauri";
-import { formatMoney } from "@/lib/formatters";
+import { onGoalMilestone } from "@/lib/events";
+import { formatDate, formatMoney } from "@/lib/formatters";
 import { cn } from "@/lib/utils";
-import type { Goal } from "@/types";
+import type {
+  AllocationStrategy,
+  DebtPayoffPlan,
+  Goal,
+  GoalContribution,
+  GoalContributionSchedule,
+  GoalMilestone,
+  GoalProjection,
+  PayoffStrategy,
+  RoundUpSettings,
+  SurplusPlan,
+} from "@/types";
 
 const GOAL_TYPES = [
   { value: "savings", label: "Savings Goal" },
@@ -51,6 +99,31 @@ const GOAL_TYPES = [
   { value: "spending_limit", label: "Spending Limit" },
 ];
 
+const PAYOFF_STRATEGIES: { value: PayoffStrategy; label: string }[] = [
+  { value: "avalanche", label: "Avalanche (highest interest first)" },
+  { value: "snowball", label: "Snowball (smallest balance first)" },
+];
+
+const DEBT_ACCOUNT_TYPES = ["credit_card", "loan"];
+
+const formatMonth = (month: string) =>
+  new Date(`${month}-01T00:00:00`).toLocaleDateString("en-US", { month: "short", year: "numeric" });
+
+const SCHEDULE_FREQUENCIES: { value: GoalContributionSchedule["frequency"]; label: string }[] = [
+  { value: "weekly", label: "Weekly" },
+  { value: "biweekly", label: "Every 2 weeks" },
+  { value: "monthly", label: "Monthly" },
+  { value: "quarterly", label: "Quarterly" },
+  { value: "yearly", label: "Yearly" },
+];
+
+const DEFAULT_MILESTONES = "25, 50, 75, 100";
+
+const ALLOCATION_STRATEGIES: { value: AllocationStrategy; label: string }[] = [
+  { value: "priority", label: "By priority (fill goals in order)" },
+  { value: "weighted", label: "By weight (share across goals)" },
+];
+
 const GOAL_COLORS = [
   "#ef4444", "#f97316", "#f59e0b", "#84cc16", "#22c55e",
   "#14b8a6", "#06b6d4", "#3b82f6", "#6366f1", "#8b5cf6",
@@ -58,8 +131,10 @@ const GOAL_COLORS = [
 ];
 
 export function Goals() {
-  const { accounts, fetchAccounts } = useAccountStore();
+  const { accounts, fetchAccounts, getOpenAccounts } = useAccountStore();
   const [goals, setGoals] = useState<Goal[]>([]);
+  const [archivedGoals, setArchivedGoals] = useState<Goal[]>([]);
+  const [projections, setProjections] = useState<Record<string, GoalProjection>>({});
   const [loading, setLoading] = useState(true);
   const [formOpen, setFormOpen] = useState(false);
   const [contributeOpen, setContributeOpen] = useState(false);
@@ -74,21 +149,83 @@ export function Goals() {
     currentAmount: "",
     targetDate: "",
     linkedAccountId: "",
+    contributionAccountId: "",
     color: GOAL_COLORS[0],
+    payoffStrategy: "avalanche" as PayoffStrategy,
+    monthlyPayment: "",
+    // Minimum payment entered for each selected debt account
+    debts: {} as Record<string, string>,
+    milestones: DEFAULT_MILESTONES,
+    allocationWeight: "1",
   });
+  const [payoffPlans, setPayoffPlans] = useState<Record<string, DebtPayoffPlan>>({});
+  const [planGoal, setPlanGoal] = useState<Goal | null>(null);
 
   // Contribution state
   const [contributionAmount, setContributionAmount] = useState("");
+  const [historyGoal, setHistoryGoal] = useState<Goal | null>(null);
+  const [contributions, setContributions] = useState<GoalContribution[]>([]);
+  const [schedules, setSchedules] = useState<Record<string, GoalContributionSchedule>>({});
+  const [scheduleGoal, setScheduleGoal] = useState<Goal | null>(null);
+  const [scheduleForm, setScheduleForm] = useState({
+    amount: "",
+    frequency: "monthly" as GoalContributionSchedule["frequency"],
+    startDate: "",
+    fromAccountId: "",
+  });
+  const [scheduleError, setScheduleError] = useState<string | null>(null);
+  const [reachedMilestones, setReachedMilestones] = useState<GoalMilestone[]>([]);
+  // Milestones reached while the page is open, shown until dismissed
+  const [celebrations, setCelebrations] = useState<GoalMilestone[]>([]);
+  const [roundUps, setRoundUps] = useState<RoundUpSettings | null>(null);
+  const [roundUpOpen, setRoundUpOpen] = useState(false);
+  const [roundUpForm, setRoundUpForm] = useState({
+    isEnabled: false,
+    goalId: "",
+    accountIds: [] as string[],
+    frequency: "weekly" as RoundUpSettings["frequency"],
+  });
+  const [roundUpError, setRoundUpError] = useState<string | null>(null);
+  const [surplusOpen, setSurplusOpen] = useState(false);
+  const [surplusAmount, setSurplusAmount] = useState("");
+  const [surplusStrategy, setSurplusStrategy] = useState<AllocationStrategy>("priority");
+  const [surplusPlan, setSurplusPlan] = useState<SurplusPlan | null>(null);
 
   useEffect(() => {
     fetchAccounts();
     loadGoals();
   }, [fetchAccounts]);
 
+  useEffect(() => {
+    const unlisten = onGoalMilestone((milestones) =>
+      setCelebrations((prev) => [...milestones, ...prev])
+    );
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
   const loadGoals = async () => {
     try {
       const data = await listGoals();
       setGoals(data);
+      const active = data.filter((g) => !g.isAchieved);
+      const results = await Promise.all(active.map((g) => getGoalProjection(g.id)));
+      setProjections(Object.fromEntries(results.map((p) => [p.goalId, p])));
+      const goalSchedules = await listGoalContributionSchedules();
+      setSchedules(Object.fromEntries(goalSchedules.map((s) => [s.goalId, s])));
+      setReachedMilestones(await listGoalMilestones());
+      setRoundUps(await getRoundUpSettings());
+      setArchivedGoals(await listArchivedGoals());
+      // Debt goals without any debts yet have no plan
+      const plans = await Promise.all(
+        active
+          .filter((g) => g.goalType === "debt_payoff")
+          .map((g) => getDebtPayoffPlan(g.id).catch(() => null))
+      );
+      setPayoffPlans(
+        Object.fromEntries(plans.filter((p) => p !== null).map((p) => [p.goalId, p]))
+      );
     } catch (err) {
       console.error("Failed to load goals:", err);
     } finally {
@@ -105,7 +242,13 @@ export function Goals() {
       currentAmount: "0",
       targetDate: "",
       linkedAccountId: "",
+      contributionAccountId: "",
       color: GOAL_COLORS[Math.floor(Math.random() * GOAL_COLORS.length)],
+      payoffStrategy: "avalanche",
+      monthlyPayment: "",
+      debts: {},
+      milestones: DEFAULT_MILESTONES,
+      allocationWeight: "1",
     });
     setFormOpen(true);
   };
@@ -119,7 +262,15 @@ export function Goals() {
       currentAmount: String(goal.currentAmount / 100),
       targetDate: goal.targetDate || "",
       linkedAccountId: goal.linkedAccountId || "",
+      contributionAccountId: goal.contributionAccountId || "",
       color: goal.color || GOAL_COLORS[0],
+      payoffStrategy: goal.payoffStrategy || "avalanche",
+      monthlyPayment: goal.monthlyPayment ? String(goal.monthlyPayment / 100) : "",
+      debts: Object.fromEntries(
+        (payoffPlans[goal.id]?.debts ?? []).map((d) => [d.accountId, String(d.minimumPayment / 100)])
+      ),
+      milestones: goal.milestones.join(", "),
+      allocationWeight: String(goal.allocationWeight),
     });
     setFormOpen(true);
   };
@@ -155,7 +306,23 @@ export function Goals() {
         currentAmount: Math.round(parseFloat(formData.currentAmount || "0") * 100),
         targetDate: formData.targetDate || null,
         linkedAccountId: formData.linkedAccountId || null,
+        contributionAccountId: formData.contributionAccountId || null,
         color: formData.color,
+        milestones: formData.milestones
+          .split(",")
+          .map((m) => parseInt(m.trim(), 10))
+          .filter((m) => !isNaN(m)),
+        allocationWeight: parseInt(formData.allocationWeight, 10) || 1,
+        ...(formData.goalType === "debt_payoff" && {
+          payoffStrategy: formData.payoffStrategy,
+          monthlyPayment: formData.monthlyPayment
+            ? Math.round(parseFloat(formData.monthlyPayment) * 100)
+            : null,
+          debts: Object.entries(formData.debts).map(([accountId, minimum]) => ({
+            accountId,
+            minimumPayment: Math.round(parseFloat(minimum || "0") * 100),
+          })),
+        }),
       };
 
       if (selectedGoal) {
@@ -182,6 +349,164 @@ export function Goals() {
     }
   };
 
+  const handleShowHistory = async (goal: Goal) => {
+    setHistoryGoal(goal);
+    try {
+      setContributions(await listGoalContributions(goal.id));
+    } catch (err) {
+      console.error("Failed to load contributions:", err);
+    }
+  };
+
+  const handleDeleteContribution = async (contribution: GoalContribution) => {
+    try {
+      await deleteGoalContribution(contribution.id);
+      setContributions((prev) => prev.filter((c) => c.id !== contribution.id));
+      await loadGoals();
+    } catch (err) {
+      console.error("Failed to delete contribution:", err);
+    }
+  };
+
+  const handleSchedule = (goal: Goal) => {
+    const schedule = schedules[goal.id];
+    setScheduleGoal(goal);
+    setScheduleForm({
+      amount: schedule ? String(schedule.amount / 100) : "",
+      frequency: schedule?.frequency || "monthly",
+      startDate: schedule?.startDate || new Date().toISOString().slice(0, 10),
+      fromAccountId: schedule?.fromAccountId || "",
+    });
+    setScheduleError(null);
+  };
+
+  const handleScheduleSubmit = async () => {
+    if (!scheduleGoal) return;
+    try {
+      await setGoalContributionSchedule(scheduleGoal.id, {
+        amount: Math.round(parseFloat(scheduleForm.amount) * 100),
+        frequency: scheduleForm.frequency,
+        startDate: scheduleForm.startDate,
+        fromAccountId: scheduleForm.fromAccountId || null,
+      });
+      await loadGoals();
+      setScheduleGoal(null);
+    } catch (err) {
+      setScheduleError(String(err));
+    }
+  };
+
+  const handleScheduleDelete = async () => {
+    if (!scheduleGoal) return;
+    try {
+      await deleteGoalContributionSchedule(scheduleGoal.id);
+      await loadGoals();
+      setScheduleGoal(null);
+    } catch (err) {
+      setScheduleError(String(err));
+    }
+  };
+
+  const toggleDebt = (accountId: string) => {
+    setFormData((p) => {
+      const debts = { ...p.debts };
+      if (accountId in debts) {
+        delete debts[accountId];
+      } else {
+        debts[accountId] = "";
+      }
+      return { ...p, debts };
+    });
+  };
+
+  const handleRoundUps = () => {
+    setRoundUpForm({
+      isEnabled: roundUps?.isEnabled ?? false,
+      goalId: roundUps?.goalId || "",
+      accountIds: roundUps?.accountIds ?? [],
+      frequency: roundUps?.frequency || "weekly",
+    });
+    setRoundUpError(null);
+    setRoundUpOpen(true);
+  };
+
+  const toggleRoundUpAccount = (accountId: string) => {
+    setRoundUpForm((p) => ({
+      ...p,
+      accountIds: p.accountIds.includes(accountId)
+        ? p.accountIds.filter((id) => id !== accountId)
+        : [...p.accountIds, accountId],
+    }));
+  };
+
+  const handleRoundUpSubmit = async () => {
+    try {
+      setRoundUps(
+        await setRoundUpSettings({
+          isEnabled: roundUpForm.isEnabled,
+          goalId: roundUpForm.goalId || null,
+          accountIds: roundUpForm.accountIds,
+          frequency: roundUpForm.frequency,
+        })
+      );
+      setRoundUpOpen(false);
+    } catch (err) {
+      setRoundUpError(String(err));
+    }
+  };
+
+  const handleArchive = async (goal: Goal) => {
+    try {
+      await archiveGoal(goal.id);
+      await loadGoals();
+    } catch (err) {
+      console.error("Failed to archive goal:", err);
+    }
+  };
+
+  const handleRestore = async (goal: Goal) => {
+    try {
+      await restoreGoal(goal.id);
+      await loadGoals();
+    } catch (err) {
+      console.error("Failed to restore goal:", err);
+    }
+  };
+
+  const handleMoveGoal = async (index: number, offset: number) => {
+    const ordered = activeGoals.map((g) => g.id);
+    const [moved] = ordered.splice(index, 1);
+    ordered.splice(index + offset, 0, moved!);
+    try {
+      await reorderGoals(ordered);
+      await loadGoals();
+    } catch (err) {
+      console.error("Failed to reorder goals:", err);
+    }
+  };
+
+  useEffect(() => {
+    const amount = Math.round(parseFloat(surplusAmount) * 100);
+    if (!surplusOpen || !(amount > 0)) {
+      setSurplusPlan(null);
+      return;
+    }
+    previewSurplusAllocation(amount, surplusStrategy)
+      .then(setSurplusPlan)
+      .catch((err) => console.error("Failed to preview allocation:", err));
+  }, [surplusOpen, surplusAmount, surplusStrategy]);
+
+  const handleAllocateSurplus = async () => {
+    try {
+      await allocateSurplus(Math.round(parseFloat(surplusAmount) * 100), surplusStrategy);
+      await loadGoals();
+      setSurplusOpen(false);
+      setSurplusAmount("");
+    } catch (err) {
+      console.error("Failed to allocate surplus:", err);
+    }
+  };
+
   const activeGoals = goals.filter((g) => !g.isAchieved);
   const achievedGoals = goals.filter((g) => g.isAchieved);
 
@@ -198,13 +523,45 @@ export function Goals() {
       <Header
         title="Goals"
         actions={
-          <Button onClick={handleCreate}>
-            <Plus className="h-4 w-4 mr-2" />
-            Add Goal
-          </Button>
+          <div className="flex gap-2">
+            <Button variant="outline" onClick={() => setSurplusOpen(true)}>
+              <Split className="h-4 w-4 mr-2" />
+              Allocate Surplus
+            </Button>
+            <Button variant="outline" onClick={handleRoundUps}>
+              <Coins className="h-4 w-4 mr-2" />
+              Round-ups
+            </Button>
+            <Button onClick={handleCreate}>
+              <Plus className="h-4 w-4 mr-2" />
+              Add Goal
+            </Button>
+          </div>
         }
       />
       <PageContainer>
+        {celebrations.length > 0 && (
+          <Card className="mb-6 border-green-500">
+            <CardContent className="flex items-start justify-between gap-4 pt-6">
+              <div className="flex items-start gap-3">
+                <PartyPopper className="h-6 w-6 text-green-600 shrink-0" />
+                <div className="space-y-1">
+                  {celebrations.map((m) => (
+                    <p key={`${m.goalId}-${m.percent}-${m.reachedAt}`} className="font-medium">
+                      {m.percent === 100
+                        ? `${m.goalName} reached its target of ${formatMoney(m.targetAmount)}!`
+                        : `${m.goalName} is ${m.percent}% of the way there`}
+                    </p>
+                  ))}
+                </div>
+              </div>
+              <Button variant="ghost" size="icon" onClick={() => setCelebrations([])}>
+                <X className="h-4 w-4" />
+              </Button>
+            </CardContent>
+          </Card>
+        )}
+
         {/* Summary */}
         <div className="grid gap-4 md:grid-cols-3 mb-6">
           <Card>
@@ -270,13 +627,14 @@ export function Goals() {
               </div>
             ) : (
               <div className="grid gap-4 md:grid-cols-2 lg:grid-cols-3">
-                {activeGoals.map((goal) => {
+                {activeGoals.map((goal, index) => {
                   const percentComplete = Math.min(
                     (goal.currentAmount / goal.targetAmount) * 100,
                     100
                   );
                   const remaining = goal.targetAmount - goal.currentAmount;
                   const daysRemaining = calculateDaysRemaining(goal.targetDate);
+                  const projection = projections[goal.id];
 
                   return (
                     <Card key={goal.id} className="relative overflow-hidden">
@@ -287,9 +645,29 @@ export function Goals() {
                       <CardHeader className="pb-2">
                         <div className="flex items-center justify-between">
                           <CardTitle className="text-lg">{goal.name}</CardTitle>
-                          <Badge variant="outline">
-                            {GOAL_TYPES.find((t) => t.value === goal.goalType)?.label}
-                          </Badge>
+                          <div className="flex items-center gap-1">
+                            <Badge variant="outline">
+                              {GOAL_TYPES.find((t) => t.value === goal.goalType)?.label}
+                            </Badge>
+                            <Button
+                              variant="ghost"
+                              size="icon"
+                              className="h-6 w-6"
+                              disabled={index === 0}
+                              onClick={() => handleMoveGoal(index, -1)}
+                            >
+                              <ChevronUp className="h-4 w-4" />
+                            </Button>
+                            <Button
+                              variant="ghost"
+                              size="icon"
+                              className="h-6 w-6"
+                              disabled={index === activeGoals.length - 1}
+                              onClick={() => handleMoveGoal(index, 1)}
+                            >
+                              <ChevronDown className="h-4 w-4" />
+                            </Button>
+                          </div>
                         </div>
                         {goal.targetDate && (
                           <CardDescription>
@@ -319,6 +697,72 @@ export function Goals() {
                           <p className="text-sm text-muted-foreground mt-1">
                             {formatMoney(remaining)} to go ({percentComplete.toFixed(0)}%)
                           </p>
+                          {goal.milestones.length > 0 && (
+                            <div className="flex flex-wrap gap-1 my-1">
+                              {goal.milestones.map((percent) => {
+                                const reached = reachedMilestones.find(
+                                  (m) => m.goalId === goal.id && m.percent === percent
+                                );
+                                return (
+                                  <Badge
+                                    key={percent}
+                                    variant={reached ? "default" : "outline"}
+                                    title={reached ? `Reached ${formatDate(reached.reachedAt.slice(0, 10))}` : undefined}
+                                  >
+                                    {percent}%
+                                  </Badge>
+                                );
+                              })}
+                            </div>
+                          )}
+                          {projection?.requiredMonthlyContribution != null &&
+                            projection.requiredMonthlyContribution > 0 && (
+                              <p
+                                className={cn(
+                                  "text-sm",
+                                  projection.requiredMonthlyContribution > projection.monthlyContribution
+                                    ? "text-amber-600"
+                                    : "text-muted-foreground"
+                                )}
+                              >
+                                {formatMoney(projection.requiredMonthlyContribution)}/month needed
+                                to reach it by {formatDate(goal.targetDate!)}
+                              </p>
+                            )}
+                          {payoffPlans[goal.id] && (
+                            <p className="text-sm text-muted-foreground">
+                              {payoffPlans[goal.id]!.payoffMonth
+                                ? `Debt-free by ${formatMonth(payoffPlans[goal.id]!.payoffMonth!)}`
+                                : "Payments don't keep up with interest"}
+                              {payoffPlans[goal.id]!.interestSaved > 0 &&
+                                `, saving ${formatMoney(payoffPlans[goal.id]!.interestSaved)} in interest`}
+                            </p>
+                          )}
+                          {schedules[goal.id]?.isActive && (
+                            <p className="text-sm text-muted-foreground">
+                              {formatMoney(schedules[goal.id]!.amount)}{" "}
+                              {SCHEDULE_FREQUENCIES.find(
+                                (f) => f.value === schedules[goal.id]!.frequency
+                              )?.label.toLowerCase()}
+                              , next on {formatDate(schedules[goal.id]!.nextDate)}
+                            </p>
+                          )}
+                          {roundUps?.isEnabled && roundUps.goalId === goal.id && (
+                            <p className="text-sm text-muted-foreground">
+                              {formatMoney(roundUps.pendingAmount)} in round-ups, added on{" "}
+                              {formatDate(roundUps.nextDate)}
+                            </p>
+                          )}
+                          {projection?.projectedDate && (
+                            <p className="text-sm text-muted-foreground">
+                              Projected {formatDate(projection.projectedDate)}
+                              {projection.apy !== null &&
+                                projection.linearProjectedDate !== projection.projectedDate &&
+                                (projection.linearProjectedDate
+                                  ? ` (${formatDate(projection.linearProjectedDate)} without interest)`
+                                  : " with interest")}
+                            </p>
+                          )}
                         </div>
 
                         <div className="flex justify-between">
@@ -331,6 +775,29 @@ export function Goals() {
                             Add Funds
                           </Button>
                           <div className="flex gap-1">
+                            <Button
+                              variant="ghost"
+                              size="icon"
+                              onClick={() => handleShowHistory(goal)}
+                            >
+                              <History className="h-4 w-4" />
+                            </Button>
+                            <Button
+                              variant="ghost"
+                              size="icon"
+                              onClick={() => handleSchedule(goal)}
+                            >
+                              <Repeat className="h-4 w-4" />
+                            </Button>
+                            {payoffPlans[goal.id] && (
+                              <Button
+                                variant="ghost"
+                                size="icon"
+                                onClick={() => setPlanGoal(goal)}
+                              >
+                                <ListOrdered className="h-4 w-4" />
+                              </Button>
+                            )}
                             <Button
                               variant="ghost"
                               size="icon"
@@ -338,6 +805,13 @@ export function Goals() {
                             >
                               <Pencil className="h-4 w-4" />
                             </Button>
+                            <Button
+                              variant="ghost"
+                              size="icon"
+                              onClick={() => handleArchive(goal)}
+                            >
+                              <Archive className="h-4 w-4" />
+                            </Button>
                             <Button
                               variant="ghost"
                               size="icon"
@@ -382,8 +856,51 @@ export function Goals() {
                         </p>
                       </div>
                     </div>
-                    <Button variant="ghost" size="icon" onClick={() => handleDelete(goal)}>
-                      <Trash2 className="h-4 w-4" />
+                    <div className="flex gap-1">
+                      <Button variant="ghost" size="icon" onClick={() => handleArchive(goal)}>
+                        <Archive className="h-4 w-4" />
+                      </Button>
+                      <Button variant="ghost" size="icon" onClick={() => handleDelete(goal)}>
+                        <Trash2 className="h-4 w-4" />
+                      </Button>
+                    </div>
+                  </div>
+                ))}
+              </div>
+            </CardContent>
+          </Card>
+        )}
+
+        {/* Archived Goals */}
+        {archivedGoals.length > 0 && (
+          <Card className="mt-6">
+            <CardHeader>
+              <CardTitle className="flex items-center gap-2 text-muted-foreground">
+                <Archive className="h-5 w-5" />
+                Archived Goals
+              </CardTitle>
+              <CardDescription>
+                Put away with their contribution history. Restore one to track it again.
+              </CardDescription>
+            </CardHeader>
+            <CardContent>
+              <div className="space-y-3">
+                {archivedGoals.map((goal) => (
+                  <div
+                    key={goal.id}
+                    className="flex items-center justify-between p-3 border rounded-lg"
+                  >
+                    <div>
+                      <p className="font-medium">{goal.name}</p>
+                      <p className="text-sm text-muted-foreground">
+                        {formatMoney(goal.currentAmount)} of {formatMoney(goal.targetAmount)}
+                        {goal.archivedAt &&
+                          `, archived ${new Date(goal.archivedAt).toLocaleDateString()}`}
+                      </p>
+                    </div>
+                    <Button variant="outline" size="sm" onClick={() => handleRestore(goal)}>
+                      <ArchiveRestore className="h-4 w-4 mr-1" />
+                      Restore
                     </Button>
                   </div>
                 ))}
@@ -494,7 +1011,7 @@ export function Goals() {
                   </SelectTrigger>
                   <SelectContent>
                     <SelectItem value="none">No linked account</SelectItem>
-                    {accounts.map((account) => (
+                    {getOpenAccounts(formData.linkedAccountId).map((account) => (
                       <SelectItem key={account.id} value={account.id}>
                         {account.name}
                       </SelectItem>
@@ -503,6 +1020,134 @@ export function Goals() {
                 </Select>
               </div>
 
+              <div className="space-y-2">
+                <Label>Count Transfers Into (optional)</Label>
+                <Select
+                  value={formData.contributionAccountId}
+                  onValueChange={(v) =>
+                    setFormData((p) => ({ ...p, contributionAccountId: v === "none" ? "" : v }))
+                  }
+                >
+                  <SelectTrigger>
+                    <SelectValue placeholder="Manual contributions only" />
+                  </SelectTrigger>
+                  <SelectContent>
+                    <SelectItem value="none">Manual contributions only</SelectItem>
+                    {getOpenAccounts(formData.contributionAccountId).map((account) => (
+                      <SelectItem key={account.id} value={account.id}>
+                        {account.name}
+                      </SelectItem>
+                    ))}
+                  </SelectContent>
+                </Select>
+                <p className="text-sm text-muted-foreground">
+                  Imported transfers into this account are added to the goal automatically.
+                </p>
+              </div>
+
+              {formData.goalType === "debt_payoff" && (
+                <>
+                  <div className="space-y-2">
+                    <Label>Debts</Label>
+                    <div className="space-y-2 border rounded-lg p-3">
+                      {accounts
+                        .filter((a) => DEBT_ACCOUNT_TYPES.includes(a.accountType))
+                        .map((account) => (
+                          <div key={account.id} className="flex items-center gap-2">
+                            <Checkbox
+                              checked={account.id in formData.debts}
+                              onCheckedChange={() => toggleDebt(account.id)}
+                            />
+                            <Label className="flex-1 font-normal">{account.name}</Label>
+                            {account.id in formData.debts && (
+                              <Input
+                                type="number"
+                                step="0.01"
+                                value={formData.debts[account.id]}
+                                onChange={(e) =>
+                                  setFormData((p) => ({
+                                    ...p,
+                                    debts: { ...p.debts, [account.id]: e.target.value },
+                                  }))
+                                }
+                                placeholder="Minimum"
+                                className="w-28"
+                              />
+                            )}
+                          </div>
+                        ))}
+                    </div>
+                    <p className="text-sm text-muted-foreground">
+                      Leave a minimum blank to assume interest plus 1% of the balance.
+                    </p>
+                  </div>
+
+                  <div className="grid gap-4 sm:grid-cols-2">
+                    <div className="space-y-2">
+                      <Label>Strategy</Label>
+                      <Select
+                        value={formData.payoffStrategy}
+                        onValueChange={(v) =>
+                          setFormData((p) => ({ ...p, payoffStrategy: v as PayoffStrategy }))
+                        }
+                      >
+                        <SelectTrigger>
+                          <SelectValue />
+                        </SelectTrigger>
+                        <SelectContent>
+                          {PAYOFF_STRATEGIES.map((strategy) => (
+                            <SelectItem key={strategy.value} value={strategy.value}>
+                              {strategy.label}
+                            </SelectItem>
+                          ))}
+                        </SelectContent>
+                      </Select>
+                    </div>
+                    <div className="space-y-2">
+                      <Label>Monthly Payment</Label>
+                      <div className="relative">
+                        <span className="absolute left-3 top-1/2 -translate-y-1/2 text-muted-foreground">
+                          $
+                        </span>
+                        <Input
+                          type="number"
+                          step="0.01"
+                          value={formData.monthlyPayment}
+                          onChange={(e) =>
+                            setFormData((p) => ({ ...p, monthlyPayment: e.target.value }))
+                          }
+                          placeholder="Minimums only"
+                          className="pl-7"
+                        />
+                      </div>
+                    </div>
+                  </div>
+                </>
+              )}
+
+              <div className="grid grid-cols-2 gap-4">
+                <div className="space-y-2">
+                  <Label>Milestones (%)</Label>
+                  <Input
+                    value={formData.milestones}
+                    onChange={(e) => setFormData((p) => ({ ...p, milestones: e.target.value }))}
+                    placeholder="None"
+                  />
+                </div>
+                <div className="space-y-2">
+                  <Label>Surplus Weight</Label>
+                  <Input
+                    type="number"
+                    min="1"
+                    step="1"
+                    value={formData.allocationWeight}
+                    onChange={(e) =>
+                      setFormData((p) => ({ ...p, allocationWeight: e.target.value }))
+                    }
+                  />
+                </div>
+              </div>
+
               <div className="space-y-2">
                 <Label>Color</Label>
                 <div className="flex flex-wrap gap-2">
@@ -584,6 +1229,405 @@ export function Goals() {
           </DialogContent>
         </Dialog>
 
+        {/* Debt Payoff Plan */}
+        <Dialog open={!!planGoal} onOpenChange={(open) => !open && setPlanGoal(null)}>
+          <DialogContent className="max-w-2xl">
+            <DialogHeader>
+              <DialogTitle>Payoff Plan</DialogTitle>
+              <DialogDescription>
+                {planGoal && payoffPlans[planGoal.id] && (
+                  <>
+                    {formatMoney(payoffPlans[planGoal.id]!.monthlyPayment)} a month, paid down{" "}
+                    {payoffPlans[planGoal.id]!.strategy === "snowball"
+                      ? "smallest balance first"
+                      : "highest interest first"}
+                    . {formatMoney(payoffPlans[planGoal.id]!.totalInterest)} in interest against{" "}
+                    {formatMoney(payoffPlans[planGoal.id]!.minimumOnlyInterest)} paying minimums only.
+                  </>
+                )}
+              </DialogDescription>
+            </DialogHeader>
+
+            {planGoal && payoffPlans[planGoal.id] && (
+              <div className="space-y-4 py-4">
+                <div className="space-y-2">
+                  {payoffPlans[planGoal.id]!.debts.map((debt, i) => (
+                    <div
+                      key={debt.accountId}
+                      className="flex items-center justify-between p-2 border rounded-lg text-sm"
+                    >
+                      <span className="font-medium">
+                        {i + 1}. {debt.accountName}
+                      </span>
+                      <span className="text-muted-foreground">
+                        {formatMoney(debt.startingBalance)} at {debt.interestRate}% ·{" "}
+                        {debt.payoffMonth ? `paid off ${formatMonth(debt.payoffMonth)}` : "not paid off"}
+                      </span>
+                    </div>
+                  ))}
+                </div>
+
+                <div className="max-h-64 overflow-y-auto border rounded-lg">
+                  <table className="w-full text-sm">
+                    <thead className="sticky top-0 bg-background">
+                      <tr className="border-b text-muted-foreground">
+                        <th className="text-left p-2">Month</th>
+                        <th className="text-right p-2">Paid</th>
+                        <th className="text-right p-2">Interest</th>
+                        <th className="text-right p-2">Remaining</th>
+                      </tr>
+                    </thead>
+                    <tbody>
+                      {payoffPlans[planGoal.id]!.months.map((month) => (
+                        <tr key={month.month} className="border-b last:border-0">
+                          <td className="p-2">{formatMonth(month.month)}</td>
+                          <td className="text-right p-2">
+                            {formatMoney(month.payments.reduce((sum, p) => sum + p.payment, 0))}
+                          </td>
+                          <td className="text-right p-2">
+                            {formatMoney(month.payments.reduce((sum, p) => sum + p.interest, 0))}
+                          </td>
+                          <td className="text-right p-2">{formatMoney(month.totalBalance)}</td>
+                        </tr>
+                      ))}
+                    </tbody>
+                  </table>
+                </div>
+              </div>
+            )}
+          </DialogContent>
+        </Dialog>
+
+        {/* Contribution Schedule */}
+        <Dialog open={!!scheduleGoal} onOpenChange={(open) => !open && setScheduleGoal(null)}>
+          <DialogContent>
+            <DialogHeader>
+              <DialogTitle>Automatic Contributions</DialogTitle>
+              <DialogDescription>
+                Contribute to &quot;{scheduleGoal?.name}&quot; on a schedule. Due contributions are
+                made when the app is unlocked.
+              </DialogDescription>
+            </DialogHeader>
+
+            <div className="space-y-4 py-4">
+              <div className="grid grid-cols-2 gap-4">
+                <div className="space-y-2">
+                  <Label>Amount</Label>
+                  <div className="relative">
+                    <span className="absolute left-3 top-1/2 -translate-y-1/2 text-muted-foreground">
+                      $
+                    </span>
+                    <Input
+                      type="number"
+                      step="0.01"
+                      value={scheduleForm.amount}
+                      onChange={(e) => setScheduleForm((p) => ({ ...p, amount: e.target.value }))}
+                      placeholder="0.00"
+                      className="pl-7"
+                    />
+                  </div>
+                </div>
+                <div className="space-y-2">
+                  <Label>Frequency</Label>
+                  <Select
+                    value={scheduleForm.frequency}
+                    onValueChange={(v) =>
+                      setScheduleForm((p) => ({
+                        ...p,
+                        frequency: v as GoalContributionSchedule["frequency"],
+                      }))
+                    }
+                  >
+                    <SelectTrigger>
+                      <SelectValue />
+                    </SelectTrigger>
+                    <SelectContent>
+                      {SCHEDULE_FREQUENCIES.map((f) => (
+                        <SelectItem key={f.value} value={f.value}>
+                          {f.label}
+                        </SelectItem>
+                      ))}
+                    </SelectContent>
+                  </Select>
+                </div>
+              </div>
+
+              <div className="space-y-2">
+                <Label>Starting</Label>
+                <Input
+                  type="date"
+                  value={scheduleForm.startDate}
+                  onChange={(e) => setScheduleForm((p) => ({ ...p, startDate: e.target.value }))}
+                />
+              </div>
+
+              <div className="space-y-2">
+                <Label>Transfer From (optional)</Label>
+                <Select
+                  value={scheduleForm.fromAccountId}
+                  onValueChange={(v) =>
+                    setScheduleForm((p) => ({ ...p, fromAccountId: v === "none" ? "" : v }))
+                  }
+                >
+                  <SelectTrigger>
+                    <SelectValue placeholder="Don't record a transfer" />
+                  </SelectTrigger>
+                  <SelectContent>
+                    <SelectItem value="none">Don&apos;t record a transfer</SelectItem>
+                    {getOpenAccounts(scheduleForm.fromAccountId).map((account) => (
+                      <SelectItem key={account.id} value={account.id}>
+                        {account.name}
+                      </SelectItem>
+                    ))}
+                  </SelectContent>
+                </Select>
+                <p className="text-xs text-muted-foreground">
+                  Each contribution is recorded as a transfer into the goal&apos;s account
+                </p>
+              </div>
+
+              {scheduleError && <p className="text-sm text-red-600">{scheduleError}</p>}
+            </div>
+
+            <DialogFooter>
+              {scheduleGoal && schedules[scheduleGoal.id] && (
+                <Button variant="outline" onClick={handleScheduleDelete}>
+                  Stop Contributions
+                </Button>
+              )}
+              <Button onClick={handleScheduleSubmit} disabled={!scheduleForm.amount}>
+                Save
+              </Button>
+            </DialogFooter>
+          </DialogContent>
+        </Dialog>
+
+        {/* Surplus Allocation */}
+        <Dialog open={surplusOpen} onOpenChange={setSurplusOpen}>
+          <DialogContent>
+            <DialogHeader>
+              <DialogTitle>Allocate Surplus</DialogTitle>
+              <DialogDescription>
+                Split a lump sum across your active goals and add it to each in one step. Goal
+                order sets priority; weights are set on each goal.
+              </DialogDescription>
+            </DialogHeader>
+
+            <div className="space-y-4 py-4">
+              <div className="grid grid-cols-2 gap-4">
+                <div className="space-y-2">
+                  <Label>Amount</Label>
+                  <div className="relative">
+                    <span className="absolute left-3 top-1/2 -translate-y-1/2 text-muted-foreground">
+                      $
+                    </span>
+                    <Input
+                      type="number"
+                      step="0.01"
+                      value={surplusAmount}
+                      onChange={(e) => setSurplusAmount(e.target.value)}
+                      placeholder="0.00"
+                      className="pl-7"
+                    />
+                  </div>
+                </div>
+                <div className="space-y-2">
+                  <Label>Split</Label>
+                  <Select
+                    value={surplusStrategy}
+                    onValueChange={(v) => setSurplusStrategy(v as AllocationStrategy)}
+                  >
+                    <SelectTrigger>
+                      <SelectValue />
+                    </SelectTrigger>
+                    <SelectContent>
+                      {ALLOCATION_STRATEGIES.map((s) => (
+                        <SelectItem key={s.value} value={s.value}>
+                          {s.label}
+                        </SelectItem>
+                      ))}
+                    </SelectContent>
+                  </Select>
+                </div>
+              </div>
+
+              {surplusPlan && (
+                <div className="space-y-2">
+                  {surplusPlan.allocations.length === 0 ? (
+                    <p className="text-sm text-muted-foreground">No goals need funding</p>
+                  ) : (
+                    surplusPlan.allocations.map((allocation) => (
+                      <div key={allocation.goalId} className="flex justify-between text-sm">
+                        <span>{allocation.goalName}</span>
+                        <span className="font-medium">{formatMoney(allocation.amount)}</span>
+                      </div>
+                    ))
+                  )}
+                  {surplusPlan.unallocated > 0 && (
+                    <p className="text-sm text-muted-foreground">
+                      {formatMoney(surplusPlan.unallocated)} left over once every goal is funded
+                    </p>
+                  )}
+                </div>
+              )}
+            </div>
+
+            <DialogFooter>
+              <Button variant="outline" onClick={() => setSurplusOpen(false)}>
+                Cancel
+              </Button>
+              <Button
+                onClick={handleAllocateSurplus}
+                disabled={!surplusPlan || surplusPlan.allocations.length === 0}
+              >
+                Allocate
+              </Button>
+            </DialogFooter>
+          </DialogContent>
+        </Dialog>
+
+        {/* Round-up Savings */}
+        <Dialog open={roundUpOpen} onOpenChange={setRoundUpOpen}>
+          <DialogContent>
+            <DialogHeader>
+              <DialogTitle>Round-up Savings</DialogTitle>
+              <DialogDescription>
+                Round spending up to the next dollar and add the difference to a goal. Round-ups
+                are set aside as transactions come in and contributed on a schedule.
+              </DialogDescription>
+            </DialogHeader>
+
+            <div className="space-y-4 py-4">
+              <div className="flex items-center gap-2">
+                <Switch
+                  checked={roundUpForm.isEnabled}
+                  onCheckedChange={(checked) =>
+                    setRoundUpForm((p) => ({ ...p, isEnabled: checked }))
+                  }
+                />
+                <Label>Round up spending</Label>
+              </div>
+
+              <div className="space-y-2">
+                <Label>Goal</Label>
+                <Select
+                  value={roundUpForm.goalId}
+                  onValueChange={(v) => setRoundUpForm((p) => ({ ...p, goalId: v }))}
+                >
+                  <SelectTrigger>
+                    <SelectValue placeholder="Choose a goal" />
+                  </SelectTrigger>
+                  <SelectContent>
+                    {activeGoals.map((goal) => (
+                      <SelectItem key={goal.id} value={goal.id}>
+                        {goal.name}
+                      </SelectItem>
+                    ))}
+                  </SelectContent>
+                </Select>
+              </div>
+
+              <div className="space-y-2">
+                <Label>Accounts to Round Up</Label>
+                <div className="max-h-40 overflow-y-auto space-y-2">
+                  {accounts
+                    .filter((a) => !a.closedOn || roundUpForm.accountIds.includes(a.id))
+                    .map((account) => (
+                      <div key={account.id} className="flex items-center gap-2">
+                        <Checkbox
+                          checked={roundUpForm.accountIds.includes(account.id)}
+                          onCheckedChange={() => toggleRoundUpAccount(account.id)}
+                        />
+                        <Label className="font-normal">{account.name}</Label>
+                      </div>
+                    ))}
+                </div>
+              </div>
+
+              <div className="space-y-2">
+                <Label>Contribute</Label>
+                <Select
+                  value={roundUpForm.frequency}
+                  onValueChange={(v) =>
+                    setRoundUpForm((p) => ({
+                      ...p,
+                      frequency: v as RoundUpSettings["frequency"],
+                    }))
+                  }
+                >
+                  <SelectTrigger>
+                    <SelectValue />
+                  </SelectTrigger>
+                  <SelectContent>
+                    {SCHEDULE_FREQUENCIES.map((f) => (
+                      <SelectItem key={f.value} value={f.value}>
+                        {f.label}
+                      </SelectItem>
+                    ))}
+                  </SelectContent>
+                </Select>
+              </div>
+
+              {roundUps && roundUps.pendingCount > 0 && (
+                <p className="text-sm text-muted-foreground">
+                  {formatMoney(roundUps.pendingAmount)} set aside from {roundUps.pendingCount}{" "}
+                  {roundUps.pendingCount === 1 ? "transaction" : "transactions"}
+                </p>
+              )}
+
+              {roundUpError && <p className="text-sm text-red-600">{roundUpError}</p>}
+            </div>
+
+            <DialogFooter>
+              <Button variant="outline" onClick={() => setRoundUpOpen(false)}>
+                Cancel
+              </Button>
+              <Button onClick={handleRoundUpSubmit}>Save</Button>
+            </DialogFooter>
+          </DialogContent>
+        </Dialog>
+
+        {/* Contribution History */}
+        <Dialog open={!!historyGoal} onOpenChange={(open) => !open && setHistoryGoal(null)}>
+          <DialogContent>
+            <DialogHeader>
+              <DialogTitle>Contributions</DialogTitle>
+              <DialogDescription>
+                Everything added to &quot;{historyGoal?.name}&quot;. Deleting a contribution takes
+                it back off the goal.
+              </DialogDescription>
+            </DialogHeader>
+
+            <div className="max-h-80 overflow-y-auto py-4 space-y-2">
+              {contributions.length === 0 ? (
+                <p className="text-sm text-muted-foreground">No contributions yet</p>
+              ) : (
+                contributions.map((contribution) => (
+                  <div
+                    key={contribution.id}
+                    className="flex items-center justify-between p-2 border rounded-lg"
+                  >
+                    <div>
+                      <p className="font-medium">{formatMoney(contribution.amount)}</p>
+                      <p className="text-sm text-muted-foreground">
+                        {formatDate(contribution.date)}
+                        {contribution.transactionId && " · from a transfer"}
+                      </p>
+                    </div>
+                    <Button
+                      variant="ghost"
+                      size="icon"
+                      onClick={() => handleDeleteContribution(contribution)}
+                    >
+                      <Trash2 className="h-4 w-4" />
+                    </Button>
+                  </div>
+                ))
+              )}
+            </div>
+          </DialogContent>
+        </Dialog>
+
         {/* Delete Confirmation */}
         <AlertDialog open={deleteDialogOpen} onOpenChange={setDeleteDialogOpen}>
           <AlertDialogContent>
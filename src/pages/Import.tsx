@@ -28,6 +28,7 @@ import {
   previewCsvFile,
   parseCsvFile,
   importTransactions,
+  findPreviousImport,
   previewBoaFile,
   parseBoaFile,
   previewPdfFile,
@@ -38,7 +39,7 @@ import {
   type BoaPreview,
   type PdfPreview,
 } from "@/lib/tauri";
-import { formatMoney } from "@/lib/formatters";
+import { formatDate, formatMoney } from "@/lib/formatters";
 import { cn } from "@/lib/utils";
 
 type Step = "upload" | "mapping" | "preview" | "complete";
@@ -47,7 +48,7 @@ type FileType = "csv" | "boa" | "pdf";
 
 export function Import() {
   const navigate = useNavigate();
-  const { accounts, fetchAccounts } = useAccountStore();
+  const { accounts, fetchAccounts, getOpenAccounts } = useAccountStore();
   const [step, setStep] = useState<Step>("upload");
   const [filePath, setFilePath] = useState<string | null>(null);
   const [fileName, setFileName] = useState<string | null>(null);
@@ -69,7 +70,7 @@ export function Import() {
   const [parsedTransactions, setParsedTransactions] = useState<ParsedTransaction[]>([]);
   const [selectedTransactions, setSelectedTransactions] = useState<Set<number>>(new Set());
   const [lastClickedIndex, setLastClickedIndex] = useState<number | null>(null);
-  const [importResult, setImportResult] = useState<{ imported: number; skipped: number; categorized: number } | null>(null);
+  const [importResult, setImportResult] = useState<{ imported: number; skipped: number; categorized: number; errors: string[] } | null>(null);
   const [error, setError] = useState<string | null>(null);
   const [loading, setLoading] = useState(false);
 
@@ -88,6 +89,18 @@ export function Import() {
       });
 
       if (selected && typeof selected === "string") {
+        // Same file contents as an earlier import: ask before going through it again
+        const previous = await findPreviousImport(selected).catch(() => null);
+        if (
+          previous &&
+          !confirm(
+            `This file was already imported on ${formatDate(previous.importedAt.slice(0, 10))}` +
+              `${previous.accountName ? ` into ${previous.accountName}` : ""}. Re-run anyway?`
+          )
+        ) {
+          return;
+        }
+
         setFilePath(selected);
         const name = selected.split("/").pop() || selected;
         setFileName(name);
@@ -262,15 +275,15 @@ export function Import() {
           pdfCategory: tx.categoryHint,
         }));
 
-      const result = await importTransactions(accountId, transactionsToImport);
-      setImportResult({ imported: result.imported, skipped: result.skipped, categorized: result.categorized });
+      const result = await importTransactions(accountId, transactionsToImport, filePath ?? undefined);
+      setImportResult({ imported: result.imported, skipped: result.skipped, categorized: result.categorized, errors: result.errors });
       setStep("complete");
     } catch (err) {
       setError(String(err));
     } finally {
       setLoading(false);
     }
-  }, [accountId, parsedTransactions, selectedTransactions]);
+  }, [accountId, parsedTransactions, selectedTransactions, filePath]);
 
   const toggleTransaction = (index: number, event?: React.MouseEvent) => {
     if (event?.shiftKey && lastClickedIndex !== null) {
@@ -407,7 +420,7 @@ export function Import() {
                     <SelectValue placeholder="Select an account" />
                   </SelectTrigger>
                   <SelectContent>
-                    {accounts.map((account) => (
+                    {getOpenAccounts(accountId).map((account) => (
                       <SelectItem key={account.id} value={account.id}>
                         {account.name}
                       </SelectItem>
@@ -661,7 +674,7 @@ export function Import() {
                     <SelectValue placeholder="Select an account" />
                   </SelectTrigger>
                   <SelectContent>
-                    {accounts.map((account) => (
+                    {getOpenAccounts(accountId).map((account) => (
                       <SelectItem key={account.id} value={account.id}>
                         {account.name}
                       </SelectItem>
@@ -750,7 +763,7 @@ export function Import() {
                     <SelectValue placeholder="Select an account" />
                   </SelectTrigger>
                   <SelectContent>
-                    {accounts.map((account) => (
+                    {getOpenAccounts(accountId).map((account) => (
                       <SelectItem key={account.id} value={account.id}>
                         {account.name}
                       </SelectItem>
@@ -882,6 +895,13 @@ export function Import() {
                 Successfully imported {importResult.imported} transactions
                 {importResult.skipped > 0 && ` (${importResult.skipped} duplicates skipped)`}
               </p>
+              {importResult.errors.length > 0 && (
+                <div className="text-sm text-red-600 mb-6">
+                  {importResult.errors.map((error) => (
+                    <p key={error}>{error}</p>
+                  ))}
+                </div>
+              )}
               <div className="flex gap-3 justify-center">
                 <Button variant="outline" onClick={() => {
                   setStep("upload");
@@ -1,5 +1,14 @@
 import { useEffect, useState, useMemo } from "react";
-import { TrendingUp, TrendingDown, PieChart, RefreshCw } from "lucide-react";
+import {
+  TrendingUp,
+  TrendingDown,
+  PieChart,
+  RefreshCw,
+  Plus,
+  Pencil,
+  Trash2,
+  ArrowRightLeft,
+} from "lucide-react";
 import {
   PieChart as RechartsPie,
   Pie,
@@ -11,6 +20,23 @@ import {
 import { Card, CardContent, CardHeader, CardTitle, CardDescription } from "@/components/ui/card";
 import { Button } from "@/components/ui/button";
 import { Badge } from "@/components/ui/badge";
+import {
+  Dialog,
+  DialogContent,
+  DialogDescription,
+  DialogFooter,
+  DialogHeader,
+  DialogTitle,
+} from "@/components/ui/dialog";
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/components/ui/select";
 import {
   Table,
   TableBody,
@@ -22,24 +48,19 @@ import {
 import { Header } from "@/components/layout/Header";
 import { PageContainer } from "@/components/layout/PageContainer";
 import { useAccountStore } from "@/stores/useAccountStore";
-import { listHoldings } from "@/lib/tauri";
-import { formatMoney } from "@/lib/formatters";
+import * as api from "@/lib/tauri";
+import { formatDate, formatMoney, parseMoney } from "@/lib/formatters";
 import { cn } from "@/lib/utils";
-
-interface InvestmentHolding {
-  id: string;
-  accountId: string;
-  accountName: string;
-  symbol: string;
-  name: string | null;
-  securityType: string | null;
-  quantity: number;
-  currentPrice: number | null;
-  costBasis: number | null;
-  marketValue: number;
-  gainLoss: number | null;
-  gainLossPercent: number | null;
-}
+import type { AccountType } from "@/lib/constants";
+import type {
+  CostBasisMethod,
+  Holding,
+  InvestmentSummary,
+  InvestmentTransaction,
+  InvestmentTransactionType,
+  Security,
+  SecurityType,
+} from "@/types";
 
 const COLORS = [
   "#0088FE", "#00C49F", "#FFBB28", "#FF8042", "#8884D8",
@@ -55,10 +76,56 @@ const SECURITY_TYPE_LABELS: Record<string, string> = {
   other: "Other",
 };
 
+const INVESTMENT_TRANSACTION_LABELS: Record<InvestmentTransactionType, string> = {
+  buy: "Buy",
+  sell: "Sell",
+  dividend: "Dividend",
+  split: "Split",
+  fee: "Fee",
+};
+
+const emptyTradeForm = () => ({
+  accountId: "",
+  transactionType: "buy" as InvestmentTransactionType,
+  date: new Date().toISOString().split("T")[0] as string,
+  securityId: "none",
+  quantity: "",
+  price: "",
+  amount: "",
+  fees: "",
+  notes: "",
+});
+
+const INVESTMENT_ACCOUNT_TYPES: AccountType[] = ["investment", "retirement"];
+
+const EMPTY_HOLDING_FORM = {
+  accountId: "",
+  symbol: "",
+  name: "",
+  securityType: "stock" as SecurityType,
+  price: "",
+  quantity: "",
+  costBasis: "",
+  acquisitionDate: "",
+};
+
 export function Investments() {
-  const { accounts, fetchAccounts } = useAccountStore();
-  const [holdings, setHoldings] = useState<InvestmentHolding[]>([]);
+  const { accounts, fetchAccounts, getOpenAccounts } = useAccountStore();
+  const [holdings, setHoldings] = useState<Holding[]>([]);
+  const [securities, setSecurities] = useState<Security[]>([]);
   const [loading, setLoading] = useState(true);
+  const [isDialogOpen, setIsDialogOpen] = useState(false);
+  const [editingHolding, setEditingHolding] = useState<Holding | null>(null);
+  const [formData, setFormData] = useState(EMPTY_HOLDING_FORM);
+  const [formError, setFormError] = useState<string | null>(null);
+  const [investmentTransactions, setInvestmentTransactions] = useState<InvestmentTransaction[]>(
+    []
+  );
+  const [isTradeOpen, setIsTradeOpen] = useState(false);
+  const [tradeForm, setTradeForm] = useState(emptyTradeForm);
+  const [tradeError, setTradeError] = useState<string | null>(null);
+  const [activityError, setActivityError] = useState<string | null>(null);
+  const [summary, setSummary] = useState<InvestmentSummary | null>(null);
 
   useEffect(() => {
     fetchAccounts();
@@ -67,8 +134,16 @@ export function Investments() {
 
   const loadHoldings = async () => {
     try {
-      const data = await listHoldings();
-      setHoldings(data as unknown as InvestmentHolding[]);
+      const [holdingData, securityData, transactionData, summaryData] = await Promise.all([
+        api.listHoldings(),
+        api.listSecurities(),
+        api.listInvestmentTransactions(),
+        api.getInvestmentSummary(),
+      ]);
+      setHoldings(holdingData);
+      setSecurities(securityData);
+      setInvestmentTransactions(transactionData);
+      setSummary(summaryData);
     } catch (err) {
       console.error("Failed to load holdings:", err);
     } finally {
@@ -76,17 +151,139 @@ export function Investments() {
     }
   };
 
+  // A symbol already on file is reused; a new one is created along with the holding
+  const existingSecurity = securities.find(
+    (s) => s.symbol === formData.symbol.trim().toUpperCase()
+  );
+
+  const handleOpenDialog = (holding?: Holding) => {
+    setEditingHolding(holding ?? null);
+    setFormError(null);
+    setFormData(
+      holding
+        ? {
+            ...EMPTY_HOLDING_FORM,
+            accountId: holding.accountId,
+            symbol: holding.symbol,
+            price: holding.currentPrice != null ? (holding.currentPrice / 100).toFixed(2) : "",
+            quantity: holding.quantity.toString(),
+            costBasis: holding.costBasis != null ? (holding.costBasis / 100).toFixed(2) : "",
+            acquisitionDate: holding.acquisitionDate ?? "",
+          }
+        : EMPTY_HOLDING_FORM
+    );
+    setIsDialogOpen(true);
+  };
+
+  const handleSubmit = async (e: React.FormEvent) => {
+    e.preventDefault();
+    const price = formData.price ? parseMoney(formData.price) : null;
+    const details = {
+      quantity: parseFloat(formData.quantity) || 0,
+      costBasis: formData.costBasis ? parseMoney(formData.costBasis) : null,
+      acquisitionDate: formData.acquisitionDate || null,
+    };
+    try {
+      if (editingHolding) {
+        await api.updateHolding(editingHolding.id, details);
+        if (price !== editingHolding.currentPrice) {
+          await api.updateSecurity(editingHolding.securityId, { currentPrice: price });
+        }
+      } else {
+        const security =
+          existingSecurity ??
+          (await api.createSecurity({
+            symbol: formData.symbol,
+            name: formData.name.trim() || null,
+            securityType: formData.securityType,
+            currentPrice: price,
+          }));
+        await api.createHolding({
+          accountId: formData.accountId,
+          securityId: security.id,
+          ...details,
+        });
+      }
+      setIsDialogOpen(false);
+      loadHoldings();
+    } catch (err) {
+      setFormError(String(err));
+    }
+  };
+
+  const handleDelete = async (holding: Holding) => {
+    if (confirm(`Remove ${holding.symbol} from ${holding.accountName}?`)) {
+      await api.deleteHolding(holding.id);
+      loadHoldings();
+    }
+  };
+
+  const openTradeDialog = () => {
+    setTradeForm(emptyTradeForm());
+    setTradeError(null);
+    setIsTradeOpen(true);
+  };
+
+  // Trades and splits are in units of a security; dividends and fees are a cash amount
+  const tradesUnits = ["buy", "sell", "split"].includes(tradeForm.transactionType);
+  const isTrade = tradeForm.transactionType === "buy" || tradeForm.transactionType === "sell";
+
+  const handleTradeSubmit = async (e: React.FormEvent) => {
+    e.preventDefault();
+    try {
+      await api.createInvestmentTransaction({
+        accountId: tradeForm.accountId,
+        transactionType: tradeForm.transactionType,
+        date: tradeForm.date,
+        securityId: tradeForm.securityId === "none" ? null : tradeForm.securityId,
+        quantity: tradesUnits ? parseFloat(tradeForm.quantity) || 0 : null,
+        pricePerUnit: isTrade ? parseMoney(tradeForm.price) : null,
+        totalAmount: tradesUnits ? null : parseMoney(tradeForm.amount),
+        fees: tradeForm.fees ? parseMoney(tradeForm.fees) : 0,
+        notes: tradeForm.notes || null,
+      });
+      setIsTradeOpen(false);
+      loadHoldings();
+    } catch (err) {
+      setTradeError(String(err));
+    }
+  };
+
+  const handleDeleteTransaction = async (transaction: InvestmentTransaction) => {
+    if (confirm("Delete this transaction? Its change to the holding and cash is undone.")) {
+      try {
+        await api.deleteInvestmentTransaction(transaction.id);
+        setActivityError(null);
+        loadHoldings();
+      } catch (err) {
+        setActivityError(String(err));
+      }
+    }
+  };
+
+  // Applies to sales recorded from now on; earlier sales keep the cost basis they took
+  const handleCostBasisMethodChange = async (method: CostBasisMethod) => {
+    try {
+      await api.setSetting("cost_basis_method", method);
+      setActivityError(null);
+      await loadHoldings();
+    } catch (err) {
+      setActivityError(String(err));
+    }
+  };
+
   // Calculate totals
   const totals = useMemo(() => {
     const totalValue = holdings.reduce((sum, h) => sum + h.marketValue, 0);
     const totalCostBasis = holdings.reduce((sum, h) => sum + (h.costBasis || 0), 0);
-    const totalGainLoss = totalValue - totalCostBasis;
+    // Gains already realized by sales count along with those on what's still held
+    const totalGainLoss = totalValue - totalCostBasis + (summary?.realizedGainLoss ?? 0);
     const totalGainLossPercent = totalCostBasis > 0
       ? (totalGainLoss / totalCostBasis) * 100
       : 0;
 
     return { totalValue, totalCostBasis, totalGainLoss, totalGainLossPercent };
-  }, [holdings]);
+  }, [holdings, summary]);
 
   // Group by security type for pie chart
   const allocationData = useMemo(() => {
@@ -105,8 +302,8 @@ export function Investments() {
   }, [holdings, totals.totalValue]);
 
   // Investment accounts
-  const investmentAccounts = accounts.filter(
-    (a) => a.accountType === "investment" || a.accountType === "retirement"
+  const investmentAccounts = accounts.filter((a) =>
+    INVESTMENT_ACCOUNT_TYPES.includes(a.accountType)
   );
 
   return (
@@ -114,10 +311,20 @@ export function Investments() {
       <Header
         title="Investments"
         actions={
-          <Button variant="outline" onClick={loadHoldings}>
-            <RefreshCw className="h-4 w-4 mr-2" />
-            Refresh
-          </Button>
+          <div className="flex gap-2">
+            <Button variant="outline" onClick={loadHoldings}>
+              <RefreshCw className="h-4 w-4 mr-2" />
+              Refresh
+            </Button>
+            <Button variant="outline" onClick={openTradeDialog}>
+              <ArrowRightLeft className="h-4 w-4 mr-2" />
+              Record Transaction
+            </Button>
+            <Button onClick={() => handleOpenDialog()}>
+              <Plus className="h-4 w-4 mr-2" />
+              Add Holding
+            </Button>
+          </div>
         }
       />
       <PageContainer>
@@ -165,6 +372,12 @@ export function Investments() {
                   {formatMoney(totals.totalGainLoss)}
                 </p>
               </div>
+              {summary && summary.realizedGainLoss !== 0 && (
+                <p className="text-xs text-muted-foreground mt-1">
+                  Realized {formatMoney(summary.realizedGainLoss)} · Unrealized{" "}
+                  {formatMoney(summary.unrealizedGainLoss)}
+                </p>
+              )}
             </CardContent>
           </Card>
           <Card>
@@ -254,7 +467,7 @@ export function Investments() {
                     No investment holdings yet.
                   </p>
                   <p className="text-sm text-muted-foreground">
-                    Holdings will appear here when you import investment account data.
+                    Add a holding, or import investment account data.
                   </p>
                 </div>
               ) : (
@@ -267,6 +480,7 @@ export function Investments() {
                       <TableHead className="text-right">Price</TableHead>
                       <TableHead className="text-right">Value</TableHead>
                       <TableHead className="text-right">Gain/Loss</TableHead>
+                      <TableHead />
                     </TableRow>
                   </TableHeader>
                   <TableBody>
@@ -320,6 +534,22 @@ export function Investments() {
                             <span className="text-muted-foreground">—</span>
                           )}
                         </TableCell>
+                        <TableCell className="text-right whitespace-nowrap">
+                          <Button
+                            variant="ghost"
+                            size="icon"
+                            onClick={() => handleOpenDialog(holding)}
+                          >
+                            <Pencil className="h-4 w-4" />
+                          </Button>
+                          <Button
+                            variant="ghost"
+                            size="icon"
+                            onClick={() => handleDelete(holding)}
+                          >
+                            <Trash2 className="h-4 w-4" />
+                          </Button>
+                        </TableCell>
                       </TableRow>
                     ))}
                   </TableBody>
@@ -329,6 +559,93 @@ export function Investments() {
           </Card>
         </div>
 
+        {/* Investment Activity */}
+        {investmentTransactions.length > 0 && (
+          <Card className="mt-6">
+            <CardHeader>
+              <CardTitle className="flex items-center gap-2">
+                <ArrowRightLeft className="h-5 w-5" />
+                Activity
+              </CardTitle>
+              <CardDescription>Buys, sells, dividends, splits and fees</CardDescription>
+              <div className="flex items-center gap-2 pt-2">
+                <Label className="text-sm text-muted-foreground">Cost basis of sales</Label>
+                <Select
+                  value={summary?.costBasisMethod ?? "fifo"}
+                  onValueChange={(v) => handleCostBasisMethodChange(v as CostBasisMethod)}
+                >
+                  <SelectTrigger className="w-[200px]">
+                    <SelectValue />
+                  </SelectTrigger>
+                  <SelectContent>
+                    <SelectItem value="fifo">First in, first out</SelectItem>
+                    <SelectItem value="average">Average cost</SelectItem>
+                  </SelectContent>
+                </Select>
+              </div>
+            </CardHeader>
+            <CardContent>
+              {activityError && <p className="text-sm text-red-600 mb-4">{activityError}</p>}
+              <Table>
+                <TableHeader>
+                  <TableRow>
+                    <TableHead>Date</TableHead>
+                    <TableHead>Type</TableHead>
+                    <TableHead>Symbol</TableHead>
+                    <TableHead className="text-right">Shares</TableHead>
+                    <TableHead className="text-right">Amount</TableHead>
+                    <TableHead className="text-right">Realized</TableHead>
+                    <TableHead />
+                  </TableRow>
+                </TableHeader>
+                <TableBody>
+                  {investmentTransactions.map((transaction) => (
+                    <TableRow key={transaction.id}>
+                      <TableCell>{formatDate(transaction.date)}</TableCell>
+                      <TableCell>
+                        <Badge variant="secondary">
+                          {INVESTMENT_TRANSACTION_LABELS[transaction.transactionType]}
+                        </Badge>
+                      </TableCell>
+                      <TableCell>{transaction.symbol ?? "—"}</TableCell>
+                      <TableCell className="text-right">
+                        {transaction.transactionType === "split"
+                          ? `${transaction.quantity}:1`
+                          : transaction.quantity?.toFixed(4) ?? "—"}
+                      </TableCell>
+                      <TableCell className="text-right">
+                        {transaction.transactionType === "split"
+                          ? "—"
+                          : formatMoney(transaction.totalAmount)}
+                      </TableCell>
+                      <TableCell
+                        className={cn(
+                          "text-right",
+                          transaction.realizedGain != null &&
+                            (transaction.realizedGain >= 0 ? "text-green-600" : "text-red-600")
+                        )}
+                      >
+                        {transaction.realizedGain != null
+                          ? formatMoney(transaction.realizedGain)
+                          : "—"}
+                      </TableCell>
+                      <TableCell className="text-right">
+                        <Button
+                          variant="ghost"
+                          size="icon"
+                          onClick={() => handleDeleteTransaction(transaction)}
+                        >
+                          <Trash2 className="h-4 w-4" />
+                        </Button>
+                      </TableCell>
+                    </TableRow>
+                  ))}
+                </TableBody>
+              </Table>
+            </CardContent>
+          </Card>
+        )}
+
         {/* Investment Accounts */}
         {investmentAccounts.length > 0 && (
           <Card className="mt-6">
@@ -357,6 +674,312 @@ export function Investments() {
             </CardContent>
           </Card>
         )}
+
+        <Dialog open={isDialogOpen} onOpenChange={setIsDialogOpen}>
+          <DialogContent>
+            <DialogHeader>
+              <DialogTitle>
+                {editingHolding
+                  ? `${editingHolding.symbol} in ${editingHolding.accountName}`
+                  : "Add Holding"}
+              </DialogTitle>
+              <DialogDescription>
+                {editingHolding
+                  ? "Update the position, or the security's price"
+                  : "Record a position in a security held in an investment account"}
+              </DialogDescription>
+            </DialogHeader>
+            <form onSubmit={handleSubmit} className="space-y-4">
+              {!editingHolding && (
+                <>
+                  <div className="space-y-2">
+                    <Label>Account</Label>
+                    <Select
+                      value={formData.accountId}
+                      onValueChange={(value) => setFormData({ ...formData, accountId: value })}
+                    >
+                      <SelectTrigger>
+                        <SelectValue placeholder="Select an account" />
+                      </SelectTrigger>
+                      <SelectContent>
+                        {getOpenAccounts()
+                          .filter((a) => INVESTMENT_ACCOUNT_TYPES.includes(a.accountType))
+                          .map((account) => (
+                            <SelectItem key={account.id} value={account.id}>
+                              {account.name}
+                            </SelectItem>
+                          ))}
+                      </SelectContent>
+                    </Select>
+                  </div>
+                  <div className="space-y-2">
+                    <Label htmlFor="symbol">Symbol</Label>
+                    <Input
+                      id="symbol"
+                      value={formData.symbol}
+                      onChange={(e) => setFormData({ ...formData, symbol: e.target.value })}
+                      placeholder="VTI"
+                      required
+                    />
+                    {existingSecurity?.name && (
+                      <p className="text-xs text-muted-foreground">{existingSecurity.name}</p>
+                    )}
+                  </div>
+                  {!existingSecurity && (
+                    <div className="grid grid-cols-2 gap-4">
+                      <div className="space-y-2">
+                        <Label htmlFor="securityName">Name (optional)</Label>
+                        <Input
+                          id="securityName"
+                          value={formData.name}
+                          onChange={(e) => setFormData({ ...formData, name: e.target.value })}
+                        />
+                      </div>
+                      <div className="space-y-2">
+                        <Label>Type</Label>
+                        <Select
+                          value={formData.securityType}
+                          onValueChange={(value) =>
+                            setFormData({ ...formData, securityType: value as SecurityType })
+                          }
+                        >
+                          <SelectTrigger>
+                            <SelectValue />
+                          </SelectTrigger>
+                          <SelectContent>
+                            {Object.entries(SECURITY_TYPE_LABELS).map(([key, label]) => (
+                              <SelectItem key={key} value={key}>
+                                {label}
+                              </SelectItem>
+                            ))}
+                          </SelectContent>
+                        </Select>
+                      </div>
+                    </div>
+                  )}
+                </>
+              )}
+              <div className="grid grid-cols-2 gap-4">
+                <div className="space-y-2">
+                  <Label htmlFor="quantity">Shares</Label>
+                  <Input
+                    id="quantity"
+                    type="number"
+                    step="any"
+                    min="0"
+                    value={formData.quantity}
+                    onChange={(e) => setFormData({ ...formData, quantity: e.target.value })}
+                    required
+                  />
+                </div>
+                {(editingHolding || !existingSecurity) && (
+                  <div className="space-y-2">
+                    <Label htmlFor="price">Price (optional)</Label>
+                    <Input
+                      id="price"
+                      type="number"
+                      step="0.01"
+                      min="0"
+                      value={formData.price}
+                      onChange={(e) => setFormData({ ...formData, price: e.target.value })}
+                    />
+                  </div>
+                )}
+                <div className="space-y-2">
+                  <Label htmlFor="costBasis">Cost Basis (optional)</Label>
+                  <Input
+                    id="costBasis"
+                    type="number"
+                    step="0.01"
+                    min="0"
+                    value={formData.costBasis}
+                    onChange={(e) => setFormData({ ...formData, costBasis: e.target.value })}
+                  />
+                </div>
+                <div className="space-y-2">
+                  <Label htmlFor="acquisitionDate">Acquired (optional)</Label>
+                  <Input
+                    id="acquisitionDate"
+                    type="date"
+                    value={formData.acquisitionDate}
+                    onChange={(e) =>
+                      setFormData({ ...formData, acquisitionDate: e.target.value })
+                    }
+                  />
+                </div>
+              </div>
+              {formError && <p className="text-sm text-red-600">{formError}</p>}
+              <DialogFooter>
+                <Button type="button" variant="outline" onClick={() => setIsDialogOpen(false)}>
+                  Cancel
+                </Button>
+                <Button type="submit" disabled={!editingHolding && !formData.accountId}>
+                  {editingHolding ? "Save" : "Add Holding"}
+                </Button>
+              </DialogFooter>
+            </form>
+          </DialogContent>
+        </Dialog>
+
+        <Dialog open={isTradeOpen} onOpenChange={setIsTradeOpen}>
+          <DialogContent>
+            <DialogHeader>
+              <DialogTitle>Record Transaction</DialogTitle>
+              <DialogDescription>
+                Updates the holding and posts the cash to the account
+              </DialogDescription>
+            </DialogHeader>
+            <form onSubmit={handleTradeSubmit} className="space-y-4">
+              <div className="grid grid-cols-2 gap-4">
+                <div className="space-y-2">
+                  <Label>Type</Label>
+                  <Select
+                    value={tradeForm.transactionType}
+                    onValueChange={(value) =>
+                      setTradeForm({
+                        ...tradeForm,
+                        transactionType: value as InvestmentTransactionType,
+                      })
+                    }
+                  >
+                    <SelectTrigger>
+                      <SelectValue />
+                    </SelectTrigger>
+                    <SelectContent>
+                      {Object.entries(INVESTMENT_TRANSACTION_LABELS).map(([key, label]) => (
+                        <SelectItem key={key} value={key}>
+                          {label}
+                        </SelectItem>
+                      ))}
+                    </SelectContent>
+                  </Select>
+                </div>
+                <div className="space-y-2">
+                  <Label htmlFor="tradeDate">Date</Label>
+                  <Input
+                    id="tradeDate"
+                    type="date"
+                    value={tradeForm.date}
+                    onChange={(e) => setTradeForm({ ...tradeForm, date: e.target.value })}
+                    required
+                  />
+                </div>
+                <div className="space-y-2">
+                  <Label>Account</Label>
+                  <Select
+                    value={tradeForm.accountId}
+                    onValueChange={(value) => setTradeForm({ ...tradeForm, accountId: value })}
+                  >
+                    <SelectTrigger>
+                      <SelectValue placeholder="Select an account" />
+                    </SelectTrigger>
+                    <SelectContent>
+                      {getOpenAccounts()
+                        .filter((a) => INVESTMENT_ACCOUNT_TYPES.includes(a.accountType))
+                        .map((account) => (
+                          <SelectItem key={account.id} value={account.id}>
+                            {account.name}
+                          </SelectItem>
+                        ))}
+                    </SelectContent>
+                  </Select>
+                </div>
+                <div className="space-y-2">
+                  <Label>Security{tradesUnits ? "" : " (optional)"}</Label>
+                  <Select
+                    value={tradeForm.securityId}
+                    onValueChange={(value) => setTradeForm({ ...tradeForm, securityId: value })}
+                  >
+                    <SelectTrigger>
+                      <SelectValue />
+                    </SelectTrigger>
+                    <SelectContent>
+                      <SelectItem value="none">None</SelectItem>
+                      {securities.map((security) => (
+                        <SelectItem key={security.id} value={security.id}>
+                          {security.symbol}
+                        </SelectItem>
+                      ))}
+                    </SelectContent>
+                  </Select>
+                </div>
+                {tradesUnits ? (
+                  <div className="space-y-2">
+                    <Label htmlFor="tradeQuantity">
+                      {tradeForm.transactionType === "split" ? "New Shares per Share" : "Shares"}
+                    </Label>
+                    <Input
+                      id="tradeQuantity"
+                      type="number"
+                      step="any"
+                      min="0"
+                      value={tradeForm.quantity}
+                      onChange={(e) => setTradeForm({ ...tradeForm, quantity: e.target.value })}
+                      required
+                    />
+                  </div>
+                ) : (
+                  <div className="space-y-2">
+                    <Label htmlFor="tradeAmount">Amount</Label>
+                    <Input
+                      id="tradeAmount"
+                      type="number"
+                      step="0.01"
+                      min="0"
+                      value={tradeForm.amount}
+                      onChange={(e) => setTradeForm({ ...tradeForm, amount: e.target.value })}
+                      required
+                    />
+                  </div>
+                )}
+                {isTrade && (
+                  <div className="space-y-2">
+                    <Label htmlFor="tradePrice">Price per Share</Label>
+                    <Input
+                      id="tradePrice"
+                      type="number"
+                      step="0.01"
+                      min="0"
+                      value={tradeForm.price}
+                      onChange={(e) => setTradeForm({ ...tradeForm, price: e.target.value })}
+                      required
+                    />
+                  </div>
+                )}
+                {tradeForm.transactionType !== "split" && (
+                  <div className="space-y-2">
+                    <Label htmlFor="tradeFees">Fees (optional)</Label>
+                    <Input
+                      id="tradeFees"
+                      type="number"
+                      step="0.01"
+                      min="0"
+                      value={tradeForm.fees}
+                      onChange={(e) => setTradeForm({ ...tradeForm, fees: e.target.value })}
+                    />
+                  </div>
+                )}
+              </div>
+              <div className="space-y-2">
+                <Label htmlFor="tradeNotes">Notes (optional)</Label>
+                <Input
+                  id="tradeNotes"
+                  value={tradeForm.notes}
+                  onChange={(e) => setTradeForm({ ...tradeForm, notes: e.target.value })}
+                />
+              </div>
+              {tradeError && <p className="text-sm text-red-600">{tradeError}</p>}
+              <DialogFooter>
+                <Button type="button" variant="outline" onClick={() => setIsTradeOpen(false)}>
+                  Cancel
+                </Button>
+                <Button type="submit" disabled={!tradeForm.accountId}>
+                  Record
+                </Button>
+              </DialogFooter>
+            </form>
+          </DialogContent>
+        </Dialog>
       </PageContainer>
     </>
   );
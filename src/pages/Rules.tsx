@@ -1,5 +1,5 @@
 import { useEffect, useState } from "react";
-import { Plus, Pencil, Trash2, Wand2, Play } from "lucide-react";
+import { Plus, Pencil, Trash2, Wand2, Play, Lightbulb, Check, X, ChevronUp, ChevronDown } from "lucide-react";
 import { Card, CardContent, CardHeader, CardTitle, CardDescription } from "@/components/ui/card";
 import { Button } from "@/components/ui/button";
 import { Input } from "@/components/ui/input";
@@ -34,24 +34,58 @@ import { Badge } from "@/components/ui/badge";
 import { Header } from "@/components/layout/Header";
 import { PageContainer } from "@/components/layout/PageContainer";
 import { useCategoryStore } from "@/stores/useCategoryStore";
+import { useAccountStore } from "@/stores/useAccountStore";
 import {
   listCategoryRules,
   createCategoryRule,
   updateCategoryRule,
   deleteCategoryRule,
+  reorderCategoryRules,
   applyCategoryRules,
+  previewCategoryRule,
+  suggestCategoryRules,
+  backtestCategoryRules,
 } from "@/lib/tauri";
-import type { CategoryRule } from "@/types";
+import { formatDate, formatMoney } from "@/lib/formatters";
+import type {
+  CategoryRule,
+  RuleAction,
+  RuleCondition,
+  RuleMatchMode,
+  RulePreview,
+  RuleBacktest,
+  RuleSuggestion,
+} from "@/types";
 
 const RULE_TYPES = [
   { value: "payee_contains", label: "Payee Contains" },
   { value: "payee_exact", label: "Payee Exact Match" },
   { value: "payee_starts_with", label: "Payee Starts With" },
   { value: "payee_regex", label: "Payee Regex" },
+  { value: "memo_contains", label: "Memo Contains" },
+  { value: "amount_equals", label: "Amount Equals" },
+  { value: "check_number", label: "Check Number" },
+  { value: "transaction_type", label: "Transaction Type" },
+  { value: "atm_withdrawal", label: "ATM Withdrawal" },
 ];
 
+// Rule types that still match something when the pattern is left empty
+const OPTIONAL_PATTERN_TYPES = ["atm_withdrawal", "check_number"];
+
+// Matches listed under the rule form; the total count is shown regardless
+const PREVIEW_LIMIT = 5;
+
+const PATTERN_PLACEHOLDERS: Record<string, string> = {
+  payee_regex: ".*amazon.*",
+  memo_contains: "Overdraft fee",
+  amount_equals: "15.99",
+  check_number: "1042",
+  transaction_type: "FEE",
+};
+
 export function Rules() {
   const { categories, fetchCategories } = useCategoryStore();
+  const { accounts, fetchAccounts, getOpenAccounts } = useAccountStore();
   const [rules, setRules] = useState<CategoryRule[]>([]);
   const [loading, setLoading] = useState(true);
   const [formOpen, setFormOpen] = useState(false);
@@ -59,6 +93,10 @@ export function Rules() {
   const [selectedRule, setSelectedRule] = useState<CategoryRule | null>(null);
   const [applying, setApplying] = useState(false);
   const [applyResult, setApplyResult] = useState<number | null>(null);
+  const [preview, setPreview] = useState<RulePreview | null>(null);
+  const [previewError, setPreviewError] = useState<string | null>(null);
+  const [suggestions, setSuggestions] = useState<RuleSuggestion[]>([]);
+  const [backtest, setBacktest] = useState<RuleBacktest | null>(null);
 
   // Form state
   const [formData, setFormData] = useState({
@@ -69,17 +107,34 @@ export function Rules() {
     amountMax: "",
     priority: "0",
     isActive: true,
+    transferAccountId: "none",
+    matchMode: "all",
   });
 
   useEffect(() => {
     fetchCategories();
+    fetchAccounts();
     loadRules();
-  }, [fetchCategories]);
+  }, [fetchCategories, fetchAccounts]);
+
+  const handleMove = async (index: number, offset: number) => {
+    const ordered = rules.map((r) => r.id);
+    const [moved] = ordered.splice(index, 1);
+    ordered.splice(index + offset, 0, moved!);
+    try {
+      await reorderCategoryRules(ordered);
+      await loadRules();
+    } catch (err) {
+      console.error("Failed to reorder rules:", err);
+    }
+  };
 
   const loadRules = async () => {
     try {
       const data = await listCategoryRules();
       setRules(data);
+      setSuggestions(await suggestCategoryRules());
+      setBacktest(await backtestCategoryRules());
     } catch (err) {
       console.error("Failed to load rules:", err);
     } finally {
@@ -97,7 +152,11 @@ export function Rules() {
       amountMax: "",
       priority: "0",
       isActive: true,
+      transferAccountId: "none",
+      matchMode: "all",
     });
+    setPreview(null);
+    setPreviewError(null);
     setFormOpen(true);
   };
 
@@ -111,7 +170,11 @@ export function Rules() {
       amountMax: rule.amountMax ? String(rule.amountMax / 100) : "",
       priority: String(rule.priority),
       isActive: rule.isActive,
+      transferAccountId: rule.transferAccountId ?? "none",
+      matchMode: rule.matchMode,
     });
+    setPreview(null);
+    setPreviewError(null);
     setFormOpen(true);
   };
 
@@ -131,18 +194,34 @@ export function Rules() {
     setDeleteDialogOpen(false);
   };
 
+  const ruleFormData = () => ({
+    categoryId: formData.categoryId,
+    ruleType: formData.ruleType as CategoryRule["ruleType"],
+    pattern: formData.pattern,
+    amountMin: formData.amountMin ? Math.round(parseFloat(formData.amountMin) * 100) : null,
+    amountMax: formData.amountMax ? Math.round(parseFloat(formData.amountMax) * 100) : null,
+    accountId: null,
+    priority: parseInt(formData.priority) || 0,
+    isActive: formData.isActive,
+    transferAccountId: formData.transferAccountId === "none" ? null : formData.transferAccountId,
+    conditions: selectedRule?.conditions ?? null,
+    actions: selectedRule?.actions ?? null,
+    matchMode: formData.matchMode as RuleMatchMode,
+  });
+
+  const handlePreview = async () => {
+    setPreviewError(null);
+    try {
+      setPreview(await previewCategoryRule(ruleFormData(), PREVIEW_LIMIT));
+    } catch (err) {
+      setPreview(null);
+      setPreviewError(String(err));
+    }
+  };
+
   const handleSubmit = async () => {
     try {
-      const data = {
-        categoryId: formData.categoryId,
-        ruleType: formData.ruleType as "payee_contains" | "payee_exact" | "payee_starts_with" | "payee_regex",
-        pattern: formData.pattern,
-        amountMin: formData.amountMin ? Math.round(parseFloat(formData.amountMin) * 100) : null,
-        amountMax: formData.amountMax ? Math.round(parseFloat(formData.amountMax) * 100) : null,
-        accountId: null,
-        priority: parseInt(formData.priority) || 0,
-        isActive: formData.isActive,
-      };
+      const data = ruleFormData();
 
       if (selectedRule) {
         await updateCategoryRule(selectedRule.id, data);
@@ -156,6 +235,32 @@ export function Rules() {
     }
   };
 
+  const handleAcceptSuggestion = async (suggestion: RuleSuggestion) => {
+    try {
+      await createCategoryRule({
+        ruleType: suggestion.ruleType,
+        pattern: suggestion.pattern,
+        categoryId: suggestion.categoryId,
+        amountMin: null,
+        amountMax: null,
+        accountId: null,
+        priority: 0,
+        isActive: true,
+        transferAccountId: null,
+        conditions: null,
+        actions: null,
+        matchMode: "all",
+      });
+      await loadRules();
+    } catch (err) {
+      console.error("Failed to create rule:", err);
+    }
+  };
+
+  const dismissSuggestion = (suggestion: RuleSuggestion) => {
+    setSuggestions((current) => current.filter((s) => s.pattern !== suggestion.pattern));
+  };
+
   const handleApplyRules = async () => {
     setApplying(true);
     setApplyResult(null);
@@ -178,6 +283,51 @@ export function Rules() {
     return RULE_TYPES.find((t) => t.value === ruleType)?.label || ruleType;
   };
 
+  const describeCondition = (condition: RuleCondition) => {
+    switch (condition.conditionType) {
+      case "amount_between":
+        return `Amount ${condition.amountMin !== null ? `$${(condition.amountMin / 100).toFixed(2)}` : "any"} - ${
+          condition.amountMax !== null ? `$${(condition.amountMax / 100).toFixed(2)}` : "any"
+        }`;
+      case "account":
+        return `Account is ${accounts.find((a) => a.id === condition.accountId)?.name || "Unknown"}`;
+      default:
+        return `${getRuleTypeLabel(condition.conditionType)} "${condition.pattern}"`;
+    }
+  };
+
+  const describeAction = (action: RuleAction) => {
+    switch (action.actionType) {
+      case "rename_payee":
+        return `rename payee to "${action.value}"`;
+      case "append_note":
+        return `add note "${action.value}"`;
+      case "add_tag":
+        return `tag #${action.value}`;
+      case "mark_transfer":
+        return `mark as transfer with ${accounts.find((a) => a.id === action.value)?.name || "Unknown"}`;
+      case "set_status":
+        return `mark ${action.value}`;
+    }
+  };
+
+  // How the rule fares against transactions that are already categorized
+  const renderBacktest = (rule: CategoryRule) => {
+    const entry = backtest?.rules.find((e) => e.ruleId === rule.id);
+    if (!entry || entry.matchCount === 0) return null;
+    const competing = entry.competingRuleIds
+      .map((id) => rules.find((r) => r.id === id)?.pattern)
+      .filter(Boolean);
+    return (
+      <p className={`text-xs mt-1 ${entry.conflicts > 0 ? "text-amber-600" : "text-muted-foreground"}`}>
+        Agrees with {entry.agreements} of {entry.matchCount} categorized match
+        {entry.matchCount !== 1 ? "es" : ""}
+        {entry.shadowedCount > 0 && `; ${entry.shadowedCount} decided by a higher-priority rule`}
+        {competing.length > 0 && `; competes with ${competing.map((p) => `"${p}"`).join(", ")}`}
+      </p>
+    );
+  };
+
   return (
     <>
       <Header
@@ -233,7 +383,7 @@ export function Rules() {
               </div>
             ) : (
               <div className="space-y-3">
-                {rules.map((rule) => (
+                {rules.map((rule, index) => (
                   <div
                     key={rule.id}
                     className="flex items-center justify-between p-4 border rounded-lg hover:bg-accent/50 transition-colors"
@@ -247,13 +397,31 @@ export function Rules() {
                           Priority: {rule.priority}
                         </span>
                       </div>
-                      <p className="font-medium">
-                        When <span className="text-primary">{getRuleTypeLabel(rule.ruleType)}</span>{" "}
-                        &quot;<code className="bg-muted px-1 rounded">{rule.pattern}</code>&quot;
-                      </p>
+                      {rule.conditions?.length ? (
+                        <p className="font-medium">
+                          When{" "}
+                          <span className="text-primary">
+                            {rule.conditions
+                              .map(describeCondition)
+                              .join(rule.matchMode === "any" ? " or " : " and ")}
+                          </span>
+                        </p>
+                      ) : (
+                        <p className="font-medium">
+                          When <span className="text-primary">{getRuleTypeLabel(rule.ruleType)}</span>{" "}
+                          &quot;<code className="bg-muted px-1 rounded">{rule.pattern}</code>&quot;
+                        </p>
+                      )}
                       <p className="text-sm text-muted-foreground">
-                        Set category to{" "}
-                        <Badge variant="outline">{getCategoryName(rule.categoryId)}</Badge>
+                        {rule.categoryId ? (
+                          <>
+                            Set category to{" "}
+                            <Badge variant="outline">{getCategoryName(rule.categoryId)}</Badge>
+                          </>
+                        ) : (
+                          "Leave category unchanged"
+                        )}
+                        {rule.actions?.length ? <span>, then {rule.actions.map(describeAction).join(", ")}</span> : null}
                         {(rule.amountMin || rule.amountMax) && (
                           <span>
                             {" "}
@@ -263,8 +431,25 @@ export function Rules() {
                           </span>
                         )}
                       </p>
+                      {renderBacktest(rule)}
                     </div>
                     <div className="flex items-center gap-2">
+                      <Button
+                        variant="ghost"
+                        size="icon"
+                        disabled={index === 0}
+                        onClick={() => handleMove(index, -1)}
+                      >
+                        <ChevronUp className="h-4 w-4" />
+                      </Button>
+                      <Button
+                        variant="ghost"
+                        size="icon"
+                        disabled={index === rules.length - 1}
+                        onClick={() => handleMove(index, 1)}
+                      >
+                        <ChevronDown className="h-4 w-4" />
+                      </Button>
                       <Button variant="ghost" size="icon" onClick={() => handleEdit(rule)}>
                         <Pencil className="h-4 w-4" />
                       </Button>
@@ -279,6 +464,59 @@ export function Rules() {
           </CardContent>
         </Card>
 
+        {suggestions.length > 0 && (
+          <Card className="mt-4">
+            <CardHeader>
+              <CardTitle className="flex items-center gap-2">
+                <Lightbulb className="h-5 w-5" />
+                Suggested Rules
+              </CardTitle>
+              <CardDescription>
+                Payees you already categorize the same way every time.
+              </CardDescription>
+            </CardHeader>
+            <CardContent>
+              <div className="space-y-3">
+                {suggestions.map((suggestion) => (
+                  <div
+                    key={suggestion.pattern}
+                    className="flex items-center justify-between p-4 border rounded-lg"
+                  >
+                    <div className="flex-1">
+                      <p className="font-medium">
+                        When <span className="text-primary">Payee Contains</span>{" "}
+                        &quot;<code className="bg-muted px-1 rounded">{suggestion.pattern}</code>&quot;
+                        {" "}set category to{" "}
+                        <Badge variant="outline">{suggestion.categoryName}</Badge>
+                      </p>
+                      <p className="text-sm text-muted-foreground">
+                        {Math.round(suggestion.consistency * 100)}% of {suggestion.matchCount} matching
+                        transactions already use this category
+                        {suggestion.uncategorizedCount > 0 &&
+                          `; would categorize ${suggestion.uncategorizedCount} more`}
+                        {" "}(e.g. {suggestion.samplePayees.join(", ")})
+                      </p>
+                    </div>
+                    <div className="flex items-center gap-2">
+                      <Button size="sm" onClick={() => handleAcceptSuggestion(suggestion)}>
+                        <Check className="h-4 w-4 mr-1" />
+                        Accept
+                      </Button>
+                      <Button
+                        variant="ghost"
+                        size="icon"
+                        onClick={() => dismissSuggestion(suggestion)}
+                      >
+                        <X className="h-4 w-4" />
+                      </Button>
+                    </div>
+                  </div>
+                ))}
+              </div>
+            </CardContent>
+          </Card>
+        )}
+
         {/* Create/Edit Dialog */}
         <Dialog open={formOpen} onOpenChange={setFormOpen}>
           <DialogContent>
@@ -290,6 +528,24 @@ export function Rules() {
             </DialogHeader>
 
             <div className="space-y-4 py-4">
+              {selectedRule?.conditions?.length ? (
+                <div className="space-y-2">
+                  <Label>Conditions</Label>
+                  <Select
+                    value={formData.matchMode}
+                    onValueChange={(v) => setFormData((p) => ({ ...p, matchMode: v }))}
+                  >
+                    <SelectTrigger>
+                      <SelectValue />
+                    </SelectTrigger>
+                    <SelectContent>
+                      <SelectItem value="all">Match all conditions</SelectItem>
+                      <SelectItem value="any">Match any condition</SelectItem>
+                    </SelectContent>
+                  </Select>
+                </div>
+              ) : null}
+
               <div className="space-y-2">
                 <Label>Rule Type</Label>
                 <Select
@@ -314,17 +570,67 @@ export function Rules() {
                 <Input
                   value={formData.pattern}
                   onChange={(e) => setFormData((p) => ({ ...p, pattern: e.target.value }))}
-                  placeholder={
-                    formData.ruleType === "payee_regex"
-                      ? ".*amazon.*"
-                      : "Amazon"
-                  }
+                  placeholder={PATTERN_PLACEHOLDERS[formData.ruleType] ?? "Amazon"}
                 />
                 <p className="text-sm text-muted-foreground">
                   {formData.ruleType === "payee_contains" && "Match if payee contains this text (case-insensitive)"}
                   {formData.ruleType === "payee_exact" && "Match if payee exactly matches this text (case-insensitive)"}
                   {formData.ruleType === "payee_starts_with" && "Match if payee starts with this text (case-insensitive)"}
                   {formData.ruleType === "payee_regex" && "Match using regular expression pattern"}
+                  {formData.ruleType === "memo_contains" && "Match if memo contains this text (case-insensitive)"}
+                  {formData.ruleType === "amount_equals" &&
+                    "Match this exact amount; add a sign (e.g. -15.99) to match only withdrawals or deposits"}
+                  {formData.ruleType === "check_number" && "Match this check number, or any check if left empty"}
+                  {formData.ruleType === "transaction_type" &&
+                    "Match the bank's transaction type (e.g. FEE, DIRECTDEP), ignoring case"}
+                  {formData.ruleType === "atm_withdrawal" &&
+                    "Match ATM and cash withdrawals; optionally only those whose payee contains this text"}
+                </p>
+                <div className="flex items-center gap-3">
+                  <Button type="button" variant="outline" size="sm" onClick={handlePreview}>
+                    Preview matches
+                  </Button>
+                  {preview && (
+                    <span className="text-sm text-muted-foreground">
+                      Matches {preview.matchCount} existing transaction{preview.matchCount !== 1 ? "s" : ""}
+                    </span>
+                  )}
+                </div>
+                {previewError && <p className="text-sm text-destructive">{previewError}</p>}
+                {preview && preview.transactions.length > 0 && (
+                  <ul className="text-sm border rounded-md divide-y">
+                    {preview.transactions.map((t) => (
+                      <li key={t.id} className="flex justify-between gap-2 px-3 py-1.5">
+                        <span className="truncate">
+                          {formatDate(t.date)} · {t.payee || "No payee"}
+                        </span>
+                        <span className="tabular-nums">{formatMoney(t.amount)}</span>
+                      </li>
+                    ))}
+                  </ul>
+                )}
+              </div>
+
+              <div className="space-y-2">
+                <Label>Transfer To (optional)</Label>
+                <Select
+                  value={formData.transferAccountId}
+                  onValueChange={(v) => setFormData((p) => ({ ...p, transferAccountId: v }))}
+                >
+                  <SelectTrigger>
+                    <SelectValue />
+                  </SelectTrigger>
+                  <SelectContent>
+                    <SelectItem value="none">Don't transfer</SelectItem>
+                    {getOpenAccounts(formData.transferAccountId).map((account) => (
+                      <SelectItem key={account.id} value={account.id}>
+                        {account.name}
+                      </SelectItem>
+                    ))}
+                  </SelectContent>
+                </Select>
+                <p className="text-sm text-muted-foreground">
+                  Record matching withdrawals as transfers into this account, e.g. your cash wallet
                 </p>
               </div>
 
@@ -338,11 +644,13 @@ export function Rules() {
                     <SelectValue placeholder="Select a category" />
                   </SelectTrigger>
                   <SelectContent>
-                    {categories.map((category) => (
-                      <SelectItem key={category.id} value={category.id}>
-                        {category.name}
-                      </SelectItem>
-                    ))}
+                    {categories
+                      .filter((c) => !c.isArchived || c.id === formData.categoryId)
+                      .map((category) => (
+                        <SelectItem key={category.id} value={category.id}>
+                          {category.name}
+                        </SelectItem>
+                      ))}
                   </SelectContent>
                 </Select>
               </div>
@@ -396,7 +704,12 @@ export function Rules() {
               <Button variant="outline" onClick={() => setFormOpen(false)}>
                 Cancel
               </Button>
-              <Button onClick={handleSubmit} disabled={!formData.pattern || !formData.categoryId}>
+              <Button
+                onClick={handleSubmit}
+                disabled={
+                  (!formData.pattern && !OPTIONAL_PATTERN_TYPES.includes(formData.ruleType)) || !formData.categoryId
+                }
+              >
                 {selectedRule ? "Update" : "Create"}
               </Button>
             </DialogFooter>
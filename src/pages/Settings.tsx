@@ -4,6 +4,14 @@ import { Card, CardContent, CardDescription, CardHeader, CardTitle } from "@/com
 import { Button } from "@/components/ui/button";
 import { Input } from "@/components/ui/input";
 import { Label } from "@/components/ui/label";
+import { Switch } from "@/components/ui/switch";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/components/ui/select";
 import {
   Dialog,
   DialogContent,
@@ -17,6 +25,8 @@ import { Header } from "@/components/layout/Header";
 import { PageContainer } from "@/components/layout/PageContainer";
 import { useAppStore } from "@/stores/useAppStore";
 import * as api from "@/lib/tauri";
+import type { FxRate } from "@/types";
+import { save } from "@tauri-apps/plugin-dialog";
 
 export function Settings() {
   const { theme, setTheme, setUnlocked } = useAppStore();
@@ -42,10 +52,142 @@ export function Settings() {
   const [deleteConfirmText, setDeleteConfirmText] = useState("");
   const [showFinalDeleteConfirm, setShowFinalDeleteConfirm] = useState(false);
 
+  // Category rules run on import and on edits that leave a transaction uncategorized
+  const [autoApplyRules, setAutoApplyRules] = useState(true);
+
+  // Weekly reports group days by these; ISO weeks always start on Monday
+  const [weekStart, setWeekStart] = useState("sunday");
+  const [weekNumbering, setWeekNumbering] = useState("calendar");
+
+  // Net worth, budgets and reports are totalled in the base currency
+  const [baseCurrency, setBaseCurrency] = useState("USD");
+  const [fxRates, setFxRates] = useState<FxRate[]>([]);
+  const [missingFxRates, setMissingFxRates] = useState<string[]>([]);
+  const [fxForm, setFxForm] = useState({
+    fromCurrency: "",
+    date: new Date().toISOString().slice(0, 10),
+    rate: "",
+  });
+  const [fxError, setFxError] = useState<string | null>(null);
+  const [fxProviderUrl, setFxProviderUrl] = useState("");
+  const [fxAutoFetch, setFxAutoFetch] = useState(false);
+  const [fetchingFxRates, setFetchingFxRates] = useState(false);
+
   useEffect(() => {
     loadDbPaths();
+    api
+      .getSetting("auto_apply_category_rules")
+      .then((value) => setAutoApplyRules(value !== "false"))
+      .catch((err) => console.error("Failed to load rule settings:", err));
+    Promise.all([api.getSetting("week_start"), api.getSetting("week_numbering")])
+      .then(([start, numbering]) => {
+        setWeekStart(start ?? "sunday");
+        setWeekNumbering(numbering ?? "calendar");
+      })
+      .catch((err) => console.error("Failed to load week settings:", err));
+    api.getBaseCurrency().then(setBaseCurrency).catch(console.error);
+    Promise.all([api.getSetting("fx_provider_url"), api.getSetting("fx_auto_fetch")])
+      .then(([url, autoFetch]) => {
+        setFxProviderUrl(url ?? "");
+        setFxAutoFetch(autoFetch === "true");
+      })
+      .catch((err) => console.error("Failed to load exchange rate settings:", err));
+    loadFxRates();
   }, []);
 
+  const loadFxRates = () => {
+    api
+      .listFxRates()
+      .then(setFxRates)
+      .catch((err) => console.error("Failed to load exchange rates:", err));
+    api
+      .listMissingFxRates()
+      .then(setMissingFxRates)
+      .catch((err) => console.error("Failed to load missing exchange rates:", err));
+  };
+
+  const handleBaseCurrencySave = async () => {
+    setFxError(null);
+    try {
+      setBaseCurrency(await api.setBaseCurrency(baseCurrency));
+      loadFxRates();
+    } catch (err) {
+      setFxError(String(err));
+    }
+  };
+
+  const handleAddFxRate = async () => {
+    setFxError(null);
+    try {
+      await api.setFxRate({
+        fromCurrency: fxForm.fromCurrency,
+        toCurrency: baseCurrency,
+        date: fxForm.date,
+        rate: parseFloat(fxForm.rate),
+      });
+      setFxForm({ ...fxForm, fromCurrency: "", rate: "" });
+      loadFxRates();
+    } catch (err) {
+      setFxError(String(err));
+    }
+  };
+
+  const handleFetchFxRates = async () => {
+    setFxError(null);
+    setFetchingFxRates(true);
+    try {
+      await api.setSetting("fx_provider_url", fxProviderUrl.trim());
+      await api.fetchFxRates(fxForm.date);
+      loadFxRates();
+    } catch (err) {
+      setFxError(String(err));
+    } finally {
+      setFetchingFxRates(false);
+    }
+  };
+
+  const handleFxAutoFetchChange = async (checked: boolean) => {
+    setFxAutoFetch(checked);
+    try {
+      await api.setSetting("fx_auto_fetch", String(checked));
+    } catch (err) {
+      console.error("Failed to save exchange rate settings:", err);
+      setFxAutoFetch(!checked);
+    }
+  };
+
+  const handleDeleteFxRate = async (id: string) => {
+    try {
+      await api.deleteFxRate(id);
+      loadFxRates();
+    } catch (err) {
+      console.error("Failed to delete exchange rate:", err);
+    }
+  };
+
+  const handleWeekSettingChange = async (key: "week_start" | "week_numbering", value: string) => {
+    if (key === "week_start") {
+      setWeekStart(value);
+    } else {
+      setWeekNumbering(value);
+    }
+    try {
+      await api.setSetting(key, value);
+    } catch (err) {
+      console.error("Failed to save week settings:", err);
+    }
+  };
+
+  const handleAutoApplyRulesChange = async (checked: boolean) => {
+    setAutoApplyRules(checked);
+    try {
+      await api.setSetting("auto_apply_category_rules", String(checked));
+    } catch (err) {
+      console.error("Failed to save rule settings:", err);
+      setAutoApplyRules(!checked);
+    }
+  };
+
   const loadDbPaths = async () => {
     try {
       const [current, defaultPath] = await Promise.all([
@@ -147,14 +289,12 @@ export function Settings() {
 
   const handleExportCsv = async () => {
     try {
-      const csv = await api.exportToCsv({});
-      const blob = new Blob([csv], { type: "text/csv" });
-      const url = URL.createObjectURL(blob);
-      const a = document.createElement("a");
-      a.href = url;
-      a.download = `transactions-${new Date().toISOString().split("T")[0]}.csv`;
-      a.click();
-      URL.revokeObjectURL(url);
+      const path = await save({
+        defaultPath: `transactions-${new Date().toISOString().split("T")[0]}.csv`,
+        filters: [{ name: "CSV", extensions: ["csv"] }],
+      });
+      if (!path) return;
+      await api.exportFilteredTransactions({}, "csv", path);
     } catch (err) {
       console.error("CSV export failed:", err);
     }
@@ -208,6 +348,195 @@ export function Settings() {
             </CardContent>
           </Card>
 
+          {/* Category Rules */}
+          <Card>
+            <CardHeader>
+              <CardTitle>Category Rules</CardTitle>
+              <CardDescription>
+                Choose when category rules run
+              </CardDescription>
+            </CardHeader>
+            <CardContent>
+              <div className="flex items-center gap-2">
+                <Switch checked={autoApplyRules} onCheckedChange={handleAutoApplyRulesChange} />
+                <Label>Apply rules automatically to imported and newly uncategorized transactions</Label>
+              </div>
+            </CardContent>
+          </Card>
+
+          {/* Reports */}
+          <Card>
+            <CardHeader>
+              <CardTitle>Reports</CardTitle>
+              <CardDescription>
+                How weekly reports group transactions
+              </CardDescription>
+            </CardHeader>
+            <CardContent>
+              <div className="grid grid-cols-2 gap-4">
+                <div className="space-y-2">
+                  <Label>Weeks</Label>
+                  <Select
+                    value={weekNumbering}
+                    onValueChange={(v) => handleWeekSettingChange("week_numbering", v)}
+                  >
+                    <SelectTrigger>
+                      <SelectValue />
+                    </SelectTrigger>
+                    <SelectContent>
+                      <SelectItem value="calendar">Calendar weeks</SelectItem>
+                      <SelectItem value="iso">ISO weeks</SelectItem>
+                    </SelectContent>
+                  </Select>
+                </div>
+                <div className="space-y-2">
+                  <Label>Week Starts On</Label>
+                  <Select
+                    value={weekNumbering === "iso" ? "monday" : weekStart}
+                    onValueChange={(v) => handleWeekSettingChange("week_start", v)}
+                    disabled={weekNumbering === "iso"}
+                  >
+                    <SelectTrigger>
+                      <SelectValue />
+                    </SelectTrigger>
+                    <SelectContent>
+                      <SelectItem value="sunday">Sunday</SelectItem>
+                      <SelectItem value="monday">Monday</SelectItem>
+                    </SelectContent>
+                  </Select>
+                </div>
+              </div>
+            </CardContent>
+          </Card>
+
+          {/* Currencies */}
+          <Card>
+            <CardHeader>
+              <CardTitle>Currencies</CardTitle>
+              <CardDescription>
+                Accounts in other currencies are converted to the base currency for net worth,
+                budgets and reports, at the rate nearest each transaction's date
+              </CardDescription>
+            </CardHeader>
+            <CardContent className="space-y-4">
+              <div className="flex items-end gap-2">
+                <div className="space-y-2">
+                  <Label htmlFor="baseCurrency">Base Currency</Label>
+                  <Input
+                    id="baseCurrency"
+                    value={baseCurrency}
+                    onChange={(e) => setBaseCurrency(e.target.value.toUpperCase())}
+                    maxLength={3}
+                    className="w-24"
+                  />
+                </div>
+                <Button variant="outline" onClick={handleBaseCurrencySave}>
+                  Save
+                </Button>
+              </div>
+
+              <Separator />
+
+              <div className="space-y-2">
+                <Label>Exchange Rates</Label>
+                <div className="flex items-end gap-2">
+                  <Input
+                    value={fxForm.fromCurrency}
+                    onChange={(e) =>
+                      setFxForm({ ...fxForm, fromCurrency: e.target.value.toUpperCase() })
+                    }
+                    maxLength={3}
+                    placeholder="EUR"
+                    className="w-20"
+                  />
+                  <span className="pb-2 text-sm text-muted-foreground">
+                    = {baseCurrency}
+                  </span>
+                  <Input
+                    type="number"
+                    step="any"
+                    min="0"
+                    value={fxForm.rate}
+                    onChange={(e) => setFxForm({ ...fxForm, rate: e.target.value })}
+                    placeholder="1.08"
+                    className="w-28"
+                  />
+                  <Input
+                    type="date"
+                    value={fxForm.date}
+                    onChange={(e) => setFxForm({ ...fxForm, date: e.target.value })}
+                    className="w-40"
+                  />
+                  <Button
+                    variant="outline"
+                    onClick={handleAddFxRate}
+                    disabled={!fxForm.fromCurrency || !fxForm.rate}
+                  >
+                    Add
+                  </Button>
+                </div>
+                <div className="flex items-end gap-2">
+                  <div className="flex-1 space-y-2">
+                    <Label htmlFor="fxProviderUrl">Rate Provider URL</Label>
+                    <Input
+                      id="fxProviderUrl"
+                      value={fxProviderUrl}
+                      onChange={(e) => setFxProviderUrl(e.target.value)}
+                      placeholder="https://api.frankfurter.app/{date}?from={base}&to={symbols}"
+                    />
+                  </div>
+                  <Button
+                    variant="outline"
+                    onClick={handleFetchFxRates}
+                    disabled={fetchingFxRates}
+                  >
+                    {fetchingFxRates ? "Fetching..." : "Fetch Rates"}
+                  </Button>
+                </div>
+                <p className="text-xs text-muted-foreground">
+                  Fetches the rates on the date above for every account currency. Rates entered
+                  by hand are kept over fetched ones.
+                </p>
+                <div className="flex items-center gap-2">
+                  <Switch checked={fxAutoFetch} onCheckedChange={handleFxAutoFetchChange} />
+                  <Label>Fetch today's rates each time the database is unlocked</Label>
+                </div>
+                {fxError && <p className="text-sm text-destructive">{fxError}</p>}
+                {missingFxRates.length > 0 && (
+                  <p className="text-sm text-amber-600">
+                    No rate to {baseCurrency} yet for {missingFxRates.join(", ")}. Amounts in
+                    these currencies are counted unconverted in totals and reports.
+                  </p>
+                )}
+                {fxRates.length > 0 && (
+                  <div className="space-y-1">
+                    {fxRates.map((rate) => (
+                      <div
+                        key={rate.id}
+                        className="flex items-center justify-between text-sm"
+                      >
+                        <span>
+                          {rate.rateDate} · 1 {rate.fromCurrency} = {rate.rate}{" "}
+                          {rate.toCurrency}
+                          <span className="ml-2 text-muted-foreground">
+                            {rate.source}
+                          </span>
+                        </span>
+                        <Button
+                          variant="ghost"
+                          size="icon"
+                          onClick={() => handleDeleteFxRate(rate.id)}
+                        >
+                          <Trash2 className="h-4 w-4" />
+                        </Button>
+                      </div>
+                    ))}
+                  </div>
+                )}
+              </div>
+            </CardContent>
+          </Card>
+
           {/* Security */}
           <Card>
             <CardHeader>
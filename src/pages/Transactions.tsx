@@ -57,7 +57,8 @@ import { useAccountStore } from "@/stores/useAccountStore";
 import { useCategoryStore } from "@/stores/useCategoryStore";
 import { formatMoney, formatDate, parseMoney, getTodayString } from "@/lib/formatters";
 import { cn } from "@/lib/utils";
-import type { Transaction } from "@/types";
+import { suggestCategory } from "@/lib/tauri";
+import type { CategorySuggestion, Transaction } from "@/types";
 
 // Memoized transaction row to prevent re-renders when parent state changes
 interface TransactionRowProps {
@@ -163,13 +164,14 @@ export function Transactions() {
     clearSelection,
     setFilters,
   } = useTransactionStore();
-  const { accounts, fetchAccounts } = useAccountStore();
+  const { accounts, fetchAccounts, getOpenAccounts } = useAccountStore();
   const { categories, fetchCategories } = useCategoryStore();
 
   const [isDialogOpen, setIsDialogOpen] = useState(false);
   const [isImportDialogOpen, setIsImportDialogOpen] = useState(false);
   const [isCategorizeDialogOpen, setIsCategorizeDialogOpen] = useState(false);
   const [editingTransaction, setEditingTransaction] = useState<Transaction | null>(null);
+  const [categorySuggestion, setCategorySuggestion] = useState<CategorySuggestion | null>(null);
   const [selectedCategoryId, setSelectedCategoryId] = useState<string>("");
   const [lastClickedIndex, setLastClickedIndex] = useState<number | null>(null);
   const [sortColumn, setSortColumn] = useState<"date" | "payee" | "category" | "account" | "amount">("date");
@@ -183,6 +185,7 @@ export function Transactions() {
     payee: "",
     categoryId: "",
     notes: "",
+    excludeFromBudget: false,
   });
   const [deleteConfirmOpen, setDeleteConfirmOpen] = useState(false);
   const [pendingDeleteIds, setPendingDeleteIds] = useState<string[]>([]);
@@ -213,6 +216,7 @@ export function Transactions() {
     if (filters.endDate) count++;
     if (filters.minAmount !== null) count++;
     if (filters.maxAmount !== null) count++;
+    if (filters.checkNumber) count++;
     return count;
   }, [filters]);
 
@@ -225,6 +229,7 @@ export function Transactions() {
       endDate: null,
       minAmount: null,
       maxAmount: null,
+      checkNumber: null,
     });
   };
 
@@ -332,7 +337,14 @@ export function Transactions() {
           payee: transaction.payee || "",
           categoryId: transaction.categoryId || "",
           notes: transaction.notes || "",
+          excludeFromBudget: transaction.excludeFromBudget,
         });
+        setCategorySuggestion(null);
+        if (!transaction.categoryId) {
+          suggestCategory(transaction.id)
+            .then(setCategorySuggestion)
+            .catch((err) => console.error("Failed to suggest category:", err));
+        }
       } else {
         setEditingTransaction(null);
         setFormData({
@@ -343,7 +355,9 @@ export function Transactions() {
           payee: "",
           categoryId: "",
           notes: "",
+          excludeFromBudget: false,
         });
+        setCategorySuggestion(null);
       }
       setIsDialogOpen(true);
     },
@@ -363,6 +377,7 @@ export function Transactions() {
       originalPayee: formData.payee || null,
       categoryId: formData.categoryId || null,
       notes: formData.notes || null,
+      excludeFromBudget: formData.excludeFromBudget,
       postedDate: null,
       memo: null,
       checkNumber: null,
@@ -755,7 +770,7 @@ export function Transactions() {
                   <SelectValue placeholder="Select account" />
                 </SelectTrigger>
                 <SelectContent>
-                  {accounts.map((account) => (
+                  {getOpenAccounts(formData.accountId).map((account) => (
                     <SelectItem key={account.id} value={account.id}>
                       {account.name}
                     </SelectItem>
@@ -845,13 +860,37 @@ export function Transactions() {
                 </SelectTrigger>
                 <SelectContent>
                   <SelectItem value="none">Uncategorized</SelectItem>
-                  {categories.map((category) => (
-                    <SelectItem key={category.id} value={category.id}>
-                      {category.name}
-                    </SelectItem>
-                  ))}
+                  {categories
+                    .filter((c) => !c.isArchived || c.id === formData.categoryId)
+                    .map((category) => (
+                      <SelectItem key={category.id} value={category.id}>
+                        {category.name}
+                      </SelectItem>
+                    ))}
                 </SelectContent>
               </Select>
+              {categorySuggestion?.categoryId && !formData.categoryId && (
+                <p className="text-sm text-muted-foreground">
+                  Suggested: {categorySuggestion.categoryName}{" "}
+                  {categorySuggestion.source === "rule"
+                    ? "(from a rule)"
+                    : `(${Math.round(categorySuggestion.confidence * 100)}% confident)`}{" "}
+                  <Button
+                    type="button"
+                    variant="link"
+                    size="sm"
+                    className="h-auto p-0"
+                    onClick={() =>
+                      setFormData({
+                        ...formData,
+                        categoryId: categorySuggestion.categoryId ?? "",
+                      })
+                    }
+                  >
+                    Use
+                  </Button>
+                </p>
+              )}
             </div>
 
             <div className="space-y-2">
@@ -865,6 +904,19 @@ export function Transactions() {
               />
             </div>
 
+            <div className="flex items-center gap-2">
+              <Checkbox
+                id="exclude-from-budget"
+                checked={formData.excludeFromBudget}
+                onCheckedChange={(checked) =>
+                  setFormData({ ...formData, excludeFromBudget: checked === true })
+                }
+              />
+              <Label htmlFor="exclude-from-budget" className="font-normal">
+                Exclude from budgets and spending reports
+              </Label>
+            </div>
+
             <DialogFooter>
               <Button
                 type="button"
@@ -908,11 +960,13 @@ export function Transactions() {
                 </SelectTrigger>
                 <SelectContent>
                   <SelectItem value="none">Uncategorized</SelectItem>
-                  {categories.map((category) => (
-                    <SelectItem key={category.id} value={category.id}>
-                      {category.name}
-                    </SelectItem>
-                  ))}
+                  {categories
+                    .filter((c) => !c.isArchived)
+                    .map((category) => (
+                      <SelectItem key={category.id} value={category.id}>
+                        {category.name}
+                      </SelectItem>
+                    ))}
                 </SelectContent>
               </Select>
             </div>
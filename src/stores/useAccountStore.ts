@@ -1,6 +1,7 @@
 import { create } from "zustand";
-import type { Account } from "@/types";
+import type { Account, AccountDeleteTransactions } from "@/types";
 import * as api from "@/lib/tauri";
+import { ASSET_ACCOUNT_TYPES, LIABILITY_ACCOUNT_TYPES } from "@/lib/constants";
 
 interface AccountState {
   accounts: Account[];
@@ -10,13 +11,20 @@ interface AccountState {
   lastFetchedAt: number | null;
 
   fetchAccounts: () => Promise<void>;
-  createAccount: (data: Omit<Account, "id" | "createdAt" | "updatedAt">) => Promise<Account>;
+  createAccount: (data: Omit<Account, "id" | "aliases" | "createdAt" | "updatedAt">) => Promise<Account>;
   updateAccount: (id: string, data: Partial<Account>) => Promise<void>;
-  deleteAccount: (id: string) => Promise<void>;
+  reorderAccounts: (orderedIds: string[]) => Promise<void>;
+  adjustAccountBalance: (id: string, newBalance: number) => Promise<void>;
+  deleteAccount: (
+    id: string,
+    transactions?: AccountDeleteTransactions,
+    reassignToAccountId?: string
+  ) => Promise<void>;
   selectAccount: (id: string | null) => void;
 
   // Computed
   getAccountById: (id: string) => Account | undefined;
+  getOpenAccounts: (includeId?: string | null) => Account[];
   getTotalAssets: () => number;
   getTotalLiabilities: () => number;
   getNetWorth: () => number;
@@ -56,8 +64,22 @@ export const useAccountStore = create<AccountState>((set, get) => ({
     }));
   },
 
-  deleteAccount: async (id) => {
-    await api.deleteAccount(id);
+  reorderAccounts: async (orderedIds) => {
+    await api.reorderAccounts(orderedIds);
+    await get().fetchAccounts();
+  },
+
+  adjustAccountBalance: async (id, newBalance) => {
+    await api.adjustAccountBalance(id, newBalance);
+    await get().fetchAccounts();
+  },
+
+  deleteAccount: async (id, transactions, reassignToAccountId) => {
+    await api.deleteAccount(id, { transactions, reassignToAccountId });
+    // Reassigned transactions change another account's balance
+    if (transactions === "reassign") {
+      await get().fetchAccounts();
+    }
     set((state) => ({
       accounts: state.accounts.filter((a) => a.id !== id),
       selectedAccountId: state.selectedAccountId === id ? null : state.selectedAccountId,
@@ -68,18 +90,22 @@ export const useAccountStore = create<AccountState>((set, get) => ({
 
   getAccountById: (id) => get().accounts.find((a) => a.id === id),
 
+  // Accounts that can be picked for new activity. Closed accounts are left out unless
+  // already chosen, e.g. on a transaction being edited.
+  getOpenAccounts: (includeId) =>
+    get().accounts.filter((a) => !a.closedOn || a.id === includeId),
+
+  // Totals are in the base currency
   getTotalAssets: () => {
-    const assetTypes = ["checking", "savings", "investment", "cash"];
     return get()
-      .accounts.filter((a) => assetTypes.includes(a.accountType) && a.isActive)
-      .reduce((sum, a) => sum + a.currentBalance, 0);
+      .accounts.filter((a) => ASSET_ACCOUNT_TYPES.includes(a.accountType) && a.isActive)
+      .reduce((sum, a) => sum + a.baseBalance, 0);
   },
 
   getTotalLiabilities: () => {
-    const liabilityTypes = ["credit_card", "loan"];
     return get()
-      .accounts.filter((a) => liabilityTypes.includes(a.accountType) && a.isActive)
-      .reduce((sum, a) => sum + Math.abs(a.currentBalance), 0);
+      .accounts.filter((a) => LIABILITY_ACCOUNT_TYPES.includes(a.accountType) && a.isActive)
+      .reduce((sum, a) => sum + Math.abs(a.baseBalance), 0);
   },
 
   getNetWorth: () => {
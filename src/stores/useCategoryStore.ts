@@ -13,6 +13,8 @@ interface CategoryState {
   createCategory: (data: Omit<Category, "id" | "createdAt" | "updatedAt" | "isSystem">) => Promise<Category>;
   updateCategory: (id: string, data: Partial<Category>) => Promise<void>;
   deleteCategory: (id: string) => Promise<void>;
+  setCategoryArchived: (id: string, archived: boolean) => Promise<void>;
+  reorderCategories: (orderedIds: string[]) => Promise<void>;
 
   fetchRules: () => Promise<void>;
   createRule: (data: Omit<CategoryRule, "id" | "createdAt" | "updatedAt">) => Promise<CategoryRule>;
@@ -67,6 +69,21 @@ export const useCategoryStore = create<CategoryState>((set, get) => ({
     }));
   },
 
+  setCategoryArchived: async (id, archived) => {
+    if (archived) {
+      await api.archiveCategory(id);
+    } else {
+      await api.unarchiveCategory(id);
+    }
+    // Subcategories follow their parent, so refetch rather than patch one entry
+    await get().fetchCategories();
+  },
+
+  reorderCategories: async (orderedIds) => {
+    await api.reorderCategories(orderedIds);
+    await get().fetchCategories();
+  },
+
   fetchRules: async () => {
     try {
       const rules = await api.listCategoryRules();
@@ -36,6 +36,7 @@ const defaultFilters: TransactionFilters = {
   minAmount: null,
   maxAmount: null,
   isTransfer: null,
+  checkNumber: null,
 };
 
 export const useTransactionStore = create<TransactionState>((set, get) => ({
@@ -1,4 +1,10 @@
-import type { AccountType, TransactionStatus, CategoryType, BudgetPeriod } from "@/lib/constants";
+import type {
+  AccountType,
+  TransactionStatus,
+  CategoryType,
+  BudgetPeriod,
+  InterestCompounding,
+} from "@/lib/constants";
 
 export interface Account {
   id: string;
@@ -11,12 +17,120 @@ export interface Account {
   availableBalance: number | null;
   creditLimit: number | null;
   interestRate: number | null;
+  interestCompounding: InterestCompounding;
   isActive: boolean;
   isHidden: boolean;
+  // Keeps the account's spending out of budgets and spending reports
+  excludeFromBudget: boolean;
   displayOrder: number;
   ofxAccountId: string | null;
   lastSyncAt: string | null;
   notes: string | null;
+  aliases: string[];
+  institutionName: string | null;
+  accountGroupId: string | null;
+  // Credit card statement cycle, as days of the month
+  statementClosingDay: number | null;
+  paymentDueDay: number | null;
+  minimumPayment: number | null;
+  // Whether estimated interest is posted each month, and the last month posted (YYYY-MM)
+  accrueInterest: boolean;
+  interestAccruedThrough: string | null;
+  // Current balance in the base currency, at the latest exchange rate
+  baseBalance: number;
+  // Date the account was closed; closed accounts are also inactive
+  closedOn: string | null;
+  // Balance before any of the account's transactions, as of the opening date
+  openingBalance: number;
+  openingDate: string | null;
+  createdAt: string;
+  updatedAt: string;
+}
+
+// What deleting an account does, or would do, with its transactions
+export interface AccountDeleteResult {
+  dryRun: boolean;
+  transactions: AccountDeleteTransactions;
+  transactionCount: number;
+  // Sum of the transactions' amounts
+  transactionTotal: number;
+  reassignToAccountName: string | null;
+}
+
+// Keep transactions attached to the deleted account, move them to the trash too, or move
+// them to another account
+export type AccountDeleteTransactions = "keep" | "delete" | "reassign";
+
+// What merging one account into another moved
+export interface AccountMergeResult {
+  account: Account;
+  transactionsMoved: number;
+  recurringMoved: number;
+  holdingsMoved: number;
+}
+
+// Where an account stood when it was closed
+export interface AccountClosure {
+  accountId: string;
+  closedOn: string;
+  // Balance before any transfer out
+  closingBalance: number;
+  transferAccountId: string | null;
+  transferId: string | null;
+  transactionCount: number;
+  firstTransactionDate: string | null;
+  lastTransactionDate: string | null;
+  notes: string | null;
+  createdAt: string;
+}
+
+export interface AccountGroup {
+  id: string;
+  name: string;
+  displayOrder: number;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface AccountGroupNetWorth {
+  // null for accounts that aren't in a group
+  groupId: string | null;
+  name: string;
+  totalAssets: number;
+  totalLiabilities: number;
+  netWorth: number;
+}
+
+export interface CreditCardStatus {
+  accountId: string;
+  accountName: string;
+  balanceOwed: number;
+  creditLimit: number | null;
+  // Balance owed as a percentage of the credit limit
+  utilization: number | null;
+  // The statement cycle fields are null until the card's closing day is set
+  lastStatementDate: string | null;
+  statementBalance: number | null;
+  paidSinceStatement: number | null;
+  nextStatementDate: string | null;
+  minimumPayment: number | null;
+  paymentDueDate: string | null;
+  // Negative once the payment is overdue
+  daysUntilDue: number | null;
+  isDueSoon: boolean;
+  isOverdue: boolean;
+  isHighUtilization: boolean;
+}
+
+// One unit of fromCurrency buys `rate` of toCurrency on rateDate
+export interface FxRate {
+  id: string;
+  fromCurrency: string;
+  toCurrency: string;
+  rateDate: string;
+  rate: number;
+  // "manual", or the host of the provider the rate was fetched from
+  source: string;
   createdAt: string;
   updatedAt: string;
 }
@@ -60,6 +174,13 @@ export interface Transaction {
   parentTransactionId: string | null;
   createdAt: string;
   updatedAt: string;
+  originalAmount: number | null;
+  originalCurrency: string | null;
+  isHold: boolean;
+  holdExpiresAt: string | null;
+  tags: string[];
+  // Kept out of budgets and spending reports, e.g. a reimbursed expense
+  excludeFromBudget: boolean;
 }
 
 export interface TransactionSplit {
@@ -80,15 +201,90 @@ export interface Category {
   color: string | null;
   isSystem: boolean;
   displayOrder: number;
+  dueDay: number | null;
+  isArchived: boolean;
+  isTaxDeductible: boolean;
   createdAt: string;
   updatedAt: string;
   children?: Category[];
 }
 
+export interface CategoryImportResult {
+  created: number;
+  merged: number;
+}
+
+export interface Payee {
+  id: string;
+  name: string;
+  defaultCategoryId: string | null;
+  aliases: string[];
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface NoteTemplate {
+  id: string;
+  name: string;
+  template: string;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface TransactionTemplateSplit {
+  id: string;
+  templateId: string;
+  categoryId: string | null;
+  amount: number;
+  memo: string | null;
+}
+
+export interface TransactionTemplate {
+  id: string;
+  name: string;
+  accountId: string | null;
+  payee: string | null;
+  amount: number | null;
+  categoryId: string | null;
+  notes: string | null;
+  splits: TransactionTemplateSplit[];
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface PayeeSuggestion {
+  payeeId: string;
+  name: string;
+  defaultCategoryId: string | null;
+  lastAmount: number | null;
+  lastCategoryId: string | null;
+  lastDate: string | null;
+  frequency: number;
+}
+
+export interface PayeeStats {
+  payee: Payee;
+  transactionCount: number;
+  totalAmount: number;
+  averageAmount: number;
+  firstDate: string | null;
+  lastDate: string | null;
+  mostUsedCategoryId: string | null;
+}
+
 export interface CategoryRule {
   id: string;
   categoryId: string;
-  ruleType: "payee_contains" | "payee_exact" | "payee_starts_with" | "payee_regex";
+  ruleType:
+    | "payee_contains"
+    | "payee_exact"
+    | "payee_starts_with"
+    | "payee_regex"
+    | "memo_contains"
+    | "amount_equals"
+    | "check_number"
+    | "transaction_type"
+    | "atm_withdrawal";
   pattern: string;
   amountMin: number | null;
   amountMax: number | null;
@@ -97,6 +293,83 @@ export interface CategoryRule {
   isActive: boolean;
   createdAt: string;
   updatedAt: string;
+  transferAccountId: string | null;
+  conditions: RuleCondition[] | null;
+  actions: RuleAction[] | null;
+  // Whether every condition has to match, or any one of them
+  matchMode: RuleMatchMode;
+}
+
+export type RuleMatchMode = "all" | "any";
+
+export interface RuleCondition {
+  conditionType: CategoryRule["ruleType"] | "amount_between" | "account";
+  pattern: string;
+  amountMin: number | null;
+  amountMax: number | null;
+  accountId: string | null;
+}
+
+export interface RulePreview {
+  matchCount: number;
+  // Most recent matches first, up to the requested limit
+  transactions: Transaction[];
+}
+
+export interface CategorySuggestion {
+  transactionId: string;
+  categoryId: string | null;
+  categoryName: string | null;
+  // 1 for a rule match, otherwise the classifier's probability
+  confidence: number;
+  source: "rule" | "classifier";
+}
+
+export interface RuleSuggestion {
+  ruleType: "payee_contains";
+  pattern: string;
+  categoryId: string;
+  categoryName: string;
+  // Categorized transactions the pattern matches, and the share already in the category
+  matchCount: number;
+  consistency: number;
+  uncategorizedCount: number;
+  samplePayees: string[];
+}
+
+export interface RuleAction {
+  actionType: "rename_payee" | "append_note" | "add_tag" | "mark_transfer" | "set_status";
+  // New payee, note text, tag, transfer account id or status
+  value: string;
+}
+
+export interface RuleBacktestEntry {
+  ruleId: string;
+  categoryId: string;
+  // Categorized transactions the rule matches, and how many are already in its category
+  matchCount: number;
+  agreements: number;
+  conflicts: number;
+  // Matches a higher-priority rule categorizes first
+  shadowedCount: number;
+  // Other rules matching some of the same transactions with a different category
+  competingRuleIds: string[];
+}
+
+export interface RuleBacktest {
+  transactionCount: number;
+  matchedCount: number;
+  agreementCount: number;
+  conflictCount: number;
+  rules: RuleBacktestEntry[];
+}
+
+export interface RuleImportResult {
+  created: number;
+  // Rules identical to one already in the database
+  duplicates: number;
+  // Rules naming a category or account this database doesn't have
+  skipped: string[];
 }
 
 export interface Budget {
@@ -105,8 +378,220 @@ export interface Budget {
   periodType: BudgetPeriod;
   amount: number;
   rollover: boolean;
+  // Tracks spending without a limit; amount is unused
+  isWatchOnly: boolean;
   createdAt: string;
   updatedAt: string;
+  // Length of a custom period in days
+  periodDays: number | null;
+  // Date (YYYY-MM-DD) bi-weekly and custom periods count from
+  periodAnchor: string | null;
+  // Percentages of the budget that raise an alert when spending reaches them
+  alertThresholds: number[];
+  // Counts spending in the category's subcategories too
+  includeChildren: boolean;
+}
+
+export interface BudgetSummary {
+  budget: Budget;
+  category: Category;
+  // The budget prorated to one month; spent and remaining are measured against it
+  monthlyAmount: number;
+  spent: number;
+  // Unspent budget carried in when the previous month was closed
+  rolledOver: number;
+  remaining: number;
+  // Whether monthlyAmount was set for this month rather than taken from the budget
+  isMonthAmount: boolean;
+  // The budget's own period around the month, with spending over the whole period
+  periodStart: string;
+  periodEnd: string;
+  periodSpent: number;
+  periodRemaining: number;
+  // Spending in each subcategory when the budget includes them
+  children: BudgetChildSpending[];
+  // Where spending is headed by the end of the month; only for the current month
+  forecast: BudgetForecast | null;
+}
+
+export interface BudgetForecast {
+  projectedSpent: number;
+  // Average daily spending so far, not counting recurring charges
+  dailyAverage: number;
+  // Recurring charges still due this month
+  upcomingRecurring: number;
+  // Positive means on pace to overspend
+  projectedOver: number;
+}
+
+export interface BudgetChildSpending {
+  categoryId: string;
+  categoryName: string;
+  spent: number;
+}
+
+// Several expense categories sharing one monthly budget
+export interface BudgetGroup {
+  id: string;
+  name: string;
+  amount: number;
+  categoryIds: string[];
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface BudgetGroupSummary {
+  group: BudgetGroup;
+  spent: number;
+  remaining: number;
+  // Spending in each of the group's categories
+  categories: BudgetChildSpending[];
+}
+
+export interface BudgetAlert {
+  id: string;
+  budgetId: string;
+  categoryId: string;
+  categoryName: string;
+  month: string;
+  threshold: number;
+  // Spending and the month's budget when the alert was raised
+  spent: number;
+  budgeted: number;
+  createdAt: string;
+  dismissedAt: string | null;
+}
+
+export interface GeneratedBudgets {
+  budgets: Budget[];
+  // Average monthly income over the months looked at
+  monthlyIncome: number;
+  // Income left for savings by a 50/30/20 split
+  savings: number;
+}
+
+export interface BudgetCopyResult {
+  copied: number;
+  // Budgets that already had an amount set for the target month
+  skipped: number;
+}
+
+export interface BudgetMonthAmount {
+  budgetId: string;
+  month: string;
+  amount: number;
+}
+
+export interface IncomeBudgetSummary {
+  budget: Budget;
+  category: Category;
+  expected: number;
+  received: number;
+  outstanding: number;
+}
+
+export interface WatchedBudgetSummary {
+  budget: Budget;
+  category: Category;
+  spent: number;
+  // Average monthly spending over the previous three months
+  average: number;
+  // Spent minus the average; positive means spending more than usual
+  trend: number;
+}
+
+export interface MonthBudgetSummary {
+  expenses: BudgetSummary[];
+  income: IncomeBudgetSummary[];
+  watched: WatchedBudgetSummary[];
+  groups: BudgetGroupSummary[];
+}
+
+export interface MonthlyReport {
+  income: number;
+  expenses: number;
+  net: number;
+  spendingByCategory: SpendingByCategory[];
+}
+
+export interface MonthClose {
+  month: string;
+  netWorthSnapshotId: string;
+  totalAssets: number;
+  totalLiabilities: number;
+  netWorth: number;
+  rolledOverBudgets: number;
+  report: MonthlyReport;
+  archivedReviewCount: number;
+  lockedCount: number;
+  closedAt: string;
+}
+
+export interface AccountStatement {
+  id: string;
+  accountId: string;
+  statementDate: string;
+  balance: number;
+  ledgerBalance: number;
+  isLocked: boolean;
+  createdAt: string;
+  unlockedAt: string | null;
+}
+
+export interface BudgetAllocation {
+  categoryId: string;
+  categoryName: string;
+  budget: number | null;
+  allocated: number;
+  unallocated: number | null;
+  isOverAllocated: boolean;
+  children: BudgetAllocation[];
+}
+
+export interface RollingAverage {
+  windowMonths: number;
+  average: number;
+  difference: number;
+}
+
+export interface CategoryRollingAverage {
+  categoryId: string;
+  currentMonth: string;
+  currentMonthAmount: number;
+  averages: RollingAverage[];
+}
+
+export interface CategoryHistoryMonth {
+  month: string;
+  amount: number;
+  transactionCount: number;
+  budget: number | null;
+  vsBudget: number | null;
+}
+
+export interface CategoryHistory {
+  categoryId: string;
+  months: CategoryHistoryMonth[];
+}
+
+export interface BudgetPerformanceMonth {
+  month: string;
+  // What the budget allowed that month, not counting anything rolled over
+  budgeted: number;
+  rolledOver: number;
+  // Spending, or money received for an income budget
+  actual: number;
+  // Positive means over budget
+  difference: number;
+}
+
+export interface BudgetPerformance {
+  budgetId: string;
+  categoryId: string;
+  months: BudgetPerformanceMonth[];
+  averageBudgeted: number;
+  averageActual: number;
+  monthsOver: number;
 }
 
 export interface BudgetPeriodData {
@@ -129,14 +614,156 @@ export interface Goal {
   currentAmount: number;
   targetDate: string | null;
   linkedAccountId: string | null;
+  // Imported transfers into this account are recorded as contributions
+  contributionAccountId: string | null;
+  // For debt payoff goals: how extra payments are ordered and the total paid each month
+  payoffStrategy: PayoffStrategy | null;
+  monthlyPayment: number | null;
+  // Percentages of the target celebrated when reached
+  milestones: number[];
+  // Higher priority goals come first when splitting a surplus
+  priority: number;
+  allocationWeight: number;
   icon: string | null;
   color: string | null;
   isAchieved: boolean;
   achievedAt: string | null;
+  // Archived goals are hidden from the goal list but keep their history
+  isArchived: boolean;
+  archivedAt: string | null;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export type PayoffStrategy = "snowball" | "avalanche";
+
+export interface GoalDebt {
+  accountId: string;
+  minimumPayment: number;
+}
+
+export interface PayoffDebt {
+  accountId: string;
+  accountName: string;
+  startingBalance: number;
+  interestRate: number;
+  minimumPayment: number;
+  payoffMonth: string | null;
+  interestPaid: number;
+}
+
+export interface DebtPayment {
+  accountId: string;
+  payment: number;
+  interest: number;
+  balance: number;
+}
+
+export interface PayoffMonth {
+  month: string;
+  payments: DebtPayment[];
+  totalBalance: number;
+}
+
+export interface DebtPayoffPlan {
+  goalId: string;
+  strategy: PayoffStrategy;
+  monthlyPayment: number;
+  // In the order the strategy pays them down
+  debts: PayoffDebt[];
+  months: PayoffMonth[];
+  payoffMonth: string | null;
+  totalInterest: number;
+  // Paying only the minimums, for comparison
+  minimumOnlyPayoffMonth: string | null;
+  minimumOnlyInterest: number;
+  interestSaved: number;
+}
+
+export interface GoalMilestone {
+  goalId: string;
+  goalName: string;
+  percent: number;
+  // The saved amount and target when the milestone was reached
+  amount: number;
+  targetAmount: number;
+  reachedAt: string;
+}
+
+export interface SurplusAllocation {
+  goalId: string;
+  goalName: string;
+  amount: number;
+}
+
+export type AllocationStrategy = "priority" | "weighted";
+
+export interface SurplusPlan {
+  strategy: AllocationStrategy;
+  // In priority order, leaving out goals that get nothing
+  allocations: SurplusAllocation[];
+  // Left over once every goal is fully funded
+  unallocated: number;
+}
+
+export interface RoundUpSettings {
+  isEnabled: boolean;
+  // Round-ups from spending on the accounts are contributed to this goal
+  goalId: string | null;
+  accountIds: string[];
+  frequency: GoalContributionSchedule["frequency"];
+  startDate: string;
+  nextDate: string;
+  // Set aside since the last contribution
+  pendingAmount: number;
+  pendingCount: number;
+}
+
+export interface GoalContribution {
+  id: string;
+  goalId: string;
+  amount: number;
+  date: string;
+  // The transfer this contribution was recorded from, if any
+  transactionId: string | null;
+  notes: string | null;
+  createdAt: string;
+}
+
+export interface GoalContributionSchedule {
+  goalId: string;
+  amount: number;
+  frequency: "weekly" | "biweekly" | "monthly" | "quarterly" | "yearly";
+  startDate: string;
+  // When the next contribution is due
+  nextDate: string;
+  // Each contribution transfers the money from this account, when set
+  fromAccountId: string | null;
+  isActive: boolean;
   createdAt: string;
   updatedAt: string;
 }
 
+export interface GoalProjectionMonth {
+  month: string;
+  balance: number;
+  interest: number;
+}
+
+export interface GoalProjection {
+  goalId: string;
+  monthlyContribution: number;
+  apy: number | null;
+  compounding: InterestCompounding | null;
+  projectedDate: string | null;
+  linearProjectedDate: string | null;
+  amountAtTargetDate: number | null;
+  // Monthly contribution needed to reach the target by the target date
+  requiredMonthlyContribution: number | null;
+  interestEarned: number;
+  months: GoalProjectionMonth[];
+}
+
 export interface RecurringTransaction {
   id: string;
   accountId: string;
@@ -152,10 +779,157 @@ export interface RecurringTransaction {
   toleranceAmount: number;
   isAutoDetected: boolean;
   isActive: boolean;
+  dueDay: number | null;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface CalendarBill {
+  source: "recurring" | "category";
+  id: string;
+  name: string;
+  amount: number | null;
+  categoryId: string | null;
+  accountId: string | null;
+  isPaid: boolean;
+}
+
+export interface BillsCalendarDay {
+  date: string;
+  bills: CalendarBill[];
+  total: number;
+}
+
+export interface BillsCalendar {
+  month: string;
+  days: BillsCalendarDay[];
+  total: number;
+}
+
+export interface InsurancePolicy {
+  id: string;
+  name: string;
+  policyType: "auto" | "home" | "renters" | "life" | "health" | "disability" | "umbrella" | "pet" | "other";
+  provider: string | null;
+  policyNumber: string | null;
+  premium: number;
+  premiumFrequency: RecurringTransaction["frequency"];
+  termMonths: number;
+  renewalDate: string | null;
+  recurringTransactionId: string | null;
+  notes: string | null;
+  isActive: boolean;
   createdAt: string;
   updatedAt: string;
 }
 
+export interface PolicyRenewal {
+  policy: InsurancePolicy;
+  renewalDate: string;
+  daysUntil: number;
+  annualPremium: number;
+}
+
+export interface GiftCard {
+  id: string;
+  name: string;
+  merchant: string | null;
+  initialBalance: number;
+  balance: number;
+  expiryDate: string | null;
+  notes: string | null;
+  isActive: boolean;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface GiftCardUsage {
+  id: string;
+  giftCardId: string;
+  transactionId: string;
+  amount: number;
+  createdAt: string;
+}
+
+export interface PlannedPurchase {
+  id: string;
+  name: string;
+  targetAmount: number;
+  desiredDate: string;
+  categoryId: string | null;
+  notes: string | null;
+  isPurchased: boolean;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface PurchaseRecommendation {
+  purchaseId: string;
+  desiredMonth: string;
+  recommendedMonth: string | null;
+  fitsDesiredMonth: boolean;
+}
+
+export interface ForecastMonth {
+  month: string;
+  startingBalance: number;
+  income: number;
+  bills: number;
+  budgetedSpending: number;
+  plannedPurchases: number;
+  endingBalance: number;
+}
+
+export interface CashFlowForecast {
+  asOf: string;
+  startingBalance: number;
+  months: ForecastMonth[];
+}
+
+export interface SafeToSpend {
+  asOf: string;
+  cashBalance: number;
+  lowestBalance: number;
+  lowestBalanceMonth: string;
+  plannedPurchases: number;
+  safeToSpend: number;
+}
+
+export interface RewardRule {
+  id: string;
+  accountId: string;
+  categoryId: string | null;
+  rate: number;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface RewardsPeriod {
+  period: string;
+  spend: number;
+  estimated: number;
+  posted: number;
+}
+
+export interface CategoryRewardEstimate {
+  categoryId: string | null;
+  categoryName: string;
+  rate: number;
+  spend: number;
+  estimated: number;
+}
+
+export interface RewardsSummary {
+  accountId: string;
+  accountName: string;
+  estimatedTotal: number;
+  postedTotal: number;
+  difference: number;
+  periods: RewardsPeriod[];
+  categories: CategoryRewardEstimate[];
+  postedCredits: Transaction[];
+}
+
 export interface DetectedRecurring {
   payee: string;
   normalizedPayee: string;
@@ -186,16 +960,62 @@ export interface Security {
   updatedAt: string;
 }
 
+export type SecurityType = NonNullable<Security["securityType"]>;
+
+// A position in a security, joined with its account and security
 export interface Holding {
   id: string;
   accountId: string;
+  accountName: string;
   securityId: string;
+  symbol: string;
+  name: string | null;
+  securityType: SecurityType | null;
   quantity: number;
+  currentPrice: number | null;
   costBasis: number | null;
+  marketValue: number;
+  gainLoss: number | null;
+  gainLossPercent: number | null;
   acquisitionDate: string | null;
+}
+
+export type CostBasisMethod = "fifo" | "average";
+
+export interface InvestmentSummary {
+  totalValue: number;
+  totalCostBasis: number;
+  realizedGainLoss: number;
+  unrealizedGainLoss: number;
+  totalGainLoss: number;
+  totalGainLossPercent: number;
+  costBasisMethod: CostBasisMethod;
+  holdingsByType: { securityType: string; value: number; percentage: number }[];
+}
+
+export type InvestmentTransactionType = "buy" | "sell" | "dividend" | "split" | "fee";
+
+export interface InvestmentTransaction {
+  id: string;
+  accountId: string;
+  securityId: string | null;
+  symbol: string | null;
+  transactionType: InvestmentTransactionType;
+  date: string;
+  // Units bought or sold, or for a split the new units per old unit
+  quantity: number | null;
+  pricePerUnit: number | null;
+  totalAmount: number;
+  fees: number;
+  quantityChange: number;
+  costBasisChange: number;
+  // A sale's proceeds after fees less the cost basis of the units sold
+  realizedGain: number | null;
+  // The cash transaction posted to the account, if any
+  transactionId: string | null;
+  notes: string | null;
   createdAt: string;
   updatedAt: string;
-  security?: Security;
 }
 
 export interface NetWorthSnapshot {
@@ -207,6 +1027,22 @@ export interface NetWorthSnapshot {
   createdAt: string;
 }
 
+export interface CreditScore {
+  id: string;
+  date: string;
+  bureau: string;
+  score: number;
+  createdAt: string;
+}
+
+export interface TransactionStatusChange {
+  id: string;
+  transactionId: string;
+  fromStatus: TransactionStatus;
+  toStatus: TransactionStatus;
+  changedAt: string;
+}
+
 export interface TransactionFilters {
   accountId: string | null;
   categoryId: string | null;
@@ -217,6 +1053,61 @@ export interface TransactionFilters {
   minAmount: number | null;
   maxAmount: number | null;
   isTransfer: boolean | null;
+  checkNumber: string | null;
+}
+
+export interface TaxPackage {
+  year: number;
+  path: string;
+  files: string[];
+  transactionCount: number;
+  attachmentCount: number;
+}
+
+export interface TransactionAttachment {
+  id: string;
+  transactionId: string;
+  fileName: string;
+  size: number;
+  createdAt: string;
+}
+
+export interface AccountBalanceImpact {
+  accountId: string;
+  accountName: string | null;
+  count: number;
+  balanceChange: number;
+}
+
+export interface TransactionStats {
+  count: number;
+  totalInflow: number;
+  totalOutflow: number;
+  net: number;
+  average: number;
+}
+
+export interface BulkDeleteResult {
+  dryRun: boolean;
+  count: number;
+  accounts: AccountBalanceImpact[];
+}
+
+export interface QuickEntryDraft {
+  amount: number | null;
+  payee: string | null;
+  categoryId: string | null;
+  date: string;
+  tags: string[];
+  memo: string | null;
+}
+
+export interface CheckNumberGap {
+  accountId: string;
+  accountName: string;
+  firstMissing: number;
+  lastMissing: number;
+  missingCount: number;
 }
 
 export interface ImportPreview {
@@ -231,6 +1122,24 @@ export interface TransferCandidate {
   confidence: number;
 }
 
+export interface DuplicateGroup {
+  amount: number;
+  confidence: number;
+  transactions: Transaction[];
+}
+
+export interface HoldSettlement {
+  holdId: string;
+  settlementId: string;
+  holdAmount: number;
+  settledAmount: number;
+}
+
+export interface HoldResolution {
+  settled: HoldSettlement[];
+  expired: string[];
+}
+
 export interface SpendingByCategory {
   categoryId: string;
   categoryName: string;
@@ -239,9 +1148,101 @@ export interface SpendingByCategory {
   color: string | null;
 }
 
+export interface SpendingMatrixColumn {
+  id: string;
+  name: string;
+  total: number;
+}
+
+export interface SpendingMatrixRow {
+  categoryId: string;
+  categoryName: string;
+  color: string | null;
+  amounts: number[];
+  total: number;
+}
+
+export interface SpendingMatrix {
+  groupBy: "account" | "accountType";
+  columns: SpendingMatrixColumn[];
+  rows: SpendingMatrixRow[];
+  total: number;
+}
+
+export interface InflationCategory {
+  categoryId: string;
+  categoryName: string;
+  spendingA: number;
+  spendingB: number;
+  countA: number;
+  countB: number;
+  unit: string | null;
+  changePercent: number;
+  weight: number;
+}
+
+export interface PersonalInflation {
+  periodA: string;
+  periodB: string;
+  categories: InflationCategory[];
+  inflationPercent: number;
+}
+
 export interface CashFlowData {
   period: string;
   income: number;
   expenses: number;
   net: number;
 }
+
+export interface RateComparison {
+  accountId: string;
+  accountName: string;
+  accountType: AccountType;
+  balance: number;
+  accountRate: number;
+  referenceRate: number;
+  annualOpportunity: number;
+}
+
+export interface RateComparisonReport {
+  savingsReferenceRate: number;
+  creditCardReferenceRate: number;
+  accounts: RateComparison[];
+  totalAnnualOpportunity: number;
+}
+
+export interface DashboardSummary {
+  accounts: Account[];
+  // The currency the totals are in
+  baseCurrency: string;
+  // Currencies with no exchange rate to the base currency, counted unconverted
+  missingFxCurrencies: string[];
+  totalAssets: number;
+  totalLiabilities: number;
+  netWorth: number;
+  accountGroups: AccountGroupNetWorth[];
+  monthToDateSpending: SpendingByCategory[];
+  upcomingBills: RecurringTransaction[];
+  recentTransactions: Transaction[];
+}
+
+export interface FeatureUsage {
+  feature: string;
+  uses: number;
+  firstUsedAt: string;
+  lastUsedAt: string;
+}
+
+export interface ParserStats {
+  parser: string;
+  runs: number;
+  failures: number;
+  successRate: number;
+  lastUsedAt: string;
+}
+
+export interface LocalUsageStats {
+  features: FeatureUsage[];
+  parsers: ParserStats[];
+}